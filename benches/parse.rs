@@ -0,0 +1,143 @@
+// This file contains the parsing benchmarks, run with `cargo bench`. A large specification is generated in memory,
+// with every operation referring to the same shared schemas, and parsed a few times to report the time per parse.
+// Its references are also resolved with and without the resolver's cache, to show what the cache saves.
+
+use serde_json::{json, Map, Value};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use swagger_test_generator::parser::{parse_swagger_string, RefResolver};
+
+/// Number of CRUD resources in the generated specification, each with a collection and an item path
+const RESOURCES: usize = 1500;
+
+/// Number of timed parses of each specification
+const ITERATIONS: u32 = 5;
+
+fn main() {
+    let document = large_spec(RESOURCES);
+    let content = serde_json::to_string(&document).expect("the generated specification serializes");
+    println!(
+        "parse {} paths ({:.1} MB): {:?} per parse",
+        RESOURCES * 2,
+        content.len() as f64 / 1_000_000.0,
+        time(|| black_box(parse_swagger_string(&content).expect("the generated specification parses")))
+    );
+
+    println!(
+        "resolve {} paths: {:?} cached, {:?} uncached",
+        RESOURCES * 2,
+        time(|| black_box(RefResolver::new(&document).resolve(&document).expect("the generated specification resolves"))),
+        time(|| black_box(RefResolver::new(&document).uncached().resolve(&document).expect("the generated specification resolves")))
+    );
+
+    let single = serde_json::to_string(&large_spec(1)).expect("the generated specification serializes");
+    println!(
+        "parse 2 paths: {:?} per parse",
+        time(|| black_box(parse_swagger_string(&single).expect("the generated specification parses")))
+    );
+}
+
+/// Average time of running `f`, after one untimed run
+fn time<T>(f: impl Fn() -> T) -> Duration {
+    f();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+/// An OpenAPI 3 specification with `resources` resources, whose schemas all refer to a few shared ones
+fn large_spec(resources: usize) -> Value {
+    let mut paths = Map::new();
+    let mut schemas = Map::new();
+
+    schemas.insert("Audit".to_string(), json!({
+        "type": "object",
+        "properties": {
+            "createdAt": { "type": "string", "format": "date-time" },
+            "createdBy": { "$ref": "#/components/schemas/Owner" },
+            "tags": { "type": "array", "items": { "type": "string" } }
+        }
+    }));
+    schemas.insert("Owner".to_string(), json!({
+        "type": "object",
+        "required": ["id"],
+        "properties": {
+            "id": { "type": "string", "format": "uuid" },
+            "name": { "type": "string", "minLength": 1, "maxLength": 100 },
+            "address": { "$ref": "#/components/schemas/Address" }
+        }
+    }));
+    schemas.insert("Address".to_string(), json!({
+        "type": "object",
+        "properties": {
+            "street": { "type": "string" },
+            "city": { "type": "string" },
+            "country": { "type": "string", "enum": ["NL", "DE", "US"] }
+        }
+    }));
+    schemas.insert("Error".to_string(), json!({
+        "type": "object",
+        "properties": { "code": { "type": "integer" }, "message": { "type": "string" } }
+    }));
+
+    for index in 0..resources {
+        let name = format!("Resource{}", index);
+        let reference = json!({ "$ref": format!("#/components/schemas/{}", name) });
+        schemas.insert(name.clone(), json!({
+            "type": "object",
+            "required": ["id", "name"],
+            "properties": {
+                "id": { "type": "integer", "readOnly": true },
+                "name": { "type": "string", "example": "example" },
+                "owner": { "$ref": "#/components/schemas/Owner" },
+                "audit": { "$ref": "#/components/schemas/Audit" }
+            }
+        }));
+
+        let error = json!({ "description": "Error", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } } });
+        let body = |description: &str, schema: Value| json!({ "description": description, "content": { "application/json": { "schema": schema } } });
+        let id = json!({ "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } });
+
+        paths.insert(format!("/resources{}", index), json!({
+            "get": {
+                "operationId": format!("list{}", name),
+                "parameters": [
+                    { "name": "limit", "in": "query", "schema": { "type": "integer", "minimum": 1, "maximum": 100 } },
+                    { "name": "cursor", "in": "query", "schema": { "type": "string" } }
+                ],
+                "responses": { "200": body("List", json!({ "type": "array", "items": reference })), "default": error }
+            },
+            "post": {
+                "operationId": format!("create{}", name),
+                "requestBody": { "required": true, "content": { "application/json": { "schema": reference } } },
+                "responses": { "201": body("Created", reference.clone()), "default": error }
+            }
+        }));
+        paths.insert(format!("/resources{}/{{id}}", index), json!({
+            "parameters": [id],
+            "get": {
+                "operationId": format!("get{}", name),
+                "responses": { "200": body("Found", reference.clone()), "404": error }
+            },
+            "put": {
+                "operationId": format!("update{}", name),
+                "requestBody": { "required": true, "content": { "application/json": { "schema": reference } } },
+                "responses": { "200": body("Updated", reference.clone()), "default": error }
+            },
+            "delete": {
+                "operationId": format!("delete{}", name),
+                "responses": { "204": { "description": "Deleted" }, "default": error }
+            }
+        }));
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": { "title": "Benchmark API", "version": "1.0.0" },
+        "servers": [{ "url": "https://api.example.com" }],
+        "paths": paths,
+        "components": { "schemas": schemas }
+    })
+}
@@ -69,7 +69,7 @@ fn main() -> io::Result<()> {
     }
     
     // Extract port from base_url for the API server
-    let port = base_url.split(':').last().unwrap_or("8000");
+    let port = base_url.split(':').next_back().unwrap_or("8000");
     let port = port.split('/').next().unwrap_or("8000");
     
     let api_server = Command::new("cargo")
@@ -148,7 +148,7 @@ version = "0.1.0"
 edition = "2021"
 
 [dependencies]
-reqwest = {{ version = "0.11", features = ["json", "blocking"] }}
+reqwest = {{ version = "0.11", features = ["json", "blocking", "cookies", "multipart"] }}
 tokio = {{ version = "1", features = ["full"] }}
 serde = {{ version = "1.0", features = ["derive"] }}
 serde_json = "1.0"
@@ -2,4 +2,4 @@
 
 pub mod args;
 
-pub use args::{Args, TestFramework};
\ No newline at end of file
+pub use args::{Args, BundleArgs, Command, DiffArgs, TestFramework, ValidateArgs};
\ No newline at end of file
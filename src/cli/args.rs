@@ -1,24 +1,37 @@
-use clap::{Parser, ArgEnum};
-use std::path::PathBuf;
+use clap::{Parser, Subcommand, ArgEnum};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::parser::SpecFormat;
+use crate::generator::auth::AuthSource;
+use crate::generator::datasets::DataSets;
+use crate::generator::AssertLevel;
+use crate::generator::layout::OutputLayout;
+use crate::generator::negative::StatusCoverage;
 
 #[derive(Debug, Parser)]
 #[clap(
     name = "swagger-test-generator",
     about = "Generate tests from OpenAPI/Swagger specifications",
-    version
+    version,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
 )]
 pub struct Args {
-    /// Path to the Swagger/OpenAPI specification file
-    #[clap(short, long, value_name = "FILE")]
-    pub input: PathBuf,
+    /// Run another command instead of generating tests
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+
+    /// Path or HTTP(S) URL of the Swagger/OpenAPI specification
+    #[clap(short, long, value_name = "FILE|URL", required = true)]
+    pub input: Option<String>,
 
     /// Output directory for generated tests
-    #[clap(short, long, value_name = "DIRECTORY")]
-    pub output_dir: PathBuf,
+    #[clap(short, long, value_name = "DIRECTORY", required = true)]
+    pub output_dir: Option<PathBuf>,
 
     /// Testing framework to generate tests for
-    #[clap(short, long, value_enum)]
-    pub framework: TestFramework,
+    #[clap(short, long, value_enum, required = true)]
+    pub framework: Option<TestFramework>,
 
     /// Base URL for the API
     #[clap(long, value_name = "URL", default_value = "http://localhost:3000")]
@@ -27,6 +40,377 @@ pub struct Args {
     /// Generate detailed test cases
     #[clap(long)]
     pub verbose: bool,
+
+    /// Timeout in seconds when fetching the specification from a URL
+    #[clap(long, value_name = "SECS", default_value = "30")]
+    pub fetch_timeout: u64,
+
+    /// Header sent when fetching the specification from a URL, e.g. "Authorization: Bearer TOKEN"
+    #[clap(long, value_name = "HEADER", value_parser = parse_header)]
+    pub input_header: Vec<(String, String)>,
+
+    /// Media type to send request bodies as when an operation documents several, e.g. "application/xml"
+    #[clap(long, value_name = "MEDIA_TYPE")]
+    pub content_type: Option<String>,
+
+    /// Override a variable of the server URL, e.g. "region=eu" (reqwest and ureq)
+    #[clap(long, value_name = "NAME=VALUE", value_parser = parse_key_value)]
+    pub server_var: Vec<(String, String)>,
+
+    /// Take the base path from the server at this position in the specification's `servers`, starting at 0 (reqwest
+    /// and ureq)
+    #[clap(long, value_name = "INDEX", conflicts_with = "server-name")]
+    pub server_index: Option<usize>,
+
+    /// Take the base path from the server with this description, e.g. "Staging" (reqwest and ureq)
+    #[clap(long, value_name = "DESCRIPTION")]
+    pub server_name: Option<String>,
+
+    /// How tests are split into files (or Postman folders): "single" for one file of all operations, "per-tag" for one
+    /// per operation tag, or "per-path" for one per path. Each framework has its own default
+    #[clap(long, value_name = "LAYOUT", value_parser = parse_layout)]
+    pub layout: Option<OutputLayout>,
+
+    /// Write one test file (or Postman folder) per operation tag, same as --layout per-tag
+    #[clap(long, conflicts_with = "layout")]
+    pub group_by_tag: bool,
+
+    /// Leave out deprecated operations, instead of generating their tests marked as skipped
+    #[clap(long)]
+    pub skip_deprecated: bool,
+
+    /// Only generate tests for operations with one of these tags, e.g. "users,orders"
+    #[clap(long, value_name = "TAGS", value_delimiter = ',')]
+    pub include_tags: Vec<String>,
+
+    /// Leave out operations on paths matching one of these glob patterns, where "*" stands for a path segment and
+    /// "**" for any number of them, e.g. "/admin/**"
+    #[clap(long, value_name = "GLOBS", value_delimiter = ',')]
+    pub exclude_paths: Vec<String>,
+
+    /// Only generate tests for operations with one of these HTTP methods, e.g. "GET,HEAD" for a read-only suite
+    #[clap(long, value_name = "METHODS", value_delimiter = ',', value_parser = parse_method)]
+    pub methods: Vec<String>,
+
+    /// Also generate tests sending invalid requests, e.g. without a required body property, with a value of the wrong type
+    /// or outside an enum, and expecting a 4xx status (reqwest, ureq, pytest, Jest and Postman)
+    #[clap(long)]
+    pub negative_tests: bool,
+
+    /// Also generate tests sending values at the minimum, maximum and length limits of parameters and body properties,
+    /// expecting a 2xx status, and just outside of them, expecting a 4xx status
+    #[clap(long)]
+    pub boundary_tests: bool,
+
+    /// Also generate tests calling operations with security requirements without credentials, expecting their
+    /// documented 401 or 403 status
+    #[clap(long)]
+    pub unauthorized_tests: bool,
+
+    /// Also generate tests asking GET operations with pagination query parameters (page, offset or cursor, and limit,
+    /// or those an `x-pagination` extension names) for two pages, which have to differ and respect the limit
+    #[clap(long)]
+    pub pagination_tests: bool,
+
+    /// Which documented statuses get a test: "success-only" for the first 2xx, or "all" to also send requests with a
+    /// body of the wrong type, without credentials and for a nonexistent item to operations documenting a 400 or 422,
+    /// a 401 or 403 and a 404
+    #[clap(long, value_name = "COVERAGE", default_value = "success-only", value_parser = parse_status_coverage)]
+    pub status_coverage: StatusCoverage,
+
+    /// How much of the response tests check: "status" for the status only, "schema" to also check the documented
+    /// headers and validate the body against its schema, or "exact" to also compare the body to the documented example
+    /// (reqwest, ureq, pytest, Jest and Postman)
+    #[clap(long, value_name = "LEVEL", default_value = "schema", value_parser = parse_assert_level)]
+    pub assert_level: AssertLevel,
+
+    /// Send a request again up to N times after a connection error or a 408, 429 or 5xx status, for flaky test
+    /// environments (reqwest, ureq, pytest and Jest)
+    #[clap(long, value_name = "N", default_value = "0")]
+    pub retries: u32,
+
+    /// How long to wait before retrying a request, twice as long before each next retry, in milliseconds
+    #[clap(long, value_name = "MS", requires = "retries")]
+    pub retry_backoff_ms: Option<u64>,
+
+    /// Fail requests of generated tests the API doesn't answer within this many seconds (reqwest, ureq, pytest,
+    /// Jest and Postman's Newman script)
+    #[clap(long, value_name = "SECS", value_parser = parse_timeout)]
+    pub timeout_secs: Option<u64>,
+
+    /// Header every request of the generated tests is sent with, e.g. "X-Tenant-Id: 42", replacing a header parameter
+    /// of the same name (repeatable)
+    #[clap(long, value_name = "HEADER", value_parser = parse_header)]
+    pub header: Vec<(String, String)>,
+
+    /// Environment generated tests can run against, e.g. "staging=https://staging.example.com", getting a .env file
+    /// tests read the base URL and credentials from, picked with TEST_ENV (the first one by default) (repeatable;
+    /// reqwest, ureq, pytest, Jest and Postman)
+    #[clap(long = "env", value_name = "NAME=BASE_URL", value_parser = parse_environment)]
+    pub environments: Vec<(String, String)>,
+
+    /// JSON or CSV file of requests to send to operations, by operation ID, each with parameter values, a body and the
+    /// status expected, which data-driven tests are parametrized over (reqwest, ureq, pytest and Jest)
+    #[clap(long, value_name = "PATH", value_parser = parse_data_file)]
+    pub data_file: Option<DataSets>,
+
+    /// Where generated tests read credentials from, e.g. "bearer:API_TOKEN" to send the token in the API_TOKEN
+    /// environment variable to operations requiring a bearer, OAuth2 or OpenID Connect scheme, or "apikey:API_KEY"
+    /// to send the key in API_KEY to those requiring an API key, "basic:API_USERNAME:API_PASSWORD" for HTTP basic
+    /// authentication, or "oauth2:CLIENT_ID:CLIENT_SECRET" to fetch access tokens with OAuth2's client credentials flow
+    /// (repeatable; reqwest, ureq, pytest, Jest and Postman)
+    #[clap(long, value_name = "KIND:ENV_VAR", value_parser = parse_auth)]
+    pub auth: Vec<AuthSource>,
+
+    /// Pick the values of request body properties without an example with this seed, instead of the same first
+    /// candidate for all of them. The same seed gives the same values on every run
+    #[clap(long, value_name = "N")]
+    pub seed: Option<u64>,
+
+    /// Generate property-based tests drawing request bodies and query parameters from their schemas, instead of
+    /// sending single examples (reqwest, ureq, pytest and Jest)
+    #[clap(long)]
+    pub fuzz: bool,
+
+    /// Fail the test of an operation when the API takes longer than this many milliseconds to answer its request
+    /// (reqwest, ureq, pytest, Jest and Postman)
+    #[clap(long, value_name = "MS", value_parser = parse_max_response_time)]
+    pub max_response_time_ms: Option<u64>,
+
+    /// Also send the optional query parameters of operations, instead of the required ones only
+    #[clap(long)]
+    pub optional_query_params: bool,
+
+    /// Work around references that don't resolve instead of failing, reporting them as warnings
+    #[clap(long)]
+    pub lenient: bool,
+
+    /// Number of threads generating tests (default: one per CPU)
+    #[clap(short, long, value_name = "N", value_parser = parse_jobs)]
+    pub jobs: Option<usize>,
+    
+    /// Number of virtual users load tests (k6, Locust, JMeter) simulate
+    #[clap(long, value_name = "N", value_parser = parse_vus)]
+    pub vus: Option<u32>,
+    
+    /// How long load tests (k6, Locust, JMeter) run, e.g. "30s", "5m" or "1h"
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub duration: Option<Duration>,
+}
+
+/// Commands other than generating tests
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Check a specification for structural problems, exiting with an error if any are found
+    Validate(ValidateArgs),
+
+    /// Inline the references into other files of a specification, writing a single self-contained document
+    Bundle(BundleArgs),
+
+    /// Compare two versions of a specification, exiting with an error if the new one has breaking changes
+    Diff(DiffArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ValidateArgs {
+    /// Path or HTTP(S) URL of the Swagger/OpenAPI specification
+    #[clap(short, long, value_name = "FILE|URL")]
+    pub input: String,
+
+    /// Timeout in seconds when fetching the specification from a URL
+    #[clap(long, value_name = "SECS", default_value = "30")]
+    pub fetch_timeout: u64,
+
+    /// Header sent when fetching the specification from a URL, e.g. "Authorization: Bearer TOKEN"
+    #[clap(long, value_name = "HEADER", value_parser = parse_header)]
+    pub input_header: Vec<(String, String)>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct BundleArgs {
+    /// Path of the Swagger/OpenAPI specification
+    #[clap(short, long, value_name = "FILE")]
+    pub input: PathBuf,
+
+    /// File to write the bundled specification to, instead of standard output
+    #[clap(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Format to write, "json" or "yaml" (defaults to the output file's extension, then the input's format)
+    #[clap(long, value_name = "FORMAT", value_parser = parse_format)]
+    pub format: Option<SpecFormat>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct DiffArgs {
+    /// Path of the old version of the specification
+    #[clap(value_name = "OLD")]
+    pub old: PathBuf,
+
+    /// Path of the new version of the specification
+    #[clap(value_name = "NEW")]
+    pub new: PathBuf,
+
+    /// Only report the changes that can break existing clients
+    #[clap(long)]
+    pub breaking_only: bool,
+}
+
+/// Parse a "Name: value" header argument
+pub fn parse_header(header: &str) -> Result<(String, String), String> {
+    match header.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.trim().to_string())),
+        _ => Err(format!("expected \"Name: value\", got \"{}\"", header)),
+    }
+}
+
+/// Read the data file at a path
+pub fn parse_data_file(path: &str) -> Result<DataSets, String> {
+    DataSets::from_file(Path::new(path)).map_err(|error| format!("failed to read the data file {}: {}", path, error))
+}
+
+/// Parse a "json" or "yaml" format argument
+pub fn parse_format(format: &str) -> Result<SpecFormat, String> {
+    match format.to_lowercase().as_str() {
+        "json" => Ok(SpecFormat::Json),
+        "yaml" | "yml" => Ok(SpecFormat::Yaml),
+        _ => Err(format!("expected \"json\" or \"yaml\", got \"{}\"", format)),
+    }
+}
+
+/// Parse a "success-only" or "all" status coverage argument
+pub fn parse_status_coverage(coverage: &str) -> Result<StatusCoverage, String> {
+    match coverage.to_lowercase().as_str() {
+        "success-only" => Ok(StatusCoverage::SuccessOnly),
+        "all" => Ok(StatusCoverage::All),
+        _ => Err(format!("expected \"success-only\" or \"all\", got \"{}\"", coverage)),
+    }
+}
+
+/// Parse a "single", "per-tag" or "per-path" output layout argument
+pub fn parse_layout(layout: &str) -> Result<OutputLayout, String> {
+    match layout.to_lowercase().as_str() {
+        "single" => Ok(OutputLayout::Single),
+        "per-tag" => Ok(OutputLayout::PerTag),
+        "per-path" => Ok(OutputLayout::PerPath),
+        _ => Err(format!("expected \"single\", \"per-tag\" or \"per-path\", got \"{}\"", layout)),
+    }
+}
+
+/// Parse a "status", "schema" or "exact" assertion level argument
+pub fn parse_assert_level(level: &str) -> Result<AssertLevel, String> {
+    match level.to_lowercase().as_str() {
+        "status" => Ok(AssertLevel::Status),
+        "schema" => Ok(AssertLevel::Schema),
+        "exact" => Ok(AssertLevel::Exact),
+        _ => Err(format!("expected \"status\", \"schema\" or \"exact\", got \"{}\"", level)),
+    }
+}
+
+/// Parse a thread count, which has to be at least 1
+pub fn parse_jobs(jobs: &str) -> Result<usize, String> {
+    match jobs.parse() {
+        Ok(jobs) if jobs > 0 => Ok(jobs),
+        _ => Err(format!("expected a number of threads of at least 1, got \"{}\"", jobs)),
+    }
+}
+
+/// Parse a number of virtual users, which has to be at least 1
+pub fn parse_vus(vus: &str) -> Result<u32, String> {
+    match vus.parse() {
+        Ok(vus) if vus > 0 => Ok(vus),
+        _ => Err(format!("expected a number of virtual users of at least 1, got \"{}\"", vus)),
+    }
+}
+
+/// Parse an HTTP method an operation can have, in uppercase
+pub fn parse_method(method: &str) -> Result<String, String> {
+    let method = method.to_uppercase();
+    match method.as_str() {
+        "GET" | "PUT" | "POST" | "DELETE" | "OPTIONS" | "HEAD" | "PATCH" | "TRACE" => Ok(method),
+        _ => Err(format!("expected an HTTP method such as GET or POST, got \"{}\"", method)),
+    }
+}
+
+/// Parse a response time limit in milliseconds, which has to be at least 1
+pub fn parse_max_response_time(max: &str) -> Result<u64, String> {
+    match max.parse() {
+        Ok(max) if max > 0 => Ok(max),
+        _ => Err(format!("expected a response time in milliseconds of at least 1, got \"{}\"", max)),
+    }
+}
+
+/// Parse a request timeout in seconds, which has to be at least 1
+pub fn parse_timeout(timeout: &str) -> Result<u64, String> {
+    match timeout.parse() {
+        Ok(timeout) if timeout > 0 => Ok(timeout),
+        _ => Err(format!("expected a timeout in seconds of at least 1, got \"{}\"", timeout)),
+    }
+}
+
+/// Parse a duration of whole seconds, minutes or hours such as "30s", "5m" or "1h" (plain numbers are seconds)
+pub fn parse_duration(duration: &str) -> Result<Duration, String> {
+    let (number, unit) = match duration.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => duration.split_at(index),
+        None => (duration, "s"),
+    };
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        _ => return Err(format!("expected a duration such as \"30s\", \"5m\" or \"1h\", got \"{}\"", duration)),
+    };
+    match number.parse::<u64>() {
+        Ok(number) if number > 0 => Ok(Duration::from_secs(number * seconds)),
+        _ => Err(format!("expected a duration such as \"30s\", \"5m\" or \"1h\", got \"{}\"", duration)),
+    }
+}
+
+/// Parse a "name=value" argument
+pub fn parse_key_value(pair: &str) -> Result<(String, String), String> {
+    match pair.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.trim().to_string())),
+        _ => Err(format!("expected \"name=value\", got \"{}\"", pair)),
+    }
+}
+
+/// Parse a "name=base URL" environment argument, whose name goes in file names
+pub fn parse_environment(environment: &str) -> Result<(String, String), String> {
+    match parse_key_value(environment) {
+        Ok((name, base_url)) if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') => Ok((name, base_url)),
+        _ => Err(format!("expected \"name=base URL\" with a name of letters, digits, '-' and '_', got \"{}\"", environment)),
+    }
+}
+
+/// Parse a "kind:ENV_VAR" credentials argument ("basic:USERNAME_VAR:PASSWORD_VAR" for basic authentication and
+/// "oauth2:CLIENT_ID_VAR:CLIENT_SECRET_VAR" for an OAuth2 client), the names of variables optionally written with a
+/// leading "$"
+pub fn parse_auth(auth: &str) -> Result<AuthSource, String> {
+    let expected = || {
+        format!(
+            "expected \"bearer:ENV_VAR\", \"apikey:ENV_VAR\", \"basic:USERNAME_VAR:PASSWORD_VAR\" or \"oauth2:CLIENT_ID_VAR:CLIENT_SECRET_VAR\", got \"{}\"",
+            auth
+        )
+    };
+    let (kind, vars) = auth.split_once(':').ok_or_else(expected)?;
+    let vars = vars
+        .split(':')
+        .map(|var| {
+            let var = var.strip_prefix('$').unwrap_or(var);
+            let valid = var.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && var.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            valid.then(|| var.to_string()).ok_or_else(expected)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match (kind.to_lowercase().as_str(), vars.as_slice()) {
+        ("bearer", [var]) => Ok(AuthSource::Bearer(var.clone())),
+        ("apikey", [var]) => Ok(AuthSource::ApiKey(var.clone())),
+        ("basic", [username_var, password_var]) => Ok(AuthSource::Basic(username_var.clone(), password_var.clone())),
+        ("oauth2", [client_id_var, client_secret_var]) => {
+            Ok(AuthSource::ClientCredentials(client_id_var.clone(), client_secret_var.clone()))
+        },
+        _ => Err(expected()),
+    }
 }
 
 #[derive(Debug, Copy, Clone, ArgEnum)]
@@ -39,4 +423,54 @@ pub enum TestFramework {
     Jest,
     /// Generate tests for Postman collections
     Postman,
+    /// Generate tests for Go's net/http & testing packages
+    Go,
+    /// Generate tests for Java's JUnit 5 & RestAssured
+    RestAssured,
+    /// Generate tests for C#'s xUnit & HttpClient
+    Xunit,
+    /// Generate tests for PHP's PHPUnit & Guzzle
+    Phpunit,
+    /// Generate Karate feature files
+    Karate,
+    /// Generate k6 load-test scripts
+    K6,
+    /// Generate a Locust load-test locustfile
+    Locust,
+    /// Generate a JMeter test plan
+    Jmeter,
+    /// Generate Hurl files
+    Hurl,
+    /// Generate VS Code REST Client .http files
+    HttpFile,
+    /// Generate a Bash curl smoke-test script
+    Curl,
+    /// Generate PowerShell Pester tests
+    Pester,
+    /// Generate TypeScript Playwright API tests
+    Playwright,
+    /// Generate JavaScript Vitest tests
+    Vitest,
+    /// Generate Tavern YAML tests
+    Tavern,
+    /// Generate Gherkin features with Python behave step definitions
+    Gherkin,
+    /// Generate WireMock stub mappings mocking the API
+    Wiremock,
+    /// Generate Mock Service Worker handlers mocking the API
+    Msw,
+    /// Generate an Insomnia export
+    Insomnia,
+    /// Generate a Bruno collection
+    Bruno,
+    /// Generate async Python pytest tests using httpx and pytest-asyncio
+    PytestAsync,
+    /// Generate synchronous Rust tests using ureq
+    Ureq,
+    /// Generate a Schemathesis command and hooks fuzzing the API
+    Schemathesis,
+    /// Generate JavaScript Mocha and Chai tests
+    Mocha,
+    /// Generate Deno tests using the built-in `fetch`
+    Deno,
 }
\ No newline at end of file
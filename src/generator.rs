@@ -1,11 +1,50 @@
 pub mod test_framework;
 pub mod api_endpoints;
 pub mod swagger_doc;
+pub mod body;
+pub mod data;
+pub mod layout;
+pub mod links;
+pub mod lifecycle;
+pub mod go;
+pub mod rest_assured;
+pub mod xunit;
+pub mod phpunit;
+pub mod karate;
+pub mod k6;
+pub mod locust;
+pub mod jmeter;
+pub mod hurl;
+pub mod http_file;
+pub mod curl;
+pub mod pester;
+pub mod playwright;
+pub mod vitest;
+pub mod tavern;
+pub mod gherkin;
+pub mod wiremock;
+pub mod msw;
+pub mod insomnia;
+pub mod bruno;
+pub mod schemathesis;
+pub mod mocha;
+pub mod deno;
+pub mod negative;
+pub mod fuzz;
+pub mod auth;
+pub mod environments;
+pub mod datasets;
+pub mod pagination;
 
 pub use test_framework::{
     TestGenerator,
     create_generator,
+    create_generator_with_options,
     GeneratorError,
+    GeneratorOptions,
+    AssertLevel,
 };
 
+pub use auth::{AuthConfig, AuthSource};
+
 pub use api_endpoints::generate_axum_api;
\ No newline at end of file
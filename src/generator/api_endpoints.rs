@@ -1,10 +1,13 @@
 // This file contains the logic for generating custom API endpoints based on the Swagger document.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 use std::fs::{self, File};
 use std::io::Write;
-use crate::parser::{SwaggerSpec, ApiPath, ApiOperation};
+use crate::parser::{SwaggerSpec, ApiPath, ApiOperation, ApiCallback, PrimitiveType, Schema};
 use crate::utils::sanitize_path_for_filename;
+use crate::utils::helpers::camel_to_snake;
+use super::body::{body_example, to_json_literal};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -32,11 +35,17 @@ pub fn generate_axum_api(spec: &SwaggerSpec, output_dir: &Path) -> Result<()> {
     // Generate handlers module with route handlers
     generate_handlers_module(spec, output_dir)?;
     
+    // Generate callbacks module, letting clients trigger the callbacks the API documents
+    let has_callbacks = spec.paths.iter().flat_map(|p| &p.operations).any(|op| !op.callbacks.is_empty());
+    if has_callbacks {
+        generate_callbacks_module(spec, output_dir)?;
+    }
+    
     // Generate main.rs entrypoint
-    generate_main_file(spec, output_dir)?;
+    generate_main_file(spec, output_dir, has_callbacks)?;
     
     // Generate Cargo.toml file
-    generate_cargo_toml(output_dir)?;
+    generate_cargo_toml(spec, output_dir, has_callbacks)?;
     
     Ok(())
 }
@@ -55,14 +64,19 @@ fn generate_models_module(spec: &SwaggerSpec, output_dir: &Path) -> Result<()> {
     
     if let Some(defs) = definitions {
         if let Some(def_obj) = defs.as_object() {
+            let models: BTreeMap<String, Schema> = def_obj
+                .iter()
+                .map(|(name, schema)| (name.clone(), Schema::from_value(schema)))
+                .collect();
+            
             // Write model structs
-            for (name, schema) in def_obj {
+            for (name, schema) in &models {
                 let model_name = name.clone();
                 let model_path = models_dir.join(format!("{}.rs", model_name.to_lowercase()));
                 let mut model_file = File::create(&model_path)?;
                 
                 // Generate model struct from schema
-                let model_code = generate_model_from_schema(&model_name, schema);
+                let model_code = generate_model_from_schema(&model_name, schema, &models);
                 writeln!(model_file, "{}", model_code)?;
                 
                 // Add model to mod.rs
@@ -111,41 +125,80 @@ pub struct UserUpdate {{
     Ok(())
 }
 
-fn generate_model_from_schema(name: &str, schema: &serde_json::Value) -> String {
+fn generate_model_from_schema(name: &str, schema: &Schema, models: &BTreeMap<String, Schema>) -> String {
     // Simple model generator - can be expanded for more complex schemas
     let mut model = format!(r#"use serde::{{Deserialize, Serialize}};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct {} {{
 "#, name);
-    
-    if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
-        for (prop_name, prop_schema) in props {
-            let field_type = match prop_schema.get("type").and_then(|t| t.as_str()) {
-                Some("string") => {
-                    if let Some("date-time") = prop_schema.get("format").and_then(|f| f.as_str()) {
-                        "DateTime<Utc>".to_string()
-                    } else {
-                        "String".to_string()
-                    }
+    let mut imports = BTreeSet::new();
+    
+    if let Schema::Object(object) = schema {
+        for (prop_name, prop_schema) in &object.properties {
+            let model_ref = |schema: &Schema| schema.ref_name().filter(|target| models.contains_key(*target)).map(String::from);
+            let field_type = match prop_schema {
+                Schema::Primitive(primitive) => match primitive.kind {
+                    PrimitiveType::String if primitive.info.format.as_deref() == Some("date-time") => "DateTime<Utc>".to_string(),
+                    PrimitiveType::String => "String".to_string(),
+                    PrimitiveType::Integer => "i64".to_string(),
+                    PrimitiveType::Number => "f64".to_string(),
+                    PrimitiveType::Boolean => "bool".to_string(),
+                },
+                // A model that refers back to this one has to be boxed, and optional so that values can end
+                Schema::Ref(_) => match model_ref(prop_schema) {
+                    Some(target) if refers_to(&target, name, models, &mut BTreeSet::new()) => {
+                        imports.insert(target.clone());
+                        format!("Option<Box<{}>>", target)
+                    },
+                    Some(target) => {
+                        imports.insert(target.clone());
+                        target
+                    },
+                    None => "serde_json::Value".to_string(),
                 },
-                Some("integer") => "i64".to_string(),
-                Some("number") => "f64".to_string(),
-                Some("boolean") => "bool".to_string(),
-                Some("array") => {
+                Schema::Array(array) => match array.items.as_deref().and_then(model_ref) {
+                    Some(target) => {
+                        imports.insert(target.clone());
+                        format!("Vec<{}>", target)
+                    },
                     // Simple array handling
-                    "Vec<String>".to_string()
+                    None => "Vec<String>".to_string(),
                 },
-                Some("object") => "serde_json::Value".to_string(),
+                Schema::Object(_) => "serde_json::Value".to_string(),
                 _ => "String".to_string(),
             };
             
+            let info = prop_schema.info().cloned().unwrap_or_default();
+            
+            // Nullable properties may be sent as `null`
+            let field_type = if info.nullable {
+                format!("Option<{}>", field_type)
+            } else {
+                field_type
+            };
+            
+            // Read-only properties are set by the server and never accepted from clients,
+            // write-only properties are accepted from clients but never returned
+            if info.read_only {
+                model.push_str("    #[serde(skip_deserializing)]\n");
+            }
+            if info.write_only {
+                model.push_str("    #[serde(skip_serializing)]\n");
+            }
+            
             model.push_str(&format!("    pub {}: {},\n", prop_name, field_type));
         }
     }
     
     model.push_str("}\n");
     
+    // Other models are re-exported by models/mod.rs
+    imports.remove(name);
+    for import in imports.iter().rev() {
+        model = format!("use super::{};\n{}", import, model);
+    }
+    
     if model.contains("DateTime<Utc>") {
         model = format!("use chrono::{{DateTime, Utc}};\n{}", model);
     }
@@ -153,6 +206,39 @@ pub struct {} {{
     model
 }
 
+/// Whether the model `from` refers to the model `to`, directly or through the models it refers to
+fn refers_to(from: &str, to: &str, models: &BTreeMap<String, Schema>, visited: &mut BTreeSet<String>) -> bool {
+    if !visited.insert(from.to_string()) {
+        return false;
+    }
+    
+    let mut references = Vec::new();
+    if let Some(schema) = models.get(from) {
+        collect_references(schema, &mut references);
+    }
+    references.iter().any(|target| target == to || refers_to(target, to, models, visited))
+}
+
+/// The names of the schemas referenced anywhere within a schema
+fn collect_references(schema: &Schema, references: &mut Vec<String>) {
+    match schema {
+        Schema::Ref(_) => references.extend(schema.ref_name().map(String::from)),
+        Schema::Object(object) => {
+            object.properties.values().for_each(|prop| collect_references(prop, references));
+            if let Some(additional) = object.additional_properties.as_deref() {
+                collect_references(additional, references);
+            }
+        },
+        Schema::Array(array) => {
+            if let Some(items) = array.items.as_deref() {
+                collect_references(items, references);
+            }
+        },
+        Schema::Composite(composite) => composite.members.iter().for_each(|member| collect_references(member, references)),
+        Schema::Primitive(_) | Schema::Any(_) => {},
+    }
+}
+
 fn generate_routes_module(spec: &SwaggerSpec, output_dir: &Path) -> Result<()> {
     let routes_dir = output_dir.join("src").join("routes");
     fs::create_dir_all(&routes_dir)?;
@@ -186,7 +272,7 @@ use tower_http::cors::CorsLayer;
     
     // Generate each route module
     for (group, paths) in &route_groups {
-        let group_name = sanitize_path_for_filename(&group);
+        let group_name = sanitize_path_for_filename(group);
         let group_path = routes_dir.join(format!("{}.rs", group_name));
         let mut group_file = File::create(&group_path)?;
         
@@ -289,7 +375,6 @@ use std::collections::HashMap;
         // Generate handlers for paths in this group
         for path in paths {
             for op in &path.operations {
-                let handler_name = op.operation_id.to_lowercase();
                 let method = op.method.to_uppercase();
                 
                 // Generate handler based on HTTP method and path
@@ -507,17 +592,122 @@ pub async fn {}(Path(id): Path<{}>) -> impl IntoResponse {{
     Ok(())
 }
 
-fn generate_main_file(_spec: &SwaggerSpec, output_dir: &Path) -> Result<()> {
+/// Generate a module with one handler per documented callback. A client that registered a callback URL
+/// POSTs `{"url": ...}` to `/_callbacks/<operationId>/<callback>` to have the mock server send the
+/// documented request there, as the real API would when the event occurs.
+fn generate_callbacks_module(spec: &SwaggerSpec, output_dir: &Path) -> Result<()> {
+    let callbacks_path = output_dir.join("src").join("callbacks.rs");
+    let mut callbacks_file = File::create(callbacks_path)?;
+    
+    writeln!(callbacks_file, r#"use axum::{{
+    extract::Json,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::post,
+    Router,
+}};
+use serde::Deserialize;
+use serde_json::json;
+
+/// Where to send a simulated callback
+#[derive(Debug, Deserialize)]
+pub struct CallbackTarget {{
+    /// The URL the documented runtime expression resolves to
+    pub url: String,
+}}"#)?;
+    
+    let mut routes = Vec::new();
+    
+    for path in &spec.paths {
+        for op in &path.operations {
+            for callback in &op.callbacks {
+                for callback_op in &callback.operations {
+                    let handler_name = callback_handler_name(op, callback, callback_op);
+                    generate_callback_handler(&mut callbacks_file, &handler_name, op, callback, callback_op)?;
+                    
+                    let route = if callback.operations.len() > 1 {
+                        format!("/_callbacks/{}/{}/{}", op.operation_id, callback.name, callback_op.method.to_lowercase())
+                    } else {
+                        format!("/_callbacks/{}/{}", op.operation_id, callback.name)
+                    };
+                    routes.push((route, handler_name));
+                }
+            }
+        }
+    }
+    
+    writeln!(callbacks_file, "\npub fn routes() -> Router {{")?;
+    writeln!(callbacks_file, "    Router::new()")?;
+    for (route, handler_name) in routes {
+        writeln!(callbacks_file, "        .route(\"{}\", post({}))", route, handler_name)?;
+    }
+    writeln!(callbacks_file, "}}")?;
+    
+    Ok(())
+}
+
+fn callback_handler_name(op: &ApiOperation, callback: &ApiCallback, callback_op: &ApiOperation) -> String {
+    let name = format!("{}_{}", camel_to_snake(&op.operation_id), camel_to_snake(&callback.name));
+    let name = if callback.operations.len() > 1 {
+        format!("{}_{}", name, callback_op.method.to_lowercase())
+    } else {
+        name
+    };
+    
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect()
+}
+
+fn generate_callback_handler(
+    file: &mut File,
+    handler_name: &str,
+    op: &ApiOperation,
+    callback: &ApiCallback,
+    callback_op: &ApiOperation,
+) -> Result<()> {
+    let payload = match callback_op.body_param.as_ref() {
+        Some(body) => format!("\n    let payload = json!({});\n", to_json_literal(&body_example(body), "    ")),
+        None => String::new(),
+    };
+    let send_payload = if callback_op.body_param.is_some() { ".json(&payload)" } else { "" };
+    
+    writeln!(file, r#"
+/// `{}` callback of `{}`, sent to `{}`
+pub async fn {}(Json(target): Json<CallbackTarget>) -> impl IntoResponse {{{}
+    let client = reqwest::Client::new();
+    match client.request(reqwest::Method::{}, &target.url){}.send().await {{
+        Ok(response) => (StatusCode::OK, Json(json!({{ "status": response.status().as_u16() }}))),
+        Err(err) => (StatusCode::BAD_GATEWAY, Json(json!({{ "error": err.to_string() }}))),
+    }}
+}}"#,
+        callback.name, op.operation_id, callback.expression.replace('`', "'"),
+        handler_name,
+        payload,
+        callback_op.method.to_uppercase(),
+        send_payload
+    )?;
+    
+    Ok(())
+}
+
+fn generate_main_file(_spec: &SwaggerSpec, output_dir: &Path, has_callbacks: bool) -> Result<()> {
     let src_dir = output_dir.join("src");
     fs::create_dir_all(&src_dir)?;
     
     let main_path = src_dir.join("main.rs");
     let mut main_file = File::create(main_path)?;
     
+    let (callbacks_module, callback_routes) = if has_callbacks {
+        ("mod callbacks;\n", ".merge(callbacks::routes())")
+    } else {
+        ("", "")
+    };
+    
     writeln!(main_file, r#"mod models;
 mod handlers;
 mod routes;
-
+{callbacks_module}
 use routes::app_router;
 use std::net::SocketAddr;
 
@@ -527,7 +717,7 @@ async fn main() {{
     tracing_subscriber::fmt::init();
 
     // Build our application
-    let app = app_router();
+    let app = app_router(){callback_routes};
 
     // Listen on the default port
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
@@ -543,13 +733,20 @@ async fn main() {{
     Ok(())
 }
 
-fn generate_cargo_toml(output_dir: &Path) -> Result<()> {
+fn generate_cargo_toml(spec: &SwaggerSpec, output_dir: &Path, has_callbacks: bool) -> Result<()> {
     let cargo_path = output_dir.join("Cargo.toml");
     let mut cargo_file = File::create(cargo_path)?;
     
+    // Callbacks are sent with reqwest
+    let reqwest_dependency = if has_callbacks {
+        "reqwest = { version = \"0.11\", features = [\"json\"] }\n"
+    } else {
+        ""
+    };
+    
     writeln!(cargo_file, r#"[package]
-name = "generated_api"
-version = "0.1.0"
+name = "{name}"
+version = "{version}"
 edition = "2021"
 
 [dependencies]
@@ -564,7 +761,9 @@ tracing-subscriber = {{ version = "0.3", features = ["env-filter"] }}
 tower-http = {{ version = "0.4", features = ["cors"] }}
 once_cell = "1.17"
 thiserror = "1.0"
-"#)?;
+{reqwest_dependency}"#,
+        name = spec.info.package_name("_", "api"),
+        version = spec.info.package_version("0.1.0"))?;
     
     Ok(())
 }
@@ -576,8 +775,8 @@ pub fn generate_swagger_doc(spec: &SwaggerSpec, output_dir: &Path) -> Result<()>
     let mut swagger_file = File::create(swagger_path)?;
     
     // Write the raw spec back out
-    let json_str = serde_json::to_string_pretty(&spec.raw_spec)
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    let json_str = serde_json::to_string_pretty(&*spec.raw_spec)
+        .map_err(std::io::Error::other)?;
         
     swagger_file.write_all(json_str.as_bytes())?;
     
@@ -0,0 +1,199 @@
+// This file contains the configuration of the credentials generated tests authenticate with, and the choice of the
+// credentials each operation is sent with from its security requirements.
+
+use crate::parser::{ApiOperation, SecuritySchemeKind, SwaggerSpec};
+
+/// A source of credentials given with `--auth`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthSource {
+    /// A bearer token, read from the environment variable named (`bearer:API_TOKEN`)
+    Bearer(String),
+
+    /// An API key, read from the environment variable named (`apikey:API_KEY`)
+    ApiKey(String),
+
+    /// A user name and password, read from the environment variables named (`basic:API_USERNAME:API_PASSWORD`)
+    Basic(String, String),
+
+    /// An OAuth2 client ID and secret, read from the environment variables named (`oauth2:CLIENT_ID:CLIENT_SECRET`)
+    ClientCredentials(String, String),
+}
+
+/// Where generated tests read the credentials for the API's security schemes from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuthConfig {
+    /// Environment variable holding the token of bearer, OAuth2 and OpenID Connect schemes
+    pub bearer_env: Option<String>,
+
+    /// Environment variable holding the key of API key schemes
+    pub api_key_env: Option<String>,
+
+    /// Environment variables holding the user name and password of HTTP basic authentication schemes
+    pub basic_env: Option<(String, String)>,
+
+    /// Environment variables holding the client ID and secret tests fetch access tokens of OAuth2 schemes with, through
+    /// their client credentials flow
+    pub client_credentials_env: Option<(String, String)>,
+}
+
+impl AuthConfig {
+    /// Whether no credentials are configured
+    pub fn is_empty(&self) -> bool {
+        self.bearer_env.is_none() && self.api_key_env.is_none() && self.basic_env.is_none() && self.client_credentials_env.is_none()
+    }
+}
+
+impl FromIterator<AuthSource> for AuthConfig {
+    /// Collect `--auth` values into a configuration, later sources of the same kind replacing earlier ones
+    fn from_iter<I: IntoIterator<Item = AuthSource>>(sources: I) -> Self {
+        let mut config = AuthConfig::default();
+        for source in sources {
+            match source {
+                AuthSource::Bearer(env) => config.bearer_env = Some(env),
+                AuthSource::ApiKey(env) => config.api_key_env = Some(env),
+                AuthSource::Basic(username_env, password_env) => config.basic_env = Some((username_env, password_env)),
+                AuthSource::ClientCredentials(id_env, secret_env) => config.client_credentials_env = Some((id_env, secret_env)),
+            }
+        }
+        config
+    }
+}
+
+/// A credential sent with a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credential {
+    /// An `Authorization: Bearer <token>` header, with the token read from an environment variable
+    Bearer {
+        /// Name of the environment variable
+        env: String,
+    },
+
+    /// An API key sent as the header or query parameter `name`, read from an environment variable
+    ApiKey {
+        /// Name of the header or query parameter
+        name: String,
+        /// Where the key is sent, "header" or "query"
+        location: String,
+        /// Name of the environment variable
+        env: String,
+    },
+
+    /// HTTP basic authentication, with the user name and password read from environment variables
+    Basic {
+        /// Name of the environment variable holding the user name
+        username_env: String,
+        /// Name of the environment variable holding the password
+        password_env: String,
+    },
+
+    /// A bearer token fetched from an OAuth2 token endpoint with the client credentials flow
+    ClientCredentials {
+        /// URL of the token endpoint
+        token_url: String,
+        /// Space-separated scopes the token is requested for
+        scope: String,
+        /// Name of the environment variable holding the client ID
+        client_id_env: String,
+        /// Name of the environment variable holding the client secret
+        client_secret_env: String,
+    },
+}
+
+impl Credential {
+    /// Where the credential is sent: "header" or "query", or "basic" for HTTP basic authentication, which HTTP clients
+    /// have their own support for
+    pub fn location(&self) -> &str {
+        match self {
+            Credential::Bearer { .. } | Credential::ClientCredentials { .. } => "header",
+            Credential::ApiKey { location, .. } => location,
+            Credential::Basic { .. } => "basic",
+        }
+    }
+
+    /// Name of the header or query parameter the credential is sent as
+    pub fn name(&self) -> &str {
+        match self {
+            Credential::Bearer { .. } | Credential::Basic { .. } | Credential::ClientCredentials { .. } => "Authorization",
+            Credential::ApiKey { name, .. } => name,
+        }
+    }
+
+    /// Names of the environment variables the credential is read from
+    pub fn env_vars(&self) -> Vec<&str> {
+        match self {
+            Credential::Bearer { env } | Credential::ApiKey { env, .. } => vec![env],
+            Credential::Basic { username_env, password_env } => vec![username_env, password_env],
+            Credential::ClientCredentials { client_id_env, client_secret_env, .. } => vec![client_id_env, client_secret_env],
+        }
+    }
+}
+
+/// The credentials to send an operation's requests with: those of the first of its security requirements that every
+/// scheme of has configured credentials. Operations which can be called anonymously get none
+pub fn operation_credentials(spec: &SwaggerSpec, operation: &ApiOperation, config: &AuthConfig) -> Vec<Credential> {
+    if !operation.requires_auth() || config.is_empty() {
+        return Vec::new();
+    }
+
+    operation.security
+        .iter()
+        .find_map(|requirement| {
+            requirement.schemes
+                .iter()
+                .map(|(name, scopes)| {
+                    let scheme = spec.security_schemes.iter().find(|scheme| &scheme.name == name)?;
+                    scheme_credential(&scheme.kind, scopes, config)
+                })
+                .collect::<Option<Vec<_>>>()
+        })
+        .unwrap_or_default()
+}
+
+/// The credentials any operation of the specification is sent with, each once
+pub fn used_credentials(spec: &SwaggerSpec, config: &AuthConfig) -> Vec<Credential> {
+    let mut used = Vec::new();
+    for operation in spec.paths.iter().flat_map(|path| &path.operations) {
+        for credential in operation_credentials(spec, operation, config) {
+            if !used.contains(&credential) {
+                used.push(credential);
+            }
+        }
+    }
+    used
+}
+
+/// The token endpoint of an OAuth2 scheme's client credentials flow
+pub fn client_credentials_url(kind: &SecuritySchemeKind) -> Option<&str> {
+    match kind {
+        SecuritySchemeKind::OAuth2 { flows } => {
+            flows.iter().find(|flow| flow.flow == "clientCredentials").and_then(|flow| flow.token_url.as_deref())
+        },
+        _ => None,
+    }
+}
+
+/// The credential satisfying a security scheme, with the `scopes` a requirement asks of it, if one is configured.
+/// OAuth2 schemes with a client credentials flow get tokens fetched with it when a client is configured, and the
+/// configured bearer token otherwise
+fn scheme_credential(kind: &SecuritySchemeKind, scopes: &[String], config: &AuthConfig) -> Option<Credential> {
+    if let Some((token_url, (client_id_env, client_secret_env))) = client_credentials_url(kind).zip(config.client_credentials_env.as_ref()) {
+        return Some(Credential::ClientCredentials {
+            token_url: token_url.to_string(),
+            scope: scopes.join(" "),
+            client_id_env: client_id_env.clone(),
+            client_secret_env: client_secret_env.clone(),
+        });
+    }
+
+    match kind {
+        SecuritySchemeKind::Bearer { .. } | SecuritySchemeKind::OAuth2 { .. } | SecuritySchemeKind::OpenIdConnect { .. } => {
+            config.bearer_env.clone().map(|env| Credential::Bearer { env })
+        },
+        // Keys sent in cookies would clash with the `Cookie` header of cookie parameters
+        SecuritySchemeKind::ApiKey { name, location } if location == "header" || location == "query" => {
+            config.api_key_env.clone().map(|env| Credential::ApiKey { name: name.clone(), location: location.clone(), env })
+        },
+        SecuritySchemeKind::Basic => config.basic_env.clone().map(|(username_env, password_env)| Credential::Basic { username_env, password_env }),
+        SecuritySchemeKind::ApiKey { .. } | SecuritySchemeKind::Other(_) => None,
+    }
+}
@@ -0,0 +1,336 @@
+// This file contains the logic for building example request bodies and parameter values from the schemas in the Swagger document.
+
+use serde_json::{Map, Value};
+use crate::parser::{ApiOperation, ApiParameter, Schema, SchemaVariant};
+use crate::utils::synthesis::synthesize_value;
+use super::data::{example_from_schema, property_example};
+
+/// The request bodies to generate a test for: a single `None` for a plain body,
+/// otherwise one entry per subtype of a polymorphic body
+pub fn body_variants(operation: &ApiOperation) -> Vec<Option<&SchemaVariant>> {
+    match operation.body_param.as_ref() {
+        Some(body) if !body.variants.is_empty() => body.variants.iter().map(Some).collect(),
+        _ => vec![None],
+    }
+}
+
+/// Suffix appended to test names to tell the subtypes of a polymorphic body apart
+pub fn variant_suffix(variant: Option<&SchemaVariant>) -> String {
+    match variant {
+        Some(variant) => {
+            let name: String = variant.name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+                .collect();
+            format!("_{}", name.trim_matches('_'))
+        },
+        None => String::new(),
+    }
+}
+
+/// Build an example value for a parameter, from its documented example, its schema (OpenAPI 3)
+/// or its declared type (Swagger 2.0)
+pub fn example_for_parameter(param: &ApiParameter) -> Value {
+    if let Some(value) = specified_value(param) {
+        return value;
+    }
+    
+    match param.schema.as_ref() {
+        Some(schema) => example_from_schema(schema),
+        None => example_from_schema(&Schema::from_value(&serde_json::json!({ "type": param.param_type, "format": param.format }))),
+    }
+}
+
+/// The example the specification documents for a parameter or request body, if any
+pub fn documented_example(param: &ApiParameter) -> Option<Value> {
+    param.example
+        .as_ref()
+        .or_else(|| param.schema.as_ref().and_then(Schema::example))
+        .cloned()
+}
+
+/// A value the specification gives for a parameter: its documented example, then its `default`,
+/// then the first member of its `enum`, so that requests aren't rejected for an invalid value
+pub fn specified_value(param: &ApiParameter) -> Option<Value> {
+    documented_example(param)
+        .or_else(|| param.default.clone())
+        .or_else(|| param.enum_values.first().cloned())
+}
+
+/// Example of a request body or webhook payload: its documented example, or one built from its schema
+pub fn body_example(body: &ApiParameter) -> Value {
+    documented_example(body)
+        .or_else(|| body.schema.as_ref().map(example_from_schema))
+        .unwrap_or_else(|| Value::Object(Map::new()))
+}
+
+/// Example of the JSON body sent in a test of the operation, built from `variant` for a subtype of a polymorphic body
+pub fn request_body_example(operation: &ApiOperation, variant: Option<&SchemaVariant>) -> Option<Value> {
+    match variant {
+        Some(variant) => Some(example_from_schema(&variant.schema)),
+        None => operation.body_param.as_ref().map(body_example),
+    }
+}
+
+/// Value sent for a query parameter: its documented example, default or first `enum` member,
+/// otherwise one synthesized from its type and format
+pub fn query_value(param: &ApiParameter) -> String {
+    to_plain_text(&specified_value(param).unwrap_or_else(|| synthesized_value(param)))
+}
+
+/// Value bound to a path parameter in generated code: its documented example, default or first `enum` member,
+/// otherwise one synthesized from its type and format
+pub fn path_value(param: &ApiParameter) -> Value {
+    match specified_value(param).unwrap_or_else(|| synthesized_value(param)) {
+        value @ (Value::Number(_) | Value::String(_)) => value,
+        value => Value::String(to_plain_text(&value)),
+    }
+}
+
+/// The operation's path with its path parameters filled in with their example values
+pub fn example_path(path: &str, operation: &ApiOperation) -> String {
+    operation.path_params.iter().fold(path.to_string(), |path, param| {
+        path.replace(&format!("{{{}}}", param.name), &to_plain_text(&path_value(param)))
+    })
+}
+
+/// The query string sending the operation's query parameters with their example values (`?limit=10&status=available`),
+/// percent-encoded, or an empty string when it has none
+pub fn query_string(operation: &ApiOperation) -> String {
+    if operation.query_params.is_empty() {
+        return String::new();
+    }
+    
+    let pairs: Vec<String> = operation.query_params
+        .iter()
+        .map(|p| format!("{}={}", percent_encode(&p.name), percent_encode(&query_value(p))))
+        .collect();
+    format!("?{}", pairs.join("&"))
+}
+
+/// Percent-encode every byte of the text other than the unreserved characters of RFC 3986
+pub fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// A value synthesized from a parameter's type and format, for parameters the specification gives no value for.
+/// Arrays and objects are built from their schemas, with items and properties of their own types
+fn synthesized_value(param: &ApiParameter) -> Value {
+    match param.schema.as_ref() {
+        Some(schema @ (Schema::Array(_) | Schema::Object(_))) => example_from_schema(schema),
+        _ => synthesize_value(&param.param_type, param.format.as_deref(), &param.constraints),
+    }
+}
+
+/// Render an example value as the plain text sent in a header or query string, with the items of arrays separated
+/// by commas
+pub fn to_plain_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(to_plain_text).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    }
+}
+
+/// How a request body is encoded on the wire, derived from the media type it's sent as
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BodyEncoding {
+    /// A JSON document, for `application/json` and `+json` media types
+    Json,
+    
+    /// `multipart/form-data` fields and files
+    Multipart,
+    
+    /// `application/x-www-form-urlencoded` fields
+    Form,
+    
+    /// Any other media type, sent as plain text
+    Raw,
+}
+
+/// Work out how a request body is encoded
+pub fn body_encoding(body: &ApiParameter) -> BodyEncoding {
+    match body.content_type.as_deref() {
+        None | Some("*/*") => BodyEncoding::Json,
+        Some("multipart/form-data") => BodyEncoding::Multipart,
+        Some("application/x-www-form-urlencoded") => BodyEncoding::Form,
+        Some(media_type) if media_type.contains("json") => BodyEncoding::Json,
+        Some(_) => BodyEncoding::Raw,
+    }
+}
+
+/// Whether the operation uploads a `multipart/form-data` body
+pub fn has_multipart_body(operation: &ApiOperation) -> bool {
+    operation.body_param.as_ref().is_some_and(|body| body_encoding(body) == BodyEncoding::Multipart)
+}
+
+/// The `Content-Type` a generated request has to set by hand, because its HTTP client
+/// won't derive it from the body (anything but plain JSON, multipart and form bodies)
+pub fn custom_content_type(body: &ApiParameter) -> Option<&str> {
+    match (body_encoding(body), body.content_type.as_deref()) {
+        (BodyEncoding::Json, Some("application/json")) | (BodyEncoding::Json, Some("*/*")) => None,
+        (BodyEncoding::Json, media_type) | (BodyEncoding::Raw, media_type) => media_type,
+        _ => None,
+    }
+}
+
+/// The media type to ask for in `Accept`: JSON when the operation documents it, then any `+json` type,
+/// then whichever comes first. `None` when no response media type is documented.
+pub fn accept_header(operation: &ApiOperation) -> Option<&str> {
+    let produces = &operation.produces;
+    produces
+        .iter()
+        .find(|media_type| *media_type == "application/json")
+        .or_else(|| produces.iter().find(|media_type| media_type.ends_with("+json")))
+        .or_else(|| produces.first())
+        .map(String::as_str)
+}
+
+/// Header parameters with their example values, then `static_headers` (sent with every request, e.g. from `--header`),
+/// followed by the body's `Content-Type` when it has to be set by hand and the `Accept` header for the documented
+/// response media types
+pub fn request_headers(operation: &ApiOperation, static_headers: &[(String, String)]) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = operation.header_params
+        .iter()
+        .map(|p| (p.name.clone(), to_plain_text(&example_for_parameter(p))))
+        .collect();
+    
+    // Static headers replace header parameters of the same name
+    headers.retain(|(name, _)| !static_headers.iter().any(|(header, _)| header.eq_ignore_ascii_case(name)));
+    headers.extend(static_headers.iter().cloned());
+    
+    if let Some(content_type) = operation.body_param.as_ref().and_then(custom_content_type) {
+        if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Content-Type")) {
+            headers.push(("Content-Type".to_string(), content_type.to_string()));
+        }
+    }
+    
+    // Header parameters or static headers setting `Accept` themselves take precedence
+    if let Some(accept) = accept_header(operation) {
+        if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Accept")) {
+            headers.push(("Accept".to_string(), accept.to_string()));
+        }
+    }
+    
+    headers
+}
+
+/// The headers an operation's requests are sent with: `request_headers`, and cookie parameters as a `Cookie` header
+pub fn fixed_headers(operation: &ApiOperation, static_headers: &[(String, String)]) -> Vec<(String, String)> {
+    let mut headers = request_headers(operation, static_headers);
+    headers.extend(cookie_header(operation).map(|cookies| ("Cookie".to_string(), cookies)));
+    headers
+}
+
+/// Example text for a body sent with a non-JSON, non-form media type
+pub fn raw_body_example(body: &ApiParameter) -> String {
+    body.schema.as_ref().map(|schema| to_plain_text(&example_from_schema(schema))).unwrap_or_default()
+}
+
+/// The fields of a multipart or form body as `(name, text value)` pairs, where a `None` value marks a file part
+pub fn form_fields(body: &ApiParameter) -> Vec<(String, Option<String>)> {
+    let properties = match body.schema.as_ref() {
+        Some(Schema::Object(object)) => &object.properties,
+        _ => return Vec::new(),
+    };
+    
+    // A documented example of the whole body takes precedence over the fields' own examples
+    let example = documented_example(body);
+    
+    properties
+        .iter()
+        .filter(|(_, schema)| !is_read_only(schema))
+        .map(|(name, schema)| {
+            let value = if is_file_schema(schema) {
+                None
+            } else {
+                let value = example.as_ref().and_then(|e| e.get(name)).cloned().unwrap_or_else(|| property_example(name, schema));
+                Some(to_plain_text(&value))
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+/// Whether a property is only returned by the server (`readOnly`), and so never sent in a request
+pub fn is_read_only(schema: &Schema) -> bool {
+    schema.info().is_some_and(|info| info.read_only)
+}
+
+/// Whether the property of an object schema is only sent to the server (`writeOnly`), and so never returned in a response
+pub fn is_write_only(schema: Option<&Schema>, property: &str) -> bool {
+    schema
+        .and_then(|s| s.property(property))
+        .and_then(Schema::info)
+        .is_some_and(|info| info.write_only)
+}
+
+/// Whether a schema describes file content (`format: binary`/`base64`, or a 3.1 `contentMediaType`)
+fn is_file_schema(schema: &Schema) -> bool {
+    schema.info().is_some_and(|info| {
+        matches!(info.format.as_deref(), Some("binary") | Some("base64")) || info.content_media_type.is_some()
+    })
+}
+
+/// Build the value of a `Cookie` header carrying the operation's cookie parameters, if it has any
+pub fn cookie_header(operation: &ApiOperation) -> Option<String> {
+    if operation.cookie_params.is_empty() {
+        return None;
+    }
+    
+    Some(operation.cookie_params
+        .iter()
+        .map(|p| format!("{}={}", p.name, to_plain_text(&example_for_parameter(p))))
+        .collect::<Vec<_>>()
+        .join("; "))
+}
+
+/// Pretty-print a JSON value with every line after the first indented by `indent`,
+/// ready to be embedded in generated code. JSON is valid as-is in Rust's `json!` and JavaScript.
+pub fn to_json_literal(value: &Value, indent: &str) -> String {
+    let pretty = serde_json::to_string_pretty(value).unwrap_or_else(|_| "null".to_string());
+    pretty.replace('\n', &format!("\n{}", indent))
+}
+
+/// Render text as a JavaScript string literal, valid in TypeScript too since JSON strings are
+pub fn js_string(text: &str) -> String {
+    to_json_literal(&Value::String(text.to_string()), "")
+}
+
+/// Render a JSON value as a Python literal, with nested lines indented by `indent`
+pub fn to_python_literal(value: &Value, indent: &str) -> String {
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => serde_json::to_string(s).unwrap_or_default(),
+        Value::Array(items) if items.is_empty() => "[]".to_string(),
+        Value::Array(items) => {
+            let inner = format!("{}    ", indent);
+            let items = items
+                .iter()
+                .map(|item| format!("{}{}", inner, to_python_literal(item, &inner)))
+                .collect::<Vec<_>>()
+                .join(",\n");
+            format!("[\n{}\n{}]", items, indent)
+        },
+        Value::Object(obj) if obj.is_empty() => "{}".to_string(),
+        Value::Object(obj) => {
+            let inner = format!("{}    ", indent);
+            let entries = obj
+                .iter()
+                .map(|(key, item)| {
+                    format!("{}{}: {}", inner, serde_json::to_string(key).unwrap_or_default(), to_python_literal(item, &inner))
+                })
+                .collect::<Vec<_>>()
+                .join(",\n");
+            format!("{{\n{}\n{}}}", entries, indent)
+        },
+    }
+}
@@ -0,0 +1,226 @@
+// This file contains the Bruno collection generator, writing a `.bru` request file per operation with assertions on its response.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use crate::parser::{ApiOperation, Schema, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, body_variants, cookie_header, example_path, form_fields, js_string, query_string, query_value,
+    raw_body_example, request_body_example, request_headers, variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{
+    expected_headers, expected_status, required_response_properties, response_schema, GeneratorOptions, Result, TestGenerator,
+};
+
+// Bruno collection generator
+pub struct BrunoGenerator {
+    options: GeneratorOptions,
+}
+
+impl BrunoGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        BrunoGenerator { options }
+    }
+
+    /// Generate the request file for one request of an operation, with its body taken from `variant` when given,
+    /// at position `seq` in its folder
+    fn generate_request(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>, seq: usize) -> String {
+        let method = operation.method.to_lowercase();
+        let name = match (&operation.summary, variant) {
+            (Some(summary), Some(variant)) => format!("{} ({})", summary, variant.name),
+            (Some(summary), None) => summary.clone(),
+            (None, Some(variant)) => format!("{} ({})", operation.operation_id, variant.name),
+            (None, None) => operation.operation_id.clone(),
+        };
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+
+        let (body_mode, body) = match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((param, BodyEncoding::Multipart)) => {
+                // Bruno sets the boundary itself
+                headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Content-Type"));
+                let fields: Vec<(String, String)> = form_fields(param)
+                    .into_iter()
+                    .map(|(name, value)| (name, value.unwrap_or_else(|| "@file(upload.txt)".to_string())))
+                    .collect();
+                ("multipartForm", Some(block("body:multipart-form", &dictionary(&fields))))
+            },
+            Some((param, BodyEncoding::Form)) => {
+                let fields: Vec<(String, String)> = form_fields(param)
+                    .into_iter()
+                    .map(|(name, value)| (name, value.unwrap_or_default()))
+                    .collect();
+                ("formUrlEncoded", Some(block("body:form-urlencoded", &dictionary(&fields))))
+            },
+            Some((param, BodyEncoding::Raw)) => ("text", Some(block("body:text", &raw_body_example(param)))),
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                ("json", Some(block("body:json", &serde_json::to_string_pretty(&example).unwrap_or_default())))
+            },
+            None => ("none", None),
+        };
+
+        let mut blocks = vec![
+            block("meta", &dictionary(&[
+                ("name".to_string(), name),
+                ("type".to_string(), "http".to_string()),
+                ("seq".to_string(), seq.to_string()),
+            ])),
+            block(&method, &dictionary(&[
+                ("url".to_string(), format!("{{{{baseUrl}}}}{}{}", example_path(path, operation), query_string(operation))),
+                ("body".to_string(), body_mode.to_string()),
+                ("auth".to_string(), "none".to_string()),
+            ])),
+        ];
+        if !operation.query_params.is_empty() {
+            let params: Vec<(String, String)> = operation.query_params.iter().map(|p| (p.name.clone(), query_value(p))).collect();
+            blocks.push(block("params:query", &dictionary(&params)));
+        }
+        if !headers.is_empty() {
+            blocks.push(block("headers", &dictionary(&headers)));
+        }
+        blocks.extend(body);
+
+        // Assertions on the response: its status, documented headers, and the shape of its body
+        let mut assertions = vec![("res.status".to_string(), format!("eq {}", expected_status(operation)))];
+        for header in expected_headers(operation) {
+            assertions.push((format!("res.headers[{}]", js_string(&header.to_lowercase())), "isDefined".to_string()));
+        }
+        if let Some(schema) = response_schema(operation) {
+            assertions.extend(body_assertions(schema));
+        }
+        blocks.push(block("assert", &dictionary(&assertions)));
+
+        // Deprecated operations are still generated, but skipped when the collection runs
+        if operation.deprecated {
+            blocks.push(block("script:pre-request", "// Deprecated operation\nbru.runner.skipRequest();"));
+        }
+
+        let mut docs = format!("`{}` {} {}", operation.operation_id, operation.method.to_uppercase(), path);
+        if let Some(description) = &operation.description {
+            docs.push_str(&format!("\n\n{}", description.trim()));
+        }
+        if operation.deprecated {
+            docs.push_str("\n\n**Deprecated**");
+        }
+        blocks.push(block("docs", &docs));
+
+        blocks.join("\n\n")
+    }
+}
+
+impl TestGenerator for BrunoGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        let environments_dir = output_dir.join("environments");
+        fs::create_dir_all(&environments_dir)?;
+
+        // The collection's configuration, which Bruno opens the directory by
+        let collection = json!({
+            "version": "1",
+            "name": spec.info.title,
+            "type": "collection",
+            "ignore": ["node_modules", ".git"],
+        });
+        fs::write(output_dir.join("bruno.json"), serde_json::to_string_pretty(&collection).unwrap_or_default() + "\n")?;
+
+        // An environment with the base URL the requests are sent to
+        let mut environment_file = File::create(environments_dir.join("Local.bru"))?;
+
+        writeln!(environment_file, "{}", block("vars", &dictionary(&[("baseUrl".to_string(), base_url.trim_end_matches('/').to_string())])))?;
+
+        // A folder per tag, with a request file per operation, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let mut uploads = false;
+        for group in operation_groups(spec, &options) {
+            let folder = output_dir.join(&group.file_stem);
+            fs::create_dir_all(&folder)?;
+            fs::write(folder.join("folder.bru"), block("meta", &dictionary(&[("name".to_string(), group.name.clone())])) + "\n")?;
+
+            let requests = group.operations
+                .iter()
+                .flat_map(|&(path, operation)| body_variants(operation).into_iter().map(move |variant| (path, operation, variant)));
+            for (seq, (path, operation, variant)) in requests.enumerate() {
+                uploads |= operation.body_param.as_ref().is_some_and(|body| body_encoding(body) == BodyEncoding::Multipart);
+                let mut file = File::create(folder.join(format!("{}{}.bru", operation.operation_id, variant_suffix(variant))))?;
+
+                writeln!(file, "{}", self.generate_request(operation, path, variant, seq + 1))?;
+            }
+        }
+
+        // The file multipart requests upload, relative to the collection
+        if uploads {
+            fs::write(output_dir.join("upload.txt"), "test file content")?;
+        }
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Collection
+
+Generated Bruno collection for {title} (version {version}), with a folder {split} and a request per operation
+asserting its status, documented response headers and the shape of its body.
+
+## Running the collection
+
+Open this directory in Bruno with **Open Collection**, or run it with the Bruno CLI:
+
+```
+npm install -g @usebruno/cli
+bru run --env Local
+```
+
+The requests are sent to the `baseUrl` variable of the `Local` environment in `environments/`. Add an environment file
+next to it for every other deployment, and select it with `--env`.
+"#,
+            title = spec.info.title,
+            version = spec.info.version,
+            split = options.layout.unwrap_or(OutputLayout::PerTag).split())?;
+
+        Ok(())
+    }
+}
+
+/// Assertions on the response body: whether it's JSON and an array, and for objects the presence of the required properties
+fn body_assertions(schema: &Schema) -> Vec<(String, String)> {
+    if schema.info().is_some_and(|info| info.nullable) {
+        return Vec::new();
+    }
+
+    match schema {
+        Schema::Object(_) => {
+            let mut assertions = vec![("res.body".to_string(), "isJson".to_string())];
+            for property in required_response_properties(schema) {
+                // A colon would end the assertion's key
+                if property.contains(':') {
+                    continue;
+                }
+                assertions.push((format!("res.body[{}]", js_string(property)), "isDefined".to_string()));
+            }
+            assertions
+        },
+        Schema::Array(_) => vec![("res.body".to_string(), "isArray".to_string())],
+        Schema::Primitive(_) | Schema::Ref(_) | Schema::Composite(_) | Schema::Any(_) => Vec::new(),
+    }
+}
+
+/// Render a Bru block, with its content indented under the name
+fn block(name: &str, content: &str) -> String {
+    let lines: Vec<String> = content.lines().map(|line| format!("  {}", line).trim_end().to_string()).collect();
+    format!("{} {{\n{}\n}}", name, lines.join("\n"))
+}
+
+/// Render `key: value` lines of a Bru dictionary block, which can't hold line breaks in a value
+fn dictionary(entries: &[(String, String)]) -> String {
+    entries
+        .iter()
+        .map(|(key, value)| format!("{}: {}", key, value.replace(['\r', '\n'], " ")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
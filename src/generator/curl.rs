@@ -0,0 +1,189 @@
+// This file contains the curl smoke-test generator, writing a Bash script that calls every operation and summarizes the results.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, query_string, raw_body_example, request_body_example, request_headers,
+    variant_suffix, BodyEncoding,
+};
+use super::layout::operation_groups;
+use super::test_framework::{generate_in_parallel, success_statuses, GeneratorOptions, Result, TestGenerator};
+
+// Bash curl smoke-test generator
+pub struct CurlGenerator {
+    options: GeneratorOptions,
+}
+
+impl CurlGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        CurlGenerator { options }
+    }
+
+    /// Generate the check of one request of an operation, with its body taken from `variant` when given
+    fn generate_check(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_uppercase();
+        let summary = operation.summary.clone().unwrap_or_else(|| format!("{} {}", method, path));
+        let name = format!("{}{}", operation.operation_id, variant_suffix(variant));
+
+        // Deprecated operations are left out of the run
+        if operation.deprecated {
+            return format!("# {}\n# Deprecated: {} is not checked", summary.replace('\n', " "), name);
+        }
+
+        // curl waits for a body that never comes when HEAD is sent with `--request`
+        let mut args = vec![if method == "HEAD" { "--head".to_string() } else { format!("--request {}", method) }];
+        args.push(format!("\"$BASE_URL\"{}", shell_quote(&format!("{}{}", example_path(path, operation), query_string(operation)))));
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+
+        let mut body_args = Vec::new();
+        match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Content-Type"));
+                for (name, value) in form_fields(body) {
+                    body_args.push(match value {
+                        Some(value) => format!("--form-string {}", shell_quote(&format!("{}={}", name, value))),
+                        None => format!("--form {}", shell_quote(&format!("{}=@upload.txt;type=text/plain", name))),
+                    });
+                }
+            },
+            Some((body, BodyEncoding::Form)) => {
+                for (name, value) in form_fields(body) {
+                    body_args.push(format!("--data-urlencode {}", shell_quote(&format!("{}={}", name, value.unwrap_or_default()))));
+                }
+            },
+            Some((body, BodyEncoding::Raw)) => body_args.push(format!("--data-binary {}", shell_quote(&raw_body_example(body)))),
+            Some((_, BodyEncoding::Json)) => {
+                if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Content-Type")) {
+                    headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                }
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                body_args.push(format!("--data-binary {}", shell_quote(&serde_json::to_string(&example).unwrap_or_default())));
+            },
+            None => {},
+        }
+        args.extend(headers.iter().map(|(name, value)| format!("--header {}", shell_quote(&format!("{}: {}", name, value)))));
+        args.extend(body_args);
+
+        format!(
+            "# {summary}\ncheck {name} {statuses} \\\n  {args}",
+            summary = summary.replace('\n', " "),
+            name = shell_quote(&name),
+            statuses = shell_quote(&success_statuses(operation).join(" ")),
+            args = args.join(" \\\n  "),
+        )
+    }
+}
+
+impl TestGenerator for CurlGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // A single script checks every operation
+        let operations: Vec<_> = operation_groups(spec, &self.options)
+            .into_iter()
+            .flat_map(|group| group.operations)
+            .collect();
+        let uploads = operations
+            .iter()
+            .filter_map(|(_, operation)| operation.body_param.as_ref())
+            .any(|body| body_encoding(body) == BodyEncoding::Multipart);
+        let checks = generate_in_parallel(&[operations], &self.options, |path, operation, variant| {
+            self.generate_check(operation, path, variant)
+        })?;
+        let checks = checks.into_iter().flatten().collect::<Vec<_>>();
+
+        let script_path = output_dir.join("smoke.sh");
+        let mut script = File::create(&script_path)?;
+
+        writeln!(script, r#"#!/usr/bin/env bash
+# Smoke test of {title} (version {version}): calls every operation and checks its status
+set -u
+
+# The API_BASE_URL environment variable overrides where the API under test runs
+BASE_URL="${{API_BASE_URL:-{base_url}}}"
+
+# Uploaded files are read from next to the script
+cd "$(dirname "$0")" || exit 1
+
+passed=0
+failed=0
+
+# check NAME STATUSES CURL_ARGS...: send a request, passing when its status is one of the space-separated STATUSES
+check() {{
+  local name="$1" statuses="$2"
+  shift 2
+  local body status
+  body="$(mktemp)"
+  status="$(curl --silent --show-error --fail-with-body --output "$body" --write-out '%{{http_code}}' "$@")"
+  if [[ " $statuses " == *" $status "* ]]; then
+    echo "PASS $name ($status)"
+    passed=$((passed + 1))
+  else
+    echo "FAIL $name (got $status, expected $statuses)"
+    sed 's/^/    /' "$body"
+    failed=$((failed + 1))
+  fi
+  rm -f "$body"
+}}
+
+{checks}
+
+echo
+echo "$passed passed, $failed failed"
+[ "$failed" -eq 0 ]"#,
+            title = spec.info.title,
+            version = spec.info.version,
+            base_url = base_url.trim_end_matches('/').replace('\\', "\\\\").replace('"', "\\\"").replace('$', "\\$").replace('`', "\\`"),
+            checks = checks.join("\n\n"))?;
+
+        // The script is meant to be run directly
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&script_path, fs::Permissions::from_mode(0o755))?;
+        }
+
+        // The file multipart requests upload
+        if uploads {
+            fs::write(output_dir.join("upload.txt"), "test file content")?;
+        }
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Smoke Test
+
+Generated smoke test for {title} (version {version}), needing nothing but Bash and curl 7.76 or later.
+
+## Running the test
+
+Run the script against the API at `API_BASE_URL`:
+
+```
+API_BASE_URL=http://localhost:3000 ./smoke.sh
+```
+
+Every operation is called once and reported as `PASS` or `FAIL`, with the body of failed responses.
+The script exits with a non-zero status when any check fails, so it can gate a deployment.
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// Render text as a single-quoted shell word
+pub fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
@@ -0,0 +1,180 @@
+// This file contains the synthesis of test data from schemas: request bodies and responses built by walking the resolved
+// schema, with plausible values for each type, format and set of constraints, and for strings, for the property's name.
+// Without a seed every property gets the first of its candidate values; `--seed` picks among them instead, the same
+// way on every run with the same seed.
+
+use std::cell::Cell;
+
+use rand::{RngExt, SeedableRng};
+use rand_pcg::Pcg64;
+use serde_json::{Map, Value};
+use crate::parser::{CompositeKind, PrimitiveType, Schema};
+use crate::utils::synthesis::{constrain, format_value};
+use super::body::is_read_only;
+
+/// Arrays get at most this many items, whatever their `minItems`
+const MAX_GENERATED_ITEMS: u64 = 10;
+
+thread_local! {
+    /// Seed of the values synthesized on this thread, set for the duration of `with_seed`
+    static SEED: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Run `f` with the values it synthesizes picked with `seed`, or the first candidates when `None`
+pub fn with_seed<T>(seed: Option<u64>, f: impl FnOnce() -> T) -> T {
+    let previous = SEED.replace(seed);
+    let result = f();
+    SEED.set(previous);
+    result
+}
+
+/// Build an example value that matches the given schema, preferring the examples it documents.
+/// Examples are sent in requests, so `readOnly` properties are left out of objects.
+/// Recursive schemas stop at the reference back to themselves: it's left out of objects unless required,
+/// and arrays of it are empty.
+pub fn example_from_schema(schema: &Schema) -> Value {
+    build_example(schema, None, is_read_only)
+}
+
+/// Build an example value for the property `name` of a request body, plausible for what the property holds
+/// (`user@example.com` for an `email`) when its schema doesn't say more than its type
+pub fn property_example(name: &str, schema: &Schema) -> Value {
+    build_example(schema, Some(name), is_read_only)
+}
+
+/// Build an example response body that matches the given schema, preferring the examples it documents.
+/// Responses never return `writeOnly` properties, so those are left out of objects instead.
+pub fn response_example(schema: &Schema) -> Value {
+    build_example(schema, None, |prop| prop.info().is_some_and(|info| info.write_only))
+}
+
+/// Build an example value that matches the given schema, for the property `name` when it is one, leaving out
+/// the properties `skip` is true of
+fn build_example(schema: &Schema, name: Option<&str>, skip: fn(&Schema) -> bool) -> Value {
+    if let Some(info) = schema.info() {
+        if let Some(value) = info.example.as_ref().or(info.default.as_ref()).or(info.enum_values.first()) {
+            return value.clone();
+        }
+
+        if let Some(value) = info.format.as_deref().and_then(format_value) {
+            return constrain(value, &info.constraints);
+        }
+    }
+
+    match schema {
+        Schema::Object(object) => {
+            let mut example = Map::new();
+            let recursive = |name: &String, prop: &Schema| matches!(prop, Schema::Ref(_)) && !object.required.contains(name);
+            for (name, prop) in object.properties.iter().filter(|(name, prop)| !skip(prop) && !recursive(name, prop)) {
+                example.insert(name.clone(), build_example(prop, Some(name), skip));
+            }
+
+            // Maps get a single entry
+            if let (true, Some(values)) = (object.properties.is_empty(), object.additional_properties.as_deref()) {
+                example.insert("key".to_string(), build_example(values, None, skip));
+            }
+            Value::Object(example)
+        },
+        Schema::Array(array) => match array.items.as_deref() {
+            Some(Schema::Ref(_)) => Value::Array(Vec::new()),
+            items => {
+                let constraints = &array.info.constraints;
+                let count = constraints.min_items.unwrap_or(1).max(1).min(constraints.max_items.unwrap_or(u64::MAX)).min(MAX_GENERATED_ITEMS);
+                let item = items.map(|items| build_example(items, name, skip)).unwrap_or(Value::Null);
+                Value::Array(vec![item; count as usize])
+            },
+        },
+        Schema::Primitive(primitive) => {
+            let mut rng = seeded_rng(name.unwrap_or_default());
+            let value = match (primitive.kind, rng.as_mut()) {
+                (PrimitiveType::Integer, None) => Value::from(1),
+                (PrimitiveType::Integer, Some(rng)) => Value::from(rng.random_range(1..=100)),
+                (PrimitiveType::Number, None) => Value::from(1.5),
+                (PrimitiveType::Number, Some(rng)) => Value::from(rng.random_range(100..=10_000) as f64 / 100.0),
+                (PrimitiveType::Boolean, None) => Value::Bool(true),
+                (PrimitiveType::Boolean, Some(rng)) => Value::Bool(rng.random_bool(0.5)),
+                (PrimitiveType::String, _) => name
+                    .and_then(|name| plausible_string(name, &mut rng))
+                    .unwrap_or_else(|| Value::String(pick(&["string", "alpha", "bravo", "charlie", "delta"], &mut rng).to_string())),
+            };
+            constrain(value, &primitive.info.constraints)
+        },
+        // Any member of a choice will do, while every member of an `allOf` contributes its properties
+        Schema::Composite(composite) if composite.kind == CompositeKind::AllOf => {
+            let mut example = Map::new();
+            for member in &composite.members {
+                if let Value::Object(part) = build_example(member, name, skip) {
+                    example.extend(part);
+                }
+            }
+            Value::Object(example)
+        },
+        Schema::Composite(composite) => composite.members
+            .first()
+            .map(|member| build_example(member, name, skip))
+            .unwrap_or_else(|| Value::Object(Map::new())),
+        Schema::Ref(_) | Schema::Any(_) => Value::Object(Map::new()),
+    }
+}
+
+/// The generator of the values synthesized for the property `name` when a seed is set. Each property gets its own,
+/// so that its value doesn't depend on which properties were synthesized before it
+fn seeded_rng(name: &str) -> Option<Pcg64> {
+    // FNV-1a, which unlike the standard library's hasher is the same in every Rust release
+    let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+    SEED.get().map(|seed| Pcg64::seed_from_u64(seed ^ hash))
+}
+
+/// One of the candidate values: the first without a generator, otherwise a random one
+fn pick<'a>(candidates: &[&'a str], rng: &mut Option<Pcg64>) -> &'a str {
+    match rng {
+        Some(rng) => candidates[rng.random_range(0..candidates.len())],
+        None => candidates[0],
+    }
+}
+
+/// A plausible value for a string property without a format, going by its name (`firstName`, `first_name` and
+/// `emails` are all recognized), or `None` for names that don't say what the property holds
+fn plausible_string(name: &str, rng: &mut Option<Pcg64>) -> Option<Value> {
+    let key: String = name.chars().filter(char::is_ascii_alphanumeric).collect::<String>().to_ascii_lowercase();
+    let singular = key.strip_suffix('s').unwrap_or(&key);
+    let timestamp = name.ends_with("At") || name.to_ascii_lowercase().ends_with("_at");
+
+    let value = [key.as_str(), singular].into_iter().find_map(|key| {
+        let candidates: &[&str] = match key {
+            "email" | "emailaddress" | "mail" => return format_value("email"),
+            "url" | "uri" | "link" | "href" | "website" | "homepage" => return format_value("uri"),
+            "uuid" | "guid" => return format_value("uuid"),
+            "host" | "hostname" | "domain" => return format_value("hostname"),
+            "ip" | "ipaddress" => return format_value("ipv4"),
+            "password" | "passphrase" => return format_value("password"),
+            "date" | "birthdate" | "dateofbirth" | "birthday" => return format_value("date"),
+            "timestamp" | "datetime" => return format_value("date-time"),
+            "name" | "fullname" | "displayname" => &["Jane Doe", "John Smith", "Maria Garcia", "Wei Chen", "Amara Okafor"],
+            "firstname" | "givenname" => &["Jane", "John", "Maria", "Wei", "Amara"],
+            "lastname" | "surname" | "familyname" => &["Doe", "Smith", "Garcia", "Chen", "Okafor"],
+            "username" | "login" | "nickname" | "handle" => &["jdoe", "jsmith", "mgarcia", "wchen", "aokafor"],
+            "phone" | "phonenumber" | "telephone" | "mobile" => &["+1-555-0100", "+1-555-0142", "+1-555-0175", "+1-555-0199"],
+            "address" | "street" | "streetaddress" | "addressline1" => &["1 Main Street", "42 Oak Avenue", "7 Elm Road", "300 Pine Lane"],
+            "city" | "town" => &["Springfield", "Riverside", "Fairview", "Franklin"],
+            "state" | "region" | "province" => &["Oregon", "Texas", "Ohio", "Maine"],
+            "country" => &["United States", "Canada", "Germany", "Japan"],
+            "countrycode" => &["US", "CA", "DE", "JP"],
+            "zip" | "zipcode" | "postcode" | "postalcode" => &["97403", "73301", "44101", "04101"],
+            "company" | "organization" | "organisation" | "employer" => &["Example Inc.", "Acme Corp.", "Globex Ltd.", "Initech"],
+            "title" | "subject" | "headline" => &["Example title", "Quarterly report", "Release notes", "Meeting agenda"],
+            "description" | "summary" | "comment" | "note" | "message" | "bio" | "body" | "content" | "text" => {
+                &["An example description", "A short note for testing", "Written by the generated tests"]
+            },
+            "currency" | "currencycode" => &["USD", "EUR", "GBP", "JPY"],
+            "language" | "locale" | "lang" => &["en", "de", "fr", "ja"],
+            "color" | "colour" => &["blue", "green", "red", "orange"],
+            "slug" => &["example-slug", "quarterly-report", "release-notes"],
+            _ => return None,
+        };
+        Some(Value::String(pick(candidates, rng).to_string()))
+    });
+
+    // `createdAt`, `updated_at` and the like are moments in time
+    value.or_else(|| if timestamp { format_value("date-time") } else { None })
+}
@@ -0,0 +1,306 @@
+// This file contains the data sets of `--data-file`: requests to send to operations, read from a JSON or CSV file,
+// which data-driven tests are parametrized over.
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::parser::{ApiOperation, ApiParameter, SchemaVariant, SwaggerSpec};
+use super::body::{body_variants, example_path, percent_encode, query_value, request_body_example, request_headers, to_plain_text};
+use super::test_framework::{expected_status, GeneratorOptions};
+
+/// Error reading or applying a data file
+#[derive(Debug, Error)]
+pub enum DataSetError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Invalid JSON: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Invalid data file: {0}")]
+    FormatError(String),
+
+    #[error("Invalid CSV on line {line}: {message}")]
+    CsvError { line: usize, message: String },
+
+    #[error("The data file has requests for {0}, but no operation has that ID")]
+    UnknownOperation(String),
+
+    #[error("The data file sets {parameter} for {operation}, which has no path, query or header parameter of that name")]
+    UnknownParameter { operation: String, parameter: String },
+}
+
+/// One request of a data set, overriding the values an operation's test sends
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataRow {
+    /// Values of path, query and header parameters, by name
+    pub params: Vec<(String, Value)>,
+
+    /// The JSON request body, the operation's example when `None`
+    pub body: Option<Value>,
+
+    /// The status the API has to answer with, the operation's expected one when `None`
+    pub status: Option<String>,
+}
+
+/// The requests of a data file, by operation ID
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataSets {
+    pub operations: Vec<(String, Vec<DataRow>)>,
+}
+
+/// A data set's request to an operation, with the values it doesn't set taken from the operation's test
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataCase {
+    /// The path the request is sent to, with the values of the path parameters filled in
+    pub path: String,
+
+    /// The query parameters sent, as `(name, value)` pairs
+    pub query: Vec<(String, String)>,
+
+    /// The headers sent, as `(name, value)` pairs
+    pub headers: Vec<(String, String)>,
+
+    /// The JSON body sent, if any
+    pub body: Option<Value>,
+
+    /// The status the API has to answer with
+    pub status: String,
+}
+
+impl DataSets {
+    /// Read a data file, as CSV when its extension is `.csv` and as JSON otherwise
+    pub fn from_file(path: &Path) -> Result<DataSets, DataSetError> {
+        let text = fs::read_to_string(path)?;
+        if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("csv")) {
+            DataSets::from_csv(&text)
+        } else {
+            DataSets::from_json(&text)
+        }
+    }
+
+    /// Read data sets from JSON mapping operation IDs to lists of requests, each an object with optional `params`
+    /// (an object of parameter values), `body` and `status`:
+    ///
+    /// ```json
+    /// { "getUserById": [{ "params": { "id": 1 }, "status": 200 }, { "params": { "id": 999 }, "status": 404 }] }
+    /// ```
+    pub fn from_json(text: &str) -> Result<DataSets, DataSetError> {
+        let format_error = |message: &str| DataSetError::FormatError(message.to_string());
+        let Value::Object(operations) = serde_json::from_str(text)? else {
+            return Err(format_error("expected an object of operation IDs to lists of requests"));
+        };
+
+        let mut data_sets = DataSets::default();
+        for (operation_id, requests) in operations {
+            let Value::Array(requests) = requests else {
+                return Err(format_error(&format!("expected a list of requests for {}", operation_id)));
+            };
+            let mut rows = Vec::new();
+            for request in requests {
+                let Value::Object(request) = request else {
+                    return Err(format_error(&format!("expected the requests for {} to be objects", operation_id)));
+                };
+                let mut row = DataRow::default();
+                for (key, value) in request {
+                    match (key.as_str(), value) {
+                        ("params", Value::Object(params)) => row.params = params.into_iter().collect(),
+                        ("body", body) => row.body = Some(body),
+                        ("status", status) => row.status = Some(parse_status(&to_plain_text(&status)).map_err(DataSetError::FormatError)?),
+                        ("params", _) => return Err(format_error(&format!("expected the params of {} to be an object", operation_id))),
+                        (key, _) => {
+                            return Err(format_error(&format!("unknown key {} in a request for {}, expected params, body or status", key, operation_id)));
+                        },
+                    }
+                }
+                rows.push(row);
+            }
+            data_sets.add(operation_id, rows);
+        }
+        Ok(data_sets)
+    }
+
+    /// Read data sets from CSV with a row per request: an `operationId` column, a `params.<name>` column for each
+    /// parameter set, and optional `body` (as JSON) and `status` columns. Empty cells leave the value unset
+    pub fn from_csv(text: &str) -> Result<DataSets, DataSetError> {
+        let mut records = csv_records(text)?.into_iter();
+        let Some((_, columns)) = records.next() else {
+            return Err(DataSetError::FormatError("the CSV file is empty".to_string()));
+        };
+        if !columns.iter().any(|column| column == "operationId") {
+            return Err(DataSetError::CsvError { line: 1, message: "there is no operationId column".to_string() });
+        }
+        if let Some(column) = columns.iter().find(|column| !matches!(column.as_str(), "operationId" | "body" | "status") && !column.starts_with("params.")) {
+            return Err(DataSetError::CsvError {
+                line: 1,
+                message: format!("unknown column {}, expected operationId, params.<name>, body or status", column),
+            });
+        }
+
+        let mut data_sets = DataSets::default();
+        for (line, record) in records {
+            if record.len() != columns.len() {
+                return Err(DataSetError::CsvError { line, message: format!("expected {} cells, got {}", columns.len(), record.len()) });
+            }
+            let mut operation_id = String::new();
+            let mut row = DataRow::default();
+            for (column, cell) in columns.iter().zip(record).filter(|(_, cell)| !cell.is_empty()) {
+                match column.as_str() {
+                    "operationId" => operation_id = cell,
+                    "body" => {
+                        let body = serde_json::from_str(&cell)
+                            .map_err(|error| DataSetError::CsvError { line, message: format!("the body isn't JSON: {}", error) })?;
+                        row.body = Some(body);
+                    },
+                    "status" => row.status = Some(parse_status(&cell).map_err(|message| DataSetError::CsvError { line, message })?),
+                    column => row.params.push((column["params.".len()..].to_string(), Value::String(cell))),
+                }
+            }
+            if operation_id.is_empty() {
+                return Err(DataSetError::CsvError { line, message: "the operationId is empty".to_string() });
+            }
+            data_sets.add(operation_id, vec![row]);
+        }
+        Ok(data_sets)
+    }
+
+    /// Add requests for an operation, after the ones it already has
+    fn add(&mut self, operation_id: String, rows: Vec<DataRow>) {
+        match self.operations.iter_mut().find(|(id, _)| *id == operation_id) {
+            Some((_, known)) => known.extend(rows),
+            None => self.operations.push((operation_id, rows)),
+        }
+    }
+
+    /// The requests for an operation, found by its operation ID or the one the specification declares
+    pub fn rows(&self, operation: &ApiOperation) -> &[DataRow] {
+        self.operations
+            .iter()
+            .find(|(id, _)| *id == operation.operation_id || Some(id) == operation.declared_operation_id.as_ref())
+            .map(|(_, rows)| rows.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Check each data set is for an operation of the specification, and sets only its parameters
+    pub fn check(&self, spec: &SwaggerSpec) -> Result<(), DataSetError> {
+        let operations: Vec<&ApiOperation> = spec.paths.iter().flat_map(|path| &path.operations).collect();
+        for (operation_id, rows) in &self.operations {
+            let operation = operations.iter()
+                .find(|operation| operation.operation_id == *operation_id || operation.declared_operation_id.as_ref() == Some(operation_id))
+                .ok_or_else(|| DataSetError::UnknownOperation(operation_id.clone()))?;
+            let is_param = |name: &str| {
+                operation.path_params.iter().chain(&operation.query_params).chain(&operation.header_params).any(|param| param.name.eq_ignore_ascii_case(name))
+            };
+            for (name, _) in rows.iter().flat_map(|row| &row.params) {
+                if !is_param(name) {
+                    return Err(DataSetError::UnknownParameter { operation: operation_id.clone(), parameter: name.clone() });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The requests of the data sets for an operation, for the first of its body variants only so that its data-driven test
+/// is generated once. Parameters a request doesn't set get the values of the operation's test, and the headers include
+/// the options' static headers
+pub fn data_cases(operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>, options: &GeneratorOptions) -> Vec<DataCase> {
+    if body_variants(operation).first().copied().flatten().map(|first| first.name.as_str()) != variant.map(|variant| variant.name.as_str()) {
+        return Vec::new();
+    }
+
+    options.data_sets
+        .rows(operation)
+        .iter()
+        .map(|row| {
+            let value = |name: &str| row.params.iter().find(|(param, _)| param.eq_ignore_ascii_case(name)).map(|(_, value)| to_plain_text(value));
+            let path = operation.path_params.iter().fold(path.to_string(), |path, param| match value(&param.name) {
+                Some(value) => path.replace(&format!("{{{}}}", param.name), &percent_encode(&value)),
+                None => path,
+            });
+
+            // The query parameters the spec has left out are optional ones, sent when the data set gives a value
+            let is_param = |name: &str, params: &[ApiParameter]| params.iter().any(|param| param.name.eq_ignore_ascii_case(name));
+            let mut query: Vec<(String, String)> = operation.query_params
+                .iter()
+                .map(|param| (param.name.clone(), value(&param.name).unwrap_or_else(|| query_value(param))))
+                .collect();
+            query.extend(row.params.iter()
+                .filter(|(name, _)| {
+                    !is_param(name, &operation.path_params) && !is_param(name, &operation.query_params) && !is_param(name, &operation.header_params)
+                })
+                .map(|(name, value)| (name.clone(), to_plain_text(value))));
+
+            let mut headers = request_headers(operation, &options.headers);
+            for (name, header_value) in headers.iter_mut() {
+                if is_param(name, &operation.header_params) {
+                    *header_value = value(name).unwrap_or_else(|| header_value.clone());
+                }
+            }
+
+            DataCase {
+                path: example_path(&path, operation),
+                query,
+                headers,
+                body: row.body.clone().or_else(|| request_body_example(operation, variant)),
+                status: row.status.clone().unwrap_or_else(|| expected_status(operation).to_string()),
+            }
+        })
+        .collect()
+}
+
+/// A status given in a data file, which has to be a three-digit HTTP status
+fn parse_status(status: &str) -> Result<String, String> {
+    match status.parse::<u16>() {
+        Ok(100..=599) => Ok(status.to_string()),
+        _ => Err(format!("expected an HTTP status, got \"{}\"", status)),
+    }
+}
+
+/// The records of CSV text with the line each starts on, its fields separated by commas and optionally quoted with `"`,
+/// in which `""` is a quote and commas and line breaks are kept
+fn csv_records(text: &str) -> Result<Vec<(usize, Vec<String>)>, DataSetError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let (mut line, mut record_line) = (1, 1);
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            },
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            '\n' if quoted => {
+                line += 1;
+                field.push(c);
+            },
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {},
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|field| !field.is_empty()) {
+                    records.push((record_line, std::mem::take(&mut record)));
+                }
+                record.clear();
+                line += 1;
+                record_line = line;
+            },
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(DataSetError::CsvError { line: record_line, message: "a quoted field isn't closed".to_string() });
+    }
+    record.push(field);
+    if record.iter().any(|field| !field.is_empty()) {
+        records.push((record_line, record));
+    }
+    Ok(records)
+}
@@ -0,0 +1,156 @@
+// This file contains the Deno test generator, writing TypeScript `Deno.test` cases that send their requests with the built-in `fetch`.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::json;
+use crate::parser::{ApiOperation, PrimitiveType, Schema, SchemaVariant, SwaggerSpec};
+use super::body::js_string;
+use super::layout::operation_groups;
+use super::test_framework::{
+    expected_headers, expected_status, generate_in_parallel, required_response_properties, response_schema, GeneratorOptions, Result, TestGenerator,
+};
+use super::vitest::fetch_request;
+
+// Deno test generator
+pub struct DenoGenerator {
+    options: GeneratorOptions,
+}
+
+impl DenoGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        DenoGenerator { options }
+    }
+
+    /// Generate the test for one request of an operation, with its body taken from `variant` when given
+    fn generate_operation_test(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let operation_id = match variant {
+            Some(variant) => format!("{} ({})", operation.operation_id, variant.name),
+            None => operation.operation_id.clone(),
+        };
+        let summary = operation.summary.clone().unwrap_or_else(|| format!("{} {}", operation.method.to_uppercase(), path));
+
+        let mut lines = vec![format!("  // {}", summary.replace('\n', " "))];
+        lines.extend(fetch_request(operation, path, variant, &self.options.headers));
+        lines.push(String::new());
+
+        // Verify status code and documented headers
+        lines.push(format!("  assertEquals(response.status, {});", expected_status(operation)));
+        for header in expected_headers(operation) {
+            lines.push(format!(
+                "  assert(response.headers.has({}), {});",
+                js_string(header),
+                js_string(&format!("missing {} header", header))
+            ));
+        }
+
+        // Verify the response body. Deno fails tests leaving a response body unread, so it's discarded when not checked
+        let assertions = response_schema(operation).map(body_assertions).unwrap_or_default();
+        if assertions.is_empty() {
+            lines.push("  await response.body?.cancel();".to_string());
+        } else {
+            lines.push(String::new());
+            lines.push("  const body = await response.json();".to_string());
+            lines.extend(assertions.into_iter().map(|assertion| format!("  {}", assertion)));
+        }
+
+        // Deprecated operations are still generated, but ignored
+        let test = if operation.deprecated {
+            format!("{{ name: {}, ignore: true }}", js_string(&operation_id))
+        } else {
+            js_string(&operation_id)
+        };
+        format!("Deno.test({}, async () => {{\n{}\n}});", test, lines.join("\n"))
+    }
+}
+
+impl TestGenerator for DenoGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // Create a test file for each path, or each tag
+        let groups = operation_groups(spec, &self.options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let tests = generate_in_parallel(&operations, &self.options, |path, operation, variant| {
+            self.generate_operation_test(operation, path, variant)
+        })?;
+
+        for (group, tests) in groups.iter().zip(tests) {
+            let mut file = File::create(output_dir.join(format!("{}_test.ts", group.file_stem)))?;
+
+            writeln!(file, r#"import {{ assert, assertEquals }} from "@std/assert";
+
+// The API_BASE_URL environment variable overrides where the API under test runs
+const BASE_URL = (Deno.env.get("API_BASE_URL") ?? {base_url}).replace(/\/$/, "");
+
+{tests}"#,
+                base_url = js_string(base_url.trim_end_matches('/')),
+                tests = tests.join("\n\n"))?;
+        }
+
+        // Create the Deno configuration, mapping the standard library's assertions and with a task running the tests
+        let config = json!({
+            "imports": {
+                "@std/assert": "jsr:@std/assert@^1.0.0"
+            },
+            "tasks": {
+                "test": "deno test --allow-net --allow-env=API_BASE_URL"
+            }
+        });
+        fs::write(output_dir.join("deno.json"), serde_json::to_string_pretty(&config).unwrap_or_default() + "\n")?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), using Deno's built-in test runner and `fetch`.
+There is nothing to install besides Deno 1.42 or later; the assertions come from the Deno standard library.
+
+## Running the tests
+
+Run the tests against the API at `API_BASE_URL`:
+
+```
+API_BASE_URL=http://localhost:3000 deno task test
+```
+
+The task only grants the tests network access and read access to `API_BASE_URL`.
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// Assertions on the parsed response body (`body`): its type, and for objects the presence of the required properties
+fn body_assertions(schema: &Schema) -> Vec<String> {
+    if schema.info().is_some_and(|info| info.nullable) {
+        return Vec::new();
+    }
+
+    match schema {
+        Schema::Object(_) => {
+            let mut assertions =
+                vec!["assert(typeof body === \"object\" && body !== null && !Array.isArray(body), \"expected an object\");".to_string()];
+            for property in required_response_properties(schema) {
+                assertions.push(format!(
+                    "assert(Object.hasOwn(body, {}), {});",
+                    js_string(property),
+                    js_string(&format!("missing property {}", property))
+                ));
+            }
+            assertions
+        },
+        Schema::Array(_) => vec!["assert(Array.isArray(body), \"expected an array\");".to_string()],
+        Schema::Primitive(primitive) => vec![format!("assertEquals(typeof body, {});", js_string(match primitive.kind {
+            PrimitiveType::String => "string",
+            PrimitiveType::Integer | PrimitiveType::Number => "number",
+            PrimitiveType::Boolean => "boolean",
+        }))],
+        Schema::Ref(_) | Schema::Composite(_) | Schema::Any(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,84 @@
+// This file contains the configuration files of the environments (dev, staging, prod...) generated tests run against,
+// which set the base URL and credentials the tests read from environment variables.
+
+use std::fs;
+use std::path::Path;
+
+use crate::parser::SwaggerSpec;
+use super::auth::used_credentials;
+use super::test_framework::{GeneratorOptions, Result};
+
+/// Environment variable naming the environment tests run against, the first configured one when unset
+pub const ENVIRONMENT_VAR: &str = "TEST_ENV";
+
+/// Environment variable holding the base URL of the API under test
+pub const BASE_URL_VAR: &str = "API_BASE_URL";
+
+/// The environment tests run against when `TEST_ENV` isn't set, if the options configure any
+pub fn default_environment(options: &GeneratorOptions) -> Option<&str> {
+    options.environments.first().map(|(name, _)| name.as_str())
+}
+
+/// Names of the environment variables tests read credentials from, each once
+pub fn credential_vars(spec: &SwaggerSpec, options: &GeneratorOptions) -> Vec<String> {
+    let mut vars: Vec<String> = Vec::new();
+    for credential in used_credentials(spec, &options.auth) {
+        for var in credential.env_vars() {
+            if !vars.iter().any(|known| known == var) {
+                vars.push(var.to_string());
+            }
+        }
+    }
+    vars
+}
+
+/// Write a `.env.<name>` file for each environment of the options, setting `API_BASE_URL` to its base URL and leaving
+/// the credentials for the user to fill in. Their lines are commented out, since loading `TOKEN=` would set the variable
+/// to an empty credential, sent as is instead of the tests failing for want of it
+pub fn write_env_files(spec: &SwaggerSpec, options: &GeneratorOptions, output_dir: &Path) -> Result<()> {
+    let credentials: String = credential_vars(spec, options)
+        .iter()
+        .map(|var| format!("# {}=\n", var))
+        .collect();
+    for (name, base_url) in &options.environments {
+        fs::write(
+            output_dir.join(format!(".env.{}", name)),
+            format!(
+                "# Settings of the {} environment for the {} tests, used when {} is \"{}\"\n{}={}\n{}",
+                name, spec.info.title, ENVIRONMENT_VAR, name, BASE_URL_VAR, base_url, credentials,
+            ),
+        )?;
+    }
+    Ok(())
+}
+
+/// The README section on the environments of the options, `command` running the tests
+pub fn readme_section(options: &GeneratorOptions, command: &str) -> String {
+    let Some(default) = default_environment(options) else {
+        return String::new();
+    };
+    let environments: Vec<String> = options.environments
+        .iter()
+        .map(|(name, base_url)| format!("- `{}`: {} (`.env.{}`)", name, base_url, name))
+        .collect();
+    let example = options.environments.last().map_or(default, |(name, _)| name.as_str());
+    format!(
+        r#"
+## Environments
+
+The tests read the base URL of the API from `{base_url_var}`, and their credentials from environment variables too. The
+`.env` file of each environment sets them, without replacing variables which are already set:
+
+{environments}
+
+`{environment_var}` picks the environment, `{default}` by default:
+
+```
+{environment_var}={example} {command}
+```
+"#,
+        base_url_var = BASE_URL_VAR,
+        environment_var = ENVIRONMENT_VAR,
+        environments = environments.join("\n"),
+    )
+}
@@ -0,0 +1,394 @@
+// This file contains the strategies of `--fuzz`: property-based tests draw the JSON body and query parameters of their
+// requests from strategies derived from the schemas and their constraints, rendered for proptest, Hypothesis and
+// fast-check.
+
+use serde_json::{json, Value};
+use crate::parser::{ApiOperation, ApiParameter, CompositeKind, Constraints, PrimitiveType, Schema, SchemaVariant};
+use super::test_framework::snake_case;
+use super::body::{body_encoding, is_read_only, to_json_literal, to_python_literal, BodyEncoding};
+
+/// Number of requests each property-based test sends
+pub const FUZZ_CASES: u32 = 50;
+
+/// Strings without a `maxLength` are at most this much longer than their `minLength`
+const MAX_EXTRA_LENGTH: u64 = 20;
+
+/// Arrays without a `maxItems` have at most this many more items than their `minItems`
+const MAX_EXTRA_ITEMS: u64 = 5;
+
+/// Numbers without a bound are drawn this far from the other one, or from zero
+const NUMBER_SPAN: f64 = 1_000_000.0;
+
+/// How the values of a schema are drawn
+#[derive(Debug, Clone, PartialEq)]
+pub enum Strategy {
+    /// Always the same value
+    Constant(Value),
+
+    /// One of the members of an `enum`
+    Choice(Vec<Value>),
+
+    /// An integer within the bounds that are given
+    Integer {
+        /// Lowest value drawn
+        min: Option<i64>,
+        /// Highest value drawn
+        max: Option<i64>,
+    },
+
+    /// A number within the bounds
+    Number {
+        /// Lowest value drawn
+        min: f64,
+        /// Highest value drawn
+        max: f64,
+    },
+
+    /// `true` or `false`
+    Boolean,
+
+    /// A string matching a regular expression
+    Pattern(String),
+
+    /// A string of a format with a strategy of its own: `email`, `uuid`, `date` or `date-time`
+    Format(String),
+
+    /// Any text with a length within the bounds
+    Text {
+        /// Shortest length drawn
+        min_length: u64,
+        /// Longest length drawn
+        max_length: u64,
+    },
+
+    /// An array with a number of items within the bounds
+    Array {
+        /// Strategy of the items
+        items: Box<Strategy>,
+        /// Fewest items drawn
+        min_items: u64,
+        /// Most items drawn
+        max_items: u64,
+    },
+
+    /// An object with a value for each of the properties
+    Object(Vec<(String, Strategy)>),
+
+    /// A value of any of the strategies
+    OneOf(Vec<Strategy>),
+}
+
+impl Strategy {
+    /// The strategy of values matching a schema. Like examples, they're sent in requests, so `readOnly` properties are
+    /// left out, and recursive schemas stop at the reference back to themselves
+    pub fn from_schema(schema: &Schema) -> Strategy {
+        if let Some(info) = schema.info() {
+            match info.enum_values.as_slice() {
+                [] => {},
+                [value] => return Strategy::Constant(value.clone()),
+                values => return Strategy::Choice(values.to_vec()),
+            }
+        }
+
+        match schema {
+            Schema::Object(object) => {
+                let recursive = |name: &String, prop: &Schema| matches!(prop, Schema::Ref(_)) && !object.required.contains(name);
+                Strategy::Object(
+                    object.properties
+                        .iter()
+                        .filter(|(name, prop)| !is_read_only(prop) && !recursive(name, prop))
+                        .map(|(name, prop)| (name.clone(), Strategy::from_schema(prop)))
+                        .collect(),
+                )
+            },
+            Schema::Array(array) => match array.items.as_deref() {
+                Some(Schema::Ref(_)) => Strategy::Constant(json!([])),
+                items => {
+                    let min_items = array.info.constraints.min_items.unwrap_or(0);
+                    Strategy::Array {
+                        items: Box::new(items.map(Strategy::from_schema).unwrap_or(Strategy::Constant(Value::Null))),
+                        min_items,
+                        max_items: array.info.constraints.max_items.unwrap_or(min_items + MAX_EXTRA_ITEMS),
+                    }
+                },
+            },
+            Schema::Primitive(primitive) => primitive_strategy(primitive.kind, primitive.info.format.as_deref(), &primitive.info.constraints),
+            // Every member of an `allOf` contributes its properties, while a value of any member of a choice will do
+            Schema::Composite(composite) if composite.kind == CompositeKind::AllOf => Strategy::Object(
+                composite.members
+                    .iter()
+                    .flat_map(|member| match Strategy::from_schema(member) {
+                        Strategy::Object(properties) => properties,
+                        _ => Vec::new(),
+                    })
+                    .collect(),
+            ),
+            Schema::Composite(composite) => Strategy::OneOf(composite.members.iter().map(Strategy::from_schema).collect()),
+            Schema::Ref(_) | Schema::Any(_) => Strategy::Constant(json!({})),
+        }
+    }
+
+    /// The strategy of the values of a query parameter, from its schema (OpenAPI 3) or its declared type (Swagger 2.0).
+    /// Query strings are text, so booleans are drawn as `true` and `false`
+    pub fn from_parameter(param: &ApiParameter) -> Strategy {
+        if !param.enum_values.is_empty() {
+            return Strategy::Choice(param.enum_values.clone());
+        }
+
+        let strategy = match param.schema.as_ref() {
+            Some(schema) => Strategy::from_schema(schema),
+            None => {
+                let kind = match param.param_type.as_str() {
+                    "integer" => PrimitiveType::Integer,
+                    "number" => PrimitiveType::Number,
+                    "boolean" => PrimitiveType::Boolean,
+                    _ => PrimitiveType::String,
+                };
+                primitive_strategy(kind, param.format.as_deref(), &param.constraints)
+            },
+        };
+        match strategy {
+            Strategy::Boolean => Strategy::Choice(vec![json!("true"), json!("false")]),
+            strategy => strategy,
+        }
+    }
+
+    /// Render the strategy as a proptest expression of a `Strategy<Value = serde_json::Value>`, its continuation lines
+    /// indented with `indent`
+    pub fn proptest(&self, indent: &str) -> String {
+        let to_json = ".prop_map(|value| json!(value))";
+        let regex = |pattern: &str| format!("proptest::string::string_regex({:?}).expect(\"Invalid pattern\"){}", pattern, to_json);
+        match self {
+            Strategy::Constant(value) => format!("Just(json!({}))", to_json_literal(value, indent)),
+            Strategy::Choice(values) => {
+                let values: Vec<String> = values.iter().map(|value| format!("json!({})", to_json_literal(value, indent))).collect();
+                format!("prop::sample::select(vec![{}])", values.join(", "))
+            },
+            Strategy::Integer { min, max } => match (min, max) {
+                (Some(min), Some(max)) => format!("({}i64..={}i64){}", min, max, to_json),
+                (Some(min), None) => format!("({}i64..){}", min, to_json),
+                (None, Some(max)) => format!("(..={}i64){}", max, to_json),
+                (None, None) => format!("any::<i64>(){}", to_json),
+            },
+            Strategy::Number { min, max } => format!("({:?}f64..={:?}f64){}", min, max, to_json),
+            Strategy::Boolean => format!("any::<bool>(){}", to_json),
+            // proptest generates the strings of a whole expression, without anchors
+            Strategy::Pattern(pattern) => regex(pattern.trim_start_matches('^').trim_end_matches('$')),
+            Strategy::Format(format) => regex(format_pattern(format)),
+            Strategy::Text { min_length, max_length } => regex(&format!("\\PC{{{},{}}}", min_length, max_length)),
+            Strategy::Array { items, min_items, max_items } => format!(
+                "prop::collection::vec({}, {}..={}).prop_map(|items| json!(items))",
+                items.proptest(indent),
+                min_items,
+                max_items
+            ),
+            Strategy::Object(properties) if properties.is_empty() => "Just(json!({}))".to_string(),
+            // The values are drawn into a vector, so that objects aren't limited to the size of proptest's tuples
+            Strategy::Object(properties) => {
+                let inner = format!("{}    ", indent);
+                let strategies: Vec<String> = properties
+                    .iter()
+                    .map(|(_, strategy)| format!("{}{}.boxed(),", inner, strategy.proptest(&inner)))
+                    .collect();
+                let fields: Vec<String> = properties
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (name, _))| format!("{}{:?}: values[{}]", inner, name, index))
+                    .collect();
+                format!(
+                    "vec![\n{}\n{indent}].prop_map(|values| json!({{\n{}\n{indent}}}))",
+                    strategies.join("\n"),
+                    fields.join(",\n"),
+                    indent = indent
+                )
+            },
+            Strategy::OneOf(strategies) => {
+                let strategies: Vec<String> = strategies.iter().map(|strategy| strategy.proptest(indent)).collect();
+                format!("prop_oneof![{}]", strategies.join(", "))
+            },
+        }
+    }
+
+    /// Render the strategy as a Hypothesis strategy, with the strategies module imported as `st`
+    pub fn hypothesis(&self, indent: &str) -> String {
+        match self {
+            Strategy::Constant(value) => format!("st.just({})", to_python_literal(value, indent)),
+            Strategy::Choice(values) => format!("st.sampled_from({})", to_python_literal(&Value::Array(values.clone()), indent)),
+            Strategy::Integer { min, max } => {
+                let bounds: Vec<String> = [("min_value", min), ("max_value", max)]
+                    .into_iter()
+                    .filter_map(|(name, bound)| bound.map(|bound| format!("{}={}", name, bound)))
+                    .collect();
+                format!("st.integers({})", bounds.join(", "))
+            },
+            Strategy::Number { min, max } => {
+                format!("st.floats(min_value={:?}, max_value={:?}, allow_nan=False, allow_infinity=False)", min, max)
+            },
+            Strategy::Boolean => "st.booleans()".to_string(),
+            Strategy::Pattern(pattern) => format!("st.from_regex({}, fullmatch=True)", to_python_literal(&json!(pattern), "")),
+            Strategy::Format(format) => match format.as_str() {
+                "email" => "st.emails()".to_string(),
+                "uuid" => "st.uuids().map(str)".to_string(),
+                "date" => "st.dates().map(lambda value: value.isoformat())".to_string(),
+                _ => "st.datetimes().map(lambda value: value.isoformat() + \"Z\")".to_string(),
+            },
+            Strategy::Text { min_length, max_length } => format!("st.text(min_size={}, max_size={})", min_length, max_length),
+            Strategy::Array { items, min_items, max_items } => {
+                format!("st.lists({}, min_size={}, max_size={})", items.hypothesis(indent), min_items, max_items)
+            },
+            Strategy::Object(properties) if properties.is_empty() => "st.just({})".to_string(),
+            Strategy::Object(properties) => {
+                let inner = format!("{}    ", indent);
+                let fields: Vec<String> = properties
+                    .iter()
+                    .map(|(name, strategy)| format!("{}{}: {},", inner, to_python_literal(&json!(name), ""), strategy.hypothesis(&inner)))
+                    .collect();
+                format!("st.fixed_dictionaries({{\n{}\n{}}})", fields.join("\n"), indent)
+            },
+            Strategy::OneOf(strategies) => {
+                let strategies: Vec<String> = strategies.iter().map(|strategy| strategy.hypothesis(indent)).collect();
+                format!("st.one_of({})", strategies.join(", "))
+            },
+        }
+    }
+
+    /// Render the strategy as a fast-check arbitrary, with the library imported as `fc`
+    pub fn fast_check(&self, indent: &str) -> String {
+        let dates = "fc.date({ min: new Date('1970-01-01T00:00:00Z'), max: new Date('2099-12-31T23:59:59Z') })";
+        match self {
+            Strategy::Constant(value) => format!("fc.constant({})", to_json_literal(value, indent)),
+            Strategy::Choice(values) => {
+                let values: Vec<String> = values.iter().map(|value| to_json_literal(value, indent)).collect();
+                format!("fc.constantFrom({})", values.join(", "))
+            },
+            Strategy::Integer { min, max } => {
+                let bounds: Vec<String> = [("min", min), ("max", max)]
+                    .into_iter()
+                    .filter_map(|(name, bound)| bound.map(|bound| format!("{}: {}", name, bound)))
+                    .collect();
+                if bounds.is_empty() { "fc.integer()".to_string() } else { format!("fc.integer({{ {} }})", bounds.join(", ")) }
+            },
+            Strategy::Number { min, max } => format!("fc.double({{ min: {}, max: {}, noNaN: true, noDefaultInfinity: true }})", min, max),
+            Strategy::Boolean => "fc.boolean()".to_string(),
+            Strategy::Pattern(pattern) => format!("fc.stringMatching(new RegExp({}))", to_json_literal(&json!(pattern), "")),
+            Strategy::Format(format) => match format.as_str() {
+                "email" => "fc.emailAddress()".to_string(),
+                "uuid" => "fc.uuid()".to_string(),
+                "date" => format!("{}.map((date) => date.toISOString().slice(0, 10))", dates),
+                _ => format!("{}.map((date) => date.toISOString())", dates),
+            },
+            Strategy::Text { min_length, max_length } => format!("fc.string({{ minLength: {}, maxLength: {} }})", min_length, max_length),
+            Strategy::Array { items, min_items, max_items } => {
+                format!("fc.array({}, {{ minLength: {}, maxLength: {} }})", items.fast_check(indent), min_items, max_items)
+            },
+            Strategy::Object(properties) if properties.is_empty() => "fc.constant({})".to_string(),
+            Strategy::Object(properties) => {
+                let inner = format!("{}  ", indent);
+                let fields: Vec<String> = properties
+                    .iter()
+                    .map(|(name, strategy)| format!("{}{}: {},", inner, to_json_literal(&json!(name), ""), strategy.fast_check(&inner)))
+                    .collect();
+                format!("fc.record({{\n{}\n{}}})", fields.join("\n"), indent)
+            },
+            Strategy::OneOf(strategies) => {
+                let strategies: Vec<String> = strategies.iter().map(|strategy| strategy.fast_check(indent)).collect();
+                format!("fc.oneof({})", strategies.join(", "))
+            },
+        }
+    }
+}
+
+/// Whether `--fuzz` gives the operation a property-based test: it needs a JSON body or query parameters to draw, and
+/// bodies of other media types aren't built from values that can be drawn
+pub fn is_fuzzed(operation: &ApiOperation) -> bool {
+    match operation.body_param.as_ref() {
+        Some(body) => body_encoding(body) == BodyEncoding::Json,
+        None => !operation.query_params.is_empty(),
+    }
+}
+
+/// The strategy of the operation's JSON body, drawn from `variant` for a subtype of a polymorphic body
+pub fn body_strategy(operation: &ApiOperation, variant: Option<&SchemaVariant>) -> Option<Strategy> {
+    match variant {
+        Some(variant) => Some(Strategy::from_schema(&variant.schema)),
+        None => operation.body_param.as_ref().map(|body| body.schema.as_ref().map(Strategy::from_schema).unwrap_or(Strategy::Constant(json!({})))),
+    }
+}
+
+/// The query parameters of the operation, each with the variable its drawn value is held in and its strategy
+pub fn query_strategies(operation: &ApiOperation) -> Vec<(&ApiParameter, String, Strategy)> {
+    operation.query_params
+        .iter()
+        .map(|param| (param, parameter_variable(param), Strategy::from_parameter(param)))
+        .collect()
+}
+
+/// The name of the variable holding the drawn value of a parameter in generated tests: its name in snake case, followed
+/// by `_value` when that isn't an identifier, is a keyword of Rust, Python or JavaScript, or is a name the tests use
+fn parameter_variable(param: &ApiParameter) -> String {
+    const KEYWORDS: &[&str] = &[
+        "and", "as", "async", "await", "break", "case", "catch", "class", "const", "continue", "crate", "def", "default", "del",
+        "delete", "do", "dyn", "else", "enum", "extern", "false", "fn", "for", "from", "function", "global", "if", "impl",
+        "import", "in", "is", "lambda", "let", "loop", "match", "mod", "move", "mut", "new", "none", "not", "or", "pass", "pub",
+        "raise", "ref", "return", "self", "static", "struct", "super", "switch", "this", "trait", "true", "try", "type",
+        "typeof", "unsafe", "use", "var", "where", "while", "with", "yield",
+        // Names of the generated tests
+        "axios", "body", "client", "fc", "json", "json_data", "response", "runtime", "st", "status",
+    ];
+    let variable: String = snake_case(&param.name)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    let variable = variable.trim_matches('_');
+    if variable.is_empty() || variable.starts_with(|c: char| c.is_ascii_digit()) || KEYWORDS.contains(&variable) {
+        format!("{}_value", if variable.is_empty() { "param" } else { variable })
+    } else {
+        variable.to_string()
+    }
+}
+
+/// The strategy of a primitive value of the type, format and constraints
+fn primitive_strategy(kind: PrimitiveType, format: Option<&str>, constraints: &Constraints) -> Strategy {
+    match kind {
+        PrimitiveType::Integer => {
+            let (lowest, highest) = constraints.accepted_range(1.0);
+            Strategy::Integer { min: lowest.map(|lowest| lowest.ceil() as i64), max: highest.map(|highest| highest.floor() as i64) }
+        },
+        // Exclusive bounds are stepped inside by a fraction of the range, or by 1 without the other bound
+        PrimitiveType::Number => {
+            let span = match (constraints.minimum, constraints.maximum) {
+                (Some(min), Some(max)) if max > min => (max - min) / 10.0,
+                _ => 1.0,
+            };
+            match constraints.accepted_range(span) {
+                (Some(min), Some(max)) => Strategy::Number { min, max },
+                (Some(min), None) => Strategy::Number { min, max: min + NUMBER_SPAN },
+                (None, Some(max)) => Strategy::Number { min: max - NUMBER_SPAN, max },
+                (None, None) => Strategy::Number { min: -NUMBER_SPAN, max: NUMBER_SPAN },
+            }
+        },
+        PrimitiveType::Boolean => Strategy::Boolean,
+        PrimitiveType::String => {
+            if let Some(pattern) = constraints.pattern.as_ref() {
+                return Strategy::Pattern(pattern.clone());
+            }
+            match format {
+                Some(format @ ("email" | "uuid" | "date" | "date-time")) => Strategy::Format(format.to_string()),
+                _ => {
+                    let min_length = constraints.min_length.unwrap_or(0);
+                    Strategy::Text { min_length, max_length: constraints.max_length.unwrap_or(min_length + MAX_EXTRA_LENGTH) }
+                },
+            }
+        },
+    }
+}
+
+/// A regular expression of the strings of a format, for proptest, which has no strategies of its own for them
+fn format_pattern(format: &str) -> &'static str {
+    match format {
+        "email" => "[a-z]{1,10}@example\\.com",
+        "uuid" => "[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}",
+        "date" => "20[0-9]{2}-(0[1-9]|1[0-2])-(0[1-9]|1[0-9]|2[0-8])",
+        _ => "20[0-9]{2}-(0[1-9]|1[0-2])-(0[1-9]|1[0-9]|2[0-8])T([01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9]Z",
+    }
+}
@@ -0,0 +1,295 @@
+// This file contains the Gherkin test generator, writing feature files with Python behave step definitions that send the requests.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, Schema, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, example_for_parameter, example_path, form_fields, query_value, raw_body_example, request_body_example, request_headers,
+    to_plain_text, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{
+    expected_headers, expected_status, generate_in_parallel, required_response_properties, response_schema, GeneratorOptions, Result, TestGenerator,
+};
+
+// Gherkin feature file generator, with behave step definitions
+pub struct GherkinGenerator {
+    options: GeneratorOptions,
+}
+
+impl GherkinGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        GherkinGenerator { options }
+    }
+
+    /// Generate the scenario for one request of an operation, with its body taken from `variant` when given
+    fn generate_scenario(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_uppercase();
+        let title = match (&operation.summary, variant) {
+            (Some(summary), Some(variant)) => format!("{} ({})", summary, variant.name),
+            (Some(summary), None) => summary.clone(),
+            (None, Some(variant)) => format!("{} {} ({})", method, path, variant.name),
+            (None, None) => format!("{} {}", method, path),
+        };
+
+        // Scenarios are tagged with their operation, and deprecated ones with `@deprecated`, which environment.py skips
+        let mut tags = vec![format!("@{}", operation.operation_id)];
+        if operation.deprecated {
+            tags.push("@deprecated".to_string());
+        }
+
+        let mut steps = vec![format!("the request path is {}", quoted(&example_path(path, operation)))];
+        for param in &operation.query_params {
+            steps.push(format!("the query parameter {} is {}", quoted(&param.name), quoted(&query_value(param))));
+        }
+        for (name, value) in request_headers(operation, &self.options.headers) {
+            steps.push(format!("the header {} is {}", quoted(&name), quoted(&value)));
+        }
+        for param in &operation.cookie_params {
+            steps.push(format!("the cookie {} is {}", quoted(&param.name), quoted(&to_plain_text(&example_for_parameter(param)))));
+        }
+
+        match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                for (name, value) in form_fields(body) {
+                    steps.push(match value {
+                        Some(value) => format!("the form field {} is {}", quoted(&name), quoted(&value)),
+                        None => format!("a file is uploaded as {}", quoted(&name)),
+                    });
+                }
+            },
+            Some((body, BodyEncoding::Form)) => {
+                for (name, value) in form_fields(body) {
+                    steps.push(format!("the form field {} is {}", quoted(&name), quoted(&value.unwrap_or_default())));
+                }
+            },
+            Some((body, BodyEncoding::Raw)) => steps.push(format!("the request body is:\n{}", doc_string(&raw_body_example(body)))),
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                steps.push(format!("the JSON request body is:\n{}", doc_string(&serde_json::to_string_pretty(&example).unwrap_or_default())));
+            },
+            None => {},
+        }
+
+        let mut lines: Vec<String> = steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| format!("    {} {}", if index == 0 { "Given" } else { "And" }, step))
+            .collect();
+        lines.push(format!("    When I send a {} request", method));
+        lines.push(format!("    Then the response status is {}", expected_status(operation)));
+
+        // Assertions on the response
+        for header in expected_headers(operation) {
+            lines.push(format!("    And the response has the header {}", quoted(header)));
+        }
+        if let Some(schema) = response_schema(operation) {
+            lines.extend(body_steps(schema).into_iter().map(|step| format!("    And {}", step)));
+        }
+
+        format!("  {}\n  Scenario: {}\n{}", tags.join(" "), title.replace('\n', " "), lines.join("\n"))
+    }
+}
+
+impl TestGenerator for GherkinGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        let features_dir = output_dir.join("features");
+        let steps_dir = features_dir.join("steps");
+        fs::create_dir_all(&steps_dir)?;
+
+        // One feature per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let scenarios = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_scenario(operation, path, variant)
+        })?;
+
+        for (group, scenarios) in groups.iter().zip(scenarios) {
+            let mut file = File::create(features_dir.join(format!("{}.feature", group.file_stem)))?;
+
+            writeln!(file, "Feature: {}\n\n{}", group.title(), scenarios.join("\n\n"))?;
+        }
+
+        // The hooks giving every scenario the base URL and a fresh request, and skipping deprecated operations
+        let mut environment_file = File::create(features_dir.join("environment.py"))?;
+
+        writeln!(environment_file, r#"import os
+
+
+def before_all(context):
+    # The API_BASE_URL environment variable overrides where the API under test runs
+    context.base_url = os.environ.get("API_BASE_URL", {base_url}).rstrip("/")
+
+
+def before_scenario(context, scenario):
+    if "deprecated" in scenario.effective_tags:
+        scenario.skip("deprecated operation")
+    context.request = {{
+        "path": "",
+        "params": {{}},
+        "headers": {{}},
+        "cookies": {{}},
+        "data": {{}},
+        "files": {{}},
+        "body": None,
+        "json": None,
+    }}
+    context.response = None"#,
+            base_url = serde_json::to_string(base_url.trim_end_matches('/')).unwrap_or_default())?;
+
+        // The step definitions every feature shares
+        let mut steps_file = File::create(steps_dir.join("api_steps.py"))?;
+
+        writeln!(steps_file, r#""""Step definitions building a request, sending it with requests, and checking the response"""
+import json
+
+import requests
+from behave import given, then, when
+
+
+@given('the request path is "{{path}}"')
+def request_path(context, path):
+    context.request["path"] = path
+
+
+@given('the query parameter "{{name}}" is "{{value}}"')
+def query_parameter(context, name, value):
+    context.request["params"][name] = value
+
+
+@given('the header "{{name}}" is "{{value}}"')
+def header(context, name, value):
+    context.request["headers"][name] = value
+
+
+@given('the cookie "{{name}}" is "{{value}}"')
+def cookie(context, name, value):
+    context.request["cookies"][name] = value
+
+
+@given('the form field "{{name}}" is "{{value}}"')
+def form_field(context, name, value):
+    context.request["data"][name] = value
+
+
+@given('a file is uploaded as "{{name}}"')
+def file_upload(context, name):
+    context.request["files"][name] = ("upload.txt", b"test file content", "text/plain")
+
+
+@given("the request body is:")
+def request_body(context):
+    context.request["body"] = context.text.encode("utf-8")
+
+
+@given("the JSON request body is:")
+def json_request_body(context):
+    context.request["json"] = json.loads(context.text)
+
+
+@when("I send a {{method}} request")
+def send_request(context, method):
+    request = context.request
+    context.response = requests.request(
+        method,
+        context.base_url + request["path"],
+        params=request["params"],
+        headers=request["headers"],
+        cookies=request["cookies"],
+        data=request["body"] if request["body"] is not None else request["data"],
+        files=request["files"] or None,
+        json=request["json"],
+        timeout=30,
+    )
+
+
+@then("the response status is {{status:d}}")
+def response_status(context, status):
+    assert context.response.status_code == status, (
+        f"expected status {{status}}, got {{context.response.status_code}}: {{context.response.text}}"
+    )
+
+
+@then('the response has the header "{{name}}"')
+def response_header(context, name):
+    assert name in context.response.headers, f"response header {{name}} is missing"
+
+
+@then("the response body is a JSON object")
+def response_object(context):
+    assert isinstance(context.response.json(), dict), "the response body is not a JSON object"
+
+
+@then("the response body is a JSON array")
+def response_array(context):
+    assert isinstance(context.response.json(), list), "the response body is not a JSON array"
+
+
+@then('the response body has the property "{{name}}"')
+def response_property(context, name):
+    assert name in context.response.json(), f"the response body has no property {{name}}""#)?;
+
+        // Create a requirements.txt file
+        let mut requirements_file = File::create(output_dir.join("requirements.txt"))?;
+
+        writeln!(requirements_file, "behave>=1.2.6\nrequests>=2.31.0")?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), as Gherkin features with behave step definitions.
+
+## Running the tests
+
+Install the requirements, then run the features against the API at `API_BASE_URL`:
+
+```
+pip install -r requirements.txt
+API_BASE_URL=http://localhost:3000 behave
+```
+
+Scenarios are tagged with their operation ID, so a single one can be run with `behave --tags=@getPet`.
+The steps are defined in `features/steps/api_steps.py`, so the features also suit other Cucumber implementations given matching steps.
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// Steps checking the response body: whether it's an object or array, and for objects the presence of the required properties
+fn body_steps(schema: &Schema) -> Vec<String> {
+    if schema.info().is_some_and(|info| info.nullable) {
+        return Vec::new();
+    }
+
+    match schema {
+        Schema::Object(_) => {
+            let mut steps = vec!["the response body is a JSON object".to_string()];
+            for property in required_response_properties(schema) {
+                steps.push(format!("the response body has the property {}", quoted(property)));
+            }
+            steps
+        },
+        Schema::Array(_) => vec!["the response body is a JSON array".to_string()],
+        Schema::Primitive(_) | Schema::Ref(_) | Schema::Composite(_) | Schema::Any(_) => Vec::new(),
+    }
+}
+
+/// Render a step argument in double quotes, which behave's patterns match up to the closing quote
+fn quoted(text: &str) -> String {
+    format!("\"{}\"", text.replace('\n', " "))
+}
+
+/// Render text as a Gherkin doc string, indented under its step
+fn doc_string(text: &str) -> String {
+    let lines: Vec<String> = text.lines().map(|line| format!("      {}", line).trim_end().to_string()).collect();
+    format!("      \"\"\"\n{}\n      \"\"\"", lines.join("\n"))
+}
@@ -0,0 +1,309 @@
+// This file contains the Go test generator, writing a module of table-driven tests that use `net/http` and `testing`.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, query_value, raw_body_example, request_body_example, request_headers,
+    variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{expected_headers, expected_status, generate_in_parallel, pascal_case, GeneratorOptions, Result, TestGenerator};
+
+// Go net/http test generator
+pub struct GoGenerator {
+    options: GeneratorOptions,
+}
+
+impl GoGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        GoGenerator { options }
+    }
+
+    /// Generate the table entry testing one request of an operation, with its body taken from `variant` when given
+    fn generate_test_case(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let mut fields = vec![
+            ("name", go_string(&format!("{}{}", operation.operation_id, variant_suffix(variant)))),
+            ("method", go_string(&operation.method.to_uppercase())),
+            ("path", go_string(&example_path(path, operation))),
+        ];
+
+        let query: Vec<(String, String)> = operation.query_params.iter().map(|p| (p.name.clone(), query_value(p))).collect();
+        if !query.is_empty() {
+            fields.push(("query", go_string_map(&query)));
+        }
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+
+        match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                let fields_and_files = form_fields(body);
+                let form: Vec<(String, String)> = fields_and_files
+                    .iter()
+                    .filter_map(|(name, value)| Some((name.clone(), value.clone()?)))
+                    .collect();
+                let files: Vec<String> = fields_and_files
+                    .iter()
+                    .filter(|(_, value)| value.is_none())
+                    .map(|(name, _)| go_string(name))
+                    .collect();
+                fields.push(("form", go_string_map(&form)));
+                fields.push(("files", format!("[]string{{{}}}", files.join(", "))));
+                fields.push(("multipart", "true".to_string()));
+            },
+            Some((body, BodyEncoding::Form)) => {
+                let form: Vec<(String, String)> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| (name, value.unwrap_or_default()))
+                    .collect();
+                fields.push(("form", go_string_map(&form)));
+            },
+            Some((body, BodyEncoding::Raw)) => fields.push(("body", go_string(&raw_body_example(body)))),
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                fields.push(("body", go_string(&serde_json::to_string(&example).unwrap_or_default())));
+                if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Content-Type")) {
+                    headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                }
+            },
+            None => {},
+        }
+
+        if !headers.is_empty() {
+            fields.push(("headers", go_string_map(&headers)));
+        }
+        fields.push(("wantStatus", expected_status(operation).to_string()));
+
+        let want_headers = expected_headers(operation);
+        if !want_headers.is_empty() {
+            let names: Vec<String> = want_headers.iter().map(|name| go_string(name)).collect();
+            fields.push(("wantHeaders", format!("[]string{{{}}}", names.join(", "))));
+        }
+
+        // Deprecated operations are still generated, but skipped
+        if operation.deprecated {
+            fields.push(("skip", go_string("deprecated operation")));
+        }
+
+        // Align the values the way gofmt does
+        let width = fields.iter().map(|(key, _)| key.len() + 1).max().unwrap_or(0);
+        let lines: Vec<String> = fields
+            .iter()
+            .map(|(key, value)| format!("\t\t\t{:width$} {},", format!("{}:", key), value, width = width))
+            .collect();
+        format!("\t\t{{\n{}\n\t\t}},", lines.join("\n"))
+    }
+}
+
+impl TestGenerator for GoGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // Go tests are split into one file per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let cases = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_test_case(operation, path, variant)
+        })?;
+
+        for (group, cases) in groups.iter().zip(cases) {
+            let mut file = File::create(output_dir.join(format!("{}_test.go", group.file_stem)))?;
+
+            writeln!(file, r#"package apitests
+
+import "testing"
+
+// Test{name} tests {description}
+func Test{name}(t *testing.T) {{
+	runAPITests(t, []apiTest{{
+{cases}
+	}})
+}}"#,
+                name = pascal_case(&group.file_stem),
+                description = group.description,
+                cases = cases.join("\n"))?;
+        }
+
+        // The helpers shared by every test file
+        let mut helpers = File::create(output_dir.join("helpers_test.go"))?;
+
+        writeln!(helpers, r#"package apitests
+
+import (
+	"bytes"
+	"io"
+	"mime/multipart"
+	"net/http"
+	"net/url"
+	"os"
+	"strings"
+	"testing"
+)
+
+// baseURL is where the API under test runs, overridden by the API_BASE_URL environment variable
+var baseURL = func() string {{
+	if value := os.Getenv("API_BASE_URL"); value != "" {{
+		return strings.TrimRight(value, "/")
+	}}
+	return {base_url}
+}}()
+
+// apiTest is a request to send and the response it should get
+type apiTest struct {{
+	name        string
+	method      string
+	path        string
+	query       map[string]string
+	headers     map[string]string
+	body        string
+	form        map[string]string
+	files       []string
+	multipart   bool
+	wantStatus  int
+	wantHeaders []string
+	skip        string
+}}
+
+// runAPITests sends the request of every test, each in a subtest of its own
+func runAPITests(t *testing.T, tests []apiTest) {{
+	client := &http.Client{{}}
+
+	for _, tt := range tests {{
+		t.Run(tt.name, func(t *testing.T) {{
+			if tt.skip != "" {{
+				t.Skip(tt.skip)
+			}}
+
+			req, err := newRequest(tt)
+			if err != nil {{
+				t.Fatalf("building the request: %v", err)
+			}}
+			resp, err := client.Do(req)
+			if err != nil {{
+				t.Fatalf("sending the request: %v", err)
+			}}
+			defer resp.Body.Close()
+
+			if resp.StatusCode != tt.wantStatus {{
+				body, _ := io.ReadAll(resp.Body)
+				t.Fatalf("%s %s: got status %d, want %d\n%s", tt.method, tt.path, resp.StatusCode, tt.wantStatus, body)
+			}}
+			for _, header := range tt.wantHeaders {{
+				if resp.Header.Get(header) == "" {{
+					t.Errorf("%s %s: response header %s is missing", tt.method, tt.path, header)
+				}}
+			}}
+		}})
+	}}
+}}
+
+// newRequest builds the HTTP request of a test, encoding its body
+func newRequest(tt apiTest) (*http.Request, error) {{
+	query := url.Values{{}}
+	for name, value := range tt.query {{
+		query.Set(name, value)
+	}}
+	target := baseURL + tt.path
+	if len(query) > 0 {{
+		target += "?" + query.Encode()
+	}}
+
+	var body io.Reader
+	contentType := ""
+	switch {{
+	case tt.multipart:
+		buffer := &bytes.Buffer{{}}
+		writer := multipart.NewWriter(buffer)
+		for name, value := range tt.form {{
+			if err := writer.WriteField(name, value); err != nil {{
+				return nil, err
+			}}
+		}}
+		for _, name := range tt.files {{
+			part, err := writer.CreateFormFile(name, "upload.txt")
+			if err != nil {{
+				return nil, err
+			}}
+			if _, err := part.Write([]byte("test file content")); err != nil {{
+				return nil, err
+			}}
+		}}
+		if err := writer.Close(); err != nil {{
+			return nil, err
+		}}
+		body, contentType = buffer, writer.FormDataContentType()
+	case tt.form != nil:
+		form := url.Values{{}}
+		for name, value := range tt.form {{
+			form.Set(name, value)
+		}}
+		body, contentType = strings.NewReader(form.Encode()), "application/x-www-form-urlencoded"
+	case tt.body != "":
+		body = strings.NewReader(tt.body)
+	}}
+
+	req, err := http.NewRequest(tt.method, target, body)
+	if err != nil {{
+		return nil, err
+	}}
+	if contentType != "" {{
+		req.Header.Set("Content-Type", contentType)
+	}}
+	for name, value := range tt.headers {{
+		req.Header.Set(name, value)
+	}}
+	return req, nil
+}}"#,
+            base_url = go_string(base_url.trim_end_matches('/')))?;
+
+        // Create a go.mod for the test module, which only needs the standard library
+        let mut go_mod = File::create(output_dir.join("go.mod"))?;
+
+        writeln!(go_mod, "module {}\n\ngo 1.21", spec.info.package_name("-", "tests"))?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}).
+
+## Running the tests
+
+The tests only use Go's standard library. Run them against the API at `API_BASE_URL`:
+
+```
+API_BASE_URL=http://localhost:3000 go test -v ./...
+```
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// Render text as a Go string literal. JSON string escapes are valid in Go's interpreted strings.
+fn go_string(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
+
+/// Render name/value pairs as a Go `map[string]string` literal. Go rejects repeated keys, so only the first of them is kept.
+fn go_string_map(entries: &[(String, String)]) -> String {
+    let entries: Vec<String> = entries
+        .iter()
+        .enumerate()
+        .filter(|(index, (name, _))| !entries[..*index].iter().any(|(earlier, _)| earlier == name))
+        .map(|(_, (name, value))| format!("{}: {}", go_string(name), go_string(value)))
+        .collect();
+    format!("map[string]string{{{}}}", entries.join(", "))
+}
@@ -0,0 +1,157 @@
+// This file contains the HTTP file generator, writing `.http` files of named requests for the REST Client extension of VS Code.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, percent_encode, query_string, raw_body_example, request_body_example,
+    request_headers, variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{generate_in_parallel, GeneratorOptions, Result, TestGenerator};
+
+/// Boundary separating the parts of multipart request bodies
+pub const MULTIPART_BOUNDARY: &str = "----SwaggerTestGeneratorBoundary";
+
+// VS Code REST Client request file generator
+pub struct HttpFileGenerator {
+    options: GeneratorOptions,
+}
+
+impl HttpFileGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        HttpFileGenerator { options }
+    }
+
+    /// Generate the named request for one request of an operation, with its body taken from `variant` when given
+    fn generate_request(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_uppercase();
+        let title = operation.summary.clone().unwrap_or_else(|| format!("{} {}", method, path));
+
+        let mut lines = vec![format!("### {}", title.replace('\n', " "))];
+        if operation.deprecated {
+            lines.push("# Deprecated".to_string());
+        }
+        lines.push(format!("# @name {}{}", operation.operation_id, variant_suffix(variant)));
+        lines.push(format!("{} {{{{baseUrl}}}}{}{}", method, example_path(path, operation), query_string(operation)));
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+
+        let body = match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Content-Type"));
+                headers.push(("Content-Type".to_string(), format!("multipart/form-data; boundary={}", MULTIPART_BOUNDARY)));
+                let mut parts = Vec::new();
+                for (name, value) in form_fields(body) {
+                    parts.push(format!("--{}", MULTIPART_BOUNDARY));
+                    match value {
+                        Some(value) => {
+                            parts.push(format!("Content-Disposition: form-data; name=\"{}\"", name));
+                            parts.push(String::new());
+                            parts.push(value);
+                        },
+                        // REST Client reads the file from next to the request file
+                        None => {
+                            parts.push(format!("Content-Disposition: form-data; name=\"{}\"; filename=\"upload.txt\"", name));
+                            parts.push("Content-Type: text/plain".to_string());
+                            parts.push(String::new());
+                            parts.push("< ./upload.txt".to_string());
+                        },
+                    }
+                }
+                parts.push(format!("--{}--", MULTIPART_BOUNDARY));
+                Some(parts.join("\n"))
+            },
+            Some((body, BodyEncoding::Form)) => {
+                if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Content-Type")) {
+                    headers.push(("Content-Type".to_string(), "application/x-www-form-urlencoded".to_string()));
+                }
+                let fields: Vec<String> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| format!("{}={}", percent_encode(&name), percent_encode(&value.unwrap_or_default())))
+                    .collect();
+                Some(fields.join("\n&"))
+            },
+            Some((body, BodyEncoding::Raw)) => Some(raw_body_example(body)),
+            Some((_, BodyEncoding::Json)) => {
+                if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Content-Type")) {
+                    headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                }
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                Some(serde_json::to_string_pretty(&example).unwrap_or_default())
+            },
+            None => None,
+        };
+
+        lines.extend(headers.iter().map(|(name, value)| format!("{}: {}", name, value)));
+        if let Some(body) = body {
+            lines.push(String::new());
+            lines.push(body);
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl TestGenerator for HttpFileGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // One file per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let requests = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_request(operation, path, variant)
+        })?;
+
+        for (group, requests) in groups.iter().zip(requests) {
+            let mut file = File::create(output_dir.join(format!("{}.http", group.file_stem)))?;
+
+            writeln!(file, r#"# {title}
+
+@baseUrl = {base_url}
+
+{requests}"#,
+                title = group.title(),
+                base_url = base_url.trim_end_matches('/'),
+                requests = requests.join("\n\n"))?;
+        }
+
+        // The file multipart requests upload
+        let uploads = groups
+            .iter()
+            .flat_map(|group| &group.operations)
+            .filter_map(|(_, operation)| operation.body_param.as_ref())
+            .any(|body| body_encoding(body) == BodyEncoding::Multipart);
+        if uploads {
+            fs::write(output_dir.join("upload.txt"), "test file content")?;
+        }
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Requests
+
+Example requests for {title} (version {version}), one `.http` file {split}.
+
+## Sending the requests
+
+Open a file in VS Code with the [REST Client](https://marketplace.visualstudio.com/items?itemName=humao.rest-client) extension installed,
+and click `Send Request` above a request. Change `@baseUrl` at the top of a file to send its requests to another server.
+"#,
+            title = spec.info.title,
+            version = spec.info.version,
+            split = options.layout.unwrap_or(OutputLayout::PerTag).split())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,216 @@
+// This file contains the Hurl test generator, writing a plain-text Hurl file per tag (or per the chosen layout) that the `hurl` CLI runs on its own.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, PrimitiveType, Schema, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, example_for_parameter, example_path, form_fields, query_value, raw_body_example, request_body_example, request_headers,
+    to_plain_text, variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{
+    expected_headers, expected_status, generate_in_parallel, required_response_properties, response_schema, GeneratorOptions, Result, TestGenerator,
+};
+
+// Hurl file generator
+pub struct HurlGenerator {
+    options: GeneratorOptions,
+}
+
+impl HurlGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        HurlGenerator { options }
+    }
+
+    /// Generate the entry for one request of an operation, with its body taken from `variant` when given
+    fn generate_entry(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_uppercase();
+        let title = match &operation.summary {
+            Some(summary) => summary.clone(),
+            None => format!("{} {}", method, path),
+        };
+
+        let mut lines = vec![
+            format!("# {}{}", operation.operation_id, variant_suffix(variant)),
+            format!("# {}", title.replace('\n', " ")),
+            format!("{} {{{{base_url}}}}{}", method, example_path(path, operation)),
+        ];
+        for (name, value) in request_headers(operation, &self.options.headers) {
+            lines.push(format!("{}: {}", name, hurl_value(&value)));
+        }
+
+        // Sections come after the headers, in the order Hurl expects them
+        if !operation.query_params.is_empty() {
+            lines.push("[QueryStringParams]".to_string());
+            for param in &operation.query_params {
+                lines.push(format!("{}: {}", param.name, hurl_value(&query_value(param))));
+            }
+        }
+
+        let encoding = operation.body_param.as_ref().map(|body| (body, body_encoding(body)));
+        match encoding {
+            Some((body, BodyEncoding::Multipart)) => {
+                lines.push("[MultipartFormData]".to_string());
+                for (name, value) in form_fields(body) {
+                    lines.push(match value {
+                        Some(value) => format!("{}: {}", name, hurl_value(&value)),
+                        None => format!("{}: file,upload.txt; text/plain", name),
+                    });
+                }
+            },
+            Some((body, BodyEncoding::Form)) => {
+                lines.push("[FormParams]".to_string());
+                for (name, value) in form_fields(body) {
+                    lines.push(format!("{}: {}", name, hurl_value(&value.unwrap_or_default())));
+                }
+            },
+            _ => {},
+        }
+
+        if !operation.cookie_params.is_empty() {
+            lines.push("[Cookies]".to_string());
+            for param in &operation.cookie_params {
+                lines.push(format!("{}: {}", param.name, hurl_value(&to_plain_text(&example_for_parameter(param)))));
+            }
+        }
+
+        // Bodies follow the sections. Hurl sends JSON bodies with a JSON content type.
+        match encoding {
+            Some((body, BodyEncoding::Raw)) => lines.push(format!("```\n{}\n```", raw_body_example(body))),
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                lines.push(serde_json::to_string_pretty(&example).unwrap_or_default());
+            },
+            _ => {},
+        }
+
+        // Assertions on the response
+        lines.push(String::new());
+        lines.push(format!("HTTP {}", expected_status(operation)));
+        let mut asserts: Vec<String> = expected_headers(operation)
+            .into_iter()
+            .map(|header| format!("header {} exists", hurl_string(header)))
+            .collect();
+        if let Some(schema) = response_schema(operation) {
+            asserts.extend(json_asserts(schema));
+        }
+        if !asserts.is_empty() {
+            lines.push("[Asserts]".to_string());
+            lines.extend(asserts);
+        }
+
+        // Deprecated operations are left in as comments, so that they don't run
+        if operation.deprecated {
+            let commented: Vec<String> = lines
+                .join("\n")
+                .lines()
+                .map(|line| if line.starts_with('#') { line.to_string() } else { format!("# {}", line).trim_end().to_string() })
+                .collect();
+            return format!("# Deprecated: left out of the run\n{}", commented.join("\n"));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl TestGenerator for HurlGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // One file per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let entries = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_entry(operation, path, variant)
+        })?;
+
+        for (group, entries) in groups.iter().zip(entries) {
+            let mut file = File::create(output_dir.join(format!("{}.hurl", group.file_stem)))?;
+
+            writeln!(file, "# {}\n\n{}", group.title(), entries.join("\n\n\n"))?;
+        }
+
+        // The file multipart requests upload
+        let uploads = groups
+            .iter()
+            .flat_map(|group| &group.operations)
+            .filter_map(|(_, operation)| operation.body_param.as_ref())
+            .any(|body| body_encoding(body) == BodyEncoding::Multipart);
+        if uploads {
+            fs::write(output_dir.join("upload.txt"), "test file content")?;
+        }
+
+        // The variables the entries use, which `--variable` or `HURL_base_url` override
+        let mut variables_file = File::create(output_dir.join("vars.env"))?;
+
+        writeln!(variables_file, "base_url={}", base_url.trim_end_matches('/'))?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), as Hurl files.
+
+## Running the tests
+
+Run the files against the API at the `base_url` that `vars.env` sets:
+
+```
+hurl --test --variables-file vars.env *.hurl
+```
+
+Pass `--variable base_url=http://localhost:3000` (or set `HURL_base_url`) to test another server.
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// `jsonpath` asserts checking the response body: its type, and for objects the presence of the required properties
+fn json_asserts(schema: &Schema) -> Vec<String> {
+    if schema.info().is_some_and(|info| info.nullable) {
+        return Vec::new();
+    }
+
+    match schema {
+        Schema::Object(_) => {
+            let mut asserts = vec!["jsonpath \"$\" isCollection".to_string()];
+            for property in required_response_properties(schema) {
+                let path = format!("$['{}']", property.replace('\\', "\\\\").replace('\'', "\\'"));
+                asserts.push(format!("jsonpath {} exists", hurl_string(&path)));
+            }
+            asserts
+        },
+        Schema::Array(_) => vec!["jsonpath \"$\" isCollection".to_string()],
+        Schema::Primitive(primitive) => match primitive.kind {
+            PrimitiveType::String => vec!["jsonpath \"$\" isString".to_string()],
+            PrimitiveType::Integer => vec!["jsonpath \"$\" isInteger".to_string()],
+            PrimitiveType::Boolean => vec!["jsonpath \"$\" isBoolean".to_string()],
+            // Numbers may be sent as integers or floats
+            PrimitiveType::Number => vec!["jsonpath \"$\" exists".to_string()],
+        },
+        Schema::Ref(_) | Schema::Composite(_) | Schema::Any(_) => Vec::new(),
+    }
+}
+
+/// Render text as the unquoted value of a header or section entry, escaping what Hurl would otherwise read as a comment or template
+fn hurl_value(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('#', "\\#")
+        .replace('{', "\\{")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Render text as a double-quoted Hurl string
+fn hurl_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
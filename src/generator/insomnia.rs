@@ -0,0 +1,197 @@
+// This file contains the Insomnia export generator, writing a v4 export with a request group per tag (or per the chosen layout) and a request per operation.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use crate::parser::{ApiOperation, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, body_variants, cookie_header, example_path, form_fields, query_value, raw_body_example, request_body_example,
+    request_headers, variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{GeneratorOptions, Result, TestGenerator};
+
+// Insomnia export generator
+pub struct InsomniaGenerator {
+    options: GeneratorOptions,
+}
+
+impl InsomniaGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        InsomniaGenerator { options }
+    }
+
+    /// Generate the request resource for one request of an operation, with its body taken from `variant` when given
+    fn generate_request(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>, parent_id: &str) -> Value {
+        let method = operation.method.to_uppercase();
+        let name = match (&operation.summary, variant) {
+            (Some(summary), Some(variant)) => format!("{} ({})", summary, variant.name),
+            (Some(summary), None) => summary.clone(),
+            (None, Some(variant)) => format!("{} ({})", operation.operation_id, variant.name),
+            (None, None) => operation.operation_id.clone(),
+        };
+        let mut description = format!("`{}` {} {}", operation.operation_id, method, path);
+        if operation.deprecated {
+            description.push_str("\n\n**Deprecated**");
+        }
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+
+        let content_type = |headers: &mut Vec<(String, String)>, mime_type: &str| {
+            if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Content-Type")) {
+                headers.push(("Content-Type".to_string(), mime_type.to_string()));
+            }
+        };
+        let body = match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((param, BodyEncoding::Multipart)) => {
+                // Insomnia sets the boundary itself
+                headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Content-Type"));
+                headers.push(("Content-Type".to_string(), "multipart/form-data".to_string()));
+                let params: Vec<Value> = form_fields(param)
+                    .into_iter()
+                    .map(|(name, value)| match value {
+                        Some(value) => json!({ "name": name, "value": value }),
+                        None => json!({ "name": name, "type": "file", "fileName": "upload.txt" }),
+                    })
+                    .collect();
+                json!({ "mimeType": "multipart/form-data", "params": params })
+            },
+            Some((param, BodyEncoding::Form)) => {
+                content_type(&mut headers, "application/x-www-form-urlencoded");
+                let params: Vec<Value> = form_fields(param)
+                    .into_iter()
+                    .map(|(name, value)| json!({ "name": name, "value": value.unwrap_or_default() }))
+                    .collect();
+                json!({ "mimeType": "application/x-www-form-urlencoded", "params": params })
+            },
+            Some((param, BodyEncoding::Raw)) => {
+                let mime_type = param.content_type.clone().unwrap_or_else(|| "text/plain".to_string());
+                content_type(&mut headers, &mime_type);
+                json!({ "mimeType": mime_type, "text": raw_body_example(param) })
+            },
+            Some((_, BodyEncoding::Json)) => {
+                content_type(&mut headers, "application/json");
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                json!({ "mimeType": "application/json", "text": serde_json::to_string_pretty(&example).unwrap_or_default() })
+            },
+            None => json!({}),
+        };
+
+        let parameters: Vec<Value> = operation.query_params
+            .iter()
+            .map(|param| json!({ "name": param.name, "value": query_value(param) }))
+            .collect();
+        let headers: Vec<Value> = headers
+            .into_iter()
+            .map(|(name, value)| json!({ "name": name, "value": value }))
+            .collect();
+
+        json!({
+            "_id": format!("req_{}{}", operation.operation_id, variant_suffix(variant)),
+            "_type": "request",
+            "parentId": parent_id,
+            "name": name,
+            "description": description,
+            "method": method,
+            "url": format!("{{{{ _.base_url }}}}{}", example_path(path, operation)),
+            "parameters": parameters,
+            "headers": headers,
+            "body": body,
+        })
+    }
+}
+
+impl TestGenerator for InsomniaGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // IDs are derived from the API and operation names, so importing a regenerated export updates the requests in place
+        let workspace_id = format!("wrk_{}", resource_name(&spec.info.title));
+        let mut resources = vec![
+            json!({
+                "_id": workspace_id,
+                "_type": "workspace",
+                "parentId": null,
+                "name": spec.info.title,
+                "description": format!("Generated API requests for {} (version {})", spec.info.title, spec.info.version),
+                "scope": "collection",
+            }),
+            json!({
+                "_id": format!("env_{}", resource_name(&spec.info.title)),
+                "_type": "environment",
+                "parentId": workspace_id,
+                "name": "Base Environment",
+                "data": { "base_url": base_url.trim_end_matches('/') },
+            }),
+        ];
+
+        // A request group per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let mut uploads = false;
+        for group in operation_groups(spec, &options) {
+            let group_id = format!("fld_{}", group.file_stem);
+            resources.push(json!({
+                "_id": group_id,
+                "_type": "request_group",
+                "parentId": workspace_id,
+                "name": group.name,
+            }));
+
+            for (path, operation) in &group.operations {
+                uploads |= operation.body_param.as_ref().is_some_and(|body| body_encoding(body) == BodyEncoding::Multipart);
+                for variant in body_variants(operation) {
+                    resources.push(self.generate_request(operation, path, variant, &group_id));
+                }
+            }
+        }
+
+        let export = json!({
+            "_type": "export",
+            "__export_format": 4,
+            "__export_source": "swagger-test-generator",
+            "resources": resources,
+        });
+        fs::write(output_dir.join("insomnia.json"), serde_json::to_string_pretty(&export).unwrap_or_default() + "\n")?;
+
+        // The file multipart requests upload, picked again in Insomnia after the import
+        if uploads {
+            fs::write(output_dir.join("upload.txt"), "test file content")?;
+        }
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Requests
+
+Generated Insomnia requests for {title} (version {version}), with a folder {split} and example request bodies.
+
+## Using the requests
+
+Import `insomnia.json` in Insomnia with **Import** > **From File**. The requests are sent to the `base_url` variable of the
+base environment, which can be changed under **Manage Environments**.
+
+File uploads refer to `upload.txt`, which has to be selected again in the request body after importing.
+"#,
+            title = spec.info.title,
+            version = spec.info.version,
+            split = options.layout.unwrap_or(OutputLayout::PerTag).split())?;
+
+        Ok(())
+    }
+}
+
+/// Part of a resource ID made of the name's letters and digits, in lowercase, with any other characters as underscores
+fn resource_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    name.trim_matches('_').to_string()
+}
@@ -0,0 +1,296 @@
+// This file contains the JMeter test plan generator, writing a `.jmx` plan with a thread group running an HTTP sampler per operation.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use reqwest::Url;
+use serde_json::Value;
+use crate::parser::{ApiOperation, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, query_string, raw_body_example, request_body_example, request_headers,
+    variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{generate_in_parallel, success_statuses, xml_escape, GeneratorOptions, Result, TestGenerator};
+
+/// Threads started when the options don't say
+const DEFAULT_THREADS: u32 = 10;
+
+/// How long the test plan runs when the options don't say
+const DEFAULT_DURATION: Duration = Duration::from_secs(30);
+
+/// File multipart samplers upload, written next to the test plan
+const UPLOAD_FILE: &str = "upload.txt";
+
+// JMeter test plan generator
+pub struct JmeterGenerator {
+    options: GeneratorOptions,
+}
+
+impl JmeterGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        JmeterGenerator { options }
+    }
+
+    /// Generate the sampler for one request of an operation, with its body taken from `variant` when given,
+    /// followed by the hash tree of its header manager and response code assertion
+    fn generate_sampler(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let name = format!("{}{}", operation.operation_id, variant_suffix(variant));
+        let target = format!("${{basePath}}{}{}", example_path(path, operation), query_string(operation));
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+
+        let mut props = vec![
+            string_prop("HTTPSampler.path", &target),
+            string_prop("HTTPSampler.method", &operation.method.to_uppercase()),
+            "<boolProp name=\"HTTPSampler.follow_redirects\">true</boolProp>".to_string(),
+            "<boolProp name=\"HTTPSampler.use_keepalive\">true</boolProp>".to_string(),
+        ];
+        let arguments = match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                let fields = form_fields(body);
+                let files: Vec<String> = fields
+                    .iter()
+                    .filter(|(_, value)| value.is_none())
+                    .map(|(name, _)| format!(
+                        "<elementProp name=\"{file}\" elementType=\"HTTPFileArg\">\n  {}\n  {}\n  {}\n</elementProp>",
+                        string_prop("File.path", UPLOAD_FILE),
+                        string_prop("File.paramname", name),
+                        string_prop("File.mimetype", "text/plain"),
+                        file = UPLOAD_FILE,
+                    ))
+                    .collect();
+                props.push("<boolProp name=\"HTTPSampler.DO_MULTIPART_POST\">true</boolProp>".to_string());
+                props.push(format!(
+                    "<elementProp name=\"HTTPsampler.Files\" elementType=\"HTTPFileArgs\">\n  <collectionProp name=\"HTTPFileArgs.files\">\n{}\n  </collectionProp>\n</elementProp>",
+                    indent(&files.join("\n"), 4)
+                ));
+                fields.into_iter().filter_map(|(name, value)| Some((name, value?))).collect()
+            },
+            Some((body, BodyEncoding::Form)) => form_fields(body)
+                .into_iter()
+                .map(|(name, value)| (name, value.unwrap_or_default()))
+                .collect(),
+            Some((body, BodyEncoding::Raw)) => {
+                props.push("<boolProp name=\"HTTPSampler.postBodyRaw\">true</boolProp>".to_string());
+                vec![(String::new(), raw_body_example(body))]
+            },
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Content-Type")) {
+                    headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                }
+                props.push("<boolProp name=\"HTTPSampler.postBodyRaw\">true</boolProp>".to_string());
+                vec![(String::new(), serde_json::to_string(&example).unwrap_or_default())]
+            },
+            None => Vec::new(),
+        };
+        let arguments: Vec<String> = arguments
+            .iter()
+            .map(|(name, value)| format!(
+                "<elementProp name=\"{}\" elementType=\"HTTPArgument\">\n  <boolProp name=\"HTTPArgument.always_encode\">{}</boolProp>\n  {}\n  {}\n  <stringProp name=\"Argument.metadata\">=</stringProp>\n</elementProp>",
+                xml_escape(name),
+                !name.is_empty(),
+                string_prop("Argument.name", name),
+                string_prop("Argument.value", value),
+            ))
+            .collect();
+        props.push(format!(
+            "<elementProp name=\"HTTPsampler.Arguments\" elementType=\"Arguments\">\n  <collectionProp name=\"Arguments.arguments\">{}</collectionProp>\n</elementProp>",
+            if arguments.is_empty() { String::new() } else { format!("\n{}\n  ", indent(&arguments.join("\n"), 4)) }
+        ));
+
+        // The sampler's hash tree holds its header manager and assertion
+        let mut children = Vec::new();
+        if !headers.is_empty() {
+            let headers: Vec<String> = headers
+                .iter()
+                .map(|(name, value)| format!(
+                    "<elementProp name=\"\" elementType=\"Header\">\n  {}\n  {}\n</elementProp>",
+                    string_prop("Header.name", name),
+                    string_prop("Header.value", value)
+                ))
+                .collect();
+            children.push(format!(
+                "<HeaderManager guiclass=\"HeaderPanel\" testclass=\"HeaderManager\" testname=\"HTTP Header Manager\">\n  <collectionProp name=\"HeaderManager.headers\">\n{}\n  </collectionProp>\n</HeaderManager>\n<hashTree/>",
+                indent(&headers.join("\n"), 4)
+            ));
+        }
+
+        // Any documented successful status passes (test type 40: equals, or'ed)
+        let statuses: Vec<String> = success_statuses(operation)
+            .iter()
+            .map(|status| format!("    <stringProp name=\"{status}\">{status}</stringProp>", status = status))
+            .collect();
+        children.push(format!(
+            r#"<ResponseAssertion guiclass="AssertionGui" testclass="ResponseAssertion" testname="Response Code Assertion">
+  <collectionProp name="Asserion.test_strings">
+{}
+  </collectionProp>
+  <stringProp name="Assertion.custom_message"></stringProp>
+  <stringProp name="Assertion.test_field">Assertion.response_code</stringProp>
+  <boolProp name="Assertion.assume_success">true</boolProp>
+  <intProp name="Assertion.test_type">40</intProp>
+</ResponseAssertion>
+<hashTree/>"#,
+            statuses.join("\n")
+        ));
+
+        // Deprecated operations are still generated, but disabled
+        format!(
+            "<HTTPSamplerProxy guiclass=\"HttpTestSampleGui\" testclass=\"HTTPSamplerProxy\" testname=\"{}\" enabled=\"{}\">\n{}\n</HTTPSamplerProxy>\n<hashTree>\n{}\n</hashTree>",
+            xml_escape(&name),
+            !operation.deprecated,
+            indent(&props.join("\n"), 2),
+            indent(&children.join("\n"), 2)
+        )
+    }
+}
+
+impl TestGenerator for JmeterGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // The samplers of each group's operations are kept together in a simple controller, a group per tag unless another
+        // layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let samplers = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_sampler(operation, path, variant)
+        })?;
+
+        let controllers: Vec<String> = groups
+            .iter()
+            .zip(samplers)
+            .map(|(group, samplers)| format!(
+                "<GenericController guiclass=\"LogicControllerGui\" testclass=\"GenericController\" testname=\"{}\"/>\n<hashTree>\n{}\n</hashTree>",
+                xml_escape(&group.name),
+                indent(&samplers.join("\n"), 2)
+            ))
+            .collect();
+
+        // The server defaults to the base URL's, and can be overridden with JMeter properties (`-Jhost=...`)
+        let url = Url::parse(base_url).ok();
+        let protocol = url.as_ref().map_or("http", |url| url.scheme()).to_string();
+        let host = url.as_ref().and_then(|url| url.host_str().map(str::to_string)).unwrap_or_else(|| "localhost".to_string());
+        let port = url.as_ref().and_then(Url::port_or_known_default).map(|port| port.to_string()).unwrap_or_default();
+        let base_path = url.as_ref().map_or(String::new(), |url| url.path().trim_end_matches('/').to_string());
+
+        let threads = self.options.virtual_users.unwrap_or(DEFAULT_THREADS);
+        let duration = self.options.duration.unwrap_or(DEFAULT_DURATION).as_secs();
+
+        let mut plan_file = File::create(output_dir.join("test-plan.jmx"))?;
+
+        writeln!(plan_file, r#"<?xml version="1.0" encoding="UTF-8"?>
+<jmeterTestPlan version="1.2" properties="5.0" jmeter="5.6.3">
+  <hashTree>
+    <TestPlan guiclass="TestPlanGui" testclass="TestPlan" testname="{title}">
+      <elementProp name="TestPlan.user_defined_variables" elementType="Arguments" guiclass="ArgumentsPanel" testclass="Arguments" testname="User Defined Variables">
+        <collectionProp name="Arguments.arguments">
+          <elementProp name="basePath" elementType="Argument">
+            <stringProp name="Argument.name">basePath</stringProp>
+            <stringProp name="Argument.value">${{__P(basePath,{base_path})}}</stringProp>
+            <stringProp name="Argument.metadata">=</stringProp>
+          </elementProp>
+        </collectionProp>
+      </elementProp>
+      <boolProp name="TestPlan.functional_mode">false</boolProp>
+      <boolProp name="TestPlan.serialize_threadgroups">false</boolProp>
+    </TestPlan>
+    <hashTree>
+      <ConfigTestElement guiclass="HttpDefaultsGui" testclass="ConfigTestElement" testname="HTTP Request Defaults">
+        <elementProp name="HTTPsampler.Arguments" elementType="Arguments" guiclass="HTTPArgumentsPanel" testclass="Arguments" testname="User Defined Variables">
+          <collectionProp name="Arguments.arguments"/>
+        </elementProp>
+        <stringProp name="HTTPSampler.protocol">${{__P(protocol,{protocol})}}</stringProp>
+        <stringProp name="HTTPSampler.domain">${{__P(host,{host})}}</stringProp>
+        <stringProp name="HTTPSampler.port">${{__P(port,{port})}}</stringProp>
+      </ConfigTestElement>
+      <hashTree/>
+      <ThreadGroup guiclass="ThreadGroupGui" testclass="ThreadGroup" testname="API Users">
+        <stringProp name="ThreadGroup.num_threads">${{__P(threads,{threads})}}</stringProp>
+        <stringProp name="ThreadGroup.ramp_time">${{__P(rampUp,{threads})}}</stringProp>
+        <stringProp name="ThreadGroup.on_sample_error">continue</stringProp>
+        <elementProp name="ThreadGroup.main_controller" elementType="LoopController" guiclass="LoopControlPanel" testclass="LoopController" testname="Loop Controller">
+          <boolProp name="LoopController.continue_forever">false</boolProp>
+          <intProp name="LoopController.loops">-1</intProp>
+        </elementProp>
+        <boolProp name="ThreadGroup.scheduler">true</boolProp>
+        <stringProp name="ThreadGroup.duration">${{__P(duration,{duration})}}</stringProp>
+        <stringProp name="ThreadGroup.delay"></stringProp>
+        <boolProp name="ThreadGroup.same_user_on_next_iteration">true</boolProp>
+      </ThreadGroup>
+      <hashTree>
+{controllers}
+      </hashTree>
+    </hashTree>
+  </hashTree>
+</jmeterTestPlan>"#,
+            title = xml_escape(&spec.info.title),
+            base_path = xml_escape(&base_path),
+            protocol = xml_escape(&protocol),
+            host = xml_escape(&host),
+            controllers = indent(&controllers.join("\n"), 8))?;
+
+        // The file multipart samplers upload
+        if groups.iter().flat_map(|group| &group.operations).any(|(_, operation)| {
+            operation.body_param.as_ref().is_some_and(|body| body_encoding(body) == BodyEncoding::Multipart)
+        }) {
+            fs::write(output_dir.join(UPLOAD_FILE), "test file content")?;
+        }
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Load Tests
+
+Generated JMeter test plan for {title} (version {version}).
+
+## Running the tests
+
+Run the plan without the GUI, writing the results to `results.jtl`:
+
+```
+jmeter -n -t test-plan.jmx -l results.jtl
+```
+
+The API under test and the load are JMeter properties, set with `-J`:
+
+| Property | Default |
+|----------|---------|
+| `protocol` | `{protocol}` |
+| `host` | `{host}` |
+| `port` | `{port}` |
+| `basePath` | `{base_path}` |
+| `threads` | `{threads}` |
+| `rampUp` | `{threads}` (seconds) |
+| `duration` | `{duration}` (seconds) |
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// A JMeter string property. Line breaks are written as character references, so that indenting the plan leaves values alone.
+fn string_prop(name: &str, value: &str) -> String {
+    format!("<stringProp name=\"{}\">{}</stringProp>", name, xml_escape(value).replace('\r', "&#13;").replace('\n', "&#10;"))
+}
+
+/// Indent every line of `text` by `spaces`
+fn indent(text: &str, spaces: usize) -> String {
+    let padding = " ".repeat(spaces);
+    text.lines()
+        .map(|line| if line.is_empty() { String::new() } else { format!("{}{}", padding, line) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,213 @@
+// This file contains the k6 load-test generator, writing a script per tag (or per the chosen layout) with a request function for every operation.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, body_variants, cookie_header, example_path, form_fields, js_string, query_string, raw_body_example,
+    request_body_example, request_headers, to_json_literal, variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{expected_status, generate_in_parallel, success_statuses, GeneratorOptions, Result, TestGenerator};
+
+/// Virtual users simulated when the options don't say
+const DEFAULT_VIRTUAL_USERS: u32 = 10;
+
+/// How long the load test runs when the options don't say
+const DEFAULT_DURATION: Duration = Duration::from_secs(30);
+
+/// Words JavaScript reserves, which request functions named after an operation have to avoid
+const JS_RESERVED_WORDS: &[&str] = &[
+    "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do", "else", "export", "extends", "false",
+    "finally", "for", "function", "if", "import", "in", "instanceof", "new", "null", "return", "super", "switch", "this", "throw", "true",
+    "try", "typeof", "var", "void", "while", "with", "yield", "let", "static", "enum", "await", "implements", "package", "protected",
+    "interface", "private", "public",
+];
+
+// k6 load-test script generator
+pub struct K6Generator {
+    options: GeneratorOptions,
+}
+
+impl K6Generator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        K6Generator { options }
+    }
+
+    /// Generate the request function for one request of an operation, with its body taken from `variant` when given
+    fn generate_request_function(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let name = function_name(operation, variant);
+        let url = format!("{}{}", example_path(path, operation), query_string(operation));
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+
+        // k6 encodes objects as form fields, and as multipart ones when any of them is a file
+        let body = match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                let fields: Vec<String> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| match value {
+                        Some(value) => format!("    {}: {},", js_string(&name), js_string(&value)),
+                        None => format!("    {}: http.file('test file content', 'upload.txt', 'text/plain'),", js_string(&name)),
+                    })
+                    .collect();
+                format!("{{\n{}\n  }}", fields.join("\n"))
+            },
+            Some((body, BodyEncoding::Form)) => {
+                let fields: Vec<String> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| format!("    {}: {},", js_string(&name), js_string(&value.unwrap_or_default())))
+                    .collect();
+                format!("{{\n{}\n  }}", fields.join("\n"))
+            },
+            Some((body, BodyEncoding::Raw)) => js_string(&raw_body_example(body)),
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Content-Type")) {
+                    headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                }
+                format!("JSON.stringify({})", to_json_literal(&example, "  "))
+            },
+            None => "null".to_string(),
+        };
+
+        // Requests answered with any documented successful status don't count as failed
+        let statuses = success_statuses(operation);
+        let mut params = Vec::new();
+        if !headers.is_empty() {
+            let headers: Vec<String> = headers
+                .iter()
+                .map(|(name, value)| format!("      {}: {},", js_string(name), js_string(value)))
+                .collect();
+            params.push(format!("    headers: {{\n{}\n    }},", headers.join("\n")));
+        }
+        params.push(format!("    tags: {{ operation: {} }},", js_string(&name)));
+        params.push(format!("    responseCallback: http.expectedStatuses({}),", statuses.join(", ")));
+
+        let status = expected_status(operation);
+        let deprecated = if operation.deprecated { "\n// Deprecated: left out of the default scenario" } else { "" };
+        format!(
+            r#"/** {method} {path} */{deprecated}
+export function {name}() {{
+  const body = {body};
+  const res = http.request({method_string}, `${{BASE_URL}}{url}`, body, {{
+{params}
+  }});
+  check(res, {{
+    {check_name}: (r) => r.status === {status},
+  }}, {{ operation: {operation} }});
+}}"#,
+            method = operation.method.to_uppercase(),
+            path = path,
+            method_string = js_string(&operation.method.to_uppercase()),
+            url = url.replace('`', "\\`").replace("${", "\\${"),
+            params = params.join("\n"),
+            check_name = js_string(&format!("{} status is {}", name, status)),
+            operation = js_string(&name),
+        )
+    }
+}
+
+impl TestGenerator for K6Generator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // One script per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let functions = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_request_function(operation, path, variant)
+        })?;
+
+        let virtual_users = self.options.virtual_users.unwrap_or(DEFAULT_VIRTUAL_USERS);
+        let duration = self.options.duration.unwrap_or(DEFAULT_DURATION);
+
+        for (group, functions) in groups.iter().zip(functions) {
+            // Every check of the operations the default scenario calls has to pass, each tracked by its own threshold
+            let mut thresholds = vec!["    http_req_failed: ['rate<0.01'],".to_string()];
+            let mut calls = Vec::new();
+            for (_, operation) in group.operations.iter().filter(|(_, operation)| !operation.deprecated) {
+                for variant in body_variants(operation) {
+                    let name = function_name(operation, variant);
+                    thresholds.push(format!("    {}: ['rate==1'],", js_string(&format!("checks{{operation:{}}}", name))));
+                    calls.push(format!("  {}();", name));
+                }
+            }
+
+            let mut file = File::create(output_dir.join(format!("{}.js", group.file_stem)))?;
+
+            writeln!(file, r#"// Load test of {description}
+import http from 'k6/http';
+import {{ check }} from 'k6';
+
+// The API_BASE_URL environment variable (`k6 run -e API_BASE_URL=...`) overrides where the API under test runs
+const BASE_URL = __ENV.API_BASE_URL || {base_url};
+
+export const options = {{
+  vus: {virtual_users},
+  duration: '{duration}s',
+  thresholds: {{
+{thresholds}
+  }},
+}};
+
+{functions}
+
+export default function () {{
+{calls}
+}}"#,
+                description = group.description,
+                base_url = js_string(base_url.trim_end_matches('/')),
+                duration = duration.as_secs(),
+                thresholds = thresholds.join("\n"),
+                functions = functions.join("\n\n"),
+                calls = calls.join("\n"))?;
+        }
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Load Tests
+
+Generated k6 load tests for {title} (version {version}), one script {split}.
+
+## Running the tests
+
+Run a script against the API at `API_BASE_URL`:
+
+```
+k6 run -e API_BASE_URL=http://localhost:3000 {example}.js
+```
+
+Each script runs {virtual_users} virtual users for {duration} seconds; `--vus` and `--duration` override these on the command line.
+The run fails when a response has an undocumented status.
+"#,
+            title = spec.info.title,
+            version = spec.info.version,
+            split = options.layout.unwrap_or(OutputLayout::PerTag).split(),
+            example = groups.first().map_or("default", |group| group.file_stem.as_str()),
+            duration = duration.as_secs())?;
+
+        Ok(())
+    }
+}
+
+/// Name of the request function for one request of an operation, which can't be a JavaScript reserved word
+fn function_name(operation: &ApiOperation, variant: Option<&SchemaVariant>) -> String {
+    let name = format!("{}{}", operation.operation_id, variant_suffix(variant));
+    if JS_RESERVED_WORDS.contains(&name.as_str()) {
+        format!("{}Request", name)
+    } else {
+        name
+    }
+}
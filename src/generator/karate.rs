@@ -0,0 +1,260 @@
+// This file contains the Karate test generator, writing feature files in Karate's Gherkin dialect with a Maven project running them.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, PrimitiveType, Schema, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, example_for_parameter, example_path, form_fields, query_value, raw_body_example, request_body_example, request_headers,
+    to_plain_text, variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{
+    expected_headers, expected_status, generate_in_parallel, required_response_properties, response_schema, xml_escape, GeneratorOptions,
+    Result, TestGenerator,
+};
+
+// Karate DSL feature file generator
+pub struct KarateGenerator {
+    options: GeneratorOptions,
+}
+
+impl KarateGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        KarateGenerator { options }
+    }
+
+    /// Generate the scenario for one request of an operation, with its body taken from `variant` when given
+    fn generate_scenario(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_lowercase();
+        let title = match (&operation.summary, variant) {
+            (Some(summary), Some(variant)) => format!("{} ({})", summary, variant.name),
+            (Some(summary), None) => summary.clone(),
+            (None, Some(variant)) => format!("{} {} ({})", method.to_uppercase(), path, variant.name),
+            (None, None) => format!("{} {}", method.to_uppercase(), path),
+        };
+
+        // Scenarios are tagged with their operation, and deprecated ones with `@ignore`, which Karate skips
+        let mut tags = vec![format!("@{}{}", operation.operation_id, variant_suffix(variant))];
+        if operation.deprecated {
+            tags.push("@ignore".to_string());
+        }
+
+        // Paths are relative to the base URL, which may have a path of its own
+        let mut steps = vec![format!("path {}", karate_string(example_path(path, operation).trim_start_matches('/')))];
+        for param in &operation.query_params {
+            steps.push(format!("param {} = {}", param.name, karate_string(&query_value(param))));
+        }
+        for (name, value) in request_headers(operation, &self.options.headers) {
+            steps.push(format!("header {} = {}", name, karate_string(&value)));
+        }
+        for param in &operation.cookie_params {
+            steps.push(format!("cookie {} = {}", param.name, karate_string(&to_plain_text(&example_for_parameter(param)))));
+        }
+
+        match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                for (name, value) in form_fields(body) {
+                    steps.push(match value {
+                        Some(value) => format!("multipart field {} = {}", name, karate_string(&value)),
+                        None => format!(
+                            "multipart file {} = {{ value: 'test file content', filename: 'upload.txt', contentType: 'text/plain' }}",
+                            name
+                        ),
+                    });
+                }
+            },
+            Some((body, BodyEncoding::Form)) => {
+                for (name, value) in form_fields(body) {
+                    steps.push(format!("form field {} = {}", name, karate_string(&value.unwrap_or_default())));
+                }
+            },
+            Some((body, BodyEncoding::Raw)) => steps.push(format!("request {}", karate_string(&raw_body_example(body)))),
+            Some((_, BodyEncoding::Json)) => {
+                // JSON is valid as-is in Karate's expressions
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                steps.push(format!("request {}", serde_json::to_string(&example).unwrap_or_default()));
+            },
+            None => {},
+        }
+
+        let mut lines: Vec<String> = steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| format!("    {} {}", if index == 0 { "Given" } else { "And" }, step))
+            .collect();
+        lines.push(format!("    When method {}", method));
+        lines.push(format!("    Then status {}", expected_status(operation)));
+
+        // Assertions on the response
+        for header in expected_headers(operation) {
+            lines.push(format!("    And match header {} == '#notnull'", header));
+        }
+        if let Some(schema) = response_schema(operation) {
+            lines.extend(response_matches(schema).into_iter().map(|step| format!("    And {}", step)));
+        }
+
+        format!("  {}\n  Scenario: {}\n{}", tags.join(" "), title, lines.join("\n"))
+    }
+}
+
+impl TestGenerator for KarateGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        let package_dir = output_dir.join("src").join("test").join("java").join("apitests");
+        fs::create_dir_all(&package_dir)?;
+
+        // One feature per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let scenarios = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_scenario(operation, path, variant)
+        })?;
+
+        for (group, scenarios) in groups.iter().zip(scenarios) {
+            let mut file = File::create(package_dir.join(format!("{}.feature", group.file_stem)))?;
+
+            writeln!(file, r#"Feature: {title}
+
+  Background:
+    * url baseUrl
+
+{scenarios}"#,
+                title = group.title(),
+                scenarios = scenarios.join("\n\n"))?;
+        }
+
+        // The configuration Karate reads before every feature, providing the base URL
+        let mut config_file = File::create(output_dir.join("src").join("test").join("java").join("karate-config.js"))?;
+
+        writeln!(config_file, r#"function fn() {{
+  // The API_BASE_URL environment variable overrides where the API under test runs
+  var baseUrl = java.lang.System.getenv('API_BASE_URL') || {base_url};
+  return {{ baseUrl: baseUrl }};
+}}"#,
+            base_url = karate_string(base_url.trim_end_matches('/')))?;
+
+        // The JUnit 5 runner running every feature of the package
+        let mut runner_file = File::create(package_dir.join("ApiTestsRunner.java"))?;
+
+        writeln!(runner_file, r#"package apitests;
+
+import com.intuit.karate.junit5.Karate;
+
+class ApiTestsRunner {{
+
+    @Karate.Test
+    Karate testAll() {{
+        return Karate.run().relativeTo(getClass());
+    }}
+}}"#)?;
+
+        // Create a pom.xml for the Maven project. Karate reads the features from next to the runner.
+        let mut pom_file = File::create(output_dir.join("pom.xml"))?;
+
+        writeln!(pom_file, r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0"
+         xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+         xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd">
+  <modelVersion>4.0.0</modelVersion>
+
+  <groupId>apitests</groupId>
+  <artifactId>{artifact_id}</artifactId>
+  <version>{version}</version>
+  <name>{name}</name>
+
+  <properties>
+    <maven.compiler.release>17</maven.compiler.release>
+    <project.build.sourceEncoding>UTF-8</project.build.sourceEncoding>
+  </properties>
+
+  <dependencies>
+    <dependency>
+      <groupId>io.karatelabs</groupId>
+      <artifactId>karate-junit5</artifactId>
+      <version>1.4.1</version>
+      <scope>test</scope>
+    </dependency>
+  </dependencies>
+
+  <build>
+    <testResources>
+      <testResource>
+        <directory>src/test/java</directory>
+        <excludes>
+          <exclude>**/*.java</exclude>
+        </excludes>
+      </testResource>
+    </testResources>
+    <plugins>
+      <plugin>
+        <groupId>org.apache.maven.plugins</groupId>
+        <artifactId>maven-surefire-plugin</artifactId>
+        <version>3.2.5</version>
+      </plugin>
+    </plugins>
+  </build>
+</project>"#,
+            artifact_id = spec.info.package_name("-", "tests"),
+            version = xml_escape(spec.info.package_version("1.0.0")),
+            name = xml_escape(&format!("{} Tests", spec.info.title)))?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), as Karate features.
+
+## Running the tests
+
+Run the features against the API at `API_BASE_URL`:
+
+```
+API_BASE_URL=http://localhost:3000 mvn test
+```
+
+Scenarios are tagged with their operation ID, so a single one can be run with `-Dkarate.options="--tags @getPet"`.
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// `match` steps checking the response body: its type, and for objects the presence of the required properties
+fn response_matches(schema: &Schema) -> Vec<String> {
+    if schema.info().is_some_and(|info| info.nullable) {
+        return Vec::new();
+    }
+
+    match schema {
+        Schema::Object(_) => {
+            let required: Vec<String> = required_response_properties(schema)
+                .iter()
+                .map(|property| format!("{}: '#present'", serde_json::to_string(property).unwrap_or_default()))
+                .collect();
+            let mut steps = vec!["match response == '#object'".to_string()];
+            if !required.is_empty() {
+                steps.push(format!("match response contains {{ {} }}", required.join(", ")));
+            }
+            steps
+        },
+        Schema::Array(_) => vec!["match response == '#array'".to_string()],
+        Schema::Primitive(primitive) => vec![format!("match response == '{}'", match primitive.kind {
+            PrimitiveType::String => "#string",
+            PrimitiveType::Integer | PrimitiveType::Number => "#number",
+            PrimitiveType::Boolean => "#boolean",
+        })],
+        Schema::Ref(_) | Schema::Composite(_) | Schema::Any(_) => Vec::new(),
+    }
+}
+
+/// Render text as a single-quoted string in Karate's JavaScript expressions
+fn karate_string(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('\'', "\\'").replace('\n', "\\n").replace('\r', "\\r");
+    format!("'{}'", escaped)
+}
@@ -0,0 +1,174 @@
+// This file contains the grouping of operations into the test files (or Postman folders) they are written to.
+
+use crate::parser::{ApiOperation, SwaggerSpec};
+use crate::utils::helpers::glob_matches;
+use super::test_framework::GeneratorOptions;
+
+/// How the tests of the operations are split into files (or Postman folders)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputLayout {
+    /// All of them together, in a group named `api`
+    Single,
+
+    /// One group per tag (see `group_by_tag`)
+    PerTag,
+
+    /// One group per path (see `group_by_path`)
+    PerPath,
+}
+
+impl OutputLayout {
+    /// How tests are split, to describe the output with: `per tag`, `per path` or `for all operations`
+    pub fn split(&self) -> &'static str {
+        match self {
+            OutputLayout::Single => "for all operations",
+            OutputLayout::PerTag => "per tag",
+            OutputLayout::PerPath => "per path",
+        }
+    }
+}
+
+/// Operations written to the same test file or Postman folder
+pub struct OperationGroup<'a> {
+    /// Human readable name, used for Postman folders
+    pub name: String,
+
+    /// Name the group's test file is derived from
+    pub file_stem: String,
+
+    /// What the group holds, to go on in a sentence: `operations tagged pets`, `operations on /pets/{petId}` or
+    /// `all operations`
+    pub description: String,
+
+    /// The operations in the group, each with the path it is declared on
+    pub operations: Vec<(&'a str, &'a ApiOperation)>,
+}
+
+impl OperationGroup<'_> {
+    /// Whether the operation is in the group
+    pub fn contains(&self, operation: &ApiOperation) -> bool {
+        self.operations.iter().any(|(_, op)| op.operation_id == operation.operation_id)
+    }
+
+    /// The description with a capital letter, to title suites and files with: `Operations tagged pets`
+    pub fn title(&self) -> String {
+        let mut chars = self.description.chars();
+        chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+    }
+}
+
+/// The operations to generate tests for, grouped as the layout chosen in `options` says, one group per path when it
+/// chooses none. Operations left out by the options are dropped, along with the groups they leave empty.
+pub fn operation_groups<'a>(spec: &'a SwaggerSpec, options: &GeneratorOptions) -> Vec<OperationGroup<'a>> {
+    let mut groups = match options.layout.unwrap_or(OutputLayout::PerPath) {
+        OutputLayout::Single => vec![single_group(spec)],
+        OutputLayout::PerTag => group_by_tag(spec),
+        OutputLayout::PerPath => group_by_path(spec),
+    };
+
+    for group in &mut groups {
+        group.operations.retain(|(path, operation)| is_included(path, operation, options));
+    }
+    groups.retain(|group| !group.operations.is_empty());
+
+    groups
+}
+
+/// Whether the options keep the operation declared on `path`: it isn't deprecated if they skip those, it has one of
+/// the included tags, its path matches none of the excluded patterns, and its method is one of the chosen ones.
+/// Empty lists of tags and methods keep every operation
+pub fn is_included(path: &str, operation: &ApiOperation, options: &GeneratorOptions) -> bool {
+    !(options.skip_deprecated && operation.deprecated)
+        && (options.include_tags.is_empty()
+            || operation.tags.iter().any(|tag| options.include_tags.iter().any(|included| included.eq_ignore_ascii_case(tag))))
+        && !options.exclude_paths.iter().any(|pattern| glob_matches(pattern, path))
+        && (options.methods.is_empty() || options.methods.iter().any(|method| method.eq_ignore_ascii_case(&operation.method)))
+}
+
+/// The webhook operations to generate tests for, each with the name of its webhook
+pub fn webhook_operations<'a>(spec: &'a SwaggerSpec, options: &GeneratorOptions) -> Vec<(&'a str, &'a ApiOperation)> {
+    spec.webhooks
+        .iter()
+        .flat_map(|webhook| webhook.operations.iter().map(move |op| (webhook.name.as_str(), op)))
+        .filter(|(_, operation)| !(options.skip_deprecated && operation.deprecated))
+        .collect()
+}
+
+/// A single group of every operation, in the order of the specification
+pub fn single_group(spec: &SwaggerSpec) -> OperationGroup<'_> {
+    OperationGroup {
+        name: spec.info.title.clone(),
+        file_stem: "api".to_string(),
+        description: "all operations".to_string(),
+        operations: spec.paths
+            .iter()
+            .flat_map(|path| path.operations.iter().map(move |op| (path.path.as_str(), op)))
+            .collect(),
+    }
+}
+
+/// One group per path, in the order of the specification. The root path's group is named `root`
+pub fn group_by_path(spec: &SwaggerSpec) -> Vec<OperationGroup<'_>> {
+    spec.paths
+        .iter()
+        .map(|path| {
+            let name = path.path
+                .trim_start_matches('/')
+                .replace('/', " ")
+                .replace(['{', '}'], "");
+            let file_stem = path.path
+                .trim_start_matches('/')
+                .replace('/', "_")
+                .replace(['{', '}'], "");
+            OperationGroup {
+                name: if name.is_empty() { "root".to_string() } else { name },
+                file_stem: if file_stem.is_empty() { "root".to_string() } else { file_stem },
+                description: format!("operations on {}", path.path),
+                operations: path.operations.iter().map(|op| (path.path.as_str(), op)).collect(),
+            }
+        })
+        .collect()
+}
+
+/// One group per tag, in order of first appearance. Operations are grouped under their first tag
+/// so that each is generated once, and untagged operations end up in a `default` group.
+pub fn group_by_tag(spec: &SwaggerSpec) -> Vec<OperationGroup<'_>> {
+    let mut groups: Vec<OperationGroup> = Vec::new();
+
+    for path in &spec.paths {
+        for operation in &path.operations {
+            let tag = operation.tags.first().map(String::as_str).unwrap_or("default");
+
+            let index = match groups.iter().position(|group| group.name == tag) {
+                Some(index) => index,
+                None => {
+                    groups.push(OperationGroup {
+                        name: tag.to_string(),
+                        file_stem: tag_file_stem(tag),
+                        description: format!("operations tagged {}", tag),
+                        operations: Vec::new(),
+                    });
+                    groups.len() - 1
+                },
+            };
+            groups[index].operations.push((path.path.as_str(), operation));
+        }
+    }
+
+    groups
+}
+
+/// Turn a tag into a name usable for files and modules, e.g. "Pet Store" into "pet_store"
+pub fn tag_file_stem(tag: &str) -> String {
+    let stem: String = tag
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    let stem = stem.trim_matches('_');
+
+    match stem.chars().next() {
+        None => "untitled".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("tag_{}", stem),
+        Some(_) => stem.to_string(),
+    }
+}
@@ -0,0 +1,152 @@
+// This file contains the resource lifecycles tested end to end: an item is created with a POST to its collection, read,
+// updated and deleted through the operations on it, and reading it once deleted has to fail with 404.
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, ApiParameter, SwaggerSpec};
+use super::body::{body_encoding, query_string, request_body_example, BodyEncoding};
+use super::layout::{operation_groups, tag_file_stem};
+use super::links::{incoming_link, LinkedSetup};
+use super::test_framework::{expected_status, snake_case, GeneratorOptions};
+
+/// What a step of a lifecycle does to the created item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Read it, expecting the operation's success status
+    Read,
+
+    /// Update it, expecting the operation's success status
+    Update,
+
+    /// Delete it, expecting the operation's success status
+    Delete,
+
+    /// Read it again once deleted, expecting 404
+    ReadDeleted,
+}
+
+impl Action {
+    /// Description of the step, for comments and request names
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Read => "Read the created resource",
+            Action::Update => "Update it",
+            Action::Delete => "Delete it",
+            Action::ReadDeleted => "Check it is gone",
+        }
+    }
+}
+
+/// A request of a lifecycle, sent once the item is created
+pub struct LifecycleStep<'a> {
+    /// What the request does
+    pub action: Action,
+
+    /// Path the operation is declared on
+    pub path: &'a str,
+
+    /// The operation called
+    pub operation: &'a ApiOperation,
+}
+
+impl LifecycleStep<'_> {
+    /// The status the API has to answer with
+    pub fn expected_status(&self) -> &str {
+        match self.action {
+            Action::ReadDeleted => "404",
+            _ => expected_status(self.operation),
+        }
+    }
+
+    /// The JSON body sent, for updates
+    pub fn body(&self) -> Option<Value> {
+        request_body_example(self.operation, None)
+    }
+
+    /// The query string sent, with the example values of the query parameters
+    pub fn query(&self) -> String {
+        query_string(self.operation)
+    }
+}
+
+/// The test of a resource's lifecycle
+pub struct Lifecycle<'a> {
+    /// Suffix of the test name, of lowercase letters, digits and underscores: the tag of the create operation, followed
+    /// by the create operation's ID when the tag has several resources
+    pub name: String,
+
+    /// Tag of the create operation, `default` for untagged ones
+    pub tag: String,
+
+    /// The request creating the item, supplying the path parameters of the others
+    pub create: LinkedSetup<'a>,
+
+    /// The requests sent to the created item, in order
+    pub steps: Vec<LifecycleStep<'a>>,
+}
+
+impl<'a> Lifecycle<'a> {
+    /// The operation reading the item, whose path parameters the create request supplies
+    pub fn read_operation(&self) -> &'a ApiOperation {
+        self.steps[0].operation
+    }
+
+    /// The path parameters of the steps that the create request doesn't supply, each once, which are sent with their
+    /// example values
+    pub fn unbound_params(&self) -> Vec<&'a ApiParameter> {
+        let bound = self.create.linked_params(self.read_operation());
+        let mut params: Vec<&ApiParameter> = Vec::new();
+        for param in self.steps.iter().flat_map(|step| &step.operation.path_params) {
+            if !bound.iter().any(|(linked, _)| linked.name == param.name) && !params.iter().any(|p| p.name == param.name) {
+                params.push(param);
+            }
+        }
+        params
+    }
+}
+
+/// The lifecycles of the specification's resources, in the order of their create operations: those created by a POST
+/// that the GET and DELETE of an item depend on (by a link, `x-depends-on` or their paths, see `links`), with the PUT or
+/// PATCH of the item updating it when there is one. Resources with deprecated or left out operations are skipped,
+/// and so are updates with a body other than JSON
+pub fn lifecycles<'a>(spec: &'a SwaggerSpec, options: &GeneratorOptions) -> Vec<Lifecycle<'a>> {
+    let operations: Vec<(&str, &ApiOperation)> = operation_groups(spec, options)
+        .into_iter()
+        .flat_map(|group| group.operations)
+        .filter(|(_, operation)| !operation.deprecated)
+        .collect();
+    let item_operation = |create: &ApiOperation, methods: &[&str]| {
+        methods.iter().find_map(|method| {
+            operations.iter().copied().find(|(_, operation)| {
+                operation.method.eq_ignore_ascii_case(method)
+                    && !operation.path_params.is_empty()
+                    && incoming_link(spec, operation).is_some_and(|setup| setup.operation.operation_id == create.operation_id)
+            })
+        })
+    };
+
+    let mut lifecycles: Vec<Lifecycle> = Vec::new();
+    for &(_, create) in operations.iter().filter(|(_, operation)| operation.method.eq_ignore_ascii_case("POST")) {
+        let (Some(read), Some(delete)) = (item_operation(create, &["GET"]), item_operation(create, &["DELETE"])) else {
+            continue;
+        };
+        let Some(setup) = incoming_link(spec, read.1) else {
+            continue;
+        };
+        let update = item_operation(create, &["PUT", "PATCH"]).filter(|(_, operation)| {
+            operation.body_param.as_ref().is_none_or(|body| body_encoding(body) == BodyEncoding::Json)
+        });
+
+        let mut steps = vec![LifecycleStep { action: Action::Read, path: read.0, operation: read.1 }];
+        steps.extend(update.map(|(path, operation)| LifecycleStep { action: Action::Update, path, operation }));
+        steps.push(LifecycleStep { action: Action::Delete, path: delete.0, operation: delete.1 });
+        steps.push(LifecycleStep { action: Action::ReadDeleted, path: read.0, operation: read.1 });
+
+        let tag = create.tags.first().cloned().unwrap_or_else(|| "default".to_string());
+        let mut name = tag_file_stem(&tag);
+        if lifecycles.iter().any(|lifecycle| lifecycle.tag == tag) {
+            name = format!("{}_{}", name, snake_case(&create.operation_id));
+        }
+        lifecycles.push(Lifecycle { name, tag, create: setup, steps });
+    }
+    lifecycles
+}
@@ -0,0 +1,297 @@
+// This file contains the chaining of generated tests along the links the specification declares between operations,
+// the dependencies operations declare with `x-depends-on`, and those inferred from the paths: an operation on an item of
+// a collection (`GET /pets/{petId}`) needs the item created first (`POST /pets`). Items created by a POST are deleted
+// once the test is done when a DELETE of the item follows from it the same way.
+
+use std::borrow::Cow;
+
+use serde_json::{json, Value};
+use crate::parser::{links::parse_links, ApiLink, ApiOperation, ApiParameter, LinkValue, Schema, SwaggerSpec};
+use super::body::{body_example, example_for_parameter, path_value, query_value, to_plain_text};
+
+/// How a test was found to need a setup request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dependency {
+    /// A link declared on the setup operation's response
+    Link,
+
+    /// The tested operation's `x-depends-on` extension, naming the setup operation
+    DependsOn,
+
+    /// The tested operation works on an item of the collection the setup operation adds to
+    Inferred,
+}
+
+/// A request sent at the start of a test to obtain values for the tested operation's parameters,
+/// following a link from the setup operation's response
+pub struct LinkedSetup<'a> {
+    /// Path the setup operation is declared on
+    pub path: &'a str,
+
+    /// The operation called first
+    pub operation: &'a ApiOperation,
+
+    /// The link from the setup operation's response to the tested operation, declared or built for the dependency
+    pub link: Cow<'a, ApiLink>,
+
+    /// How the dependency was found
+    pub dependency: Dependency,
+}
+
+impl<'a> LinkedSetup<'a> {
+    /// The setup request's path, with its own path parameters filled in with example values
+    pub fn request_path(&self) -> String {
+        self.operation.path_params.iter().fold(self.path.to_string(), |path, param| {
+            path.replace(&format!("{{{}}}", param.name), &to_plain_text(&path_value(param)))
+        })
+    }
+
+    /// Where the dependency comes from, for comments in generated tests: `link "GetUserById"`, `x-depends-on`, or
+    /// `it creates the resource` when inferred from the paths
+    pub fn origin(&self) -> String {
+        match self.dependency {
+            Dependency::Link => format!("link \"{}\"", self.link.name),
+            Dependency::DependsOn => "x-depends-on".to_string(),
+            Dependency::Inferred => "it creates the resource".to_string(),
+        }
+    }
+
+    /// Example body sent with the setup request, if it takes one
+    pub fn request_body(&self) -> Option<Value> {
+        self.operation.body_param.as_ref().map(body_example)
+    }
+
+    /// The tested operation's path and query parameters that the link supplies, with where their values come from
+    pub fn linked_params<'t>(&self, target: &'t ApiOperation) -> Vec<(&'t ApiParameter, &LinkValue)> {
+        target.path_params
+            .iter()
+            .chain(&target.query_params)
+            .filter_map(|param| {
+                let qualified = format!("{}.{}", param.location, param.name);
+                self.link.parameters
+                    .iter()
+                    .find(|(name, _)| *name == param.name || *name == qualified)
+                    .map(|(_, value)| (param, value))
+            })
+            .collect()
+    }
+
+    /// Values that don't depend on the setup response are known when generating the test:
+    /// constants, and the example values the setup request is sent with
+    pub fn static_value(&self, value: &LinkValue) -> Option<String> {
+        let param = |params: &[ApiParameter], name: &str| params.iter().find(|p| p.name == name).cloned();
+
+        match value {
+            LinkValue::Constant(value) => Some(to_plain_text(value)),
+            LinkValue::RequestPath(name) => param(&self.operation.path_params, name).map(|p| to_plain_text(&path_value(&p))),
+            LinkValue::RequestQuery(name) => param(&self.operation.query_params, name).map(|p| query_value(&p)),
+            LinkValue::RequestHeader(name) => param(&self.operation.header_params, name).map(|p| to_plain_text(&example_for_parameter(&p))),
+            LinkValue::RequestBody(pointer) => self.request_body().and_then(|body| body.pointer(pointer).map(to_plain_text)),
+            LinkValue::ResponseBody(_) | LinkValue::ResponseHeader(_) => None,
+        }
+    }
+}
+
+/// The DELETE removing an item a POST created, once the test creating it is done
+pub struct Cleanup<'a> {
+    /// Path the DELETE operation is declared on
+    pub path: &'a str,
+
+    /// The DELETE operation
+    pub operation: &'a ApiOperation,
+
+    /// The dependency of the DELETE on the POST, supplying its path parameters
+    pub setup: LinkedSetup<'a>,
+}
+
+impl<'a> Cleanup<'a> {
+    /// The DELETE's path parameters, with where the POST supplies their values from
+    pub fn params(&self) -> Vec<(&'a ApiParameter, &LinkValue)> {
+        self.setup.linked_params(self.operation)
+    }
+}
+
+/// What a test creates before its request and deletes once done
+pub struct Resources<'a> {
+    /// The request sent first, see `incoming_link`
+    pub setup: Option<LinkedSetup<'a>>,
+
+    /// The DELETE removing what the setup request created
+    pub setup_cleanup: Option<Cleanup<'a>>,
+
+    /// The DELETE removing what the tested request creates
+    pub cleanup: Option<Cleanup<'a>>,
+}
+
+impl Resources<'_> {
+    /// Whether the test deletes anything once done
+    pub fn cleans_up(&self) -> bool {
+        self.setup_cleanup.is_some() || self.cleanup.is_some()
+    }
+}
+
+/// The setup request of the tested operation, and the deletion of the items it and the setup request create. The
+/// setup request's item isn't deleted again by the DELETE it is the setup request of
+pub fn resources<'a>(spec: &'a SwaggerSpec, operation: &ApiOperation) -> Resources<'a> {
+    let setup = incoming_link(spec, operation);
+    let setup_cleanup = setup.as_ref()
+        .and_then(|setup| cleanup(spec, setup.operation))
+        .filter(|cleanup| cleanup.operation.operation_id != operation.operation_id);
+    Resources { setup, setup_cleanup, cleanup: cleanup(spec, operation) }
+}
+
+/// The DELETE removing the items a POST creates: a DELETE whose setup request is the POST, which supplies all its path
+/// parameters
+pub fn cleanup<'a>(spec: &'a SwaggerSpec, create: &ApiOperation) -> Option<Cleanup<'a>> {
+    if !create.method.eq_ignore_ascii_case("POST") {
+        return None;
+    }
+    operations(spec)
+        .filter(|(_, op)| op.method.eq_ignore_ascii_case("DELETE"))
+        .find_map(|(path, operation)| {
+            let setup = incoming_link(spec, operation)?;
+            let supplied = setup.linked_params(operation);
+            let all_supplied = operation.path_params.iter().all(|param| supplied.iter().any(|(p, _)| p.name == param.name));
+            (setup.operation.operation_id == create.operation_id && all_supplied).then_some(Cleanup { path, operation, setup })
+        })
+}
+
+/// Whether the tests of any operation delete what they created
+pub fn has_cleanups(spec: &SwaggerSpec) -> bool {
+    operations(spec).any(|(_, operation)| cleanup(spec, operation).is_some())
+}
+
+/// Whether the tests of any operation send a setup request first
+pub fn has_setups(spec: &SwaggerSpec) -> bool {
+    operations(spec).any(|(_, operation)| incoming_link(spec, operation).is_some())
+}
+
+/// Find the setup request of `target`: a link into it from the successful response of another operation, preferring
+/// links from POST operations since those usually create the resource the target works on, then the operation its
+/// `x-depends-on` names, then the POST creating the item it works on
+pub fn incoming_link<'a>(spec: &'a SwaggerSpec, target: &ApiOperation) -> Option<LinkedSetup<'a>> {
+    declared_link(spec, target)
+        .or_else(|| depends_on(spec, target))
+        .or_else(|| {
+            let (path, operation) = collection_create(spec, target)?;
+            Some(inferred_setup(path, operation, target))
+        })
+}
+
+/// The links out of the operation's responses, declared or built for the dependencies of the operations it is the
+/// setup request of
+pub fn outgoing_links<'a>(spec: &'a SwaggerSpec, operation: &'a ApiOperation) -> Vec<Cow<'a, ApiLink>> {
+    let mut links: Vec<Cow<ApiLink>> = operation.responses
+        .iter()
+        .filter(|resp| resp.status_code.starts_with('2'))
+        .flat_map(|resp| &resp.links)
+        .map(Cow::Borrowed)
+        .collect();
+    links.extend(
+        operations(spec)
+            .filter_map(|(_, target)| incoming_link(spec, target))
+            .filter(|setup| setup.dependency != Dependency::Link && setup.operation.operation_id == operation.operation_id)
+            .map(|setup| setup.link),
+    );
+    links
+}
+
+/// The POST adding to the collection the target works on an item of, as `POST /pets` is for `GET`, `PUT`, `PATCH` and
+/// `DELETE /pets/{petId}`
+pub fn collection_create<'a>(spec: &'a SwaggerSpec, target: &ApiOperation) -> Option<(&'a str, &'a ApiOperation)> {
+    if !matches!(target.method.to_uppercase().as_str(), "GET" | "PUT" | "PATCH" | "DELETE") {
+        return None;
+    }
+    let (path, _) = operations(spec).find(|(_, op)| op.operation_id == target.operation_id)?;
+    let (collection, item) = path.trim_end_matches('/').rsplit_once('/')?;
+    if !(item.starts_with('{') && item.ends_with('}') && item.matches('{').count() == 1) {
+        return None;
+    }
+    operations(spec).find(|(path, op)| *path == collection && op.method.eq_ignore_ascii_case("POST"))
+}
+
+/// Every operation of the specification, with the path it is declared on
+fn operations(spec: &SwaggerSpec) -> impl Iterator<Item = (&str, &ApiOperation)> {
+    spec.paths.iter().flat_map(|path| path.operations.iter().map(move |op| (path.path.as_str(), op)))
+}
+
+/// The setup request of a link into `target` declared on another operation's successful response
+fn declared_link<'a>(spec: &'a SwaggerSpec, target: &ApiOperation) -> Option<LinkedSetup<'a>> {
+    let mut candidates: Vec<LinkedSetup> = spec.paths
+        .iter()
+        .flat_map(|path| path.operations.iter().map(move |op| (path.path.as_str(), op)))
+        .filter(|(_, op)| op.operation_id != target.operation_id)
+        .flat_map(|(path, operation)| {
+            operation.responses
+                .iter()
+                .filter(|resp| resp.status_code.starts_with('2'))
+                .flat_map(|resp| &resp.links)
+                .filter(|link| spec.link_target(link).is_some_and(|(_, op)| op.operation_id == target.operation_id))
+                .map(move |link| LinkedSetup { path, operation, link: Cow::Borrowed(link), dependency: Dependency::Link })
+        })
+        .collect();
+
+    // A stable sort keeps the specification's order among POST and among other operations
+    candidates.sort_by_key(|setup| setup.operation.method != "POST");
+    candidates.into_iter().next()
+}
+
+/// The setup request named by the target's `x-depends-on` extension: the operationId of the setup operation, or an
+/// object shaped like a link giving the parameters it supplies too (`{operationId: createPet, parameters: {petId:
+/// $response.body#/id}}`). Without parameters, they're inferred
+fn depends_on<'a>(spec: &'a SwaggerSpec, target: &ApiOperation) -> Option<LinkedSetup<'a>> {
+    let value = match target.extensions.get("x-depends-on")? {
+        Value::String(operation_id) => json!({ "operationId": operation_id }),
+        value => value.clone(),
+    };
+    let mut link = parse_links(Some(&json!({ target.operation_id.clone(): value }))).pop()?;
+    let setup_id = link.operation_id.take()?;
+    let (path, operation) = operations(spec)
+        .find(|(_, op)| op.operation_id == setup_id || op.declared_operation_id.as_deref() == Some(setup_id.as_str()))?;
+
+    link.operation_id = Some(target.operation_id.clone());
+    if link.parameters.is_empty() {
+        link.parameters = inferred_parameters(operation, target);
+    }
+    Some(LinkedSetup { path, operation, link: Cow::Owned(link), dependency: Dependency::DependsOn })
+}
+
+/// A setup request sending `setup` before `target`, with the parameters it supplies inferred
+fn inferred_setup<'a>(path: &'a str, setup: &'a ApiOperation, target: &ApiOperation) -> LinkedSetup<'a> {
+    let link = ApiLink {
+        name: target.operation_id.clone(),
+        operation_id: Some(target.operation_id.clone()),
+        operation_ref: None,
+        parameters: inferred_parameters(setup, target),
+        request_body: None,
+        description: None,
+    };
+    LinkedSetup { path, operation: setup, link: Cow::Owned(link), dependency: Dependency::Inferred }
+}
+
+/// The values `setup` supplies to the target's path parameters: those of its own path parameters of the same name
+/// (`ownerId` of `POST /owners/{ownerId}/pets`), otherwise the property of its response body named after the
+/// parameter, or else its `id`
+fn inferred_parameters(setup: &ApiOperation, target: &ApiOperation) -> Vec<(String, LinkValue)> {
+    let response_properties = setup.responses
+        .iter()
+        .filter(|resp| resp.status_code.starts_with('2'))
+        .find_map(|resp| match resp.schema.as_ref() {
+            Some(Schema::Object(object)) => Some(&object.properties),
+            _ => None,
+        });
+
+    target.path_params
+        .iter()
+        .map(|param| {
+            let value = if setup.path_params.iter().any(|p| p.name == param.name) {
+                LinkValue::RequestPath(param.name.clone())
+            } else if response_properties.is_some_and(|properties| properties.contains_key(&param.name)) {
+                LinkValue::ResponseBody(format!("/{}", param.name))
+            } else {
+                LinkValue::ResponseBody("/id".to_string())
+            };
+            (param.name.clone(), value)
+        })
+        .collect()
+}
@@ -0,0 +1,223 @@
+// This file contains the Locust load-test generator, writing a locustfile whose user calls every operation as a weighted task.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, query_value, raw_body_example, request_body_example, request_headers,
+    to_python_literal, variant_suffix, BodyEncoding,
+};
+use super::layout::operation_groups;
+use super::test_framework::{generate_in_parallel, snake_case, success_statuses, GeneratorOptions, Result, TestGenerator};
+
+/// Users simulated when the options don't say
+const DEFAULT_USERS: u32 = 10;
+
+/// How long the load test runs when the options don't say
+const DEFAULT_DURATION: Duration = Duration::from_secs(30);
+
+/// Vendor extension giving the weight of an operation's task, or of the tasks of a tag's operations
+const WEIGHT_EXTENSION: &str = "x-weight";
+
+// Python Locust load-test generator
+pub struct LocustGenerator {
+    options: GeneratorOptions,
+}
+
+impl LocustGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        LocustGenerator { options }
+    }
+
+    /// Generate the task for one request of an operation, with its body taken from `variant` when given
+    fn generate_task(&self, spec: &SwaggerSpec, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let python_str = |text: &str| to_python_literal(&Value::String(text.to_string()), "");
+
+        // Requests are named after their path template, so that Locust's statistics group them
+        let mut args = vec![
+            python_str(&operation.method.to_uppercase()),
+            python_str(&example_path(path, operation)),
+            format!("name={}", python_str(&format!("{} {}", operation.method.to_uppercase(), path))),
+        ];
+
+        if !operation.query_params.is_empty() {
+            let params: Vec<String> = operation.query_params
+                .iter()
+                .map(|p| format!("{}: {}", python_str(&p.name), python_str(&query_value(p))))
+                .collect();
+            args.push(format!("params={{{}}}", params.join(", ")));
+        }
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+        if !headers.is_empty() {
+            let headers: Vec<String> = headers.iter().map(|(name, value)| format!("{}: {}", python_str(name), python_str(value))).collect();
+            args.push(format!("headers={{{}}}", headers.join(", ")));
+        }
+
+        match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                let fields = form_fields(body);
+                let data: Vec<String> = fields
+                    .iter()
+                    .filter_map(|(name, value)| Some(format!("{}: {}", python_str(name), python_str(value.as_ref()?))))
+                    .collect();
+                let files: Vec<String> = fields
+                    .iter()
+                    .filter(|(_, value)| value.is_none())
+                    .map(|(name, _)| format!("{}: (\"upload.txt\", b\"test file content\")", python_str(name)))
+                    .collect();
+                args.push(format!("data={{{}}}", data.join(", ")));
+                args.push(format!("files={{{}}}", files.join(", ")));
+            },
+            Some((body, BodyEncoding::Form)) => {
+                let data: Vec<String> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| format!("{}: {}", python_str(&name), python_str(&value.unwrap_or_default())))
+                    .collect();
+                args.push(format!("data={{{}}}", data.join(", ")));
+            },
+            Some((body, BodyEncoding::Raw)) => args.push(format!("data={}", python_str(&raw_body_example(body)))),
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                args.push(format!("json={}", to_python_literal(&example, "            ")));
+            },
+            None => {},
+        }
+        args.push("catch_response=True".to_string());
+
+        // Only documented successful statuses count as successes
+        let statuses = success_statuses(operation);
+        let condition = match statuses.as_slice() {
+            [status] => format!("response.status_code != {}", status),
+            statuses => format!("response.status_code not in ({})", statuses.join(", ")),
+        };
+
+        // Deprecated operations get a method, but no task
+        let decorator = if operation.deprecated {
+            "    # Deprecated: not run as a task\n".to_string()
+        } else {
+            format!("    @task({})\n", operation_weight(spec, operation))
+        };
+        let summary = operation.summary.clone().unwrap_or_else(|| format!("{} {}", operation.method.to_uppercase(), path));
+
+        format!(
+            r#"{decorator}    def {name}(self):
+        """{summary}"""
+        with self.client.request(
+            {args},
+        ) as response:
+            if {condition}:
+                response.failure(f"unexpected status {{response.status_code}}")"#,
+            name = snake_case(&format!("{}{}", operation.operation_id, variant_suffix(variant))),
+            summary = summary.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\""),
+            args = args.join(",\n            "),
+        )
+    }
+}
+
+impl TestGenerator for LocustGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // A single user class has a task for every operation
+        let operations: Vec<_> = operation_groups(spec, &self.options)
+            .into_iter()
+            .flat_map(|group| group.operations)
+            .collect();
+        let tasks = generate_in_parallel(&[operations], &self.options, |path, operation, variant| {
+            self.generate_task(spec, operation, path, variant)
+        })?;
+        let tasks = tasks.into_iter().flatten().collect::<Vec<_>>();
+
+        let mut locustfile = File::create(output_dir.join("locustfile.py"))?;
+
+        writeln!(locustfile, r#""""Load test of {title} (version {version})"""
+import os
+
+from locust import HttpUser, between, task
+
+
+class ApiUser(HttpUser):
+    """A user calling the operations of the API, each as often as its task's weight says"""
+
+    # The API_BASE_URL environment variable (or Locust's --host) overrides where the API under test runs
+    host = os.environ.get("API_BASE_URL", {base_url})
+    wait_time = between(1, 3)
+
+{tasks}"#,
+            title = spec.info.title,
+            version = spec.info.version,
+            base_url = to_python_literal(&Value::String(base_url.trim_end_matches('/').to_string()), ""),
+            tasks = tasks.join("\n\n"))?;
+
+        // The configuration running the test without the web UI, with the load the options ask for
+        let users = self.options.virtual_users.unwrap_or(DEFAULT_USERS);
+        let mut config_file = File::create(output_dir.join("locust.conf"))?;
+
+        writeln!(config_file, r#"locustfile = locustfile.py
+headless = true
+users = {users}
+spawn-rate = {users}
+run-time = {duration}s"#,
+            duration = self.options.duration.unwrap_or(DEFAULT_DURATION).as_secs())?;
+
+        // Create a requirements.txt file
+        let mut requirements_file = File::create(output_dir.join("requirements.txt"))?;
+
+        writeln!(requirements_file, "locust>=2.20.0")?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Load Tests
+
+Generated Locust load tests for {title} (version {version}).
+
+## Running the tests
+
+Install the requirements, then run the load test against the API at `API_BASE_URL`:
+
+```
+pip install -r requirements.txt
+API_BASE_URL=http://localhost:3000 locust --config locust.conf
+```
+
+Leave out `--config locust.conf` to start Locust's web UI instead.
+The weight of an operation's task comes from its `{extension}` extension, or else from that of its first tag.
+"#,
+            title = spec.info.title,
+            version = spec.info.version,
+            extension = WEIGHT_EXTENSION)?;
+
+        Ok(())
+    }
+}
+
+/// The weight of an operation's task: its own `x-weight` extension, else that of the declaration of its first tag, else 1
+fn operation_weight(spec: &SwaggerSpec, operation: &ApiOperation) -> u64 {
+    let tag_weight = || {
+        let tag = operation.tags.first()?;
+        spec.raw_spec
+            .get("tags")?
+            .as_array()?
+            .iter()
+            .find(|declared| declared.get("name").and_then(Value::as_str) == Some(tag.as_str()))?
+            .get(WEIGHT_EXTENSION)
+    };
+
+    operation.extensions
+        .get(WEIGHT_EXTENSION)
+        .or_else(tag_weight)
+        .and_then(Value::as_u64)
+        .filter(|weight| *weight > 0)
+        .unwrap_or(1)
+}
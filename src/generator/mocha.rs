@@ -0,0 +1,241 @@
+// This file contains the Mocha test generator, writing CommonJS `describe`/`it` suites that send their requests with axios and check them with Chai.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, PrimitiveType, Schema, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, has_multipart_body, js_string, query_value, raw_body_example,
+    request_body_example, request_headers, to_json_literal, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{
+    expected_headers, expected_status, generate_in_parallel, required_response_properties, response_schema, GeneratorOptions, Result, TestGenerator,
+};
+
+// JavaScript Mocha and Chai test generator
+pub struct MochaGenerator {
+    options: GeneratorOptions,
+}
+
+impl MochaGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        MochaGenerator { options }
+    }
+
+    /// Generate the `it` block for one request of an operation, with its body taken from `variant` when given
+    fn generate_operation_test(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_lowercase();
+        let operation_id = match variant {
+            Some(variant) => format!("{} ({})", operation.operation_id, variant.name),
+            None => operation.operation_id.clone(),
+        };
+        let summary = operation.summary.clone().unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+        let mut spread_headers = None;
+
+        // axios sets the content type of URL-encoded and JSON bodies itself, and form-data that of multipart ones
+        let mut setup = Vec::new();
+        let data = match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                setup.push("const form = new FormData();".to_string());
+                for (name, value) in form_fields(body) {
+                    setup.push(match value {
+                        Some(value) => format!("form.append({}, {});", js_string(&name), js_string(&value)),
+                        None => format!(
+                            "form.append({}, Buffer.from('test file content'), {{ filename: 'upload.txt', contentType: 'text/plain' }});",
+                            js_string(&name)
+                        ),
+                    });
+                }
+                headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Content-Type"));
+                spread_headers = Some("        ...form.getHeaders(),".to_string());
+                Some("form".to_string())
+            },
+            Some((body, BodyEncoding::Form)) => {
+                let fields: Vec<String> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| format!("        {}: {},", js_string(&name), js_string(&value.unwrap_or_default())))
+                    .collect();
+                Some(format!("new URLSearchParams({{\n{}\n      }})", fields.join("\n")))
+            },
+            Some((body, BodyEncoding::Raw)) => Some(js_string(&raw_body_example(body))),
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                Some(to_json_literal(&example, "      "))
+            },
+            None => None,
+        };
+
+        let mut config = vec![
+            format!("      method: {},", js_string(&method)),
+            format!("      url: {},", js_string(&example_path(path, operation))),
+        ];
+        if !operation.query_params.is_empty() {
+            let params: Vec<String> = operation.query_params
+                .iter()
+                .map(|param| format!("        {}: {},", js_string(&param.name), js_string(&query_value(param))))
+                .collect();
+            config.push(format!("      params: {{\n{}\n      }},", params.join("\n")));
+        }
+        let headers: Vec<String> = headers
+            .iter()
+            .map(|(name, value)| format!("        {}: {},", js_string(name), js_string(value)))
+            .chain(spread_headers)
+            .collect();
+        if !headers.is_empty() {
+            config.push(format!("      headers: {{\n{}\n      }},", headers.join("\n")));
+        }
+        if let Some(data) = data {
+            config.push(format!("      data: {},", data));
+        }
+
+        let mut lines = vec![format!("    // {}", summary.replace('\n', " "))];
+        lines.extend(setup.into_iter().map(|line| format!("    {}", line)));
+        lines.push(format!("    const response = await api.request({{\n{}\n    }});", config.join("\n")));
+        lines.push(String::new());
+
+        // Verify status code and documented headers, which axios names in lowercase
+        lines.push(format!("    expect(response.status).to.equal({});", expected_status(operation)));
+        for header in expected_headers(operation) {
+            lines.push(format!("    expect(response.headers).to.have.property({});", js_string(&header.to_lowercase())));
+        }
+
+        // Verify the response body
+        let assertions = response_schema(operation).map(body_assertions).unwrap_or_default();
+        if !assertions.is_empty() {
+            lines.push(String::new());
+            lines.extend(assertions.into_iter().map(|assertion| format!("    {}", assertion)));
+        }
+
+        // Deprecated operations are still generated, but skipped
+        let it = if operation.deprecated { "it.skip" } else { "it" };
+        format!("  {}({}, async function () {{\n{}\n  }});", it, js_string(&operation_id), lines.join("\n"))
+    }
+}
+
+impl TestGenerator for MochaGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        let test_dir = output_dir.join("test");
+        fs::create_dir_all(&test_dir)?;
+
+        // A suite per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let tests = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_operation_test(operation, path, variant)
+        })?;
+
+        for (group, tests) in groups.iter().zip(tests) {
+            let mut file = File::create(test_dir.join(format!("{}.spec.js", group.file_stem)))?;
+
+            let form_data = if group.operations.iter().any(|(_, operation)| has_multipart_body(operation)) {
+                "\nconst FormData = require('form-data');"
+            } else {
+                ""
+            };
+            writeln!(file, r#"const axios = require('axios');
+const {{ expect }} = require('chai');{form_data}
+
+// The API_BASE_URL environment variable overrides where the API under test runs, and every status is checked by the tests
+const api = axios.create({{
+  baseURL: process.env.API_BASE_URL || {base_url},
+  validateStatus: () => true,
+}});
+
+describe({name}, function () {{
+{tests}
+}});"#,
+                base_url = js_string(base_url.trim_end_matches('/')),
+                name = js_string(&group.title()),
+                tests = tests.join("\n\n"))?;
+        }
+
+        // Create the Mocha configuration
+        let mocharc = serde_json::json!({
+            "spec": "test/**/*.spec.js",
+            "timeout": 30000
+        });
+        fs::write(output_dir.join(".mocharc.json"), serde_json::to_string_pretty(&mocharc).unwrap_or_default() + "\n")?;
+
+        // Create a package.json file. Chai 4 is the last release that can be `require`d
+        let mut dependencies = serde_json::Map::new();
+        dependencies.insert("axios".to_string(), Value::String("^1.7.2".to_string()));
+        dependencies.insert("chai".to_string(), Value::String("^4.4.1".to_string()));
+        if spec.paths.iter().flat_map(|p| &p.operations).any(has_multipart_body) {
+            dependencies.insert("form-data".to_string(), Value::String("^4.0.0".to_string()));
+        }
+        dependencies.insert("mocha".to_string(), Value::String("^10.4.0".to_string()));
+        let package = serde_json::json!({
+            "name": spec.info.package_name("-", "tests"),
+            "version": spec.info.package_version("1.0.0"),
+            "description": format!("Generated API tests for {}", spec.info.title),
+            "private": true,
+            "scripts": {
+                "test": "mocha"
+            },
+            "devDependencies": dependencies
+        });
+        fs::write(output_dir.join("package.json"), serde_json::to_string_pretty(&package).unwrap_or_default() + "\n")?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), using Mocha, Chai and axios.
+
+## Setup
+
+Install the dependencies:
+
+```
+npm install
+```
+
+## Running the tests
+
+Run the tests against the API at `API_BASE_URL`:
+
+```
+API_BASE_URL=http://localhost:3000 npm test
+```
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// Chai assertions on the parsed response body (`response.data`): its type, and for objects the presence of the required properties
+fn body_assertions(schema: &Schema) -> Vec<String> {
+    if schema.info().is_some_and(|info| info.nullable) {
+        return Vec::new();
+    }
+
+    match schema {
+        Schema::Object(_) => {
+            let mut assertions = vec!["expect(response.data).to.be.an('object');".to_string()];
+            for property in required_response_properties(schema) {
+                assertions.push(format!("expect(response.data).to.have.property({});", js_string(property)));
+            }
+            assertions
+        },
+        Schema::Array(_) => vec!["expect(response.data).to.be.an('array');".to_string()],
+        Schema::Primitive(primitive) => vec![format!("expect(response.data).to.be.a({});", js_string(match primitive.kind {
+            PrimitiveType::String => "string",
+            PrimitiveType::Integer | PrimitiveType::Number => "number",
+            PrimitiveType::Boolean => "boolean",
+        }))],
+        Schema::Ref(_) | Schema::Composite(_) | Schema::Any(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,140 @@
+// This file contains the Mock Service Worker generator, writing request handlers that answer every operation with an example response.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use crate::parser::{ApiOperation, SwaggerSpec};
+use super::body::{js_string, to_json_literal, to_plain_text};
+use super::data::{example_from_schema, response_example};
+use super::layout::operation_groups;
+use super::test_framework::{expected_status, GeneratorOptions, Result, TestGenerator};
+
+/// Methods MSW has a handler function for; other methods are handled with `http.all`
+const MSW_METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "head", "options"];
+
+// Mock Service Worker handler generator
+pub struct MswGenerator {
+    options: GeneratorOptions,
+}
+
+impl MswGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        MswGenerator { options }
+    }
+
+    /// Generate the handler of an operation, answering with its documented status, headers and an example body
+    fn generate_handler(&self, operation: &ApiOperation, path: &str) -> String {
+        let method = operation.method.to_lowercase();
+        let function = if MSW_METHODS.contains(&method.as_str()) { method.as_str() } else { "all" };
+        let status = expected_status(operation);
+        let response = operation.responses.iter().find(|resp| resp.status_code == status);
+
+        let mut init = vec![format!("status: {}", status.parse::<u16>().unwrap_or(200))];
+        let headers: Vec<String> = response
+            .map(|resp| resp.headers.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|header| {
+                let value = header.schema.as_ref().map(example_from_schema).map(|value| to_plain_text(&value));
+                format!("{}: {}", js_string(&header.name), js_string(&value.unwrap_or_else(|| "string".to_string())))
+            })
+            .collect();
+        if !headers.is_empty() {
+            init.push(format!("headers: {{ {} }}", headers.join(", ")));
+        }
+        let init = format!("{{ {} }}", init.join(", "));
+
+        let reply = match response.and_then(|resp| resp.schema.as_ref()) {
+            Some(schema) => format!("HttpResponse.json({}, {})", to_json_literal(&response_example(schema), "    "), init),
+            None => format!("new HttpResponse(null, {})", init),
+        };
+
+        let summary = operation.summary.as_deref().map(|summary| format!(": {}", summary.replace('\n', " "))).unwrap_or_default();
+        let deprecated = if operation.deprecated { " (deprecated)" } else { "" };
+        format!(
+            "  // {}{}{}\n  http.{}(`${{BASE_URL}}{}`, () =>\n    {},\n  ),",
+            operation.operation_id,
+            deprecated,
+            summary,
+            function,
+            msw_path(path).replace('`', "\\`").replace("${", "\\${"),
+            reply,
+        )
+    }
+}
+
+impl TestGenerator for MswGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // MSW uses the first handler matching a request, so literal paths come before the templates that could match them
+        let mut operations: Vec<_> = operation_groups(spec, &self.options)
+            .into_iter()
+            .flat_map(|group| group.operations)
+            .collect();
+        operations.sort_by_key(|(_, operation)| operation.path_params.len());
+        let handlers: Vec<String> = operations
+            .iter()
+            .map(|(path, operation)| self.generate_handler(operation, path))
+            .collect();
+
+        let mut handlers_file = File::create(output_dir.join("handlers.ts"))?;
+
+        writeln!(handlers_file, r#"// Mock Service Worker handlers for {title} (version {version})
+import {{ http, HttpResponse }} from 'msw';
+
+// Where the API the handlers mock runs
+export const BASE_URL = {base_url};
+
+export const handlers = [
+{handlers}
+];"#,
+            title = spec.info.title,
+            version = spec.info.version,
+            base_url = js_string(base_url.trim_end_matches('/')),
+            handlers = handlers.join("\n"))?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Mocks
+
+Generated [Mock Service Worker](https://mswjs.io) handlers for {title} (version {version}),
+answering every operation with an example of its documented response.
+
+## Using the handlers
+
+Install MSW 2 (`npm install --save-dev msw`), copy `handlers.ts` into the project, and start a server with it in the test setup:
+
+```ts
+import {{ setupServer }} from 'msw/node';
+import {{ handlers }} from './handlers';
+
+const server = setupServer(...handlers);
+
+beforeAll(() => server.listen());
+afterEach(() => server.resetHandlers());
+afterAll(() => server.close());
+```
+
+In the browser, use `setupWorker` from `msw/browser` instead. Change `BASE_URL` if the app calls the API elsewhere.
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// The path of a path template in MSW's syntax, with `{id}` parameters written as `:id`
+fn msw_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('{').and_then(|name| name.strip_suffix('}')) {
+            Some(name) => format!(":{}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,560 @@
+// This file contains the extra request cases of `--negative-tests`, `--boundary-tests`, `--unauthorized-tests` and
+// `--status-coverage all`: requests breaking the rules of the specification, which the API is expected to reject,
+// requests with values at the edge of what it accepts, requests to secured operations sent without credentials, and
+// requests provoking the error statuses operations document. Operations whose successful response is documented in
+// several media types also get a request asking for each of them.
+
+use std::collections::HashSet;
+
+use serde_json::{json, Value};
+use crate::parser::{ApiOperation, ApiParameter, Constraints, ObjectSchema, PrimitiveType, Schema, SchemaVariant};
+use super::body::{
+    body_encoding, body_variants, cookie_header, example_path, is_read_only, percent_encode, query_value, request_body_example, request_headers,
+    to_plain_text, BodyEncoding,
+};
+use super::auth::Credential;
+use super::test_framework::{expected_status, snake_case, GeneratorOptions};
+
+/// Strings longer than this aren't generated for length boundaries, to keep the tests readable
+const MAX_GENERATED_LENGTH: u64 = 1024;
+
+/// How the request of a test case differs from the operation's valid request
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mutation {
+    /// Leave a property out of the JSON body
+    OmitProperty(String),
+
+    /// Set a property of the JSON body to a value, added if it wasn't sent
+    SetProperty(String, Value),
+
+    /// Send a path, query or header parameter with a value of its own
+    SetParameter {
+        /// Where the parameter goes: `path`, `query` or `header`
+        location: String,
+
+        /// Name of the parameter
+        name: String,
+
+        /// The value sent, as text
+        value: String,
+    },
+
+    /// Send the request without credentials
+    OmitCredentials,
+
+    /// Send a JSON body of its own instead of the valid one
+    ReplaceBody(Value),
+
+    /// Send a path parameter with a value no item has, so that the API answers 404
+    NonexistentItem {
+        /// Name of the parameter
+        name: String,
+
+        /// The value sent, as text
+        value: String,
+    },
+
+    /// Ask for the response in a media type with `Accept`, which the response's `Content-Type` has to match
+    Accept(String),
+}
+
+/// Which of the statuses an operation documents its tests check
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatusCoverage {
+    /// Only the first documented 2xx, the status of the valid request
+    #[default]
+    SuccessOnly,
+
+    /// Also the documented 400 or 422 (a body of the wrong type, or a query parameter of the wrong type), 401 or 403
+    /// (no credentials) and 404 (an item that doesn't exist), each with a test provoking it. Other statuses have no
+    /// request known to provoke them, and get no test
+    All,
+}
+
+/// A variation of an operation's valid request, which the API has to reject or, for values at a boundary, accept
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestCase {
+    /// Suffix of the test name, of lowercase letters, digits and underscores (`missing_email`)
+    pub name: String,
+
+    /// How the request differs, to describe the test with (`without the required property email`)
+    pub description: String,
+
+    /// The change made to the valid request
+    pub mutation: Mutation,
+
+    /// Whether the API has to accept the request rather than reject it
+    pub accepted: bool,
+}
+
+impl RequestCase {
+    /// The path the request is sent to, with the values of the path parameters filled in
+    pub fn path(&self, path: &str, operation: &ApiOperation) -> String {
+        match self.parameter("path") {
+            Some((name, value)) => example_path(&path.replace(&format!("{{{}}}", name), &percent_encode(value)), operation),
+            None => example_path(path, operation),
+        }
+    }
+
+    /// The query parameters sent, as `(name, value)` pairs
+    pub fn query(&self, operation: &ApiOperation) -> Vec<(String, String)> {
+        let parameter = self.parameter("query");
+        operation.query_params
+            .iter()
+            .map(|param| match parameter {
+                Some((name, value)) if name == param.name => (param.name.clone(), value.to_string()),
+                _ => (param.name.clone(), query_value(param)),
+            })
+            .collect()
+    }
+
+    /// The headers sent, as `(name, value)` pairs, with `static_headers` and cookie parameters as a `Cookie` header
+    pub fn headers(&self, operation: &ApiOperation, static_headers: &[(String, String)]) -> Vec<(String, String)> {
+        let mut headers = request_headers(operation, static_headers);
+        if let Some((name, value)) = self.parameter("header") {
+            for header in headers.iter_mut().filter(|(header, _)| header.eq_ignore_ascii_case(name)) {
+                header.1 = value.to_string();
+            }
+        }
+        if let Some(media_type) = self.media_type() {
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Accept"));
+            headers.push(("Accept".to_string(), media_type.to_string()));
+        }
+        headers.extend(cookie_header(operation).map(|cookies| ("Cookie".to_string(), cookies)));
+        headers
+    }
+
+    /// The JSON body sent, if the operation takes one, built from `variant` for a subtype of a polymorphic body
+    pub fn body(&self, operation: &ApiOperation, variant: Option<&SchemaVariant>) -> Option<Value> {
+        let mut body = request_body_example(operation, variant)?;
+        match &self.mutation {
+            Mutation::OmitProperty(property) => {
+                if let Value::Object(object) = &mut body {
+                    object.remove(property);
+                }
+            },
+            Mutation::SetProperty(property, value) => {
+                if let Value::Object(object) = &mut body {
+                    object.insert(property.clone(), value.clone());
+                }
+            },
+            Mutation::ReplaceBody(value) => return Some(value.clone()),
+            Mutation::SetParameter { .. } | Mutation::OmitCredentials | Mutation::NonexistentItem { .. } | Mutation::Accept(_) => {},
+        }
+        Some(body)
+    }
+
+    /// The status the API has to answer with: the operation's expected one for accepted requests, otherwise its
+    /// `rejection_status`. `None` means any 4xx status will do
+    pub fn expected_status<'a>(&self, operation: &'a ApiOperation) -> Option<&'a str> {
+        if self.mutation == Mutation::OmitCredentials {
+            Some(unauthorized_status(operation))
+        } else if matches!(self.mutation, Mutation::NonexistentItem { .. }) {
+            Some("404")
+        } else if self.accepted {
+            Some(expected_status(operation))
+        } else {
+            rejection_status(operation)
+        }
+    }
+
+    /// The credentials the request is sent with, out of the operation's `credentials`
+    pub fn credentials<'a>(&self, credentials: &'a [Credential]) -> &'a [Credential] {
+        if self.mutation == Mutation::OmitCredentials { &[] } else { credentials }
+    }
+
+    /// The media type the request asks for, which the response's `Content-Type` has to match
+    pub fn media_type(&self) -> Option<&str> {
+        match &self.mutation {
+            Mutation::Accept(media_type) => Some(media_type),
+            _ => None,
+        }
+    }
+
+    /// What the API has to do with the request, for test descriptions: `accepted` or `rejected`
+    pub fn outcome(&self) -> &'static str {
+        if self.accepted { "accepted" } else { "rejected" }
+    }
+
+    /// The name and value of the parameter set at `location`, if the case sets one there
+    fn parameter(&self, location: &str) -> Option<(&str, &str)> {
+        match &self.mutation {
+            Mutation::SetParameter { location: at, name, value } if at == location => Some((name, value)),
+            Mutation::NonexistentItem { name, value } if location == "path" => Some((name, value)),
+            _ => None,
+        }
+    }
+}
+
+/// The request cases of an operation enabled in `options`, for its body taken from `variant`, and those asking for each
+/// media type of its successful response when it documents several. Only operations with a JSON body or none get any,
+/// since the others' bodies aren't built from a value that can be changed; deprecated operations get none
+pub fn request_cases(operation: &ApiOperation, variant: Option<&SchemaVariant>, options: &GeneratorOptions) -> Vec<RequestCase> {
+    if operation.deprecated || operation.body_param.as_ref().is_some_and(|body| body_encoding(body) != BodyEncoding::Json) {
+        return Vec::new();
+    }
+
+    // Parameters are the same for every request of an operation, so only its first one gets their cases
+    let first_request = body_variants(operation).first().copied().flatten().map(|first| &first.name) == variant.map(|variant| &variant.name);
+    let mut cases = if first_request { negotiation_cases(operation) } else { Vec::new() };
+    let covers_statuses = options.status_coverage == StatusCoverage::All;
+    if !(options.negative_tests || options.boundary_tests || options.unauthorized_tests || covers_statuses) {
+        return cases;
+    }
+
+    let parameters: Vec<&ApiParameter> = if first_request {
+        operation.path_params.iter().chain(&operation.query_params).chain(&operation.header_params).collect()
+    } else {
+        Vec::new()
+    };
+    let schema = match variant {
+        Some(variant) => Some(&variant.schema),
+        None => operation.body_param.as_ref().and_then(|body| body.schema.as_ref()),
+    };
+    let object = match schema {
+        Some(Schema::Object(object)) => Some(object),
+        _ => None,
+    };
+
+    if options.negative_tests {
+        cases.extend(negative_cases(&parameters, object));
+    }
+    if options.boundary_tests {
+        cases.extend(boundary_cases(&parameters, object));
+    }
+    let documents = |statuses: &[&str]| operation.responses.iter().any(|resp| statuses.contains(&resp.status_code.as_str()));
+    let unauthorized = options.unauthorized_tests || (covers_statuses && documents(&["401", "403"]));
+    if unauthorized && operation.requires_auth() && first_request {
+        cases.push(RequestCase {
+            name: "unauthorized".to_string(),
+            description: "without credentials".to_string(),
+            mutation: Mutation::OmitCredentials,
+            accepted: false,
+        });
+    }
+    if covers_statuses {
+        cases.extend(status_cases(operation, &parameters, schema));
+    }
+
+    // Names that come out the same are numbered, so that every test gets its own
+    let mut names = HashSet::new();
+    for case in &mut cases {
+        let name = case.name.clone();
+        let mut number = 1;
+        while !names.insert(case.name.clone()) {
+            number += 1;
+            case.name = format!("{}_{}", name, number);
+        }
+    }
+    cases
+}
+
+/// The status negative tests of the operation expect: the documented 400 or, failing that, 422. When it documents
+/// neither, any 4xx status will do
+pub fn rejection_status(operation: &ApiOperation) -> Option<&str> {
+    ["400", "422"]
+        .into_iter()
+        .find(|status| operation.responses.iter().any(|resp| resp.status_code == *status))
+}
+
+/// The status unauthorized tests of the operation expect: the documented 401 or, failing that, 403. Operations
+/// documenting neither are expected to answer with 401
+pub fn unauthorized_status(operation: &ApiOperation) -> &str {
+    ["401", "403"]
+        .into_iter()
+        .find(|status| operation.responses.iter().any(|resp| resp.status_code == *status))
+        .unwrap_or("401")
+}
+
+/// Requests asking for each media type the response of the operation's expected status is documented in, when there are
+/// several. Wildcards such as `*/*` aren't asked for, since any `Content-Type` matches them
+fn negotiation_cases(operation: &ApiOperation) -> Vec<RequestCase> {
+    let status = expected_status(operation);
+    let media_types: Vec<&String> = operation.responses
+        .iter()
+        .filter(|resp| resp.status_code == status)
+        .flat_map(|resp| &resp.media_types)
+        .filter(|media_type| !media_type.contains('*'))
+        .collect();
+    if media_types.len() < 2 {
+        return Vec::new();
+    }
+
+    media_types
+        .into_iter()
+        .map(|media_type| RequestCase {
+            name: format!("accept_{}", identifier(media_type)),
+            description: format!("with Accept: {}", media_type),
+            mutation: Mutation::Accept(media_type.clone()),
+            accepted: true,
+        })
+        .collect()
+}
+
+/// Requests provoking the error statuses the operation documents, apart from 401 and 403: a body of the wrong type, or
+/// else a query parameter of the wrong type, for 400 or 422, and the first path parameter set to an item that doesn't
+/// exist for 404
+fn status_cases(operation: &ApiOperation, parameters: &[&ApiParameter], body: Option<&Schema>) -> Vec<RequestCase> {
+    let mut cases = Vec::new();
+
+    if rejection_status(operation).is_some() {
+        let typed_query = parameters
+            .iter()
+            .find(|param| param.location == "query" && matches!(param.param_type.as_str(), "integer" | "number" | "boolean"));
+        if operation.body_param.is_some() {
+            // Text wherever the body isn't text, and an object otherwise
+            let is_text = matches!(body, Some(Schema::Primitive(primitive)) if primitive.kind == PrimitiveType::String);
+            let value = if is_text { json!({ "invalid": true }) } else { json!("not-a-valid-body") };
+            cases.push(RequestCase {
+                name: "invalid_body".to_string(),
+                description: "with a body of the wrong type".to_string(),
+                mutation: Mutation::ReplaceBody(value),
+                accepted: false,
+            });
+        } else if let Some(param) = typed_query {
+            cases.push(RequestCase {
+                name: format!("invalid_query_{}", identifier(&param.name)),
+                description: format!("with the {} query parameter {} set to text", param.param_type, param.name),
+                mutation: Mutation::SetParameter { location: "query".to_string(), name: param.name.clone(), value: "not-a-number".to_string() },
+                accepted: false,
+            });
+        }
+    }
+
+    if operation.responses.iter().any(|resp| resp.status_code == "404") {
+        if let Some(param) = parameters.iter().find(|param| param.location == "path") {
+            let value = nonexistent_value(param);
+            cases.push(RequestCase {
+                name: "not_found".to_string(),
+                description: format!("with the nonexistent {} {}", param.name, value),
+                mutation: Mutation::NonexistentItem { name: param.name.clone(), value },
+                accepted: false,
+            });
+        }
+    }
+    cases
+}
+
+/// A value of a path parameter that no item is likely to have: the nil UUID, the largest allowed integer, or text
+fn nonexistent_value(param: &ApiParameter) -> String {
+    match (param.param_type.as_str(), param.format.as_deref()) {
+        (_, Some("uuid")) => "00000000-0000-0000-0000-000000000000".to_string(),
+        ("integer" | "number", _) => param.constraints.maximum.map_or(999_999_999, |maximum| maximum.floor() as i64).to_string(),
+        _ => "nonexistent-item".to_string(),
+    }
+}
+
+/// Requests breaking the rules of the specification, for the parameters and body properties given
+fn negative_cases(parameters: &[&ApiParameter], object: Option<&ObjectSchema>) -> Vec<RequestCase> {
+    let mut cases = Vec::new();
+
+    // Each parameter restricted by an `enum` sent with a value outside of it
+    for param in parameters {
+        if let Some(value) = outside_enum(&param.enum_values) {
+            let value = to_plain_text(&value);
+            cases.push(RequestCase {
+                name: format!("invalid_{}_{}", param.location, identifier(&param.name)),
+                description: format!("with the {} parameter {} set to {}, outside its enum", param.location, param.name, value),
+                mutation: Mutation::SetParameter { location: param.location.clone(), name: param.name.clone(), value },
+                accepted: false,
+            });
+        }
+    }
+
+    let object = match object {
+        Some(object) => object,
+        None => return cases,
+    };
+
+    // Each required property of the body left out in turn, apart from those only found in responses (`readOnly`)
+    for property in &object.required {
+        if object.properties.get(property).is_some_and(is_read_only) {
+            continue;
+        }
+        cases.push(RequestCase {
+            name: format!("missing_{}", identifier(property)),
+            description: format!("without the required property {}", property),
+            mutation: Mutation::OmitProperty(property.clone()),
+            accepted: false,
+        });
+    }
+
+    // Each property sent with a value of the wrong type: text for numbers and booleans, a number for text
+    for (property, schema) in &object.properties {
+        let primitive = match schema {
+            Schema::Primitive(primitive) if !primitive.info.read_only => primitive,
+            _ => continue,
+        };
+        let (kind, sent_as, value) = match primitive.kind {
+            PrimitiveType::Integer => ("integer", "string", json!("not-a-number")),
+            PrimitiveType::Number => ("number", "string", json!("not-a-number")),
+            PrimitiveType::Boolean => ("boolean", "string", json!("not-a-boolean")),
+            PrimitiveType::String => ("string", "integer", json!(12345)),
+        };
+        cases.push(RequestCase {
+            name: format!("{}_as_{}", identifier(property), sent_as),
+            description: format!("with the {} property {} as {} {}", kind, property, if sent_as == "integer" { "an" } else { "a" }, sent_as),
+            mutation: Mutation::SetProperty(property.clone(), value),
+            accepted: false,
+        });
+    }
+
+    // Each property restricted by an `enum` sent with a value outside of it
+    for (property, schema) in &object.properties {
+        let info = match schema.info() {
+            Some(info) if !info.read_only => info,
+            _ => continue,
+        };
+        if let Some(value) = outside_enum(&info.enum_values) {
+            cases.push(RequestCase {
+                name: format!("invalid_{}", identifier(property)),
+                description: format!("with the property {} set to {}, outside its enum", property, to_plain_text(&value)),
+                mutation: Mutation::SetProperty(property.clone(), value),
+                accepted: false,
+            });
+        }
+    }
+    cases
+}
+
+/// Requests sending each bounded parameter and body property at its bounds, which the API has to accept, and just
+/// outside of them, which it has to reject
+fn boundary_cases(parameters: &[&ApiParameter], object: Option<&ObjectSchema>) -> Vec<RequestCase> {
+    let mut cases = Vec::new();
+
+    for param in parameters {
+        let kind = match param.param_type.as_str() {
+            "integer" => PrimitiveType::Integer,
+            "number" => PrimitiveType::Number,
+            "string" if param.format.is_none() && param.enum_values.is_empty() => PrimitiveType::String,
+            _ => continue,
+        };
+        for boundary in boundaries(kind, &param.constraints) {
+            let value = to_plain_text(&boundary.value);
+            cases.push(RequestCase {
+                name: format!("{}_{}_{}", param.location, identifier(&param.name), boundary.name),
+                description: format!("with the {} parameter {} {}", param.location, param.name, boundary.description),
+                mutation: Mutation::SetParameter { location: param.location.clone(), name: param.name.clone(), value },
+                accepted: boundary.accepted,
+            });
+        }
+    }
+
+    for (property, schema) in object.map(|object| &object.properties).into_iter().flatten() {
+        let primitive = match schema {
+            Schema::Primitive(primitive) if !primitive.info.read_only => primitive,
+            _ => continue,
+        };
+        if primitive.kind == PrimitiveType::String && (primitive.info.format.is_some() || !primitive.info.enum_values.is_empty()) {
+            continue;
+        }
+        for boundary in boundaries(primitive.kind, &primitive.info.constraints) {
+            cases.push(RequestCase {
+                name: format!("{}_{}", identifier(property), boundary.name),
+                description: format!("with the property {} {}", property, boundary.description),
+                mutation: Mutation::SetProperty(property.clone(), boundary.value),
+                accepted: boundary.accepted,
+            });
+        }
+    }
+    cases
+}
+
+/// A value at or just outside one of the bounds of a number or string
+struct Boundary {
+    /// Suffix of the test name (`at_minimum`)
+    name: &'static str,
+
+    /// Where the value lies (`at its minimum, 10`)
+    description: String,
+
+    value: Value,
+
+    /// Whether the value is within the bounds
+    accepted: bool,
+}
+
+/// The values at and just outside the `minimum`/`maximum` of a number, or the `minLength`/`maxLength` of a string.
+/// Integers step by one; numbers have no value just inside an exclusive bound, and step by one outside an inclusive
+/// one. Strings with a `pattern` get none, since a run of letters wouldn't match it
+fn boundaries(kind: PrimitiveType, constraints: &Constraints) -> Vec<Boundary> {
+    let mut boundaries = Vec::new();
+    let boundary = |name, position: &str, value: Value, accepted| {
+        let description = match &value {
+            Value::String(text) => format!("{}, {} characters", position, text.chars().count()),
+            value => format!("{}, {}", position, value),
+        };
+        Boundary { name, description, value, accepted }
+    };
+
+    match kind {
+        PrimitiveType::Integer | PrimitiveType::Number => {
+            let integer = kind == PrimitiveType::Integer;
+            let number = |value: f64| if integer { Value::from(value as i64) } else { Value::from(value) };
+
+            if let Some(minimum) = constraints.minimum {
+                let minimum = if integer { minimum.ceil() } else { minimum };
+                match (constraints.exclusive_minimum, integer) {
+                    (true, true) => boundaries.push(boundary("at_minimum", "at its minimum", number(minimum + 1.0), true)),
+                    (false, _) => boundaries.push(boundary("at_minimum", "at its minimum", number(minimum), true)),
+                    (true, false) => {},
+                }
+                let below = if constraints.exclusive_minimum { minimum } else { minimum - 1.0 };
+                boundaries.push(boundary("below_minimum", "below its minimum", number(below), false));
+            }
+            if let Some(maximum) = constraints.maximum {
+                let maximum = if integer { maximum.floor() } else { maximum };
+                match (constraints.exclusive_maximum, integer) {
+                    (true, true) => boundaries.push(boundary("at_maximum", "at its maximum", number(maximum - 1.0), true)),
+                    (false, _) => boundaries.push(boundary("at_maximum", "at its maximum", number(maximum), true)),
+                    (true, false) => {},
+                }
+                let above = if constraints.exclusive_maximum { maximum } else { maximum + 1.0 };
+                boundaries.push(boundary("above_maximum", "above its maximum", number(above), false));
+            }
+        },
+        PrimitiveType::String if constraints.pattern.is_none() => {
+            let text = |length: u64| Value::String("a".repeat(length as usize));
+
+            if let Some(min_length) = constraints.min_length.filter(|length| *length <= MAX_GENERATED_LENGTH) {
+                boundaries.push(boundary("at_min_length", "at its minimum length", text(min_length), true));
+                if min_length > 0 {
+                    boundaries.push(boundary("below_min_length", "below its minimum length", text(min_length - 1), false));
+                }
+            }
+            if let Some(max_length) = constraints.max_length.filter(|length| *length < MAX_GENERATED_LENGTH) {
+                boundaries.push(boundary("at_max_length", "at its maximum length", text(max_length), true));
+                boundaries.push(boundary("above_max_length", "above its maximum length", text(max_length + 1), false));
+            }
+        },
+        PrimitiveType::String | PrimitiveType::Boolean => {},
+    }
+    boundaries
+}
+
+/// A value of the same type as the members of an `enum` but outside of it, for enums of strings or of integers
+fn outside_enum(values: &[Value]) -> Option<Value> {
+    if values.is_empty() {
+        None
+    } else if values.iter().all(Value::is_string) {
+        let mut value = "invalid-value".to_string();
+        while values.iter().any(|member| member == value.as_str()) {
+            value.push('x');
+        }
+        Some(Value::String(value))
+    } else if values.iter().all(Value::is_i64) {
+        values.iter().filter_map(Value::as_i64).max().and_then(|max| max.checked_add(1)).map(Value::from)
+    } else {
+        None
+    }
+}
+
+/// A name made of the lowercase letters and digits of a property or parameter name, with single underscores for the rest
+fn identifier(name: &str) -> String {
+    let identifier: String = snake_case(name)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    let words: Vec<&str> = identifier.split('_').filter(|word| !word.is_empty()).collect();
+    if words.is_empty() { "value".to_string() } else { words.join("_") }
+}
@@ -0,0 +1,140 @@
+// This file contains the pagination tests of `--pagination-tests`: requests for the first two pages of the results of
+// operations that page them (see `parser::pagination`), which have to differ and hold no more items than asked for.
+
+use crate::parser::{ApiOperation, ObjectSchema, Schema, SchemaVariant};
+use super::body::{body_variants, cookie_header, example_path, query_value, request_headers};
+use super::test_framework::{expected_status, GeneratorOptions};
+
+/// Items a page is asked to hold at most, few so that a handful of items already fill two pages
+pub const PAGE_LIMIT: u64 = 2;
+
+/// Usual names of the property holding a page's items, looked for before any other array property
+const ITEMS_NAMES: &[&str] = &["items", "data", "results", "content", "records", "entries", "values"];
+
+/// Usual names of the property holding the cursor of the next page
+const NEXT_CURSOR_NAMES: &[&str] = &["next_cursor", "nextCursor", "next_page_token", "nextPageToken", "next_token", "nextToken", "cursor"];
+
+/// The requests of an operation's pagination test, for its first two pages
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRequests {
+    /// The path the requests are sent to, with the values of the path parameters filled in
+    pub path: String,
+
+    /// The headers sent, as `(name, value)` pairs
+    pub headers: Vec<(String, String)>,
+
+    /// The query parameters of the first page, as `(name, value)` pairs
+    pub first_query: Vec<(String, String)>,
+
+    /// The query parameters of the second page, without its cursor for cursor pagination
+    pub second_query: Vec<(String, String)>,
+
+    /// For cursor pagination, the parameter sending the second page's cursor and the JSON pointer to it in the first
+    /// page's body
+    pub cursor: Option<(String, String)>,
+
+    /// The most items a page may hold, unchecked when the operation has no limit parameter
+    pub limit: Option<u64>,
+
+    /// JSON pointer to the items in the response bodies, empty when the body is the list of items
+    pub items_pointer: String,
+
+    /// The status both pages have to be answered with
+    pub status: String,
+}
+
+/// The requests of an operation's pagination test, when the options ask for pagination tests and it is a GET paging its
+/// results. Numbered pages are asked for from the first one, and offsets from 0 on by the limit; cursors are read from
+/// the first page's body. Only the first of the operation's body variants gets them, so that the test is generated once.
+/// `None` when the items (or the next cursor) aren't found in the documented response body, or offsets have no limit to
+/// step by
+pub fn page_requests(operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>, options: &GeneratorOptions) -> Option<PageRequests> {
+    if !options.pagination_tests || operation.deprecated || !operation.method.eq_ignore_ascii_case("GET") {
+        return None;
+    }
+    if body_variants(operation).first().copied().flatten().map(|first| first.name.as_str()) != variant.map(|variant| variant.name.as_str()) {
+        return None;
+    }
+    let pagination = operation.pagination.as_ref()?;
+    let status = expected_status(operation);
+    let schema = operation.responses.iter().find(|resp| resp.status_code == status).and_then(|resp| resp.schema.as_ref());
+    let items_pointer = pagination.items_pointer.clone().or_else(|| schema.and_then(items_pointer))?;
+
+    let limit = pagination.limit_param.as_ref().map(|name| (name.clone(), PAGE_LIMIT.to_string()));
+    let page = |name: &String, value: u64| vec![(name.clone(), value.to_string())];
+    let (first, second, cursor) = if let Some(param) = &pagination.page_param {
+        (page(param, pagination.first_page), page(param, pagination.first_page + 1), None)
+    } else if let (Some(param), Some(_)) = (&pagination.offset_param, &limit) {
+        (page(param, 0), page(param, PAGE_LIMIT), None)
+    } else if let Some(param) = &pagination.cursor_param {
+        let pointer = pagination.next_cursor_pointer.clone().or_else(|| schema.and_then(next_cursor_pointer))?;
+        (Vec::new(), Vec::new(), Some((param.clone(), pointer)))
+    } else {
+        return None;
+    };
+
+    // The pagination parameters replace the values the operation's test sends
+    let paging = [&pagination.page_param, &pagination.offset_param, &pagination.cursor_param, &pagination.limit_param];
+    let query: Vec<(String, String)> = operation.query_params
+        .iter()
+        .filter(|param| !paging.iter().any(|name| name.as_ref() == Some(&param.name)))
+        .map(|param| (param.name.clone(), query_value(param)))
+        .collect();
+    let with_query = |pages: Vec<(String, String)>| query.iter().cloned().chain(pages).chain(limit.clone()).collect();
+
+    let mut headers = request_headers(operation, &options.headers);
+    headers.extend(cookie_header(operation).map(|cookies| ("Cookie".to_string(), cookies)));
+    Some(PageRequests {
+        path: example_path(path, operation),
+        headers,
+        first_query: with_query(first),
+        second_query: with_query(second),
+        cursor,
+        limit: limit.map(|_| PAGE_LIMIT),
+        items_pointer,
+        status: status.to_string(),
+    })
+}
+
+/// The property names a JSON pointer goes through, unescaped
+pub fn pointer_segments(pointer: &str) -> Vec<String> {
+    pointer.split('/').skip(1).map(|segment| segment.replace("~1", "/").replace("~0", "~")).collect()
+}
+
+/// JSON pointer to the items of a page in a response body of `schema`: the body itself when it is an array, or else its
+/// array property with a usual name, or its first one
+fn items_pointer(schema: &Schema) -> Option<String> {
+    match schema {
+        Schema::Array(_) => Some(String::new()),
+        Schema::Object(object) => ITEMS_NAMES
+            .iter()
+            .filter_map(|name| object.properties.get_key_value(*name))
+            .chain(&object.properties)
+            .find(|(_, schema)| matches!(schema, Schema::Array(_)))
+            .map(|(name, _)| pointer_to(&[name])),
+        _ => None,
+    }
+}
+
+/// JSON pointer to the next page's cursor in a response body of `schema`: a property with a usual name, of the body or
+/// of one of its object properties (such as `meta`)
+fn next_cursor_pointer(schema: &Schema) -> Option<String> {
+    let cursor = |object: &ObjectSchema| {
+        NEXT_CURSOR_NAMES.iter().copied().find(|name| matches!(object.properties.get(*name), Some(Schema::Primitive(_))))
+    };
+    let Schema::Object(object) = schema else {
+        return None;
+    };
+    if let Some(name) = cursor(object) {
+        return Some(pointer_to(&[name]));
+    }
+    object.properties.iter().find_map(|(parent, schema)| match schema {
+        Schema::Object(nested) => cursor(nested).map(|name| pointer_to(&[parent, name])),
+        _ => None,
+    })
+}
+
+/// JSON pointer going through the property names given
+fn pointer_to(names: &[&str]) -> String {
+    names.iter().map(|name| format!("/{}", name.replace('~', "~0").replace('/', "~1"))).collect()
+}
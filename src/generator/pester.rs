@@ -0,0 +1,207 @@
+// This file contains the PowerShell test generator, writing Pester v5 test files that send their requests with Invoke-RestMethod.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, query_string, raw_body_example, request_body_example, request_headers,
+    variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{
+    expected_headers, expected_status, generate_in_parallel, pascal_case, required_properties, GeneratorOptions, Result, TestGenerator,
+};
+
+// PowerShell Pester test generator
+pub struct PesterGenerator {
+    options: GeneratorOptions,
+}
+
+impl PesterGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        PesterGenerator { options }
+    }
+
+    /// Generate the Describe block for one request of an operation, with its body taken from `variant` when given
+    fn generate_describe(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_uppercase();
+        let status = expected_status(operation);
+
+        // Parameters splatted into Invoke-RestMethod, which reports unexpected statuses rather than throwing
+        let mut params = vec![
+            ("Method", ps_string(&method)),
+            ("Uri", format!("$BaseUrl + {}", ps_string(&format!("{}{}", example_path(path, operation), query_string(operation))))),
+        ];
+
+        // Cookie parameters are sent as a `Cookie` header, and the content type is a parameter of its own
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+        let content_type = headers
+            .iter()
+            .position(|(name, _)| name.eq_ignore_ascii_case("Content-Type"))
+            .map(|index| headers.remove(index).1);
+        if !headers.is_empty() {
+            params.push(("Headers", ps_hashtable(&headers)));
+        }
+
+        match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                let fields: Vec<(String, String)> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| match value {
+                        Some(value) => (name, ps_string(&value)),
+                        None => (name, "Get-Item (Join-Path $PSScriptRoot 'upload.txt')".to_string()),
+                    })
+                    .collect();
+                params.push(("Form", ps_hashtable_raw(&fields)));
+            },
+            Some((body, BodyEncoding::Form)) => {
+                let fields: Vec<(String, String)> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| (name, value.unwrap_or_default()))
+                    .collect();
+                params.push(("ContentType", ps_string(content_type.as_deref().unwrap_or("application/x-www-form-urlencoded"))));
+                params.push(("Body", ps_hashtable(&fields)));
+            },
+            Some((body, BodyEncoding::Raw)) => {
+                params.push(("ContentType", ps_string(content_type.as_deref().unwrap_or("text/plain"))));
+                params.push(("Body", ps_string(&raw_body_example(body))));
+            },
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                params.push(("ContentType", ps_string(content_type.as_deref().unwrap_or("application/json"))));
+                params.push(("Body", ps_string(&serde_json::to_string(&example).unwrap_or_default())));
+            },
+            None => {},
+        }
+        params.push(("SkipHttpErrorCheck", "$true".to_string()));
+        params.push(("StatusCodeVariable", "'status'".to_string()));
+        params.push(("ResponseHeadersVariable", "'responseHeaders'".to_string()));
+
+        let mut lines = vec!["$params = @{".to_string()];
+        lines.extend(params.iter().map(|(name, value)| format!("    {} = {}", name, value)));
+        lines.push("}".to_string());
+        lines.push("$response = Invoke-RestMethod @params".to_string());
+        lines.push(String::new());
+
+        // Assertions on the response
+        lines.push(format!("$status | Should -Be {}", status));
+        for header in expected_headers(operation) {
+            lines.push(format!(
+                "$responseHeaders.Keys | Should -Contain {} -Because {}",
+                ps_string(header),
+                ps_string(&format!("the operation documents the {} header", header))
+            ));
+        }
+        for property in required_properties(operation) {
+            lines.push(format!("$response.PSObject.Properties.Name | Should -Contain {}", ps_string(property)));
+        }
+
+        // Deprecated operations are still generated, but skipped
+        let skip = if operation.deprecated { " -Skip" } else { "" };
+        let body = lines
+            .iter()
+            .map(|line| if line.is_empty() { String::new() } else { format!("            {}", line) })
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            r#"    Describe {name} {{
+        It {title}{skip} {{
+{body}
+        }}
+    }}"#,
+            name = ps_string(&format!("{}{}", operation.operation_id, variant_suffix(variant))),
+            title = ps_string(&format!("{} {} returns {}", method, path, status)),
+        )
+    }
+}
+
+impl TestGenerator for PesterGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // One test file per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let describes = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_describe(operation, path, variant)
+        })?;
+
+        for (group, describes) in groups.iter().zip(describes) {
+            let mut file = File::create(output_dir.join(format!("{}.Tests.ps1", pascal_case(&group.file_stem))))?;
+
+            writeln!(file, r#"#Requires -Version 7.0
+
+BeforeAll {{
+    # The API_BASE_URL environment variable overrides where the API under test runs
+    $BaseUrl = if ($env:API_BASE_URL) {{ $env:API_BASE_URL.TrimEnd('/') }} else {{ {base_url} }}
+}}
+
+Describe {tag} {{
+{describes}
+}}"#,
+                base_url = ps_string(base_url.trim_end_matches('/')),
+                tag = ps_string(&group.title()),
+                describes = describes.join("\n\n"))?;
+        }
+
+        // The file multipart requests upload
+        let uploads = groups
+            .iter()
+            .flat_map(|group| &group.operations)
+            .filter_map(|(_, operation)| operation.body_param.as_ref())
+            .any(|body| body_encoding(body) == BodyEncoding::Multipart);
+        if uploads {
+            fs::write(output_dir.join("upload.txt"), "test file content")?;
+        }
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), using Pester v5 on PowerShell 7.
+
+## Running the tests
+
+Install Pester, then run the tests against the API at `API_BASE_URL`:
+
+```
+Install-Module Pester -MinimumVersion 5.0 -Scope CurrentUser
+$env:API_BASE_URL = 'http://localhost:3000'
+Invoke-Pester -Output Detailed
+```
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// Render name/value pairs as a PowerShell hashtable of strings, one entry per line, indented as a splatted parameter
+fn ps_hashtable(entries: &[(String, String)]) -> String {
+    let entries: Vec<(String, String)> = entries.iter().map(|(name, value)| (name.clone(), ps_string(value))).collect();
+    ps_hashtable_raw(&entries)
+}
+
+/// Render names and PowerShell expressions as a hashtable, one entry per line, indented as a splatted parameter
+fn ps_hashtable_raw(entries: &[(String, String)]) -> String {
+    let entries: Vec<String> = entries
+        .iter()
+        .map(|(name, value)| format!("                    {} = {}", ps_string(name), value))
+        .collect();
+    format!("@{{\n{}\n                }}", entries.join("\n"))
+}
+
+/// Render text as a single-quoted PowerShell string, in which only `'` needs escaping
+fn ps_string(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
@@ -0,0 +1,305 @@
+// This file contains the PHP test generator, writing a Composer project of PHPUnit tests that send their requests with Guzzle.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, PrimitiveType, Schema, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, query_value, raw_body_example, request_body_example, request_headers,
+    variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{
+    expected_headers, expected_status, generate_in_parallel, pascal_case, required_response_properties, response_schema, GeneratorOptions, Result, TestGenerator,
+};
+
+// PHP PHPUnit + Guzzle test generator
+pub struct PhpunitGenerator {
+    options: GeneratorOptions,
+}
+
+impl PhpunitGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        PhpunitGenerator { options }
+    }
+
+    /// Generate the test method for one request of an operation, with its body taken from `variant` when given
+    fn generate_operation_test(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let mut lines = Vec::new();
+
+        // Deprecated operations are still generated, but skipped
+        if operation.deprecated {
+            lines.push("$this->markTestSkipped('deprecated operation');".to_string());
+            lines.push(String::new());
+        }
+
+        // Request options, as Guzzle takes them. Lines after the first of an option are indented for the method body.
+        let mut options = Vec::new();
+        let query: Vec<(String, String)> = operation.query_params.iter().map(|p| (p.name.clone(), query_value(p))).collect();
+        if !query.is_empty() {
+            options.push(("query", php_array(&query)));
+        }
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+
+        match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                let parts: Vec<String> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| match value {
+                        Some(value) => format!("                ['name' => {}, 'contents' => {}],", php_string(&name), php_string(&value)),
+                        None => format!(
+                            "                ['name' => {}, 'contents' => 'test file content', 'filename' => 'upload.txt'],",
+                            php_string(&name)
+                        ),
+                    })
+                    .collect();
+                options.push(("multipart", format!("[\n{}\n            ]", parts.join("\n"))));
+            },
+            Some((body, BodyEncoding::Form)) => {
+                let fields: Vec<(String, String)> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| (name, value.unwrap_or_default()))
+                    .collect();
+                options.push(("form_params", php_array(&fields)));
+            },
+            Some((body, BodyEncoding::Raw)) => options.push(("body", php_string(&raw_body_example(body)))),
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                options.push(("body", php_string(&serde_json::to_string(&example).unwrap_or_default())));
+                if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Content-Type")) {
+                    headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                }
+            },
+            None => {},
+        }
+
+        if !headers.is_empty() {
+            options.insert(0, ("headers", php_array(&headers)));
+        }
+
+        // Paths are relative to the client's base URI, which may have a path of its own
+        let method = php_string(&operation.method.to_uppercase());
+        let target = php_string(example_path(path, operation).trim_start_matches('/'));
+        if options.is_empty() {
+            lines.push(format!("$response = $this->client->request({}, {});", method, target));
+        } else {
+            let options: Vec<String> = options.iter().map(|(key, value)| format!("            '{}' => {},", key, value)).collect();
+            lines.push(format!("$response = $this->client->request({}, {}, [\n{}\n        ]);", method, target, options.join("\n")));
+        }
+        lines.push(String::new());
+
+        // Assertions on the response
+        lines.push(format!("$this->assertSame({}, $response->getStatusCode());", expected_status(operation)));
+        for header in expected_headers(operation) {
+            lines.push(format!(
+                "$this->assertTrue($response->hasHeader({}), {});",
+                php_string(header),
+                php_string(&format!("response header {} is missing", header))
+            ));
+        }
+        if let Some(schema) = response_schema(operation) {
+            lines.push(String::new());
+            lines.push("$body = $this->decodeJson($response);".to_string());
+            lines.extend(json_body_assertions(schema));
+        }
+
+        let body = lines
+            .iter()
+            .map(|line| if line.is_empty() { String::new() } else { format!("        {}", line) })
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            r#"    public function test{name}(): void
+    {{
+{body}
+    }}"#,
+            name = pascal_case(&format!("{}{}", operation.operation_id, variant_suffix(variant))),
+        )
+    }
+}
+
+impl TestGenerator for PhpunitGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        let tests_dir = output_dir.join("tests");
+        fs::create_dir_all(&tests_dir)?;
+
+        // One test class per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let tests = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_operation_test(operation, path, variant)
+        })?;
+
+        for (group, tests) in groups.iter().zip(tests) {
+            let class_name = format!("{}Test", pascal_case(&group.file_stem));
+            let mut file = File::create(tests_dir.join(format!("{}.php", class_name)))?;
+
+            writeln!(file, r#"<?php
+
+declare(strict_types=1);
+
+namespace ApiTests;
+
+/**
+ * Tests of {description}
+ */
+final class {class_name} extends ApiTestCase
+{{
+{tests}
+}}"#,
+                description = group.description,
+                tests = tests.join("\n\n"))?;
+        }
+
+        // The base class creating the client every test sends its requests with
+        let mut base_class = File::create(tests_dir.join("ApiTestCase.php"))?;
+
+        writeln!(base_class, r#"<?php
+
+declare(strict_types=1);
+
+namespace ApiTests;
+
+use GuzzleHttp\Client;
+use PHPUnit\Framework\TestCase;
+use Psr\Http\Message\ResponseInterface;
+
+/**
+ * Base class of the API tests, with a Guzzle client pointed at the API under test.
+ * The API_BASE_URL environment variable overrides its address.
+ */
+abstract class ApiTestCase extends TestCase
+{{
+    protected Client $client;
+
+    protected function setUp(): void
+    {{
+        $baseUrl = getenv('API_BASE_URL') ?: {base_url};
+        $this->client = new Client([
+            'base_uri' => rtrim($baseUrl, '/') . '/',
+            // Unexpected statuses are reported by the assertions, rather than thrown
+            'http_errors' => false,
+        ]);
+    }}
+
+    /**
+     * Decode the JSON body of a response, failing the test when it isn't valid JSON
+     */
+    protected function decodeJson(ResponseInterface $response): mixed
+    {{
+        try {{
+            return json_decode((string) $response->getBody(), true, 512, JSON_THROW_ON_ERROR);
+        }} catch (\JsonException $e) {{
+            $this->fail('the response body is not valid JSON: ' . $e->getMessage());
+        }}
+    }}
+}}"#,
+            base_url = php_string(base_url.trim_end_matches('/')))?;
+
+        // Create a composer.json for the project
+        let composer = serde_json::json!({
+            "name": format!("apitests/{}", spec.info.package_name("-", "tests")),
+            "description": format!("Generated API tests for {}", spec.info.title),
+            "type": "project",
+            "require-dev": {
+                "guzzlehttp/guzzle": "^7.8",
+                "phpunit/phpunit": "^10.5"
+            },
+            "autoload-dev": {
+                "psr-4": {
+                    "ApiTests\\": "tests/"
+                }
+            },
+            "scripts": {
+                "test": "phpunit"
+            }
+        });
+        fs::write(output_dir.join("composer.json"), serde_json::to_string_pretty(&composer).unwrap_or_default() + "\n")?;
+
+        // Create the PHPUnit configuration
+        let mut phpunit_file = File::create(output_dir.join("phpunit.xml"))?;
+
+        writeln!(phpunit_file, r#"<?xml version="1.0" encoding="UTF-8"?>
+<phpunit xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+         xsi:noNamespaceSchemaLocation="vendor/phpunit/phpunit/phpunit.xsd"
+         bootstrap="vendor/autoload.php"
+         colors="true">
+  <testsuites>
+    <testsuite name="api">
+      <directory>tests</directory>
+    </testsuite>
+  </testsuites>
+</phpunit>"#)?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), using PHPUnit and Guzzle.
+
+## Running the tests
+
+Install the dependencies, then run the tests against the API at `API_BASE_URL`:
+
+```
+composer install
+API_BASE_URL=http://localhost:3000 composer test
+```
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// Assertions on a decoded JSON body (`$body`): its type, and for objects the presence of the required properties
+fn json_body_assertions(schema: &Schema) -> Vec<String> {
+    if schema.info().is_some_and(|info| info.nullable) {
+        return Vec::new();
+    }
+
+    match schema {
+        Schema::Object(_) => {
+            let mut lines = vec!["$this->assertIsArray($body);".to_string()];
+            for property in required_response_properties(schema) {
+                lines.push(format!("$this->assertArrayHasKey({}, $body);", php_string(property)));
+            }
+            lines
+        },
+        Schema::Array(_) => vec![
+            "$this->assertIsArray($body);".to_string(),
+            "$this->assertTrue(array_is_list($body), 'the response body is not a JSON array');".to_string(),
+        ],
+        Schema::Primitive(primitive) => vec![match primitive.kind {
+            PrimitiveType::String => "$this->assertIsString($body);",
+            PrimitiveType::Integer => "$this->assertIsInt($body);",
+            PrimitiveType::Number => "$this->assertTrue(is_int($body) || is_float($body), 'the response body is not a number');",
+            PrimitiveType::Boolean => "$this->assertIsBool($body);",
+        }.to_string()],
+        Schema::Ref(_) | Schema::Composite(_) | Schema::Any(_) => Vec::new(),
+    }
+}
+
+/// Render name/value pairs as a PHP array literal, one entry per line, indented as a request option
+fn php_array(entries: &[(String, String)]) -> String {
+    let entries: Vec<String> = entries
+        .iter()
+        .map(|(name, value)| format!("                {} => {},", php_string(name), php_string(value)))
+        .collect();
+    format!("[\n{}\n            ]", entries.join("\n"))
+}
+
+/// Render text as a single-quoted PHP string literal, in which only `\` and `'` need escaping
+fn php_string(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
@@ -0,0 +1,236 @@
+// This file contains the Playwright test generator, writing TypeScript API tests that send their requests with Playwright's `request` fixture.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, PrimitiveType, Schema, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, js_string, query_value, raw_body_example, request_body_example,
+    request_headers, to_json_literal, variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{
+    expected_headers, expected_status, generate_in_parallel, required_response_properties, response_schema, GeneratorOptions, Result, TestGenerator,
+};
+
+// TypeScript Playwright API test generator
+pub struct PlaywrightGenerator {
+    options: GeneratorOptions,
+}
+
+impl PlaywrightGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        PlaywrightGenerator { options }
+    }
+
+    /// Generate the test for one request of an operation, with its body taken from `variant` when given
+    fn generate_operation_test(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_uppercase();
+        let summary = operation.summary.clone().unwrap_or_else(|| format!("{} {}", method, path));
+
+        // Options of the request, as `request.fetch` takes them
+        let mut options = vec![format!("    method: {},", js_string(&method))];
+        if !operation.query_params.is_empty() {
+            let params: Vec<(String, String)> = operation.query_params.iter().map(|p| (p.name.clone(), js_string(&query_value(p)))).collect();
+            options.push(format!("    params: {},", ts_object(&params)));
+        }
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+
+        match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Content-Type"));
+                let fields: Vec<(String, String)> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| match value {
+                        Some(value) => (name, js_string(&value)),
+                        None => (
+                            name,
+                            r#"{ name: "upload.txt", mimeType: "text/plain", buffer: Buffer.from("test file content") }"#.to_string(),
+                        ),
+                    })
+                    .collect();
+                options.push(format!("    multipart: {},", ts_object(&fields)));
+            },
+            Some((body, BodyEncoding::Form)) => {
+                let fields: Vec<(String, String)> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| (name, js_string(&value.unwrap_or_default())))
+                    .collect();
+                options.push(format!("    form: {},", ts_object(&fields)));
+            },
+            Some((body, BodyEncoding::Raw)) => options.push(format!("    data: {},", js_string(&raw_body_example(body)))),
+            // Playwright sends objects as JSON
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                options.push(format!("    data: {},", to_json_literal(&example, "    ")));
+            },
+            None => {},
+        }
+        if !headers.is_empty() {
+            let headers: Vec<(String, String)> = headers.into_iter().map(|(name, value)| (name, js_string(&value))).collect();
+            options.insert(1, format!("    headers: {},", ts_object(&headers)));
+        }
+
+        // Paths are relative to the base URL, which may have a path of its own
+        let mut lines = vec![
+            format!("  // {}", summary.replace('\n', " ")),
+            format!(
+                "  const response = await request.fetch({}, {{\n{}\n  }});",
+                js_string(example_path(path, operation).trim_start_matches('/')),
+                options.join("\n")
+            ),
+            String::new(),
+            format!("  expect(response.status()).toBe({});", expected_status(operation)),
+        ];
+
+        // Playwright lowercases the names of response headers
+        for header in expected_headers(operation) {
+            lines.push(format!("  expect(response.headers()[{}]).toBeDefined();", js_string(&header.to_lowercase())));
+        }
+        if let Some(schema) = response_schema(operation) {
+            let assertions = json_body_assertions(schema);
+            if !assertions.is_empty() {
+                lines.push(String::new());
+                lines.push("  const body = await response.json();".to_string());
+                lines.extend(assertions.into_iter().map(|assertion| format!("  {}", assertion)));
+            }
+        }
+
+        // Deprecated operations are still generated, but skipped
+        let test = if operation.deprecated { "test.skip" } else { "test" };
+        let body = lines
+            .iter()
+            .map(|line| if line.is_empty() { String::new() } else { format!("  {}", line).replace('\n', "\n  ") })
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "  {test}({name}, async ({{ request }}) => {{\n{body}\n  }});",
+            name = js_string(&format!("{}{}", operation.operation_id, variant_suffix(variant))),
+        )
+    }
+}
+
+impl TestGenerator for PlaywrightGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        let tests_dir = output_dir.join("tests");
+        fs::create_dir_all(&tests_dir)?;
+
+        // One spec file per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let tests = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_operation_test(operation, path, variant)
+        })?;
+
+        for (group, tests) in groups.iter().zip(tests) {
+            let mut file = File::create(tests_dir.join(format!("{}.spec.ts", group.file_stem)))?;
+
+            writeln!(file, r#"import {{ test, expect }} from '@playwright/test';
+
+test.describe({describe}, () => {{
+{tests}
+}});"#,
+                describe = js_string(&group.title()),
+                tests = tests.join("\n\n"))?;
+        }
+
+        // The configuration pointing the `request` fixture at the API under test
+        let mut config_file = File::create(output_dir.join("playwright.config.ts"))?;
+
+        writeln!(config_file, r#"import {{ defineConfig }} from '@playwright/test';
+
+// The API_BASE_URL environment variable overrides where the API under test runs.
+// Requests are relative to the base URL, so it ends with a slash.
+const baseURL = (process.env.API_BASE_URL ?? {base_url}).replace(/\/?$/, '/');
+
+export default defineConfig({{
+  testDir: './tests',
+  reporter: 'list',
+  use: {{
+    baseURL,
+  }},
+}});"#,
+            base_url = js_string(base_url.trim_end_matches('/')))?;
+
+        // Create a package.json file
+        let package = serde_json::json!({
+            "name": spec.info.package_name("-", "tests"),
+            "version": spec.info.package_version("1.0.0"),
+            "description": format!("Generated API tests for {}", spec.info.title),
+            "private": true,
+            "scripts": {
+                "test": "playwright test"
+            },
+            "devDependencies": {
+                "@playwright/test": "^1.44.0",
+                "@types/node": "^20.12.0"
+            }
+        });
+        fs::write(output_dir.join("package.json"), serde_json::to_string_pretty(&package).unwrap_or_default() + "\n")?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), using Playwright's `request` fixture.
+
+## Running the tests
+
+Install the dependencies, then run the tests against the API at `API_BASE_URL`:
+
+```
+npm install
+API_BASE_URL=http://localhost:3000 npm test
+```
+
+API tests need no browsers, so `npx playwright install` can be skipped.
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// Assertions on a parsed JSON body (`body`) with Jest-style `expect`: its type, and for objects the presence of the required properties
+pub fn json_body_assertions(schema: &Schema) -> Vec<String> {
+    if schema.info().is_some_and(|info| info.nullable) {
+        return Vec::new();
+    }
+
+    match schema {
+        Schema::Object(_) => {
+            let mut assertions = vec!["expect(body).toEqual(expect.any(Object));".to_string()];
+            for property in required_response_properties(schema) {
+                // An array of keys keeps dots in property names from being read as paths
+                assertions.push(format!("expect(body).toHaveProperty([{}]);", js_string(property)));
+            }
+            assertions
+        },
+        Schema::Array(_) => vec!["expect(Array.isArray(body)).toBe(true);".to_string()],
+        Schema::Primitive(primitive) => vec![format!("expect(typeof body).toBe({});", js_string(match primitive.kind {
+            PrimitiveType::String => "string",
+            PrimitiveType::Integer | PrimitiveType::Number => "number",
+            PrimitiveType::Boolean => "boolean",
+        }))],
+        Schema::Ref(_) | Schema::Composite(_) | Schema::Any(_) => Vec::new(),
+    }
+}
+
+/// Render names and TypeScript expressions as an object literal, one property per line
+fn ts_object(entries: &[(String, String)]) -> String {
+    let entries: Vec<String> = entries
+        .iter()
+        .map(|(name, value)| format!("      {}: {},", js_string(name), value))
+        .collect();
+    format!("{{\n{}\n    }}", entries.join("\n"))
+}
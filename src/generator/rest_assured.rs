@@ -0,0 +1,294 @@
+// This file contains the Java test generator, writing a Maven project of JUnit 5 tests that send their requests with RestAssured.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, example_for_parameter, form_fields, query_value, raw_body_example, request_body_example, request_headers, to_plain_text,
+    path_value, variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{
+    expected_headers, expected_status, generate_in_parallel, pascal_case, response_schema, xml_escape, GeneratorOptions,
+    Result, TestGenerator,
+};
+
+/// Words Java reserves, which test methods named after an operation have to avoid
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const", "continue", "default", "do", "double",
+    "else", "enum", "extends", "false", "final", "finally", "float", "for", "goto", "if", "implements", "import", "instanceof", "int",
+    "interface", "long", "native", "new", "null", "package", "private", "protected", "public", "return", "short", "static", "strictfp",
+    "super", "switch", "synchronized", "this", "throw", "throws", "transient", "true", "try", "void", "volatile", "while",
+];
+
+// Java JUnit 5 + RestAssured test generator
+pub struct RestAssuredGenerator {
+    options: GeneratorOptions,
+}
+
+impl RestAssuredGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        RestAssuredGenerator { options }
+    }
+
+    /// Generate the test method for one request of an operation, with its body taken from `variant` when given
+    fn generate_operation_test(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_uppercase();
+        let display_name = match (&operation.summary, variant) {
+            (Some(summary), Some(variant)) => format!("{} ({})", summary, variant.name),
+            (Some(summary), None) => summary.clone(),
+            (None, Some(variant)) => format!("{} {} ({})", method, path, variant.name),
+            (None, None) => format!("{} {}", method, path),
+        };
+
+        // Deprecated operations are still generated, but disabled
+        let disabled = if operation.deprecated { "\n    @Disabled(\"deprecated operation\")" } else { "" };
+
+        // Request specification
+        let mut given = Vec::new();
+        for param in &operation.path_params {
+            given.push(format!(".pathParam({}, {})", java_string(&param.name), java_string(&to_plain_text(&path_value(param)))));
+        }
+        for param in &operation.query_params {
+            given.push(format!(".queryParam({}, {})", java_string(&param.name), java_string(&query_value(param))));
+        }
+
+        // The body's media type is set through RestAssured's content type, so it isn't sent twice
+        let mut content_type = None;
+        for (name, value) in request_headers(operation, &self.options.headers) {
+            if name.eq_ignore_ascii_case("Content-Type") {
+                content_type = Some(value);
+            } else {
+                given.push(format!(".header({}, {})", java_string(&name), java_string(&value)));
+            }
+        }
+        for param in &operation.cookie_params {
+            given.push(format!(".cookie({}, {})", java_string(&param.name), java_string(&to_plain_text(&example_for_parameter(param)))));
+        }
+
+        match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                for (name, value) in form_fields(body) {
+                    given.push(match value {
+                        Some(value) => format!(".multiPart({}, {})", java_string(&name), java_string(&value)),
+                        None => format!(".multiPart({}, \"upload.txt\", \"test file content\".getBytes())", java_string(&name)),
+                    });
+                }
+            },
+            Some((body, BodyEncoding::Form)) => {
+                for (name, value) in form_fields(body) {
+                    given.push(format!(".formParam({}, {})", java_string(&name), java_string(&value.unwrap_or_default())));
+                }
+            },
+            Some((body, BodyEncoding::Raw)) => {
+                given.push(format!(".contentType({})", java_string(content_type.as_deref().unwrap_or("text/plain"))));
+                given.push(format!(".body({})", java_string(&raw_body_example(body))));
+            },
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                given.push(format!(".contentType({})", java_string(content_type.as_deref().unwrap_or("application/json"))));
+                given.push(format!(".body({})", java_string(&serde_json::to_string(&example).unwrap_or_default())));
+            },
+            None => {},
+        }
+
+        // Assertions on the response
+        let status = expected_status(operation);
+        let mut then = vec![format!(".statusCode(equalTo({}))", status)];
+        for header in expected_headers(operation) {
+            then.push(format!(".header({}, notNullValue())", java_string(header)));
+        }
+        if response_schema(operation).is_some() {
+            then.push(format!(".body(matchesJsonSchemaInClasspath(\"schemas/{}.json\"))", operation.operation_id));
+        }
+
+        let given = given.iter().map(|line| format!("\n            {}", line)).collect::<String>();
+        let then = then.iter().map(|line| format!("\n            {}", line)).collect::<String>();
+        format!(
+            r#"    @Test
+    @DisplayName({display_name}){disabled}
+    void {name}() {{
+        given(){given}
+        .when()
+            .request({method}, {path})
+        .then(){then};
+    }}"#,
+            display_name = java_string(&display_name),
+            name = method_name(&format!("{}{}", operation.operation_id, variant_suffix(variant))),
+            method = java_string(&method),
+            path = java_string(path),
+        )
+    }
+}
+
+impl TestGenerator for RestAssuredGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        let package_dir = output_dir.join("src").join("test").join("java").join("apitests");
+        let schema_dir = output_dir.join("src").join("test").join("resources").join("schemas");
+        fs::create_dir_all(&package_dir)?;
+
+        // One test class per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let tests = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_operation_test(operation, path, variant)
+        })?;
+
+        for (group, tests) in groups.iter().zip(tests) {
+            let class_name = format!("{}Test", pascal_case(&group.file_stem));
+            let mut file = File::create(package_dir.join(format!("{}.java", class_name)))?;
+
+            writeln!(file, r#"package apitests;
+
+import static io.restassured.RestAssured.given;
+import static io.restassured.module.jsv.JsonSchemaValidator.matchesJsonSchemaInClasspath;
+import static org.hamcrest.Matchers.equalTo;
+import static org.hamcrest.Matchers.notNullValue;
+
+import org.junit.jupiter.api.Disabled;
+import org.junit.jupiter.api.DisplayName;
+import org.junit.jupiter.api.Test;
+
+/**
+ * Tests of {description}
+ */
+class {class_name} extends ApiTest {{
+
+{tests}
+}}"#,
+                description = group.description,
+                tests = tests.join("\n\n"))?;
+
+            // The response schemas the tests validate against are read from the classpath
+            for (_, operation) in &group.operations {
+                if let Some(schema) = response_schema(operation) {
+                    fs::create_dir_all(&schema_dir)?;
+                    let schema_json = serde_json::to_string_pretty(&schema.to_json_schema()).unwrap_or_default();
+                    fs::write(schema_dir.join(format!("{}.json", operation.operation_id)), schema_json + "\n")?;
+                }
+            }
+        }
+
+        // The base class pointing RestAssured at the API under test
+        let mut base_class = File::create(package_dir.join("ApiTest.java"))?;
+
+        writeln!(base_class, r#"package apitests;
+
+import io.restassured.RestAssured;
+import org.junit.jupiter.api.BeforeAll;
+
+/**
+ * Points RestAssured at the API under test, which the API_BASE_URL environment variable overrides
+ */
+abstract class ApiTest {{
+
+    @BeforeAll
+    static void configureBaseUri() {{
+        String baseUrl = System.getenv("API_BASE_URL");
+        RestAssured.baseURI = baseUrl != null && !baseUrl.isEmpty() ? baseUrl : {base_url};
+        RestAssured.enableLoggingOfRequestAndResponseIfValidationFails();
+    }}
+}}"#,
+            base_url = java_string(base_url.trim_end_matches('/')))?;
+
+        // Create a pom.xml for the Maven project
+        let mut pom_file = File::create(output_dir.join("pom.xml"))?;
+
+        writeln!(pom_file, r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0"
+         xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+         xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd">
+  <modelVersion>4.0.0</modelVersion>
+
+  <groupId>apitests</groupId>
+  <artifactId>{artifact_id}</artifactId>
+  <version>{version}</version>
+  <name>{name}</name>
+
+  <properties>
+    <maven.compiler.release>17</maven.compiler.release>
+    <project.build.sourceEncoding>UTF-8</project.build.sourceEncoding>
+  </properties>
+
+  <dependencies>
+    <dependency>
+      <groupId>org.junit.jupiter</groupId>
+      <artifactId>junit-jupiter</artifactId>
+      <version>5.10.2</version>
+      <scope>test</scope>
+    </dependency>
+    <dependency>
+      <groupId>io.rest-assured</groupId>
+      <artifactId>rest-assured</artifactId>
+      <version>5.4.0</version>
+      <scope>test</scope>
+    </dependency>
+    <dependency>
+      <groupId>io.rest-assured</groupId>
+      <artifactId>json-schema-validator</artifactId>
+      <version>5.4.0</version>
+      <scope>test</scope>
+    </dependency>
+    <dependency>
+      <groupId>org.hamcrest</groupId>
+      <artifactId>hamcrest</artifactId>
+      <version>2.2</version>
+      <scope>test</scope>
+    </dependency>
+  </dependencies>
+
+  <build>
+    <plugins>
+      <plugin>
+        <groupId>org.apache.maven.plugins</groupId>
+        <artifactId>maven-surefire-plugin</artifactId>
+        <version>3.2.5</version>
+      </plugin>
+    </plugins>
+  </build>
+</project>"#,
+            artifact_id = spec.info.package_name("-", "tests"),
+            version = spec.info.package_version("1.0.0"),
+            name = xml_escape(&format!("{} Tests", spec.info.title)))?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), using JUnit 5 and RestAssured.
+
+## Running the tests
+
+Run the tests against the API at `API_BASE_URL`:
+
+```
+API_BASE_URL=http://localhost:3000 mvn test
+```
+
+Response bodies are validated against the JSON schemas in `src/test/resources/schemas`.
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// A Java method name for a test, which can't be one of Java's keywords
+fn method_name(name: &str) -> String {
+    if JAVA_KEYWORDS.contains(&name) {
+        format!("{}Test", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Render text as a Java string literal. JSON string escapes are valid in Java.
+fn java_string(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
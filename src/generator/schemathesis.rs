@@ -0,0 +1,203 @@
+// This file contains the Schemathesis generator, writing a command and hooks that fuzz the API from its specification.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use crate::parser::{SecuritySchemeKind, SwaggerSpec};
+use super::curl::shell_quote;
+use super::test_framework::{GeneratorOptions, Result, TestGenerator};
+
+/// The checks every response has to pass
+const CHECKS: &[&str] = &[
+    "not_a_server_error",
+    "status_code_conformance",
+    "content_type_conformance",
+    "response_headers_conformance",
+    "response_schema_conformance",
+];
+
+// Schemathesis configuration and hooks generator
+pub struct SchemathesisGenerator {
+    options: GeneratorOptions,
+}
+
+impl SchemathesisGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        SchemathesisGenerator { options }
+    }
+
+    /// Generate the hooks module, with an auth class setting the credentials of the API's security schemes on every request
+    fn generate_hooks(&self, spec: &SwaggerSpec) -> String {
+        let header = format!("\"\"\"Schemathesis hooks for {}, loaded through SCHEMATHESIS_HOOKS\"\"\"\n", spec.info.title);
+        if spec.security_schemes.is_empty() {
+            return format!("{}\n# The API documents no security schemes, so requests are sent without credentials\n", header);
+        }
+
+        // Bearer tokens and basic credentials both go in `Authorization`, so the basic ones are only sent without a token
+        let names = |matches: fn(&SecuritySchemeKind) -> bool| {
+            spec.security_schemes.iter().filter(|scheme| matches(&scheme.kind)).map(|scheme| scheme.name.as_str()).collect::<Vec<_>>()
+        };
+        let token_schemes = names(|kind| matches!(
+            kind,
+            SecuritySchemeKind::Bearer { .. } | SecuritySchemeKind::OAuth2 { .. } | SecuritySchemeKind::OpenIdConnect { .. }
+        ));
+        let basic_schemes = names(|kind| matches!(kind, SecuritySchemeKind::Basic));
+
+        let mut statements = Vec::new();
+        if !token_schemes.is_empty() {
+            statements.push(format!(
+                "        # {}\n        if data[\"API_TOKEN\"]:\n            case.headers[\"Authorization\"] = f\"Bearer {{data['API_TOKEN']}}\"",
+                token_schemes.join(", ")
+            ));
+        }
+        if !basic_schemes.is_empty() {
+            statements.push(format!(
+                "        # {}\n        {} data[\"API_USERNAME\"] and data[\"API_PASSWORD\"]:\n            credentials = f\"{{data['API_USERNAME']}}:{{data['API_PASSWORD']}}\".encode()\n            case.headers[\"Authorization\"] = f\"Basic {{base64.b64encode(credentials).decode()}}\"",
+                basic_schemes.join(", "),
+                if token_schemes.is_empty() { "if" } else { "elif" }
+            ));
+        }
+        for scheme in &spec.security_schemes {
+            match &scheme.kind {
+                SecuritySchemeKind::ApiKey { name, location } => {
+                    let target = match location.as_str() {
+                        "query" => "query",
+                        "cookie" => "cookies",
+                        _ => "headers",
+                    };
+                    statements.push(format!(
+                        "        # {}\n        if data[\"API_KEY\"]:\n            case.{}[{}] = data[\"API_KEY\"]",
+                        scheme.name,
+                        target,
+                        serde_json::to_string(name).unwrap_or_default()
+                    ));
+                },
+                SecuritySchemeKind::Other(kind) => {
+                    statements.push(format!("        # {}: {} credentials can't be set from the environment", scheme.name, kind));
+                },
+                _ => {},
+            }
+        }
+
+        let imports = if basic_schemes.is_empty() { "import os" } else { "import base64\nimport os" };
+        format!(r#"{header}{imports}
+
+import schemathesis
+
+
+@schemathesis.auth()
+class ApiAuth:
+    """
+    Credentials for the documented security schemes, read from environment variables; schemes without them are left out
+    """
+
+    def get(self, case, context):
+        return {{name: os.environ.get(name) for name in ("API_TOKEN", "API_USERNAME", "API_PASSWORD", "API_KEY")}}
+
+    def set(self, case, data, context):
+        case.headers = case.headers or {{}}
+        case.query = case.query or {{}}
+        case.cookies = case.cookies or {{}}
+{statements}
+"#,
+            statements = statements.join("\n"))
+    }
+}
+
+impl TestGenerator for SchemathesisGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // Schemathesis generates the requests from the specification itself
+        fs::write(output_dir.join("openapi.json"), serde_json::to_string_pretty(&*spec.raw_spec).unwrap_or_default() + "\n")?;
+
+        fs::write(output_dir.join("hooks.py"), self.generate_hooks(spec))?;
+
+        // The command running Schemathesis, with the checks, the number of examples to generate per operation, and as many
+        // workers as generator threads. Deprecated operations aren't fuzzed
+        let script_path = output_dir.join("run-schemathesis.sh");
+        let mut script = File::create(&script_path)?;
+
+        let checks: Vec<String> = CHECKS.iter().map(|check| format!("  {}", check)).collect();
+        let headers: String = self.options.headers.iter()
+            .map(|(name, value)| format!("  --header {} \\\n", shell_quote(&format!("{}: {}", name, value))))
+            .collect();
+        writeln!(script, r#"#!/usr/bin/env bash
+# Fuzz {title} (version {version}) with Schemathesis, generating requests from openapi.json
+set -euo pipefail
+cd "$(dirname "$0")"
+
+# Where the API under test runs
+BASE_URL="${{API_BASE_URL:-{base_url}}}"
+
+# The checks every response has to pass
+CHECKS=(
+{checks}
+)
+
+checks=()
+for check in "${{CHECKS[@]}}"; do
+  checks+=(--checks "$check")
+done
+
+SCHEMATHESIS_HOOKS=hooks exec schemathesis run openapi.json \
+  --base-url "$BASE_URL" \
+  "${{checks[@]}}" \
+{headers}  --hypothesis-max-examples "${{MAX_EXAMPLES:-50}}" \
+  --workers "${{WORKERS:-{workers}}}" \
+  --exclude-deprecated \
+  "$@""#,
+            title = spec.info.title,
+            version = spec.info.version,
+            base_url = base_url.trim_end_matches('/'),
+            checks = checks.join("\n"),
+            workers = self.options.jobs.map_or("auto".to_string(), |jobs| jobs.to_string()))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&script_path, fs::Permissions::from_mode(0o755))?;
+        }
+
+        // Create a requirements.txt file
+        let mut requirements_file = File::create(output_dir.join("requirements.txt"))?;
+
+        writeln!(requirements_file, "schemathesis>=3.30,<4")?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Fuzzing
+
+Property-based tests for {title} (version {version}) with [Schemathesis](https://schemathesis.readthedocs.io),
+which generates requests for every operation from `openapi.json` and checks the responses conform to it.
+
+## Running the tests
+
+Install the requirements, then run the script against the API at `API_BASE_URL`:
+
+```
+pip install -r requirements.txt
+API_BASE_URL=http://localhost:3000 ./run-schemathesis.sh
+```
+
+Extra arguments are passed on to `schemathesis run`, e.g. `--include-operation-id getUser` to fuzz a single operation.
+`MAX_EXAMPLES` sets how many requests are generated per operation and `WORKERS` how many are sent at once.
+The checks are listed at the top of the script.
+
+## Authentication
+
+`hooks.py` adds credentials to every request from these environment variables, when set:
+
+- `API_TOKEN`: a bearer token, for bearer, OAuth2 and OpenID Connect schemes
+- `API_USERNAME` and `API_PASSWORD`: for HTTP basic authentication
+- `API_KEY`: for API keys
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
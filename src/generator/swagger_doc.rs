@@ -253,7 +253,7 @@ pub fn update_swagger_spec(mut spec: Value) -> Result<Value> {
     // Example modifications that could be made to an existing spec
     
     // Add security definitions if they don't exist
-    if !spec.get("securityDefinitions").is_some() {
+    if spec.get("securityDefinitions").is_none() {
         let security_defs = json!({
             "api_key": {
                 "type": "apiKey",
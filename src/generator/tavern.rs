@@ -0,0 +1,214 @@
+// This file contains the Tavern test generator, writing YAML tests that pytest runs with the Tavern plugin.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use serde_yaml::{Mapping, Value as Yaml};
+use crate::parser::{ApiOperation, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, query_value, raw_body_example, request_body_example, request_headers,
+    variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{
+    expected_headers, expected_status, generate_in_parallel, required_properties, GeneratorOptions, Result, TestGenerator,
+};
+
+/// Stand-in for Tavern's `!anything` tag, which serde_yaml can't write, replaced once the YAML is rendered
+const ANYTHING: &str = "!anything";
+
+// Tavern YAML test generator
+pub struct TavernGenerator {
+    options: GeneratorOptions,
+}
+
+impl TavernGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        TavernGenerator { options }
+    }
+
+    /// Generate the test document for one request of an operation, with its body taken from `variant` when given
+    fn generate_test(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_uppercase();
+        let summary = operation.summary.clone().unwrap_or_else(|| format!("{} {}", method, path));
+
+        // Tavern formats every string of a request, so literal braces are doubled
+        let text = |text: &str| Yaml::String(text.replace('{', "{{").replace('}', "}}"));
+
+        let mut request = vec![
+            ("url", Yaml::String(format!("{{tavern.env_vars.API_BASE_URL}}{}", example_path(path, operation).replace('{', "{{").replace('}', "}}")))),
+            ("method", Yaml::String(method.clone())),
+        ];
+        if !operation.query_params.is_empty() {
+            let params = operation.query_params.iter().map(|p| (p.name.as_str(), text(&query_value(p)))).collect();
+            request.push(("params", mapping(params)));
+        }
+
+        // Cookie parameters are sent as a `Cookie` header
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+
+        let mut body = Vec::new();
+        match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((param, BodyEncoding::Multipart)) => {
+                headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Content-Type"));
+                let fields = form_fields(param);
+                let data: Vec<_> = fields.iter().filter_map(|(name, value)| Some((name.as_str(), text(value.as_ref()?)))).collect();
+                let files: Vec<_> = fields
+                    .iter()
+                    .filter(|(_, value)| value.is_none())
+                    .map(|(name, _)| (name.as_str(), Yaml::String("upload.txt".to_string())))
+                    .collect();
+                if !data.is_empty() {
+                    body.push(("data", mapping(data)));
+                }
+                body.push(("files", mapping(files)));
+            },
+            Some((param, BodyEncoding::Form)) => {
+                let fields = form_fields(param);
+                let data = fields.iter().map(|(name, value)| (name.as_str(), text(value.as_deref().unwrap_or_default()))).collect();
+                body.push(("data", mapping(data)));
+            },
+            Some((param, BodyEncoding::Raw)) => body.push(("data", text(&raw_body_example(param)))),
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                body.push(("json", escape_braces(serde_yaml::to_value(&example).unwrap_or(Yaml::Null))));
+            },
+            None => {},
+        }
+        if !headers.is_empty() {
+            request.push(("headers", mapping(headers.iter().map(|(name, value)| (name.as_str(), text(value))).collect())));
+        }
+        request.extend(body);
+
+        // Checks of the response: its status, documented headers, and the required properties of its body
+        let status = expected_status(operation);
+        let mut response = vec![("status_code", Yaml::Number(status.parse::<u64>().unwrap_or(200).into()))];
+        let headers = expected_headers(operation);
+        if !headers.is_empty() {
+            response.push(("headers", mapping(headers.into_iter().map(|name| (name, Yaml::String(ANYTHING.to_string()))).collect())));
+        }
+        let checks: Vec<Yaml> = required_properties(operation)
+            .into_iter()
+            .map(|property| mapping(vec![
+                ("function", Yaml::String("tavern.helpers:check_jmespath_match".to_string())),
+                ("extra_kwargs", mapping(vec![("query", Yaml::String(serde_json::to_string(property).unwrap_or_default()))])),
+            ]))
+            .collect();
+        if !checks.is_empty() {
+            response.push(("verify_response_with", Yaml::Sequence(checks)));
+        }
+
+        let stage = mapping(vec![
+            ("name", Yaml::String(summary)),
+            ("request", mapping(request)),
+            ("response", mapping(response)),
+        ]);
+        let mut test = vec![("test_name", Yaml::String(format!("{}{}", operation.operation_id, variant_suffix(variant))))];
+
+        // Deprecated operations are still generated, but skipped
+        if operation.deprecated {
+            test.push(("marks", Yaml::Sequence(vec![Yaml::String("skip".to_string())])));
+        }
+        test.push(("stages", Yaml::Sequence(vec![stage])));
+
+        serde_yaml::to_string(&mapping(test))
+            .unwrap_or_default()
+            .replace(&format!("\"{}\"", ANYTHING), ANYTHING)
+            .replace(&format!("'{}'", ANYTHING), ANYTHING)
+    }
+}
+
+impl TestGenerator for TavernGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        let tests_dir = output_dir.join("tests");
+        fs::create_dir_all(&tests_dir)?;
+
+        // One file of test documents per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let tests = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_test(operation, path, variant)
+        })?;
+
+        // Tavern only collects files named `test_*.tavern.yaml`
+        for (group, tests) in groups.iter().zip(tests) {
+            let mut file = File::create(tests_dir.join(format!("test_{}.tavern.yaml", group.file_stem)))?;
+
+            writeln!(file, "# {}\n{}", group.title(), tests.iter().map(|test| test.trim_end()).collect::<Vec<_>>().join("\n"))?;
+        }
+
+        // The file multipart requests upload, read from the directory pytest runs in
+        let uploads = groups
+            .iter()
+            .flat_map(|group| &group.operations)
+            .filter_map(|(_, operation)| operation.body_param.as_ref())
+            .any(|body| body_encoding(body) == BodyEncoding::Multipart);
+        if uploads {
+            fs::write(output_dir.join("upload.txt"), "test file content")?;
+        }
+
+        // The pytest configuration, and a conftest.py giving the base URL a default
+        let mut pytest_ini = File::create(output_dir.join("pytest.ini"))?;
+
+        writeln!(pytest_ini, "[pytest]\ntestpaths = tests")?;
+
+        let mut conftest = File::create(output_dir.join("conftest.py"))?;
+
+        writeln!(conftest, r#"import os
+
+# The API_BASE_URL environment variable overrides where the API under test runs
+os.environ.setdefault("API_BASE_URL", {base_url})"#,
+            base_url = serde_json::to_string(base_url.trim_end_matches('/')).unwrap_or_default())?;
+
+        // Create a requirements.txt file
+        let mut requirements_file = File::create(output_dir.join("requirements.txt"))?;
+
+        writeln!(requirements_file, "tavern>=2.0.0")?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), as Tavern YAML tests.
+
+## Running the tests
+
+Install the requirements, then run the tests against the API at `API_BASE_URL`:
+
+```
+pip install -r requirements.txt
+API_BASE_URL=http://localhost:3000 pytest
+```
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// A YAML mapping of the entries, in order
+fn mapping(entries: Vec<(&str, Yaml)>) -> Yaml {
+    let mut mapping = Mapping::new();
+    for (key, value) in entries {
+        mapping.insert(Yaml::String(key.to_string()), value);
+    }
+    Yaml::Mapping(mapping)
+}
+
+/// Double the braces of every string in a YAML value, which Tavern would otherwise read as format fields
+fn escape_braces(value: Yaml) -> Yaml {
+    match value {
+        Yaml::String(text) => Yaml::String(text.replace('{', "{{").replace('}', "}}")),
+        Yaml::Sequence(items) => Yaml::Sequence(items.into_iter().map(escape_braces).collect()),
+        Yaml::Mapping(entries) => Yaml::Mapping(entries.into_iter().map(|(key, value)| (key, escape_braces(value))).collect()),
+        other => other,
+    }
+}
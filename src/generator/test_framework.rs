@@ -1,9 +1,54 @@
 use std::path::Path;
+use std::time::Duration;
 use std::fs::{self, File};
 use std::io::Write;
+use rayon::prelude::*;
 
-use crate::parser::{SwaggerSpec, ApiOperation};
+use crate::parser::{
+    SwaggerSpec, ApiLink, ApiOperation, ArraySchema, LinkValue, ObjectSchema, PrimitiveType, Schema, SchemaVariant, SecurityRequirement,
+    SecuritySchemeKind,
+};
 use crate::cli::args::TestFramework;
+use super::go::GoGenerator;
+use super::rest_assured::RestAssuredGenerator;
+use super::xunit::XunitGenerator;
+use super::phpunit::PhpunitGenerator;
+use super::karate::KarateGenerator;
+use super::k6::K6Generator;
+use super::locust::LocustGenerator;
+use super::jmeter::JmeterGenerator;
+use super::hurl::HurlGenerator;
+use super::http_file::HttpFileGenerator;
+use super::curl::CurlGenerator;
+use super::pester::PesterGenerator;
+use super::playwright::PlaywrightGenerator;
+use super::vitest::VitestGenerator;
+use super::tavern::TavernGenerator;
+use super::gherkin::GherkinGenerator;
+use super::wiremock::WiremockGenerator;
+use super::msw::MswGenerator;
+use super::http_file::MULTIPART_BOUNDARY;
+use super::insomnia::InsomniaGenerator;
+use super::bruno::BrunoGenerator;
+use super::schemathesis::SchemathesisGenerator;
+use super::mocha::MochaGenerator;
+use super::deno::DenoGenerator;
+use super::layout::{operation_groups, tag_file_stem, webhook_operations, OutputLayout};
+use super::links::{has_cleanups, has_setups, incoming_link, outgoing_links, resources, Cleanup, LinkedSetup, Resources};
+use super::negative::{request_cases, Mutation, RequestCase, StatusCoverage};
+use super::data::with_seed;
+use super::fuzz::{body_strategy, is_fuzzed, query_strategies, FUZZ_CASES};
+use super::lifecycle::{lifecycles, Action, Lifecycle};
+use super::auth::{client_credentials_url, operation_credentials, used_credentials, AuthConfig, Credential};
+use super::datasets::{data_cases, DataSetError, DataSets};
+use super::pagination::{page_requests, pointer_segments, PageRequests};
+use super::environments::{default_environment, readme_section, write_env_files, BASE_URL_VAR, ENVIRONMENT_VAR};
+use super::body::{
+    body_encoding, body_variants, cookie_header, custom_content_type, body_example, documented_example, example_for_parameter, example_path, fixed_headers,
+    form_fields, has_multipart_body, is_write_only, path_value, percent_encode, query_value, raw_body_example, request_body_example, request_headers, specified_value, to_json_literal, to_plain_text,
+    to_python_literal, variant_suffix, BodyEncoding,
+};
+use serde_json::{json, Value};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -16,6 +61,12 @@ pub enum GeneratorError {
     
     #[error("Unsupported framework: {0:?}")]
     UnsupportedFramework(TestFramework),
+    
+    #[error("Thread pool error: {0}")]
+    ThreadPoolError(#[from] rayon::ThreadPoolBuildError),
+    
+    #[error("{0}")]
+    DataSetError(#[from] DataSetError),
 }
 
 pub type Result<T> = std::result::Result<T, GeneratorError>;
@@ -26,125 +77,737 @@ pub trait TestGenerator {
     fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()>;
 }
 
+/// Options shared by all test generators
+#[derive(Debug, Clone, Default)]
+pub struct GeneratorOptions {
+    /// How tests are split into files (or Postman folders), the framework's usual layout when `None`
+    pub layout: Option<OutputLayout>,
+    
+    /// Leave deprecated operations out, instead of generating their tests marked as skipped
+    pub skip_deprecated: bool,
+    
+    /// Number of threads generating tests, one per CPU when `None`
+    pub jobs: Option<usize>,
+    
+    /// Number of virtual users load tests simulate, 10 when `None`
+    pub virtual_users: Option<u32>,
+    
+    /// How long load tests run, 30 seconds when `None`
+    pub duration: Option<Duration>,
+    
+    /// Also generate negative tests, sending requests the API has to reject (see `negative`)
+    pub negative_tests: bool,
+    
+    /// Also generate boundary tests, sending values at and just outside the bounds of parameters and body properties
+    pub boundary_tests: bool,
+    
+    /// Also generate unauthorized tests, calling secured operations without credentials
+    pub unauthorized_tests: bool,
+    
+    /// Also generate pagination tests, asking operations that page their results for two pages (see `pagination`)
+    pub pagination_tests: bool,
+    
+    /// Credentials generated tests authenticate with, read from environment variables (see `auth`)
+    pub auth: AuthConfig,
+    
+    /// Seed picking the synthesized values of request bodies, which get the first candidates when `None` (see `data`)
+    pub seed: Option<u64>,
+    
+    /// Generate property-based tests, drawing request bodies and query parameters from their schemas (see `fuzz`)
+    pub fuzz: bool,
+    
+    /// Which documented statuses get a test, the first 2xx only by default (see `negative`)
+    pub status_coverage: StatusCoverage,
+    
+    /// Longest the API may take to answer the request of an operation's test, in milliseconds, unchecked when `None`
+    pub max_response_time_ms: Option<u64>,
+    
+    /// Also send the optional query parameters, instead of the required ones only
+    pub optional_query_params: bool,
+    
+    /// Only generate tests for operations with one of these tags, or for all of them when empty
+    pub include_tags: Vec<String>,
+    
+    /// Leave out operations on paths matching one of these glob patterns, e.g. `/admin/**`
+    pub exclude_paths: Vec<String>,
+    
+    /// Only generate tests for operations with one of these HTTP methods, or for all of them when empty
+    pub methods: Vec<String>,
+    
+    /// How much of the response an operation's test checks, its documented headers and schema by default
+    pub assert_level: AssertLevel,
+    
+    /// Times tests send a request again after a connection error or a 408, 429 or 5xx status, none when 0
+    pub retries: u32,
+    
+    /// How long tests wait before retrying a request, twice as long before each next retry, 500 ms when `None`
+    pub retry_backoff_ms: Option<u64>,
+    
+    /// How long tests wait for the API to answer a request before failing it, in seconds, forever when `None`
+    pub timeout_secs: Option<u64>,
+    
+    /// Headers sent with every request, e.g. a tenant or tracing header, replacing header parameters of the same name
+    pub headers: Vec<(String, String)>,
+    
+    /// Environments tests can run against, as `(name, base URL)` pairs, each getting a `.env.<name>` file tests read
+    /// their base URL and credentials from at runtime (see `environments`). The base URL is written into the tests when empty
+    pub environments: Vec<(String, String)>,
+    
+    /// Requests of a data file, which operations get a data-driven test parametrized over (see `datasets`)
+    pub data_sets: DataSets,
+}
+
+/// How much of the response to an operation's request its test checks, beyond its status
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AssertLevel {
+    /// The status only
+    Status,
+    
+    /// The documented headers, and that the body matches the documented schema (for HEAD and OPTIONS, that there is
+    /// no body and that the allowed methods are listed)
+    #[default]
+    Schema,
+    
+    /// As `Schema`, and that the body is the example documented for the response, when there is one
+    Exact,
+}
+
+impl AssertLevel {
+    /// Whether tests check more of the response than its status
+    pub fn checks_response(&self) -> bool {
+        *self != AssertLevel::Status
+    }
+}
+
+impl GeneratorOptions {
+    /// The options with `layout` chosen when they choose none, for generators whose usual layout isn't per path
+    pub fn or_layout(&self, layout: OutputLayout) -> GeneratorOptions {
+        GeneratorOptions { layout: Some(self.layout.unwrap_or(layout)), ..self.clone() }
+    }
+}
+
+/// A generator synthesizing its values with a seed, and sending only the required query parameters unless told
+/// otherwise. Data sets are checked against the whole specification first, since they may set optional query parameters
+struct PreparedGenerator {
+    generator: Box<dyn TestGenerator>,
+    seed: Option<u64>,
+    optional_query_params: bool,
+    data_sets: DataSets,
+}
+
+impl TestGenerator for PreparedGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        self.data_sets.check(spec)?;
+        if self.optional_query_params {
+            return with_seed(self.seed, || self.generator.generate_tests(spec, output_dir, base_url));
+        }
+        let spec = without_optional_query_params(spec);
+        with_seed(self.seed, || self.generator.generate_tests(&spec, output_dir, base_url))
+    }
+}
+
+/// The specification with the optional query parameters of its operations left out
+fn without_optional_query_params(spec: &SwaggerSpec) -> SwaggerSpec {
+    let mut spec = spec.clone();
+    for operation in spec.paths.iter_mut().flat_map(|path| &mut path.operations) {
+        operation.query_params.retain(|param| param.required);
+    }
+    spec
+}
+
 /// Factory function to create a test generator based on the framework
 pub fn create_generator(framework: TestFramework) -> Result<Box<dyn TestGenerator>> {
+    create_generator_with_options(framework, &GeneratorOptions::default())
+}
+
+/// Factory function to create a test generator based on the framework, configured with `options`
+pub fn create_generator_with_options(framework: TestFramework, options: &GeneratorOptions) -> Result<Box<dyn TestGenerator>> {
+    Ok(Box::new(PreparedGenerator {
+        generator: framework_generator(framework, options.clone()),
+        seed: options.seed,
+        optional_query_params: options.optional_query_params,
+        data_sets: options.data_sets.clone(),
+    }))
+}
+
+/// The generator of the framework
+fn framework_generator(framework: TestFramework, options: GeneratorOptions) -> Box<dyn TestGenerator> {
     match framework {
-        TestFramework::Reqwest => Ok(Box::new(ReqwestGenerator::new())),
-        TestFramework::Pytest => Ok(Box::new(PytestGenerator::new())),
-        TestFramework::Jest => Ok(Box::new(JestGenerator::new())),
-        TestFramework::Postman => Ok(Box::new(PostmanGenerator::new())),
+        TestFramework::Reqwest => Box::new(ReqwestGenerator::new(options)),
+        TestFramework::Pytest => Box::new(PytestGenerator::new(options)),
+        TestFramework::Jest => Box::new(JestGenerator::new(options)),
+        TestFramework::Postman => Box::new(PostmanGenerator::new(options)),
+        TestFramework::Go => Box::new(GoGenerator::new(options)),
+        TestFramework::RestAssured => Box::new(RestAssuredGenerator::new(options)),
+        TestFramework::Xunit => Box::new(XunitGenerator::new(options)),
+        TestFramework::Phpunit => Box::new(PhpunitGenerator::new(options)),
+        TestFramework::Karate => Box::new(KarateGenerator::new(options)),
+        TestFramework::K6 => Box::new(K6Generator::new(options)),
+        TestFramework::Locust => Box::new(LocustGenerator::new(options)),
+        TestFramework::Jmeter => Box::new(JmeterGenerator::new(options)),
+        TestFramework::Hurl => Box::new(HurlGenerator::new(options)),
+        TestFramework::HttpFile => Box::new(HttpFileGenerator::new(options)),
+        TestFramework::Curl => Box::new(CurlGenerator::new(options)),
+        TestFramework::Pester => Box::new(PesterGenerator::new(options)),
+        TestFramework::Playwright => Box::new(PlaywrightGenerator::new(options)),
+        TestFramework::Vitest => Box::new(VitestGenerator::new(options)),
+        TestFramework::Tavern => Box::new(TavernGenerator::new(options)),
+        TestFramework::Gherkin => Box::new(GherkinGenerator::new(options)),
+        TestFramework::Wiremock => Box::new(WiremockGenerator::new(options)),
+        TestFramework::Msw => Box::new(MswGenerator::new(options)),
+        TestFramework::Insomnia => Box::new(InsomniaGenerator::new(options)),
+        TestFramework::Bruno => Box::new(BrunoGenerator::new(options)),
+        TestFramework::PytestAsync => Box::new(PytestGenerator::new_async(options)),
+        TestFramework::Ureq => Box::new(ReqwestGenerator::new_blocking(options)),
+        TestFramework::Schemathesis => Box::new(SchemathesisGenerator::new(options)),
+        TestFramework::Mocha => Box::new(MochaGenerator::new(options)),
+        TestFramework::Deno => Box::new(DenoGenerator::new(options)),
+    }
+}
+
+/// How long tests wait before the first retry of a request unless the options say otherwise, in milliseconds
+const DEFAULT_RETRY_BACKOFF_MS: u64 = 500;
+
+/// Receiver that webhook tests deliver to unless the `WEBHOOK_URL` environment variable says otherwise
+const DEFAULT_WEBHOOK_URL: &str = "http://localhost:8080/webhooks";
+
+/// Convert camelCase to snake_case for Rust and Python function naming conventions
+pub fn snake_case(name: &str) -> String {
+    name.chars().fold(String::new(), |mut acc, c| {
+        if c.is_uppercase() {
+            // Add underscore before uppercase letters, but not at the beginning
+            if !acc.is_empty() {
+                acc.push('_');
+            }
+            acc.push(c.to_lowercase().next().unwrap());
+        } else {
+            acc.push(c);
+        }
+        acc
+    })
+}
+
+/// Convert a snake_case name such as `pet_store` into PascalCase such as `PetStore`, for class and type names
+pub fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// The documented successful (2xx and 3xx) statuses of an operation, or the status it's expected to answer with
+/// when it documents none
+pub fn success_statuses(operation: &ApiOperation) -> Vec<String> {
+    let statuses: Vec<String> = operation.responses
+        .iter()
+        .map(|resp| resp.status_code.as_str())
+        .filter(|status| status.len() == 3 && status.chars().all(|c| c.is_ascii_digit()))
+        .filter(|status| status.starts_with('2') || status.starts_with('3'))
+        .map(str::to_string)
+        .collect();
+    
+    if statuses.is_empty() {
+        vec![expected_status(operation).to_string()]
+    } else {
+        statuses
+    }
+}
+
+/// Escape text for use in XML content and attribute values
+pub fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// The status a webhook receiver is expected to answer with: the first documented 2xx, or 200
+fn webhook_status(operation: &ApiOperation) -> &str {
+    operation.responses
+        .iter()
+        .map(|resp| resp.status_code.as_str())
+        .find(|status| status.starts_with('2'))
+        .unwrap_or("200")
+}
+
+/// The status a successful call of the operation answers with: the first documented 2xx (ranges such as `2XX` don't count),
+/// otherwise 201 for POST, 204 for DELETE and 200 for the other methods
+pub fn expected_status(operation: &ApiOperation) -> &str {
+    operation.responses
+        .iter()
+        .map(|resp| resp.status_code.as_str())
+        .find(|status| status.starts_with('2') && status.len() == 3 && status.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or(match operation.method.to_uppercase().as_str() {
+            "POST" => "201",
+            "DELETE" => "204",
+            _ => "200",
+        })
+}
+
+/// The schema of the JSON body the operation's expected response returns, if documented. Responses to HEAD have no body
+/// to check, only their status and headers
+pub fn response_schema(operation: &ApiOperation) -> Option<&Schema> {
+    if operation.method.eq_ignore_ascii_case("HEAD") {
+        return None;
+    }
+    let status = expected_status(operation);
+    operation.responses
+        .iter()
+        .find(|resp| resp.status_code == status)
+        .and_then(|resp| resp.schema.as_ref())
+}
+
+/// The required properties of the JSON object the operation's expected response returns
+pub fn required_properties(operation: &ApiOperation) -> Vec<&str> {
+    response_schema(operation).map(required_response_properties).unwrap_or_default()
+}
+
+/// The required properties a response body of the schema has, when it is a JSON object that can't be null
+pub fn required_response_properties(schema: &Schema) -> Vec<&str> {
+    match schema {
+        // Properties only sent in requests aren't part of responses
+        Schema::Object(object) if !object.info.nullable => object.required
+            .iter()
+            .filter(|property| !is_write_only(Some(schema), property))
+            .map(String::as_str)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Names of the headers documented on the response with the expected status, which tests check are returned
+pub fn expected_headers(operation: &ApiOperation) -> Vec<&str> {
+    let status = expected_status(operation);
+    operation.responses
+        .iter()
+        .find(|resp| resp.status_code == status)
+        .map(|resp| resp.headers.iter().map(|header| header.name.as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// The JSON Schema the body of the operation's response is validated against: that of the first documented 2xx
+/// response, which the tests expect, when it restricts the body at all. Responses to HEAD have no body to validate
+fn response_validation_schema(operation: &ApiOperation) -> Option<Value> {
+    if operation.method.eq_ignore_ascii_case("HEAD") {
+        return None;
+    }
+    let schema = operation.responses.iter().find(|resp| resp.status_code.starts_with('2'))?.schema.as_ref()?;
+    let mut json_schema = match schema.to_json_schema() {
+        Value::Object(json_schema) if !json_schema.is_empty() => json_schema,
+        _ => return None,
+    };
+    json_schema.insert("$schema".to_string(), Value::from("http://json-schema.org/draft-04/schema#"));
+    Some(Value::Object(json_schema))
+}
+
+/// The body the response to the operation has to be at the `Exact` assertion level: the example documented for the
+/// response the tests expect, or for its schema. Responses to HEAD have no body to compare, and those to OPTIONS are
+/// checked for the methods they allow instead
+fn response_example(operation: &ApiOperation) -> Option<Value> {
+    if operation.method.eq_ignore_ascii_case("HEAD") || operation.method.eq_ignore_ascii_case("OPTIONS") {
+        return None;
+    }
+    let response = operation.responses.iter().find(|resp| resp.status_code == expected_status(operation))?;
+    response.example.as_ref().or_else(|| response.schema.as_ref()?.example()).cloned()
+}
+
+/// Write the schema each operation's response body is validated against to `schemas/<operationId>.json`, returning
+/// whether there were any
+fn write_response_schemas(operations: &[(&str, &ApiOperation)], output_dir: &Path) -> Result<bool> {
+    let schema_dir = output_dir.join("schemas");
+    let mut written = false;
+    for (_, operation) in operations {
+        if let Some(schema) = response_validation_schema(operation) {
+            fs::create_dir_all(&schema_dir)?;
+            let schema = serde_json::to_string_pretty(&schema).unwrap_or_default();
+            fs::write(schema_dir.join(format!("{}.json", operation.operation_id)), schema + "\n")?;
+            written = true;
+        }
+    }
+    Ok(written)
+}
+
+/// Rust code deserializing a response body, parsed with `parse_json`, into a type following its schema, so the test fails
+/// on missing properties and values of the wrong type, then checking the values of enum properties. Objects become a
+/// struct declared in the test, with optional fields for the properties that may be left out or null
+fn typed_body_check(schema: &Schema, parse_json: &str) -> Option<String> {
+    if schema.info().is_some_and(|info| info.nullable) {
+        return None;
+    }
+    
+    let (declaration, body_type, checks) = match schema {
+        Schema::Object(object) if !object.properties.is_empty() => {
+            let (declaration, checks) = response_struct("ResponseBody", object, "body");
+            (declaration, "ResponseBody".to_string(), checks)
+        },
+        Schema::Array(ArraySchema { items: Some(items), .. }) => match items.as_ref() {
+            Schema::Object(object) if !object.properties.is_empty() && !object.info.nullable => {
+                let (declaration, checks) = response_struct("ResponseItem", object, "item");
+                let checks: Vec<String> = if checks.is_empty() {
+                    Vec::new()
+                } else {
+                    let checks: String = checks.iter().map(|check| format!("\n        {}", check)).collect();
+                    vec![format!("for item in &body {{{}\n    }}", checks)]
+                };
+                (declaration, "Vec<ResponseItem>".to_string(), checks)
+            },
+            items if !items.info().is_some_and(|info| info.nullable) => (String::new(), format!("Vec<{}>", rust_field_type(items)), Vec::new()),
+            _ => (String::new(), "Vec<serde_json::Value>".to_string(), Vec::new()),
+        },
+        Schema::Object(_) => (String::new(), "serde_json::Map<String, serde_json::Value>".to_string(), Vec::new()),
+        Schema::Array(_) => (String::new(), "Vec<serde_json::Value>".to_string(), Vec::new()),
+        Schema::Primitive(_) => (String::new(), rust_field_type(schema).to_string(), Vec::new()),
+        Schema::Ref(_) | Schema::Composite(_) | Schema::Any(_) => return None,
+    };
+    
+    let binding = if checks.is_empty() { "_body" } else { "body" };
+    let checks: String = checks.iter().map(|check| format!("\n    {}", check)).collect();
+    Some(format!(
+        "    // Verify the response body matches its schema{}\n    let {}: {} = {}.expect(\"Response body doesn't match its schema\");{}",
+        declaration, binding, body_type, parse_json, checks
+    ))
+}
+
+/// Declaration of a struct named `name` deserializing objects of the schema, and checks of the enum properties of the
+/// value bound to `binding`
+fn response_struct(name: &str, object: &ObjectSchema, binding: &str) -> (String, Vec<String>) {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for",
+        "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static", "struct", "trait",
+        "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro", "override",
+        "priv", "try", "typeof", "unsized", "virtual", "yield",
+    ];
+    
+    let mut fields = Vec::new();
+    let mut checks = Vec::new();
+    let mut idents = std::collections::HashSet::new();
+    for (property, schema) in &object.properties {
+        let info = schema.info();
+        if info.is_some_and(|info| info.write_only) {
+            continue;
+        }
+        
+        let mut ident: String = snake_case(property)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+            ident = format!("field_{}", ident);
+        }
+        // Properties whose names come out the same are left unchecked
+        if !idents.insert(ident.clone()) || ident == "self" || ident == "super" || ident == "crate" {
+            continue;
+        }
+        let rename = if &ident == property { String::new() } else { format!("        #[serde(rename = {:?})]\n", property) };
+        let field = if KEYWORDS.contains(&ident.as_str()) { format!("r#{}", ident) } else { ident };
+        
+        let nullable = info.is_some_and(|info| info.nullable);
+        let required = object.required.contains(property) && !nullable;
+        let field_type = if required { rust_field_type(schema).to_string() } else { format!("Option<{}>", rust_field_type(schema)) };
+        fields.push(format!("{}        {}: {},", rename, field, field_type));
+        
+        // Values of required string enums have to be one of those documented
+        let values: Option<Vec<&str>> = info
+            .filter(|info| required && !info.enum_values.is_empty())
+            .and_then(|info| info.enum_values.iter().map(Value::as_str).collect());
+        if let (Some(values), Schema::Primitive(_)) = (values, schema) {
+            let message = format!("Unexpected {}: {{}}", property.replace('{', "{{").replace('}', "}}"));
+            checks.push(format!("assert!({:?}.contains(&{binding}.{field}.as_str()), {:?}, {binding}.{field});", values, message));
+        }
+    }
+    
+    let declaration = format!(
+        "\n    #[derive(Debug, Deserialize)]\n    #[allow(dead_code)]\n    struct {} {{\n{}\n    }}",
+        name,
+        fields.join("\n")
+    );
+    (declaration, checks)
+}
+
+/// The Rust type deserializing values of a schema, with nested objects and arrays of them kept as JSON
+fn rust_field_type(schema: &Schema) -> &'static str {
+    match schema {
+        Schema::Primitive(primitive) => match primitive.kind {
+            PrimitiveType::String => "String",
+            PrimitiveType::Integer => "i64",
+            PrimitiveType::Number => "f64",
+            PrimitiveType::Boolean => "bool",
+        },
+        Schema::Array(_) => "Vec<serde_json::Value>",
+        Schema::Object(_) | Schema::Ref(_) | Schema::Composite(_) | Schema::Any(_) => "serde_json::Value",
     }
 }
 
-// Rust reqwest test generator
-struct ReqwestGenerator;
+/// Every operation to generate tests for, each with the path it is declared on
+fn all_operations<'a>(spec: &'a SwaggerSpec, options: &GeneratorOptions) -> Vec<(&'a str, &'a ApiOperation)> {
+    operation_groups(spec, options)
+        .into_iter()
+        .flat_map(|group| group.operations)
+        .collect()
+}
+
+/// Generate the code for every body variant of the operations in each group, on the threads set in `options`.
+/// The code comes back in the order of the groups and their operations, so the output doesn't depend on scheduling.
+pub fn generate_in_parallel<'a, F>(
+    groups: &[Vec<(&'a str, &'a ApiOperation)>],
+    options: &GeneratorOptions,
+    generate: F,
+) -> Result<Vec<Vec<String>>>
+where
+    F: Fn(&'a str, &'a ApiOperation, Option<&'a SchemaVariant>) -> String + Sync,
+{
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(options.jobs.unwrap_or(0)).build()?;
+    
+    Ok(pool.install(|| {
+        groups
+            .par_iter()
+            .map(|operations| {
+                operations
+                    .par_iter()
+                    .flat_map_iter(|&(path, operation)| body_variants(operation).into_iter().map(move |variant| (path, operation, variant)))
+                    .map(|(path, operation, variant)| with_seed(options.seed, || generate(path, operation, variant)))
+                    .collect()
+            })
+            .collect()
+    }))
+}
+
+// Rust reqwest test generator, or with `blocking` set, one writing synchronous tests sending their requests with ureq
+struct ReqwestGenerator {
+    options: GeneratorOptions,
+    blocking: bool,
+}
 
 impl ReqwestGenerator {
-    pub fn new() -> Self {
-        ReqwestGenerator
+    pub fn new(options: GeneratorOptions) -> Self {
+        ReqwestGenerator { options, blocking: false }
+    }
+    
+    pub fn new_blocking(options: GeneratorOptions) -> Self {
+        ReqwestGenerator { options, blocking: true }
+    }
+    
+    /// Code sending `request`, which `call` completes: with ureq, the method sending it (`.call()`, `.send_json(&body)`);
+    /// with reqwest, what is left to add, such as its body. Requests are sent again after transient failures when the
+    /// options ask for retries, and reqwest requests failing to be sent panic with `failure`
+    fn send_request(&self, request: &str, call: &str, failure: &str) -> String {
+        let request = format!("{}{}", request, self.timeout_apply());
+        match (self.blocking, self.options.retries > 0) {
+            (true, false) => format!("send({}{})", request, call),
+            (true, true) => format!("send(with_retries(|| {}{}))", request, call),
+            (false, false) => format!("{}{}\n        .send()\n        .await\n        .expect({:?})", request, call, failure),
+            (false, true) => format!("with_retries({}{})\n        .await\n        .expect({:?})", request, call, failure),
+        }
+    }
+    
+    /// The builder calls setting the options' static headers, each following `separator`, for the requests that don't
+    /// take their headers from `request_headers` (setup requests and cleanups)
+    fn static_headers_apply(&self, separator: &str) -> String {
+        let header_method = if self.blocking { "set" } else { "header" };
+        self.options.headers.iter()
+            .map(|(name, value)| format!("{}.{}({:?}, {:?})", separator, header_method, name, value))
+            .collect()
     }
     
-    fn generate_operation_test(&self, operation: &ApiOperation, path: &str, base_url: &str) -> String {
+    /// A `&str` expression for the URL `path` makes up under `base_url`, which is `{BASE_URL}` when the options configure
+    /// environments, and the URL has to be formatted
+    fn url_literal(&self, base_url: &str, path: &str) -> String {
+        if self.options.environments.is_empty() {
+            format!("{:?}", format!("{}{}", base_url, path))
+        } else {
+            format!("&format!({:?})", format!("{}{}", base_url, path.replace('{', "{{").replace('}', "}}")))
+        }
+    }
+    
+    /// The builder call giving up on a request after the options' timeout, if any
+    fn timeout_apply(&self) -> String {
+        self.options.timeout_secs
+            .map(|secs| format!("\n        .timeout(std::time::Duration::from_secs({}))", secs))
+            .unwrap_or_default()
+    }
+    
+    fn generate_operation_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        resources: &Resources,
+        credentials: &[Credential],
+    ) -> String {
         let method = operation.method.to_lowercase();
         let operation_id = &operation.operation_id;
+        let setup = resources.setup.as_ref();
         
-        // Convert camelCase to snake_case for Rust function naming convention
-        let snake_case_operation_id = operation_id.chars().fold(String::new(), |mut acc, c| {
-            if c.is_uppercase() {
-                // Add underscore before uppercase letters, but not at the beginning
-                if !acc.is_empty() {
-                    acc.push('_');
-                }
-                acc.push(c.to_lowercase().next().unwrap());
-            } else {
-                acc.push(c);
-            }
-            acc
-        });
+        let snake_case_operation_id = snake_case(operation_id) + &variant_suffix(variant);
         
         let summary = operation.summary.as_deref().unwrap_or("");
         
-        // Special handling for operations that require a specific user ID
-        let needs_user_creation = operation.path_params.iter().any(|p| p.name == "id") && 
-                                 (method == "get" || method == "put" || method == "delete");
-        
-        let path_params_decl = if needs_user_creation {
-            // Create a test user first if this operation needs a specific user ID
-            let test_name = match method.as_str() {
-                "get" => "\"Get User Test\"",
-                "put" => "\"Update Test\"",
-                "delete" => "\"Delete Test\"",
-                _ => "\"Test User\"",
-            };
-            
-            let test_email = match method.as_str() {
-                "get" => "\"get_test@example.com\"",
-                "put" => "\"update_test@example.com\"",
-                "delete" => "\"delete_test@example.com\"",
-                _ => "\"test@example.com\"",
-            };
-            
-            format!("    // Create a test user first\n    let id = create_test_user({}, {}).await;", test_name, test_email)
-        } else {
-            operation.path_params.iter()
-                .map(|p| format!("    let {} = 1; // TODO: Replace with actual test value for {}", p.name, p.name))
-                .collect::<Vec<_>>()
-                .join("\n")
+        // Deprecated operations are still generated, but not run by default
+        let ignore = if operation.deprecated { "\n#[ignore = \"deprecated operation\"]" } else { "" };
+        
+        // Parameters supplied by a link are taken from the response of a setup request
+        let (link_setup, linked) = match setup {
+            Some(setup) => (
+                self.generate_link_setup(setup, operation, base_url, resources.setup_cleanup.as_ref()),
+                setup.linked_params(operation),
+            ),
+            None => (String::new(), Vec::new()),
         };
+        let is_linked = |name: &str| linked.iter().any(|(p, _)| p.name == name);
+        
+        // Path parameters not supplied by the setup request get their example values
+        let mut decls = vec![link_setup].into_iter().filter(|setup| !setup.is_empty()).collect::<Vec<_>>();
+        decls.extend(operation.path_params.iter()
+            .filter(|p| !is_linked(&p.name))
+            .map(|p| match specified_value(p) {
+                Some(_) => format!("    let {} = {};", p.name, to_json_literal(&path_value(p), "")),
+                None => format!("    let {} = {}; // TODO: Replace with actual test value for {}", p.name, to_json_literal(&path_value(p), ""), p.name),
+            }));
+        let path_params_decl = decls.join("\n");
             
         let query_params = if !operation.query_params.is_empty() {
             "    let query_params = [".to_string() + &operation.query_params.iter()
-                .map(|p| format!(r#"        ("{}", "test_value")"#, p.name))
+                .map(|p| if is_linked(&p.name) {
+                    format!(r#"        ("{}", {}.as_str())"#, p.name, p.name)
+                } else {
+                    format!(r#"        ("{}", {:?})"#, p.name, query_value(p))
+                })
                 .collect::<Vec<_>>()
                 .join(",\n") + "\n    ];"
         } else {
             "".to_string()
         };
         
-        let body_param = if method == "put" {
-            r#"    let body = json!({
-        "name": "Updated Name",
-        "email": "updated@example.com"
-    });"#.to_string()
-        } else if operation.body_param.is_some() {
-            r#"    let body = json!({
-        "name": "Test User",
-        "email": "test@example.com"
-    });"#.to_string()
+        let encoding = operation.body_param.as_ref().map(|body| (body, body_encoding(body)));
+        let documented_body = operation.body_param.as_ref().and_then(documented_example);
+        let json_body = request_body_example(operation, variant);
+        
+        let body_param = if let (Some((body, BodyEncoding::Multipart)), true) = (encoding, self.blocking) {
+            // ureq has no multipart support, so the body is written out by hand
+            let mut form = String::new();
+            for (name, value) in form_fields(body) {
+                match value {
+                    Some(value) => form.push_str(&format!(
+                        "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                        MULTIPART_BOUNDARY, name, value
+                    )),
+                    None => form.push_str(&format!(
+                        "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"upload.txt\"\r\nContent-Type: text/plain\r\n\r\ntest file content\r\n",
+                        MULTIPART_BOUNDARY, name
+                    )),
+                }
+            }
+            form.push_str(&format!("--{}--\r\n", MULTIPART_BOUNDARY));
+            format!("    let form = {:?};", form)
+        } else if let Some((body, BodyEncoding::Multipart)) = encoding {
+            let parts = form_fields(body)
+                .into_iter()
+                .map(|(name, value)| match value {
+                    Some(value) => format!("\n        .text({:?}, {:?})", name, value),
+                    None => format!(
+                        "\n        .part({:?}, reqwest::multipart::Part::bytes(std::fs::read(&upload_path).expect(\"Failed to read upload file\")).file_name(\"upload.txt\"))",
+                        name
+                    ),
+                })
+                .collect::<String>();
+            format!(
+                r#"    let upload_path = std::env::temp_dir().join("swagger_test_upload.txt");
+    std::fs::write(&upload_path, "test file content").expect("Failed to write upload file");
+    let form = reqwest::multipart::Form::new(){};"#,
+                parts
+            )
+        } else if let Some((body, BodyEncoding::Form)) = encoding {
+            let fields = form_fields(body)
+                .into_iter()
+                .map(|(name, value)| format!("\n        ({:?}, {:?}),", name, value.unwrap_or_default()))
+                .collect::<String>();
+            format!("    let form = [{}\n    ];", fields)
+        } else if let Some((body, BodyEncoding::Raw)) = encoding {
+            format!("    let body = {:?};", raw_body_example(body))
+        } else if let Some(example) = &json_body {
+            format!("    let body = json!({});", to_json_literal(example, "    "))
         } else {
             "".to_string()
         };
             
-        // Create path with parameter interpolation
-        let mut endpoint_path = path.to_string();
-        for param in &operation.path_params {
-            endpoint_path = endpoint_path.replace(&format!("{{{}}}", param.name), &format!("{{{}}}", param.name));
-        }
+        // Path parameters use the same `{name}` syntax as the spec, so the path can be interpolated as-is
+        let endpoint_path = path;
         
-        let client_method = match method.as_str() {
-            "get" => "client.get(&url)",
-            "post" => {
-                if operation.body_param.is_some() {
-                    "client.post(&url).json(&body)"
-                } else {
-                    "client.post(&url)"
-                }
-            },
-            "put" => {
-                if operation.body_param.is_some() {
-                    "client.put(&url).json(&body)"
-                } else {
-                    "client.put(&url)"
-                }
+        // Bodies with a hand-set `Content-Type` are sent as text, the header comes from `request_headers`
+        let body_apply = match encoding {
+            Some((_, BodyEncoding::Multipart)) => ".multipart(form)",
+            Some((_, BodyEncoding::Form)) => ".form(&form)",
+            Some((_, BodyEncoding::Raw)) => ".body(body)",
+            Some((body, BodyEncoding::Json)) if custom_content_type(body).is_some() => ".body(body.to_string())",
+            Some((_, BodyEncoding::Json)) => ".json(&body)",
+            None => "",
+        };
+        
+        // ureq sends the body with the call finishing the request, so it goes after the query and headers
+        let (body_apply, send_call) = match (self.blocking, method.as_str(), encoding) {
+            (false, _, _) => (body_apply, String::new()),
+            (true, "post" | "put" | "patch", Some((_, BodyEncoding::Multipart))) => (
+                "",
+                format!("\n        .set(\"Content-Type\", \"multipart/form-data; boundary={}\")\n        .send_string(form)", MULTIPART_BOUNDARY),
+            ),
+            (true, "post" | "put" | "patch", Some((_, BodyEncoding::Form))) => ("", "\n        .send_form(&form)".to_string()),
+            (true, "post" | "put" | "patch", Some((_, BodyEncoding::Raw))) => ("", "\n        .send_string(body)".to_string()),
+            (true, "post" | "put" | "patch", Some((body, BodyEncoding::Json))) if custom_content_type(body).is_some() => {
+                ("", "\n        .send_string(&body.to_string())".to_string())
             },
-            "delete" => "client.delete(&url)",
-            _ => "client.get(&url)",
+            (true, "post" | "put" | "patch", Some((_, BodyEncoding::Json))) => ("", "\n        .send_json(&body)".to_string()),
+            (true, _, _) => ("", "\n        .call()".to_string()),
         };
         
-        let query_params_apply = if !operation.query_params.is_empty() {
-            ".query(&query_params)"
-        } else {
-            ""
+        let client_method = match method.as_str() {
+            "get" => "client.get(&url)".to_string(),
+            "post" => format!("client.post(&url){}", body_apply),
+            "put" => format!("client.put(&url){}", body_apply),
+            "patch" => format!("client.patch(&url){}", body_apply),
+            "delete" => "client.delete(&url)".to_string(),
+            "head" => "client.head(&url)".to_string(),
+            _ if self.blocking => format!("client.request({:?}, &url)", method.to_uppercase()),
+            _ => format!("client.request(reqwest::Method::{}, &url)", method.to_uppercase()),
+        };
+        
+        let query_params_apply = match (operation.query_params.is_empty(), self.blocking) {
+            (true, _) => "",
+            (false, false) => ".query(&query_params)",
+            (false, true) => ".query_pairs(query_params)",
+        };
+        
+        // ureq sends cookie parameters as a `Cookie` header
+        let mut header_values = request_headers(operation, &self.options.headers);
+        if self.blocking {
+            header_values.extend(cookie_header(operation).map(|cookies| ("Cookie".to_string(), cookies)));
+        }
+        let header_method = if self.blocking { "set" } else { "header" };
+        let headers_apply = header_values.iter()
+            .map(|(name, value)| format!("\n        .{}({:?}, {:?})", header_method, name, value))
+            .collect::<String>() + &self.credentials_apply(credentials);
+        
+        // Cookie parameters go through a cookie store scoped to the request URL
+        let client_setup = match cookie_header(operation) {
+            _ if self.blocking => format!("let client = ureq::agent();\n    let url = format!(\"{}{}\");", base_url, endpoint_path),
+            Some(cookies) => {
+                let cookies = cookies
+                    .split("; ")
+                    .map(|cookie| format!("\n    cookies.add_cookie_str({:?}, &url.parse().unwrap());", cookie))
+                    .collect::<String>();
+                format!(
+                    r#"let url = format!("{}{}");
+    let cookies = std::sync::Arc::new(reqwest::cookie::Jar::default());{}
+    let client = reqwest::Client::builder()
+        .cookie_provider(cookies)
+        .build()
+        .expect("Failed to build client");"#,
+                    base_url, endpoint_path, cookies
+                )
+            },
+            None => format!("let client = reqwest::Client::new();\n    let url = format!(\"{}{}\");", base_url, endpoint_path),
         };
         
         let mut expected_status = "200";
@@ -162,108 +825,825 @@ impl ReqwestGenerator {
             }
         }
         
+        // Headers documented on the expected response must be returned, unless the test only checks the status
+        let expected_response = operation.responses.iter().find(|resp| resp.status_code == expected_status);
+        let expected_headers = expected_response
+            .filter(|_| self.options.assert_level.checks_response())
+            .map(|resp| resp.headers.as_slice())
+            .unwrap_or_default();
+        let header_assertions = expected_headers.iter()
+            .map(|h| if self.blocking {
+                format!("\n    assert!(response.header({:?}).is_some(), \"Expected a {} header\");", h.name, h.name)
+            } else {
+                format!("\n    assert!(response.headers().contains_key({:?}), \"Expected a {} header\");", h.name, h.name)
+            })
+            .collect::<String>();
+        
+        // The user's fields sent in the request are expected back, unless the response never includes them (`writeOnly`)
+        let response_schema = expected_response.and_then(|resp| resp.schema.as_ref());
+        let echo_assertions = || {
+            ["name", "email"].into_iter()
+                .filter_map(|name| json_body.as_ref()?.get(name)?.as_str().map(|value| (name, value)))
+                .filter(|(name, _)| !is_write_only(response_schema, name))
+                .map(|(name, value)| format!("\n    assert_eq!(user.{}, {:?});", name, value))
+                .collect::<String>()
+        };
+        
+        // How the response body is parsed, and the status read
+        let (mut parse_json, status) = if self.blocking {
+            ("response.into_json()", "status()")
+        } else {
+            ("response.json().await", "status().as_u16()")
+        };
+        
+        // What the request created is deleted once the test is done, with the values read out of the response first
+        let mut created_cleanup = String::new();
+        if let Some(cleanup) = resources.cleanup.as_ref() {
+            let values: Vec<&LinkValue> = cleanup.params().into_iter().map(|(_, value)| value).collect();
+            if values.iter().any(|value| matches!(value, LinkValue::ResponseHeader(_))) && self.blocking {
+                created_cleanup.push_str("\n    let created_headers: std::collections::HashMap<String, String> = response.headers_names()\n        .into_iter()\n        .filter_map(|name| Some((name.to_lowercase(), response.header(&name)?.to_string())))\n        .collect();");
+            } else if values.iter().any(|value| matches!(value, LinkValue::ResponseHeader(_))) {
+                created_cleanup.push_str("\n    let created_headers = response.headers().clone();");
+            }
+            if values.iter().any(|value| matches!(value, LinkValue::ResponseBody(_))) {
+                created_cleanup.push_str(&format!("\n    let created: serde_json::Value = {}.unwrap_or_default();", parse_json));
+                parse_json = "serde_json::from_value(created.clone())";
+            }
+            created_cleanup.push_str(&self.generate_cleanup(cleanup, base_url, "created", "created_headers"));
+        }
+        
+        // At the exact level, the body has to be the documented example; it is parsed once, for the checks below too
+        let example_check = match response_example(operation) {
+            Some(example) if self.options.assert_level == AssertLevel::Exact => {
+                let check = format!(
+                    "\n    // Verify the response body is the documented example\n    let actual: serde_json::Value = {}.expect(\"Failed to parse response\");\n    assert_eq!(actual, json!({}));",
+                    parse_json, to_json_literal(&example, "    ")
+                );
+                parse_json = "serde_json::from_value(actual)";
+                check
+            },
+            _ => String::new(),
+        };
+        
+        // Bodies with a documented schema are deserialized into a type following it
+        let schema_check = response_schema
+            .and_then(|schema| typed_body_check(schema, parse_json))
+            .map(|check| format!("\n{}", check))
+            .unwrap_or_default();
+        
         // Additional verification for delete operation
-        let additional_verification = if method == "delete" {
-            format!(r#"
+        let additional_verification = if !self.options.assert_level.checks_response() {
+            String::new()
+        } else if method == "head" && self.blocking {
+            r#"
+    // HEAD responses have no body
+    assert!(response.into_string().unwrap_or_default().is_empty(), "Expected no body in a HEAD response");"#.to_string()
+        } else if method == "head" {
+            r#"
+    // HEAD responses have no body
+    assert!(response.bytes().await.expect("Failed to read response").is_empty(), "Expected no body in a HEAD response");"#.to_string()
+        } else if method == "options" && self.blocking {
+            r#"
+    // Verify the allowed methods are listed
+    assert!(response.header("Allow").or(response.header("Access-Control-Allow-Methods")).is_some(), "Expected an Allow header");"#.to_string()
+        } else if method == "options" {
+            r#"
+    // Verify the allowed methods are listed
+    let headers = response.headers();
+    assert!(headers.contains_key("Allow") || headers.contains_key("Access-Control-Allow-Methods"), "Expected an Allow header");"#.to_string()
+        } else if setup.is_some() && method != "delete" {
+            // The checks below assume the sample user API, which linked operations aren't
+            schema_check
+        } else if method == "delete" && self.blocking {
+            r#"
+    // Verify the user is deleted by trying to get it
+    let get_response = send(client.get(&url).call());
+        
+    assert_eq!(get_response.status(), 404);"#.to_string()
+        } else if method == "delete" {
+            r#"
     // Verify the user is deleted by trying to get it
     let get_response = client.get(&url)
         .send()
         .await
         .expect("Failed to send GET request");
         
-    assert_eq!(get_response.status().as_u16(), 404);"#)
-        } else if method == "get" && operation.path_params.iter().any(|p| p.name == "id") {
-            // Add verification for get user by ID
-            r#"
-    // Verify the response body contains the right data
-    let user: User = response.json().await.expect("Failed to parse response");
-    assert_eq!(user.id, id);"#.to_string()
-        } else if method == "put" {
+    assert_eq!(get_response.status().as_u16(), 404);"#.to_string()
+        } else if method == "get" && !schema_check.is_empty() {
+            schema_check
+        } else if method == "put" && documented_body.is_none() {
             // Add verification for update user
-            r#"
+            format!(r#"
     // Verify the response body
-    let user: User = response.json().await.expect("Failed to parse response");
-    assert_eq!(user.name, "Updated Name");
-    assert_eq!(user.email, "updated@example.com");"#.to_string()
-        } else if method == "post" && path.contains("users") && !path.contains("{") {
+    let user: User = {parse_json}.expect("Failed to parse response");{}"#,
+                echo_assertions())
+        } else if method == "post" && variant.is_none() && documented_body.is_none() && path.contains("users") && !path.contains("{") {
             // Add verification for create user
-            r#"
+            format!(r#"
     // Verify the response body
-    let user: User = response.json().await.expect("Failed to parse response");
-    assert_eq!(user.name, "Test User");
-    assert_eq!(user.email, "test@example.com");"#.to_string()
+    let user: User = {parse_json}.expect("Failed to parse response");{}"#,
+                echo_assertions())
         } else if method == "get" && !path.contains("{") {
             // Add verification for get all users
-            r#"
+            format!(r#"
     // Verify the response body contains users
-    let users: Vec<User> = response.json().await.expect("Failed to parse response");
-    assert!(!users.is_empty(), "Expected users array to not be empty");"#.to_string()
+    let users: Vec<User> = {parse_json}.expect("Failed to parse response");
+    assert!(!users.is_empty(), "Expected users array to not be empty");"#)
         } else {
-            "".to_string()
+            schema_check
+        };
+        
+        // The time until the response arrives is measured around sending the request
+        let (timer, latency_assertion) = match self.options.max_response_time_ms {
+            Some(max) => (
+                "let started = std::time::Instant::now();\n    ",
+                format!(
+                    "\n    let elapsed = started.elapsed();\n    assert!(elapsed.as_millis() <= {max}, \"Response took {{}} ms, more than {max} ms\", elapsed.as_millis());"
+                ),
+            ),
+            None => ("", String::new()),
         };
         
+        // ureq answers error statuses with an `Err`, which `send` takes as the response to check
+        let response = self.send_request(&format!("{client_method}{query_params_apply}{headers_apply}"), &send_call, "Failed to send request");
+        let (attribute, function) = if self.blocking { ("#[test]", "fn") } else { ("#[tokio::test]", "async fn") };
+        
         format!(
-            r#"#[tokio::test]
-async fn test_{snake_case_operation_id}() {{
+            r#"{attribute}{ignore}
+{function} test_{snake_case_operation_id}() {{
     // {summary}
 {path_params_decl}
 {query_params}
 {body_param}
 
-    let client = reqwest::Client::new();
-    let url = format!("{base_url}{endpoint_path}");
+    {client_setup}
     
-    let response = {client_method}{query_params_apply}
-        .send()
-        .await
-        .expect("Failed to send request");
+    {timer}let response = {response};{latency_assertion}
         
-    assert_eq!(response.status().as_u16(), {expected_status});{additional_verification}
+    assert_eq!(response.{status}, {expected_status});{header_assertions}{created_cleanup}{example_check}{additional_verification}
 }}
 "#
         )
     }
 }
 
-impl TestGenerator for ReqwestGenerator {
-    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
-        // Create the output directory if it doesn't exist
-        fs::create_dir_all(output_dir)?;
+impl ReqwestGenerator {
+    /// The negative and boundary tests of one request of an operation, each sending a variation of its request
+    fn generate_case_tests(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let method = operation.method.to_uppercase();
+        let (attribute, function, client) = if self.blocking {
+            ("#[test]", "fn", "ureq::agent()".to_string())
+        } else {
+            ("#[tokio::test]", "async fn", "reqwest::Client::new()".to_string())
+        };
+        let status = if self.blocking { "response.status()" } else { "response.status().as_u16()" };
+        let assertion = |expected: Option<&str>| match expected {
+            Some(expected) => format!("assert_eq!({}, {});", status, expected),
+            None => format!("assert!((400..500).contains(&{0}), \"Expected a 4xx status, got {{}}\", {0});", status),
+        };
+        // ureq leaves the parameters (`; charset=utf-8`) out of the content type
+        let media_type_check = |media_type: Option<&str>| match (media_type, self.blocking) {
+            (Some(media_type), true) => format!("\n    assert_eq!(response.content_type(), {:?});", media_type),
+            (Some(media_type), false) => format!(
+                "\n    let content_type = response.headers().get(\"Content-Type\").and_then(|value| value.to_str().ok()).unwrap_or_default();\n    assert!(content_type.starts_with({:?}), \"Expected {}, got {{}}\", content_type);",
+                media_type,
+                media_type,
+            ),
+            (None, _) => String::new(),
+        };
         
-        // Extract the base path from the spec's base_url
-        // The base_url in the spec contains something like "http://api.sample.com/v1"
-        // We need to extract the "/v1" part to append to our custom base URL
-        let base_path = if let Some(url_parts) = spec.base_url.split("://").nth(1) {
-            // Get everything after the host (domain)
-            if let Some(path) = url_parts.find('/') {
-                let base_path = &url_parts[path..];
-                if !base_path.is_empty() {
-                    base_path
+        request_cases(operation, variant, &self.options)
+            .into_iter()
+            .map(|case| {
+                let name = format!("{}{}_{}", snake_case(&operation.operation_id), variant_suffix(variant), case.name);
+                let body = case.body(operation, variant);
+                let body_decl = body.as_ref()
+                    .map(|body| format!("\n    let body = json!({});\n", to_json_literal(body, "    ")))
+                    .unwrap_or_default();
+                
+                let mut request = if self.blocking {
+                    format!("client.request({:?}, url)", method)
                 } else {
-                    ""
+                    format!("client.request(reqwest::Method::{}, url)", method)
+                };
+                let query = case.query(operation);
+                if self.blocking {
+                    for (name, value) in &query {
+                        request.push_str(&format!("\n        .query({:?}, {:?})", name, value));
+                    }
+                } else if !query.is_empty() {
+                    let pairs: Vec<String> = query.iter().map(|(name, value)| format!("({:?}, {:?})", name, value)).collect();
+                    request.push_str(&format!("\n        .query(&[{}])", pairs.join(", ")));
                 }
-            } else {
-                ""
-            }
-        } else {
-            ""
+                let header_method = if self.blocking { "set" } else { "header" };
+                for (name, value) in case.headers(operation, &self.options.headers) {
+                    request.push_str(&format!("\n        .{}({:?}, {:?})", header_method, name, value));
+                }
+                request.push_str(&self.credentials_apply(case.credentials(credentials)));
+                let call = match (self.blocking, body.is_some()) {
+                    (true, true) => "\n        .send_json(&body)",
+                    (true, false) => "\n        .call()",
+                    (false, true) => "\n        .json(&body)",
+                    (false, false) => "",
+                };
+                let response = self.send_request(&request, call, "Failed to send request");
+                
+                format!(
+                    r#"
+{attribute}
+{function} test_{name}() {{
+    // Sent {description}, the request has to be {outcome}{body_decl}
+    let client = {client};
+    let url = {url};
+    
+    let response = {response};
+    
+    {assertion}{media_type_check}
+}}
+"#,
+                    description = case.description,
+                    outcome = case.outcome(),
+                    url = self.url_literal(base_url, &case.path(path, operation)),
+                    assertion = assertion(case.expected_status(operation)),
+                    media_type_check = media_type_check(case.media_type()),
+                )
+            })
+            .collect()
+    }
+    
+    /// An rstest test of an operation with a case for each request the data file has for it, sending its parameters and
+    /// body and expecting its status. Returns nothing when there are none
+    fn generate_data_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let cases = data_cases(operation, path, variant, &self.options);
+        if cases.is_empty() {
+            return String::new();
+        }
+        let pairs = |pairs: &[(String, String)]| {
+            let pairs: Vec<String> = pairs.iter().map(|(name, value)| format!("({:?}, {:?})", name, value)).collect();
+            format!("&[{}]", pairs.join(", "))
         };
+        let cases: Vec<String> = cases.iter()
+            .enumerate()
+            .map(|(index, case)| format!(
+                "#[case::request_{}({:?}, {}, {}, {}, {})]",
+                index + 1,
+                case.path,
+                pairs(&case.query),
+                pairs(&case.headers),
+                case.body.as_ref().map(|body| format!("Some(json!({}))", body)).unwrap_or_else(|| "None".to_string()),
+                case.status,
+            ))
+            .collect();
         
-        // Combine our command line base_url with the base path from the spec
-        // Make sure we don't have double slashes
-        let final_base_url = if base_url.ends_with('/') || base_path.starts_with('/') {
-            format!("{}{}", base_url.trim_end_matches('/'), base_path)
-        } else if !base_path.is_empty() {
-            format!("{}/{}", base_url, base_path.trim_start_matches('/'))
+        // rstest marks the cases of sync tests as tests itself
+        let method = operation.method.to_uppercase();
+        let credentials = self.credentials_apply(credentials);
+        let (attribute, function, client, request) = if self.blocking {
+            ("", "fn", "ureq::agent()", format!(
+                "let mut request = client.request({:?}, &url);\n    for (name, value) in query {{\n        request = request.query(name, value);\n    }}\n    for (name, value) in headers {{\n        request = request.set(name, value);\n    }}",
+                method,
+            ))
         } else {
-            base_url.to_string()
+            ("\n#[tokio::test]", "async fn", "reqwest::Client::new()", format!(
+                "let mut request = client.request(reqwest::Method::{}, &url).query(query);\n    for (name, value) in headers {{\n        request = request.header(*name, *value);\n    }}\n    if let Some(body) = &body {{\n        request = request.json(body);\n    }}",
+                method,
+            ))
+        };
+        let response = if self.blocking {
+            // Retried requests are sent again from a copy
+            let request = if self.options.retries > 0 { "request.clone()" } else { "request" };
+            let send = |call: &str| {
+                self.send_request(&format!("{}{}", request, credentials), call, "Failed to send request").replace("\n        ", "\n            ")
+            };
+            format!(
+                "match &body {{\n        Some(body) => {},\n        None => {},\n    }}",
+                send("\n        .send_json(body)"),
+                send("\n        .call()"),
+            )
+        } else {
+            self.send_request(&format!("request{}", credentials), "", "Failed to send request")
+        };
+        // The path is formatted into the URL, after the base URL or `BASE_URL`
+        let url = if self.options.environments.is_empty() {
+            format!("{}{{path}}", base_url.replace('{', "{{").replace('}', "}}"))
+        } else {
+            format!("{}{{path}}", base_url)
+        };
+        
+        format!(
+            r#"
+#[rstest::rstest]
+{cases}{attribute}{ignore}
+{function} test_{name}_data(
+    #[case] path: &str,
+    #[case] query: &[(&str, &str)],
+    #[case] headers: &[(&str, &str)],
+    #[case] body: Option<serde_json::Value>,
+    #[case] expected_status: u16,
+) {{
+    // {summary}, sending each request the data file has for it
+    let client = {client};
+    let url = format!({url:?});
+    {request}
+    
+    let response = {response};
+    
+    assert_eq!({status}, expected_status);
+}}
+"#,
+            cases = cases.join("\n"),
+            ignore = if operation.deprecated { "\n#[ignore = \"deprecated operation\"]" } else { "" },
+            name = snake_case(&operation.operation_id),
+            summary = operation.summary.as_deref().unwrap_or(&operation.operation_id),
+            status = if self.blocking { "response.status()" } else { "response.status().as_u16()" },
+        )
+    }
+    
+    /// A test asking an operation that pages its results for its first two pages, which have to differ and hold no more
+    /// items than the limit. Returns nothing when the operation gets no pagination test
+    fn generate_pagination_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let Some(pages) = page_requests(operation, path, variant, &self.options) else {
+            return String::new();
+        };
+        let (attribute, function, client) = if self.blocking {
+            ("#[test]", "fn", "ureq::agent()")
+        } else {
+            ("#[tokio::test]", "async fn", "reqwest::Client::new()")
+        };
+        let header_method = if self.blocking { "set" } else { "header" };
+        let credentials = self.credentials_apply(credentials);
+        // The query values are Rust expressions, the cursor of the second page being read from the first one
+        let send = |query: &[(String, String)]| {
+            let mut request = if self.blocking {
+                "client.request(\"GET\", url)".to_string()
+            } else {
+                "client.request(reqwest::Method::GET, url)".to_string()
+            };
+            if self.blocking {
+                for (name, value) in query {
+                    request.push_str(&format!("\n        .query({:?}, {})", name, value));
+                }
+            } else if !query.is_empty() {
+                let pairs: Vec<String> = query.iter().map(|(name, value)| format!("({:?}, {})", name, value)).collect();
+                request.push_str(&format!("\n        .query(&[{}])", pairs.join(", ")));
+            }
+            for (name, value) in &pages.headers {
+                request.push_str(&format!("\n        .{}({:?}, {:?})", header_method, name, value));
+            }
+            request.push_str(&credentials);
+            self.send_request(&request, if self.blocking { "\n        .call()" } else { "" }, "Failed to send request")
+        };
+        let literals = |query: &[(String, String)]| -> Vec<(String, String)> {
+            query.iter().map(|(name, value)| (name.clone(), format!("{:?}", value))).collect()
+        };
+        let page = |number: &str, query: &[(String, String)]| {
+            let (status, body) = if self.blocking {
+                ("response.status()", format!("response.into_json().expect(\"Failed to parse the {} page\")", number))
+            } else {
+                ("response.status().as_u16()", format!("response.json().await.expect(\"Failed to parse the {} page\")", number))
+            };
+            let limit = pages.limit
+                .map(|limit| format!("\n    assert!({0}_items.len() <= {1}, \"Expected at most {1} items, got {{}}\", {0}_items.len());", number, limit))
+                .unwrap_or_default();
+            format!(
+                "let response = {send};\n    assert_eq!({status}, {expected});\n    let {number}_page: serde_json::Value = {body};\n    let {number}_items = {number}_page.pointer({pointer:?}).and_then(|items| items.as_array()).expect(\"Expected the items of the {number} page\");{limit}",
+                send = send(query),
+                expected = pages.status,
+                pointer = pages.items_pointer,
+            )
+        };
+        
+        let mut second_query = literals(&pages.second_query);
+        let cursor = match &pages.cursor {
+            Some((param, pointer)) => {
+                second_query.push((param.clone(), if self.blocking { "&cursor".to_string() } else { "cursor.as_str()".to_string() }));
+                format!(
+                    "\n    let cursor = match first_page.pointer({0:?}) {{\n        Some(serde_json::Value::String(cursor)) => cursor.clone(),\n        Some(cursor) if !cursor.is_null() => cursor.to_string(),\n        _ => panic!(\"Expected the cursor of the next page at {0}\"),\n    }};",
+                    pointer,
+                )
+            },
+            None => String::new(),
+        };
+        
+        format!(
+            r#"
+{attribute}
+{function} test_{name}_pagination() {{
+    // Asks for two pages of {operation_id}, which have to differ{limit}
+    let client = {client};
+    let url = {url};
+    
+    {first}{cursor}
+    
+    {second}
+    
+    assert_ne!(first_items, second_items, "Expected the second page to differ from the first");
+}}
+"#,
+            name = snake_case(&operation.operation_id),
+            operation_id = operation.operation_id,
+            limit = pages.limit.map(|limit| format!(" and hold at most {} items each", limit)).unwrap_or_default(),
+            url = self.url_literal(base_url, &pages.path),
+            first = page("first", &literals(&pages.first_query)),
+            second = page("second", &second_query),
+        )
+    }
+    
+    /// The test of a resource's lifecycle: the item is created, read, updated and deleted, and then has to be gone
+    fn generate_lifecycle_test(&self, spec: &SwaggerSpec, lifecycle: &Lifecycle, base_url: &str) -> String {
+        let mut code = vec![self.generate_link_setup(&lifecycle.create, lifecycle.read_operation(), base_url, None)];
+        code.extend(lifecycle.unbound_params().into_iter().map(|p| format!("    let {} = {};", p.name, to_json_literal(&path_value(p), ""))));
+        code.push(format!("    let client = {};", if self.blocking { "ureq::agent()" } else { "reqwest::Client::new()" }));
+        
+        for step in &lifecycle.steps {
+            let method = step.operation.method.to_uppercase();
+            let url = format!("format!(\"{}{}{}\")", base_url, step.path, step.query());
+            let header_method = if self.blocking { "set" } else { "header" };
+            let mut request = if self.blocking {
+                format!("client.request({:?}, &{})", method, url)
+            } else {
+                format!("client.request(reqwest::Method::{}, {})", method, url)
+            };
+            for (name, value) in fixed_headers(step.operation, &self.options.headers) {
+                request.push_str(&format!("\n        .{}({:?}, {:?})", header_method, name, value));
+            }
+            request.push_str(&self.credentials_apply(&operation_credentials(spec, step.operation, &self.options.auth)));
+            
+            let body = step.body().filter(|_| step.action == Action::Update);
+            let (call, status) = match (self.blocking, body) {
+                (true, Some(body)) => (format!("\n        .send_json(json!({}))", to_json_literal(&body, "        ")), "response.status()"),
+                (true, None) => ("\n        .call()".to_string(), "response.status()"),
+                (false, body) => (
+                    body.map(|body| format!("\n        .json(&json!({}))", to_json_literal(&body, "        "))).unwrap_or_default(),
+                    "response.status().as_u16()",
+                ),
+            };
+            let response = self.send_request(&request, &call, "Failed to send request");
+            code.push(format!(
+                "\n    // {}: {}\n    let response = {};\n    assert_eq!({}, {});",
+                step.action.description(),
+                step.operation.operation_id,
+                response,
+                status,
+                step.expected_status(),
+            ));
+        }
+        
+        let (attribute, function) = if self.blocking { ("#[test]", "fn") } else { ("#[tokio::test]", "async fn") };
+        format!(
+            r#"{attribute}
+{function} test_{name}_lifecycle() {{
+    // Create a resource with {create}, then read, update and delete it, and check it is gone
+{code}
+}}
+"#,
+            name = lifecycle.name,
+            create = lifecycle.create.operation.operation_id,
+            code = code.join("\n"),
+        )
+    }
+    
+    /// A proptest test of an operation, sending requests with bodies and query parameters drawn from their schemas, which
+    /// the API must answer without a server error
+    fn generate_fuzz_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let method = operation.method.to_uppercase();
+        let ignore = if operation.deprecated { "\n    #[ignore = \"deprecated operation\"]" } else { "" };
+        let body = body_strategy(operation, variant);
+        let query = query_strategies(operation);
+        
+        let mut inputs: Vec<String> = body.iter()
+            .map(|strategy| format!("        body in {},", strategy.proptest("        ")))
+            .collect();
+        inputs.extend(query.iter().map(|(_, variable, strategy)| format!("        {} in {},", variable, strategy.proptest("        "))));
+        
+        let mut request = if self.blocking {
+            format!("ureq::request({:?}, {})", method, self.url_literal(base_url, &example_path(path, operation)))
+        } else {
+            format!("client.request(reqwest::Method::{}, {})", method, self.url_literal(base_url, &example_path(path, operation)))
+        };
+        if self.blocking {
+            for (param, variable, _) in &query {
+                request.push_str(&format!("\n        .query({:?}, &query_text(&{}))", param.name, variable));
+            }
+        } else if !query.is_empty() {
+            let pairs: Vec<String> = query.iter().map(|(param, variable, _)| format!("({:?}, query_text(&{}))", param.name, variable)).collect();
+            request.push_str(&format!("\n        .query(&[{}])", pairs.join(", ")));
+        }
+        let header_method = if self.blocking { "set" } else { "header" };
+        for (name, value) in fixed_headers(operation, &self.options.headers) {
+            request.push_str(&format!("\n        .{}({:?}, {:?})", header_method, name, value));
+        }
+        request.push_str(&self.credentials_apply(credentials));
+        request.push_str(&self.timeout_apply());
+        
+        // Server errors are what the test looks for, so requests aren't retried
+        let send = if self.blocking {
+            let call = if body.is_some() { "send_json(&body)" } else { "call()" };
+            format!("let status = send({}\n        .{}).status();", request, call)
+        } else {
+            let request = format!(
+                "{}{}\n        .send()\n        .await\n        .expect(\"Failed to send request\")\n        .status()\n        .as_u16()",
+                request,
+                if body.is_some() { "\n        .json(&body)" } else { "" },
+            );
+            format!(
+                "let runtime = tokio::runtime::Runtime::new().expect(\"Failed to start a runtime\");\n    let status = runtime.block_on(async {{\n        let client = reqwest::Client::new();\n        {}\n    }});",
+                request.replace("\n        .", "\n            ."),
+            )
+        };
+        
+        let name = format!("{}{}", snake_case(&operation.operation_id), variant_suffix(variant));
+        format!(
+            r#"proptest! {{
+    #![proptest_config(ProptestConfig::with_cases({cases}))]
+    
+    #[test]{ignore}
+    fn test_{name}(
+{inputs}
+    ) {{
+        // {summary}, with values drawn from the schemas: the API must answer without a server error
+        {send}
+        
+        prop_assert!(status < 500, "Server error {{}}", status);
+    }}
+}}
+"#,
+            cases = FUZZ_CASES,
+            inputs = inputs.join("\n"),
+            summary = operation.summary.as_deref().unwrap_or(&operation.operation_id),
+            send = send.replace('\n', "\n    "),
+        )
+    }
+    
+    /// Builder calls sending a request's credentials, read with the generated `credential` helper
+    fn credentials_apply(&self, credentials: &[Credential]) -> String {
+        credentials.iter()
+            .map(|credential| match credential {
+                Credential::Bearer { env } if self.blocking => {
+                    format!("\n        .set(\"Authorization\", &format!(\"Bearer {{}}\", credential({:?})))", env)
+                },
+                Credential::Bearer { env } => format!("\n        .bearer_auth(credential({:?}))", env),
+                Credential::ApiKey { name, location, env } => match (location.as_str(), self.blocking) {
+                    ("query", true) => format!("\n        .query({:?}, &credential({:?}))", name, env),
+                    ("query", false) => format!("\n        .query(&[({:?}, credential({:?}))])", name, env),
+                    (_, true) => format!("\n        .set({:?}, &credential({:?}))", name, env),
+                    (_, false) => format!("\n        .header({:?}, credential({:?}))", name, env),
+                },
+                // ureq has no support for basic authentication, so the header is built by the generated `basic_auth`
+                Credential::Basic { username_env, password_env } if self.blocking => {
+                    format!("\n        .set(\"Authorization\", &basic_auth({:?}, {:?}))", username_env, password_env)
+                },
+                Credential::Basic { username_env, password_env } => {
+                    format!("\n        .basic_auth(credential({:?}), Some(credential({:?})))", username_env, password_env)
+                },
+                Credential::ClientCredentials { token_url, scope, .. } if self.blocking => {
+                    format!("\n        .set(\"Authorization\", &format!(\"Bearer {{}}\", access_token({:?}, {:?})))", token_url, scope)
+                },
+                Credential::ClientCredentials { token_url, scope, .. } => {
+                    format!("\n        .bearer_auth(access_token({:?}, {:?}).await)", token_url, scope)
+                },
+            })
+            .collect()
+    }
+    
+    /// Code sending the setup request of a link, and binding the values it supplies to the parameters of `target`, then
+    /// registering the deletion of what it created
+    fn generate_link_setup(&self, setup: &LinkedSetup, target: &ApiOperation, base_url: &str, cleanup: Option<&Cleanup>) -> String {
+        let linked = setup.linked_params(target);
+        let read = linked.iter().map(|(_, value)| *value).chain(cleanup.iter().flat_map(|cleanup| cleanup.params()).map(|(_, value)| value));
+        let (uses_headers, uses_body) = read.fold((false, false), |(headers, body), value| {
+            (headers || matches!(value, LinkValue::ResponseHeader(_)), body || matches!(value, LinkValue::ResponseBody(_)))
+        });
+        
+        let method = setup.operation.method.to_uppercase();
+        let url = self.url_literal(base_url, &setup.request_path());
+        let mut code = if self.blocking {
+            let call = match setup.request_body() {
+                Some(body) => format!("\n        .send_json(json!({}))", to_json_literal(&body, "        ")),
+                None => "\n        .call()".to_string(),
+            };
+            format!(
+                r#"    // Call {operation_id} first, and take parameters from its response ({origin})
+    let setup_response = {send};
+    assert!((200..300).contains(&setup_response.status()), "Setup request failed with {{}}", setup_response.status());"#,
+                operation_id = setup.operation.operation_id,
+                origin = setup.origin(),
+                send = self.send_request(&format!("ureq::request({:?}, {}){}", method, url, self.static_headers_apply("\n        ")), &call, ""),
+            )
+        } else {
+            let body = setup.request_body()
+                .map(|body| format!("\n        .json(&json!({}))", to_json_literal(&body, "        ")))
+                .unwrap_or_default();
+            let request = format!(
+                "reqwest::Client::new()\n        .request(reqwest::Method::{}, {}){}",
+                method, url, self.static_headers_apply("\n        "),
+            );
+            format!(
+                r#"    // Call {operation_id} first, and take parameters from its response ({origin})
+    let setup_response = {send};
+    assert!(setup_response.status().is_success(), "Setup request failed with {{}}", setup_response.status());"#,
+                operation_id = setup.operation.operation_id,
+                origin = setup.origin(),
+                send = self.send_request(&request, &body, "Failed to send setup request"),
+            )
+        };
+        if uses_headers && self.blocking {
+            // Reading the body consumes a ureq response, so its headers are copied out first
+            code.push_str("\n    let setup_headers: std::collections::HashMap<String, String> = setup_response.headers_names()\n        .into_iter()\n        .filter_map(|name| Some((name.to_lowercase(), setup_response.header(&name)?.to_string())))\n        .collect();");
+        } else if uses_headers {
+            code.push_str("\n    let setup_headers = setup_response.headers().clone();");
+        }
+        if uses_body && self.blocking {
+            code.push_str("\n    let setup_body: serde_json::Value = setup_response.into_json().unwrap_or_default();");
+        } else if uses_body {
+            code.push_str("\n    let setup_body: serde_json::Value = setup_response.json().await.unwrap_or_default();");
+        }
+        
+        for (param, value) in linked {
+            code.push_str(&format!("\n    let {} = {};", param.name, self.link_binding(setup, value, "setup_body", "setup_headers")));
+        }
+        if let Some(cleanup) = cleanup {
+            code.push_str(&self.generate_cleanup(cleanup, base_url, "setup_body", "setup_headers"));
+        }
+        
+        code
+    }
+    
+    /// Expression reading a value a link supplies, from the response body and headers bound to `body` and `headers` when
+    /// it comes from the response
+    fn link_binding(&self, setup: &LinkedSetup, value: &LinkValue, body: &str, headers: &str) -> String {
+        match value {
+            LinkValue::ResponseBody(pointer) => format!("linked_value(&{}, {:?})", body, pointer),
+            LinkValue::ResponseHeader(name) if self.blocking => format!(
+                "{}.get({:?}).expect(\"Missing linked header\").clone()",
+                headers,
+                name.to_lowercase()
+            ),
+            LinkValue::ResponseHeader(name) => format!(
+                "{}.get({:?}).and_then(|v| v.to_str().ok()).expect(\"Missing linked header\").to_string()",
+                headers,
+                name
+            ),
+            other => format!("{:?}.to_string()", setup.static_value(other).unwrap_or_default()),
+        }
+    }
+    
+    /// Code registering the deletion of an item a request created, which the `Cleanup` guard sends once the test is
+    /// done, even when it fails
+    fn generate_cleanup(&self, cleanup: &Cleanup, base_url: &str, body: &str, headers: &str) -> String {
+        let params: Vec<String> = cleanup.params()
+            .into_iter()
+            .map(|(param, value)| format!(", {} = {}", param.name, self.link_binding(&cleanup.setup, value, body, headers)))
+            .collect();
+        format!(
+            "\n    // Delete what {} created once the test is done: {}\n    let _cleanup = Cleanup(format!(\"{}{}\"{}));",
+            cleanup.setup.operation.operation_id,
+            cleanup.operation.operation_id,
+            base_url,
+            cleanup.path,
+            params.concat(),
+        )
+    }
+    
+    /// Write `webhook_tests.rs`, delivering each webhook's documented payload to the receiver under test
+    fn generate_webhook_tests(&self, webhooks: &[(&str, &ApiOperation)], output_dir: &Path) -> Result<()> {
+        let mut file = File::create(output_dir.join("webhook_tests.rs"))?;
+        
+        writeln!(file, r#"use serde_json::json;
+
+// Endpoint of the webhook receiver under test
+fn webhook_url() -> String {{
+    std::env::var("WEBHOOK_URL").unwrap_or_else(|_| "{DEFAULT_WEBHOOK_URL}".to_string())
+}}
+"#)?;
+        
+        for (name, operation) in webhooks {
+            let test_name = snake_case(&operation.operation_id);
+            let summary = operation.summary.as_deref().unwrap_or(name);
+            let ignore = if operation.deprecated { "\n#[ignore = \"deprecated operation\"]" } else { "" };
+            let method = operation.method.to_uppercase();
+            let (payload, send_payload) = match operation.body_param.as_ref() {
+                Some(body) => (
+                    format!("\n    let payload = json!({});\n", to_json_literal(&body_example(body), "    ")),
+                    ".json(&payload)",
+                ),
+                None => (String::new(), ""),
+            };
+            let expected_status = webhook_status(operation);
+            
+            if self.blocking {
+                let send = if send_payload.is_empty() { ".call()" } else { ".send_json(&payload)" };
+                writeln!(file, r#"#[test]{ignore}
+fn test_webhook_{test_name}() {{
+    // {summary}{payload}
+    let response = match ureq::request("{method}", &webhook_url()){send} {{
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(error) => panic!("Failed to deliver webhook: {{}}", error),
+    }};
+        
+    assert_eq!(response.status(), {expected_status});
+}}
+"#)?;
+                continue;
+            }
+            
+            writeln!(file, r#"#[tokio::test]{ignore}
+async fn test_webhook_{test_name}() {{
+    // {summary}{payload}
+    let client = reqwest::Client::new();
+    let response = client.request(reqwest::Method::{method}, webhook_url()){send_payload}
+        .send()
+        .await
+        .expect("Failed to deliver webhook");
+        
+    assert_eq!(response.status().as_u16(), {expected_status});
+}}
+"#)?;
+        }
+        
+        Ok(())
+    }
+}
+
+impl TestGenerator for ReqwestGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+        
+        // Extract the base path from the spec's base_url
+        // The base_url in the spec contains something like "http://api.sample.com/v1"
+        // We need to extract the "/v1" part to append to our custom base URL
+        let base_path = if let Some(url_parts) = spec.base_url.split("://").nth(1) {
+            // Get everything after the host (domain)
+            if let Some(path) = url_parts.find('/') {
+                let base_path = &url_parts[path..];
+                if !base_path.is_empty() {
+                    base_path
+                } else {
+                    ""
+                }
+            } else {
+                ""
+            }
+        } else {
+            ""
+        };
+        
+        // Combine our command line base_url with the base path from the spec
+        // Make sure we don't have double slashes
+        let final_base_url = if base_url.ends_with('/') || base_path.starts_with('/') {
+            format!("{}{}", base_url.trim_end_matches('/'), base_path)
+        } else if !base_path.is_empty() {
+            format!("{}/{}", base_url, base_path.trim_start_matches('/'))
+        } else {
+            base_url.to_string()
         };
         
+        // With environments, request URLs start with the `BASE_URL` of the one tested instead
+        let tests_base_url = if self.options.environments.is_empty() { final_base_url.clone() } else { "{BASE_URL}".to_string() };
+        write_env_files(spec, &self.options, output_dir)?;
+        
         // Create a single test file for all operations
         let test_file_path = output_dir.join("api_tests.rs");
         let mut file = File::create(test_file_path)?;
         
         // Write the file header with common helpers and structs
+        let fuzzed: Vec<_> = all_operations(spec, &self.options)
+            .into_iter()
+            .filter(|(_, operation)| self.options.fuzz && is_fuzzed(operation))
+            .collect();
+        if !fuzzed.is_empty() {
+            writeln!(file, "use proptest::prelude::*;")?;
+        }
+        let data_driven = all_operations(spec, &self.options).iter().any(|(_, operation)| !self.options.data_sets.rows(operation).is_empty());
         write!(file, r#"use serde_json::json;
 use serde::{{Deserialize, Serialize}};
 
@@ -276,42 +1656,253 @@ struct User {{
     updated_at: Option<String>,
 }}
 
-// Helper function to create a test user and return its ID
-async fn create_test_user(name: &str, email: &str) -> i64 {{
-    let body = json!({{
-        "name": name,
-        "email": email
-    }});
+"#)?;
+        
+        if self.blocking {
+            write!(file, r#"// Take the response of a request, which ureq gives as an error for error statuses
+fn send(result: Result<ureq::Response, ureq::Error>) -> ureq::Response {{
+    match result {{
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(error) => panic!("Failed to send request: {{}}", error),
+    }}
+}}
+"#)?;
+        }
+        
+        if !self.options.environments.is_empty() {
+            writeln!(file, r#"
+// Base URL of the API under test: API_BASE_URL, which the .env file of the environment tested sets (e.g. with
+// `set -a; . ./.env.staging; set +a` before `cargo test`), followed by the base path of the API. Request URLs are
+// formatted with it
+struct BaseUrl;
 
-    let client = reqwest::Client::new();
-    let url = "{}/users";
-    
-    let response = client.post(url).json(&body)
+impl std::fmt::Display for BaseUrl {{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {{
+        let base_url = std::env::var("{BASE_URL_VAR}").unwrap_or_else(|_| "{base_url}".to_string());
+        write!(f, "{{}}{base_path}", base_url.trim_end_matches('/'))
+    }}
+}}
+
+const BASE_URL: BaseUrl = BaseUrl;"#,
+                base_url = base_url.trim_end_matches('/'),
+                base_path = final_base_url.strip_prefix(base_url.trim_end_matches('/')).unwrap_or_default().trim_end_matches('/'))?;
+        }
+        
+        // Requests are sent again after connection errors and statuses a flaky environment answers with
+        let retries = self.options.retries;
+        let backoff = self.options.retry_backoff_ms.unwrap_or(DEFAULT_RETRY_BACKOFF_MS);
+        if retries > 0 && self.blocking {
+            writeln!(file, r#"
+// Call a request, again up to {retries} times after a connection error or a 408, 429 or 5xx status, waiting {backoff} ms
+// before the first retry and twice as long before each next one
+fn with_retries(mut call: impl FnMut() -> Result<ureq::Response, ureq::Error>) -> Result<ureq::Response, ureq::Error> {{
+    let mut delay = std::time::Duration::from_millis({backoff});
+    for _ in 0..{retries} {{
+        match call() {{
+            Err(ureq::Error::Status(408 | 429 | 500..=599, _) | ureq::Error::Transport(_)) => {{}},
+            result => return result,
+        }}
+        std::thread::sleep(delay);
+        delay *= 2;
+    }}
+    call()
+}}"#)?;
+        } else if retries > 0 {
+            writeln!(file, r#"
+// Send a request, again up to {retries} times after a connection error or a 408, 429 or 5xx status, waiting {backoff} ms
+// before the first retry and twice as long before each next one. Requests with a streamed body are sent once
+async fn with_retries(request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {{
+    let mut delay = std::time::Duration::from_millis({backoff});
+    for _ in 0..{retries} {{
+        let Some(attempt) = request.try_clone() else {{ break }};
+        match attempt.send().await {{
+            Ok(response) if !matches!(response.status().as_u16(), 408 | 429 | 500..=599) => return Ok(response),
+            _ => tokio::time::sleep(delay).await,
+        }}
+        delay *= 2;
+    }}
+    request.send().await
+}}"#)?;
+        }
+        
+        if fuzzed.iter().any(|(_, operation)| !operation.query_params.is_empty()) {
+            writeln!(file, r#"
+// The text of a drawn value sent as a query parameter, with the items of arrays separated by commas
+fn query_text(value: &serde_json::Value) -> String {{
+    match value {{
+        serde_json::Value::String(text) => text.clone(),
+        serde_json::Value::Array(items) => items.iter().map(query_text).collect::<Vec<_>>().join(","),
+        value => value.to_string(),
+    }}
+}}"#)?;
+        }
+        
+        let credentials = used_credentials(spec, &self.options.auth);
+        let uses_basic_auth = self.blocking && credentials.iter().any(|credential| matches!(credential, Credential::Basic { .. }));
+        if !credentials.is_empty() {
+            writeln!(file, r#"
+// Read a credential to authenticate with from the environment
+fn credential(name: &str) -> String {{
+    std::env::var(name).unwrap_or_else(|_| panic!("Set {{}} to the credential the tests authenticate with", name))
+}}"#)?;
+        }
+        if let Some((client_id_env, client_secret_env)) = client_credentials_env(&credentials) {
+            // Tests of a suite run as threads of one process, so a token in a static is fetched once for all of them
+            let (function, mutex, new, lock, fetch) = if self.blocking {
+                ("fn", "std::sync::Mutex", "new", "ACCESS_TOKENS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())", r#"let form: Vec<(&str, &str)> = form.iter().map(|(name, value)| (*name, value.as_str())).collect();
+    let response = send(ureq::post(token_url).send_form(&form));
+    assert!((200..300).contains(&response.status()), "Token request failed with {}", response.status());
+    let body: serde_json::Value = response.into_json().expect("Failed to parse the token response");"#)
+            } else {
+                ("async fn", "tokio::sync::Mutex", "const_new", "ACCESS_TOKENS.lock().await", r#"let response = reqwest::Client::new()
+        .post(token_url)
+        .form(&form)
         .send()
         .await
-        .expect("Failed to create test user");
-        
-    assert_eq!(response.status().as_u16(), 201);
+        .expect("Failed to request an access token");
+    assert!(response.status().is_success(), "Token request failed with {}", response.status());
+    let body: serde_json::Value = response.json().await.expect("Failed to parse the token response");"#)
+            };
+            writeln!(file, r#"
+// Access tokens fetched with the OAuth2 client credentials flow, shared by all the tests: token endpoint, scope, token
+// and when to fetch it again
+static ACCESS_TOKENS: {mutex}<Vec<(String, String, String, std::time::Instant)>> = {mutex}::{new}(Vec::new());
+
+// An access token from the OAuth2 client credentials flow, fetched once for all the tests and again a minute before it
+// expires (after an hour when the token endpoint doesn't say). The lock is held while fetching, so tests running at
+// the same time wait for that token instead of each fetching one
+{function} access_token(token_url: &str, scope: &str) -> String {{
+    let mut tokens = {lock};
+    let now = std::time::Instant::now();
+    if let Some((_, _, token, _)) = tokens.iter().find(|(url, token_scope, _, refresh_at)| url == token_url && token_scope == scope && *refresh_at > now) {{
+        return token.clone();
+    }}
     
-    let user: User = response.json().await.expect("Failed to parse user response");
-    user.id
-}}
-"#, final_base_url)?;
+    let mut form = vec![
+        ("grant_type", "client_credentials".to_string()),
+        ("client_id", credential({client_id_env:?})),
+        ("client_secret", credential({client_secret_env:?})),
+    ];
+    if !scope.is_empty() {{
+        form.push(("scope", scope.to_string()));
+    }}
+    {fetch}
+    
+    let token = body["access_token"].as_str().expect("The token response has no access_token").to_string();
+    let expires_in = body["expires_in"].as_u64().unwrap_or(3600);
+    let refresh_at = std::time::Instant::now() + std::time::Duration::from_secs(expires_in.saturating_sub(60));
+    tokens.retain(|(url, token_scope, _, _)| url != token_url || token_scope != scope);
+    tokens.push((token_url.to_string(), scope.to_string(), token.clone(), refresh_at));
+    token
+}}"#)?;
+        }
+        if uses_basic_auth {
+            writeln!(file, r#"
+// The `Authorization` header of HTTP basic authentication, with the user name and password read from the environment
+fn basic_auth(username_var: &str, password_var: &str) -> String {{
+    use base64::Engine;
+    let credentials = format!("{{}}:{{}}", credential(username_var), credential(password_var));
+    format!("Basic {{}}", base64::engine::general_purpose::STANDARD.encode(credentials))
+}}"#)?;
+        }
         
-        // Generate tests for each operation
-        for path in &spec.paths {
-            for operation in &path.operations {
-                let test_code = self.generate_operation_test(operation, &path.path, &final_base_url);
+        if has_setups(spec) {
+            writeln!(file, r#"
+// Read the value a link points at in a response body, as text to put in a URL
+fn linked_value(body: &serde_json::Value, pointer: &str) -> String {{
+    match body.pointer(pointer) {{
+        Some(serde_json::Value::String(value)) => value.clone(),
+        Some(value) => value.to_string(),
+        None => panic!("Linked value {{}} is missing from the setup response", pointer),
+    }}
+}}"#)?;
+        }
+        
+        if has_cleanups(spec) && self.blocking {
+            writeln!(file, r#"
+// Deletes an item the test created, given by its URL, once the test is done, even when it fails
+struct Cleanup(String);
+
+impl Drop for Cleanup {{
+    fn drop(&mut self) {{
+        let _ = ureq::delete(&self.0){headers}.call();
+    }}
+}}"#,
+                headers = self.static_headers_apply(""))?;
+        } else if has_cleanups(spec) {
+            writeln!(file, r#"
+// Deletes an item the test created, given by its URL, once the test is done, even when it fails. The request is sent
+// from a thread of its own, as reqwest's blocking client can't run inside the test's runtime
+struct Cleanup(String);
+
+impl Drop for Cleanup {{
+    fn drop(&mut self) {{
+        let url = self.0.clone();
+        let _ = std::thread::spawn(move || reqwest::blocking::Client::new().delete(url){headers}.send()).join();
+    }}
+}}"#,
+                headers = self.static_headers_apply(""))?;
+        }
+        
+        // Generate tests for each operation, in one submodule per group unless they all go in a single file
+        let generate = |path, operation, variant| {
+            let resources = resources(spec, operation);
+            let credentials = operation_credentials(spec, operation, &self.options.auth);
+            let test = if self.options.fuzz && is_fuzzed(operation) {
+                self.generate_fuzz_test(operation, path, &tests_base_url, variant, &credentials)
+            } else {
+                self.generate_operation_test(operation, path, &tests_base_url, variant, &resources, &credentials)
+            };
+            test + &self.generate_case_tests(operation, path, &tests_base_url, variant, &credentials)
+                + &self.generate_data_test(operation, path, &tests_base_url, variant, &credentials)
+                + &self.generate_pagination_test(operation, path, &tests_base_url, variant, &credentials)
+        };
+        let resource_lifecycles = lifecycles(spec, &self.options);
+        if self.options.layout.unwrap_or(OutputLayout::Single) != OutputLayout::Single {
+            let module_dir = output_dir.join("api_tests");
+            fs::create_dir_all(&module_dir)?;
+            
+            let groups = operation_groups(spec, &self.options);
+            let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+            let tests = generate_in_parallel(&operations, &self.options, generate)?;
+            for (group, tests) in groups.iter().zip(tests) {
+                let module = format!("{}_tests", tag_file_stem(&group.file_stem));
+                writeln!(file, "\nmod {};", module)?;
+                
+                let mut module_file = File::create(module_dir.join(format!("{}.rs", module)))?;
+                writeln!(module_file, "use super::*;\n")?;
+                for test_code in tests {
+                    writeln!(module_file, "{}\n", test_code)?;
+                }
+                for lifecycle in resource_lifecycles.iter().filter(|lifecycle| group.contains(lifecycle.create.operation)) {
+                    writeln!(module_file, "{}\n", self.generate_lifecycle_test(spec, lifecycle, &tests_base_url))?;
+                }
+            }
+        } else {
+            let tests = generate_in_parallel(&[all_operations(spec, &self.options)], &self.options, generate)?;
+            for test_code in tests.concat() {
                 writeln!(file, "{}\n", test_code)?;
             }
+            for lifecycle in &resource_lifecycles {
+                writeln!(file, "{}\n", self.generate_lifecycle_test(spec, lifecycle, &tests_base_url))?;
+            }
         }
         
+        // Webhooks are tested against the consumer's receiver, in a module of their own
+        let webhooks = webhook_operations(spec, &self.options);
+        let webhook_module = if webhooks.is_empty() {
+            ""
+        } else {
+            self.generate_webhook_tests(&webhooks, output_dir)?;
+            "\n#[cfg(test)]\nmod webhook_tests;"
+        };
+        
         // Write a main test file that includes the test module
         let main_file_path = output_dir.join("main.rs");
         let mut main_file = File::create(main_file_path)?;
         
         writeln!(main_file, r#"#[cfg(test)]
-mod api_tests;
+mod api_tests;{webhook_module}
 
 fn main() {{
     println!("Run with 'cargo test' to execute the API tests");
@@ -323,78 +1914,244 @@ fn main() {{
         let mut cargo_file = File::create(cargo_file_path)?;
         
         writeln!(cargo_file, r#"[package]
-name = "api_tests"
-version = "0.1.0"
+name = "{name}"
+version = "{version}"
 edition = "2021"
 
 [dependencies]
-reqwest = {{ version = "0.11", features = ["json", "blocking"] }}
-tokio = {{ version = "1", features = ["full"] }}
+{client}{proptest}{rstest}
 serde = {{ version = "1.0", features = ["derive"] }}
 serde_json = "1.0"
-"#)?;
+"#,
+            proptest = if fuzzed.is_empty() { "" } else { "\nproptest = \"1\"" },
+            rstest = if data_driven { "\nrstest = \"0.18\"" } else { "" },
+            client = if uses_basic_auth {
+                "base64 = \"0.22\"\nureq = { version = \"2\", features = [\"json\"] }"
+            } else if self.blocking {
+                r#"ureq = { version = "2", features = ["json"] }"#
+            } else {
+                "reqwest = { version = \"0.11\", features = [\"json\", \"blocking\", \"cookies\", \"multipart\"] }\ntokio = { version = \"1\", features = [\"full\"] }"
+            },
+            name = spec.info.package_name("_", "tests"),
+            version = spec.info.package_version("0.1.0"))?;
         
         Ok(())
     }
 }
 
-// Python pytest test generator
-struct PytestGenerator;
+// Python pytest test generator, sending requests with requests, or with an httpx `AsyncClient` from async tests
+struct PytestGenerator {
+    options: GeneratorOptions,
+    is_async: bool,
+}
 
 impl PytestGenerator {
-    pub fn new() -> Self {
-        PytestGenerator
+    pub fn new(options: GeneratorOptions) -> Self {
+        PytestGenerator { options, is_async: false }
+    }
+    
+    pub fn new_async(options: GeneratorOptions) -> Self {
+        PytestGenerator { options, is_async: true }
+    }
+    
+    /// An expression for the URL `path` makes up under `base_url`, which is `{BASE_URL}` when the options configure
+    /// environments, and the URL is added to the `BASE_URL` variable
+    fn url_literal(&self, base_url: &str, path: &str) -> String {
+        if self.options.environments.is_empty() {
+            to_python_literal(&Value::String(format!("{}{}", base_url, path)), "")
+        } else {
+            format!("BASE_URL + {}", to_python_literal(&Value::String(path.to_string()), ""))
+        }
+    }
+    
+    /// The `headers` argument sending the options' static headers, for the requests that don't take their headers from
+    /// `request_headers` (setup requests and cleanups)
+    fn static_headers_arg(&self) -> String {
+        if self.options.headers.is_empty() {
+            return String::new();
+        }
+        let python_str = |text: &str| to_python_literal(&Value::String(text.to_string()), "");
+        let items: Vec<String> = self.options.headers.iter()
+            .map(|(name, value)| format!("{}: {}", python_str(name), python_str(value)))
+            .collect();
+        format!(", headers={{{}}}", items.join(", "))
+    }
+    
+    /// The expression sending a request with `call`, e.g. `requests.get(url)` or `client.get(url)`, awaited in async
+    /// tests, and sent again after transient failures when the options ask for retries. Requests pass the options'
+    /// timeout, which async tests set on their client instead
+    fn send_request(&self, call: &str) -> String {
+        let call = match self.options.timeout_secs {
+            Some(secs) if !self.is_async => format!("{}, timeout={})", call.strip_suffix(')').unwrap_or(call), secs),
+            _ => call.to_string(),
+        };
+        match (self.is_async, self.options.retries > 0) {
+            (false, false) => call,
+            (false, true) => format!("with_retries(lambda: {})", call),
+            (true, false) => format!("await {}", call),
+            (true, true) => format!("await with_retries(lambda: {})", call),
+        }
     }
     
-    fn generate_operation_test(&self, operation: &ApiOperation, path: &str, base_url: &str) -> String {
+    fn generate_operation_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        resources: &Resources,
+        credentials: &[Credential],
+    ) -> String {
         let method = operation.method.to_lowercase();
-        let operation_id = &operation.operation_id;
+        let operation_id = format!("{}{}", operation.operation_id, variant_suffix(variant));
         let summary = operation.summary.as_deref().unwrap_or("");
+        let setup = resources.setup.as_ref();
+        
+        // Deprecated operations are still generated, but skipped
+        let mut skip = if operation.deprecated { "@pytest.mark.skip(reason=\"deprecated operation\")\n" } else { "" }.to_string();
+        if self.is_async {
+            skip.push_str("@pytest.mark.asyncio\n");
+        }
+        
+        // Parameters supplied by a link are taken from the response of a setup request
+        let linked = setup.map(|setup| setup.linked_params(operation)).unwrap_or_default();
+        let is_linked = |name: &str| linked.iter().any(|(p, _)| p.name == name);
         
         // Parameter setup
-        let path_params_setup = operation.path_params.iter()
-            .map(|p| format!("    # Path parameter: {}\n    {} = 1  # Replace with actual test value", p.name, p.name))
-            .collect::<Vec<_>>()
-            .join("\n");
+        let mut path_params_setup = setup
+            .map(|setup| self.generate_link_setup(setup, operation, base_url, resources.setup_cleanup.as_ref()))
+            .into_iter()
+            .collect::<Vec<_>>();
+        path_params_setup.extend(operation.path_params.iter()
+            .filter(|p| !is_linked(&p.name))
+            .map(|p| match specified_value(p) {
+                Some(_) => format!("    # Path parameter: {}\n    {} = {}", p.name, p.name, to_python_literal(&path_value(p), "")),
+                None => format!("    # Path parameter: {}\n    {} = {}  # Replace with actual test value", p.name, p.name, to_python_literal(&path_value(p), "")),
+            }));
+        let path_params_setup = path_params_setup.join("\n");
         
-        let query_params = if !operation.query_params.is_empty() {
-            "    params = {\n".to_string() + &operation.query_params.iter()
-                .map(|p| format!(r#"        "{}": "test_value""#, p.name))
-                .collect::<Vec<_>>()
-                .join(",\n") + "\n    }"
+        // Query parameters, and the credentials sent in the query
+        let query_entries: Vec<String> = operation.query_params.iter()
+            .map(|p| if is_linked(&p.name) {
+                format!(r#"        "{}": {}"#, p.name, p.name)
+            } else {
+                format!(r#"        "{}": {}"#, p.name, to_python_literal(&Value::String(query_value(p)), ""))
+            })
+            .chain(python_credentials(credentials, "query").into_iter()
+                .map(|(name, value)| format!("        {}: {}", to_python_literal(&Value::String(name), ""), value)))
+            .collect();
+        let query_params = if !query_entries.is_empty() {
+            "    params = {\n".to_string() + &query_entries.join(",\n") + "\n    }"
         } else {
             "    params = {}".to_string()
         };
         
-        let body_param = operation.body_param.as_ref()
-            .map(|_| r#"    json_data = {
-        "name": "Test User",
-        "email": "test@example.com"
-    }"#.to_string())
-            .unwrap_or_else(|| "    json_data = None".to_string());
-        
-        // Create path with parameter interpolation
-        let mut endpoint_path = path.to_string();
-        for param in &operation.path_params {
-            endpoint_path = endpoint_path.replace(&format!("{{{}}}", param.name), &format!("{{{}}}", param.name));
-        }
+        let encoding = operation.body_param.as_ref().map(|body| (body, body_encoding(body)));
+        let python_str = |text: &str| to_python_literal(&Value::String(text.to_string()), "");
         
-        // Request construction
-        let request_call = match method.as_str() {
-            "get" => "response = requests.get(url, params=params)",
-            "post" => "response = requests.post(url, json=json_data, params=params)",
-            "put" => "response = requests.put(url, json=json_data, params=params)",
-            "delete" => "response = requests.delete(url, params=params)",
-            _ => "response = requests.get(url, params=params)",
+        let body_param = if let Some((body, BodyEncoding::Multipart)) = encoding {
+            let fields = form_fields(body);
+            let data = fields.iter()
+                .filter_map(|(name, value)| value.as_ref().map(|value| format!("        {}: {}", python_str(name), python_str(value))))
+                .collect::<Vec<_>>();
+            // Retried requests send the file's content again, which an open file has already been read to the end of
+            let upload = if self.options.retries > 0 { "(\"upload.txt\", upload_file.read_bytes())" } else { "upload_file.open(\"rb\")" };
+            let files = fields.iter()
+                .filter(|(_, value)| value.is_none())
+                .map(|(name, _)| format!("        {}: {}", python_str(name), upload))
+                .collect::<Vec<_>>();
+            format!(
+                "    upload_file = tmp_path / \"upload.txt\"\n    upload_file.write_text(\"test file content\")\n    data = {{\n{}\n    }}\n    files = {{\n{}\n    }}",
+                data.join(",\n"),
+                files.join(",\n")
+            )
+        } else if let Some((body, BodyEncoding::Form)) = encoding {
+            let data = form_fields(body)
+                .into_iter()
+                .map(|(name, value)| format!("        {}: {}", python_str(&name), python_str(&value.unwrap_or_default())))
+                .collect::<Vec<_>>();
+            format!("    data = {{\n{}\n    }}", data.join(",\n"))
+        } else if let Some((body, BodyEncoding::Raw)) = encoding {
+            format!("    data = {}", python_str(&raw_body_example(body)))
+        } else if let Some(example) = request_body_example(operation, variant) {
+            format!("    json_data = {}", to_python_literal(&example, "    "))
+        } else {
+            "    json_data = None".to_string()
         };
         
-        // Expected status code
-        let mut expected_status = "200";
-        if method == "post" {
-            expected_status = "201";
-        } else if method == "delete" {
-            expected_status = "204";
-        }
+        // Path parameters use the same `{name}` syntax as the spec, so the path can be interpolated as-is
+        let endpoint_path = path;
+        
+        // Header parameters and credentials. httpx no longer takes cookies per request, so async tests send cookie parameters
+        // as a `Cookie` header
+        let mut header_values = request_headers(operation, &self.options.headers);
+        if self.is_async {
+            header_values.extend(cookie_header(operation).map(|cookies| ("Cookie".to_string(), cookies)));
+        }
+        let header_values: Vec<(String, String)> = header_values
+            .into_iter()
+            .map(|(name, value)| (name, python_str(&value)))
+            .chain(python_credentials(credentials, "header"))
+            .collect();
+        let query_params = if !header_values.is_empty() {
+            query_params + "\n    headers = {\n" + &header_values.iter()
+                .map(|(name, value)| format!(r#"        "{}": {}"#, name, value))
+                .collect::<Vec<_>>()
+                .join(",\n") + "\n    }"
+        } else {
+            query_params
+        };
+        let headers_arg = if header_values.is_empty() { "" } else { ", headers=headers" };
+        
+        // Cookie parameters
+        let query_params = if !operation.cookie_params.is_empty() && !self.is_async {
+            query_params + "\n    cookies = {\n" + &operation.cookie_params.iter()
+                .map(|p| format!(r#"        "{}": {}"#, p.name, to_json_literal(&Value::String(to_plain_text(&example_for_parameter(p))), "")))
+                .collect::<Vec<_>>()
+                .join(",\n") + "\n    }"
+        } else {
+            query_params
+        };
+        let headers_arg = if operation.cookie_params.is_empty() || self.is_async {
+            headers_arg.to_string()
+        } else {
+            format!("{}, cookies=cookies", headers_arg)
+        };
+        
+        // Basic authentication credentials, which requests and httpx encode themselves
+        let headers_arg = match python_basic_auth(credentials) {
+            Some(auth) => format!("{}, auth={}", headers_arg, auth),
+            None => headers_arg,
+        };
+        
+        // Request construction. httpx takes raw bodies as `content`
+        let client = if self.is_async { "client" } else { "requests" };
+        let raw_arg = if self.is_async { "content" } else { "data" };
+        let request_call = match method.as_str() {
+            "get" | "head" | "options" => format!("{}.{}(url, params=params{})", client, method, headers_arg),
+            "post" | "put" | "patch" if matches!(encoding, Some((_, BodyEncoding::Multipart))) => {
+                format!("{}.{}(url, data=data, files=files, params=params{})", client, method, headers_arg)
+            },
+            "post" | "put" | "patch" if matches!(encoding, Some((_, BodyEncoding::Form))) => {
+                format!("{}.{}(url, data=data, params=params{})", client, method, headers_arg)
+            },
+            "post" | "put" | "patch" if matches!(encoding, Some((_, BodyEncoding::Raw))) => {
+                format!("{}.{}(url, {}=data, params=params{})", client, method, raw_arg, headers_arg)
+            },
+            "post" => format!("{}.post(url, json=json_data, params=params{})", client, headers_arg),
+            "put" | "patch" => format!("{}.{}(url, json=json_data, params=params{})", client, method, headers_arg),
+            "delete" => format!("{}.delete(url, params=params{})", client, headers_arg),
+            _ => format!("{}.get(url, params=params{})", client, headers_arg),
+        };
+        let request_call = format!("response = {}", self.send_request(&request_call));
+        
+        // Expected status code
+        let mut expected_status = "200";
+        if method == "post" {
+            expected_status = "201";
+        } else if method == "delete" {
+            expected_status = "204";
+        }
         
         // Find the expected status from the responses
         for resp in &operation.responses {
@@ -404,8 +2161,71 @@ impl PytestGenerator {
             }
         }
         
+        // Headers documented on the expected response must be returned
+        let expected_headers = operation.responses.iter()
+            .find(|resp| resp.status_code == expected_status)
+            .map(|resp| resp.headers.as_slice())
+            .unwrap_or_default();
+        let header_assertions = expected_headers.iter()
+            .filter(|_| self.options.assert_level.checks_response())
+            .map(|h| format!("\n    assert {} in response.headers", to_python_literal(&Value::String(h.name.clone()), "")))
+            .collect::<String>();
+        
+        // requests and httpx both time the response, as `elapsed`
+        let latency_assertion = self.options.max_response_time_ms
+            .map(|max| format!("\n    assert response.elapsed.total_seconds() * 1000 <= {}, f\"Response took {{response.elapsed.total_seconds() * 1000:.0f}} ms, more than {} ms\"", max, max))
+            .unwrap_or_default();
+        
+        // What the request created is deleted once the test is done
+        let created_cleanup = resources.cleanup.as_ref()
+            .map(|cleanup| self.generate_cleanup(cleanup, base_url, "response"))
+            .unwrap_or_default();
+        
+        // Async tests send their requests with the `client` fixture, uploads write their file into pytest's temporary
+        // directory, the items tests create are deleted by the `cleanup` fixture, and access tokens come from the
+        // session's `access_token` fixture
+        let mut fixtures = Vec::new();
+        if self.is_async {
+            fixtures.push("client");
+        }
+        if matches!(encoding, Some((_, BodyEncoding::Multipart))) {
+            fixtures.push("tmp_path");
+        }
+        if resources.cleans_up() {
+            fixtures.push("cleanup");
+        }
+        if uses_access_token(credentials) {
+            fixtures.push("access_token");
+        }
+        let fixtures = fixtures.join(", ");
+        let def = if self.is_async { "async def" } else { "def" };
+        
+        // The response body is validated against the documented schema, written to schemas/, and at the exact level
+        // compared to the documented example
+        let example_check = match response_example(operation) {
+            Some(example) if self.options.assert_level == AssertLevel::Exact => format!(
+                "# Verify the response body is the documented example\n    assert response.json() == {}",
+                to_python_literal(&example, "    ")
+            ),
+            _ => String::new(),
+        };
+        let body_check = if !self.options.assert_level.checks_response() {
+            "# Only the status is checked".to_string()
+        } else if method == "head" {
+            "# HEAD responses have no body\n    assert response.content == b\"\"".to_string()
+        } else if method == "options" {
+            "# Verify the allowed methods are listed\n    assert \"Allow\" in response.headers or \"Access-Control-Allow-Methods\" in response.headers".to_string()
+        } else if response_validation_schema(operation).is_some() {
+            format!("# Verify the response body matches its schema\n    validate_schema(response.json(), {})", python_str(&operation.operation_id))
+        } else if !example_check.is_empty() {
+            String::new()
+        } else {
+            "# Verify the response body\n    # response_json = response.json()\n    # assert \"id\" in response_json".to_string()
+        };
+        let body_check = [body_check, example_check].into_iter().filter(|check| !check.is_empty()).collect::<Vec<_>>().join("\n    \n    ");
+        
         format!(
-            r#"def test_{operation_id}():
+            r#"{skip}{def} test_{operation_id}({fixtures}):
     """
     {summary}
     """
@@ -417,237 +2237,2661 @@ impl PytestGenerator {
     {request_call}
     
     # Verify status code
-    assert response.status_code == {expected_status}
+    assert response.status_code == {expected_status}{latency_assertion}{header_assertions}{created_cleanup}
     
-    # Verify the response body
-    # response_json = response.json()
-    # assert "id" in response_json
+    {body_check}
 "#
         )
     }
 }
 
+impl PytestGenerator {
+    /// The negative and boundary tests of one request of an operation, each sending a variation of its request
+    fn generate_case_tests(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let python_str = |text: &str| to_python_literal(&Value::String(text.to_string()), "");
+        let dict = |pairs: Vec<(String, String)>| {
+            let items: Vec<String> = pairs.iter().map(|(name, value)| format!("{}: {}", python_str(name), value)).collect();
+            format!("{{{}}}", items.join(", "))
+        };
+        let (def, client_fixture, client) = if self.is_async {
+            ("@pytest.mark.asyncio\nasync def", Some("client"), "client")
+        } else {
+            ("def", None, "requests")
+        };
+        let assertion = |expected: Option<&str>| match expected {
+            Some(expected) => format!("assert response.status_code == {}", expected),
+            None => "assert 400 <= response.status_code < 500".to_string(),
+        };
+        let media_type_check = |media_type: Option<&str>| {
+            media_type
+                .map(|media_type| format!("\n    assert response.headers.get(\"Content-Type\", \"\").startswith({})", python_str(media_type)))
+                .unwrap_or_default()
+        };
+        
+        request_cases(operation, variant, &self.options)
+            .into_iter()
+            .map(|case| {
+                let mut args = vec![python_str(&operation.method.to_uppercase()), "url".to_string()];
+                let literals = |pairs: Vec<(String, String)>| pairs.into_iter().map(|(name, value)| (name, python_str(&value)));
+                let credentials = case.credentials(credentials);
+                let fixtures = client_fixture.into_iter()
+                    .chain(uses_access_token(credentials).then_some("access_token"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let query: Vec<_> = literals(case.query(operation)).chain(python_credentials(credentials, "query")).collect();
+                if !query.is_empty() {
+                    args.push(format!("params={}", dict(query)));
+                }
+                let headers: Vec<_> = literals(case.headers(operation, &self.options.headers)).chain(python_credentials(credentials, "header")).collect();
+                if !headers.is_empty() {
+                    args.push(format!("headers={}", dict(headers)));
+                }
+                let body = case.body(operation, variant);
+                let body_decl = body.as_ref()
+                    .map(|body| format!("\n    json_data = {}\n", to_python_literal(body, "    ")))
+                    .unwrap_or_default();
+                if body.is_some() {
+                    args.push("json=json_data".to_string());
+                }
+                args.extend(python_basic_auth(credentials).map(|auth| format!("auth={}", auth)));
+                
+                format!(
+                    r#"
+
+{def} test_{operation_id}{suffix}_{name}({fixtures}):
+    """
+    Sent {description}, the request has to be {outcome}
+    """{body_decl}
+    url = {url}
+    response = {send}
+    
+    # Verify the request is {outcome}
+    {assertion}{media_type_check}
+"#,
+                    operation_id = operation.operation_id,
+                    suffix = variant_suffix(variant),
+                    name = case.name,
+                    description = case.description,
+                    outcome = case.outcome(),
+                    url = self.url_literal(base_url, &case.path(path, operation)),
+                    send = self.send_request(&format!("{}.request({})", client, args.join(", "))),
+                    assertion = assertion(case.expected_status(operation)),
+                    media_type_check = media_type_check(case.media_type()),
+                )
+            })
+            .collect()
+    }
+    
+    /// A test of an operation parametrized over the requests the data file has for it, each sent with its parameters,
+    /// body and status. Returns nothing when there are none
+    fn generate_data_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let cases = data_cases(operation, path, variant, &self.options);
+        if cases.is_empty() {
+            return String::new();
+        }
+        let python_str = |text: &str| to_python_literal(&Value::String(text.to_string()), "");
+        let pairs = |pairs: &[(String, String)]| Value::Object(pairs.iter().map(|(name, value)| (name.clone(), Value::String(value.clone()))).collect());
+        let cases: Vec<Value> = cases.iter()
+            .map(|case| json!({
+                "path": case.path,
+                "params": pairs(&case.query),
+                "headers": pairs(&case.headers),
+                "json": case.body,
+                "status": case.status.parse::<u16>().unwrap_or(200),
+            }))
+            .collect();
+        
+        // Credentials are added to the parameters and headers of each request
+        let merged = |field: &str, credentials: Vec<(String, String)>| {
+            if credentials.is_empty() {
+                format!("case[\"{}\"]", field)
+            } else {
+                let items: Vec<String> = credentials.iter().map(|(name, value)| format!("{}: {}", python_str(name), value)).collect();
+                format!("{{**case[\"{}\"], {}}}", field, items.join(", "))
+            }
+        };
+        let client = if self.is_async { "client" } else { "requests" };
+        let mut args = vec![
+            python_str(&operation.method.to_uppercase()),
+            "url".to_string(),
+            format!("params={}", merged("params", python_credentials(credentials, "query"))),
+            format!("headers={}", merged("headers", python_credentials(credentials, "header"))),
+            "json=case[\"json\"]".to_string(),
+        ];
+        args.extend(python_basic_auth(credentials).map(|auth| format!("auth={}", auth)));
+        let mut fixtures = vec!["case"];
+        if self.is_async {
+            fixtures.push("client");
+        }
+        if uses_access_token(credentials) {
+            fixtures.push("access_token");
+        }
+        let base_url = if self.options.environments.is_empty() { python_str(base_url) } else { "BASE_URL".to_string() };
+        
+        format!(
+            r#"
+
+{skip}@pytest.mark.parametrize("case", {cases})
+{def} test_{operation_id}_data({fixtures}):
+    """
+    {summary}, sending each request the data file has for it
+    """
+    url = {base_url} + case["path"]
+    response = {send}
+    
+    assert response.status_code == case["status"]
+"#,
+            skip = if operation.deprecated { "@pytest.mark.skip(reason=\"deprecated operation\")\n" } else { "" },
+            cases = to_python_literal(&Value::Array(cases), ""),
+            def = if self.is_async { "@pytest.mark.asyncio\nasync def" } else { "def" },
+            operation_id = operation.operation_id,
+            fixtures = fixtures.join(", "),
+            summary = operation.summary.as_deref().unwrap_or(&operation.operation_id),
+            send = self.send_request(&format!("{}.request({})", client, args.join(", "))),
+        )
+    }
+    
+    /// A test asking an operation that pages its results for its first two pages, which have to differ and hold no more
+    /// items than the limit. Returns nothing when the operation gets no pagination test
+    fn generate_pagination_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let Some(pages) = page_requests(operation, path, variant, &self.options) else {
+            return String::new();
+        };
+        let python_str = |text: &str| to_python_literal(&Value::String(text.to_string()), "");
+        let dict = |pairs: Vec<(String, String)>| {
+            let items: Vec<String> = pairs.iter().map(|(name, value)| format!("{}: {}", python_str(name), value)).collect();
+            format!("{{{}}}", items.join(", "))
+        };
+        let literals = |pairs: &[(String, String)]| pairs.iter().map(|(name, value)| (name.clone(), python_str(value))).collect::<Vec<_>>();
+        let client = if self.is_async { "client" } else { "requests" };
+        let send = |query: Vec<(String, String)>| {
+            let mut args = vec![python_str("GET"), "url".to_string()];
+            let query: Vec<_> = query.into_iter().chain(python_credentials(credentials, "query")).collect();
+            if !query.is_empty() {
+                args.push(format!("params={}", dict(query)));
+            }
+            let headers: Vec<_> = literals(&pages.headers).into_iter().chain(python_credentials(credentials, "header")).collect();
+            if !headers.is_empty() {
+                args.push(format!("headers={}", dict(headers)));
+            }
+            args.extend(python_basic_auth(credentials).map(|auth| format!("auth={}", auth)));
+            self.send_request(&format!("{}.request({})", client, args.join(", ")))
+        };
+        let body_value = |pointer: &str| {
+            pointer_segments(pointer).iter().fold("response.json()".to_string(), |value, name| format!("{}[{}]", value, python_str(name)))
+        };
+        let page = |number: &str, query: Vec<(String, String)>| {
+            let limit = pages.limit.map(|limit| format!("\n    assert len({}_items) <= {}", number, limit)).unwrap_or_default();
+            format!(
+                "response = {}\n    assert response.status_code == {}\n    {}_items = {}{}",
+                send(query),
+                pages.status,
+                number,
+                body_value(&pages.items_pointer),
+                limit,
+            )
+        };
+        
+        let mut second_query = literals(&pages.second_query);
+        let cursor = match &pages.cursor {
+            Some((param, pointer)) => {
+                second_query.push((param.clone(), "cursor".to_string()));
+                format!("\n    cursor = {}\n    assert cursor, \"Expected the cursor of the next page\"", body_value(pointer))
+            },
+            None => String::new(),
+        };
+        let mut fixtures = Vec::new();
+        if self.is_async {
+            fixtures.push("client");
+        }
+        if uses_access_token(credentials) {
+            fixtures.push("access_token");
+        }
+        
+        format!(
+            r#"
+
+{def} test_{operation_id}_pagination({fixtures}):
+    """
+    Asks for two pages of {operation_id}, which have to differ{limit}
+    """
+    url = {url}
+    {first}{cursor}
+    
+    {second}
+    
+    assert first_items != second_items, "Expected the second page to differ from the first"
+"#,
+            def = if self.is_async { "@pytest.mark.asyncio\nasync def" } else { "def" },
+            operation_id = operation.operation_id,
+            fixtures = fixtures.join(", "),
+            limit = pages.limit.map(|limit| format!(" and hold at most {} items each", limit)).unwrap_or_default(),
+            url = self.url_literal(base_url, &pages.path),
+            first = page("first", literals(&pages.first_query)),
+            second = page("second", second_query),
+        )
+    }
+    
+    /// A Hypothesis test of an operation, sending requests with bodies and query parameters drawn from their schemas,
+    /// which the API must answer without a server error. Hypothesis doesn't run async tests, so async ones send their
+    /// requests with httpx's synchronous API
+    fn generate_fuzz_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let python_str = |text: &str| to_python_literal(&Value::String(text.to_string()), "");
+        let dict = |pairs: Vec<(String, String)>| {
+            let items: Vec<String> = pairs.iter().map(|(name, value)| format!("{}: {}", python_str(name), value)).collect();
+            format!("{{{}}}", items.join(", "))
+        };
+        let skip = if operation.deprecated { "@pytest.mark.skip(reason=\"deprecated operation\")\n" } else { "" };
+        let body = body_strategy(operation, variant);
+        let query = query_strategies(operation);
+        
+        let mut strategies: Vec<String> = body.iter()
+            .map(|strategy| format!("    json_data={},", strategy.hypothesis("    ")))
+            .collect();
+        strategies.extend(query.iter().map(|(_, variable, strategy)| format!("    {}={},", variable, strategy.hypothesis("    "))));
+        let mut variables: Vec<&str> = body.iter().map(|_| "json_data").collect();
+        variables.extend(query.iter().map(|(_, variable, _)| variable.as_str()));
+        // The session's access tokens are a fixture, which Hypothesis leaves to pytest
+        if uses_access_token(credentials) {
+            variables.push("access_token");
+        }
+        
+        let mut args = vec![python_str(&operation.method.to_uppercase()), "url".to_string()];
+        let drawn = query.iter().map(|(param, variable, _)| (param.name.clone(), variable.clone()));
+        let params: Vec<_> = drawn.chain(python_credentials(credentials, "query")).collect();
+        if !params.is_empty() {
+            args.push(format!("params={}", dict(params)));
+        }
+        let headers: Vec<_> = fixed_headers(operation, &self.options.headers)
+            .into_iter()
+            .map(|(name, value)| (name, python_str(&value)))
+            .chain(python_credentials(credentials, "header"))
+            .collect();
+        if !headers.is_empty() {
+            args.push(format!("headers={}", dict(headers)));
+        }
+        if body.is_some() {
+            args.push("json=json_data".to_string());
+        }
+        args.extend(python_basic_auth(credentials).map(|auth| format!("auth={}", auth)));
+        
+        format!(
+            r#"
+
+{skip}@settings(max_examples={cases}, deadline=None)
+@given(
+{strategies}
+)
+def test_{operation_id}{suffix}({variables}):
+    """
+    {summary}, with values drawn from the schemas: the API must answer without a server error
+    """
+    url = {url}
+    response = {client}.request({args})
+    
+    assert response.status_code < 500
+"#,
+            cases = FUZZ_CASES,
+            strategies = strategies.join("\n"),
+            operation_id = operation.operation_id,
+            suffix = variant_suffix(variant),
+            variables = variables.join(", "),
+            summary = operation.summary.as_deref().unwrap_or(&operation.operation_id),
+            url = self.url_literal(base_url, &example_path(path, operation)),
+            client = if self.is_async { "httpx" } else { "requests" },
+            args = args.join(", "),
+        )
+    }
+    
+    /// A test creating a resource, then reading, updating and deleting it through the operations on it, and checking it
+    /// can't be read once deleted
+    fn generate_lifecycle_test(&self, spec: &SwaggerSpec, lifecycle: &Lifecycle, base_url: &str) -> String {
+        let python_str = |text: &str| to_python_literal(&Value::String(text.to_string()), "");
+        let dict = |pairs: Vec<(String, String)>| {
+            let items: Vec<String> = pairs.iter().map(|(name, value)| format!("{}: {}", python_str(name), value)).collect();
+            format!("{{{}}}", items.join(", "))
+        };
+        let (def, client_fixture, client) = if self.is_async {
+            ("@pytest.mark.asyncio\nasync def", Some("client"), "client")
+        } else {
+            ("def", None, "requests")
+        };
+        let uses_access_token = lifecycle.steps.iter()
+            .any(|step| uses_access_token(&operation_credentials(spec, step.operation, &self.options.auth)));
+        let fixtures = client_fixture.into_iter()
+            .chain(uses_access_token.then_some("access_token"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        
+        let mut code = vec![self.generate_link_setup(&lifecycle.create, lifecycle.read_operation(), base_url, None)];
+        code.extend(lifecycle.unbound_params().into_iter().map(|p| format!("    {} = {}", p.name, to_python_literal(&path_value(p), ""))));
+        for step in &lifecycle.steps {
+            let credentials = operation_credentials(spec, step.operation, &self.options.auth);
+            let mut args = vec![
+                python_str(&step.operation.method.to_uppercase()),
+                format!("f{}", python_str(&format!("{}{}{}", base_url, step.path, step.query()))),
+            ];
+            let query = python_credentials(&credentials, "query");
+            if !query.is_empty() {
+                args.push(format!("params={}", dict(query)));
+            }
+            let headers: Vec<_> = fixed_headers(step.operation, &self.options.headers)
+                .into_iter()
+                .map(|(name, value)| (name, python_str(&value)))
+                .chain(python_credentials(&credentials, "header"))
+                .collect();
+            if !headers.is_empty() {
+                args.push(format!("headers={}", dict(headers)));
+            }
+            if let Some(body) = step.body().filter(|_| step.action == Action::Update) {
+                args.push(format!("json={}", to_python_literal(&body, "    ")));
+            }
+            args.extend(python_basic_auth(&credentials).map(|auth| format!("auth={}", auth)));
+            code.push(format!(
+                "\n    # {}: {}\n    response = {}\n    assert response.status_code == {}",
+                step.action.description(),
+                step.operation.operation_id,
+                self.send_request(&format!("{}.request({})", client, args.join(", "))),
+                step.expected_status(),
+            ));
+        }
+        
+        format!(
+            r#"
+
+{def} test_{name}_lifecycle({fixtures}):
+    """
+    Create a resource with {create}, then read, update and delete it, and check it is gone
+    """
+{code}
+"#,
+            name = lifecycle.name,
+            create = lifecycle.create.operation.operation_id,
+            code = code.join("\n"),
+        )
+    }
+    
+    /// Code sending the setup request of a link, and binding the values it supplies to the parameters of `target`, then
+    /// registering the deletion of what it created
+    fn generate_link_setup(&self, setup: &LinkedSetup, target: &ApiOperation, base_url: &str, cleanup: Option<&Cleanup>) -> String {
+        let body = setup.request_body()
+            .map(|body| format!(", json={}", to_python_literal(&body, "    ")))
+            .unwrap_or_default();
+        
+        let (client, ok) = if self.is_async { ("client", "is_success") } else { ("requests", "ok") };
+        let mut code = format!(
+            r#"    # Call {operation_id} first, and take parameters from its response ({origin})
+    setup_response = {send}
+    assert setup_response.{ok}"#,
+            operation_id = setup.operation.operation_id,
+            origin = setup.origin(),
+            send = self.send_request(&format!(
+                "{}.request(\"{}\", f\"{}{}\"{}{})",
+                client, setup.operation.method.to_uppercase(), base_url, setup.request_path(), body, self.static_headers_arg()
+            )),
+        );
+        
+        for (param, value) in setup.linked_params(target) {
+            code.push_str(&format!("\n    {} = {}", param.name, python_link_value(setup, value, "setup_response")));
+        }
+        if let Some(cleanup) = cleanup {
+            code.push_str(&self.generate_cleanup(cleanup, base_url, "setup_response"));
+        }
+        
+        code
+    }
+    
+    /// Code adding the URL of an item a request created to the `cleanup` fixture, which deletes it once the test is done,
+    /// with the values read from the response bound to `response`
+    fn generate_cleanup(&self, cleanup: &Cleanup, base_url: &str, response: &str) -> String {
+        let params: Vec<String> = cleanup.params()
+            .into_iter()
+            .map(|(param, value)| format!("{}={}", param.name, python_link_value(&cleanup.setup, value, response)))
+            .collect();
+        format!(
+            "\n    # Delete what {} created once the test is done: {}\n    cleanup.append({}.format({}))",
+            cleanup.setup.operation.operation_id,
+            cleanup.operation.operation_id,
+            self.url_literal(base_url, cleanup.path),
+            params.join(", "),
+        )
+    }
+    
+    /// Write `test_webhooks.py`, delivering each webhook's documented payload to the receiver under test
+    fn generate_webhook_tests(&self, webhooks: &[(&str, &ApiOperation)], output_dir: &Path) -> Result<()> {
+        let mut file = File::create(output_dir.join("test_webhooks.py"))?;
+        
+        if self.is_async {
+            writeln!(file, r#"import os
+import pytest
+
+# Endpoint of the webhook receiver under test
+WEBHOOK_URL = os.environ.get("WEBHOOK_URL", "{DEFAULT_WEBHOOK_URL}")
+
+
+@pytest.fixture
+def deliver_webhook(client):
+    """
+    Send a webhook request to the receiver under test, the way the API would
+    """
+    async def deliver(method, payload=None):
+        return await client.request(method, WEBHOOK_URL, json=payload)
+    return deliver
+"#)?;
+        } else {
+            writeln!(file, r#"import os
+import requests
+import pytest
+
+# Endpoint of the webhook receiver under test
+WEBHOOK_URL = os.environ.get("WEBHOOK_URL", "{DEFAULT_WEBHOOK_URL}")
+
+
+@pytest.fixture
+def deliver_webhook():
+    """
+    Send a webhook request to the receiver under test, the way the API would
+    """
+    def deliver(method, payload=None):
+        return requests.request(method, WEBHOOK_URL, json=payload)
+    return deliver
+"#)?;
+        }
+        
+        for (name, operation) in webhooks {
+            let operation_id = &operation.operation_id;
+            let summary = operation.summary.as_deref().unwrap_or(name);
+            let skip = if operation.deprecated { "@pytest.mark.skip(reason=\"deprecated operation\")\n" } else { "" };
+            let method = operation.method.to_uppercase();
+            let (payload, payload_arg) = match operation.body_param.as_ref() {
+                Some(body) => (
+                    format!("\n    payload = {}\n", to_python_literal(&body_example(body), "    ")),
+                    ", payload",
+                ),
+                None => (String::new(), ""),
+            };
+            let expected_status = webhook_status(operation);
+            let (skip, def, deliver) = if self.is_async {
+                (format!("{}@pytest.mark.asyncio\n", skip), "async def", "await deliver_webhook")
+            } else {
+                (skip.to_string(), "def", "deliver_webhook")
+            };
+            
+            writeln!(file, r#"
+{skip}{def} test_webhook_{operation_id}(deliver_webhook):
+    """
+    {summary}
+    """{payload}
+    response = {deliver}("{method}"{payload_arg})
+    
+    assert response.status_code == {expected_status}
+"#)?;
+        }
+        
+        Ok(())
+    }
+}
+
 impl TestGenerator for PytestGenerator {
     fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
         // Create the output directory if it doesn't exist
         fs::create_dir_all(output_dir)?;
         
-        // Create a single test file for all operations
-        let test_file_path = output_dir.join("test_api.py");
-        let mut file = File::create(test_file_path)?;
+        // With environments, request URLs start with the `BASE_URL` of the one tested instead
+        let tests_base_url = if self.options.environments.is_empty() { base_url.to_string() } else { "{BASE_URL}".to_string() };
+        write_env_files(spec, &self.options, output_dir)?;
+        
+        // Create a single test file for all operations, or one per group
+        let files = if self.options.layout.unwrap_or(OutputLayout::Single) != OutputLayout::Single {
+            operation_groups(spec, &self.options)
+                .into_iter()
+                .map(|group| (format!("test_{}.py", tag_file_stem(&group.file_stem)), group.operations))
+                .collect()
+        } else {
+            vec![("test_api.py".to_string(), all_operations(spec, &self.options))]
+        };
         
-        // Write the file header
-        writeln!(file, "import requests\nimport pytest\n")?;
+        let operations: Vec<_> = files.iter().map(|(_, operations)| operations.clone()).collect();
+        let tests = generate_in_parallel(&operations, &self.options, |path, operation, variant| {
+            let resources = resources(spec, operation);
+            let credentials = operation_credentials(spec, operation, &self.options.auth);
+            let test = if self.options.fuzz && is_fuzzed(operation) {
+                self.generate_fuzz_test(operation, path, &tests_base_url, variant, &credentials)
+            } else {
+                self.generate_operation_test(operation, path, &tests_base_url, variant, &resources, &credentials)
+            };
+            test + &self.generate_case_tests(operation, path, &tests_base_url, variant, &credentials)
+                + &self.generate_data_test(operation, path, &tests_base_url, variant, &credentials)
+                + &self.generate_pagination_test(operation, path, &tests_base_url, variant, &credentials)
+        })?;
         
-        // Generate tests for each operation
-        for path in &spec.paths {
-            for operation in &path.operations {
-                let test_code = self.generate_operation_test(operation, &path.path, base_url);
+        let resource_lifecycles = lifecycles(spec, &self.options);
+        let mut validates_schemas = false;
+        let mut fuzzes = false;
+        let mut suite_credentials: Vec<Credential> = Vec::new();
+        for ((file_name, operations), tests) in files.into_iter().zip(tests) {
+            let mut file = File::create(output_dir.join(file_name))?;
+            // Each lifecycle is tested in the file of its create operation
+            let file_lifecycles: Vec<&Lifecycle> = resource_lifecycles.iter()
+                .filter(|lifecycle| operations.iter().any(|(_, op)| op.operation_id == lifecycle.create.operation.operation_id))
+                .collect();
+            
+            // Write the file header; async tests get their client from conftest.py
+            let validate_schema = self.options.assert_level.checks_response() && write_response_schemas(&operations, output_dir)?;
+            validates_schemas |= validate_schema;
+            // Standard library modules come first, then the third-party ones; credentials are read from `os.environ`, and
+            // access tokens come from the `access_token` fixture in conftest.py
+            let credentials: Vec<Credential> = operations.iter()
+                .map(|(_, operation)| *operation)
+                .chain(file_lifecycles.iter().flat_map(|lifecycle| lifecycle.steps.iter().map(|step| step.operation)))
+                .flat_map(|operation| operation_credentials(spec, operation, &self.options.auth))
+                .collect();
+            let reads_environ = credentials.iter().any(|credential| !matches!(credential, Credential::ClientCredentials { .. }));
+            let fuzz = self.options.fuzz && operations.iter().any(|(_, operation)| is_fuzzed(operation));
+            fuzzes |= fuzz;
+            let cleans_up = operations.iter().any(|(_, operation)| resources(spec, operation).cleans_up());
+            let imports = |statements: &[(&str, bool)]| {
+                statements.iter().filter(|(_, used)| *used).map(|(statement, _)| format!("{}\n", statement)).collect::<String>()
+            };
+            let retries = self.options.retries > 0;
+            let standard = imports(&[
+                ("import asyncio", self.is_async && retries),
+                ("import json", validate_schema),
+                ("import os", reads_environ || !self.options.environments.is_empty()),
+                ("import pathlib", validate_schema),
+                ("import time", !self.is_async && retries),
+            ]);
+            suite_credentials.extend(credentials);
+            let third_party = imports(&[
+                ("import httpx", self.is_async && (fuzz || retries)),
+                ("import jsonschema", validate_schema),
+                ("import requests", !self.is_async),
+                ("import pytest", true),
+                ("import pytest_asyncio", self.is_async && cleans_up),
+                ("from hypothesis import given, settings, strategies as st", fuzz),
+            ]);
+            let imports = if standard.is_empty() { third_party } else { format!("{}\n{}", standard, third_party) };
+            writeln!(file, "{}", imports)?;
+            if !self.options.environments.is_empty() {
+                writeln!(
+                    file,
+                    "# Base URL of the API under test, which the .env file of the environment tested sets (see conftest.py)\nBASE_URL = os.environ.get(\"{}\", {})\n",
+                    BASE_URL_VAR,
+                    to_python_literal(&Value::String(base_url.trim_end_matches('/').to_string()), ""),
+                )?;
+            }
+            if validate_schema {
+                writeln!(file, r#"SCHEMA_DIR = pathlib.Path(__file__).parent / "schemas"
+
+
+def validate_schema(body, operation_id):
+    """
+    Validate a response body against the schema of the operation's response, from schemas/
+    """
+    with open(SCHEMA_DIR / f"{{operation_id}}.json") as schema_file:
+        jsonschema.validate(body, json.load(schema_file))
+
+"#)?;
+            }
+            if retries {
+                let (def, send, error, sleep) = if self.is_async {
+                    ("async def", "await send()", "httpx.TransportError", "await asyncio.sleep(delay)")
+                } else {
+                    ("def", "send()", "requests.ConnectionError", "time.sleep(delay)")
+                };
+                writeln!(file, r#"
+{def} with_retries(send):
+    """
+    Send a request with send, again up to {retries} times after a connection error or a 408, 429 or 5xx status,
+    waiting {backoff} ms before the first retry and twice as long before each next one
+    """
+    delay = {backoff} / 1000
+    for _ in range({retries}):
+        try:
+            response = {send}
+            if response.status_code not in (408, 429) and response.status_code < 500:
+                return response
+        except {error}:
+            pass
+        {sleep}
+        delay *= 2
+    return {send}
+
+"#,
+                    retries = self.options.retries,
+                    backoff = self.options.retry_backoff_ms.unwrap_or(DEFAULT_RETRY_BACKOFF_MS))?;
+            }
+            if has_setups(spec) {
+                writeln!(file, r#"
+def linked_value(body, pointer):
+    """
+    Read the value a link points at in a response body, given as a JSON pointer
+    """
+    for token in pointer.split("/")[1:]:
+        token = token.replace("~1", "/").replace("~0", "~")
+        body = body[int(token)] if isinstance(body, list) else body[token]
+    return body
+
+"#)?;
+            }
+            if cleans_up && self.is_async {
+                writeln!(file, r#"
+@pytest_asyncio.fixture
+async def cleanup(client):
+    """
+    URLs of the items a test created, deleted once it is done, even when it fails
+    """
+    urls = []
+    yield urls
+    for url in reversed(urls):
+        await client.delete(url{headers})
+
+"#,
+                    headers = self.static_headers_arg())?;
+            } else if cleans_up {
+                writeln!(file, r#"
+@pytest.fixture
+def cleanup():
+    """
+    URLs of the items a test created, deleted once it is done, even when it fails
+    """
+    urls = []
+    yield urls
+    for url in reversed(urls):
+        requests.delete(url{headers})
+
+"#,
+                    headers = self.static_headers_arg())?;
+            }
+            
+            // Write the tests for each operation, then the lifecycle tests
+            for test_code in tests {
                 writeln!(file, "{}\n", test_code)?;
             }
+            for lifecycle in file_lifecycles {
+                writeln!(file, "{}\n", self.generate_lifecycle_test(spec, lifecycle, &tests_base_url))?;
+            }
+        }
+        
+        // Webhooks are tested against the consumer's receiver, in a file of their own
+        let webhooks = webhook_operations(spec, &self.options);
+        if !webhooks.is_empty() {
+            self.generate_webhook_tests(&webhooks, output_dir)?;
+        }
+        
+        // The .env file of the environment tested is loaded before the tests, access tokens are fetched once for the
+        // whole session, and async tests share an httpx client, closed once each test is done
+        let load_environment = default_environment(&self.options).map(|default| format!(
+            r#"# Settings of the environment {environment_var} names ("{default}" by default) from its .env file, which don't replace variables already set
+load_dotenv(pathlib.Path(__file__).parent / f".env.{{os.environ.get('{environment_var}', '{default}')}}")"#,
+            environment_var = ENVIRONMENT_VAR,
+        ));
+        let client_credentials = client_credentials_env(&suite_credentials);
+        let mut fixtures = Vec::new();
+        if let Some((client_id_env, client_secret_env)) = client_credentials {
+            fixtures.push(format!(r#"@pytest.fixture(scope="session")
+def access_token():
+    """
+    Fetch access tokens with the OAuth2 client credentials flow, shared by all the tests of the session: each one is
+    fetched once, and again a minute before it expires (after an hour when the token endpoint doesn't say)
+    """
+    tokens = {{}}
+    
+    def fetch(token_url, scope):
+        cached = tokens.get((token_url, scope))
+        if cached and cached[1] > time.monotonic():
+            return cached[0]
+        data = {{
+            "grant_type": "client_credentials",
+            "client_id": os.environ["{client_id_env}"],
+            "client_secret": os.environ["{client_secret_env}"],
+        }}
+        if scope:
+            data["scope"] = scope
+        response = {http}.post(token_url, data=data)
+        response.raise_for_status()
+        body = response.json()
+        tokens[(token_url, scope)] = (body["access_token"], time.monotonic() + max(body.get("expires_in", 3600) - 60, 0))
+        return body["access_token"]
+    
+    return fetch"#,
+                http = if self.is_async { "httpx" } else { "requests" }));
+        }
+        if self.is_async {
+            fixtures.push(format!(r#"@pytest_asyncio.fixture
+async def client():
+    """
+    The httpx client tests send their requests with
+    """
+    async with httpx.AsyncClient(timeout={timeout}) as client:
+        yield client"#,
+                timeout = self.options.timeout_secs.unwrap_or(30)));
+        }
+        if load_environment.is_some() || !fixtures.is_empty() {
+            let imports = |statements: &[(&str, bool)]| {
+                statements.iter().filter(|(_, used)| *used).map(|(statement, _)| format!("{}\n", statement)).collect::<String>()
+            };
+            let standard = imports(&[
+                ("import os", load_environment.is_some() || client_credentials.is_some()),
+                ("import pathlib", load_environment.is_some()),
+                ("import time", client_credentials.is_some()),
+            ]);
+            let third_party = imports(&[
+                ("import httpx", self.is_async),
+                ("import pytest", client_credentials.is_some()),
+                ("import pytest_asyncio", self.is_async),
+                ("import requests", client_credentials.is_some() && !self.is_async),
+                ("from dotenv import load_dotenv", load_environment.is_some()),
+            ]);
+            let mut conftest = if standard.is_empty() { third_party } else { format!("{}\n{}", standard, third_party) };
+            if let Some(load_environment) = &load_environment {
+                conftest.push_str(&format!("\n{}\n", load_environment));
+            }
+            for fixture in fixtures {
+                conftest.push_str(&format!("\n\n{}\n", fixture));
+            }
+            fs::write(output_dir.join("conftest.py"), conftest)?;
         }
         
         // Create a requirements.txt file
         let req_file_path = output_dir.join("requirements.txt");
         let mut req_file = File::create(req_file_path)?;
         
-        writeln!(req_file, "requests==2.28.1\npytest==7.3.1")?;
+        if validates_schemas {
+            writeln!(req_file, "jsonschema==4.22.0")?;
+        }
+        if fuzzes {
+            writeln!(req_file, "hypothesis==6.103.1")?;
+        }
+        if self.is_async {
+            writeln!(req_file, "httpx==0.27.0\npytest==8.2.2\npytest-asyncio==0.23.7")?;
+        } else {
+            writeln!(req_file, "requests==2.28.1\npytest==7.3.1")?;
+        }
+        if !self.options.environments.is_empty() {
+            writeln!(req_file, "python-dotenv==1.0.1")?;
+        }
         
         // Create a README.md file with instructions
         let readme_file_path = output_dir.join("README.md");
         let mut readme_file = File::create(readme_file_path)?;
         
-        writeln!(readme_file, r#"# API Tests
+        let flavor = if self.is_async { ", as async tests sending their requests with an httpx `AsyncClient`" } else { "" };
+        writeln!(readme_file, r#"# {title} Tests
 
-Generated API tests for the Swagger/OpenAPI specification.
+Generated API tests for {title} (version {version}){flavor}.
 
 ## Setup
 
 Install the requirements:
 
-```
-pip install -r requirements.txt
-```
+```
+pip install -r requirements.txt
+```
+
+## Running the tests
+
+To run the tests:
+
+```
+pytest -v
+```
+{environments}"#,
+            title = spec.info.title,
+            version = spec.info.version,
+            environments = readme_section(&self.options, "pytest -v"))?;
+        
+        Ok(())
+    }
+}
+
+// JavaScript Jest test generator
+struct JestGenerator {
+    options: GeneratorOptions,
+}
+
+impl JestGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        JestGenerator { options }
+    }
+    
+    /// An expression for the URL `path` makes up under `base_url`, which is `${BASE_URL}` when the options configure
+    /// environments, and the URL is added to the `BASE_URL` constant
+    fn url_literal(&self, base_url: &str, path: &str) -> String {
+        if self.options.environments.is_empty() {
+            to_json_literal(&Value::String(format!("{}{}", base_url, path)), "")
+        } else {
+            format!("BASE_URL + {}", to_json_literal(&Value::String(path.to_string()), ""))
+        }
+    }
+    
+    /// The options' static headers as the properties of an axios `headers` object, for the requests that don't take their
+    /// headers from `request_headers` (setup requests and cleanups)
+    fn static_headers_object(&self) -> String {
+        let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+        self.options.headers.iter()
+            .map(|(name, value)| format!("{}: {}", js_str(name), js_str(value)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+    
+    fn generate_operation_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        resources: &Resources,
+        credentials: &[Credential],
+    ) -> String {
+        let method = operation.method.to_lowercase();
+        let operation_id = match variant {
+            Some(variant) => format!("{} ({})", operation.operation_id, variant.name),
+            None => operation.operation_id.clone(),
+        };
+        let summary = operation.summary.as_deref().unwrap_or("");
+        let setup = resources.setup.as_ref();
+        
+        // Deprecated operations are still generated, but skipped
+        let test = if operation.deprecated { "test.skip" } else { "test" };
+        
+        // Parameters supplied by a link are taken from the response of a setup request
+        let linked = setup.map(|setup| setup.linked_params(operation)).unwrap_or_default();
+        let is_linked = |name: &str| linked.iter().any(|(p, _)| p.name == name);
+        
+        // Parameter setup
+        let mut path_params_setup = setup
+            .map(|setup| self.generate_link_setup(setup, operation, base_url, resources.setup_cleanup.as_ref()))
+            .into_iter()
+            .collect::<Vec<_>>();
+        path_params_setup.extend(operation.path_params.iter()
+            .filter(|p| !is_linked(&p.name))
+            .map(|p| match specified_value(p) {
+                Some(_) => format!("  // Path parameter: {}\n  const {} = {};", p.name, p.name, to_json_literal(&path_value(p), "")),
+                None => format!("  // Path parameter: {}\n  const {} = {}; // Replace with actual test value", p.name, p.name, to_json_literal(&path_value(p), "")),
+            }));
+        let path_params_setup = path_params_setup.join("\n");
+        
+        // Query parameters, and the credentials sent in the query
+        let query_entries: Vec<String> = operation.query_params.iter()
+            .map(|p| if is_linked(&p.name) {
+                format!(r#"    {}: {}"#, p.name, p.name)
+            } else {
+                format!(r#"    {}: {}"#, p.name, to_json_literal(&Value::String(query_value(p)), ""))
+            })
+            .chain(js_credentials(credentials, "query").into_iter()
+                .map(|(name, value)| format!("    {}: {}", to_json_literal(&Value::String(name), ""), value)))
+            .collect();
+        let query_params = if !query_entries.is_empty() {
+            "  const params = {\n".to_string() + &query_entries.join(",\n") + "\n  };"
+        } else {
+            "  const params = {};".to_string()
+        };
+        
+        let encoding = operation.body_param.as_ref().map(|body| (body, body_encoding(body)));
+        let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+        
+        let body_param = if let Some((body, BodyEncoding::Multipart)) = encoding {
+            let appends = form_fields(body)
+                .into_iter()
+                .map(|(name, value)| match value {
+                    Some(value) => format!("\n  form.append({}, {});", js_str(&name), js_str(&value)),
+                    None => format!("\n  form.append({}, fs.createReadStream(uploadPath));", js_str(&name)),
+                })
+                .collect::<String>();
+            format!(
+                "  const uploadPath = path.join(os.tmpdir(), 'upload.txt');\n  fs.writeFileSync(uploadPath, 'test file content');\n  const form = new FormData();{}",
+                appends
+            )
+        } else if let Some((body, BodyEncoding::Form)) = encoding {
+            let fields = form_fields(body)
+                .into_iter()
+                .map(|(name, value)| format!("    {}: {}", js_str(&name), js_str(&value.unwrap_or_default())))
+                .collect::<Vec<_>>();
+            format!("  const form = new URLSearchParams({{\n{}\n  }});", fields.join(",\n"))
+        } else if let Some((body, BodyEncoding::Raw)) = encoding {
+            format!("  const rawBody = {};", js_str(&raw_body_example(body)))
+        } else if let Some(example) = request_body_example(operation, variant) {
+            format!("  const jsonData = {};", to_json_literal(&example, "  "))
+        } else {
+            "  const jsonData = null;".to_string()
+        };
+        
+        // Create path with parameter interpolation
+        let mut endpoint_path = path.to_string();
+        for param in &operation.path_params {
+            endpoint_path = endpoint_path.replace(&format!("{{{}}}", param.name), &format!("${{{}}}", param.name));
+        }
+        
+        // Header parameters and credentials, with cookie parameters sent as a `Cookie` header
+        let mut header_values = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            header_values.push(("Cookie".to_string(), cookies));
+        }
+        let header_values: Vec<(String, String)> = header_values
+            .into_iter()
+            .map(|(name, value)| (name, js_str(&value)))
+            .chain(js_credentials(credentials, "header"))
+            .collect();
+        let query_params = if !header_values.is_empty() {
+            query_params + "\n  const headers = {\n" + &header_values.iter()
+                .map(|(name, value)| format!(r#"    "{}": {}"#, name, value))
+                .collect::<Vec<_>>()
+                .join(",\n") + "\n  };"
+        } else {
+            query_params
+        };
+        
+        // Basic authentication credentials, which axios encodes itself
+        let basic_auth = js_basic_auth(credentials);
+        let query_params = match &basic_auth {
+            Some(auth) => format!("{}\n  const auth = {};", query_params, auth),
+            None => query_params,
+        };
+        
+        let multipart = matches!(encoding, Some((_, BodyEncoding::Multipart)));
+        let mut request_config = vec!["params"];
+        match (multipart, header_values.is_empty()) {
+            (true, true) => request_config.push("headers: form.getHeaders()"),
+            (true, false) => request_config.push("headers: { ...headers, ...form.getHeaders() }"),
+            (false, true) => {},
+            (false, false) => request_config.push("headers"),
+        }
+        if basic_auth.is_some() {
+            request_config.push("auth");
+        }
+        let request_config = format!("{{ {} }}", request_config.join(", "));
+        
+        // Request method options
+        let request_params = match method.as_str() {
+            "get" | "delete" | "head" | "options" => request_config.to_string(),
+            _ => match encoding {
+                Some((_, BodyEncoding::Multipart | BodyEncoding::Form)) => format!("form, {}", request_config),
+                Some((_, BodyEncoding::Raw)) => format!("rawBody, {}", request_config),
+                _ => format!("jsonData, {}", request_config),
+            },
+        };
+        
+        // Expected status code
+        let mut expected_status = "200";
+        if method == "post" {
+            expected_status = "201";
+        } else if method == "delete" {
+            expected_status = "204";
+        }
+        
+        // Find the expected status from the responses
+        for resp in &operation.responses {
+            if resp.status_code.starts_with('2') {
+                expected_status = &resp.status_code;
+                break;
+            }
+        }
+        
+        // Headers documented on the expected response must be returned
+        let expected_headers = operation.responses.iter()
+            .find(|resp| resp.status_code == expected_status)
+            .map(|resp| resp.headers.as_slice())
+            .unwrap_or_default();
+        // axios lowercases the names of response headers
+        let header_assertions = expected_headers.iter()
+            .filter(|_| self.options.assert_level.checks_response())
+            .map(|h| format!("\n  expect(response.headers[{}]).toBeDefined();", to_json_literal(&Value::String(h.name.to_lowercase()), "")))
+            .collect::<String>();
+        
+        // axios doesn't time responses, so the time until the response arrives is measured around the request
+        let (timer, latency_assertion) = match self.options.max_response_time_ms {
+            Some(max) => (
+                "const started = Date.now();\n  ",
+                format!("\n  expect(Date.now() - started).toBeLessThanOrEqual({});", max),
+            ),
+            None => ("", String::new()),
+        };
+        
+        // What the request created is deleted once the test is done
+        let created_cleanup = resources.cleanup.as_ref()
+            .map(|cleanup| self.generate_cleanup(cleanup, base_url, "response"))
+            .unwrap_or_default();
+        
+        // The response body is validated against the documented schema, written to schemas/, and at the exact level
+        // compared to the documented example. axios lowercases the names of response headers
+        let example_check = match response_example(operation) {
+            Some(example) if self.options.assert_level == AssertLevel::Exact => format!(
+                "// Verify the response body is the documented example\n  expect(response.data).toEqual({});",
+                to_json_literal(&example, "  ")
+            ),
+            _ => String::new(),
+        };
+        let body_check = if !self.options.assert_level.checks_response() {
+            "// Only the status is checked".to_string()
+        } else if method == "head" {
+            "// HEAD responses have no body\n  expect(response.data).toBe('');".to_string()
+        } else if method == "options" {
+            "// Verify the allowed methods are listed\n  expect(response.headers['allow'] ?? response.headers['access-control-allow-methods']).toBeDefined();".to_string()
+        } else if response_validation_schema(operation).is_some() {
+            format!("// Verify the response body matches its schema\n  expect(schemaErrors(response.data, {})).toEqual([]);", js_str(&operation.operation_id))
+        } else if !example_check.is_empty() {
+            String::new()
+        } else {
+            "// Verify the response body\n  // expect(response.data).toHaveProperty('id');".to_string()
+        };
+        let body_check = [body_check, example_check].into_iter().filter(|check| !check.is_empty()).collect::<Vec<_>>().join("\n  \n  ");
+        
+        format!(
+            r#"{test}('{operation_id}', async () => {{
+  // {summary}
+{path_params_setup}
+{query_params}
+{body_param}
+
+  const url = `{base_url}{endpoint_path}`;
+  
+  {timer}const response = await axios.{method}(url, {request_params});{latency_assertion}
+  
+  // Verify status code
+  expect(response.status).toBe({expected_status});{header_assertions}{created_cleanup}
+  
+  {body_check}
+}});"#
+        )
+    }
+}
+
+impl JestGenerator {
+    /// The negative and boundary tests of one request of an operation, each sending a variation of its request. axios
+    /// throws on error statuses unless told every status is valid
+    fn generate_case_tests(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+        let object = |pairs: Vec<(String, String)>| {
+            let items: Vec<String> = pairs.iter().map(|(name, value)| format!("      {}: {},", js_str(name), value)).collect();
+            format!("{{\n{}\n    }}", items.join("\n"))
+        };
+        let assertion = |expected: Option<&str>| match expected {
+            Some(expected) => format!("expect(response.status).toBe({});", expected),
+            None => "expect(response.status).toBeGreaterThanOrEqual(400);\n  expect(response.status).toBeLessThan(500);".to_string(),
+        };
+        // axios lowercases the names of response headers
+        let media_type_check = |media_type: Option<&str>| {
+            media_type
+                .map(|media_type| format!("\n  expect(response.headers['content-type']?.split(';')[0].trim()).toBe({});", js_str(media_type)))
+                .unwrap_or_default()
+        };
+        
+        request_cases(operation, variant, &self.options)
+            .into_iter()
+            .map(|case| {
+                let mut config = vec![
+                    format!("    method: {},", js_str(&operation.method.to_lowercase())),
+                    format!("    url: {},", self.url_literal(base_url, &case.path(path, operation))),
+                ];
+                let literals = |pairs: Vec<(String, String)>| pairs.into_iter().map(|(name, value)| (name, js_str(&value)));
+                let credentials = case.credentials(credentials);
+                let query: Vec<_> = literals(case.query(operation)).chain(js_credentials(credentials, "query")).collect();
+                if !query.is_empty() {
+                    config.push(format!("    params: {},", object(query)));
+                }
+                let headers: Vec<_> = literals(case.headers(operation, &self.options.headers)).chain(js_credentials(credentials, "header")).collect();
+                if !headers.is_empty() {
+                    config.push(format!("    headers: {},", object(headers)));
+                }
+                if let Some(body) = case.body(operation, variant) {
+                    config.push(format!("    data: {},", to_json_literal(&body, "    ")));
+                }
+                config.extend(js_basic_auth(credentials).map(|auth| format!("    auth: {},", auth)));
+                config.push("    validateStatus: () => true,".to_string());
+                
+                let operation_id = match variant {
+                    Some(variant) => format!("{} ({})", operation.operation_id, variant.name),
+                    None => operation.operation_id.clone(),
+                };
+                format!(
+                    r#"
+
+test({name}, async () => {{
+  const response = await axios.request({{
+{config}
+  }});
+  
+  // Verify the request is {outcome}
+  {assertion}{media_type_check}
+}});"#,
+                    name = js_str(&format!("{} {}", operation_id, case.description)),
+                    config = config.join("\n"),
+                    outcome = case.outcome(),
+                    assertion = assertion(case.expected_status(operation)),
+                    media_type_check = media_type_check(case.media_type()),
+                )
+            })
+            .collect()
+    }
+    
+    /// A test of an operation run for each request the data file has for it, sending its parameters and body and
+    /// expecting its status. Returns nothing when there are none
+    fn generate_data_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let cases = data_cases(operation, path, variant, &self.options);
+        if cases.is_empty() {
+            return String::new();
+        }
+        let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+        let pairs = |pairs: &[(String, String)]| Value::Object(pairs.iter().map(|(name, value)| (name.clone(), Value::String(value.clone()))).collect());
+        let cases: Vec<Value> = cases.iter()
+            .map(|case| json!({
+                "path": case.path,
+                "params": pairs(&case.query),
+                "headers": pairs(&case.headers),
+                "data": case.body,
+                "status": case.status.parse::<u16>().unwrap_or(200),
+            }))
+            .collect();
+        
+        // Credentials are added to the parameters and headers of each request
+        let merged = |field: &str, credentials: Vec<(String, String)>| {
+            let items: Vec<String> = credentials.iter().map(|(name, value)| format!(", {}: {}", js_str(name), value)).collect();
+            if items.is_empty() { field.to_string() } else { format!("{0}: {{ ...{0}{1} }}", field, items.concat()) }
+        };
+        let mut config = vec![
+            format!("    method: {},", js_str(&operation.method.to_lowercase())),
+            format!("    url: {} + path,", if self.options.environments.is_empty() { js_str(base_url) } else { "BASE_URL".to_string() }),
+            format!("    {},", merged("params", js_credentials(credentials, "query"))),
+            format!("    {},", merged("headers", js_credentials(credentials, "header"))),
+            "    data,".to_string(),
+        ];
+        config.extend(js_basic_auth(credentials).map(|auth| format!("    auth: {},", auth)));
+        config.push("    validateStatus: () => true,".to_string());
+        
+        format!(
+            r#"
+
+{test}.each({cases})({name}, async ({{ path, params, headers, data, status }}) => {{
+  const response = await axios.request({{
+{config}
+  }});
+  
+  expect(response.status).toBe(status);
+}});"#,
+            test = if operation.deprecated { "test.skip" } else { "test" },
+            cases = to_json_literal(&Value::Array(cases), ""),
+            name = js_str(&format!("{} data file request %#", operation.operation_id)),
+            config = config.join("\n"),
+        )
+    }
+    
+    /// A test asking an operation that pages its results for its first two pages, which have to differ and hold no more
+    /// items than the limit. Returns nothing when the operation gets no pagination test
+    fn generate_pagination_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let Some(pages) = page_requests(operation, path, variant, &self.options) else {
+            return String::new();
+        };
+        let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+        let object = |pairs: Vec<(String, String)>| {
+            let items: Vec<String> = pairs.iter().map(|(name, value)| format!("      {}: {},", js_str(name), value)).collect();
+            format!("{{\n{}\n    }}", items.join("\n"))
+        };
+        let literals = |pairs: &[(String, String)]| pairs.iter().map(|(name, value)| (name.clone(), js_str(value))).collect::<Vec<_>>();
+        let url = self.url_literal(base_url, &pages.path);
+        let send = |query: Vec<(String, String)>| {
+            let mut config = vec!["    method: \"get\",".to_string(), format!("    url: {},", url)];
+            let query: Vec<_> = query.into_iter().chain(js_credentials(credentials, "query")).collect();
+            if !query.is_empty() {
+                config.push(format!("    params: {},", object(query)));
+            }
+            let headers: Vec<_> = literals(&pages.headers).into_iter().chain(js_credentials(credentials, "header")).collect();
+            if !headers.is_empty() {
+                config.push(format!("    headers: {},", object(headers)));
+            }
+            config.extend(js_basic_auth(credentials).map(|auth| format!("    auth: {},", auth)));
+            config.push("    validateStatus: () => true,".to_string());
+            format!("await axios.request({{\n{}\n  }})", config.join("\n"))
+        };
+        let body_value = |pointer: &str| {
+            pointer_segments(pointer).iter().fold("response.data".to_string(), |value, name| format!("{}[{}]", value, js_str(name)))
+        };
+        let page = |number: &str, query: Vec<(String, String)>| {
+            let limit = pages.limit.map(|limit| format!("\n  expect({}Items.length).toBeLessThanOrEqual({});", number, limit)).unwrap_or_default();
+            format!(
+                "response = {};\n  expect(response.status).toBe({});\n  const {}Items = {};{}",
+                send(query),
+                pages.status,
+                number,
+                body_value(&pages.items_pointer),
+                limit,
+            )
+        };
+        
+        let mut second_query = literals(&pages.second_query);
+        let cursor = match &pages.cursor {
+            Some((param, pointer)) => {
+                second_query.push((param.clone(), "cursor".to_string()));
+                format!("\n  const cursor = {};\n  expect(cursor).toBeTruthy();", body_value(pointer))
+            },
+            None => String::new(),
+        };
+        
+        format!(
+            r#"
+
+test({name}, async () => {{
+  // Asks for two pages, which have to differ{limit}
+  let {first}{cursor}
+  
+  {second}
+  
+  expect(secondItems).not.toEqual(firstItems);
+}});"#,
+            name = js_str(&format!("{} pagination", operation.operation_id)),
+            limit = pages.limit.map(|limit| format!(" and hold at most {} items each", limit)).unwrap_or_default(),
+            first = page("first", literals(&pages.first_query)),
+            second = page("second", second_query),
+        )
+    }
+    
+    /// A fast-check test of an operation, sending requests with bodies and query parameters drawn from their schemas,
+    /// which the API must answer without a server error. Its runs take longer than Jest's default timeout
+    fn generate_fuzz_test(
+        &self,
+        operation: &ApiOperation,
+        path: &str,
+        base_url: &str,
+        variant: Option<&SchemaVariant>,
+        credentials: &[Credential],
+    ) -> String {
+        let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+        let object = |pairs: Vec<(String, String)>| {
+            let items: Vec<String> = pairs.iter().map(|(name, value)| format!("            {}: {},", js_str(name), value)).collect();
+            format!("{{\n{}\n          }}", items.join("\n"))
+        };
+        let test = if operation.deprecated { "test.skip" } else { "test" };
+        let body = body_strategy(operation, variant);
+        let query = query_strategies(operation);
+        
+        let mut arbitraries: Vec<String> = body.iter().map(|strategy| format!("      {},", strategy.fast_check("      "))).collect();
+        arbitraries.extend(query.iter().map(|(_, _, strategy)| format!("      {},", strategy.fast_check("      "))));
+        let mut variables: Vec<&str> = body.iter().map(|_| "body").collect();
+        variables.extend(query.iter().map(|(_, variable, _)| variable.as_str()));
+        
+        let mut config = vec![
+            format!("          method: {},", js_str(&operation.method.to_lowercase())),
+            format!("          url: {},", self.url_literal(base_url, &example_path(path, operation))),
+        ];
+        let drawn = query.iter().map(|(param, variable, _)| (param.name.clone(), variable.clone()));
+        let params: Vec<_> = drawn.chain(js_credentials(credentials, "query")).collect();
+        if !params.is_empty() {
+            config.push(format!("          params: {},", object(params)));
+        }
+        let headers: Vec<_> = fixed_headers(operation, &self.options.headers)
+            .into_iter()
+            .map(|(name, value)| (name, js_str(&value)))
+            .chain(js_credentials(credentials, "header"))
+            .collect();
+        if !headers.is_empty() {
+            config.push(format!("          headers: {},", object(headers)));
+        }
+        if body.is_some() {
+            config.push("          data: body,".to_string());
+        }
+        config.extend(js_basic_auth(credentials).map(|auth| format!("          auth: {},", auth)));
+        config.push("          validateStatus: () => true,".to_string());
+        
+        let operation_id = match variant {
+            Some(variant) => format!("{} ({})", operation.operation_id, variant.name),
+            None => operation.operation_id.clone(),
+        };
+        format!(
+            r#"
+
+{test}({name}, async () => {{
+  // {summary}, with values drawn from the schemas: the API must answer without a server error
+  await fc.assert(
+    fc.asyncProperty(
+{arbitraries}
+      async ({variables}) => {{
+        const response = await axios.request({{
+{config}
+        }});
+        expect(response.status).toBeLessThan(500);
+      }},
+    ),
+    {{ numRuns: {cases} }},
+  );
+}}, 60000);"#,
+            name = js_str(&format!("{} with values drawn from the schemas", operation_id)),
+            summary = operation.summary.as_deref().unwrap_or(&operation.operation_id),
+            arbitraries = arbitraries.join("\n"),
+            variables = variables.join(", "),
+            config = config.join("\n"),
+            cases = FUZZ_CASES,
+        )
+    }
+    
+    /// A test creating a resource, then reading, updating and deleting it through the operations on it, and checking it
+    /// can't be read once deleted. axios throws on error statuses unless told every status is valid
+    fn generate_lifecycle_test(&self, spec: &SwaggerSpec, lifecycle: &Lifecycle, base_url: &str) -> String {
+        let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+        let object = |pairs: Vec<(String, String)>| {
+            let items: Vec<String> = pairs.iter().map(|(name, value)| format!("      {}: {},", js_str(name), value)).collect();
+            format!("{{\n{}\n    }}", items.join("\n"))
+        };
+        
+        let mut code = vec![self.generate_link_setup(&lifecycle.create, lifecycle.read_operation(), base_url, None)];
+        code.extend(lifecycle.unbound_params().into_iter().map(|p| format!("  const {} = {};", p.name, to_json_literal(&path_value(p), ""))));
+        for (index, step) in lifecycle.steps.iter().enumerate() {
+            let credentials = operation_credentials(spec, step.operation, &self.options.auth);
+            let path = step.operation.path_params.iter().fold(step.path.to_string(), |path, param| {
+                path.replace(&format!("{{{}}}", param.name), &format!("${{{}}}", param.name))
+            });
+            let mut config = vec![
+                format!("    method: {},", js_str(&step.operation.method.to_lowercase())),
+                format!("    url: `{}{}{}`,", base_url, path, step.query()),
+            ];
+            let query = js_credentials(&credentials, "query");
+            if !query.is_empty() {
+                config.push(format!("    params: {},", object(query)));
+            }
+            let headers: Vec<_> = fixed_headers(step.operation, &self.options.headers)
+                .into_iter()
+                .map(|(name, value)| (name, js_str(&value)))
+                .chain(js_credentials(&credentials, "header"))
+                .collect();
+            if !headers.is_empty() {
+                config.push(format!("    headers: {},", object(headers)));
+            }
+            if let Some(body) = step.body().filter(|_| step.action == Action::Update) {
+                config.push(format!("    data: {},", to_json_literal(&body, "    ")));
+            }
+            config.extend(js_basic_auth(&credentials).map(|auth| format!("    auth: {},", auth)));
+            config.push("    validateStatus: () => true,".to_string());
+            code.push(format!(
+                "\n  // {}: {}\n  {} = await axios.request({{\n{}\n  }});\n  expect(response.status).toBe({});",
+                step.action.description(),
+                step.operation.operation_id,
+                if index == 0 { "let response" } else { "response" },
+                config.join("\n"),
+                step.expected_status(),
+            ));
+        }
+        
+        format!(
+            r#"
+
+test({name}, async () => {{
+  // Create a resource with {create}, then read, update and delete it, and check it is gone
+{code}
+}});"#,
+            name = js_str(&format!("{} lifecycle", lifecycle.tag)),
+            create = lifecycle.create.operation.operation_id,
+            code = code.join("\n"),
+        )
+    }
+    
+    /// Code sending the setup request of a link, and binding the values it supplies to the parameters of `target`, then
+    /// registering the deletion of what it created
+    fn generate_link_setup(&self, setup: &LinkedSetup, target: &ApiOperation, base_url: &str, cleanup: Option<&Cleanup>) -> String {
+        let mut body = setup.request_body()
+            .map(|body| format!(",\n    data: {}", to_json_literal(&body, "    ")))
+            .unwrap_or_default();
+        if !self.options.headers.is_empty() {
+            body.push_str(&format!(",\n    headers: {{ {} }}", self.static_headers_object()));
+        }
+        
+        let mut code = format!(
+            r#"  // Call {operation_id} first, and take parameters from its response ({origin})
+  const setupResponse = await axios.request({{
+    method: '{method}',
+    url: `{base_url}{path}`{body}
+  }});
+  expect(setupResponse.status).toBeGreaterThanOrEqual(200);
+  expect(setupResponse.status).toBeLessThan(300);"#,
+            operation_id = setup.operation.operation_id,
+            origin = setup.origin(),
+            method = setup.operation.method.to_lowercase(),
+            path = setup.request_path(),
+        );
+        
+        for (param, value) in setup.linked_params(target) {
+            code.push_str(&format!("\n  const {} = {};", param.name, js_link_value(setup, value, "setupResponse")));
+        }
+        if let Some(cleanup) = cleanup {
+            code.push_str(&self.generate_cleanup(cleanup, base_url, "setupResponse"));
+        }
+        
+        code
+    }
+    
+    /// Code adding the URL of an item a request created to `createdUrls`, which `afterEach` deletes once the test is done,
+    /// with the values read from the axios response bound to `response`
+    fn generate_cleanup(&self, cleanup: &Cleanup, base_url: &str, response: &str) -> String {
+        let path = cleanup.params().into_iter().fold(cleanup.path.to_string(), |path, (param, value)| {
+            path.replace(&format!("{{{}}}", param.name), &format!("${{{}}}", js_link_value(&cleanup.setup, value, response)))
+        });
+        format!(
+            "\n  // Delete what {} created once the test is done: {}\n  createdUrls.push(`{}{}`);",
+            cleanup.setup.operation.operation_id,
+            cleanup.operation.operation_id,
+            base_url,
+            path,
+        )
+    }
+    
+    /// Write `webhooks.test.js`, delivering each webhook's documented payload to the receiver under test
+    fn generate_webhook_tests(&self, webhooks: &[(&str, &ApiOperation)], output_dir: &Path) -> Result<()> {
+        let mut file = File::create(output_dir.join("webhooks.test.js"))?;
+        
+        writeln!(file, r#"const axios = require('axios');
+
+// Endpoint of the webhook receiver under test
+const WEBHOOK_URL = process.env.WEBHOOK_URL || '{DEFAULT_WEBHOOK_URL}';
+"#)?;
+        
+        for (name, operation) in webhooks {
+            let operation_id = &operation.operation_id;
+            let summary = operation.summary.as_deref().unwrap_or(name);
+            let test = if operation.deprecated { "test.skip" } else { "test" };
+            let method = operation.method.to_lowercase();
+            let (payload, data) = match operation.body_param.as_ref() {
+                Some(body) => (
+                    format!("\n  const payload = {};\n", to_json_literal(&body_example(body), "  ")),
+                    ", data: payload",
+                ),
+                None => (String::new(), ""),
+            };
+            let expected_status = webhook_status(operation);
+            
+            writeln!(file, r#"{test}('webhook {operation_id}', async () => {{
+  // {summary}{payload}
+  const response = await axios.request({{ method: '{method}', url: WEBHOOK_URL{data} }});
+  
+  expect(response.status).toBe({expected_status});
+}});
+"#)?;
+        }
+        
+        Ok(())
+    }
+}
+
+impl TestGenerator for JestGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+        
+        // With environments, request URLs start with the `BASE_URL` of the one tested instead
+        let tests_base_url = if self.options.environments.is_empty() { base_url.to_string() } else { "${BASE_URL}".to_string() };
+        write_env_files(spec, &self.options, output_dir)?;
+        
+        // Create a test file for each group of operations, one per path unless another layout is chosen
+        let groups = operation_groups(spec, &self.options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let tests = generate_in_parallel(&operations, &self.options, |path, operation, variant| {
+            let resources = resources(spec, operation);
+            let credentials = operation_credentials(spec, operation, &self.options.auth);
+            let test = if self.options.fuzz && is_fuzzed(operation) {
+                self.generate_fuzz_test(operation, path, &tests_base_url, variant, &credentials)
+            } else {
+                self.generate_operation_test(operation, path, &tests_base_url, variant, &resources, &credentials)
+            };
+            test + &self.generate_case_tests(operation, path, &tests_base_url, variant, &credentials)
+                + &self.generate_data_test(operation, path, &tests_base_url, variant, &credentials)
+                + &self.generate_pagination_test(operation, path, &tests_base_url, variant, &credentials)
+        })?;
+        
+        let resource_lifecycles = lifecycles(spec, &self.options);
+        let mut validates_schemas = false;
+        let mut fuzzes = false;
+        let mut suite_credentials: Vec<Credential> = Vec::new();
+        for (group, tests) in groups.into_iter().zip(tests) {
+            let test_file_path = output_dir.join(format!("{}.test.js", group.file_stem));
+            let mut file = File::create(test_file_path)?;
+            // Each lifecycle is tested in the file of its create operation
+            let group_lifecycles: Vec<&Lifecycle> = resource_lifecycles.iter()
+                .filter(|lifecycle| group.contains(lifecycle.create.operation))
+                .collect();
+            
+            // Write the file header, with the modules needed to retry requests, build uploads and draw values
+            writeln!(file, "const axios = require('axios');")?;
+            if self.options.retries > 0 {
+                writeln!(file, "const axiosRetry = require('axios-retry').default;")?;
+            }
+            if self.options.fuzz && group.operations.iter().any(|(_, op)| is_fuzzed(op)) {
+                writeln!(file, "const fc = require('fast-check');")?;
+                fuzzes = true;
+            }
+            if group.operations.iter().any(|(_, op)| has_multipart_body(op)) {
+                writeln!(file, "const FormData = require('form-data');\nconst fs = require('fs');\nconst os = require('os');\nconst path = require('path');")?;
+            }
+            if let Some(default) = default_environment(&self.options) {
+                writeln!(file, r#"
+{load_environment}
+
+// Base URL of the API under test, which the .env file of the environment tested sets
+const BASE_URL = process.env.{base_url_var} || {base_url};"#,
+                    load_environment = jest_load_environment(default),
+                    base_url_var = BASE_URL_VAR,
+                    base_url = to_json_literal(&Value::String(base_url.trim_end_matches('/').to_string()), ""))?;
+            }
+            if let Some(secs) = self.options.timeout_secs {
+                writeln!(file, "\n// Fail requests the API doesn't answer within {} s\naxios.defaults.timeout = {};", secs, secs * 1000)?;
+            }
+            if self.options.retries > 0 {
+                writeln!(file, r#"
+// Send requests again up to {retries} times after a connection error or a 408, 429 or 5xx status, waiting {backoff} ms
+// before the first retry and twice as long before each next one. Requests accepting every status are sent once
+axiosRetry(axios, {{
+  retries: {retries},
+  retryDelay: (retryCount) => {backoff} * 2 ** (retryCount - 1),
+  retryCondition: (error) => axiosRetry.isNetworkError(error) || [408, 429].includes(error.response?.status) || error.response?.status >= 500,
+}});"#,
+                    retries = self.options.retries,
+                    backoff = self.options.retry_backoff_ms.unwrap_or(DEFAULT_RETRY_BACKOFF_MS))?;
+            }
+            
+            // Response bodies are validated against the draft 4 schemas in schemas/
+            let validate_schema = self.options.assert_level.checks_response() && write_response_schemas(&group.operations, output_dir)?;
+            validates_schemas |= validate_schema;
+            if validate_schema {
+                writeln!(file, r#"const Ajv = require('ajv-draft-04');
+
+const ajv = new Ajv({{ allErrors: true, strict: false }});
+
+// Validate a response body against the schema of the operation's response, returning the errors
+function schemaErrors(body, operationId) {{
+  const validate = ajv.compile(require(`./schemas/${{operationId}}.json`));
+  return validate(body) ? [] : validate.errors;
+}}"#)?;
+            }
+            let credentials: Vec<Credential> = group.operations.iter()
+                .map(|(_, operation)| *operation)
+                .chain(group_lifecycles.iter().flat_map(|lifecycle| lifecycle.steps.iter().map(|step| step.operation)))
+                .flat_map(|operation| operation_credentials(spec, operation, &self.options.auth))
+                .collect();
+            if let Some((client_id_env, client_secret_env)) = client_credentials_env(&credentials) {
+                writeln!(file, r#"
+// Access tokens fetched with the OAuth2 client credentials flow, by token endpoint and scope, with when to fetch them
+// again: global-setup.js fetches them once for all the test files
+const accessTokens = new Map(Object.entries(JSON.parse(process.env.ACCESS_TOKENS || '{{}}')));
+
+// An access token from the OAuth2 client credentials flow, the one global-setup.js fetched until a minute before it
+// expires (after an hour when the token endpoint doesn't say), and one fetched again then
+async function accessToken(tokenUrl, scope) {{
+  const cached = accessTokens.get(`${{tokenUrl}} ${{scope}}`);
+  if (cached && cached.refreshAt > Date.now()) {{
+    return cached.token;
+  }}
+  const form = new URLSearchParams({{
+    grant_type: 'client_credentials',
+    client_id: process.env.{client_id_env},
+    client_secret: process.env.{client_secret_env},
+  }});
+  if (scope) {{
+    form.append('scope', scope);
+  }}
+  const response = await axios.post(tokenUrl, form);
+  const expiresIn = response.data.expires_in ?? 3600;
+  accessTokens.set(`${{tokenUrl}} ${{scope}}`, {{
+    token: response.data.access_token,
+    refreshAt: Date.now() + Math.max(expiresIn - 60, 0) * 1000,
+  }});
+  return response.data.access_token;
+}}"#)?;
+            }
+            suite_credentials.extend(credentials);
+            if has_setups(spec) {
+                writeln!(file, r#"
+// Read the value a link points at in a response body, given as a JSON pointer
+function linkedValue(body, pointer) {{
+  return pointer
+    .split('/')
+    .slice(1)
+    .map((token) => token.replace(/~1/g, '/').replace(/~0/g, '~'))
+    .reduce((value, token) => value[token], body);
+}}"#)?;
+            }
+            if group.operations.iter().any(|(_, operation)| resources(spec, operation).cleans_up()) {
+                writeln!(file, r#"
+// URLs of the items a test created, deleted once it is done, even when it fails
+const createdUrls = [];
+
+afterEach(async () => {{
+  while (createdUrls.length > 0) {{
+    await axios.delete(createdUrls.pop(), {{ {headers}validateStatus: () => true }});
+  }}
+}});"#,
+                    headers = if self.options.headers.is_empty() {
+                        String::new()
+                    } else {
+                        format!("headers: {{ {} }}, ", self.static_headers_object())
+                    })?;
+            }
+            writeln!(file)?;
+            
+            // Write the tests for each operation in this group, then the lifecycle tests
+            for test_code in tests {
+                writeln!(file, "{}\n", test_code)?;
+            }
+            for lifecycle in group_lifecycles {
+                writeln!(file, "{}\n", self.generate_lifecycle_test(spec, lifecycle, &tests_base_url))?;
+            }
+        }
+        
+        // Webhooks are tested against the consumer's receiver, in a file of their own
+        let webhooks = webhook_operations(spec, &self.options);
+        if !webhooks.is_empty() {
+            self.generate_webhook_tests(&webhooks, output_dir)?;
+        }
+        
+        // The access tokens tests send are fetched once, before any test file runs
+        let client_credentials = client_credentials_env(&suite_credentials);
+        if let Some((client_id_env, client_secret_env)) = client_credentials {
+            let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+            let mut scopes: Vec<String> = Vec::new();
+            for credential in &suite_credentials {
+                if let Credential::ClientCredentials { token_url, scope, .. } = credential {
+                    let entry = format!("[{}, {}]", js_str(token_url), js_str(scope));
+                    if !scopes.contains(&entry) {
+                        scopes.push(entry);
+                    }
+                }
+            }
+            let load_environment = default_environment(&self.options)
+                .map(|default| format!("\n{}\n", jest_load_environment(default)))
+                .unwrap_or_default();
+            fs::write(output_dir.join("global-setup.js"), format!(r#"const axios = require('axios');
+{load_environment}
+// Fetch the access tokens the tests send with the OAuth2 client credentials flow once, before any test file runs, and
+// hand them to the test files in ACCESS_TOKENS
+module.exports = async () => {{
+  const tokens = {{}};
+  for (const [tokenUrl, scope] of [
+    {scopes},
+  ]) {{
+    const form = new URLSearchParams({{
+      grant_type: 'client_credentials',
+      client_id: process.env.{client_id_env},
+      client_secret: process.env.{client_secret_env},
+    }});
+    if (scope) {{
+      form.append('scope', scope);
+    }}
+    const response = await axios.post(tokenUrl, form);
+    const expiresIn = response.data.expires_in ?? 3600;
+    tokens[`${{tokenUrl}} ${{scope}}`] = {{
+      token: response.data.access_token,
+      refreshAt: Date.now() + Math.max(expiresIn - 60, 0) * 1000,
+    }};
+  }}
+  process.env.ACCESS_TOKENS = JSON.stringify(tokens);
+}};
+"#,
+                scopes = scopes.join(",\n    ")))?;
+        }
+        
+        // Create a package.json file
+        let package_file_path = output_dir.join("package.json");
+        let mut package_file = File::create(package_file_path)?;
+        
+        let form_data_dependency = if spec.paths.iter().flat_map(|p| &p.operations).any(has_multipart_body) {
+            ",\n    \"form-data\": \"^4.0.0\""
+        } else {
+            ""
+        };
+        let ajv_dependencies = if validates_schemas {
+            ",\n    \"ajv\": \"^8.12.0\",\n    \"ajv-draft-04\": \"^1.0.0\""
+        } else {
+            ""
+        };
+        let retry_dependency = if self.options.retries > 0 { ",\n    \"axios-retry\": \"^4.0.0\"" } else { "" };
+        let dotenv_dependency = if self.options.environments.is_empty() { "" } else { ",\n    \"dotenv\": \"^16.4.5\"" };
+        
+        writeln!(package_file, r#"{{
+  "name": "{name}",
+  "version": "{version}",
+  "description": {description},
+  "scripts": {{
+    "test": "jest"
+  }},
+  "dependencies": {{
+    "axios": "^1.3.4"{retry_dependency}{form_data_dependency}{ajv_dependencies}{dotenv_dependency}
+  }},
+  "devDependencies": {{
+    {fast_check}"jest": "^29.5.0"
+  }}{jest_config}
+}}
+"#,
+            jest_config = if client_credentials.is_some() { ",\n  \"jest\": {\n    \"globalSetup\": \"./global-setup.js\"\n  }" } else { "" },
+            fast_check = if fuzzes { "\"fast-check\": \"^3.19.0\",\n    " } else { "" },
+            name = spec.info.package_name("-", "tests"),
+            version = spec.info.package_version("1.0.0"),
+            description = Value::String(format!("Generated API tests for {}", spec.info.title)))?;
+        
+        // Create a README.md file with instructions
+        let readme_file_path = output_dir.join("README.md");
+        let mut readme_file = File::create(readme_file_path)?;
+        
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}).
+
+## Setup
+
+Install the dependencies:
+
+```
+npm install
+```
+
+## Running the tests
+
+To run the tests:
+
+```
+npm test
+```
+{environments}"#,
+            title = spec.info.title,
+            version = spec.info.version,
+            environments = readme_section(&self.options, "npm test"))?;
+        
+        Ok(())
+    }
+}
+
+// Postman collection generator
+struct PostmanGenerator {
+    options: GeneratorOptions,
+}
+
+impl PostmanGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        PostmanGenerator { options }
+    }
+    
+    /// Generate the collection item for one request of an operation, with its body taken from `variant` when given
+    fn generate_request(&self, spec: &SwaggerSpec, path: &str, operation: &ApiOperation, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_uppercase();
+        let summary = operation.summary.as_deref().unwrap_or(&operation.operation_id);
+        let summary = match variant {
+            Some(variant) => format!("{} ({})", summary, variant.name),
+            None => summary.to_string(),
+        };
+        
+        // Parameters supplied by a link come from collection variables set by the linked request
+        let setup = incoming_link(spec, operation);
+        let linked_values = setup.as_ref().map(|setup| {
+            setup.linked_params(operation)
+                .into_iter()
+                .map(|(param, value)| {
+                    let value = setup.static_value(value).unwrap_or_else(|| format!("{{{{{}}}}}", link_variable(&setup.link, &param.name)));
+                    (param.name.clone(), value)
+                })
+                .collect::<Vec<_>>()
+        }).unwrap_or_default();
+        let linked_value = |name: &str| linked_values.iter().find(|(linked, _)| linked == name).map(|(_, value)| value.clone());
+        
+        // Linked path parameters are filled in, the others left as placeholders
+        let mut request_path = path.to_string();
+        for param in &operation.path_params {
+            let value = linked_value(&param.name).unwrap_or_else(|| format!(":{}", param.name));
+            request_path = request_path.replace(&format!("{{{}}}", param.name), &value);
+        }
+        
+        // Create URL with parameter placeholders, sent to the environment's base URL
+        let url = format!("{{{{baseUrl}}}}{}", request_path);
+        
+        // Query parameters
+        let query_params = if !operation.query_params.is_empty() {
+            let params = operation.query_params.iter()
+                .map(|p| {
+                    format!(
+                        r#"            {{
+              "key": "{}",
+              "value": {},
+              "description": "{}"
+            }}"#, 
+                        p.name,
+                        serde_json::to_string(&linked_value(&p.name).unwrap_or_else(|| query_value(p))).unwrap_or_default(),
+                        p.name
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",\n");
+                
+            format!(r#"          "query": [
+{}
+          ],"#, params)
+        } else {
+            "".to_string()
+        };
+        
+        // Header parameters, with cookie parameters sent as a `Cookie` header
+        let mut header_values = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            header_values.push(("Cookie".to_string(), cookies));
+        }
+        let headers = header_values.iter()
+            .map(|(name, value)| {
+                format!(
+                    r#"
+              {{
+                "key": {},
+                "value": {},
+                "type": "text"
+              }}"#,
+                    serde_json::to_string(name).unwrap_or_default(),
+                    serde_json::to_string(value).unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let headers = if headers.is_empty() { headers } else { format!("{}\n            ", headers) };
+        
+        // Request body
+        let example = request_body_example(operation, variant).unwrap_or_default();
+        let raw_body = serde_json::to_string(&serde_json::to_string_pretty(&example).unwrap_or_default()).unwrap_or_default();
+        let encoding = operation.body_param.as_ref().map(|body| (body, body_encoding(body)));
+        let body = if let Some((body, encoding @ (BodyEncoding::Multipart | BodyEncoding::Form))) = encoding {
+            let mode = if encoding == BodyEncoding::Multipart { "formdata" } else { "urlencoded" };
+            let fields = form_fields(body)
+                .into_iter()
+                .map(|(name, value)| {
+                    let name = serde_json::to_string(&name).unwrap_or_default();
+                    match value {
+                        Some(value) => format!(
+                            r#"
+              {{
+                "key": {},
+                "value": {},
+                "type": "text"
+              }}"#,
+                            name,
+                            serde_json::to_string(&value).unwrap_or_default()
+                        ),
+                        None => format!(
+                            r#"
+              {{
+                "key": {},
+                "type": "file",
+                "src": "upload.txt"
+              }}"#,
+                            name
+                        ),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            format!(r#"          "body": {{
+            "mode": "{mode}",
+            "{mode}": [{fields}
+            ]
+          }},"#)
+        } else if let Some((body, BodyEncoding::Raw)) = encoding {
+            format!(r#"          "body": {{
+            "mode": "raw",
+            "raw": {},
+            "options": {{
+              "raw": {{
+                "language": "text"
+              }}
+            }}
+          }},"#, serde_json::to_string(&raw_body_example(body)).unwrap_or_default())
+        } else if operation.body_param.is_some() {
+            format!(r#"          "body": {{
+            "mode": "raw",
+            "raw": {},
+            "options": {{
+              "raw": {{
+                "language": "json"
+              }}
+            }}
+          }},"#, raw_body)
+        } else {
+            "".to_string()
+        };
+        
+        // Tests for validating responses
+        let mut expected_status = "200";
+        if method == "POST" {
+            expected_status = "201";
+        } else if method == "DELETE" {
+            expected_status = "204";
+        }
+        
+        // Find the expected status from the responses
+        for resp in &operation.responses {
+            if resp.status_code.starts_with('2') {
+                expected_status = &resp.status_code;
+                break;
+            }
+        }
+        
+        // Requests inherit the collection's auth, unless the operation is secured differently or not at all
+        let auth = postman_auth(spec, &operation.security);
+        let auth = if auth == postman_auth(spec, &spec.security) {
+            String::new()
+        } else {
+            let auth = auth.unwrap_or_else(|| serde_json::json!({ "type": "noauth" }));
+            format!("            \"auth\": {},\n", to_json_literal(&auth, "            "))
+        };
+        
+        // Deprecated operations are still sent, but their tests are skipped
+        let pm_test = if operation.deprecated { "pm.test.skip" } else { "pm.test" };
+        
+        // Headers documented on the expected response must be returned, and the body match the documented schema (and
+        // at the exact level, be the documented example), unless the tests only check statuses
+        let response_scripts = match self.options.assert_level {
+            AssertLevel::Status => Vec::new(),
+            AssertLevel::Schema => [method_test_script(operation, pm_test), schema_test_script(operation, pm_test)].concat(),
+            AssertLevel::Exact => [
+                method_test_script(operation, pm_test),
+                schema_test_script(operation, pm_test),
+                example_test_script(operation, pm_test),
+            ].concat(),
+        };
+        let header_tests = operation.responses.iter()
+            .find(|resp| resp.status_code == expected_status)
+            .filter(|_| self.options.assert_level.checks_response())
+            .map(|resp| resp.headers.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|h| {
+                let name = serde_json::to_string(&h.name).unwrap_or_default();
+                [
+                    format!("{}({}, function () {{", pm_test, serde_json::to_string(&format!("{} header is present", h.name)).unwrap_or_default()),
+                    format!("    pm.response.to.have.header({});", name),
+                    "})".to_string(),
+                ]
+            })
+            .chain(self.options.max_response_time_ms.into_iter().flat_map(|max| [
+                format!("{}(\"Response time is at most {} ms\", function () {{", pm_test, max),
+                format!("    pm.expect(pm.response.responseTime).to.be.at.most({});", max),
+                "})".to_string(),
+            ]))
+            .chain(response_scripts)
+            .chain(link_variable_script(spec, operation))
+            .map(|line| format!(",\n                  {}", serde_json::to_string(&line).unwrap_or_default()))
+            .collect::<String>();
+        
+        let tests = format!(
+            r#"          "event": [
+            {{
+              "listen": "test",
+              "script": {{
+                "exec": [
+                  "{}(\"Status code is {}\", function () {{",
+                  "    pm.response.to.have.status({});",
+                  "}})"{}
+                ],
+                "type": "text/javascript"
+              }}
+            }}
+          ],"#, 
+            pm_test, expected_status, expected_status, header_tests
+        );
+        
+        format!(r#"        {{
+          "name": "{} {}",
+          "request": {{
+            "method": "{}",
+            "header": [{}],
+{}{}
+{}
+            "url": {{
+              "raw": "{}",
+              "host": [
+                "{{{{baseUrl}}}}"
+              ],
+              "path": [{}
+              ]
+            }},
+            "description": "{}"
+          }},
+{}
+          "response": []
+        }}"#,
+            method, summary,
+            method,
+            headers,
+            auth,
+            query_params,
+            body,
+            url,
+            request_path.trim_start_matches('/').split('/').map(|p| format!("                \"{}\"", p)).collect::<Vec<_>>().join(",\n"),
+            operation.description.as_deref().unwrap_or(""),
+            tests
+        )
+    }
+}
+
+/// A Postman request delivering a webhook's documented payload to `{{webhookUrl}}`
+fn webhook_request(name: &str, operation: &ApiOperation) -> Value {
+    let method = operation.method.to_uppercase();
+    let test = if operation.deprecated { "pm.test.skip" } else { "pm.test" };
+    let expected_status = webhook_status(operation);
+    
+    let mut request = serde_json::json!({
+        "method": method,
+        "header": [],
+        "url": { "raw": "{{webhookUrl}}", "host": ["{{webhookUrl}}"] },
+        "description": operation.description.as_deref().unwrap_or(""),
+    });
+    if let Some(body) = operation.body_param.as_ref() {
+        request["header"] = serde_json::json!([{ "key": "Content-Type", "value": "application/json", "type": "text" }]);
+        request["body"] = serde_json::json!({
+            "mode": "raw",
+            "raw": serde_json::to_string_pretty(&body_example(body)).unwrap_or_default(),
+            "options": { "raw": { "language": "json" } },
+        });
+    }
+    
+    serde_json::json!({
+        "name": format!("{} {}", method, operation.summary.as_deref().unwrap_or(name)),
+        "request": request,
+        "event": [{
+            "listen": "test",
+            "script": {
+                "exec": [
+                    format!("{}(\"Status code is {}\", function () {{", test, expected_status),
+                    format!("    pm.response.to.have.status({});", expected_status),
+                    "})",
+                ],
+                "type": "text/javascript",
+            },
+        }],
+        "response": [],
+    })
+}
+
+/// A Postman folder testing a resource's lifecycle: the create request stores the values it supplies in the same
+/// collection variables as for the linked requests, and the following requests read them
+fn lifecycle_folder(spec: &SwaggerSpec, lifecycle: &Lifecycle, static_headers: &[(String, String)]) -> Value {
+    let setup = &lifecycle.create;
+    let status_test = |status: &str| vec![
+        format!("pm.test(\"Status code is {}\", function () {{", status),
+        format!("    pm.response.to.have.status({});", status),
+        "})".to_string(),
+    ];
+    let mut create_tests = vec![
+        "pm.test(\"Status code is successful\", function () {".to_string(),
+        "    pm.response.to.be.success;".to_string(),
+        "})".to_string(),
+    ];
+    create_tests.extend(link_value_script(&setup.link));
+    let mut items = vec![lifecycle_request(
+        spec,
+        &format!("Create it: {}", setup.operation.operation_id),
+        setup.operation,
+        &setup.request_path(),
+        setup.request_body(),
+        create_tests,
+        static_headers,
+    )];
+    
+    // Path parameters the create request supplies come from its collection variables, the others get their example values
+    let linked = setup.linked_params(lifecycle.read_operation());
+    for step in &lifecycle.steps {
+        let mut path = step.path.to_string();
+        for param in &step.operation.path_params {
+            let value = match linked.iter().find(|(linked, _)| linked.name == param.name) {
+                Some((_, value)) => setup.static_value(value).unwrap_or_else(|| format!("{{{{{}}}}}", link_variable(&setup.link, &param.name))),
+                None => to_plain_text(&path_value(param)),
+            };
+            path = path.replace(&format!("{{{}}}", param.name), &value);
+        }
+        items.push(lifecycle_request(
+            spec,
+            &format!("{}: {}", step.action.description(), step.operation.operation_id),
+            step.operation,
+            &format!("{}{}", path, step.query()),
+            step.body().filter(|_| step.action == Action::Update),
+            status_test(step.expected_status()),
+            static_headers,
+        ));
+    }
+    
+    serde_json::json!({
+        "name": format!("{} lifecycle", lifecycle.tag),
+        "item": items,
+    })
+}
+
+/// A Postman request of a lifecycle folder, sent to the environment's base URL with the operation's credentials and
+/// `static_headers`
+fn lifecycle_request(
+    spec: &SwaggerSpec,
+    name: &str,
+    operation: &ApiOperation,
+    path: &str,
+    body: Option<Value>,
+    tests: Vec<String>,
+    static_headers: &[(String, String)],
+) -> Value {
+    let mut headers: Vec<Value> = fixed_headers(operation, static_headers)
+        .into_iter()
+        .map(|(key, value)| serde_json::json!({ "key": key, "value": value, "type": "text" }))
+        .collect();
+    if body.is_some() {
+        headers.push(serde_json::json!({ "key": "Content-Type", "value": "application/json", "type": "text" }));
+    }
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let mut url = serde_json::json!({
+        "raw": format!("{{{{baseUrl}}}}{}", path),
+        "host": ["{{baseUrl}}"],
+        "path": path.trim_start_matches('/').split('/').collect::<Vec<_>>(),
+    });
+    if !query.is_empty() {
+        url["raw"] = Value::String(format!("{{{{baseUrl}}}}{}?{}", path, query));
+        url["query"] = query
+            .split('&')
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                serde_json::json!({ "key": key, "value": value })
+            })
+            .collect();
+    }
+    let mut request = serde_json::json!({
+        "method": operation.method.to_uppercase(),
+        "header": headers,
+        "url": url,
+    });
+    if let Some(body) = body {
+        request["body"] = serde_json::json!({
+            "mode": "raw",
+            "raw": serde_json::to_string_pretty(&body).unwrap_or_default(),
+            "options": { "raw": { "language": "json" } },
+        });
+    }
+    // Requests inherit the collection's auth, unless the operation is secured differently or not at all
+    let auth = postman_auth(spec, &operation.security);
+    if auth != postman_auth(spec, &spec.security) {
+        request["auth"] = auth.unwrap_or_else(|| serde_json::json!({ "type": "noauth" }));
+    }
+    
+    serde_json::json!({
+        "name": name,
+        "request": request,
+        "event": [{
+            "listen": "test",
+            "script": { "exec": tests, "type": "text/javascript" },
+        }],
+        "response": [],
+    })
+}
+
+/// A collection pre-request script fetching an access token with the OAuth2 client credentials flow into `authToken`,
+/// for all the scopes `credentials` ask for, and again a minute before it expires
+fn client_credentials_event(credentials: &[Credential]) -> Option<Value> {
+    let token_url = credentials.iter().find_map(|credential| match credential {
+        Credential::ClientCredentials { token_url, .. } => Some(token_url),
+        _ => None,
+    })?;
+    let mut scopes: Vec<&str> = Vec::new();
+    for credential in credentials {
+        if let Credential::ClientCredentials { token_url: url, scope, .. } = credential {
+            for scope in scope.split_whitespace() {
+                if url == token_url && !scopes.contains(&scope) {
+                    scopes.push(scope);
+                }
+            }
+        }
+    }
+    let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+    
+    Some(serde_json::json!({
+        "listen": "prerequest",
+        "script": {
+            "exec": [
+                "// Fetch an access token with the OAuth2 client credentials flow, and again a minute before it expires",
+                "if (Date.now() >= Number(pm.environment.get('authTokenRefreshAt') || 0)) {",
+                "    pm.sendRequest({",
+                format!("        url: {},", js_str(token_url)),
+                "        method: 'POST',",
+                "        header: { 'Content-Type': 'application/x-www-form-urlencoded' },",
+                "        body: {",
+                "            mode: 'urlencoded',",
+                "            urlencoded: [",
+                "                { key: 'grant_type', value: 'client_credentials' },",
+                "                { key: 'client_id', value: pm.environment.get('clientId') },",
+                "                { key: 'client_secret', value: pm.environment.get('clientSecret') },",
+                format!("                {{ key: 'scope', value: {} }},", js_str(&scopes.join(" "))),
+                "            ],",
+                "        },",
+                "    }, function (error, response) {",
+                "        if (error) {",
+                "            throw error;",
+                "        }",
+                "        const body = response.json();",
+                "        pm.environment.set('authToken', body.access_token);",
+                "        pm.environment.set('authTokenRefreshAt', Date.now() + Math.max((body.expires_in || 3600) - 60, 0) * 1000);",
+                "    });",
+                "}",
+            ],
+            "type": "text/javascript",
+        },
+    }))
+}
+
+/// A Postman request for a negative or boundary test case, with a test of the status the API has to answer with
+fn case_request(
+    spec: &SwaggerSpec,
+    path: &str,
+    operation: &ApiOperation,
+    variant: Option<&SchemaVariant>,
+    case: &RequestCase,
+    static_headers: &[(String, String)],
+) -> Value {
+    let method = operation.method.to_uppercase();
+    let summary = operation.summary.as_deref().unwrap_or(&operation.operation_id);
+    let summary = match variant {
+        Some(variant) => format!("{} ({})", summary, variant.name),
+        None => summary.to_string(),
+    };
+    
+    let request_path = case.path(path, operation);
+    let query = case.query(operation);
+    let raw_query = if query.is_empty() {
+        String::new()
+    } else {
+        let pairs: Vec<String> = query.iter().map(|(name, value)| format!("{}={}", percent_encode(name), percent_encode(value))).collect();
+        format!("?{}", pairs.join("&"))
+    };
+    let mut headers: Vec<(String, String)> = case.headers(operation, static_headers);
+    
+    let mut request = serde_json::json!({
+        "method": method,
+        "url": {
+            "raw": format!("{{{{baseUrl}}}}{}{}", request_path, raw_query),
+            "host": ["{{baseUrl}}"],
+            "path": request_path.trim_start_matches('/').split('/').collect::<Vec<_>>(),
+        },
+        "description": format!("Sent {}, the request has to be {}", case.description, case.outcome()),
+    });
+    if !query.is_empty() {
+        request["url"]["query"] = query.iter().map(|(key, value)| serde_json::json!({ "key": key, "value": value })).collect();
+    }
+    if let Some(body) = case.body(operation, variant) {
+        if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Content-Type")) {
+            headers.push(("Content-Type".to_string(), "application/json".to_string()));
+        }
+        request["body"] = serde_json::json!({
+            "mode": "raw",
+            "raw": serde_json::to_string_pretty(&body).unwrap_or_default(),
+            "options": { "raw": { "language": "json" } },
+        });
+    }
+    request["header"] = headers.iter().map(|(key, value)| serde_json::json!({ "key": key, "value": value, "type": "text" })).collect();
+    
+    // Sent with the same credentials as the operation's other requests, unless the case leaves them out
+    let auth = postman_auth(spec, &operation.security);
+    if case.mutation == Mutation::OmitCredentials {
+        request["auth"] = serde_json::json!({ "type": "noauth" });
+    } else if auth != postman_auth(spec, &spec.security) {
+        request["auth"] = auth.unwrap_or_else(|| serde_json::json!({ "type": "noauth" }));
+    }
+    
+    let (test, check) = match case.expected_status(operation) {
+        Some(status) => (format!("Status code is {}", status), format!("    pm.response.to.have.status({});", status)),
+        None => ("Status code is 4xx".to_string(), "    pm.expect(pm.response.code).to.be.within(400, 499);".to_string()),
+    };
+    let mut exec = vec![format!("pm.test({}, function () {{", serde_json::to_string(&test).unwrap_or_default()), check, "})".to_string()];
+    if let Some(media_type) = case.media_type() {
+        let test = serde_json::to_string(&format!("Content-Type is {}", media_type)).unwrap_or_default();
+        exec.push(format!("pm.test({}, function () {{", test));
+        exec.push(format!(
+            "    pm.expect((pm.response.headers.get(\"Content-Type\") || \"\").split(\";\")[0].trim()).to.eql({});",
+            serde_json::to_string(media_type).unwrap_or_default(),
+        ));
+        exec.push("})".to_string());
+    }
+    serde_json::json!({
+        "name": format!("{} {} {}", method, summary, case.description),
+        "request": request,
+        "event": [{
+            "listen": "test",
+            "script": {
+                "exec": exec,
+                "type": "text/javascript",
+            },
+        }],
+        "response": [],
+    })
+}
+
+/// The two Postman requests of an operation's pagination test, run in turn: the first page's stores its items (and the
+/// next page's cursor) in collection variables, which the second page's compares its items to (and is sent with)
+fn pagination_requests(spec: &SwaggerSpec, operation: &ApiOperation, pages: &PageRequests) -> Vec<Value> {
+    let summary = operation.summary.as_deref().unwrap_or(&operation.operation_id);
+    let string = |text: &str| serde_json::to_string(text).unwrap_or_default();
+    let items_variable = format!("{}.firstPageItems", operation.operation_id);
+    let cursor_variable = format!("{}.nextCursor", operation.operation_id);
+    let body_value = |pointer: &str| {
+        pointer_segments(pointer).iter().fold("pm.response.json()".to_string(), |value, name| format!("{}[{}]", value, string(name)))
+    };
+    let auth = postman_auth(spec, &operation.security);
+    
+    // The cursor variable is left unencoded, for Postman to replace
+    let cursor = pages.cursor.as_ref().map(|(param, _)| (param.clone(), format!("{{{{{}}}}}", cursor_variable)));
+    [("first", &pages.first_query, None), ("second", &pages.second_query, cursor)]
+        .into_iter()
+        .map(|(number, query, cursor)| {
+            let mut pairs: Vec<String> = query.iter().map(|(name, value)| format!("{}={}", percent_encode(name), percent_encode(value))).collect();
+            pairs.extend(cursor.iter().map(|(name, value)| format!("{}={}", percent_encode(name), value)));
+            let query: Vec<&(String, String)> = query.iter().chain(&cursor).collect();
+            let raw_query = if pairs.is_empty() { String::new() } else { format!("?{}", pairs.join("&")) };
+            let mut request = serde_json::json!({
+                "method": "GET",
+                "url": {
+                    "raw": format!("{{{{baseUrl}}}}{}{}", pages.path, raw_query),
+                    "host": ["{{baseUrl}}"],
+                    "path": pages.path.trim_start_matches('/').split('/').collect::<Vec<_>>(),
+                },
+                "header": pages.headers.iter().map(|(key, value)| serde_json::json!({ "key": key, "value": value, "type": "text" })).collect::<Vec<_>>(),
+                "description": format!("The {} of two pages, which have to differ", number),
+            });
+            if !query.is_empty() {
+                request["url"]["query"] = query.iter().map(|(key, value)| serde_json::json!({ "key": key, "value": value })).collect();
+            }
+            if auth != postman_auth(spec, &spec.security) {
+                request["auth"] = auth.clone().unwrap_or_else(|| serde_json::json!({ "type": "noauth" }));
+            }
+            
+            let mut exec = vec![
+                format!("pm.test(\"Status code is {}\", function () {{", pages.status),
+                format!("    pm.response.to.have.status({});", pages.status),
+                "})".to_string(),
+                format!("const items = {};", body_value(&pages.items_pointer)),
+            ];
+            if let Some(limit) = pages.limit {
+                exec.push(format!("pm.test(\"Page holds at most {} items\", function () {{", limit));
+                exec.push(format!("    pm.expect(items.length).to.be.at.most({});", limit));
+                exec.push("})".to_string());
+            }
+            if number == "first" {
+                exec.push(format!("pm.collectionVariables.set({}, JSON.stringify(items));", string(&items_variable)));
+                if let Some((_, pointer)) = &pages.cursor {
+                    exec.push(format!("pm.collectionVariables.set({}, {});", string(&cursor_variable), body_value(pointer)));
+                }
+            } else {
+                exec.push("pm.test(\"Second page differs from the first\", function () {".to_string());
+                exec.push(format!("    pm.expect(JSON.stringify(items)).to.not.eql(pm.collectionVariables.get({}));", string(&items_variable)));
+                exec.push("})".to_string());
+            }
+            serde_json::json!({
+                "name": format!("GET {} {} page", summary, number),
+                "request": request,
+                "event": [{
+                    "listen": "test",
+                    "script": {
+                        "exec": exec,
+                        "type": "text/javascript",
+                    },
+                }],
+                "response": [],
+            })
+        })
+        .collect()
+}
 
-## Running the tests
+/// Header or query parameter (`location`) entries sending a request's credentials in Python tests, the values being
+/// expressions reading them from `os.environ`
+fn python_credentials(credentials: &[Credential], location: &str) -> Vec<(String, String)> {
+    credentials
+        .iter()
+        .filter(|credential| credential.location() == location)
+        .filter_map(|credential| {
+            let value = match credential {
+                Credential::Bearer { env } => format!("f\"Bearer {{os.environ['{}']}}\"", env),
+                Credential::ApiKey { env, .. } => format!("os.environ['{}']", env),
+                Credential::ClientCredentials { token_url, scope, .. } => {
+                    let quote = |text: &str| format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"));
+                    format!("f\"Bearer {{access_token({}, {})}}\"", quote(token_url), quote(scope))
+                },
+                Credential::Basic { .. } => return None,
+            };
+            Some((credential.name().to_string(), value))
+        })
+        .collect()
+}
 
-To run the tests:
+/// The environment variables holding the ID and secret of the OAuth2 client access tokens are fetched for, when any of
+/// `credentials` needs a token
+fn client_credentials_env(credentials: &[Credential]) -> Option<(&str, &str)> {
+    credentials.iter().find_map(|credential| match credential {
+        Credential::ClientCredentials { client_id_env, client_secret_env, .. } => Some((client_id_env.as_str(), client_secret_env.as_str())),
+        _ => None,
+    })
+}
 
-```
-pytest -v
-```
-"#)?;
-        
-        Ok(())
-    }
+/// Whether sending `credentials` takes an access token fetched with the OAuth2 client credentials flow
+fn uses_access_token(credentials: &[Credential]) -> bool {
+    credentials.iter().any(|credential| matches!(credential, Credential::ClientCredentials { .. }))
 }
 
-// JavaScript Jest test generator
-struct JestGenerator;
+/// The `auth` argument of requests and httpx sending a request's basic authentication credentials in Python tests
+fn python_basic_auth(credentials: &[Credential]) -> Option<String> {
+    credentials.iter().find_map(|credential| match credential {
+        Credential::Basic { username_env, password_env } => {
+            Some(format!("(os.environ['{}'], os.environ['{}'])", username_env, password_env))
+        },
+        _ => None,
+    })
+}
 
-impl JestGenerator {
-    pub fn new() -> Self {
-        JestGenerator
+/// Expression reading a value a link supplies in Python tests, from the response bound to `response` when it comes from
+/// the response
+fn python_link_value(setup: &LinkedSetup, value: &LinkValue, response: &str) -> String {
+    let text = |text: &str| to_python_literal(&Value::String(text.to_string()), "");
+    match value {
+        LinkValue::ResponseBody(pointer) => format!("linked_value({}.json(), {})", response, text(pointer)),
+        LinkValue::ResponseHeader(name) => format!("{}.headers[{}]", response, text(name)),
+        other => text(&setup.static_value(other).unwrap_or_default()),
     }
-    
-    fn generate_operation_test(&self, operation: &ApiOperation, path: &str, base_url: &str) -> String {
-        let method = operation.method.to_lowercase();
-        let operation_id = &operation.operation_id;
-        let summary = operation.summary.as_deref().unwrap_or("");
-        
-        // Parameter setup
-        let path_params_setup = operation.path_params.iter()
-            .map(|p| format!("  // Path parameter: {}\n  const {} = 1; // Replace with actual test value", p.name, p.name))
-            .collect::<Vec<_>>()
-            .join("\n");
-        
-        let query_params = if !operation.query_params.is_empty() {
-            "  const params = {\n".to_string() + &operation.query_params.iter()
-                .map(|p| format!(r#"    {}: "test_value""#, p.name))
-                .collect::<Vec<_>>()
-                .join(",\n") + "\n  };"
-        } else {
-            "  const params = {};".to_string()
-        };
-        
-        let body_param = operation.body_param.as_ref()
-            .map(|_| r#"  const jsonData = {
-    name: "Test User",
-    email: "test@example.com"
-  };"#.to_string())
-            .unwrap_or_else(|| "  const jsonData = null;".to_string());
-        
-        // Create path with parameter interpolation
-        let mut endpoint_path = path.to_string();
-        for param in &operation.path_params {
-            endpoint_path = endpoint_path.replace(&format!("{{{}}}", param.name), &format!("${{{}}}", param.name));
-        }
-        
-        // Request method options
-        let request_params = match method.as_str() {
-            "get" | "delete" => "{ params }",
-            _ => "jsonData, { params }",
-        };
-        
-        // Expected status code
-        let mut expected_status = "200";
-        if method == "post" {
-            expected_status = "201";
-        } else if method == "delete" {
-            expected_status = "204";
-        }
-        
-        // Find the expected status from the responses
-        for resp in &operation.responses {
-            if resp.status_code.starts_with('2') {
-                expected_status = &resp.status_code;
-                break;
-            }
-        }
-        
-        format!(
-            r#"test('{operation_id}', async () => {{
-  // {summary}
-{path_params_setup}
-{query_params}
-{body_param}
+}
 
-  const url = `{base_url}{endpoint_path}`;
-  
-  const response = await axios.{method}(url, {request_params});
-  
-  // Verify status code
-  expect(response.status).toBe({expected_status});
-  
-  // Verify the response body
-  // expect(response.data).toHaveProperty('id');
-}});"#
-        )
+/// Expression reading a value a link supplies in JavaScript tests, from the axios response bound to `response` when it
+/// comes from the response
+fn js_link_value(setup: &LinkedSetup, value: &LinkValue, response: &str) -> String {
+    let text = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+    match value {
+        LinkValue::ResponseBody(pointer) => format!("linkedValue({}.data, {})", response, text(pointer)),
+        // axios exposes header names in lowercase
+        LinkValue::ResponseHeader(name) => format!("{}.headers[{}]", response, text(&name.to_lowercase())),
+        other => text(&setup.static_value(other).unwrap_or_default()),
     }
 }
 
-impl TestGenerator for JestGenerator {
-    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
-        // Create the output directory if it doesn't exist
-        fs::create_dir_all(output_dir)?;
-        
-        // Create a test file for each path
-        for path in &spec.paths {
-            let path_name = path.path
-                .trim_start_matches('/')
-                .replace('/', "_")
-                .replace('{', "")
-                .replace('}', "");
-                
-            let test_file_path = output_dir.join(format!("{}.test.js", path_name));
-            let mut file = File::create(test_file_path)?;
-            
-            // Write the file header
-            writeln!(file, "const axios = require('axios');\n")?;
-            
-            // Generate tests for each operation in this path
-            for operation in &path.operations {
-                let test_code = self.generate_operation_test(operation, &path.path, base_url);
-                writeln!(file, "{}\n", test_code)?;
-            }
-        }
-        
-        // Create a package.json file
-        let package_file_path = output_dir.join("package.json");
-        let mut package_file = File::create(package_file_path)?;
-        
-        writeln!(package_file, r#"{{
-  "name": "api-tests",
-  "version": "1.0.0",
-  "description": "Generated API tests for the Swagger/OpenAPI specification",
-  "scripts": {{
-    "test": "jest"
-  }},
-  "dependencies": {{
-    "axios": "^1.3.4"
-  }},
-  "devDependencies": {{
-    "jest": "^29.5.0"
-  }}
-}}
-"#)?;
-        
-        // Create a README.md file with instructions
-        let readme_file_path = output_dir.join("README.md");
-        let mut readme_file = File::create(readme_file_path)?;
-        
-        writeln!(readme_file, r#"# API Tests
+/// JavaScript loading the .env file of the environment tested, `default` when `TEST_ENV` doesn't name one
+fn jest_load_environment(default: &str) -> String {
+    format!(
+        r#"// Settings of the environment {environment_var} names ("{default}" by default) from its .env file, which don't replace variables already set
+require('dotenv').config({{ path: require('path').join(__dirname, `.env.${{process.env.{environment_var} || '{default}'}}`) }});"#,
+        environment_var = ENVIRONMENT_VAR,
+    )
+}
 
-Generated API tests for the Swagger/OpenAPI specification.
+/// Header or query parameter (`location`) entries sending a request's credentials in JavaScript tests, the values being
+/// expressions reading them from `process.env`
+fn js_credentials(credentials: &[Credential], location: &str) -> Vec<(String, String)> {
+    credentials
+        .iter()
+        .filter(|credential| credential.location() == location)
+        .filter_map(|credential| {
+            let value = match credential {
+                Credential::Bearer { env } => format!("`Bearer ${{process.env.{}}}`", env),
+                Credential::ApiKey { env, .. } => format!("process.env.{}", env),
+                Credential::ClientCredentials { token_url, scope, .. } => {
+                    let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+                    format!("`Bearer ${{await accessToken({}, {})}}`", js_str(token_url), js_str(scope))
+                },
+                Credential::Basic { .. } => return None,
+            };
+            Some((credential.name().to_string(), value))
+        })
+        .collect()
+}
 
-## Setup
+/// The axios `auth` option sending a request's basic authentication credentials in JavaScript tests
+fn js_basic_auth(credentials: &[Credential]) -> Option<String> {
+    credentials.iter().find_map(|credential| match credential {
+        Credential::Basic { username_env, password_env } => {
+            Some(format!("{{ username: process.env.{}, password: process.env.{} }}", username_env, password_env))
+        },
+        _ => None,
+    })
+}
 
-Install the dependencies:
+/// Postman auth sending the credentials of the first requirement's schemes Postman supports, from environment variables
+fn postman_auth(spec: &SwaggerSpec, requirements: &[SecurityRequirement]) -> Option<Value> {
+    let variable = |key: &str, value: &str| serde_json::json!({ "key": key, "value": value, "type": "string" });
+    requirements
+        .iter()
+        .flat_map(|requirement| &requirement.schemes)
+        .filter_map(|(name, _)| spec.security_schemes.iter().find(|scheme| &scheme.name == name))
+        .find_map(|scheme| match &scheme.kind {
+            SecuritySchemeKind::Bearer { .. } | SecuritySchemeKind::OAuth2 { .. } | SecuritySchemeKind::OpenIdConnect { .. } => {
+                Some(serde_json::json!({ "type": "bearer", "bearer": [variable("token", "{{authToken}}")] }))
+            },
+            SecuritySchemeKind::Basic => Some(serde_json::json!({
+                "type": "basic",
+                "basic": [variable("username", "{{username}}"), variable("password", "{{password}}")],
+            })),
+            // Postman can't send API keys in cookies
+            SecuritySchemeKind::ApiKey { name, location } if location != "cookie" => Some(serde_json::json!({
+                "type": "apikey",
+                "apikey": [variable("key", name), variable("value", "{{apiKey}}"), variable("in", location)],
+            })),
+            SecuritySchemeKind::ApiKey { .. } | SecuritySchemeKind::Other(_) => None,
+        })
+}
 
-```
-npm install
-```
+/// Test script lines validating the response body against the documented schema with tv4, which Postman bundles
+/// and which reads the draft 4 schemas generated
+fn schema_test_script(operation: &ApiOperation, pm_test: &str) -> Vec<String> {
+    let schema = match response_validation_schema(operation) {
+        Some(schema) => schema,
+        None => return Vec::new(),
+    };
+    vec![
+        format!("{}(\"Response body matches its schema\", function () {{", pm_test),
+        format!("    var schema = {};", serde_json::to_string(&schema).unwrap_or_default()),
+        "    pm.expect(tv4.validate(pm.response.json(), schema), JSON.stringify(tv4.error)).to.be.true;".to_string(),
+        "})".to_string(),
+    ]
+}
 
-## Running the tests
+/// Test script lines checking the response body is the example documented for it
+fn example_test_script(operation: &ApiOperation, pm_test: &str) -> Vec<String> {
+    let example = match response_example(operation) {
+        Some(example) => example,
+        None => return Vec::new(),
+    };
+    vec![
+        format!("{}(\"Response body is the documented example\", function () {{", pm_test),
+        format!("    pm.expect(pm.response.json()).to.eql({});", serde_json::to_string(&example).unwrap_or_default()),
+        "})".to_string(),
+    ]
+}
 
-To run the tests:
+/// Test script lines checking what responses to HEAD and OPTIONS have to be like: no body, and the allowed methods listed
+fn method_test_script(operation: &ApiOperation, pm_test: &str) -> Vec<String> {
+    let (name, assertion) = match operation.method.to_uppercase().as_str() {
+        "HEAD" => ("Response has no body", "    pm.expect(pm.response.text()).to.be.empty;"),
+        "OPTIONS" => (
+            "Allowed methods are listed",
+            "    pm.expect(pm.response.headers.has(\"Allow\") || pm.response.headers.has(\"Access-Control-Allow-Methods\")).to.be.true;",
+        ),
+        _ => return Vec::new(),
+    };
+    vec![
+        format!("{}(\"{}\", function () {{", pm_test, name),
+        assertion.to_string(),
+        "})".to_string(),
+    ]
+}
 
-```
-npm test
-```
-"#)?;
-        
-        Ok(())
-    }
+/// Name of the collection variable holding the value a link supplies for a parameter
+fn link_variable(link: &ApiLink, param: &str) -> String {
+    format!("{}.{}", link.name, param)
 }
 
-// Postman collection generator
-struct PostmanGenerator;
+/// Test script lines storing the response values the operation's links pass on, so that the linked requests can use
+/// them, including those of the operations depending on it (see `links`)
+fn link_variable_script(spec: &SwaggerSpec, operation: &ApiOperation) -> Vec<String> {
+    outgoing_links(spec, operation).iter().flat_map(|link| link_value_script(link)).collect()
+}
 
-impl PostmanGenerator {
-    pub fn new() -> Self {
-        PostmanGenerator
-    }
+/// Test script lines storing the response values one link passes on in collection variables
+fn link_value_script(link: &ApiLink) -> Vec<String> {
+    link.parameters
+        .iter()
+        .filter_map(|(param, value)| {
+            let expression = match value {
+                LinkValue::ResponseBody(pointer) => pointer
+                    .split('/')
+                    .skip(1)
+                    .map(|token| format!("[{}]", serde_json::to_string(&token.replace("~1", "/").replace("~0", "~")).unwrap_or_default()))
+                    .fold("pm.response.json()".to_string(), |expression, key| expression + &key),
+                LinkValue::ResponseHeader(name) => format!("pm.response.headers.get({})", serde_json::to_string(name).unwrap_or_default()),
+                _ => return None,
+            };
+            // A parameter may be qualified with its location, as in `path.id`
+            let param = ["path.", "query.", "header.", "cookie."]
+                .iter()
+                .find_map(|location| param.strip_prefix(location))
+                .unwrap_or(param);
+            Some(format!(
+                "pm.collectionVariables.set({}, {});",
+                serde_json::to_string(&link_variable(link, param)).unwrap_or_default(),
+                expression
+            ))
+        })
+        .collect()
 }
 
 impl TestGenerator for PostmanGenerator {
@@ -661,160 +4905,53 @@ impl TestGenerator for PostmanGenerator {
         
         // Collection ID and metadata
         let collection_id = uuid::Uuid::new_v4().to_string();
-        let collection_name = "API Tests";
+        let collection_name = Value::String(format!("{} Tests", spec.info.title));
+        let description = Value::String(format!("Generated API tests for {} (version {})", spec.info.title, spec.info.version));
         
         // Write collection header
         writeln!(file, r#"{{
   "info": {{
     "_postman_id": "{}",
-    "name": "{}",
-    "description": "Generated API tests for the Swagger/OpenAPI specification",
+    "name": {},
+    "description": {},
     "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
   }},
-  "item": ["#, collection_id, collection_name)?;
+  "item": ["#, collection_id, collection_name, description)?;
         
-        // Group requests by path
+        // Group requests by path, or by tag
+        let groups = operation_groups(spec, &self.options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let requests = generate_in_parallel(&operations, &self.options, |path, operation, variant| {
+            let mut request = self.generate_request(spec, path, operation, variant);
+            for case in request_cases(operation, variant, &self.options) {
+                let case = case_request(spec, path, operation, variant, &case, &self.options.headers);
+                request.push_str(&format!(",\n        {}", to_json_literal(&case, "        ")));
+            }
+            for page in page_requests(operation, path, variant, &self.options).iter().flat_map(|pages| pagination_requests(spec, operation, pages)) {
+                request.push_str(&format!(",\n        {}", to_json_literal(&page, "        ")));
+            }
+            request
+        })?;
         let mut is_first_path = true;
         
-        for path in &spec.paths {
+        for (group, requests) in groups.into_iter().zip(requests) {
             if !is_first_path {
                 writeln!(file, ",")?;
             }
             
-            // Sanitize path name for folder name
-            let folder_name = path.path
-                .trim_start_matches('/')
-                .replace('/', " ")
-                .replace('{', "")
-                .replace('}', "");
-                
             // Start path folder
             writeln!(file, r#"    {{
-      "name": "{}",
-      "item": ["#, folder_name)?;
+      "name": {},
+      "item": ["#, serde_json::to_string(&group.name).unwrap_or_default())?;
                 
             // Add requests for each operation
             let mut is_first_op = true;
             
-            for operation in &path.operations {
+            for request in requests {
                 if !is_first_op {
                     writeln!(file, ",")?;
                 }
-                
-                let method = operation.method.to_uppercase();
-                let summary = operation.summary.as_deref().unwrap_or(&operation.operation_id);
-                
-                // Create URL with parameter placeholders
-                let mut url = format!("{}{}", base_url, path.path);
-                
-                // Example path parameter values
-                for param in &operation.path_params {
-                    url = url.replace(&format!("{{{}}}", param.name), &format!(":{}", param.name));
-                }
-                
-                // Query parameters
-                let query_params = if !operation.query_params.is_empty() {
-                    let params = operation.query_params.iter()
-                        .map(|p| {
-                            format!(
-                                r#"            {{
-              "key": "{}",
-              "value": "test_value",
-              "description": "{}"
-            }}"#, 
-                                p.name,
-                                p.name
-                            )
-                        })
-                        .collect::<Vec<_>>()
-                        .join(",\n");
-                        
-                    format!(r#"          "query": [
-{}
-          ],"#, params)
-                } else {
-                    "".to_string()
-                };
-                
-                // Request body
-                let body = if operation.body_param.is_some() {
-                    r#"          "body": {
-            "mode": "raw",
-            "raw": "{\n  \"name\": \"Test User\",\n  \"email\": \"test@example.com\"\n}",
-            "options": {
-              "raw": {
-                "language": "json"
-              }
-            }
-          },"#
-                } else {
-                    ""
-                };
-                
-                // Tests for validating responses
-                let mut expected_status = "200";
-                if method == "POST" {
-                    expected_status = "201";
-                } else if method == "DELETE" {
-                    expected_status = "204";
-                }
-                
-                // Find the expected status from the responses
-                for resp in &operation.responses {
-                    if resp.status_code.starts_with('2') {
-                        expected_status = &resp.status_code;
-                        break;
-                    }
-                }
-                
-                let tests = format!(
-                    r#"          "event": [
-            {{
-              "listen": "test",
-              "script": {{
-                "exec": [
-                  "pm.test(\"Status code is {}\", function () {{",
-                  "    pm.response.to.have.status({});",
-                  "}})"
-                ],
-                "type": "text/javascript"
-              }}
-            }}
-          ],"#, 
-                    expected_status, expected_status
-                );
-                
-                // Write the request
-                writeln!(file, r#"        {{
-          "name": "{} {}",
-          "request": {{
-            "method": "{}",
-            "header": [],
-{}
-{}
-            "url": {{
-              "raw": "{}",
-              "host": [
-                "{}"
-              ],
-              "path": [{}
-              ]
-            }},
-            "description": "{}"
-          }},
-{}
-          "response": []
-        }}"#,
-                    method, summary,
-                    method,
-                    query_params,
-                    body,
-                    url,
-                    base_url.replace("http://", "").replace("https://", "").split('/').next().unwrap_or("localhost"),
-                    path.path.trim_start_matches('/').split('/').map(|p| format!("                \"{}\"", p.replace("{", ":").replace("}", ""))).collect::<Vec<_>>().join(",\n"),
-                    operation.description.as_deref().unwrap_or(""),
-                    tests
-                )?;
+                writeln!(file, "{}", request)?;
                 
                 is_first_op = false;
             }
@@ -827,29 +4964,209 @@ impl TestGenerator for PostmanGenerator {
             is_first_path = false;
         }
         
+        // Each lifecycle is tested in a folder of its own, in order
+        for lifecycle in lifecycles(spec, &self.options) {
+            if !is_first_path {
+                writeln!(file, ",")?;
+            }
+            writeln!(file, "    {}", to_json_literal(&lifecycle_folder(spec, &lifecycle, &self.options.headers), "    "))?;
+            is_first_path = false;
+        }
+        
+        // Webhooks go in a folder of their own, delivered to the receiver set in the `webhookUrl` variable
+        let webhooks = webhook_operations(spec, &self.options);
+        let variables = if webhooks.is_empty() {
+            String::new()
+        } else {
+            if !is_first_path {
+                writeln!(file, ",")?;
+            }
+            let folder = serde_json::json!({
+                "name": "Webhooks",
+                "item": webhooks.iter().map(|(name, operation)| webhook_request(name, operation)).collect::<Vec<_>>(),
+            });
+            writeln!(file, "    {}", to_json_literal(&folder, "    "))?;
+            
+            let variable = serde_json::json!([{ "key": "webhookUrl", "value": DEFAULT_WEBHOOK_URL }]);
+            format!(",\n  \"variable\": {}", to_json_literal(&variable, "  "))
+        };
+        
+        // Requests send the credentials of the API's security requirements unless they set their own
+        let auth = postman_auth(spec, &spec.security)
+            .map(|auth| format!(",\n  \"auth\": {}", to_json_literal(&auth, "  ")))
+            .unwrap_or_default();
+        
+        // Access tokens of the client credentials flow are fetched before the requests needing them
+        let events: Vec<Value> = client_credentials_event(&used_credentials(spec, &self.options.auth)).into_iter().collect();
+        let events = to_json_literal(&Value::Array(events), "  ");
+        
         // Close collection
         writeln!(file, r#"
   ],
-  "event": []
+  "event": {events}{auth}{variables}
 }}"#)?;
         
+        // Create an environment with the base URL and a variable for each kind of credentials the API accepts
+        let has_scheme = |matches: fn(&SecuritySchemeKind) -> bool| spec.security_schemes.iter().any(|scheme| matches(&scheme.kind));
+        let mut variables = vec![("baseUrl", "default", base_url.trim_end_matches('/'))];
+        if has_scheme(|kind| matches!(
+            kind,
+            SecuritySchemeKind::Bearer { .. } | SecuritySchemeKind::OAuth2 { .. } | SecuritySchemeKind::OpenIdConnect { .. }
+        )) {
+            variables.push(("authToken", "secret", ""));
+        }
+        if has_scheme(|kind| matches!(kind, SecuritySchemeKind::Basic)) {
+            variables.push(("username", "default", ""));
+            variables.push(("password", "secret", ""));
+        }
+        if has_scheme(|kind| matches!(kind, SecuritySchemeKind::ApiKey { .. })) {
+            variables.push(("apiKey", "secret", ""));
+        }
+        if self.options.auth.client_credentials_env.is_some() && has_scheme(|kind| client_credentials_url(kind).is_some()) {
+            variables.push(("clientId", "default", ""));
+            variables.push(("clientSecret", "secret", ""));
+        }
+        let environment = |name: String, base_url: &str| {
+            serde_json::json!({
+                "name": name,
+                "values": variables
+                    .iter()
+                    .map(|(key, kind, value)| {
+                        let value = if *key == "baseUrl" { base_url.trim_end_matches('/') } else { value };
+                        serde_json::json!({ "key": key, "value": value, "type": kind, "enabled": true })
+                    })
+                    .collect::<Vec<_>>(),
+                "_postman_variable_scope": "environment",
+            })
+        };
+        
+        // With environments, each gets a Postman environment of its own instead
+        let environments: Vec<(String, Value)> = if self.options.environments.is_empty() {
+            vec![("postman_environment.json".to_string(), environment(format!("{} Environment", spec.info.title), base_url))]
+        } else {
+            self.options.environments
+                .iter()
+                .map(|(name, base_url)| (format!("{}.postman_environment.json", name), environment(format!("{} ({})", spec.info.title, name), base_url)))
+                .collect()
+        };
+        for (file_name, environment) in &environments {
+            fs::write(output_dir.join(file_name), serde_json::to_string_pretty(environment).unwrap_or_default() + "\n")?;
+        }
+        
+        // The file multipart requests upload, relative to the directory Newman runs in
+        if spec.paths.iter().flat_map(|p| &p.operations).any(has_multipart_body) {
+            fs::write(output_dir.join("upload.txt"), "test file content")?;
+        }
+        
+        // Create a script running the collection with Newman, taking the environment's values from environment variables
+        let script_path = output_dir.join("run-newman.sh");
+        let mut script = File::create(&script_path)?;
+        
+        let token_env = self.options.auth.bearer_env.as_deref().unwrap_or("API_TOKEN");
+        let key_env = self.options.auth.api_key_env.as_deref().unwrap_or("API_KEY");
+        let (username_env, password_env) = self.options.auth.basic_env
+            .as_ref()
+            .map_or(("API_USERNAME", "API_PASSWORD"), |(username_env, password_env)| (username_env.as_str(), password_env.as_str()));
+        let (client_id_env, client_secret_env) = self.options.auth.client_credentials_env
+            .as_ref()
+            .map_or(("", ""), |(client_id_env, client_secret_env)| (client_id_env.as_str(), client_secret_env.as_str()));
+        let overrides: String = [
+            ("API_BASE_URL", "baseUrl"),
+            (token_env, "authToken"),
+            (username_env, "username"),
+            (password_env, "password"),
+            (key_env, "apiKey"),
+            (client_id_env, "clientId"),
+            (client_secret_env, "clientSecret"),
+        ]
+            .iter()
+            .filter(|(_, key)| variables.iter().any(|(variable, _, _)| variable == key))
+            .map(|(name, key)| format!("if [[ -n \"${{{name}:-}}\" ]]; then\n  args+=(--env-var \"{key}=${name}\")\nfi\n"))
+            .collect();
+        let timeout = self.options.timeout_secs
+            .map(|secs| format!(" --timeout-request {}", secs * 1000))
+            .unwrap_or_default();
+        let environment = match default_environment(&self.options) {
+            Some(default) => format!("\"${{{}:-{}}}.postman_environment.json\"", ENVIRONMENT_VAR, default),
+            None => "postman_environment.json".to_string(),
+        };
+        writeln!(script, r#"#!/usr/bin/env bash
+# Run the {title} (version {version}) collection with Newman, writing a JUnit report to results/
+set -euo pipefail
+cd "$(dirname "$0")"
+
+args=(--environment {environment} --reporters cli,junit --reporter-junit-export results/newman.xml{timeout})
+{overrides}
+exec npx --yes newman@6 run postman_collection.json "${{args[@]}}" "$@""#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+        
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&script_path, fs::Permissions::from_mode(0o755))?;
+        }
+        
         // Create a README.md file with instructions
         let readme_file_path = output_dir.join("README.md");
         let mut readme_file = File::create(readme_file_path)?;
         
-        writeln!(readme_file, r#"# Postman API Tests
+        let credentials: Vec<String> = [
+            (token_env, "authToken", "a bearer token, for bearer, OAuth2 and OpenID Connect schemes"),
+            (username_env, "username", "the user name, for HTTP basic authentication"),
+            (password_env, "password", "the password, for HTTP basic authentication"),
+            (key_env, "apiKey", "the API key"),
+            (client_id_env, "clientId", "the ID of the OAuth2 client access tokens are fetched for"),
+            (client_secret_env, "clientSecret", "the secret of the OAuth2 client"),
+        ]
+            .iter()
+            .filter(|(_, key, _)| variables.iter().any(|(variable, _, _)| variable == key))
+            .map(|(name, key, description)| format!("- `{}` sets `{}`: {}", name, key, description))
+            .collect();
+        let credentials = if credentials.is_empty() {
+            String::new()
+        } else {
+            format!("\nThe credentials are set the same way:\n\n{}\n", credentials.join("\n"))
+        };
+        let timeout = self.options.timeout_secs
+            .map(|secs| format!("\nThe script fails requests the API doesn't answer within {} s; in Postman, set the same request timeout under Settings > General.\n", secs))
+            .unwrap_or_default();
+        writeln!(readme_file, r#"# {title} Postman Tests
 
-Generated Postman collection for testing the Swagger/OpenAPI specification.
+Generated Postman collection for testing {title} (version {version}).
 
 ## Setup
 
 1. Import the `postman_collection.json` file into Postman
-2. Create an environment and set the base URL if needed
+2. {import_environment}
 
 ## Running the tests
 
-Run the collection in Postman and review the test results.
-"#)?;
+Run the collection in Postman and review the test results, or run it headlessly with Newman, e.g. in CI:
+
+```
+API_BASE_URL=http://localhost:3000 ./run-newman.sh
+```
+
+The script needs Node.js, writes a JUnit report to `results/newman.xml`, and passes extra arguments on to `newman run`.
+`API_BASE_URL` overrides the `baseUrl` of the environment.
+{credentials}{timeout}{environments}"#,
+            title = spec.info.title,
+            version = spec.info.version,
+            import_environment = if self.options.environments.is_empty() {
+                "Import `postman_environment.json` as an environment, and set the base URL and credentials if needed"
+            } else {
+                "Import the `<name>.postman_environment.json` file of each environment, and set the credentials if needed"
+            },
+            environments = match default_environment(&self.options) {
+                Some(default) => format!(
+                    "\n`{}` picks the environment the script runs the collection against, `{}` by default: {}.\n",
+                    ENVIRONMENT_VAR,
+                    default,
+                    self.options.environments.iter().map(|(name, base_url)| format!("`{}` ({})", name, base_url)).collect::<Vec<_>>().join(", "),
+                ),
+                None => String::new(),
+            })?;
         
         Ok(())
     }
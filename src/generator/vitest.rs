@@ -0,0 +1,212 @@
+// This file contains the Vitest test generator, writing ES module tests like the Jest ones that send their requests with `fetch`.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, query_string, raw_body_example, request_body_example, request_headers,
+    to_json_literal, BodyEncoding,
+};
+use super::layout::operation_groups;
+use super::playwright::json_body_assertions;
+use super::test_framework::{expected_headers, expected_status, generate_in_parallel, response_schema, GeneratorOptions, Result, TestGenerator};
+
+// JavaScript Vitest test generator
+pub struct VitestGenerator {
+    options: GeneratorOptions,
+}
+
+impl VitestGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        VitestGenerator { options }
+    }
+
+    /// Generate the test for one request of an operation, with its body taken from `variant` when given
+    fn generate_operation_test(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+        let operation_id = match variant {
+            Some(variant) => format!("{} ({})", operation.operation_id, variant.name),
+            None => operation.operation_id.clone(),
+        };
+        let summary = operation.summary.clone().unwrap_or_else(|| format!("{} {}", operation.method.to_uppercase(), path));
+
+        let mut lines = vec![format!("  // {}", summary.replace('\n', " "))];
+        lines.extend(fetch_request(operation, path, variant, &self.options.headers));
+        lines.push(String::new());
+
+        // Verify status code and documented headers
+        lines.push(format!("  expect(response.status).toBe({});", expected_status(operation)));
+        for header in expected_headers(operation) {
+            lines.push(format!("  expect(response.headers.get({})).not.toBeNull();", js_str(header)));
+        }
+
+        // Verify the response body
+        if let Some(schema) = response_schema(operation) {
+            let assertions = json_body_assertions(schema);
+            if !assertions.is_empty() {
+                lines.push(String::new());
+                lines.push("  const body = await response.json();".to_string());
+                lines.extend(assertions.into_iter().map(|assertion| format!("  {}", assertion)));
+            }
+        }
+
+        // Deprecated operations are still generated, but skipped
+        let test = if operation.deprecated { "test.skip" } else { "test" };
+        format!("{}({}, async () => {{\n{}\n}});", test, js_str(&operation_id), lines.join("\n"))
+    }
+}
+
+impl TestGenerator for VitestGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // Create a test file for each path, or each tag
+        let groups = operation_groups(spec, &self.options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let tests = generate_in_parallel(&operations, &self.options, |path, operation, variant| {
+            self.generate_operation_test(operation, path, variant)
+        })?;
+
+        for (group, tests) in groups.iter().zip(tests) {
+            let mut file = File::create(output_dir.join(format!("{}.test.js", group.file_stem)))?;
+
+            writeln!(file, r#"import {{ expect, test }} from 'vitest';
+
+// The API_BASE_URL environment variable overrides where the API under test runs
+const BASE_URL = (process.env.API_BASE_URL ?? {base_url}).replace(/\/$/, '');
+
+{tests}"#,
+                base_url = to_json_literal(&Value::String(base_url.trim_end_matches('/').to_string()), ""),
+                tests = tests.join("\n\n"))?;
+        }
+
+        // Create the Vitest configuration
+        let mut config_file = File::create(output_dir.join("vitest.config.js"))?;
+
+        writeln!(config_file, r#"import {{ defineConfig }} from 'vitest/config';
+
+export default defineConfig({{
+  test: {{
+    include: ['**/*.test.js'],
+    testTimeout: 30000,
+  }},
+}});"#)?;
+
+        // Create a package.json file for an ES module package
+        let package = serde_json::json!({
+            "name": spec.info.package_name("-", "tests"),
+            "version": spec.info.package_version("1.0.0"),
+            "description": format!("Generated API tests for {}", spec.info.title),
+            "private": true,
+            "type": "module",
+            "scripts": {
+                "test": "vitest run"
+            },
+            "devDependencies": {
+                "vitest": "^1.6.0"
+            }
+        });
+        fs::write(output_dir.join("package.json"), serde_json::to_string_pretty(&package).unwrap_or_default() + "\n")?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), using Vitest and the `fetch` built into Node.js 18 or later.
+
+## Setup
+
+Install the dependencies:
+
+```
+npm install
+```
+
+## Running the tests
+
+Run the tests against the API at `API_BASE_URL`:
+
+```
+API_BASE_URL=http://localhost:3000 npm test
+```
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// Statements sending the request of an operation to `BASE_URL` with `fetch`, indented for a test body, and declaring
+/// the `response`, sent with `static_headers` too. The body is taken from `variant` when given
+pub fn fetch_request(operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>, static_headers: &[(String, String)]) -> Vec<String> {
+    let js_str = |text: &str| to_json_literal(&Value::String(text.to_string()), "");
+    let method = operation.method.to_uppercase();
+
+    // Cookie parameters are sent as a `Cookie` header
+    let mut headers = request_headers(operation, static_headers);
+    if let Some(cookies) = cookie_header(operation) {
+        headers.push(("Cookie".to_string(), cookies));
+    }
+
+    // `fetch` sets the content type of form data and URL-encoded bodies itself
+    let mut setup = Vec::new();
+    let body = match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+        Some((body, BodyEncoding::Multipart)) => {
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Content-Type"));
+            setup.push("const form = new FormData();".to_string());
+            for (name, value) in form_fields(body) {
+                setup.push(match value {
+                    Some(value) => format!("form.append({}, {});", js_str(&name), js_str(&value)),
+                    None => format!(
+                        "form.append({}, new Blob([\"test file content\"], {{ type: \"text/plain\" }}), \"upload.txt\");",
+                        js_str(&name)
+                    ),
+                });
+            }
+            Some("form".to_string())
+        },
+        Some((body, BodyEncoding::Form)) => {
+            let fields: Vec<String> = form_fields(body)
+                .into_iter()
+                .map(|(name, value)| format!("      {}: {},", js_str(&name), js_str(&value.unwrap_or_default())))
+                .collect();
+            Some(format!("new URLSearchParams({{\n{}\n    }})", fields.join("\n")))
+        },
+        Some((body, BodyEncoding::Raw)) => Some(js_str(&raw_body_example(body))),
+        Some((_, BodyEncoding::Json)) => {
+            if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("Content-Type")) {
+                headers.push(("Content-Type".to_string(), "application/json".to_string()));
+            }
+            let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+            Some(format!("JSON.stringify({})", to_json_literal(&example, "    ")))
+        },
+        None => None,
+    };
+
+    let mut init = vec![format!("    method: {},", js_str(&method))];
+    if !headers.is_empty() {
+        let headers: Vec<String> = headers
+            .iter()
+            .map(|(name, value)| format!("      {}: {},", js_str(name), js_str(value)))
+            .collect();
+        init.push(format!("    headers: {{\n{}\n    }},", headers.join("\n")));
+    }
+    if let Some(body) = body {
+        init.push(format!("    body: {},", body));
+    }
+
+    let url = format!("{}{}", example_path(path, operation), query_string(operation));
+    let mut lines: Vec<String> = setup.into_iter().map(|line| format!("  {}", line)).collect();
+    lines.push(format!(
+        "  const response = await fetch(`${{BASE_URL}}{}`, {{\n{}\n  }});",
+        url.replace('`', "\\`").replace("${", "\\${"),
+        init.join("\n")
+    ));
+    lines
+}
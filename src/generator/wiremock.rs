@@ -0,0 +1,126 @@
+// This file contains the WireMock stub generator, writing a stub mapping for every operation that answers with an example response.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use reqwest::Url;
+use serde_json::{json, Map, Value};
+use crate::parser::{ApiOperation, SwaggerSpec};
+use super::body::to_plain_text;
+use super::data::{example_from_schema, response_example};
+use super::layout::operation_groups;
+use super::test_framework::{expected_status, GeneratorOptions, Result, TestGenerator};
+
+// WireMock stub mapping generator
+pub struct WiremockGenerator {
+    options: GeneratorOptions,
+}
+
+impl WiremockGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        WiremockGenerator { options }
+    }
+
+    /// Generate the stub mapping of an operation, served under `base_path`
+    fn generate_mapping(&self, operation: &ApiOperation, path: &str, base_path: &str) -> Value {
+        let status = expected_status(operation);
+        let response = operation.responses.iter().find(|resp| resp.status_code == status);
+
+        // Paths with parameters are matched by pattern, and rank below the literal paths they could overlap
+        let mut mapping = Map::new();
+        mapping.insert("name".to_string(), json!(operation.operation_id));
+        let mut request = Map::new();
+        request.insert("method".to_string(), json!(operation.method.to_uppercase()));
+        if operation.path_params.is_empty() {
+            request.insert("urlPath".to_string(), json!(format!("{}{}", base_path, path)));
+        } else {
+            request.insert("urlPathPattern".to_string(), json!(format!("{}{}", regex_syntax::escape(base_path), path_pattern(path))));
+            mapping.insert("priority".to_string(), json!(1 + operation.path_params.len()));
+        }
+        mapping.insert("request".to_string(), Value::Object(request));
+
+        // The response has the documented headers, and an example of the documented body
+        let mut headers = Map::new();
+        for header in response.map(|resp| resp.headers.as_slice()).unwrap_or_default() {
+            let value = header.schema.as_ref().map(example_from_schema).map(|value| to_plain_text(&value));
+            headers.insert(header.name.clone(), json!(value.unwrap_or_else(|| "string".to_string())));
+        }
+        let mut stub_response = Map::new();
+        stub_response.insert("status".to_string(), json!(status.parse::<u16>().unwrap_or(200)));
+        if let Some(schema) = response.and_then(|resp| resp.schema.as_ref()) {
+            headers.insert("Content-Type".to_string(), json!("application/json"));
+            stub_response.insert("jsonBody".to_string(), response_example(schema));
+        }
+        if !headers.is_empty() {
+            stub_response.insert("headers".to_string(), Value::Object(headers));
+        }
+        mapping.insert("response".to_string(), Value::Object(stub_response));
+
+        if !operation.tags.is_empty() || operation.deprecated {
+            mapping.insert("metadata".to_string(), json!({ "tags": operation.tags, "deprecated": operation.deprecated }));
+        }
+
+        Value::Object(mapping)
+    }
+}
+
+impl TestGenerator for WiremockGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        let mappings_dir = output_dir.join("mappings");
+        fs::create_dir_all(&mappings_dir)?;
+
+        // The stubs are served under the path of the base URL, as the API's clients call it
+        let base_path = Url::parse(base_url)
+            .map(|url| url.path().trim_end_matches('/').to_string())
+            .unwrap_or_default();
+
+        // One stub per operation, rather than per request body the tests send
+        let operations = operation_groups(spec, &self.options).into_iter().flat_map(|group| group.operations);
+        for (path, operation) in operations {
+            let mapping = self.generate_mapping(operation, path, &base_path);
+            fs::write(
+                mappings_dir.join(format!("{}.json", operation.operation_id)),
+                serde_json::to_string_pretty(&mapping).unwrap_or_default() + "\n",
+            )?;
+        }
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Mock
+
+Generated WireMock stubs for {title} (version {version}), answering every operation with an example of its documented response.
+
+## Running the mock
+
+Start WireMock with the stubs in `mappings/`, here with Docker:
+
+```
+docker run --rm -p 8080:8080 -v "$PWD/mappings:/home/wiremock/mappings" wiremock/wiremock:3.5.4
+```
+
+The mock then serves the API at `http://localhost:8080{base_path}`.
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// Regular expression matching the paths of a path template, with each parameter matching a single segment
+fn path_pattern(path: &str) -> String {
+    let mut pattern = String::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        pattern.push_str(&regex_syntax::escape(&rest[..start]));
+        pattern.push_str("[^/]+");
+        rest = &rest[start + end + 1..];
+    }
+    pattern.push_str(&regex_syntax::escape(rest));
+    pattern
+}
@@ -0,0 +1,430 @@
+// This file contains the C# test generator, writing a .NET project of async xUnit tests that send their requests
+// with a shared `HttpClient` and deserialize response bodies into generated models with System.Text.Json.
+
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+use crate::parser::{ApiOperation, PrimitiveType, Schema, SchemaVariant, SwaggerSpec};
+use super::body::{
+    body_encoding, cookie_header, example_path, form_fields, query_string, raw_body_example, request_body_example, request_headers,
+    variant_suffix, BodyEncoding,
+};
+use super::layout::{operation_groups, OutputLayout};
+use super::test_framework::{
+    expected_headers, expected_status, generate_in_parallel, pascal_case, response_schema, xml_escape, GeneratorOptions,
+    Result, TestGenerator,
+};
+
+// C# xUnit + HttpClient test generator
+pub struct XunitGenerator {
+    options: GeneratorOptions,
+}
+
+impl XunitGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        XunitGenerator { options }
+    }
+
+    /// Generate the test method for one request of an operation, with its body taken from `variant` when given
+    fn generate_operation_test(&self, operation: &ApiOperation, path: &str, variant: Option<&SchemaVariant>) -> String {
+        let method = operation.method.to_uppercase();
+        let display_name = match (&operation.summary, variant) {
+            (Some(summary), Some(variant)) => format!("{} ({})", summary, variant.name),
+            (Some(summary), None) => summary.clone(),
+            (None, Some(variant)) => format!("{} {} ({})", method, path, variant.name),
+            (None, None) => format!("{} {}", method, path),
+        };
+
+        // Deprecated operations are still generated, but skipped
+        let skip = if operation.deprecated { ", Skip = \"deprecated operation\"" } else { "" };
+
+        // Paths are relative to the client's base address, which may have a path of its own
+        let target = format!("{}{}", example_path(path, operation), query_string(operation));
+        let mut lines = vec![format!(
+            "using var request = new HttpRequestMessage(new HttpMethod({}), {});",
+            cs_string(&method),
+            cs_string(target.trim_start_matches('/'))
+        )];
+
+        // The body's media type is set on its content, so it isn't sent twice
+        let mut content_type = None;
+        let mut headers = request_headers(operation, &self.options.headers);
+        if let Some(cookies) = cookie_header(operation) {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+        for (name, value) in headers {
+            if name.eq_ignore_ascii_case("Content-Type") {
+                content_type = Some(value);
+            } else {
+                lines.push(format!("request.Headers.TryAddWithoutValidation({}, {});", cs_string(&name), cs_string(&value)));
+            }
+        }
+
+        match operation.body_param.as_ref().map(|body| (body, body_encoding(body))) {
+            Some((body, BodyEncoding::Multipart)) => {
+                lines.push("var content = new MultipartFormDataContent();".to_string());
+                for (name, value) in form_fields(body) {
+                    lines.push(match value {
+                        Some(value) => format!("content.Add(new StringContent({}), {});", cs_string(&value), cs_string(&name)),
+                        None => format!(
+                            "content.Add(new ByteArrayContent(Encoding.UTF8.GetBytes(\"test file content\")), {}, \"upload.txt\");",
+                            cs_string(&name)
+                        ),
+                    });
+                }
+                lines.push("request.Content = content;".to_string());
+            },
+            Some((body, BodyEncoding::Form)) => {
+                let fields: Vec<String> = form_fields(body)
+                    .into_iter()
+                    .map(|(name, value)| format!("            [{}] = {},", cs_string(&name), cs_string(&value.unwrap_or_default())))
+                    .collect();
+                lines.push(format!(
+                    "request.Content = new FormUrlEncodedContent(new Dictionary<string, string>\n        {{\n{}\n        }});",
+                    fields.join("\n")
+                ));
+            },
+            Some((body, BodyEncoding::Raw)) => {
+                lines.push(format!(
+                    "request.Content = new StringContent({}, Encoding.UTF8, {});",
+                    cs_string(&raw_body_example(body)),
+                    cs_string(content_type.as_deref().unwrap_or("text/plain"))
+                ));
+            },
+            Some((_, BodyEncoding::Json)) => {
+                let example = request_body_example(operation, variant).unwrap_or(Value::Null);
+                lines.push(format!(
+                    "request.Content = new StringContent({}, Encoding.UTF8, {});",
+                    cs_string(&serde_json::to_string(&example).unwrap_or_default()),
+                    cs_string(content_type.as_deref().unwrap_or("application/json"))
+                ));
+            },
+            None => {},
+        }
+
+        lines.push(String::new());
+        lines.push("using var response = await _client.SendAsync(request);".to_string());
+        lines.push(String::new());
+
+        // Assertions on the response
+        lines.push(format!("Assert.Equal({}, (int)response.StatusCode);", expected_status(operation)));
+        for header in expected_headers(operation) {
+            lines.push(format!(
+                "Assert.True(ApiFixture.HasHeader(response, {}), {});",
+                cs_string(header),
+                cs_string(&format!("response header {} is missing", header))
+            ));
+        }
+
+        // The body has to deserialize into the model of the response
+        if let Some(schema) = response_schema(operation) {
+            let model = cs_type(schema, &response_model_name(operation));
+            lines.push(format!("var body = await response.Content.ReadFromJsonAsync<{}>();", model.name));
+            if model.reference && !schema.info().is_some_and(|info| info.nullable) {
+                lines.push("Assert.NotNull(body);".to_string());
+            }
+        }
+
+        let body = lines
+            .iter()
+            .map(|line| if line.is_empty() { String::new() } else { format!("        {}", line) })
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            r#"    [Fact(DisplayName = {display_name}{skip})]
+    public async Task {name}()
+    {{
+{body}
+    }}"#,
+            display_name = cs_string(&display_name),
+            name = test_method_name(operation, variant),
+        )
+    }
+}
+
+impl TestGenerator for XunitGenerator {
+    fn generate_tests(&self, spec: &SwaggerSpec, output_dir: &Path, base_url: &str) -> Result<()> {
+        // Create the output directory if it doesn't exist
+        fs::create_dir_all(output_dir)?;
+
+        // One test class per tag, unless another layout is chosen
+        let options = self.options.or_layout(OutputLayout::PerTag);
+        let groups = operation_groups(spec, &options);
+        let operations: Vec<_> = groups.iter().map(|group| group.operations.clone()).collect();
+        let tests = generate_in_parallel(&operations, &options, |path, operation, variant| {
+            self.generate_operation_test(operation, path, variant)
+        })?;
+
+        let mut models = Vec::new();
+        for (group, tests) in groups.iter().zip(tests) {
+            let class_name = format!("{}Tests", pascal_case(&group.file_stem));
+            let mut file = File::create(output_dir.join(format!("{}.cs", class_name)))?;
+
+            writeln!(file, r#"using System.Net.Http.Json;
+using System.Text;
+using Xunit;
+
+namespace ApiTests;
+
+/// <summary>
+/// Tests of {description}
+/// </summary>
+public class {class_name} : IClassFixture<ApiFixture>
+{{
+    private readonly HttpClient _client;
+
+    public {class_name}(ApiFixture fixture)
+    {{
+        _client = fixture.Client;
+    }}
+
+{tests}
+}}"#,
+                description = xml_escape(&group.description),
+                tests = tests.join("\n\n"))?;
+
+            // The models response bodies deserialize into
+            for (_, operation) in &group.operations {
+                if let Some(schema) = response_schema(operation) {
+                    let name = response_model_name(operation);
+                    let description = format!("Body of the {} response", operation.operation_id);
+                    collect_models(schema, &name, &description, &mut models);
+                }
+            }
+        }
+
+        let mut models_file = File::create(output_dir.join("Models.cs"))?;
+
+        writeln!(models_file, r#"using System.Text.Json;
+using System.Text.Json.Serialization;
+
+namespace ApiTests;
+{}"#,
+            models.iter().map(|model| format!("\n{}\n", model)).collect::<String>().trim_end())?;
+
+        // The fixture sharing the HTTP client between the tests
+        let mut fixture = File::create(output_dir.join("ApiFixture.cs"))?;
+
+        writeln!(fixture, r#"namespace ApiTests;
+
+/// <summary>
+/// The HTTP client the tests share, pointed at the API under test. The API_BASE_URL environment variable overrides its address.
+/// </summary>
+public sealed class ApiFixture : IDisposable
+{{
+    public HttpClient Client {{ get; }}
+
+    public ApiFixture()
+    {{
+        var baseUrl = Environment.GetEnvironmentVariable("API_BASE_URL");
+        if (string.IsNullOrEmpty(baseUrl))
+        {{
+            baseUrl = {base_url};
+        }}
+
+        // Cookies are sent in the headers the tests set, rather than from a cookie container
+        var handler = new HttpClientHandler {{ UseCookies = false }};
+        Client = new HttpClient(handler) {{ BaseAddress = new Uri(baseUrl.TrimEnd('/') + "/") }};
+    }}
+
+    /// <summary>
+    /// Whether the response has a header, among either its own or its content's headers
+    /// </summary>
+    public static bool HasHeader(HttpResponseMessage response, string name) =>
+        response.Headers.Contains(name) || response.Content.Headers.Contains(name);
+
+    public void Dispose() => Client.Dispose();
+}}"#,
+            base_url = cs_string(base_url.trim_end_matches('/')))?;
+
+        // Create the project file
+        let project_name = pascal_case(&spec.info.package_name("_", "tests"));
+        let mut project_file = File::create(output_dir.join(format!("{}.csproj", project_name)))?;
+
+        writeln!(project_file, r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+    <ImplicitUsings>enable</ImplicitUsings>
+    <Nullable>enable</Nullable>
+    <RootNamespace>ApiTests</RootNamespace>
+    <Version>{version}</Version>
+    <IsPackable>false</IsPackable>
+    <IsTestProject>true</IsTestProject>
+  </PropertyGroup>
+
+  <ItemGroup>
+    <PackageReference Include="Microsoft.NET.Test.Sdk" Version="17.9.0" />
+    <PackageReference Include="xunit" Version="2.7.0" />
+    <PackageReference Include="xunit.runner.visualstudio" Version="2.5.7" />
+  </ItemGroup>
+
+</Project>"#,
+            version = xml_escape(spec.info.package_version("1.0.0")))?;
+
+        // Create a README.md file with instructions
+        let mut readme_file = File::create(output_dir.join("README.md"))?;
+
+        writeln!(readme_file, r#"# {title} Tests
+
+Generated API tests for {title} (version {version}), using xUnit and `HttpClient`.
+
+## Running the tests
+
+Run the tests against the API at `API_BASE_URL`:
+
+```
+API_BASE_URL=http://localhost:3000 dotnet test
+```
+
+Response bodies are deserialized into the models in `Models.cs`, which fails the test when a required property is missing.
+"#,
+            title = spec.info.title,
+            version = spec.info.version)?;
+
+        Ok(())
+    }
+}
+
+/// A C# type a schema's values deserialize into
+struct CsType {
+    /// The type's name as written in C#
+    name: String,
+
+    /// Whether it's a reference type, for which a missing body deserializes to `null`
+    reference: bool,
+}
+
+/// The C# type of a schema's values. Objects with properties become the model class `name`, whose declaration
+/// `collect_models` writes; arrays of them name their items `{name}Item`.
+fn cs_type(schema: &Schema, name: &str) -> CsType {
+    let reference = |name: String| CsType { name, reference: true };
+    let value = |name: &str| CsType { name: name.to_string(), reference: false };
+    match schema {
+        Schema::Object(object) if !object.properties.is_empty() => reference(name.to_string()),
+        Schema::Object(object) => {
+            let values = object.additional_properties.as_deref().map_or_else(
+                || "JsonElement".to_string(),
+                |values| nullable(&cs_type(values, &format!("{}Value", name)).name),
+            );
+            reference(format!("Dictionary<string, {}>", values))
+        },
+        Schema::Array(array) => {
+            let items = array.items.as_deref().map_or_else(
+                || "JsonElement".to_string(),
+                |items| nullable(&cs_type(items, &format!("{}Item", name)).name),
+            );
+            reference(format!("List<{}>", items))
+        },
+        Schema::Primitive(primitive) => match primitive.kind {
+            PrimitiveType::String => reference("string".to_string()),
+            PrimitiveType::Integer => value("long"),
+            PrimitiveType::Number => value("double"),
+            PrimitiveType::Boolean => value("bool"),
+        },
+        Schema::Ref(_) | Schema::Composite(_) | Schema::Any(_) => value("JsonElement"),
+    }
+}
+
+/// The nullable form of a C# type
+fn nullable(name: &str) -> String {
+    format!("{}?", name)
+}
+
+/// Collect the declarations of the model class `name` for an object schema, and of the classes its properties and items
+/// need, depth first. Properties are nullable so that optional ones may be left out, while `[JsonRequired]` makes
+/// deserialization fail when a required one is missing.
+fn collect_models(schema: &Schema, name: &str, description: &str, models: &mut Vec<String>) {
+    let object = match schema {
+        Schema::Object(object) if !object.properties.is_empty() => object,
+        Schema::Object(object) => {
+            if let Some(values) = object.additional_properties.as_deref() {
+                collect_models(values, &format!("{}Value", name), &format!("A value of {}", name), models);
+            }
+            return;
+        },
+        Schema::Array(array) => {
+            if let Some(items) = array.items.as_deref() {
+                collect_models(items, &format!("{}Item", name), &format!("An item of {}", name), models);
+            }
+            return;
+        },
+        _ => return,
+    };
+
+    let mut used = HashSet::new();
+    let mut properties = Vec::new();
+    let mut nested = Vec::new();
+    for (property, property_schema) in &object.properties {
+        if property_schema.info().is_some_and(|info| info.write_only) {
+            continue;
+        }
+
+        let identifier = member_name(property, name, &mut used);
+        let type_name = format!("{}{}", name, identifier);
+        let required = if object.required.contains(property) { "\n    [JsonRequired]" } else { "" };
+        properties.push(format!(
+            "    [JsonPropertyName({})]{}\n    public {} {} {{ get; set; }}",
+            cs_string(property),
+            required,
+            nullable(&cs_type(property_schema, &type_name).name),
+            identifier
+        ));
+        collect_models(property_schema, &type_name, &format!("The {} property of {}", property, name), &mut nested);
+    }
+
+    models.push(format!(
+        "/// <summary>\n/// {}\n/// </summary>\npublic class {}\n{{\n{}\n}}",
+        xml_escape(description),
+        name,
+        properties.join("\n\n")
+    ));
+    models.extend(nested);
+}
+
+/// The PascalCase C# identifier of a JSON property, unique within its class and different from the class's name
+fn member_name(property: &str, class_name: &str, used: &mut HashSet<String>) -> String {
+    let words: String = property.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    let mut identifier = pascal_case(&words);
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier = format!("_{}", identifier);
+    }
+    if identifier == class_name {
+        identifier.push_str("Value");
+    }
+
+    let mut unique = identifier.clone();
+    let mut counter = 2;
+    while !used.insert(unique.clone()) {
+        unique = format!("{}{}", identifier, counter);
+        counter += 1;
+    }
+    unique
+}
+
+/// Name of the model an operation's response body deserializes into
+fn response_model_name(operation: &ApiOperation) -> String {
+    format!("{}Response", upper_first(&operation.operation_id))
+}
+
+/// Name of the test method for one request of an operation
+fn test_method_name(operation: &ApiOperation, variant: Option<&SchemaVariant>) -> String {
+    upper_first(&format!("{}{}", operation.operation_id, variant_suffix(variant)))
+}
+
+/// Upper-case the first letter of an identifier, as C# names methods and types
+fn upper_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Render text as a C# string literal. JSON string escapes are valid in C#'s regular strings.
+fn cs_string(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
@@ -5,10 +5,12 @@ pub mod utils;
 
 // Re-export frequently used items for easier access
 pub use cli::args::TestFramework;
-pub use parser::{parse_swagger_file, SwaggerSpec};
-pub use generator::{create_generator, TestGenerator};
+pub use parser::{parse_swagger_file, parse_swagger_url, FetchOptions, ParseMode, ParserOptions, ParserWarning, ServerSelection, SwaggerSpec};
+pub use generator::{create_generator, create_generator_with_options, AuthConfig, AuthSource, GeneratorOptions, TestGenerator};
 
 use std::path::Path;
+use clap::ArgEnum;
+use generator::AssertLevel;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -25,21 +27,142 @@ pub enum AppError {
 
 pub type Result<T> = std::result::Result<T, AppError>;
 
-/// Generate tests from a Swagger/OpenAPI specification file
+/// Options controlling how tests are generated from a specification
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    /// Media type to send request bodies as, for operations that document it
+    pub content_type: Option<String>,
+    
+    /// Documented server to take the base URL from, instead of the first one
+    pub server: Option<ServerSelection>,
+    
+    /// Values for the variables of the server URL, overriding their defaults
+    pub server_variables: Vec<(String, String)>,
+    
+    /// Options controlling how the specification is parsed
+    pub parser: ParserOptions,
+    
+    /// Options passed on to the test generator
+    pub generator: GeneratorOptions,
+}
+
+/// Generate tests from a Swagger/OpenAPI specification file, returning the warnings it was parsed with and those about
+/// tests the framework doesn't generate
 pub fn generate_tests_from_spec<P: AsRef<Path>, Q: AsRef<Path>>(
     input_file: P,
     output_dir: Q,
     framework: TestFramework,
     base_url: &str,
-) -> Result<()> {
+    options: &GenerateOptions,
+) -> Result<Vec<ParserWarning>> {
     // Parse the Swagger/OpenAPI specification
-    let spec = parser::parse_swagger_file(input_file)?;
+    let spec = parser::parse_swagger_file_with_options(input_file, &options.parser)?;
+    
+    generate_tests(spec, output_dir.as_ref(), framework, base_url, options)
+}
+
+/// Generate tests from a Swagger/OpenAPI specification served at an HTTP(S) URL, returning the warnings it was parsed
+/// with and those about tests the framework doesn't generate
+pub fn generate_tests_from_url<Q: AsRef<Path>>(
+    url: &str,
+    fetch_options: &FetchOptions,
+    output_dir: Q,
+    framework: TestFramework,
+    base_url: &str,
+    options: &GenerateOptions,
+) -> Result<Vec<ParserWarning>> {
+    // Download and parse the Swagger/OpenAPI specification
+    let document = parser::fetch_document(url, fetch_options)?;
+    let spec = parser::parse_swagger_value_with_options(document, &options.parser)?;
+    
+    generate_tests(spec, output_dir.as_ref(), framework, base_url, options)
+}
+
+/// The frameworks whose generators (those of `generator::test_framework`) implement the options most others ignore, and
+/// generate lifecycle tests
+const FULL_FRAMEWORKS: &str = "reqwest, ureq, pytest, Jest and Postman";
+
+/// The frameworks whose tests add the base path of the specification's server to `--base-url`
+const BASE_PATH_FRAMEWORKS: &str = "reqwest and ureq";
+
+/// Whether the framework's generator is one of `FULL_FRAMEWORKS`
+fn is_full_framework(framework: TestFramework) -> bool {
+    use TestFramework::*;
+    matches!(framework, Reqwest | Ureq | Pytest | PytestAsync | Jest | Postman)
+}
+
+/// The framework's name on the command line, e.g. `rest-assured`
+fn framework_name(framework: TestFramework) -> String {
+    framework.to_possible_value().map_or_else(String::new, |value| value.get_name().to_string())
+}
+
+/// Warnings about the options given that the framework's tests ignore, e.g. `--assert-level` for Go tests, since most
+/// generators only implement part of them
+pub fn ignored_options(framework: TestFramework, options: &GenerateOptions) -> Vec<String> {
+    let full = is_full_framework(framework);
+    let base_path = matches!(framework, TestFramework::Reqwest | TestFramework::Ureq);
+    let ignored = [
+        ("--negative-tests", options.generator.negative_tests && !full, FULL_FRAMEWORKS),
+        ("--assert-level", options.generator.assert_level != AssertLevel::default() && !full, FULL_FRAMEWORKS),
+        ("--auth bearer", options.generator.auth.bearer_env.is_some() && !full, FULL_FRAMEWORKS),
+        ("--auth apikey", options.generator.auth.api_key_env.is_some() && !full, FULL_FRAMEWORKS),
+        ("--auth basic", options.generator.auth.basic_env.is_some() && !full, FULL_FRAMEWORKS),
+        ("--auth oauth2", options.generator.auth.client_credentials_env.is_some() && !full, FULL_FRAMEWORKS),
+        ("--server-index and --server-name", options.server.is_some() && !base_path, BASE_PATH_FRAMEWORKS),
+        ("--server-var", !options.server_variables.is_empty() && !base_path, BASE_PATH_FRAMEWORKS),
+    ];
+    
+    let name = framework_name(framework);
+    ignored.iter()
+        .filter(|(_, ignored, _)| *ignored)
+        .map(|(option, _, supported)| format!("{} tests ignore {}, which only {} tests support", name, option, supported))
+        .collect()
+}
+
+/// Warnings about the resources whose lifecycle the framework's tests don't cover, at their create operation
+fn missing_lifecycles(spec: &SwaggerSpec, framework: TestFramework, options: &GeneratorOptions) -> Vec<ParserWarning> {
+    if is_full_framework(framework) {
+        return Vec::new();
+    }
+    generator::lifecycle::lifecycles(spec, options)
+        .iter()
+        .map(|lifecycle| ParserWarning {
+            pointer: format!(
+                "/paths/{}/{}",
+                lifecycle.create.path.replace('~', "~0").replace('/', "~1"),
+                lifecycle.create.operation.method.to_lowercase()
+            ),
+            message: format!(
+                "{} tests have no lifecycle test of the resource this operation creates, which only {} tests generate",
+                framework_name(framework), FULL_FRAMEWORKS
+            ),
+        })
+        .collect()
+}
+
+fn generate_tests(
+    mut spec: SwaggerSpec,
+    output_dir: &Path,
+    framework: TestFramework,
+    base_url: &str,
+    options: &GenerateOptions,
+) -> Result<Vec<ParserWarning>> {
+    if let Some(server) = options.server.as_ref() {
+        spec.select_server(server)?;
+    }
+    spec.set_server_variables(&options.server_variables)?;
+    if let Some(content_type) = options.content_type.as_deref() {
+        spec.select_content_type(content_type);
+    }
     
     // Create the appropriate test generator
-    let generator = generator::create_generator(framework)?;
+    let generator = generator::create_generator_with_options(framework, &options.generator)?;
     
     // Generate tests
-    generator.generate_tests(&spec, output_dir.as_ref(), base_url)?;
+    generator.generate_tests(&spec, output_dir, base_url)?;
     
-    Ok(())
-}
\ No newline at end of file
+    let missing = missing_lifecycles(&spec, framework, &options.generator);
+    let mut warnings = spec.warnings;
+    warnings.extend(missing);
+    Ok(warnings)
+}
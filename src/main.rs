@@ -1,30 +1,191 @@
 // This is the entry point for the CLI application.
 // It parses command-line arguments and delegates to the appropriate module for further processing.
 
+use std::fs;
+use std::path::Path;
 use std::process;
+use std::time::Duration;
 use clap::Parser;
-use cli::Args;
-use swagger_test_generator::{generate_tests_from_spec, TestFramework};
-
-mod cli;
+use swagger_test_generator::cli::{Args, BundleArgs, Command, DiffArgs, ValidateArgs};
+use swagger_test_generator::generator::layout::OutputLayout;
+use swagger_test_generator::parser::{
+    bundle_file, diff_specs, fetch_document, is_url, parse_swagger_file, read_document, validate_document, write_document, Severity,
+    SpecFormat,
+};
+use swagger_test_generator::{
+    generate_tests_from_spec, generate_tests_from_url, ignored_options, FetchOptions, GenerateOptions, GeneratorOptions, ParseMode, ParserOptions,
+    ServerSelection,
+};
 
 fn main() {
     // Parse command line arguments
     let args = Args::parse();
 
-    // Map the framework argument to the correct TestFramework variant
-    let framework = match args.framework {
-        cli::args::TestFramework::Reqwest => TestFramework::Reqwest,
-        cli::args::TestFramework::Pytest => TestFramework::Pytest,
-        cli::args::TestFramework::Jest => TestFramework::Jest,
-        cli::args::TestFramework::Postman => TestFramework::Postman,
+    match args.command {
+        Some(Command::Validate(ref validate_args)) => validate(validate_args),
+        Some(Command::Bundle(ref bundle_args)) => bundle(bundle_args),
+        Some(Command::Diff(ref diff_args)) => diff(diff_args),
+        None => generate(&args),
+    }
+}
+
+/// Generate tests from the Swagger/OpenAPI specification, downloading it first if given a URL
+fn generate(args: &Args) {
+    // clap enforces these when no subcommand is given
+    let (input, output_dir, framework) = match (&args.input, &args.output_dir, args.framework) {
+        (Some(input), Some(output_dir), Some(framework)) => (input, output_dir, framework),
+        _ => unreachable!("required arguments are checked by clap"),
+    };
+
+    let options = GenerateOptions {
+        content_type: args.content_type.clone(),
+        server: match (args.server_index, &args.server_name) {
+            (Some(index), _) => Some(ServerSelection::Index(index)),
+            (None, Some(name)) => Some(ServerSelection::Name(name.clone())),
+            (None, None) => None,
+        },
+        server_variables: args.server_var.clone(),
+        parser: ParserOptions {
+            mode: if args.lenient { ParseMode::Lenient } else { ParseMode::Strict },
+            ..ParserOptions::default()
+        },
+        generator: GeneratorOptions {
+            layout: args.layout.or(args.group_by_tag.then_some(OutputLayout::PerTag)),
+            skip_deprecated: args.skip_deprecated,
+            jobs: args.jobs,
+            virtual_users: args.vus,
+            duration: args.duration,
+            negative_tests: args.negative_tests,
+            boundary_tests: args.boundary_tests,
+            unauthorized_tests: args.unauthorized_tests,
+            pagination_tests: args.pagination_tests,
+            auth: args.auth.iter().cloned().collect(),
+            seed: args.seed,
+            fuzz: args.fuzz,
+            status_coverage: args.status_coverage,
+            max_response_time_ms: args.max_response_time_ms,
+            optional_query_params: args.optional_query_params,
+            include_tags: args.include_tags.clone(),
+            exclude_paths: args.exclude_paths.clone(),
+            methods: args.methods.clone(),
+            assert_level: args.assert_level,
+            retries: args.retries,
+            retry_backoff_ms: args.retry_backoff_ms,
+            timeout_secs: args.timeout_secs,
+            headers: args.header.clone(),
+            environments: args.environments.clone(),
+            data_sets: args.data_file.clone().unwrap_or_default(),
+        },
+    };
+
+    for ignored in ignored_options(framework, &options) {
+        eprintln!("warning: {}", ignored);
+    }
+
+    let result = if is_url(input) {
+        let fetch_options = FetchOptions {
+            timeout: Duration::from_secs(args.fetch_timeout),
+            headers: args.input_header.clone(),
+        };
+        generate_tests_from_url(input, &fetch_options, output_dir, framework, &args.base_url, &options)
+    } else {
+        generate_tests_from_spec(input, output_dir, framework, &args.base_url, &options)
+    };
+
+    let warnings = match result {
+        Ok(warnings) => warnings,
+        Err(err) => {
+            eprintln!("Error generating tests: {}", err);
+            process::exit(1);
+        },
+    };
+
+    for warning in &warnings {
+        eprintln!("warning: {}", warning);
+    }
+    println!("Tests generated successfully in {}", output_dir.display());
+}
+
+/// Print the problems found in the specification, exiting with an error if any of them is an error
+fn validate(args: &ValidateArgs) {
+    let document = if is_url(&args.input) {
+        let fetch_options = FetchOptions {
+            timeout: Duration::from_secs(args.fetch_timeout),
+            headers: args.input_header.clone(),
+        };
+        fetch_document(&args.input, &fetch_options)
+    } else {
+        read_document(&args.input)
+    };
+
+    let document = match document {
+        Ok(document) => document,
+        Err(err) => {
+            eprintln!("Error reading specification: {}", err);
+            process::exit(1);
+        },
     };
 
-    // Generate tests from the Swagger/OpenAPI specification
-    if let Err(err) = generate_tests_from_spec(&args.input, &args.output_dir, framework, &args.base_url) {
-        eprintln!("Error generating tests: {}", err);
+    let findings = validate_document(&document);
+    for finding in &findings {
+        println!("{}", finding);
+    }
+
+    let errors = findings.iter().filter(|f| f.severity == Severity::Error).count();
+    println!("{} error(s), {} warning(s)", errors, findings.len() - errors);
+
+    if errors > 0 {
         process::exit(1);
     }
+}
+
+/// Write the specification with its external references inlined, to a file or standard output
+fn bundle(args: &BundleArgs) {
+    let format = args.format
+        .or_else(|| args.output.as_deref().and_then(SpecFormat::from_path))
+        .or_else(|| SpecFormat::from_path(&args.input))
+        .unwrap_or(SpecFormat::Yaml);
+
+    let bundled = match bundle_file(&args.input).and_then(|document| write_document(&document, format)) {
+        Ok(bundled) => bundled,
+        Err(err) => {
+            eprintln!("Error bundling specification: {}", err);
+            process::exit(1);
+        },
+    };
+
+    match &args.output {
+        Some(output) => {
+            if let Err(err) = fs::write(output, bundled) {
+                eprintln!("Error writing {}: {}", output.display(), err);
+                process::exit(1);
+            }
+            eprintln!("Bundled specification written to {}", output.display());
+        },
+        None => print!("{}", bundled),
+    }
+}
+
+/// Print the changes between two versions of a specification, exiting with an error if any of them is breaking
+fn diff(args: &DiffArgs) {
+    let parse = |path: &Path| match parse_swagger_file(path) {
+        Ok(spec) => spec,
+        Err(err) => {
+            eprintln!("Error reading {}: {}", path.display(), err);
+            process::exit(1);
+        },
+    };
+    let (old, new) = (parse(&args.old), parse(&args.new));
+
+    let changes = diff_specs(&old, &new);
+    for change in changes.iter().filter(|c| c.breaking || !args.breaking_only) {
+        println!("{}", change);
+    }
+
+    let breaking = changes.iter().filter(|c| c.breaking).count();
+    println!("{} change(s), {} breaking", changes.len(), breaking);
 
-    println!("Tests generated successfully in {}", args.output_dir.display());
-}
\ No newline at end of file
+    if breaking > 0 {
+        process::exit(1);
+    }
+}
@@ -1,17 +1,58 @@
 // This file contains the implementation of the parser module, responsible for reading and interpreting the Swagger document.
 
 pub mod swagger;
+pub mod resolver;
+pub mod fetch;
+pub mod schema;
+pub mod security;
+pub mod server;
+pub mod links;
+pub mod pagination;
+pub mod validate;
+pub mod bundle;
+pub mod postman;
+pub mod har;
+pub mod diff;
+pub mod info;
 
 pub use swagger::{
     parse_swagger_file,
+    parse_swagger_file_with_options,
+    parse_swagger_string,
+    parse_swagger_value,
+    parse_swagger_value_with_options,
+    read_document,
+    ParserOptions,
+    ParseMode,
+    ParserWarning,
+    SpecFormat,
     SwaggerSpec,
     ApiPath,
+    ApiWebhook,
+    ApiCallback,
     ApiOperation,
     ApiParameter,
     ApiResponse,
+    ResponseHeader,
     ParserError,
     Result,
 };
+pub use resolver::RefResolver;
+pub use fetch::{parse_swagger_url, fetch_document, is_url, FetchOptions};
+pub use schema::{
+    ArraySchema, CompositeKind, CompositeSchema, Constraints, ObjectSchema, PrimitiveSchema, PrimitiveType, Schema, SchemaInfo,
+    SchemaVariant,
+};
+pub use security::{SecurityScheme, SecuritySchemeKind, SecurityRequirement, OAuthFlow};
+pub use server::{ApiServer, ServerSelection, ServerVariable};
+pub use links::{ApiLink, LinkValue};
+pub use pagination::Pagination;
+pub use validate::{validate_document, Finding, Severity};
+pub use bundle::{bundle_file, write_document};
+pub use postman::{is_postman_collection, parse_postman_collection};
+pub use har::{is_har, parse_har};
+pub use diff::{diff_specs, Change, ChangeKind};
+pub use info::{ApiContact, ApiInfo, ApiLicense};
 
 use std::fs::File;
 use std::io::{self, Read};
@@ -0,0 +1,24 @@
+// This file contains the bundling of a multi-file Swagger document into a single self-contained one, for the `bundle` command.
+
+use std::path::Path;
+use serde_json::Value;
+use super::resolver::RefResolver;
+use super::swagger::{read_document, Result, SpecFormat};
+
+/// Read a specification and inline every reference into another file, relative to the spec's directory.
+/// References within the specification itself are kept as they are.
+pub fn bundle_file<P: AsRef<Path>>(path: P) -> Result<Value> {
+    let path = path.as_ref();
+    let document = read_document(path)?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    RefResolver::bundler(&document, base_dir).bundle()
+}
+
+/// Serialize a document as pretty-printed JSON or as YAML
+pub fn write_document(document: &Value, format: SpecFormat) -> Result<String> {
+    match format {
+        SpecFormat::Json => Ok(serde_json::to_string_pretty(document)? + "\n"),
+        SpecFormat::Yaml => Ok(serde_yaml::to_string(document)?),
+    }
+}
@@ -0,0 +1,279 @@
+// This file contains the comparison of two versions of a specification run by the `diff` command.
+
+use serde_json::Value;
+use std::fmt;
+use super::schema::Schema;
+use super::swagger::{ApiOperation, ApiParameter, SwaggerSpec};
+
+/// What happened to the part of the specification a change is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// It only exists in the new version
+    Added,
+
+    /// It only exists in the old version
+    Removed,
+
+    /// It exists in both versions, but differs
+    Changed,
+}
+
+/// A difference between two versions of a specification
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// What happened
+    pub kind: ChangeKind,
+
+    /// Whether clients written against the old version can break, e.g. an operation or a response property was removed
+    pub breaking: bool,
+
+    /// The operation the change is in, e.g. `GET /users/{id}`
+    pub operation: String,
+
+    /// Description of the change
+    pub message: String,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Changed => "changed",
+        };
+        let breaking = if self.breaking { " (breaking)" } else { "" };
+        write!(f, "{}{}: {}: {}", kind, breaking, self.operation, self.message)
+    }
+}
+
+/// Which way a schema is sent, which decides whether narrowing or widening it is breaking
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    /// Sent by clients: accepting less breaks them
+    Request,
+
+    /// Sent by the server: returning less (or something new) breaks them
+    Response,
+}
+
+/// Compare the operations of two versions of a specification: operations added and removed, and for operations
+/// in both their parameters, request bodies and responses, down to the properties of their schemas.
+/// Operations are matched by method and path.
+pub fn diff_specs(old: &SwaggerSpec, new: &SwaggerSpec) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    for (path, operation) in operations(old) {
+        let location = format!("{} {}", operation.method, path);
+        match find_operation(new, path, &operation.method) {
+            Some(new_operation) => diff_operation(&location, operation, new_operation, &mut changes),
+            None => changes.push(change(ChangeKind::Removed, true, &location, "operation removed".to_string())),
+        }
+    }
+    for (path, operation) in operations(new) {
+        if find_operation(old, path, &operation.method).is_none() {
+            let location = format!("{} {}", operation.method, path);
+            changes.push(change(ChangeKind::Added, false, &location, "operation added".to_string()));
+        }
+    }
+
+    changes
+}
+
+fn operations(spec: &SwaggerSpec) -> impl Iterator<Item = (&str, &ApiOperation)> {
+    spec.paths
+        .iter()
+        .flat_map(|api_path| api_path.operations.iter().map(move |operation| (api_path.path.as_str(), operation)))
+}
+
+fn find_operation<'a>(spec: &'a SwaggerSpec, path: &str, method: &str) -> Option<&'a ApiOperation> {
+    operations(spec).find(|(p, operation)| *p == path && operation.method == method).map(|(_, operation)| operation)
+}
+
+fn diff_operation(location: &str, old: &ApiOperation, new: &ApiOperation, changes: &mut Vec<Change>) {
+    let parameters = |operation: &'_ ApiOperation| -> Vec<ApiParameter> {
+        operation.path_params
+            .iter()
+            .chain(&operation.query_params)
+            .chain(&operation.header_params)
+            .chain(&operation.cookie_params)
+            .chain(&operation.form_params)
+            .cloned()
+            .collect()
+    };
+    let (old_params, new_params) = (parameters(old), parameters(new));
+    let find = |params: &'_ [ApiParameter], param: &ApiParameter| -> Option<ApiParameter> {
+        params.iter().find(|p| p.location == param.location && p.name == param.name).cloned()
+    };
+
+    for param in &old_params {
+        let name = format!("{} parameter `{}`", param.location, param.name);
+        let Some(new_param) = find(&new_params, param) else {
+            changes.push(change(ChangeKind::Removed, false, location, format!("{} removed", name)));
+            continue;
+        };
+
+        if !param.required && new_param.required {
+            changes.push(change(ChangeKind::Changed, true, location, format!("{} became required", name)));
+        } else if param.required && !new_param.required {
+            changes.push(change(ChangeKind::Changed, false, location, format!("{} became optional", name)));
+        }
+        match (&param.schema, &new_param.schema) {
+            (Some(old_schema), Some(new_schema)) => diff_schema(location, &name, old_schema, new_schema, Direction::Request, 0, changes),
+            _ if param.param_type != new_param.param_type => {
+                let message = format!("{} type changed from {} to {}", name, param.param_type, new_param.param_type);
+                changes.push(change(ChangeKind::Changed, true, location, message));
+            },
+            _ => diff_enum(location, &name, &param.enum_values, &new_param.enum_values, Direction::Request, changes),
+        }
+    }
+    for param in &new_params {
+        if find(&old_params, param).is_none() {
+            let required = if param.required { "required" } else { "optional" };
+            let message = format!("{} {} parameter `{}` added", required, param.location, param.name);
+            changes.push(change(ChangeKind::Added, param.required, location, message));
+        }
+    }
+
+    match (&old.body_param, &new.body_param) {
+        (None, Some(body)) => {
+            let required = if body.required { "required" } else { "optional" };
+            changes.push(change(ChangeKind::Added, body.required, location, format!("{} request body added", required)));
+        },
+        (Some(_), None) => changes.push(change(ChangeKind::Removed, false, location, "request body removed".to_string())),
+        (Some(old_body), Some(new_body)) => {
+            if !old_body.required && new_body.required {
+                changes.push(change(ChangeKind::Changed, true, location, "request body became required".to_string()));
+            }
+            for media_type in old_body.content.keys().filter(|m| !new_body.content.contains_key(*m)) {
+                let message = format!("request body media type {} removed", media_type);
+                changes.push(change(ChangeKind::Removed, true, location, message));
+            }
+            if let (Some(old_schema), Some(new_schema)) = (&old_body.schema, &new_body.schema) {
+                diff_schema(location, "request body", old_schema, new_schema, Direction::Request, 0, changes);
+            }
+        },
+        (None, None) => {},
+    }
+
+    // Clients handle the documented statuses, so a success going away breaks them while other statuses may come and go
+    for response in &old.responses {
+        let name = format!("response {}", response.status_code);
+        let Some(new_response) = new.responses.iter().find(|r| r.status_code == response.status_code) else {
+            let breaking = response.status_code.starts_with('2');
+            changes.push(change(ChangeKind::Removed, breaking, location, format!("{} removed", name)));
+            continue;
+        };
+        match (&response.schema, &new_response.schema) {
+            (Some(old_schema), Some(new_schema)) => {
+                diff_schema(location, &format!("{} body", name), old_schema, new_schema, Direction::Response, 0, changes);
+            },
+            (Some(_), None) => changes.push(change(ChangeKind::Removed, true, location, format!("{} body removed", name))),
+            _ => {},
+        }
+    }
+    for response in &new.responses {
+        if !old.responses.iter().any(|r| r.status_code == response.status_code) {
+            changes.push(change(ChangeKind::Added, false, location, format!("response {} added", response.status_code)));
+        }
+    }
+
+    if !old.requires_auth() && new.requires_auth() {
+        changes.push(change(ChangeKind::Changed, true, location, "authentication became required".to_string()));
+    }
+}
+
+/// Compare two schemas at `name`, descending into properties and items. `depth` stops the descent at
+/// recursive schemas.
+fn diff_schema(location: &str, name: &str, old: &Schema, new: &Schema, direction: Direction, depth: usize, changes: &mut Vec<Change>) {
+    if depth > 16 {
+        return;
+    }
+
+    if let (Some(old_type), Some(new_type)) = (old.type_name(), new.type_name()) {
+        if old_type != new_type {
+            // An integer is still a number, so only the server returning one where a number was sent is safe
+            let widened = old_type == "integer" && new_type == "number" && direction == Direction::Request;
+            let message = format!("{} type changed from {} to {}", name, old_type, new_type);
+            changes.push(change(ChangeKind::Changed, !widened, location, message));
+            return;
+        }
+    }
+
+    let (old_info, new_info) = (old.info(), new.info());
+    if let (Some(old_info), Some(new_info)) = (old_info, new_info) {
+        if old_info.nullable != new_info.nullable {
+            // A request accepting null or a response never returning it is safe, the reverse isn't
+            let breaking = (direction == Direction::Request) != new_info.nullable;
+            let message = if new_info.nullable { format!("{} became nullable", name) } else { format!("{} is no longer nullable", name) };
+            changes.push(change(ChangeKind::Changed, breaking, location, message));
+        }
+        diff_enum(location, name, &old_info.enum_values, &new_info.enum_values, direction, changes);
+    }
+
+    match (old, new) {
+        (Schema::Object(old_object), Schema::Object(new_object)) => {
+            for (property, old_property) in &old_object.properties {
+                let property_name = format!("{} property `{}`", name, property);
+                match new_object.properties.get(property) {
+                    Some(new_property) => {
+                        let newly_required = !old_object.required.contains(property) && new_object.required.contains(property);
+                        let no_longer_required = old_object.required.contains(property) && !new_object.required.contains(property);
+                        if newly_required {
+                            let breaking = direction == Direction::Request;
+                            changes.push(change(ChangeKind::Changed, breaking, location, format!("{} became required", property_name)));
+                        } else if no_longer_required {
+                            let breaking = direction == Direction::Response;
+                            changes.push(change(ChangeKind::Changed, breaking, location, format!("{} became optional", property_name)));
+                        }
+                        diff_schema(location, &property_name, old_property, new_property, direction, depth + 1, changes);
+                    },
+                    None => {
+                        // Clients can keep sending a property the server ignores, but not read one it no longer returns
+                        let breaking = direction == Direction::Response;
+                        changes.push(change(ChangeKind::Removed, breaking, location, format!("{} removed", property_name)));
+                    },
+                }
+            }
+            for property in new_object.properties.keys().filter(|p| !old_object.properties.contains_key(*p)) {
+                let required = new_object.required.contains(property);
+                let breaking = direction == Direction::Request && required;
+                let message = format!("{} {}property `{}` added", name, if required { "required " } else { "" }, property);
+                changes.push(change(ChangeKind::Added, breaking, location, message));
+            }
+        },
+        (Schema::Array(old_array), Schema::Array(new_array)) => {
+            if let (Some(old_items), Some(new_items)) = (&old_array.items, &new_array.items) {
+                diff_schema(location, &format!("{} items", name), old_items, new_items, direction, depth + 1, changes);
+            }
+        },
+        (Schema::Ref(old_reference), Schema::Ref(new_reference)) if old_reference != new_reference => {
+            let message = format!("{} changed from {} to {}", name, old_reference, new_reference);
+            changes.push(change(ChangeKind::Changed, true, location, message));
+        },
+        _ => {},
+    }
+}
+
+/// Compare the allowed values of an enum: clients break when a request value they send is no longer accepted,
+/// or when a response can hold a value they don't know
+fn diff_enum(location: &str, name: &str, old: &[Value], new: &[Value], direction: Direction, changes: &mut Vec<Change>) {
+    if old.is_empty() || new.is_empty() {
+        return;
+    }
+
+    let list = |values: Vec<&Value>| values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ");
+    let removed: Vec<&Value> = old.iter().filter(|v| !new.contains(v)).collect();
+    let added: Vec<&Value> = new.iter().filter(|v| !old.contains(v)).collect();
+    if !removed.is_empty() {
+        let message = format!("{} no longer allows {}", name, list(removed));
+        changes.push(change(ChangeKind::Changed, direction == Direction::Request, location, message));
+    }
+    if !added.is_empty() {
+        let message = format!("{} now also allows {}", name, list(added));
+        changes.push(change(ChangeKind::Changed, direction == Direction::Response, location, message));
+    }
+}
+
+fn change(kind: ChangeKind, breaking: bool, operation: &str, message: String) -> Change {
+    Change { kind, breaking, operation: operation.to_string(), message }
+}
@@ -0,0 +1,358 @@
+// This file contains the conversion of a HAR (HTTP Archive) capture into the parsed specification used by the generators.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use super::info::ApiInfo;
+use super::pagination::Pagination;
+use super::schema::Schema;
+use super::security::{SecurityRequirement, SecurityScheme, SecuritySchemeKind};
+use super::swagger::{
+    default_operation_id, example_body, example_parameter, form_body, ApiOperation, ApiParameter, ApiPath, ApiResponse, Result,
+    SwaggerSpec,
+};
+
+/// Whether a document is a HAR capture, as saved by browser developer tools and proxies
+pub fn is_har(document: &Value) -> bool {
+    document.pointer("/log/entries").is_some_and(Value::is_array)
+}
+
+/// Convert a HAR capture. Only calls to the origin that most entries go to are kept, leaving out static assets;
+/// identifier-like path segments become path parameters, and calls that then share a method and path are merged
+/// into one operation. Captured bodies and statuses become the examples and documented responses.
+pub fn parse_har(capture: Value) -> Result<SwaggerSpec> {
+    let entries: Vec<(&Value, String, String)> = capture
+        .pointer("/log/entries")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|entry| !is_static_asset(entry))
+        .filter_map(|entry| {
+            let (origin, rest) = split_url(&text(entry.get("request")?, "url")?)?;
+            Some((entry, origin, rest))
+        })
+        .collect();
+
+    // Third-party calls (analytics, CDNs) go to other origins than the API under test
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for (_, origin, _) in &entries {
+        match counts.iter_mut().find(|(seen, _)| seen == origin) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((origin, 1)),
+        }
+    }
+    let base_url = counts
+        .iter()
+        .fold(None, |best: Option<(&str, usize)>, &(origin, count)| match best {
+            Some((_, most)) if most >= count => best,
+            _ => Some((origin, count)),
+        })
+        .map(|(origin, _)| origin.to_string());
+
+    let mut converter = Converter { paths: Vec::new(), security_schemes: Vec::new() };
+    for (entry, origin, rest) in &entries {
+        if Some(origin) == base_url.as_ref() {
+            converter.convert_entry(entry, rest);
+        }
+    }
+
+    Ok(SwaggerSpec {
+        info: ApiInfo::default(),
+        base_url: base_url.unwrap_or_else(|| "http://localhost".to_string()),
+        server: None,
+        servers: Vec::new(),
+        paths: converter.paths,
+        webhooks: Vec::new(),
+        security: Vec::new(),
+        security_schemes: converter.security_schemes,
+        raw_spec: Arc::new(capture),
+        warnings: Vec::new(),
+    })
+}
+
+/// State gathered while converting the entries of a capture
+struct Converter {
+    /// Operations grouped by path template, in the order the paths were first seen
+    paths: Vec<ApiPath>,
+
+    /// Schemes for the kinds of `Authorization` headers sent
+    security_schemes: Vec<SecurityScheme>,
+}
+
+impl Converter {
+    /// Convert one entry, `rest` being its URL after the origin
+    fn convert_entry(&mut self, entry: &Value, rest: &str) {
+        let request = &entry["request"];
+        let method = text(request, "method").unwrap_or_else(|| "GET".to_string()).to_uppercase();
+        let path = rest.split(['?', '#']).next().unwrap_or_default();
+        let (path, path_params) = path_template(path);
+
+        let query_params: Vec<ApiParameter> = name_values(request.get("queryString"))
+            .map(|(name, value)| example_parameter(&name, "query", false, Some(&value)))
+            .collect();
+
+        // `Content-Type` and `Accept` describe the media types, and `Authorization` the security. Of the other headers
+        // only custom `X-` ones are kept, as the rest are added by the browser or the HTTP client.
+        let mut header_params: Vec<ApiParameter> = Vec::new();
+        let mut produces = Vec::new();
+        let mut security = Vec::new();
+        for (name, value) in name_values(request.get("headers")) {
+            if name.eq_ignore_ascii_case("Accept") {
+                produces.extend(value.split(',').map(media_type).filter(|m| !m.contains('*')));
+            } else if name.eq_ignore_ascii_case("Authorization") {
+                security = self.security(&value);
+            } else if name.to_ascii_lowercase().starts_with("x-") && !header_params.iter().any(|h| h.name == name) {
+                header_params.push(example_parameter(&name, "header", true, Some(&value)));
+            }
+        }
+
+        let body_param = request.get("postData").and_then(convert_body);
+        let consumes = body_param.iter().filter_map(|body| body.content_type.clone()).collect();
+
+        let response = convert_response(entry);
+        if let Some(media_type) = response.as_ref().and_then(|(_, media_type)| media_type.clone()) {
+            if !produces.contains(&media_type) {
+                produces.push(media_type);
+            }
+        }
+        let responses: Vec<ApiResponse> = response.map(|(response, _)| response).into_iter().collect();
+
+        let api_path = match self.paths.iter().position(|api_path| api_path.path == path) {
+            Some(index) => &mut self.paths[index],
+            None => {
+                self.paths.push(ApiPath { path: path.clone(), operations: Vec::new() });
+                self.paths.last_mut().unwrap()
+            },
+        };
+
+        // A repeated call adds the query parameters and statuses not seen before, the first call's examples are kept
+        if let Some(operation) = api_path.operations.iter_mut().find(|operation| operation.method == method) {
+            for param in query_params {
+                if !operation.query_params.iter().any(|q| q.name == param.name) {
+                    operation.query_params.push(param);
+                }
+            }
+            for response in responses {
+                if !operation.responses.iter().any(|r| r.status_code == response.status_code) {
+                    operation.responses.push(response);
+                }
+            }
+            return;
+        }
+
+        let pagination = Pagination::detect(&query_params, &BTreeMap::new());
+        api_path.operations.push(ApiOperation {
+            operation_id: default_operation_id(&method, &path),
+            declared_operation_id: None,
+            method,
+            summary: None,
+            description: None,
+            path_params,
+            query_params,
+            header_params,
+            cookie_params: Vec::new(),
+            form_params: Vec::new(),
+            body_param,
+            consumes,
+            produces,
+            responses,
+            security,
+            tags: Vec::new(),
+            deprecated: false,
+            callbacks: Vec::new(),
+            extensions: BTreeMap::new(),
+            pagination,
+        });
+    }
+
+    /// The security requirements for an `Authorization` header, registering its scheme. The captured credentials
+    /// themselves are left out.
+    fn security(&mut self, authorization: &str) -> Vec<SecurityRequirement> {
+        let scheme = authorization.split_whitespace().next().unwrap_or_default().to_ascii_lowercase();
+        let kind = match scheme.as_str() {
+            "bearer" => SecuritySchemeKind::Bearer { format: None },
+            "basic" => SecuritySchemeKind::Basic,
+            _ => return Vec::new(),
+        };
+
+        if !self.security_schemes.iter().any(|s| s.name == scheme) {
+            self.security_schemes.push(SecurityScheme { name: scheme.clone(), kind, description: None });
+        }
+        vec![SecurityRequirement { schemes: vec![(scheme, Vec::new())] }]
+    }
+}
+
+/// Convert captured post data: form fields when it lists `params`, otherwise its text
+fn convert_body(post_data: &Value) -> Option<ApiParameter> {
+    let mime_type = text(post_data, "mimeType").unwrap_or_default();
+    let media_type = match media_type(&mime_type) {
+        media_type if media_type.is_empty() => "application/json".to_string(),
+        media_type => media_type,
+    };
+
+    let params = post_data.get("params").and_then(Value::as_array).filter(|params| !params.is_empty());
+    if let Some(params) = params {
+        let fields: Vec<ApiParameter> = params
+            .iter()
+            .filter_map(|field| {
+                let name = text(field, "name")?;
+                let mut param = example_parameter(&name, "formData", false, text(field, "value").as_deref());
+                if field.get("fileName").is_some() {
+                    param.param_type = "file".to_string();
+                    param.example = None;
+                }
+                Some(param)
+            })
+            .collect();
+        return Some(form_body(&fields, &media_type));
+    }
+
+    let body = text(post_data, "text").filter(|body| !body.trim().is_empty())?;
+    if media_type == "application/x-www-form-urlencoded" {
+        let fields: Vec<ApiParameter> = body
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                example_parameter(name, "formData", false, Some(value))
+            })
+            .collect();
+        return Some(form_body(&fields, &media_type));
+    }
+    Some(example_body(&body, &media_type))
+}
+
+/// The documented response for the captured one, with the media type of its body. Aborted requests
+/// (status 0) have no response.
+fn convert_response(entry: &Value) -> Option<(ApiResponse, Option<String>)> {
+    let response = entry.get("response")?;
+    let status = response.get("status").and_then(Value::as_u64).filter(|status| *status > 0)?;
+
+    let content = response.get("content");
+    let mime_type = content.and_then(|c| text(c, "mimeType")).map(|m| media_type(&m)).filter(|m| !m.is_empty());
+    let body = content
+        .filter(|c| text(c, "encoding").is_none())
+        .and_then(|c| text(c, "text"))
+        .filter(|_| mime_type.as_deref().is_some_and(|m| m.contains("json")))
+        .and_then(|body| serde_json::from_str::<Value>(&body).ok());
+
+    let response = ApiResponse {
+        status_code: status.to_string(),
+        description: text(response, "statusText").filter(|s| !s.is_empty()),
+        schema: body.as_ref().map(Schema::from_example),
+        // Captured bodies hold the values of one run, which later ones aren't expected to repeat
+        example: None,
+        media_types: mime_type.iter().cloned().collect(),
+        // Captured responses carry every header the server happened to send, so none of them are expected
+        headers: Vec::new(),
+        links: Vec::new(),
+        extensions: BTreeMap::new(),
+    };
+    Some((response, mime_type))
+}
+
+/// Turn a captured path into a template: numbers, UUIDs and long hexadecimal identifiers become parameters
+/// named after the segment before them (`/users/42` becomes `/users/{userId}`), with the captured value as the example
+fn path_template(path: &str) -> (String, Vec<ApiParameter>) {
+    let mut params: Vec<ApiParameter> = Vec::new();
+    let mut segments: Vec<String> = Vec::new();
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        if !is_identifier(segment) {
+            segments.push(segment.to_string());
+            continue;
+        }
+
+        let mut name = match segments.last().filter(|previous| !previous.starts_with('{')) {
+            Some(previous) => format!("{}Id", singular(&camel_case(previous))),
+            None => "id".to_string(),
+        };
+        if params.iter().any(|p| p.name == name) {
+            name = format!("{}{}", name, params.len() + 1);
+        }
+        segments.push(format!("{{{}}}", name));
+        params.push(example_parameter(&name, "path", true, Some(segment)));
+    }
+    (format!("/{}", segments.join("/")), params)
+}
+
+/// Whether a path segment looks like an identifier rather than a fixed part of the route
+fn is_identifier(segment: &str) -> bool {
+    let hex = |s: &str| s.chars().all(|c| c.is_ascii_hexdigit());
+    let is_uuid = segment.len() == 36
+        && segment.split('-').map(str::len).eq([8, 4, 4, 4, 12])
+        && hex(&segment.replace('-', ""));
+
+    segment.chars().all(|c| c.is_ascii_digit())
+        || is_uuid
+        || (segment.len() >= 16 && hex(segment) && segment.chars().any(|c| c.is_ascii_digit()))
+}
+
+/// A path segment in lower camel case, `line-items` becoming `lineItems`
+fn camel_case(segment: &str) -> String {
+    segment
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .enumerate()
+        .map(|(i, word)| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap_or_default();
+            let first = if i == 0 { first.to_ascii_lowercase() } else { first.to_ascii_uppercase() };
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect()
+}
+
+/// The singular of a plural collection name, for the common English endings
+fn singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if word.ends_with("ss") || !word.ends_with('s') {
+        word.to_string()
+    } else {
+        word[..word.len() - 1].to_string()
+    }
+}
+
+/// Whether an entry fetched a static asset (a script, stylesheet, image or font) rather than calling the API
+fn is_static_asset(entry: &Value) -> bool {
+    const EXTENSIONS: &[&str] = &[
+        ".js", ".mjs", ".css", ".map", ".html", ".png", ".jpg", ".jpeg", ".gif", ".svg", ".ico", ".webp", ".woff", ".woff2",
+        ".ttf",
+    ];
+
+    let path = entry
+        .pointer("/request/url")
+        .and_then(Value::as_str)
+        .map(|url| url.split(['?', '#']).next().unwrap_or_default().to_ascii_lowercase())
+        .unwrap_or_default();
+    let mime_type = entry.pointer("/response/content/mimeType").and_then(Value::as_str).map(media_type).unwrap_or_default();
+
+    EXTENSIONS.iter().any(|extension| path.ends_with(extension))
+        || ["image/", "font/", "audio/", "video/"].iter().any(|prefix| mime_type.starts_with(prefix))
+        || matches!(mime_type.as_str(), "text/css" | "text/html" | "text/javascript" | "application/javascript")
+}
+
+/// Split an HTTP(S) URL into its origin and the rest, or `None` for other schemes such as `data:`
+fn split_url(url: &str) -> Option<(String, String)> {
+    let after_scheme = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"))?;
+    let origin_length = url.len() - after_scheme.len() + after_scheme.find(['/', '?', '#']).unwrap_or(after_scheme.len());
+    let (origin, rest) = url.split_at(origin_length);
+    Some((origin.to_string(), rest.to_string()))
+}
+
+/// A media type without its parameters, e.g. `application/json` for `application/json; charset=utf-8`
+fn media_type(value: &str) -> String {
+    value.split(';').next().unwrap_or_default().trim().to_ascii_lowercase()
+}
+
+/// The `name`/`value` pairs of a HAR list such as `headers` or `queryString`
+fn name_values(list: Option<&Value>) -> impl Iterator<Item = (String, String)> + '_ {
+    list.and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|pair| Some((text(pair, "name")?, text(pair, "value").unwrap_or_default())))
+}
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(String::from)
+}
@@ -0,0 +1,128 @@
+// This file contains the parsing of the `info` object: the API's title, version, contact and license.
+
+use serde_json::Value;
+
+/// Metadata about the API (`info`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiInfo {
+    /// Title of the API, "API" when the document doesn't give one
+    pub title: String,
+
+    /// Version of the API (not of the specification format), e.g. "1.0.0"
+    pub version: String,
+
+    /// Description of the API
+    pub description: Option<String>,
+
+    /// URL of the terms of service
+    pub terms_of_service: Option<String>,
+
+    /// Who to contact about the API
+    pub contact: Option<ApiContact>,
+
+    /// License the API is provided under
+    pub license: Option<ApiLicense>,
+}
+
+/// Contact information for the API (`info.contact`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiContact {
+    /// Name of the person or organization
+    pub name: Option<String>,
+
+    /// URL of the contact information
+    pub url: Option<String>,
+
+    /// Email address
+    pub email: Option<String>,
+}
+
+/// License of the API (`info.license`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiLicense {
+    /// Name of the license, e.g. "Apache 2.0"
+    pub name: String,
+
+    /// URL of the license text
+    pub url: Option<String>,
+
+    /// SPDX identifier of the license (OpenAPI 3.1), e.g. "Apache-2.0"
+    pub identifier: Option<String>,
+}
+
+impl Default for ApiInfo {
+    fn default() -> Self {
+        ApiInfo {
+            title: "API".to_string(),
+            version: "1.0.0".to_string(),
+            description: None,
+            terms_of_service: None,
+            contact: None,
+            license: None,
+        }
+    }
+}
+
+impl ApiInfo {
+    /// A package name made from the title and `suffix`, with words joined by `separator`:
+    /// `package_name("_", "tests")` is `pet_store_tests` for "Pet Store". The suffix isn't repeated
+    /// when the title already ends with it.
+    pub fn package_name(&self, separator: &str, suffix: &str) -> String {
+        let mut words: Vec<String> = self.title
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        if words.is_empty() {
+            words.push("api".to_string());
+        }
+        if words.first().is_some_and(|word| word.starts_with(|c: char| c.is_ascii_digit())) {
+            words.insert(0, "api".to_string());
+        }
+        if words.last().map(String::as_str) != Some(suffix) {
+            words.push(suffix.to_string());
+        }
+        words.join(separator)
+    }
+
+    /// The version when it is a `major.minor.patch` version that package managers accept, otherwise `fallback`
+    pub fn package_version<'a>(&'a self, fallback: &'a str) -> &'a str {
+        let version = self.version.strip_prefix('v').unwrap_or(&self.version);
+        let parts: Vec<&str> = version.split('.').collect();
+        let is_semver = parts.len() == 3 && parts.iter().all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+        if is_semver { version } else { fallback }
+    }
+}
+
+/// Parse an `info` object, using the defaults for whatever is missing
+pub fn parse_info(info: Option<&Value>) -> ApiInfo {
+    let Some(info) = info else {
+        return ApiInfo::default();
+    };
+    let text = |value: &Value, key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+    let defaults = ApiInfo::default();
+
+    ApiInfo {
+        title: text(info, "title").filter(|title| !title.trim().is_empty()).unwrap_or(defaults.title),
+        // YAML reads versions such as `2.0` as numbers
+        version: match info.get("version") {
+            Some(Value::String(version)) => version.clone(),
+            Some(Value::Number(version)) => version.to_string(),
+            _ => defaults.version,
+        },
+        description: text(info, "description"),
+        terms_of_service: text(info, "termsOfService"),
+        contact: info.get("contact").map(|contact| ApiContact {
+            name: text(contact, "name"),
+            url: text(contact, "url"),
+            email: text(contact, "email"),
+        }),
+        license: info.get("license").and_then(|license| {
+            Some(ApiLicense {
+                name: text(license, "name")?,
+                url: text(license, "url"),
+                identifier: text(license, "identifier"),
+            })
+        }),
+    }
+}
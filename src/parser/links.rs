@@ -0,0 +1,114 @@
+// This file contains the parsing of OpenAPI 3 response links and the runtime expressions they use.
+
+use serde_json::Value;
+
+/// A link from a response to an operation that can be called with values taken from it
+#[derive(Debug, Clone)]
+pub struct ApiLink {
+    /// Name the link is declared under
+    pub name: String,
+
+    /// operationId of the linked operation
+    pub operation_id: Option<String>,
+
+    /// Reference to the linked operation, e.g. `#/paths/~1users~1{id}/get`
+    pub operation_ref: Option<String>,
+
+    /// Values for the linked operation's parameters, by parameter name (optionally qualified, as in `path.id`)
+    pub parameters: Vec<(String, LinkValue)>,
+
+    /// Value to send as the linked operation's request body
+    pub request_body: Option<LinkValue>,
+
+    /// Description of the link
+    pub description: Option<String>,
+}
+
+/// Where the value of a link parameter comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkValue {
+    /// The value at a JSON pointer in the response body (an empty pointer is the whole body)
+    ResponseBody(String),
+
+    /// The value of a response header
+    ResponseHeader(String),
+
+    /// A path parameter of the request
+    RequestPath(String),
+
+    /// A query parameter of the request
+    RequestQuery(String),
+
+    /// A header of the request
+    RequestHeader(String),
+
+    /// The value at a JSON pointer in the request body
+    RequestBody(String),
+
+    /// A constant, or an expression that isn't understood
+    Constant(Value),
+}
+
+impl LinkValue {
+    /// Interpret a link parameter value: a runtime expression such as `$response.body#/id`
+    /// (possibly wrapped in braces), or a constant
+    pub fn parse(value: &Value) -> LinkValue {
+        let expression = match value.as_str() {
+            Some(text) => text.trim(),
+            None => return LinkValue::Constant(value.clone()),
+        };
+        let expression = expression
+            .strip_prefix('{')
+            .and_then(|e| e.strip_suffix('}'))
+            .unwrap_or(expression);
+
+        let body_pointer = |source: &str| {
+            expression
+                .strip_prefix(source)
+                .map(|rest| rest.strip_prefix('#').unwrap_or(rest).to_string())
+        };
+
+        if let Some(pointer) = body_pointer("$response.body") {
+            LinkValue::ResponseBody(pointer)
+        } else if let Some(name) = expression.strip_prefix("$response.header.") {
+            LinkValue::ResponseHeader(name.to_string())
+        } else if let Some(name) = expression.strip_prefix("$request.path.") {
+            LinkValue::RequestPath(name.to_string())
+        } else if let Some(name) = expression.strip_prefix("$request.query.") {
+            LinkValue::RequestQuery(name.to_string())
+        } else if let Some(name) = expression.strip_prefix("$request.header.") {
+            LinkValue::RequestHeader(name.to_string())
+        } else if let Some(pointer) = body_pointer("$request.body") {
+            LinkValue::RequestBody(pointer)
+        } else {
+            LinkValue::Constant(value.clone())
+        }
+    }
+}
+
+/// Parse the (already resolved) `links` map of a response
+pub fn parse_links(links: Option<&Value>) -> Vec<ApiLink> {
+    let links = match links.and_then(Value::as_object) {
+        Some(links) => links,
+        None => return Vec::new(),
+    };
+
+    links
+        .iter()
+        .map(|(name, link)| {
+            let text = |key: &str| link.get(key).and_then(Value::as_str).map(String::from);
+            ApiLink {
+                name: name.clone(),
+                operation_id: text("operationId"),
+                operation_ref: text("operationRef"),
+                parameters: link
+                    .get("parameters")
+                    .and_then(Value::as_object)
+                    .map(|params| params.iter().map(|(name, value)| (name.clone(), LinkValue::parse(value))).collect())
+                    .unwrap_or_default(),
+                request_body: link.get("requestBody").map(LinkValue::parse),
+                description: text("description"),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,102 @@
+// This file contains the detection of the query parameters an operation pages its results with, by their usual names
+// (page/limit/offset/cursor and their variants) or as named by an `x-pagination` extension.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use super::swagger::ApiParameter;
+
+/// Usual names of a parameter picking the page by number, compared once lowercased with `-` as `_` and no leading `$`
+const PAGE_NAMES: &[&str] = &["page", "page_number", "pagenumber", "page_num", "pagenum"];
+
+/// Usual names of a parameter skipping a number of items (`$skip` in OData)
+const OFFSET_NAMES: &[&str] = &["offset", "skip", "start"];
+
+/// Usual names of a parameter passing the cursor a previous page returned
+const CURSOR_NAMES: &[&str] = &["cursor", "after", "page_token", "pagetoken", "next_token", "nexttoken", "continuation_token", "continuationtoken"];
+
+/// Usual names of a parameter limiting how many items a page holds (`$top` in OData)
+const LIMIT_NAMES: &[&str] = &["limit", "per_page", "perpage", "page_size", "pagesize", "size", "max_results", "maxresults", "top"];
+
+/// The query parameters an operation pages its results with
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Pagination {
+    /// Parameter picking the page by number, such as `page`
+    pub page_param: Option<String>,
+
+    /// Number of the first page: the page parameter's `minimum` or else its default, 1 when it documents neither
+    pub first_page: u64,
+
+    /// Parameter skipping a number of items, such as `offset`
+    pub offset_param: Option<String>,
+
+    /// Parameter picking the page by the cursor a previous page returned, such as `cursor`
+    pub cursor_param: Option<String>,
+
+    /// Parameter limiting how many items a page holds, such as `limit`
+    pub limit_param: Option<String>,
+
+    /// JSON pointer to the items in the response body, when `x-pagination` gives one
+    pub items_pointer: Option<String>,
+
+    /// JSON pointer to the cursor of the next page in the response body, when `x-pagination` gives one
+    pub next_cursor_pointer: Option<String>,
+}
+
+impl Pagination {
+    /// The pagination of an operation taking `query_params`. An `x-pagination` object in its `extensions` names the
+    /// parameters (`page`, `offset`, `cursor` and `limit` keys) and where the items and next cursor are in the response
+    /// body (`items` and `nextCursor` JSON pointers); parameters it doesn't name are found by their usual names,
+    /// counting ones (see `is_count`) for numbers and sizes. When it names the parameter picking the page, no other is
+    /// looked for, and `x-pagination: false` turns detection off. `None` when no parameter picks the page
+    pub fn detect(query_params: &[ApiParameter], extensions: &BTreeMap<String, Value>) -> Option<Pagination> {
+        let extension = match extensions.get("x-pagination") {
+            Some(Value::Bool(false)) => return None,
+            Some(Value::Object(extension)) => Some(extension),
+            _ => None,
+        };
+        let named = |key: &str| extension.and_then(|extension| extension.get(key)).and_then(Value::as_str).map(String::from);
+        let usual = |names: &[&str], counts: bool| {
+            query_params
+                .iter()
+                .filter(|param| !counts || is_count(param))
+                .find(|param| names.contains(&usual_name(&param.name).as_str()))
+                .map(|param| param.name.clone())
+        };
+
+        let explicit = ["page", "offset", "cursor"].into_iter().any(|key| named(key).is_some());
+        let pick = |key: &str, names: &[&str], counts: bool| named(key).or_else(|| if explicit { None } else { usual(names, counts) });
+        let page_param = pick("page", PAGE_NAMES, true);
+        let first_page = query_params
+            .iter()
+            .find(|param| Some(&param.name) == page_param.as_ref())
+            .and_then(|param| param.constraints.minimum.map(|minimum| minimum.ceil() as u64).or_else(|| param.default.as_ref().and_then(Value::as_u64)))
+            .unwrap_or(1);
+        let pagination = Pagination {
+            page_param,
+            first_page,
+            offset_param: pick("offset", OFFSET_NAMES, true),
+            cursor_param: pick("cursor", CURSOR_NAMES, false),
+            limit_param: named("limit").or_else(|| usual(LIMIT_NAMES, true)),
+            items_pointer: named("items"),
+            next_cursor_pointer: named("nextCursor"),
+        };
+        let picks_page = pagination.page_param.is_some() || pagination.offset_param.is_some() || pagination.cursor_param.is_some();
+        picks_page.then_some(pagination)
+    }
+}
+
+/// Whether a parameter holds a count: an integer, or a parameter known only by an example of digits (from a Postman
+/// collection or HAR capture)
+fn is_count(param: &ApiParameter) -> bool {
+    param.param_type == "integer"
+        || (param.schema.is_none() && param.example.as_ref().and_then(Value::as_str).is_some_and(|example| {
+            !example.is_empty() && example.chars().all(|c| c.is_ascii_digit())
+        }))
+}
+
+/// A parameter name as compared to the usual names: lowercased, with `-` as `_` and without a leading `$`
+fn usual_name(name: &str) -> String {
+    name.trim_start_matches('$').to_lowercase().replace('-', "_")
+}
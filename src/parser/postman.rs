@@ -0,0 +1,435 @@
+// This file contains the conversion of a Postman v2.1 collection into the parsed specification used by the generators.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use super::info::ApiInfo;
+use super::pagination::Pagination;
+use super::schema::Schema;
+use super::security::{SecurityRequirement, SecurityScheme, SecuritySchemeKind};
+use super::swagger::{default_operation_id, example_body, example_parameter, form_body, ApiOperation, ApiParameter, ApiPath, ApiResponse, Result, SwaggerSpec};
+
+/// Whether a document is a Postman collection rather than a Swagger/OpenAPI specification,
+/// judging by the schema URL in its `info`
+pub fn is_postman_collection(document: &Value) -> bool {
+    document
+        .pointer("/info/schema")
+        .and_then(Value::as_str)
+        .is_some_and(|schema| schema.contains("schema.getpostman.com") || schema.contains("/collection/v2"))
+}
+
+/// Convert a Postman v2.1 collection. Folders become tags, saved example responses become the documented
+/// responses, and request and response bodies get schemas inferred from their content.
+/// `{{variables}}` are replaced by the collection's values where it declares them.
+pub fn parse_postman_collection(collection: Value) -> Result<SwaggerSpec> {
+    let variables: BTreeMap<String, String> = collection
+        .get("variable")
+        .and_then(Value::as_array)
+        .map(|variables| {
+            variables
+                .iter()
+                .filter_map(|v| Some((text(v, "key")?, v.get("value").map(plain_text).unwrap_or_default())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut converter = Converter {
+        variables,
+        base_url: None,
+        paths: Vec::new(),
+        security_schemes: Vec::new(),
+    };
+    let auth = collection.get("auth");
+    converter.convert_items(collection.get("item"), None, auth);
+
+    let info = collection.get("info");
+    Ok(SwaggerSpec {
+        info: ApiInfo {
+            title: info.and_then(|info| text(info, "name")).unwrap_or_else(|| ApiInfo::default().title),
+            description: info.and_then(|info| info.get("description")).and_then(description),
+            ..ApiInfo::default()
+        },
+        base_url: converter.base_url.unwrap_or_else(|| "http://localhost".to_string()),
+        server: None,
+        servers: Vec::new(),
+        paths: converter.paths,
+        webhooks: Vec::new(),
+        security: Vec::new(),
+        security_schemes: converter.security_schemes,
+        raw_spec: Arc::new(collection),
+        warnings: Vec::new(),
+    })
+}
+
+/// State gathered while walking the items of a collection
+struct Converter {
+    /// Values of the collection's variables, by name
+    variables: BTreeMap<String, String>,
+
+    /// Scheme, host and base path of the first request, with variables replaced
+    base_url: Option<String>,
+
+    /// Operations grouped by path, in the order the paths were first seen
+    paths: Vec<ApiPath>,
+
+    /// Schemes for the kinds of `auth` used in the collection
+    security_schemes: Vec<SecurityScheme>,
+}
+
+/// The parts of a request URL
+struct RequestUrl {
+    /// Scheme, host and port, e.g. `https://api.example.com`
+    origin: Option<String>,
+
+    /// Path segments, with `:name` and `{{name}}` placeholders
+    segments: Vec<String>,
+
+    /// Query parameters, with disabled ones left out
+    query: Vec<(String, String)>,
+
+    /// Values of the `:name` path variables
+    path_variables: BTreeMap<String, String>,
+}
+
+impl Converter {
+    /// Convert a list of items: folders are walked with their name as the tag, anything with a request becomes an operation.
+    /// `auth` is inherited from the enclosing folder or collection.
+    fn convert_items(&mut self, items: Option<&Value>, folder: Option<&str>, auth: Option<&Value>) {
+        for item in items.and_then(Value::as_array).into_iter().flatten() {
+            let auth = item.get("auth").or(auth);
+            if let Some(children) = item.get("item") {
+                let name = item.get("name").and_then(Value::as_str);
+                self.convert_items(Some(children), name.or(folder), auth);
+            } else if let Some(request) = item.get("request") {
+                self.convert_request(item, request, folder, request.get("auth").or(auth));
+            }
+        }
+    }
+
+    fn convert_request(&mut self, item: &Value, request: &Value, folder: Option<&str>, auth: Option<&Value>) {
+        // A request can be given as just its URL
+        let (method, url) = match request {
+            Value::String(url) => ("GET".to_string(), self.parse_url(&Value::String(url.clone()))),
+            _ => (
+                text(request, "method").unwrap_or_else(|| "GET".to_string()).to_uppercase(),
+                self.parse_url(request.get("url").unwrap_or(&Value::Null)),
+            ),
+        };
+
+        if self.base_url.is_none() {
+            self.base_url = url.origin.clone();
+        }
+
+        // `:name` and `{{name}}` segments are path parameters
+        let mut path_params = Vec::new();
+        let segments: Vec<String> = url.segments
+            .iter()
+            .map(|segment| {
+                let name = segment
+                    .strip_prefix(':')
+                    .or_else(|| segment.strip_prefix("{{").and_then(|s| s.strip_suffix("}}")));
+                match name {
+                    Some(name) => {
+                        let value = url.path_variables.get(name).or_else(|| self.variables.get(name));
+                        path_params.push(example_parameter(name, "path", true, value.map(String::as_str)));
+                        format!("{{{}}}", name)
+                    },
+                    None => segment.clone(),
+                }
+            })
+            .collect();
+        let path = format!("/{}", segments.join("/"));
+
+        let query_params: Vec<ApiParameter> = url.query
+            .iter()
+            .map(|(name, value)| example_parameter(name, "query", false, Some(&self.substitute(value))))
+            .collect();
+
+        // `Content-Type` and `Accept` describe the media types rather than being parameters
+        let mut header_params = Vec::new();
+        let mut content_type = None;
+        let mut produces = Vec::new();
+        for header in enabled(request.get("header")) {
+            let (Some(name), value) = (text(header, "key"), text(header, "value").unwrap_or_default()) else { continue };
+            let value = self.substitute(&value);
+            if name.eq_ignore_ascii_case("Content-Type") {
+                content_type = Some(value);
+            } else if name.eq_ignore_ascii_case("Accept") {
+                produces.push(value);
+            } else {
+                header_params.push(example_parameter(&name, "header", true, Some(&value)));
+            }
+        }
+
+        let body_param = request.get("body").and_then(|body| self.convert_body(body, content_type));
+        let consumes = body_param.iter().filter_map(|body| body.content_type.clone()).collect();
+
+        // Saved example responses document the status codes and bodies to expect
+        let mut responses = Vec::new();
+        for response in item.get("response").and_then(Value::as_array).into_iter().flatten() {
+            let response_type = enabled(response.get("header"))
+                .find(|h| text(h, "key").is_some_and(|key| key.eq_ignore_ascii_case("Content-Type")))
+                .and_then(|h| text(h, "value"));
+            let media_type = response_type.as_ref().map(|m| m.split(';').next().unwrap_or_default().trim().to_string());
+            if let Some(media_type) = &media_type {
+                if !produces.contains(media_type) {
+                    produces.push(media_type.clone());
+                }
+            }
+
+            let body = response.get("body").and_then(Value::as_str).and_then(|body| serde_json::from_str::<Value>(body).ok());
+            responses.push(ApiResponse {
+                status_code: response.get("code").and_then(Value::as_u64).map(|code| code.to_string()).unwrap_or_else(|| "200".to_string()),
+                description: text(response, "name").or_else(|| text(response, "status")),
+                schema: body.as_ref().map(Schema::from_example),
+                // A saved response is an example of what the request answers
+                example: body,
+                media_types: media_type.into_iter().collect(),
+                // Saved responses carry every header the server happened to send, so none of them are expected
+                headers: Vec::new(),
+                links: Vec::new(),
+                extensions: BTreeMap::new(),
+            });
+        }
+
+        let name = text(item, "name").unwrap_or_default();
+        let pagination = Pagination::detect(&query_params, &BTreeMap::new());
+        let operation = ApiOperation {
+            operation_id: operation_id(&name, &method, &path),
+            declared_operation_id: None,
+            method,
+            summary: (!name.is_empty()).then_some(name),
+            description: request.get("description").and_then(description),
+            path_params,
+            query_params,
+            header_params,
+            cookie_params: Vec::new(),
+            form_params: Vec::new(),
+            body_param,
+            consumes,
+            produces,
+            responses,
+            security: self.security(auth),
+            tags: folder.map(String::from).into_iter().collect(),
+            deprecated: false,
+            callbacks: Vec::new(),
+            extensions: BTreeMap::new(),
+            pagination,
+        };
+
+        match self.paths.iter_mut().find(|api_path| api_path.path == path) {
+            Some(api_path) => api_path.operations.push(operation),
+            None => self.paths.push(ApiPath { path, operations: vec![operation] }),
+        }
+    }
+
+    /// Convert a request body: raw JSON gets a schema inferred from it, other raw text is sent as is,
+    /// and URL-encoded and form-data bodies become form fields
+    fn convert_body(&self, body: &Value, content_type: Option<String>) -> Option<ApiParameter> {
+        let fields = |media_type: &str| {
+            let fields: Vec<ApiParameter> = enabled(body.get(body.get("mode")?.as_str()?))
+                .filter_map(|field| {
+                    let name = text(field, "key")?;
+                    let mut param = example_parameter(&name, "formData", false, Some(&self.substitute(&text(field, "value").unwrap_or_default())));
+                    if text(field, "type").as_deref() == Some("file") {
+                        param.param_type = "file".to_string();
+                        param.example = None;
+                    }
+                    Some(param)
+                })
+                .collect();
+            Some(form_body(&fields, media_type))
+        };
+
+        match body.get("mode").and_then(Value::as_str)? {
+            "urlencoded" => fields("application/x-www-form-urlencoded"),
+            "formdata" => fields("multipart/form-data"),
+            "raw" => {
+                let raw = self.substitute(&text(body, "raw").unwrap_or_default());
+                if raw.trim().is_empty() {
+                    return None;
+                }
+
+                let language = body.pointer("/options/raw/language").and_then(Value::as_str);
+                let json = serde_json::from_str::<Value>(&raw).ok();
+                let media_type = content_type.unwrap_or_else(|| {
+                    match (language, &json) {
+                        (Some("json"), _) | (None, Some(_)) => "application/json",
+                        (Some("xml"), _) => "application/xml",
+                        (Some("html"), _) => "text/html",
+                        (Some("javascript"), _) => "application/javascript",
+                        _ => "text/plain",
+                    }
+                    .to_string()
+                });
+
+                Some(example_body(&raw, &media_type))
+            },
+            "graphql" => {
+                let graphql = body.get("graphql")?;
+                let variables = graphql
+                    .get("variables")
+                    .and_then(Value::as_str)
+                    .and_then(|variables| serde_json::from_str::<Value>(variables).ok())
+                    .unwrap_or_else(|| Value::Object(Default::default()));
+                let example = serde_json::json!({ "query": text(graphql, "query").unwrap_or_default(), "variables": variables });
+                Some(example_body(&example.to_string(), "application/json"))
+            },
+            _ => None,
+        }
+    }
+
+    /// Split a request URL, given as text or as Postman's URL object, into its parts
+    fn parse_url(&self, url: &Value) -> RequestUrl {
+        let raw = match url {
+            Value::String(raw) => raw.clone(),
+            _ => text(url, "raw").unwrap_or_default(),
+        };
+
+        // Postman's URL object lists the parts separately, otherwise they're split from the raw URL
+        let (origin, segments, query) = match url.get("path") {
+            Some(path) => {
+                let join = |value: Option<&Value>, separator: &str| match value {
+                    Some(Value::Array(parts)) => parts.iter().map(plain_text).collect::<Vec<_>>().join(separator),
+                    Some(value) => plain_text(value),
+                    None => String::new(),
+                };
+                let host = join(url.get("host"), ".");
+                let origin = match (text(url, "protocol"), text(url, "port")) {
+                    (Some(protocol), Some(port)) => format!("{}://{}:{}", protocol, host, port),
+                    (Some(protocol), None) => format!("{}://{}", protocol, host),
+                    (None, Some(port)) => format!("{}:{}", host, port),
+                    (None, None) => host,
+                };
+                let segments = join(Some(path), "/");
+                let query = enabled(url.get("query"))
+                    .filter_map(|q| Some((text(q, "key")?, text(q, "value").unwrap_or_default())))
+                    .collect();
+                (origin, segments, query)
+            },
+            None => {
+                let (without_query, query) = raw.split_once('?').unwrap_or((&raw, ""));
+                let query = query
+                    .split('&')
+                    .filter(|pair| !pair.is_empty())
+                    .map(|pair| {
+                        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                        (name.to_string(), value.to_string())
+                    })
+                    .collect();
+                let after_scheme = without_query.find("://").map(|i| i + 3).unwrap_or(0);
+                let (origin, path) = match without_query[after_scheme..].find('/') {
+                    Some(i) => without_query.split_at(after_scheme + i),
+                    None => (without_query, ""),
+                };
+                (origin.to_string(), path.to_string(), query)
+            },
+        };
+
+        // The origin is usually a variable such as `{{baseUrl}}`, which may hold a base path too
+        let origin = self.substitute(&origin);
+        let origin = (!origin.is_empty() && !origin.contains("{{")).then(|| {
+            let origin = origin.trim_end_matches('/');
+            if origin.contains("://") { origin.to_string() } else { format!("http://{}", origin) }
+        });
+
+        let path_variables = enabled(url.get("variable"))
+            .filter_map(|v| Some((text(v, "key")?, self.substitute(&v.get("value").map(plain_text).unwrap_or_default()))))
+            .collect();
+
+        RequestUrl {
+            origin,
+            segments: segments.split('/').filter(|s| !s.is_empty()).map(String::from).collect(),
+            query,
+            path_variables,
+        }
+    }
+
+    /// Replace the `{{variables}}` the collection declares, leaving unknown ones in place
+    fn substitute(&self, value: &str) -> String {
+        self.variables
+            .iter()
+            .fold(value.to_string(), |value, (name, replacement)| value.replace(&format!("{{{{{}}}}}", name), replacement))
+    }
+
+    /// The security requirements for a request's (possibly inherited) `auth`, registering its scheme
+    fn security(&mut self, auth: Option<&Value>) -> Vec<SecurityRequirement> {
+        let auth_type = match auth.and_then(|auth| text(auth, "type")) {
+            Some(auth_type) if auth_type != "noauth" => auth_type,
+            _ => return Vec::new(),
+        };
+
+        let kind = match auth_type.as_str() {
+            "bearer" => SecuritySchemeKind::Bearer { format: None },
+            "basic" => SecuritySchemeKind::Basic,
+            "apikey" => {
+                // Postman lists the settings of an auth as key/value pairs under its type
+                let setting = |key: &str| {
+                    auth.and_then(|auth| auth.get("apikey"))
+                        .and_then(Value::as_array)
+                        .and_then(|settings| settings.iter().find(|s| text(s, "key").as_deref() == Some(key)))
+                        .and_then(|s| text(s, "value"))
+                };
+                SecuritySchemeKind::ApiKey {
+                    name: setting("key").unwrap_or_else(|| "X-API-Key".to_string()),
+                    location: setting("in").unwrap_or_else(|| "header".to_string()),
+                }
+            },
+            "oauth2" => SecuritySchemeKind::OAuth2 { flows: Vec::new() },
+            other => SecuritySchemeKind::Other(other.to_string()),
+        };
+
+        if !self.security_schemes.iter().any(|scheme| scheme.name == auth_type) {
+            self.security_schemes.push(SecurityScheme { name: auth_type.clone(), kind, description: None });
+        }
+        vec![SecurityRequirement { schemes: vec![(auth_type, Vec::new())] }]
+    }
+}
+
+/// An operationId from the request name in lower camel case (`Get user` becomes `getUser`),
+/// or from the method and path for unnamed requests
+fn operation_id(name: &str, method: &str, path: &str) -> String {
+    let words: Vec<&str> = name.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()).collect();
+    if words.is_empty() {
+        return default_operation_id(method, path);
+    }
+
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap_or_default();
+            let first = if i == 0 { first.to_ascii_lowercase() } else { first.to_ascii_uppercase() };
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect()
+}
+
+/// The entries of a key/value list that aren't marked `disabled`
+fn enabled(list: Option<&Value>) -> impl Iterator<Item = &Value> {
+    list.and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|entry| !entry.get("disabled").and_then(Value::as_bool).unwrap_or(false))
+}
+
+/// A description, given either as text or as an object with its `content`
+fn description(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        _ => text(value, "content"),
+    }
+}
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(String::from)
+}
+
+/// A value as plain text, without quotes around strings
+fn plain_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
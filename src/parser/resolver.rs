@@ -0,0 +1,329 @@
+// This file contains the reference resolver, responsible for inlining `$ref` pointers in the Swagger document.
+
+use serde_json::{Map, Value};
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use super::swagger::{parse_document, ParserError, Result, SpecFormat};
+
+/// Resolves `$ref` pointers against the document they were found in
+pub struct RefResolver<'a> {
+    /// The root document used to look up local references
+    root: &'a Value,
+
+    /// Directory of the root document, set when references into other files should be followed
+    base_dir: Option<PathBuf>,
+
+    /// External documents that have already been loaded, keyed by canonical path
+    documents: RefCell<HashMap<PathBuf, Value>>,
+
+    /// Whether references within the root document are kept, as when bundling
+    keep_local: bool,
+
+    /// Names given to recursive external schemas moved into the root document, keyed like the resolution stack
+    hoisted_names: RefCell<HashMap<String, String>>,
+
+    /// Recursive external schemas moved into the root document, by name
+    hoisted: RefCell<BTreeMap<String, Value>>,
+
+    /// Whether references that can't be resolved are left in place instead of failing
+    lenient: bool,
+
+    /// Why the references left in place by a lenient resolver couldn't be resolved, since they were last taken
+    unresolved: RefCell<Vec<String>>,
+
+    /// Resolved targets of references, keyed like the resolution stack, so that schemas shared by many
+    /// operations are only resolved once
+    resolved: RefCell<HashMap<String, Value>>,
+
+    /// Whether resolved targets are cached in `resolved`, which only the benchmarks turn off
+    cache: bool,
+
+    /// How many references have been left in place so far, because they were recursive or didn't resolve.
+    /// A target resolved while this changed depends on where it was reached from, and isn't cached.
+    left_in_place: Cell<usize>,
+}
+
+impl<'a> RefResolver<'a> {
+    /// Create a resolver that only follows references within `root`
+    pub fn new(root: &'a Value) -> Self {
+        RefResolver {
+            root,
+            base_dir: None,
+            documents: RefCell::new(HashMap::new()),
+            keep_local: false,
+            hoisted_names: RefCell::new(HashMap::new()),
+            hoisted: RefCell::new(BTreeMap::new()),
+            lenient: false,
+            unresolved: RefCell::new(Vec::new()),
+            resolved: RefCell::new(HashMap::new()),
+            cache: true,
+            left_in_place: Cell::new(0),
+        }
+    }
+
+    /// Create a resolver that also follows relative file references, starting from `base_dir`
+    pub fn external(root: &'a Value, base_dir: &Path) -> Self {
+        RefResolver {
+            root,
+            base_dir: Some(base_dir.to_path_buf()),
+            documents: RefCell::new(HashMap::new()),
+            keep_local: false,
+            hoisted_names: RefCell::new(HashMap::new()),
+            hoisted: RefCell::new(BTreeMap::new()),
+            lenient: false,
+            unresolved: RefCell::new(Vec::new()),
+            resolved: RefCell::new(HashMap::new()),
+            cache: true,
+            left_in_place: Cell::new(0),
+        }
+    }
+
+    /// Create a resolver that inlines references into other files, but keeps those within `root`
+    pub fn bundler(root: &'a Value, base_dir: &Path) -> Self {
+        RefResolver {
+            keep_local: true,
+            ..RefResolver::external(root, base_dir)
+        }
+    }
+
+    /// Leave references that don't resolve (or whose file can't be read) in place instead of failing,
+    /// recording why for `take_unresolved`
+    pub fn lenient(self) -> Self {
+        RefResolver { lenient: true, ..self }
+    }
+
+    /// Resolve the target of a reference again everywhere it's used instead of caching it, as a baseline to measure
+    /// the cache against
+    pub fn uncached(self) -> Self {
+        RefResolver { cache: false, ..self }
+    }
+
+    /// Why the references left in place since the last call couldn't be resolved
+    pub fn take_unresolved(&self) -> Vec<String> {
+        self.unresolved.take()
+    }
+
+    /// Return a copy of the root document that no longer refers to other files.
+    /// External schemas that contain themselves can't be inlined, so they're added to the root document's
+    /// schemas (`definitions` or `components/schemas`) and referred to there.
+    pub fn bundle(&self) -> Result<Value> {
+        let mut bundled = self.resolve(self.root)?;
+
+        let hoisted = self.hoisted.take();
+        if !hoisted.is_empty() {
+            if let Some(root) = bundled.as_object_mut() {
+                let mut section = root;
+                for key in self.schemas_section() {
+                    section = match section.entry(key.to_string()).or_insert_with(|| Value::Object(Map::new())).as_object_mut() {
+                        Some(section) => section,
+                        None => return Err(ParserError::InvalidSpec(format!("`{}` is not an object", key))),
+                    };
+                }
+                section.extend(hoisted);
+            }
+        }
+
+        Ok(bundled)
+    }
+
+    /// Return a copy of `value` with every `$ref` replaced by the value it points to.
+    /// References that point back into themselves are left in place so that recursive schemas terminate.
+    pub fn resolve(&self, value: &Value) -> Result<Value> {
+        self.resolve_value(value, &None, &mut Vec::new())
+    }
+
+    /// `document` is the external file `value` was read from, or `None` for the root document
+    fn resolve_value(&self, value: &Value, document: &Option<PathBuf>, stack: &mut Vec<String>) -> Result<Value> {
+        match value {
+            Value::Object(obj) => {
+                if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+                    return self.resolve_reference(reference, obj, document, stack);
+                }
+
+                let mut resolved = Map::new();
+                for (key, child) in obj {
+                    resolved.insert(key.clone(), self.resolve_value(child, document, stack)?);
+                }
+                Ok(Value::Object(resolved))
+            },
+            Value::Array(items) => items
+                .iter()
+                .map(|item| self.resolve_value(item, document, stack))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array),
+            _ => Ok(value.clone()),
+        }
+    }
+
+    fn resolve_reference(
+        &self,
+        reference: &str,
+        obj: &Map<String, Value>,
+        document: &Option<PathBuf>,
+        stack: &mut Vec<String>,
+    ) -> Result<Value> {
+        let (file, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+
+        // Work out which document the reference points into
+        let target_document = if file.is_empty() {
+            if self.keep_local && document.is_none() {
+                return Ok(Value::Object(obj.clone()));
+            }
+            document.clone()
+        } else if self.base_dir.is_none() || file.contains("://") {
+            // External resolution disabled, or a remote URL: pass through untouched
+            return Ok(Value::Object(obj.clone()));
+        } else {
+            match self.load_document(file, document) {
+                Ok(path) => Some(path),
+                Err(err) => return self.leave_unresolved(obj, err),
+            }
+        };
+
+        // Recursive schema, keep the reference instead of expanding forever
+        let key = match &target_document {
+            Some(path) => format!("{}#{}", path.display(), pointer),
+            None => format!("#{}", pointer),
+        };
+        if self.keep_local && target_document.is_some() {
+            // When bundling, a recursive external schema is referred to where it's been moved in the root document
+            if let Some(name) = self.hoisted_names.borrow().get(&key) {
+                return Ok(self.hoisted_reference(name, obj));
+            }
+            if stack.contains(&key) {
+                let name = self.hoisted_name(&key, pointer);
+                return Ok(self.hoisted_reference(&name, obj));
+            }
+        } else if stack.contains(&key) {
+            self.left_in_place.set(self.left_in_place.get() + 1);
+            return Ok(Value::Object(obj.clone()));
+        }
+
+        let cached = self.resolved.borrow().get(&key).cloned();
+        let mut resolved = match cached {
+            Some(resolved) => resolved,
+            None => {
+                let Some(target) = self.lookup(&target_document, pointer) else {
+                    return self.leave_unresolved(obj, ParserError::UnresolvedReference(reference.to_string()));
+                };
+
+                let left_in_place = self.left_in_place.get();
+                stack.push(key.clone());
+                let resolved = self.resolve_value(&target, &target_document, stack);
+                stack.pop();
+                let resolved = resolved?;
+
+                // Bundling hoists recursive schemas as it goes, so only plain resolution is cached
+                if self.cache && !self.keep_local && self.left_in_place.get() == left_in_place {
+                    self.resolved.borrow_mut().insert(key.clone(), resolved.clone());
+                }
+                resolved
+            },
+        };
+
+        // The schema referred to itself while it was being resolved, so it's been given a name to move it under
+        let hoisted_name = self.hoisted_names.borrow().get(&key).cloned();
+        if let Some(name) = hoisted_name {
+            self.hoisted.borrow_mut().insert(name.clone(), resolved);
+            return Ok(self.hoisted_reference(&name, obj));
+        }
+
+        // Keys written next to a `$ref` (such as `description`) take precedence over the referenced value
+        if let Some(resolved_obj) = resolved.as_object_mut() {
+            for (key, value) in obj {
+                if key != "$ref" {
+                    resolved_obj.insert(key.clone(), self.resolve_value(value, document, stack)?);
+                }
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    /// Fail with `err`, or when lenient keep the reference as it is and record the error
+    fn leave_unresolved(&self, obj: &Map<String, Value>, err: ParserError) -> Result<Value> {
+        if !self.lenient {
+            return Err(err);
+        }
+        self.left_in_place.set(self.left_in_place.get() + 1);
+        self.unresolved.borrow_mut().push(err.to_string());
+        Ok(Value::Object(obj.clone()))
+    }
+
+    /// Keys of the root document's schemas, where recursive external schemas are moved when bundling
+    fn schemas_section(&self) -> &'static [&'static str] {
+        if self.root.get("swagger").is_some() {
+            &["definitions"]
+        } else {
+            &["components", "schemas"]
+        }
+    }
+
+    /// Pick a name for a recursive external schema that's free in the root document: the last segment
+    /// of its pointer (or its file name), followed by a number if that's taken
+    fn hoisted_name(&self, key: &str, pointer: &str) -> String {
+        let base = match pointer.rsplit('/').next().filter(|name| !name.is_empty()) {
+            Some(name) => name.to_string(),
+            None => Path::new(key.trim_end_matches('#'))
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("Schema")
+                .to_string(),
+        };
+
+        let pointer = format!("/{}", self.schemas_section().join("/"));
+        let existing = self.root.pointer(&pointer);
+        let mut hoisted_names = self.hoisted_names.borrow_mut();
+        let taken = |name: &str| {
+            existing.is_some_and(|section| section.get(name).is_some()) || hoisted_names.values().any(|n| n == name)
+        };
+
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while taken(&name) {
+            name = format!("{}{}", base, suffix);
+            suffix += 1;
+        }
+
+        hoisted_names.insert(key.to_string(), name.clone());
+        name
+    }
+
+    /// A reference to a schema moved into the root document, keeping the keys written next to the original `$ref`
+    fn hoisted_reference(&self, name: &str, obj: &Map<String, Value>) -> Value {
+        let mut reference = obj.clone();
+        reference.insert("$ref".to_string(), Value::String(format!("#/{}/{}", self.schemas_section().join("/"), name)));
+        Value::Object(reference)
+    }
+
+    /// Find the value at `pointer` in the root document or a loaded external document
+    fn lookup(&self, document: &Option<PathBuf>, pointer: &str) -> Option<Value> {
+        match document {
+            Some(path) => self.documents.borrow().get(path)?.pointer(pointer).cloned(),
+            None => self.root.pointer(pointer).cloned(),
+        }
+    }
+
+    /// Load an external document relative to the document referencing it, returning its canonical path
+    fn load_document(&self, file: &str, document: &Option<PathBuf>) -> Result<PathBuf> {
+        let dir = match document {
+            Some(path) => path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            None => self.base_dir.clone().unwrap_or_default(),
+        };
+
+        let path = fs::canonicalize(dir.join(file))
+            .map_err(|e| ParserError::ExternalReference(file.to_string(), e.to_string()))?;
+
+        if !self.documents.borrow().contains_key(&path) {
+            let content = fs::read_to_string(&path)
+                .map_err(|e| ParserError::ExternalReference(file.to_string(), e.to_string()))?;
+            let format = SpecFormat::from_path(&path).unwrap_or_else(|| SpecFormat::sniff(&content));
+            let value = parse_document(&content, format)?;
+            self.documents.borrow_mut().insert(path.clone(), value);
+        }
+
+        Ok(path)
+    }
+}
@@ -0,0 +1,761 @@
+// This file contains helpers for working with the JSON schemas embedded in a Swagger document.
+
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// A concrete alternative of a polymorphic (`oneOf`/`anyOf`) schema
+#[derive(Debug, Clone)]
+pub struct SchemaVariant {
+    /// Name of the subtype, used as its discriminator value when the mapping doesn't say otherwise
+    pub name: String,
+    
+    /// Schema of the subtype, with the discriminator property fixed to this subtype's value
+    pub schema: Schema,
+}
+
+/// The validation keywords of a schema that limit which values it accepts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Constraints {
+    /// Lowest accepted number
+    pub minimum: Option<f64>,
+    
+    /// Highest accepted number
+    pub maximum: Option<f64>,
+    
+    /// Whether `minimum` itself is rejected
+    pub exclusive_minimum: bool,
+    
+    /// Whether `maximum` itself is rejected
+    pub exclusive_maximum: bool,
+    
+    /// Shortest accepted string length
+    pub min_length: Option<u64>,
+    
+    /// Longest accepted string length
+    pub max_length: Option<u64>,
+    
+    /// Regular expression strings have to match
+    pub pattern: Option<String>,
+    
+    /// Fewest accepted array items
+    pub min_items: Option<u64>,
+    
+    /// Most accepted array items
+    pub max_items: Option<u64>,
+}
+
+impl Constraints {
+    /// Read the constraints of a schema, or of a Swagger 2.0 parameter which declares them inline.
+    /// Both the OpenAPI 3.0 boolean and the OpenAPI 3.1 numeric forms of the exclusive bounds are understood.
+    pub fn from_schema(schema: &Value) -> Constraints {
+        let number = |key: &str| schema.get(key).and_then(Value::as_f64);
+        let count = |key: &str| schema.get(key).and_then(Value::as_u64);
+        let exclusive = |key: &str, bound: &str| match schema.get(key) {
+            Some(Value::Bool(exclusive)) => (number(bound), *exclusive),
+            Some(limit) if limit.is_number() => (limit.as_f64(), true),
+            _ => (number(bound), false),
+        };
+        
+        let (minimum, exclusive_minimum) = exclusive("exclusiveMinimum", "minimum");
+        let (maximum, exclusive_maximum) = exclusive("exclusiveMaximum", "maximum");
+        
+        Constraints {
+            minimum,
+            maximum,
+            exclusive_minimum,
+            exclusive_maximum,
+            min_length: count("minLength"),
+            max_length: count("maxLength"),
+            pattern: schema.get("pattern").and_then(Value::as_str).map(String::from),
+            min_items: count("minItems"),
+            max_items: count("maxItems"),
+        }
+    }
+    
+    /// Whether the schema declares no constraints at all
+    pub fn is_empty(&self) -> bool {
+        *self == Constraints::default()
+    }
+    
+    /// The lowest and highest accepted numbers, stepping inside exclusive bounds by `step`
+    /// (1 for integers); `None` where the range is unbounded
+    pub fn accepted_range(&self, step: f64) -> (Option<f64>, Option<f64>) {
+        let lowest = self.minimum.map(|min| if self.exclusive_minimum { min + step } else { min });
+        let highest = self.maximum.map(|max| if self.exclusive_maximum { max - step } else { max });
+        (lowest, highest)
+    }
+    
+    /// Boundary values for a number: the lowest and highest accepted values, and the values just
+    /// outside of them, each paired with whether the schema accepts it
+    pub fn numeric_boundaries(&self, step: f64) -> Vec<(f64, bool)> {
+        let (lowest, highest) = self.accepted_range(step);
+        let mut boundaries = Vec::new();
+        if let Some(lowest) = lowest {
+            boundaries.push((lowest, true));
+            boundaries.push((lowest - step, false));
+        }
+        if let Some(highest) = highest {
+            boundaries.push((highest, true));
+            boundaries.push((highest + step, false));
+        }
+        boundaries
+    }
+    
+    /// Boundary string lengths: the shortest and longest accepted lengths, and the lengths just
+    /// outside of them, each paired with whether the schema accepts it
+    pub fn length_boundaries(&self) -> Vec<(u64, bool)> {
+        let mut boundaries = Vec::new();
+        if let Some(min) = self.min_length {
+            boundaries.push((min, true));
+            if min > 0 {
+                boundaries.push((min - 1, false));
+            }
+        }
+        if let Some(max) = self.max_length {
+            boundaries.push((max, true));
+            boundaries.push((max + 1, false));
+        }
+        boundaries
+    }
+}
+
+/// Get the primitive type of a schema, inferring it from `const` or `enum` when `type` is missing
+pub fn schema_type(schema: &Value) -> Option<&'static str> {
+    let type_name = schema.get("type").and_then(Value::as_str);
+    let example = schema.get("const").or_else(|| schema.get("enum").and_then(|e| e.get(0)));
+    
+    match (type_name, example) {
+        (Some(t), _) => ["string", "integer", "number", "boolean", "array", "object"]
+            .into_iter()
+            .find(|known| *known == t),
+        (None, Some(Value::String(_))) => Some("string"),
+        (None, Some(Value::Number(n))) if n.is_f64() => Some("number"),
+        (None, Some(Value::Number(_))) => Some("integer"),
+        (None, Some(Value::Bool(_))) => Some("boolean"),
+        (None, Some(Value::Array(_))) => Some("array"),
+        (None, Some(Value::Object(_))) => Some("object"),
+        _ => None,
+    }
+}
+
+/// Call `f` on every `schema` found in a parameter, request body or response object
+pub fn for_each_schema<F: FnMut(&mut Value)>(value: &mut Value, f: &mut F) {
+    match value {
+        Value::Object(obj) => {
+            for (key, child) in obj.iter_mut() {
+                if key == "schema" {
+                    f(child);
+                } else {
+                    for_each_schema(child, f);
+                }
+            }
+        },
+        Value::Array(items) => items.iter_mut().for_each(|item| for_each_schema(item, f)),
+        _ => {}
+    }
+}
+
+/// Rewrite OpenAPI 3.1 (JSON Schema 2020-12) constructs into their OpenAPI 3.0 equivalents:
+/// `type` arrays become a single type plus `nullable`, `const` becomes a one-value `enum`,
+/// and numeric `exclusiveMinimum`/`exclusiveMaximum` become a bound plus the boolean flag.
+pub fn normalize_schema_v3_1(schema: &mut Value) {
+    let obj = match schema.as_object_mut() {
+        Some(obj) => obj,
+        None => return,
+    };
+    
+    if let Some(types) = obj.get("type").and_then(Value::as_array).cloned() {
+        let non_null: Vec<&Value> = types.iter().filter(|t| t.as_str() != Some("null")).collect();
+        if non_null.len() < types.len() {
+            obj.insert("nullable".to_string(), Value::Bool(true));
+        }
+        match non_null.first() {
+            Some(t) => obj.insert("type".to_string(), (*t).clone()),
+            None => obj.remove("type"),
+        };
+    }
+    
+    if let Some(constant) = obj.get("const").cloned() {
+        obj.entry("enum").or_insert_with(|| Value::Array(vec![constant]));
+    }
+    
+    for (exclusive, bound) in [("exclusiveMinimum", "minimum"), ("exclusiveMaximum", "maximum")] {
+        if let Some(limit) = obj.get(exclusive).filter(|v| v.is_number()).cloned() {
+            obj.insert(bound.to_string(), limit);
+            obj.insert(exclusive.to_string(), Value::Bool(true));
+        }
+    }
+    
+    for_each_subschema(schema, &mut normalize_schema_v3_1);
+}
+
+/// Merge the members of an `allOf` into a single schema, so the effective properties
+/// and required fields are visible without walking the composition.
+/// Keys declared on the schema itself win over keys coming from its `allOf` members.
+pub fn flatten_all_of(schema: &mut Value) {
+    // Flatten nested compositions first so each member is already a plain schema
+    for_each_subschema(schema, &mut flatten_all_of);
+    
+    let obj = match schema.as_object_mut() {
+        Some(obj) => obj,
+        None => return,
+    };
+    
+    let parts = match obj.remove("allOf") {
+        Some(Value::Array(parts)) => parts,
+        Some(other) => {
+            obj.insert("allOf".to_string(), other);
+            return;
+        },
+        None => return,
+    };
+    
+    for part in parts {
+        let part = match part {
+            // Members that are still references (recursive schemas) cannot be merged
+            Value::Object(part) if !part.contains_key("$ref") => part,
+            _ => continue,
+        };
+        
+        for (key, value) in part {
+            match (key.as_str(), value) {
+                ("properties", Value::Object(props)) => {
+                    let merged = obj
+                        .entry("properties")
+                        .or_insert_with(|| Value::Object(Map::new()));
+                    if let Some(merged) = merged.as_object_mut() {
+                        for (name, prop) in props {
+                            merged.entry(name).or_insert(prop);
+                        }
+                    }
+                },
+                ("required", Value::Array(required)) => {
+                    let merged = obj
+                        .entry("required")
+                        .or_insert_with(|| Value::Array(Vec::new()));
+                    if let Some(merged) = merged.as_array_mut() {
+                        for name in required {
+                            if !merged.contains(&name) {
+                                merged.push(name);
+                            }
+                        }
+                    }
+                },
+                (_, value) => {
+                    obj.entry(key).or_insert(value);
+                },
+            }
+        }
+    }
+    
+    if obj.contains_key("properties") {
+        obj.entry("type").or_insert_with(|| Value::String("object".to_string()));
+    }
+}
+
+/// Split a polymorphic schema into one schema per `oneOf`/`anyOf` member.
+/// `name_of` returns the discriminator value for a member when it can be determined,
+/// otherwise members are numbered. Returns an empty list for non-polymorphic schemas.
+pub fn schema_variants<F: Fn(&Value) -> Option<String>>(schema: &Value, name_of: F) -> Vec<SchemaVariant> {
+    let members = match schema.get("oneOf").or_else(|| schema.get("anyOf")).and_then(Value::as_array) {
+        Some(members) => members,
+        None => return Vec::new(),
+    };
+    
+    // OpenAPI 3 uses `discriminator.propertyName`, Swagger 2 a plain property name
+    let discriminator = schema.get("discriminator").and_then(|d| {
+        d.get("propertyName").and_then(Value::as_str).or_else(|| d.as_str())
+    });
+    
+    members
+        .iter()
+        .enumerate()
+        .filter(|(_, member)| member.get("$ref").is_none())
+        .map(|(i, member)| {
+            let name = name_of(member).unwrap_or_else(|| format!("variant{}", i + 1));
+            let mut variant = member.clone();
+            
+            if let Some(variant_obj) = variant.as_object_mut() {
+                // Properties declared next to the `oneOf` are shared by every subtype
+                if let Some(shared) = schema.get("properties").and_then(Value::as_object) {
+                    let props = variant_obj
+                        .entry("properties")
+                        .or_insert_with(|| Value::Object(Map::new()));
+                    if let Some(props) = props.as_object_mut() {
+                        for (prop_name, prop) in shared {
+                            props.entry(prop_name.clone()).or_insert_with(|| prop.clone());
+                        }
+                    }
+                }
+                
+                if let Some(property) = discriminator {
+                    let props = variant_obj
+                        .entry("properties")
+                        .or_insert_with(|| Value::Object(Map::new()));
+                    if let Some(props) = props.as_object_mut() {
+                        props.insert(property.to_string(), serde_json::json!({
+                            "type": "string",
+                            "enum": [name],
+                        }));
+                    }
+                    
+                    let required = variant_obj
+                        .entry("required")
+                        .or_insert_with(|| Value::Array(Vec::new()));
+                    if let Some(required) = required.as_array_mut() {
+                        if !required.iter().any(|r| r.as_str() == Some(property)) {
+                            required.push(Value::String(property.to_string()));
+                        }
+                    }
+                }
+            }
+            
+            SchemaVariant { name, schema: Schema::from_value(&variant) }
+        })
+        .collect()
+}
+
+/// Call `f` on each direct subschema of `schema` (items, properties, composition members, ...)
+fn for_each_subschema<F: FnMut(&mut Value)>(schema: &mut Value, f: &mut F) {
+    let obj = match schema.as_object_mut() {
+        Some(obj) => obj,
+        None => return,
+    };
+    
+    for (key, child) in obj.iter_mut() {
+        match key.as_str() {
+            "items" | "additionalProperties" | "not" | "contains" | "propertyNames" => f(child),
+            "allOf" | "anyOf" | "oneOf" | "prefixItems" => {
+                if let Some(items) = child.as_array_mut() {
+                    items.iter_mut().for_each(&mut *f);
+                }
+            },
+            "properties" | "patternProperties" | "$defs" => {
+                if let Some(props) = child.as_object_mut() {
+                    props.values_mut().for_each(&mut *f);
+                }
+            },
+            _ => {}
+        }
+    }
+}
+
+/// A schema from the specification, with its references resolved
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schema {
+    /// An object with named properties
+    Object(ObjectSchema),
+    
+    /// An array of items
+    Array(ArraySchema),
+    
+    /// A string, integer, number or boolean
+    Primitive(PrimitiveSchema),
+    
+    /// A reference left unresolved, because the schema it points at contains itself
+    Ref(String),
+    
+    /// A `oneOf`/`anyOf` choice, or an `allOf` that couldn't be flattened
+    Composite(CompositeSchema),
+    
+    /// A schema that doesn't restrict the type of its values
+    Any(SchemaInfo),
+}
+
+/// The annotations and validation keywords any schema can carry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaInfo {
+    /// Title of the schema
+    pub title: Option<String>,
+    
+    /// Description of the schema
+    pub description: Option<String>,
+    
+    /// Format refining the type (uuid, date-time, binary, etc.)
+    pub format: Option<String>,
+    
+    /// Whether `null` is accepted: `nullable: true` in OpenAPI 3.0, a `null` type in OpenAPI 3.1
+    pub nullable: bool,
+    
+    /// Whether values are only returned by the server, and never sent in requests
+    pub read_only: bool,
+    
+    /// Whether values are only sent to the server, and never returned in responses
+    pub write_only: bool,
+    
+    /// Values the schema is restricted to, from `enum` or OpenAPI 3.1 `const`
+    pub enum_values: Vec<Value>,
+    
+    /// Value assumed when none is given
+    pub default: Option<Value>,
+    
+    /// Documented example: `example`, or the first of the OpenAPI 3.1 `examples`
+    pub example: Option<Value>,
+    
+    /// Bounds, lengths and pattern values have to satisfy
+    pub constraints: Constraints,
+    
+    /// Media type of string content (OpenAPI 3.1 `contentMediaType`)
+    pub content_media_type: Option<String>,
+}
+
+/// An object schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectSchema {
+    /// Schemas of the declared properties, by name
+    pub properties: BTreeMap<String, Schema>,
+    
+    /// Names of the properties that must be present
+    pub required: Vec<String>,
+    
+    /// Schema of the undeclared properties, when `additionalProperties` gives one
+    pub additional_properties: Option<Box<Schema>>,
+    
+    /// Annotations of the object itself
+    pub info: SchemaInfo,
+}
+
+/// An array schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArraySchema {
+    /// Schema of the items, if declared
+    pub items: Option<Box<Schema>>,
+    
+    /// Annotations of the array itself
+    pub info: SchemaInfo,
+}
+
+/// A schema of a single value
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveSchema {
+    /// Type of the value
+    pub kind: PrimitiveType,
+    
+    /// Annotations of the value
+    pub info: SchemaInfo,
+}
+
+/// The primitive JSON types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrimitiveType {
+    String,
+    Integer,
+    Number,
+    Boolean,
+}
+
+/// A composition of schemas
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompositeSchema {
+    /// How the members combine
+    pub kind: CompositeKind,
+    
+    /// The composed schemas
+    pub members: Vec<Schema>,
+    
+    /// Property telling the members of a `oneOf`/`anyOf` apart, if declared
+    pub discriminator: Option<String>,
+    
+    /// Annotations of the composition itself
+    pub info: SchemaInfo,
+}
+
+/// The composition keywords
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositeKind {
+    OneOf,
+    AnyOf,
+    AllOf,
+}
+
+impl Schema {
+    /// Convert a (resolved) JSON schema. References that are still present, as left by the resolver
+    /// for recursive schemas, become `Schema::Ref`.
+    pub fn from_value(value: &Value) -> Schema {
+        if let Some(reference) = value.get("$ref").and_then(Value::as_str) {
+            return Schema::Ref(reference.to_string());
+        }
+        
+        let info = SchemaInfo::from_value(value);
+        let subschemas = |key: &str| -> Vec<Schema> {
+            value.get(key).and_then(Value::as_array).map(|members| members.iter().map(Schema::from_value).collect()).unwrap_or_default()
+        };
+        
+        for (key, kind) in [("oneOf", CompositeKind::OneOf), ("anyOf", CompositeKind::AnyOf), ("allOf", CompositeKind::AllOf)] {
+            if value.get(key).is_some() {
+                // OpenAPI 3 uses `discriminator.propertyName`, Swagger 2 a plain property name
+                let discriminator = value.get("discriminator").and_then(|d| {
+                    d.get("propertyName").and_then(Value::as_str).or_else(|| d.as_str())
+                });
+                return Schema::Composite(CompositeSchema {
+                    kind,
+                    members: subschemas(key),
+                    discriminator: discriminator.map(String::from),
+                    info,
+                });
+            }
+        }
+        
+        // OpenAPI 3.1 documents which haven't been normalized can give the type as an array
+        let type_name = match value.get("type") {
+            Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).find(|t| *t != "null"),
+            _ => schema_type(value),
+        };
+        let type_name = type_name.or_else(|| {
+            if value.get("properties").is_some() {
+                Some("object")
+            } else if value.get("items").is_some() {
+                Some("array")
+            } else {
+                None
+            }
+        });
+        
+        let primitive = |kind, info| Schema::Primitive(PrimitiveSchema { kind, info });
+        match type_name {
+            Some("object") => Schema::Object(ObjectSchema {
+                properties: value
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .map(|props| props.iter().map(|(name, prop)| (name.clone(), Schema::from_value(prop))).collect())
+                    .unwrap_or_default(),
+                required: value
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .map(|names| names.iter().filter_map(Value::as_str).map(String::from).collect())
+                    .unwrap_or_default(),
+                additional_properties: value
+                    .get("additionalProperties")
+                    .filter(|additional| additional.is_object())
+                    .map(|additional| Box::new(Schema::from_value(additional))),
+                info,
+            }),
+            Some("array") => Schema::Array(ArraySchema {
+                items: value.get("items").map(|items| Box::new(Schema::from_value(items))),
+                info,
+            }),
+            Some("string") => primitive(PrimitiveType::String, info),
+            Some("integer") => primitive(PrimitiveType::Integer, info),
+            Some("number") => primitive(PrimitiveType::Number, info),
+            Some("boolean") => primitive(PrimitiveType::Boolean, info),
+            _ => Schema::Any(info),
+        }
+    }
+    
+    /// Infer a schema from an example value, for inputs that only carry examples (Postman collections,
+    /// captured traffic). The example is recorded on the inferred schema.
+    pub fn from_example(example: &Value) -> Schema {
+        let mut schema = Schema::infer(example);
+        schema.set_example(example.clone());
+        schema
+    }
+    
+    fn infer(value: &Value) -> Schema {
+        let primitive = |kind| Schema::Primitive(PrimitiveSchema { kind, info: SchemaInfo::default() });
+        match value {
+            Value::Object(obj) => Schema::Object(ObjectSchema {
+                properties: obj.iter().map(|(name, value)| (name.clone(), Schema::infer(value))).collect(),
+                required: Vec::new(),
+                additional_properties: None,
+                info: SchemaInfo::default(),
+            }),
+            Value::Array(items) => Schema::Array(ArraySchema {
+                items: items.first().map(|item| Box::new(Schema::infer(item))),
+                info: SchemaInfo::default(),
+            }),
+            Value::String(_) => primitive(PrimitiveType::String),
+            Value::Number(number) if number.is_i64() || number.is_u64() => primitive(PrimitiveType::Integer),
+            Value::Number(_) => primitive(PrimitiveType::Number),
+            Value::Bool(_) => primitive(PrimitiveType::Boolean),
+            Value::Null => Schema::Any(SchemaInfo { nullable: true, ..SchemaInfo::default() }),
+        }
+    }
+    
+    /// The annotations of the schema, `None` for an unresolved reference
+    pub fn info(&self) -> Option<&SchemaInfo> {
+        match self {
+            Schema::Object(object) => Some(&object.info),
+            Schema::Array(array) => Some(&array.info),
+            Schema::Primitive(primitive) => Some(&primitive.info),
+            Schema::Composite(composite) => Some(&composite.info),
+            Schema::Any(info) => Some(info),
+            Schema::Ref(_) => None,
+        }
+    }
+    
+    /// The JSON type of the schema's values, if it has a single one
+    pub fn type_name(&self) -> Option<&'static str> {
+        match self {
+            Schema::Object(_) => Some("object"),
+            Schema::Array(_) => Some("array"),
+            Schema::Primitive(primitive) => Some(match primitive.kind {
+                PrimitiveType::String => "string",
+                PrimitiveType::Integer => "integer",
+                PrimitiveType::Number => "number",
+                PrimitiveType::Boolean => "boolean",
+            }),
+            _ => None,
+        }
+    }
+    
+    /// The schema of a property of an object schema
+    pub fn property(&self, name: &str) -> Option<&Schema> {
+        match self {
+            Schema::Object(object) => object.properties.get(name),
+            _ => None,
+        }
+    }
+    
+    /// The name of the schema a reference points at, the last segment of its JSON pointer
+    /// (`Category` for `#/components/schemas/Category`)
+    pub fn ref_name(&self) -> Option<&str> {
+        match self {
+            Schema::Ref(reference) => reference.rsplit(['/', '#']).next().filter(|name| !name.is_empty()),
+            _ => None,
+        }
+    }
+    
+    /// The documented example of the schema
+    pub fn example(&self) -> Option<&Value> {
+        self.info().and_then(|info| info.example.as_ref())
+    }
+    
+    /// Record an example on the schema, as media types do for the schema they contain
+    pub fn set_example(&mut self, example: Value) {
+        let info = match self {
+            Schema::Object(object) => &mut object.info,
+            Schema::Array(array) => &mut array.info,
+            Schema::Primitive(primitive) => &mut primitive.info,
+            Schema::Composite(composite) => &mut composite.info,
+            Schema::Any(info) => info,
+            Schema::Ref(_) => return,
+        };
+        info.example = Some(example);
+    }
+    
+    /// Render the schema as a (draft 4) JSON Schema validating response bodies: types, properties, items, enums
+    /// and constraints are kept, annotations and formats left out. Properties only sent in requests (`writeOnly`)
+    /// aren't required, and recursive references accept anything, since what they point at isn't carried along.
+    pub fn to_json_schema(&self) -> Value {
+        let mut json = Map::new();
+        
+        match self {
+            Schema::Object(object) => {
+                json.insert("type".to_string(), Value::from("object"));
+                let properties: Map<String, Value> = object.properties
+                    .iter()
+                    .map(|(name, property)| (name.clone(), property.to_json_schema()))
+                    .collect();
+                if !properties.is_empty() {
+                    json.insert("properties".to_string(), Value::Object(properties));
+                }
+                let required: Vec<Value> = object.required
+                    .iter()
+                    .filter(|name| !object.properties.get(*name).and_then(Schema::info).is_some_and(|info| info.write_only))
+                    .map(|name| Value::from(name.as_str()))
+                    .collect();
+                if !required.is_empty() {
+                    json.insert("required".to_string(), Value::Array(required));
+                }
+                if let Some(additional) = &object.additional_properties {
+                    json.insert("additionalProperties".to_string(), additional.to_json_schema());
+                }
+            },
+            Schema::Array(array) => {
+                json.insert("type".to_string(), Value::from("array"));
+                if let Some(items) = &array.items {
+                    json.insert("items".to_string(), items.to_json_schema());
+                }
+            },
+            Schema::Primitive(_) => {
+                json.insert("type".to_string(), Value::from(self.type_name()));
+            },
+            Schema::Composite(composite) => {
+                let keyword = match composite.kind {
+                    CompositeKind::OneOf => "oneOf",
+                    CompositeKind::AnyOf => "anyOf",
+                    CompositeKind::AllOf => "allOf",
+                };
+                let members = composite.members.iter().map(Schema::to_json_schema).collect();
+                json.insert(keyword.to_string(), Value::Array(members));
+            },
+            Schema::Ref(_) | Schema::Any(_) => {},
+        }
+        
+        if let Some(info) = self.info() {
+            if !info.enum_values.is_empty() {
+                let mut values = info.enum_values.clone();
+                if info.nullable && !values.contains(&Value::Null) {
+                    values.push(Value::Null);
+                }
+                json.insert("enum".to_string(), Value::Array(values));
+            }
+            
+            let constraints = &info.constraints;
+            let numbers = [("minimum", constraints.minimum), ("maximum", constraints.maximum)];
+            for (keyword, number) in numbers {
+                if let Some(number) = number.and_then(serde_json::Number::from_f64) {
+                    json.insert(keyword.to_string(), Value::Number(number));
+                }
+            }
+            if constraints.exclusive_minimum && constraints.minimum.is_some() {
+                json.insert("exclusiveMinimum".to_string(), Value::Bool(true));
+            }
+            if constraints.exclusive_maximum && constraints.maximum.is_some() {
+                json.insert("exclusiveMaximum".to_string(), Value::Bool(true));
+            }
+            let counts = [
+                ("minLength", constraints.min_length),
+                ("maxLength", constraints.max_length),
+                ("minItems", constraints.min_items),
+                ("maxItems", constraints.max_items),
+            ];
+            for (keyword, count) in counts {
+                if let Some(count) = count {
+                    json.insert(keyword.to_string(), Value::from(count));
+                }
+            }
+            if let Some(pattern) = &constraints.pattern {
+                json.insert("pattern".to_string(), Value::from(pattern.as_str()));
+            }
+            
+            // Draft 4 has no `nullable`, so null is added to the accepted types
+            if info.nullable {
+                if let Some(Value::String(type_name)) = json.get("type").cloned() {
+                    json.insert("type".to_string(), serde_json::json!([type_name, "null"]));
+                }
+            }
+        }
+        
+        Value::Object(json)
+    }
+}
+
+impl SchemaInfo {
+    /// Read the annotations of a JSON schema
+    pub fn from_value(value: &Value) -> SchemaInfo {
+        let text = |key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+        let flag = |key: &str| value.get(key).and_then(Value::as_bool).unwrap_or(false);
+        
+        let null_type = value.get("type").and_then(Value::as_array).is_some_and(|types| types.iter().any(|t| t == "null"));
+        let enum_values = match (value.get("enum").and_then(Value::as_array), value.get("const")) {
+            (Some(values), _) => values.clone(),
+            (None, Some(constant)) => vec![constant.clone()],
+            (None, None) => Vec::new(),
+        };
+        
+        SchemaInfo {
+            title: text("title"),
+            description: text("description"),
+            format: text("format"),
+            nullable: flag("nullable") || null_type,
+            read_only: flag("readOnly"),
+            write_only: flag("writeOnly"),
+            enum_values,
+            default: value.get("default").cloned(),
+            example: value.get("example").or_else(|| value.get("examples").and_then(|e| e.get(0))).cloned(),
+            constraints: Constraints::from_schema(value),
+            content_media_type: text("contentMediaType"),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+// This file contains the parsing of security schemes and requirements from the Swagger document.
+
+use serde_json::Value;
+
+/// A security scheme declared in `securityDefinitions` (Swagger 2.0)
+/// or `components.securitySchemes` (OpenAPI 3)
+#[derive(Debug, Clone)]
+pub struct SecurityScheme {
+    /// Name the scheme is declared under, as referenced by security requirements
+    pub name: String,
+
+    /// How credentials are passed for this scheme
+    pub kind: SecuritySchemeKind,
+
+    /// Description of the scheme
+    pub description: Option<String>,
+}
+
+/// The supported kinds of security scheme
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecuritySchemeKind {
+    /// API key sent in a header, query parameter or cookie
+    ApiKey { name: String, location: String },
+
+    /// HTTP basic authentication
+    Basic,
+
+    /// HTTP bearer token, with an optional hint about the token format (e.g. "JWT")
+    Bearer { format: Option<String> },
+
+    /// OAuth2 with one or more flows
+    OAuth2 { flows: Vec<OAuthFlow> },
+
+    /// OpenID Connect discovery
+    OpenIdConnect { url: String },
+
+    /// Any other scheme (e.g. `http` with a non-standard scheme or `mutualTLS`)
+    Other(String),
+}
+
+/// A single OAuth2 flow
+#[derive(Debug, Clone, PartialEq)]
+pub struct OAuthFlow {
+    /// Flow type, normalized to the OpenAPI 3 names
+    /// (implicit, password, clientCredentials, authorizationCode)
+    pub flow: String,
+
+    /// Authorization endpoint for implicit and authorization code flows
+    pub authorization_url: Option<String>,
+
+    /// Token endpoint for password, client credentials and authorization code flows
+    pub token_url: Option<String>,
+
+    /// Endpoint used to refresh tokens
+    pub refresh_url: Option<String>,
+
+    /// Names of the scopes available for this flow
+    pub scopes: Vec<String>,
+}
+
+/// One alternative of a `security` list. All schemes in it must be satisfied together,
+/// and an empty requirement means anonymous access is allowed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityRequirement {
+    /// Scheme names together with the scopes required from them
+    pub schemes: Vec<(String, Vec<String>)>,
+}
+
+/// Parse the `securityDefinitions` (Swagger 2.0) or `components.securitySchemes` (OpenAPI 3) map
+pub fn parse_security_schemes(definitions: Option<&Value>) -> Vec<SecurityScheme> {
+    let definitions = match definitions.and_then(Value::as_object) {
+        Some(definitions) => definitions,
+        None => return Vec::new(),
+    };
+
+    definitions
+        .iter()
+        .filter_map(|(name, scheme)| {
+            let scheme_type = scheme.get("type").and_then(Value::as_str)?;
+            let text = |key: &str| scheme.get(key).and_then(Value::as_str).map(String::from);
+
+            let kind = match scheme_type {
+                "apiKey" => SecuritySchemeKind::ApiKey {
+                    name: text("name").unwrap_or_default(),
+                    location: text("in").unwrap_or_else(|| "header".to_string()),
+                },
+                "basic" => SecuritySchemeKind::Basic,
+                "http" => match text("scheme").map(|s| s.to_lowercase()).as_deref() {
+                    Some("basic") => SecuritySchemeKind::Basic,
+                    Some("bearer") => SecuritySchemeKind::Bearer { format: text("bearerFormat") },
+                    other => SecuritySchemeKind::Other(format!("http {}", other.unwrap_or(""))),
+                },
+                "oauth2" => SecuritySchemeKind::OAuth2 { flows: parse_oauth_flows(scheme) },
+                "openIdConnect" => SecuritySchemeKind::OpenIdConnect {
+                    url: text("openIdConnectUrl").unwrap_or_default(),
+                },
+                other => SecuritySchemeKind::Other(other.to_string()),
+            };
+
+            Some(SecurityScheme {
+                name: name.clone(),
+                kind,
+                description: text("description"),
+            })
+        })
+        .collect()
+}
+
+/// Parse a `security` array into its alternatives
+pub fn parse_security_requirements(security: Option<&Value>) -> Vec<SecurityRequirement> {
+    let alternatives = match security.and_then(Value::as_array) {
+        Some(alternatives) => alternatives,
+        None => return Vec::new(),
+    };
+
+    alternatives
+        .iter()
+        .filter_map(Value::as_object)
+        .map(|requirement| SecurityRequirement {
+            schemes: requirement
+                .iter()
+                .map(|(name, scopes)| {
+                    let scopes = scopes
+                        .as_array()
+                        .map(|s| s.iter().filter_map(Value::as_str).map(String::from).collect())
+                        .unwrap_or_default();
+                    (name.clone(), scopes)
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Parse the OAuth2 flows of a scheme, in either the Swagger 2.0 (`flow`) or OpenAPI 3 (`flows`) layout
+fn parse_oauth_flows(scheme: &Value) -> Vec<OAuthFlow> {
+    let parse_flow = |flow: &str, details: &Value| {
+        let text = |key: &str| details.get(key).and_then(Value::as_str).map(String::from);
+        OAuthFlow {
+            flow: flow.to_string(),
+            authorization_url: text("authorizationUrl"),
+            token_url: text("tokenUrl"),
+            refresh_url: text("refreshUrl"),
+            scopes: details
+                .get("scopes")
+                .and_then(Value::as_object)
+                .map(|scopes| scopes.keys().cloned().collect())
+                .unwrap_or_default(),
+        }
+    };
+
+    if let Some(flows) = scheme.get("flows").and_then(Value::as_object) {
+        return flows.iter().map(|(flow, details)| parse_flow(flow, details)).collect();
+    }
+
+    // Swagger 2.0 describes a single flow inline, with older flow names
+    match scheme.get("flow").and_then(Value::as_str) {
+        Some(flow) => {
+            let flow = match flow {
+                "application" => "clientCredentials",
+                "accessCode" => "authorizationCode",
+                other => other,
+            };
+            vec![parse_flow(flow, scheme)]
+        },
+        None => Vec::new(),
+    }
+}
@@ -0,0 +1,98 @@
+// This file contains the parsing of OpenAPI 3 server objects and the substitution of their URL variables.
+
+use serde_json::Value;
+use std::fmt;
+
+/// A server the API is served from (OpenAPI 3 `servers` entry)
+#[derive(Debug, Clone)]
+pub struct ApiServer {
+    /// URL template, possibly containing `{variable}` placeholders
+    pub url: String,
+
+    /// Description of the server
+    pub description: Option<String>,
+
+    /// Variables that can be substituted into the URL template
+    pub variables: Vec<ServerVariable>,
+}
+
+/// A variable of a server URL template
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerVariable {
+    /// Name of the variable, as written between braces in the URL
+    pub name: String,
+
+    /// Value used when no other is given
+    pub default: String,
+
+    /// Allowed values, empty when any value is accepted
+    pub enum_values: Vec<String>,
+
+    /// Description of the variable
+    pub description: Option<String>,
+}
+
+/// Which of the documented servers tests are generated for
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerSelection {
+    /// Position in the `servers` array, starting at 0
+    Index(usize),
+
+    /// Description of the server, compared case-insensitively (e.g. "Staging")
+    Name(String),
+}
+
+impl fmt::Display for ServerSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerSelection::Index(index) => write!(f, "#{}", index),
+            ServerSelection::Name(name) => write!(f, "\"{}\"", name),
+        }
+    }
+}
+
+impl ApiServer {
+    /// The server URL with every variable replaced by its override from `overrides`, or its default
+    pub fn resolve_url(&self, overrides: &[(String, String)]) -> String {
+        self.variables.iter().fold(self.url.clone(), |url, variable| {
+            let value = overrides
+                .iter()
+                .rev()
+                .find(|(name, _)| *name == variable.name)
+                .map(|(_, value)| value.as_str())
+                .unwrap_or(&variable.default);
+            url.replace(&format!("{{{}}}", variable.name), value)
+        })
+    }
+}
+
+/// Parse a single entry of a `servers` array
+pub fn parse_server(server: &Value) -> Option<ApiServer> {
+    let text = |value: &Value, key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+
+    let variables = server
+        .get("variables")
+        .and_then(Value::as_object)
+        .map(|variables| {
+            variables
+                .iter()
+                .map(|(name, variable)| ServerVariable {
+                    name: name.clone(),
+                    default: text(variable, "default").unwrap_or_default(),
+                    enum_values: variable
+                        .get("enum")
+                        .and_then(Value::as_array)
+                        .map(|values| values.iter().filter_map(Value::as_str).map(String::from).collect())
+                        .unwrap_or_default(),
+                    description: text(variable, "description"),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(ApiServer {
+        url: text(server, "url")?,
+        description: text(server, "description"),
+        variables,
+    })
+}
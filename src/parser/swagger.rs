@@ -1,10 +1,26 @@
 // src/parser/swagger.rs
 
 use serde_json::{Value, Error as JsonError};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+use std::sync::Arc;
 use thiserror::Error;
+use super::resolver::RefResolver;
+use super::links::{parse_links, ApiLink};
+use super::pagination::Pagination;
+use super::har::{is_har, parse_har};
+use super::info::{parse_info, ApiInfo};
+use super::validate::child_pointer;
+use super::postman::{is_postman_collection, parse_postman_collection};
+use super::server::{parse_server, ApiServer, ServerSelection};
+use super::security::{parse_security_requirements, parse_security_schemes, SecurityRequirement, SecurityScheme};
+use super::schema::{
+    flatten_all_of, for_each_schema, normalize_schema_v3_1, schema_type, schema_variants,
+    ArraySchema, Constraints, ObjectSchema, PrimitiveSchema, PrimitiveType, Schema, SchemaInfo, SchemaVariant,
+};
 
 #[derive(Debug, Error)]
 pub enum ParserError {
@@ -14,26 +30,153 @@ pub enum ParserError {
     #[error("JSON parsing error: {0}")]
     JsonError(#[from] JsonError),
 
+    #[error("YAML parsing error: {0}")]
+    YamlError(#[from] serde_yaml::Error),
+
+    #[error("HTTP error: {0}")]
+    HttpError(#[from] reqwest::Error),
+
     #[error("Unsupported OpenAPI version")]
     UnsupportedVersion,
     
     #[error("Invalid OpenAPI specification: {0}")]
     InvalidSpec(String),
+
+    #[error("Unresolved reference: {0}")]
+    UnresolvedReference(String),
+
+    #[error("Failed to load external reference {0}: {1}")]
+    ExternalReference(String, String),
+
+    #[error("Unknown server variable: {0}")]
+    UnknownServerVariable(String),
+
+    #[error("Unknown server: {0}")]
+    UnknownServer(String),
 }
 
 pub type Result<T> = std::result::Result<T, ParserError>;
 
+/// A problem in the specification that parsing worked around
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParserWarning {
+    /// JSON pointer to the part of the document the problem is in, e.g. `/paths/~1users/get`
+    pub pointer: String,
+    
+    /// Description of the problem and what was done about it
+    pub message: String,
+}
+
+impl fmt::Display for ParserWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pointer = if self.pointer.is_empty() { "/" } else { &self.pointer };
+        write!(f, "{}: {}", pointer, self.message)
+    }
+}
+
 /// Represents a parsed OpenAPI/Swagger specification
 #[derive(Debug, Clone)]
 pub struct SwaggerSpec {
-    /// The raw JSON Value of the parsed specification
-    pub raw_spec: Value,
+    /// The raw JSON Value of the parsed specification, shared by clones of the parsed specification
+    pub raw_spec: Arc<Value>,
+    
+    /// Title, version, contact and license of the API
+    pub info: ApiInfo,
     
     /// Base URL for the API derived from the specification
     pub base_url: String,
     
+    /// The OpenAPI 3 server `base_url` was taken from, with its URL variables
+    pub server: Option<ApiServer>,
+    
+    /// Every server the API is documented at (OpenAPI 3), in document order
+    pub servers: Vec<ApiServer>,
+    
     /// All paths defined in the API
     pub paths: Vec<ApiPath>,
+    
+    /// Webhooks the API sends to its consumers (OpenAPI 3.1)
+    pub webhooks: Vec<ApiWebhook>,
+    
+    /// Security schemes declared by the API
+    pub security_schemes: Vec<SecurityScheme>,
+    
+    /// Security requirements applied to every operation unless overridden
+    pub security: Vec<SecurityRequirement>,
+    
+    /// Problems worked around while parsing, such as malformed parameters that were left out
+    /// or (in lenient mode) references that don't resolve
+    pub warnings: Vec<ParserWarning>,
+}
+
+impl SwaggerSpec {
+    /// Take `base_url` from another of the documented servers, with the defaults of its URL variables
+    pub fn select_server(&mut self, selection: &ServerSelection) -> Result<()> {
+        let server = match selection {
+            ServerSelection::Index(index) => self.servers.get(*index),
+            ServerSelection::Name(name) => self.servers.iter().find(|server| {
+                server.description.as_deref().is_some_and(|description| description.eq_ignore_ascii_case(name))
+            }),
+        };
+        
+        let server = server.cloned().ok_or_else(|| ParserError::UnknownServer(selection.to_string()))?;
+        self.base_url = server.resolve_url(&[]);
+        self.server = Some(server);
+        Ok(())
+    }
+    
+    /// Rebuild `base_url` from the server's URL template, substituting `overrides` for the
+    /// matching variables and defaults for the rest. Fails on a variable the server doesn't declare.
+    pub fn set_server_variables(&mut self, overrides: &[(String, String)]) -> Result<()> {
+        let server = match self.server.as_ref() {
+            Some(server) => server,
+            None if overrides.is_empty() => return Ok(()),
+            None => return Err(ParserError::UnknownServerVariable(overrides[0].0.clone())),
+        };
+        
+        if let Some((name, _)) = overrides.iter().find(|(name, _)| server.variables.iter().all(|v| v.name != *name)) {
+            return Err(ParserError::UnknownServerVariable(name.clone()));
+        }
+        
+        self.base_url = server.resolve_url(overrides);
+        Ok(())
+    }
+    
+    /// The operation a link points to, by operationId or by an `operationRef` into this document
+    pub fn link_target(&self, link: &ApiLink) -> Option<(&ApiPath, &ApiOperation)> {
+        let operations = || self.paths.iter().flat_map(|path| path.operations.iter().map(move |op| (path, op)));
+        
+        if let Some(operation_id) = link.operation_id.as_deref() {
+            return operations().find(|(_, op)| op.declared_operation_id.as_deref() == Some(operation_id));
+        }
+        
+        // Local references look like `#/paths/~1users~1{id}/get`
+        let pointer = link.operation_ref.as_deref()?.strip_prefix("#/paths/")?;
+        let (path, method) = pointer.rsplit_once('/')?;
+        let path = path.replace("~1", "/").replace("~0", "~");
+        operations().find(|(api_path, op)| api_path.path == path && op.method.eq_ignore_ascii_case(method))
+    }
+    
+    /// Send request bodies as `media_type` wherever an operation documents it,
+    /// keeping the parser's default choice for bodies that don't
+    pub fn select_content_type(&mut self, media_type: &str) {
+        let bodies = self.paths
+            .iter_mut()
+            .flat_map(|path| path.operations.iter_mut())
+            .filter_map(|operation| operation.body_param.as_mut());
+        
+        for body in bodies {
+            if let Some(schema) = body.content.get(media_type).cloned() {
+                // Subtypes were split from the previously selected schema
+                if schema != body.schema {
+                    body.variants.clear();
+                }
+                body.example = schema.as_ref().and_then(Schema::example).cloned();
+                body.schema = schema;
+                body.content_type = Some(media_type.to_string());
+            }
+        }
+    }
 }
 
 /// Represents an API path with its operations
@@ -46,15 +189,41 @@ pub struct ApiPath {
     pub operations: Vec<ApiOperation>,
 }
 
+/// Represents a webhook: requests the API makes to a consumer's endpoint when an event occurs
+#[derive(Debug, Clone)]
+pub struct ApiWebhook {
+    /// The name the webhook is declared under (e.g., "newPet")
+    pub name: String,
+    
+    /// The requests sent for this webhook, usually a single POST carrying the event payload
+    pub operations: Vec<ApiOperation>,
+}
+
+/// Represents a callback: a request the API sends to a URL supplied by the caller of an operation
+#[derive(Debug, Clone)]
+pub struct ApiCallback {
+    /// The name the callback is declared under (e.g., "onShipped")
+    pub name: String,
+    
+    /// Runtime expression giving the callback URL (e.g., "{$request.body#/callbackUrl}")
+    pub expression: String,
+    
+    /// The requests sent to that URL
+    pub operations: Vec<ApiOperation>,
+}
+
 /// Represents an API operation (HTTP method + path)
 #[derive(Debug, Clone)]
 pub struct ApiOperation {
     /// HTTP method (GET, POST, PUT, DELETE, etc.)
     pub method: String,
     
-    /// Operation ID from the spec, or generated if not present
+    /// Operation ID from the spec, or generated if not present, made unique across the spec and safe to use as an identifier
     pub operation_id: String,
     
+    /// The `operationId` as declared in the spec, which `operation_id` may differ from; `None` when it was generated
+    pub declared_operation_id: Option<String>,
+    
     /// Summary of what the operation does
     pub summary: Option<String>,
     
@@ -67,11 +236,52 @@ pub struct ApiOperation {
     /// Query parameters accepted by this operation
     pub query_params: Vec<ApiParameter>,
     
+    /// Header parameters accepted by this operation
+    pub header_params: Vec<ApiParameter>,
+    
+    /// Cookie parameters accepted by this operation (OpenAPI 3 only)
+    pub cookie_params: Vec<ApiParameter>,
+    
+    /// Form fields accepted by this operation (Swagger 2.0 `in: formData`), also combined into `body_param`
+    pub form_params: Vec<ApiParameter>,
+    
     /// Body parameters (if any) for this operation
     pub body_param: Option<ApiParameter>,
     
+    /// Media types request bodies can be sent as: Swagger 2.0 `consumes`, or the request body's media types in OpenAPI 3
+    pub consumes: Vec<String>,
+    
+    /// Media types responses can be returned as: Swagger 2.0 `produces`, or the responses' media types in OpenAPI 3
+    pub produces: Vec<String>,
+    
     /// Possible responses returned by this operation
     pub responses: Vec<ApiResponse>,
+    
+    /// Security requirements for this operation, inherited from the spec when not overridden
+    pub security: Vec<SecurityRequirement>,
+    
+    /// Tags grouping this operation with related ones
+    pub tags: Vec<String>,
+    
+    /// Whether the operation is marked as deprecated
+    pub deprecated: bool,
+    
+    /// Requests the API makes back to the caller as a result of this operation (OpenAPI 3 only)
+    pub callbacks: Vec<ApiCallback>,
+    
+    /// Vendor extensions (`x-*` keys) of the operation, by name
+    pub extensions: BTreeMap<String, Value>,
+    
+    /// The query parameters the operation pages its results with, if any (see `pagination`)
+    pub pagination: Option<Pagination>,
+}
+
+impl ApiOperation {
+    /// Whether the operation can only be called with credentials, i.e. it has security
+    /// requirements and none of the alternatives allows anonymous access
+    pub fn requires_auth(&self) -> bool {
+        !self.security.is_empty() && self.security.iter().all(|r| !r.schemes.is_empty())
+    }
 }
 
 /// Represents a parameter in an API operation
@@ -80,7 +290,7 @@ pub struct ApiParameter {
     /// Name of the parameter
     pub name: String,
     
-    /// Location of the parameter (path, query, body)
+    /// Location of the parameter (path, query, header, cookie, body)
     pub location: String,
     
     /// Whether the parameter is required
@@ -89,8 +299,38 @@ pub struct ApiParameter {
     /// Type of the parameter (string, integer, etc.)
     pub param_type: String,
     
+    /// Format refining the type (uuid, date-time, int64, etc.)
+    pub format: Option<String>,
+    
     /// Schema definition for complex parameters
-    pub schema: Option<Value>,
+    pub schema: Option<Schema>,
+    
+    /// Concrete subtypes when the schema is polymorphic (`oneOf`/`anyOf`), empty otherwise
+    pub variants: Vec<SchemaVariant>,
+    
+    /// Media type the request body is sent as (e.g. `application/json`, `multipart/form-data`),
+    /// `None` for parameters outside the body
+    pub content_type: Option<String>,
+    
+    /// Every media type documented for the request body, with its schema
+    pub content: BTreeMap<String, Option<Schema>>,
+    
+    /// Example value documented for the parameter; for request bodies, the example of the selected
+    /// media type, which is also recorded as its schema's `example`
+    pub example: Option<Value>,
+    
+    /// Value the server assumes when the parameter is left out, from its `default`
+    pub default: Option<Value>,
+    
+    /// Validation keywords limiting the parameter's values (bounds, lengths, pattern)
+    pub constraints: Constraints,
+    
+    /// Values the parameter is restricted to by an `enum` (for arrays, the values of their items),
+    /// empty when it isn't restricted
+    pub enum_values: Vec<Value>,
+    
+    /// Vendor extensions (`x-*` keys) of the parameter or request body, by name
+    pub extensions: BTreeMap<String, Value>,
 }
 
 /// Represents a possible API response
@@ -103,40 +343,252 @@ pub struct ApiResponse {
     pub description: Option<String>,
     
     /// Schema of the response body
-    pub schema: Option<Value>,
+    pub schema: Option<Schema>,
+    
+    /// Example body documented for the response: its JSON media type's example (OpenAPI 3), or its
+    /// `application/json` example (Swagger 2.0)
+    pub example: Option<Value>,
+    
+    /// Media types the response body is documented in: the keys of its `content` (OpenAPI 3), or the operation's
+    /// `produces` (Swagger 2.0)
+    pub media_types: Vec<String>,
+    
+    /// Headers documented on the response, such as `Location` or `ETag`
+    pub headers: Vec<ResponseHeader>,
+    
+    /// Links to operations that can be called with values from this response (OpenAPI 3 only)
+    pub links: Vec<ApiLink>,
+    
+    /// Vendor extensions (`x-*` keys) of the response, by name
+    pub extensions: BTreeMap<String, Value>,
+}
+
+/// Represents a header documented on a response
+#[derive(Debug, Clone)]
+pub struct ResponseHeader {
+    /// Name of the header
+    pub name: String,
+    
+    /// Description of the header
+    pub description: Option<String>,
+    
+    /// Whether the header is marked as required (OpenAPI 3 only)
+    pub required: bool,
+    
+    /// Schema of the header value
+    pub schema: Option<Schema>,
+}
+
+/// Options controlling how a specification is parsed
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    /// Follow `$ref`s into other files, relative to the directory of the spec
+    pub resolve_external: bool,
+    
+    /// Whether problems that would make parsing fail are worked around instead
+    pub mode: ParseMode,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            resolve_external: true,
+            mode: ParseMode::Strict,
+        }
+    }
+}
+
+/// How parsing deals with a specification that is partly broken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Fail on references that don't resolve
+    Strict,
+    
+    /// Leave references that don't resolve in place, recording a warning for each on `SwaggerSpec::warnings`,
+    /// so that the rest of a large specification can still be used
+    Lenient,
+}
+
+/// Serialization format of a specification document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecFormat {
+    Json,
+    Yaml,
+}
+
+impl SpecFormat {
+    /// Determine the format from a file extension, if it is a known one
+    pub fn from_path(path: &Path) -> Option<SpecFormat> {
+        match path.extension().and_then(|e| e.to_str()).map(str::to_lowercase).as_deref() {
+            Some("json") => Some(SpecFormat::Json),
+            Some("yaml") | Some("yml") => Some(SpecFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Guess the format from the document content.
+    /// JSON documents always start with an object or array, anything else is treated as YAML.
+    pub fn sniff(content: &str) -> SpecFormat {
+        match content.trim_start_matches('\u{feff}').trim_start().chars().next() {
+            Some('{') | Some('[') => SpecFormat::Json,
+            _ => SpecFormat::Yaml,
+        }
+    }
 }
 
 /// Parse a Swagger/OpenAPI specification from a file
 pub fn parse_swagger_file<P: AsRef<Path>>(path: P) -> Result<SwaggerSpec> {
+    parse_swagger_file_with_options(path, &ParserOptions::default())
+}
+
+/// Parse a Swagger/OpenAPI specification from a file with custom parser options
+pub fn parse_swagger_file_with_options<P: AsRef<Path>>(path: P, options: &ParserOptions) -> Result<SwaggerSpec> {
+    let path = path.as_ref();
+    let document = read_document(path)?;
+    
+    // External references are looked up relative to the spec's own directory
+    let base_dir = if options.resolve_external {
+        Some(path.parent().unwrap_or_else(|| Path::new(".")))
+    } else {
+        None
+    };
+    
+    parse_spec(document, base_dir, options.mode)
+}
+
+/// Read a JSON or YAML document from a file, without interpreting it as a specification
+pub fn read_document<P: AsRef<Path>>(path: P) -> Result<Value> {
+    let path = path.as_ref();
     let mut file = File::open(path)?;
     let mut content = String::new();
     file.read_to_string(&mut content)?;
     
-    parse_swagger_string(&content)
+    // Prefer the file extension, and fall back to looking at the content
+    let format = SpecFormat::from_path(path).unwrap_or_else(|| SpecFormat::sniff(&content));
+    
+    parse_document(&content, format)
 }
 
-/// Parse a Swagger/OpenAPI specification from a string
+/// Parse a Swagger/OpenAPI specification from a string, detecting JSON or YAML from the content
 pub fn parse_swagger_string(content: &str) -> Result<SwaggerSpec> {
-    let spec: Value = serde_json::from_str(content)?;
-    
+    parse_swagger_value(parse_document(content, SpecFormat::sniff(content))?)
+}
+
+/// Deserialize a JSON or YAML document into a JSON value
+pub fn parse_document(content: &str, format: SpecFormat) -> Result<Value> {
+    match format {
+        SpecFormat::Json => Ok(serde_json::from_str(content)?),
+        SpecFormat::Yaml => Ok(serde_yaml::from_str(content)?),
+    }
+}
+
+/// Parse a Swagger/OpenAPI specification from an already deserialized document
+pub fn parse_swagger_value(spec: Value) -> Result<SwaggerSpec> {
+    parse_swagger_value_with_options(spec, &ParserOptions::default())
+}
+
+/// Parse an already deserialized document with custom parser options. References into other files
+/// aren't followed, since there's no directory to look them up from.
+pub fn parse_swagger_value_with_options(spec: Value, options: &ParserOptions) -> Result<SwaggerSpec> {
+    parse_spec(spec, None, options.mode)
+}
+
+/// Parse a deserialized document, following external references from `base_dir` when given
+fn parse_spec(spec: Value, base_dir: Option<&Path>, mode: ParseMode) -> Result<SwaggerSpec> {
     // Determine version and validate
     let swagger_version = spec.get("swagger").and_then(Value::as_str);
     let openapi_version = spec.get("openapi").and_then(Value::as_str);
     
-    match (swagger_version, openapi_version) {
-        (Some("2.0"), _) => parse_swagger_v2(spec),
-        (_, Some(v)) if v.starts_with("3.") => parse_openapi_v3(spec),
-        _ => Err(ParserError::UnsupportedVersion),
+    // Postman collections and HAR captures are converted into the same structure
+    let mut parsed = if is_postman_collection(&spec) {
+        parse_postman_collection(spec)?
+    } else if is_har(&spec) {
+        parse_har(spec)?
+    } else {
+        match (swagger_version, openapi_version) {
+            (Some("2.0"), _) => parse_swagger_v2(spec, base_dir, mode)?,
+            (_, Some(v)) if v.starts_with("3.1") => parse_openapi_v3(spec, base_dir, mode, true)?,
+            (_, Some(v)) if v.starts_with("3.") => parse_openapi_v3(spec, base_dir, mode, false)?,
+            _ => return Err(ParserError::UnsupportedVersion),
+        }
+    };
+    
+    assign_operation_ids(&mut parsed);
+    Ok(parsed)
+}
+
+/// Warnings gathered while parsing, including why the references a lenient resolver left in place didn't resolve
+struct Warnings<'r, 'a> {
+    resolver: &'r RefResolver<'a>,
+    collected: Vec<ParserWarning>,
+}
+
+impl Warnings<'_, '_> {
+    fn push(&mut self, pointer: &str, message: String) {
+        self.collected.push(ParserWarning { pointer: pointer.to_string(), message });
+    }
+    
+    /// Record the references left unresolved since the last call, as found in the part of the document at `pointer`
+    fn take_unresolved(&mut self, pointer: &str) {
+        for problem in self.resolver.take_unresolved() {
+            self.push(pointer, format!("{}, left unresolved", problem));
+        }
+    }
+}
+
+/// What is wrong with a parameter, if it lacks a name or isn't in one of the `locations` a parameter can be in
+fn parameter_problem(name: &str, location: &str, locations: &[&str]) -> Option<String> {
+    if name.is_empty() {
+        Some("parameter has no name, left out".to_string())
+    } else if !locations.contains(&location) {
+        Some(format!("parameter \"{}\" has unknown location \"{}\", left out", name, location))
+    } else {
+        None
+    }
+}
+
+/// Make every operationId in the spec (operations and webhooks) unique and usable as an identifier in the generated
+/// code: characters other than letters, digits and `_` are replaced, and repeated ones get a `_2`, `_3`... suffix.
+/// Declared operationIds are assigned before generated ones, so that they keep their name when a generated one collides.
+fn assign_operation_ids(spec: &mut SwaggerSpec) {
+    let mut operations: Vec<&mut ApiOperation> = spec.paths
+        .iter_mut()
+        .flat_map(|path| path.operations.iter_mut())
+        .chain(spec.webhooks.iter_mut().flat_map(|webhook| webhook.operations.iter_mut()))
+        .collect();
+    operations.sort_by_key(|operation| operation.declared_operation_id.is_none());
+    
+    let mut taken = HashSet::new();
+    for operation in operations {
+        let base = identifier(&operation.operation_id);
+        let mut operation_id = base.clone();
+        let mut suffix = 1;
+        while taken.contains(&operation_id) {
+            suffix += 1;
+            operation_id = format!("{}_{}", base, suffix);
+        }
+        taken.insert(operation_id.clone());
+        operation.operation_id = operation_id;
+    }
+}
+
+/// An operationId made of letters, digits and `_`, not starting with a digit
+fn identifier(operation_id: &str) -> String {
+    let words: Vec<&str> = operation_id.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).filter(|w| !w.is_empty()).collect();
+    match words.join("_") {
+        id if id.is_empty() => "operation".to_string(),
+        id if id.starts_with(|c: char| c.is_ascii_digit()) => format!("op_{}", id),
+        id => id,
     }
 }
 
 /// Parse Swagger 2.0 specification
-fn parse_swagger_v2(spec: Value) -> Result<SwaggerSpec> {
+fn parse_swagger_v2(spec: Value, base_dir: Option<&Path>, mode: ParseMode) -> Result<SwaggerSpec> {
     // Extract base URL components
     let scheme = spec
         .get("schemes")
         .and_then(|s| s.as_array())
-        .and_then(|a| a.get(0))
+        .and_then(|a| a.first())
         .and_then(Value::as_str)
         .unwrap_or("http");
         
@@ -158,6 +610,18 @@ fn parse_swagger_v2(spec: Value) -> Result<SwaggerSpec> {
         None => return Err(ParserError::InvalidSpec("No paths defined".into())),
     };
     
+    let ref_resolver = match base_dir {
+        Some(dir) => RefResolver::external(&spec, dir),
+        None => RefResolver::new(&spec),
+    };
+    let ref_resolver = if mode == ParseMode::Lenient { ref_resolver.lenient() } else { ref_resolver };
+    let mut warnings = Warnings { resolver: &ref_resolver, collected: Vec::new() };
+    let resolve = |value: &Value| -> Result<Value> {
+        let mut resolved = ref_resolver.resolve(value)?;
+        for_each_schema(&mut resolved, &mut flatten_all_of);
+        Ok(resolved)
+    };
+    let global_security = parse_security_requirements(spec.get("security"));
     let mut paths = Vec::new();
     
     if let Some(paths_map) = paths_obj.as_object() {
@@ -174,12 +638,10 @@ fn parse_swagger_v2(spec: Value) -> Result<SwaggerSpec> {
                         continue;
                     }
                     
+                    let pointer = child_pointer(&child_pointer("/paths", path), method);
                     if let Some(op_obj) = operation.as_object() {
-                        let operation_id = op_obj
-                            .get("operationId")
-                            .and_then(Value::as_str)
-                            .unwrap_or(&format!("{}_{}", method, sanitize_path(path)))
-                            .to_string();
+                        let declared_operation_id = op_obj.get("operationId").and_then(Value::as_str).map(String::from);
+                        let operation_id = declared_operation_id.clone().unwrap_or_else(|| default_operation_id(method, path));
                             
                         let summary = op_obj
                             .get("summary")
@@ -194,94 +656,198 @@ fn parse_swagger_v2(spec: Value) -> Result<SwaggerSpec> {
                         // Parse parameters
                         let mut path_params = Vec::new();
                         let mut query_params = Vec::new();
+                        let mut header_params = Vec::new();
+                        let mut cookie_params = Vec::new();
+                        let mut form_params = Vec::new();
                         let mut body_param = None;
                         
-                        if let Some(params) = op_obj.get("parameters").and_then(Value::as_array) {
-                            for param in params {
-                                if let Some(param_obj) = param.as_object() {
-                                    let name = param_obj
-                                        .get("name")
-                                        .and_then(Value::as_str)
-                                        .unwrap_or("")
-                                        .to_string();
-                                        
-                                    let location = param_obj
-                                        .get("in")
-                                        .and_then(Value::as_str)
-                                        .unwrap_or("")
-                                        .to_string();
-                                        
-                                    let required = param_obj
-                                        .get("required")
-                                        .and_then(Value::as_bool)
-                                        .unwrap_or(false);
-                                        
-                                    let param_type = param_obj
-                                        .get("type")
-                                        .and_then(Value::as_str)
-                                        .unwrap_or_else(|| {
-                                            param_obj
-                                                .get("schema")
-                                                .and_then(|s| s.get("type"))
-                                                .and_then(Value::as_str)
-                                                .unwrap_or("object")
-                                        })
-                                        .to_string();
+                        // Operations override the media types declared for the whole API
+                        let consumes = media_types_v2(op_obj.get("consumes").or_else(|| spec.get("consumes")));
+                        let produces = media_types_v2(op_obj.get("produces").or_else(|| spec.get("produces")));
+                        
+                        for param in merged_parameters(path_item, operation, &resolve)? {
+                            if let Some(param_obj) = param.as_object() {
+                                let name = param_obj
+                                    .get("name")
+                                    .and_then(Value::as_str)
+                                    .unwrap_or("")
+                                    .to_string();
                                     
-                                    let schema = param_obj.get("schema").cloned();
+                                let location = param_obj
+                                    .get("in")
+                                    .and_then(Value::as_str)
+                                    .unwrap_or("")
+                                    .to_string();
+                                
+                                if let Some(problem) = parameter_problem(&name, &location, &["path", "query", "header", "cookie", "body", "formData"]) {
+                                    warnings.push(&pointer, problem);
+                                    continue;
+                                }
                                     
-                                    let api_param = ApiParameter {
-                                        name,
-                                        location: location.clone(),
-                                        required,
-                                        param_type,
-                                        schema,
-                                    };
+                                let required = param_obj
+                                    .get("required")
+                                    .and_then(Value::as_bool)
+                                    .unwrap_or(false);
                                     
-                                    match location.as_str() {
-                                        "path" => path_params.push(api_param),
-                                        "query" => query_params.push(api_param),
-                                        "body" => body_param = Some(api_param),
-                                        _ => {}
-                                    }
+                                let param_type = param_obj
+                                    .get("type")
+                                    .and_then(Value::as_str)
+                                    .unwrap_or_else(|| {
+                                        param_obj
+                                            .get("schema")
+                                            .and_then(|s| s.get("type"))
+                                            .and_then(Value::as_str)
+                                            .unwrap_or("object")
+                                    })
+                                    .to_string();
+                                
+                                // Swagger 2.0 array parameters other than bodies describe their items on the parameter itself
+                                let raw_schema = param_obj.get("schema");
+                                let schema = match raw_schema {
+                                    Some(raw_schema) => Some(Schema::from_value(raw_schema)),
+                                    None if param_type == "array" && location != "body" => Some(Schema::from_value(&param)),
+                                    None => None,
+                                };
+                                
+                                // Split polymorphic bodies into their subtypes
+                                let variants = match raw_schema {
+                                    Some(raw_schema) if location == "body" => body_variants(raw_schema, &spec, &resolve),
+                                    _ => Vec::new(),
+                                };
+                                
+                                // Bodies can be sent as any of the consumed media types, and as JSON when none is declared
+                                let content: BTreeMap<String, Option<Schema>> = match location.as_str() {
+                                    "body" if consumes.is_empty() => BTreeMap::from([("application/json".to_string(), schema.clone())]),
+                                    "body" => consumes.iter().map(|media_type| (media_type.clone(), schema.clone())).collect(),
+                                    _ => BTreeMap::new(),
+                                };
+                                let content_type = default_content_type(&content);
+                                
+                                // Swagger 2.0 bodies document their examples on the schema
+                                let example = if location == "body" {
+                                    schema.as_ref().and_then(Schema::example).cloned()
+                                } else {
+                                    documented_example(&param)
+                                };
+                                
+                                let api_param = ApiParameter {
+                                    name,
+                                    location: location.clone(),
+                                    required,
+                                    param_type,
+                                    format: parse_format(&param),
+                                    schema,
+                                    variants,
+                                    content_type,
+                                    content,
+                                    example,
+                                    default: parse_default(&param),
+                                    constraints: Constraints::from_schema(param.get("schema").unwrap_or(&param)),
+                                    enum_values: parse_enum_values(&param),
+                                    extensions: parse_extensions(&param),
+                                };
+                                
+                                match location.as_str() {
+                                    "path" => path_params.push(api_param),
+                                    "query" => query_params.push(api_param),
+                                    "header" => header_params.push(api_param),
+                                    "cookie" => cookie_params.push(api_param),
+                                    "body" => body_param = Some(api_param),
+                                    "formData" => form_params.push(api_param),
+                                    _ => {}
                                 }
+                            } else {
+                                warnings.push(&pointer, "parameter is not an object, left out".to_string());
                             }
                         }
                         
+                        // Form fields are sent together as the body: multipart for file uploads or when the
+                        // operation consumes it, URL-encoded otherwise
+                        if !form_params.is_empty() {
+                            let consumes_multipart = consumes.iter().any(|media_type| media_type == "multipart/form-data");
+                            let media_type = if consumes_multipart || form_params.iter().any(|field| field.param_type == "file") {
+                                "multipart/form-data"
+                            } else {
+                                "application/x-www-form-urlencoded"
+                            };
+                            body_param = Some(form_body(&form_params, media_type));
+                        }
+                        
                         // Parse responses
                         let mut responses = Vec::new();
                         
                         if let Some(resp_obj) = op_obj.get("responses").and_then(Value::as_object) {
                             for (status_code, response) in resp_obj {
+                                let response = resolve(response)?;
                                 if let Some(resp_obj) = response.as_object() {
                                     let description = resp_obj
                                         .get("description")
                                         .and_then(Value::as_str)
                                         .map(String::from);
                                         
-                                    let schema = resp_obj.get("schema").cloned();
+                                    let schema = resp_obj.get("schema").map(Schema::from_value);
+                                    let example = resp_obj
+                                        .get("examples")
+                                        .and_then(|examples| examples.get("application/json"))
+                                        .cloned();
                                     
                                     responses.push(ApiResponse {
                                         status_code: status_code.clone(),
                                         description,
                                         schema,
+                                        example,
+                                        media_types: produces.clone(),
+                                        headers: parse_response_headers(resp_obj.get("headers")),
+                                        links: Vec::new(),
+                                        extensions: parse_extensions(&response),
                                     });
                                 }
                             }
                         }
                         
+                        let tags = op_obj
+                            .get("tags")
+                            .and_then(Value::as_array)
+                            .map(|tags| tags.iter().filter_map(Value::as_str).map(String::from).collect())
+                            .unwrap_or_default();
+                        
+                        let deprecated = op_obj.get("deprecated").and_then(Value::as_bool).unwrap_or(false);
+                        
+                        // An operation level `security` (even an empty one) replaces the global requirements
+                        let security = match op_obj.get("security") {
+                            Some(security) => parse_security_requirements(Some(security)),
+                            None => global_security.clone(),
+                        };
+                        
+                        let extensions = parse_extensions(operation);
+                        let pagination = Pagination::detect(&query_params, &extensions);
                         let api_operation = ApiOperation {
                             method: method.to_uppercase(),
                             operation_id,
+                            declared_operation_id,
                             summary,
                             description,
                             path_params,
                             query_params,
+                            header_params,
+                            cookie_params,
+                            form_params,
                             body_param,
+                            consumes,
+                            produces,
                             responses,
+                            security,
+                            tags,
+                            deprecated,
+                            callbacks: Vec::new(),
+                            extensions,
+                            pagination,
                         };
                         
                         api_path.operations.push(api_operation);
+                        warnings.take_unresolved(&pointer);
+                    } else {
+                        warnings.push(&pointer, "operation is not an object, left out".to_string());
                     }
                 }
             }
@@ -292,202 +858,666 @@ fn parse_swagger_v2(spec: Value) -> Result<SwaggerSpec> {
         }
     }
     
+    let security_schemes = parse_security_schemes(spec.get("securityDefinitions"));
+    let warnings = warnings.collected;
     Ok(SwaggerSpec {
-        raw_spec: spec,
+        info: parse_info(spec.get("info")),
+        raw_spec: Arc::new(spec),
         base_url,
+        server: None,
+        servers: Vec::new(),
         paths,
+        webhooks: Vec::new(),
+        security_schemes,
+        security: global_security,
+        warnings,
     })
 }
 
-/// Parse OpenAPI 3.0 specification
-fn parse_openapi_v3(spec: Value) -> Result<SwaggerSpec> {
-    // Extract base URL components
-    let mut base_url = "http://localhost".to_string();
-    
-    if let Some(servers) = spec.get("servers").and_then(Value::as_array) {
-        if let Some(server) = servers.get(0) {
-            if let Some(url) = server.get("url").and_then(Value::as_str) {
-                base_url = url.to_string();
-            }
-        }
-    }
+/// Parse OpenAPI 3.0 or 3.1 specification.
+/// 3.1 documents have their JSON Schema constructs normalized to the 3.0 form the generators understand.
+fn parse_openapi_v3(spec: Value, base_dir: Option<&Path>, mode: ParseMode, is_v3_1: bool) -> Result<SwaggerSpec> {
+    // Extract base URL components from the first server, substituting the default of every server variable
+    let servers: Vec<ApiServer> = spec
+        .get("servers")
+        .and_then(Value::as_array)
+        .map(|servers| servers.iter().filter_map(parse_server).collect())
+        .unwrap_or_default();
+    let server = servers.first().cloned();
+    let base_url = server
+        .as_ref()
+        .map(|server| server.resolve_url(&[]))
+        .unwrap_or_else(|| "http://localhost".to_string());
     
-    // Extract paths
+    // Extract paths, which 3.1 allows to be left out in favour of webhooks or components
+    let empty_paths = Value::Object(serde_json::Map::new());
     let paths_obj = match spec.get("paths") {
         Some(paths) => paths,
+        None if is_v3_1 => &empty_paths,
         None => return Err(ParserError::InvalidSpec("No paths defined".into())),
     };
     
+    let ref_resolver = match base_dir {
+        Some(dir) => RefResolver::external(&spec, dir),
+        None => RefResolver::new(&spec),
+    };
+    let ref_resolver = if mode == ParseMode::Lenient { ref_resolver.lenient() } else { ref_resolver };
+    let mut warnings = Warnings { resolver: &ref_resolver, collected: Vec::new() };
+    let resolve = |value: &Value| -> Result<Value> {
+        let mut resolved = ref_resolver.resolve(value)?;
+        for_each_schema(&mut resolved, &mut |schema| {
+            if is_v3_1 {
+                normalize_schema_v3_1(schema);
+            }
+            flatten_all_of(schema);
+        });
+        Ok(resolved)
+    };
+    let global_security = parse_security_requirements(spec.get("security"));
     let mut paths = Vec::new();
     
     if let Some(paths_map) = paths_obj.as_object() {
         for (path, path_item) in paths_map {
-            let mut api_path = ApiPath {
+            let pointer = child_pointer("/paths", path);
+            let api_path = ApiPath {
                 path: path.clone(),
-                operations: Vec::new(),
+                operations: parse_path_item_v3(path, &pointer, path_item, &spec, &resolve, &global_security, &mut warnings)?,
             };
             
-            if let Some(path_obj) = path_item.as_object() {
-                for (method, operation) in path_obj {
-                    // Skip non-HTTP method keys
-                    if !["get", "post", "put", "delete", "patch", "options", "head"].contains(&method.as_str()) {
-                        continue;
-                    }
+            if !api_path.operations.is_empty() {
+                paths.push(api_path);
+            }
+        }
+    }
+    
+    // Webhooks (OpenAPI 3.1) are path items keyed by name instead of path
+    let mut webhooks = Vec::new();
+    
+    if let Some(webhooks_map) = spec.get("webhooks").and_then(Value::as_object) {
+        for (name, path_item) in webhooks_map {
+            let pointer = child_pointer("/webhooks", name);
+            let path_item = resolve(path_item)?;
+            warnings.take_unresolved(&pointer);
+            let webhook = ApiWebhook {
+                name: name.clone(),
+                operations: parse_path_item_v3(name, &pointer, &path_item, &spec, &resolve, &global_security, &mut warnings)?,
+            };
+            
+            if !webhook.operations.is_empty() {
+                webhooks.push(webhook);
+            }
+        }
+    }
+    
+    let security_definitions = spec
+        .pointer("/components/securitySchemes")
+        .map(|schemes| ref_resolver.resolve(schemes))
+        .transpose()?;
+    warnings.take_unresolved("/components/securitySchemes");
+    let security_schemes = parse_security_schemes(security_definitions.as_ref());
+    let warnings = warnings.collected;
+    Ok(SwaggerSpec {
+        info: parse_info(spec.get("info")),
+        raw_spec: Arc::new(spec),
+        base_url,
+        server,
+        servers,
+        paths,
+        webhooks,
+        security_schemes,
+        security: global_security,
+        warnings,
+    })
+}
+
+/// Parse the operations of an OpenAPI 3 path item, or of a webhook when `path` is the webhook's name.
+/// `item_pointer` locates the path item in the document, for warnings.
+fn parse_path_item_v3(
+    path: &str,
+    item_pointer: &str,
+    path_item: &Value,
+    spec: &Value,
+    resolve: &dyn Fn(&Value) -> Result<Value>,
+    global_security: &[SecurityRequirement],
+    warnings: &mut Warnings,
+) -> Result<Vec<ApiOperation>> {
+    let mut operations = Vec::new();
+    
+    if let Some(path_obj) = path_item.as_object() {
+        for (method, operation) in path_obj {
+            // Skip non-HTTP method keys
+            if !["get", "post", "put", "delete", "patch", "options", "head"].contains(&method.as_str()) {
+                continue;
+            }
+            
+            let pointer = child_pointer(item_pointer, method);
+            if let Some(op_obj) = operation.as_object() {
+                let declared_operation_id = op_obj.get("operationId").and_then(Value::as_str).map(String::from);
+                let operation_id = declared_operation_id.clone().unwrap_or_else(|| default_operation_id(method, path));
                     
-                    if let Some(op_obj) = operation.as_object() {
-                        let operation_id = op_obj
-                            .get("operationId")
+                let summary = op_obj
+                    .get("summary")
+                    .and_then(Value::as_str)
+                    .map(String::from);
+                    
+                let description = op_obj
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .map(String::from);
+                
+                // Parse parameters
+                let mut path_params = Vec::new();
+                let mut query_params = Vec::new();
+                let mut header_params = Vec::new();
+                let mut cookie_params = Vec::new();
+                
+                for param in merged_parameters(path_item, operation, &resolve)? {
+                    if let Some(param_obj) = param.as_object() {
+                        let name = param_obj
+                            .get("name")
                             .and_then(Value::as_str)
-                            .unwrap_or(&format!("{}_{}", method, sanitize_path(path)))
+                            .unwrap_or("")
                             .to_string();
                             
-                        let summary = op_obj
-                            .get("summary")
+                        let location = param_obj
+                            .get("in")
                             .and_then(Value::as_str)
-                            .map(String::from);
+                            .unwrap_or("")
+                            .to_string();
+                        
+                        if let Some(problem) = parameter_problem(&name, &location, &["path", "query", "header", "cookie"]) {
+                            warnings.push(&pointer, problem);
+                            continue;
+                        }
                             
-                        let description = op_obj
-                            .get("description")
-                            .and_then(Value::as_str)
-                            .map(String::from);
+                        let required = param_obj
+                            .get("required")
+                            .and_then(Value::as_bool)
+                            .unwrap_or(false);
+                            
+                        let schema = param_obj.get("schema").map(Schema::from_value);
                         
-                        // Parse parameters
-                        let mut path_params = Vec::new();
-                        let mut query_params = Vec::new();
+                        let param_type = if let Some(schema_ref) = param_obj.get("schema") {
+                            schema_type(schema_ref).unwrap_or("object").to_string()
+                        } else {
+                            "string".to_string()
+                        };
                         
-                        if let Some(params) = op_obj.get("parameters").and_then(Value::as_array) {
-                            for param in params {
-                                if let Some(param_obj) = param.as_object() {
-                                    let name = param_obj
-                                        .get("name")
-                                        .and_then(Value::as_str)
-                                        .unwrap_or("")
-                                        .to_string();
-                                        
-                                    let location = param_obj
-                                        .get("in")
-                                        .and_then(Value::as_str)
-                                        .unwrap_or("")
-                                        .to_string();
-                                        
-                                    let required = param_obj
-                                        .get("required")
-                                        .and_then(Value::as_bool)
-                                        .unwrap_or(false);
-                                        
-                                    let schema = param_obj.get("schema").cloned();
-                                    
-                                    let param_type = if let Some(schema_ref) = schema.as_ref() {
-                                        schema_ref
-                                            .get("type")
-                                            .and_then(Value::as_str)
-                                            .unwrap_or("object")
-                                            .to_string()
-                                    } else {
-                                        "string".to_string()
-                                    };
-                                    
-                                    let api_param = ApiParameter {
-                                        name,
-                                        location: location.clone(),
-                                        required,
-                                        param_type,
-                                        schema,
-                                    };
-                                    
-                                    match location.as_str() {
-                                        "path" => path_params.push(api_param),
-                                        "query" => query_params.push(api_param),
-                                        _ => {}
-                                    }
+                        let api_param = ApiParameter {
+                            name,
+                            location: location.clone(),
+                            required,
+                            param_type,
+                            format: parse_format(&param),
+                            schema,
+                            variants: Vec::new(),
+                            content_type: None,
+                            content: BTreeMap::new(),
+                            example: documented_example(&param),
+                            default: parse_default(&param),
+                            constraints: Constraints::from_schema(param.get("schema").unwrap_or(&param)),
+                            enum_values: parse_enum_values(&param),
+                            extensions: parse_extensions(&param),
+                        };
+                        
+                        match location.as_str() {
+                            "path" => path_params.push(api_param),
+                            "query" => query_params.push(api_param),
+                            "header" => header_params.push(api_param),
+                            "cookie" => cookie_params.push(api_param),
+                            _ => {}
+                        }
+                    } else {
+                        warnings.push(&pointer, "parameter is not an object, left out".to_string());
+                    }
+                }
+                
+                // Parse request body for OpenAPI 3.0
+                let request_body = op_obj.get("requestBody").map(resolve).transpose()?;
+                let body_param = request_body.and_then(|mut body| {
+                    let required = body
+                        .get("required")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false);
+                    let extensions = parse_extensions(&body);
+                    
+                    // The body was resolved for this operation alone, so its schemas are moved out rather than copied
+                    let raw_content: BTreeMap<String, Option<Value>> = body
+                        .get_mut("content")
+                        .and_then(Value::as_object_mut)?
+                        .iter_mut()
+                        .map(|(media_type, media)| {
+                            let mut schema = media.get_mut("schema").map(Value::take);
+                            
+                            // Media type examples take precedence over the schema's own
+                            if let Some(example) = documented_example(media) {
+                                if let Some(obj) = schema.get_or_insert_with(|| serde_json::json!({})).as_object_mut() {
+                                    obj.insert("example".to_string(), example);
                                 }
                             }
-                        }
-                        
-                        // Parse request body for OpenAPI 3.0
-                        let body_param = op_obj.get("requestBody").and_then(|body| {
-                            let required = body
-                                .get("required")
-                                .and_then(Value::as_bool)
-                                .unwrap_or(false);
-                                
-                            let content = body.get("content")?;
-                            let json_content = content.get("application/json")?;
-                            let schema = json_content.get("schema").cloned();
                             
-                            Some(ApiParameter {
-                                name: "body".to_string(),
-                                location: "body".to_string(),
-                                required,
-                                param_type: "object".to_string(),
-                                schema,
-                            })
-                        });
-                        
-                        // Parse responses
-                        let mut responses = Vec::new();
-                        
-                        if let Some(resp_obj) = op_obj.get("responses").and_then(Value::as_object) {
-                            for (status_code, response) in resp_obj {
-                                if let Some(resp_obj) = response.as_object() {
-                                    let description = resp_obj
-                                        .get("description")
-                                        .and_then(Value::as_str)
-                                        .map(String::from);
-                                    
-                                    let schema = if let Some(content) = resp_obj.get("content") {
-                                        if let Some(json_content) = content.get("application/json") {
-                                            json_content.get("schema").cloned()
-                                        } else {
-                                            None
-                                        }
-                                    } else {
-                                        None
-                                    };
-                                    
-                                    responses.push(ApiResponse {
-                                        status_code: status_code.clone(),
-                                        description,
-                                        schema,
-                                    });
+                            (media_type.clone(), schema)
+                        })
+                        .collect();
+                    let content_type = default_content_type(&raw_content)?;
+                    
+                    // Split polymorphic bodies into their subtypes
+                    let variants = raw_content[&content_type]
+                        .as_ref()
+                        .map(|schema| body_variants(schema, spec, resolve))
+                        .unwrap_or_default();
+                    
+                    let content: BTreeMap<String, Option<Schema>> = raw_content
+                        .iter()
+                        .map(|(media_type, schema)| (media_type.clone(), schema.as_ref().map(Schema::from_value)))
+                        .collect();
+                    let schema = content[&content_type].clone();
+                    let example = schema.as_ref().and_then(Schema::example).cloned();
+                    
+                    Some(ApiParameter {
+                        name: "body".to_string(),
+                        location: "body".to_string(),
+                        required,
+                        param_type: "object".to_string(),
+                        format: None,
+                        schema,
+                        variants,
+                        content_type: Some(content_type),
+                        content,
+                        example,
+                        default: None,
+                        constraints: Constraints::default(),
+                        enum_values: Vec::new(),
+                        extensions,
+                    })
+                });
+                
+                let consumes: Vec<String> = body_param
+                    .as_ref()
+                    .map(|body| body.content.keys().cloned().collect())
+                    .unwrap_or_default();
+                
+                // Parse responses
+                let mut responses = Vec::new();
+                let mut produces: Vec<String> = Vec::new();
+                
+                if let Some(resp_obj) = op_obj.get("responses").and_then(Value::as_object) {
+                    for (status_code, response) in resp_obj {
+                        let response = resolve(response)?;
+                        if let Some(resp_obj) = response.as_object() {
+                            let description = resp_obj
+                                .get("description")
+                                .and_then(Value::as_str)
+                                .map(String::from);
+                            
+                            let media_types: Vec<String> = resp_obj
+                                .get("content")
+                                .and_then(Value::as_object)
+                                .map(|content| content.keys().cloned().collect())
+                                .unwrap_or_default();
+                            for media_type in &media_types {
+                                if !produces.contains(media_type) {
+                                    produces.push(media_type.clone());
                                 }
                             }
+                            
+                            let json_content = resp_obj.get("content").and_then(|content| content.get("application/json"));
+                            let schema = json_content.and_then(|json_content| json_content.get("schema")).map(Schema::from_value);
+                            let example = json_content.and_then(documented_example);
+                            
+                            responses.push(ApiResponse {
+                                status_code: status_code.clone(),
+                                description,
+                                schema,
+                                example,
+                                media_types,
+                                headers: parse_response_headers(resp_obj.get("headers")),
+                                links: parse_links(resp_obj.get("links")),
+                                extensions: parse_extensions(&response),
+                            });
                         }
-                        
-                        let api_operation = ApiOperation {
-                            method: method.to_uppercase(),
-                            operation_id,
-                            summary,
-                            description,
-                            path_params,
-                            query_params,
-                            body_param,
-                            responses,
-                        };
-                        
-                        api_path.operations.push(api_operation);
                     }
                 }
-            }
-            
-            if !api_path.operations.is_empty() {
-                paths.push(api_path);
+                
+                let tags = op_obj
+                    .get("tags")
+                    .and_then(Value::as_array)
+                    .map(|tags| tags.iter().filter_map(Value::as_str).map(String::from).collect())
+                    .unwrap_or_default();
+                
+                let deprecated = op_obj.get("deprecated").and_then(Value::as_bool).unwrap_or(false);
+                
+                // Each callback maps runtime expressions for the URL to the path item of the request sent there
+                let mut callbacks = Vec::new();
+                if let Some(callbacks_obj) = op_obj.get("callbacks").and_then(Value::as_object) {
+                    for (name, callback) in callbacks_obj {
+                        let callback = resolve(callback)?;
+                        let callback_pointer = child_pointer(&child_pointer(&pointer, "callbacks"), name);
+                        for (expression, path_item) in callback.as_object().into_iter().flatten() {
+                            let item_pointer = child_pointer(&callback_pointer, expression);
+                            callbacks.push(ApiCallback {
+                                name: name.clone(),
+                                expression: expression.clone(),
+                                operations: parse_path_item_v3(expression, &item_pointer, path_item, spec, resolve, global_security, warnings)?,
+                            });
+                        }
+                    }
+                }
+                
+                // An operation level `security` (even an empty one) replaces the global requirements
+                let security = match op_obj.get("security") {
+                    Some(security) => parse_security_requirements(Some(security)),
+                    None => global_security.to_vec(),
+                };
+                
+                let extensions = parse_extensions(operation);
+                let pagination = Pagination::detect(&query_params, &extensions);
+                let api_operation = ApiOperation {
+                    method: method.to_uppercase(),
+                    operation_id,
+                    declared_operation_id,
+                    summary,
+                    description,
+                    path_params,
+                    query_params,
+                    header_params,
+                    cookie_params,
+                    form_params: Vec::new(),
+                    body_param,
+                    consumes,
+                    produces,
+                    responses,
+                    security,
+                    tags,
+                    deprecated,
+                    callbacks,
+                    extensions,
+                    pagination,
+                };
+                
+                operations.push(api_operation);
+                warnings.take_unresolved(&pointer);
+            } else {
+                warnings.push(&pointer, "operation is not an object, left out".to_string());
             }
         }
     }
     
-    Ok(SwaggerSpec {
-        raw_spec: spec,
-        base_url,
-        paths,
+    Ok(operations)
+}
+
+/// The parameters of an operation, including those declared on its path item.
+/// An operation level parameter replaces the path level one with the same name and location.
+fn merged_parameters(path_item: &Value, operation: &Value, resolve: &dyn Fn(&Value) -> Result<Value>) -> Result<Vec<Value>> {
+    let resolve_all = |item: &Value| -> Result<Vec<Value>> {
+        item.get("parameters")
+            .and_then(Value::as_array)
+            .map(|params| params.iter().map(resolve).collect())
+            .unwrap_or_else(|| Ok(Vec::new()))
+    };
+    let key = |param: &Value| (param.get("name").cloned(), param.get("in").cloned());
+    
+    let operation_params = resolve_all(operation)?;
+    let mut params: Vec<Value> = resolve_all(path_item)?
+        .into_iter()
+        .filter(|param| operation_params.iter().all(|op_param| key(op_param) != key(param)))
+        .collect();
+    params.extend(operation_params);
+    
+    Ok(params)
+}
+
+/// Parse the `headers` map of a response. Swagger 2.0 describes the header type inline,
+/// OpenAPI 3 nests it under `schema`.
+fn parse_response_headers(headers: Option<&Value>) -> Vec<ResponseHeader> {
+    let headers = match headers.and_then(Value::as_object) {
+        Some(headers) => headers,
+        None => return Vec::new(),
+    };
+    
+    headers
+        .iter()
+        .map(|(name, header)| ResponseHeader {
+            name: name.clone(),
+            description: header.get("description").and_then(Value::as_str).map(String::from),
+            required: header.get("required").and_then(Value::as_bool).unwrap_or(false),
+            schema: header
+                .get("schema")
+                .or_else(|| header.get("type").map(|_| header))
+                .map(Schema::from_value),
+        })
+        .collect()
+}
+
+/// Collect the vendor extensions (`x-*` keys) of a specification object
+fn parse_extensions(obj: &Value) -> BTreeMap<String, Value> {
+    obj.as_object()
+        .map(|obj| {
+            obj.iter()
+                .filter(|(key, _)| key.starts_with("x-"))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Combine form fields (Swagger 2.0 `formData`, or the form bodies of a Postman collection) into a body
+/// of the given form media type, with `type: file` fields as binary strings
+pub fn form_body(fields: &[ApiParameter], media_type: &str) -> ApiParameter {
+    let properties = fields
+        .iter()
+        .map(|field| {
+            let is_file = field.param_type == "file";
+            let info = SchemaInfo {
+                format: if is_file { Some("binary".to_string()) } else { field.format.clone() },
+                enum_values: field.enum_values.clone(),
+                default: field.default.clone(),
+                example: field.example.clone(),
+                constraints: field.constraints.clone(),
+                ..SchemaInfo::default()
+            };
+            let schema = match field.param_type.as_str() {
+                "array" => Schema::Array(ArraySchema { items: None, info }),
+                "integer" => Schema::Primitive(PrimitiveSchema { kind: PrimitiveType::Integer, info }),
+                "number" => Schema::Primitive(PrimitiveSchema { kind: PrimitiveType::Number, info }),
+                "boolean" => Schema::Primitive(PrimitiveSchema { kind: PrimitiveType::Boolean, info }),
+                _ => Schema::Primitive(PrimitiveSchema { kind: PrimitiveType::String, info }),
+            };
+            (field.name.clone(), schema)
+        })
+        .collect();
+    
+    let required: Vec<String> = fields
+        .iter()
+        .filter(|field| field.required)
+        .map(|field| field.name.clone())
+        .collect();
+    
+    let schema = Some(Schema::Object(ObjectSchema {
+        properties,
+        required: required.clone(),
+        additional_properties: None,
+        info: SchemaInfo::default(),
+    }));
+    let content_type = media_type.to_string();
+    
+    ApiParameter {
+        name: "body".to_string(),
+        location: "body".to_string(),
+        required: !required.is_empty(),
+        param_type: "object".to_string(),
+        format: None,
+        schema: schema.clone(),
+        variants: Vec::new(),
+        content_type: Some(content_type.clone()),
+        content: BTreeMap::from([(content_type, schema)]),
+        example: None,
+        default: None,
+        constraints: Constraints::default(),
+        enum_values: Vec::new(),
+        extensions: BTreeMap::new(),
+    }
+}
+
+/// A string parameter documented only by an example value, as Postman collections and HAR captures give them
+pub fn example_parameter(name: &str, location: &str, required: bool, value: Option<&str>) -> ApiParameter {
+    ApiParameter {
+        name: name.to_string(),
+        location: location.to_string(),
+        required,
+        param_type: "string".to_string(),
+        format: None,
+        schema: None,
+        variants: Vec::new(),
+        content_type: None,
+        content: BTreeMap::new(),
+        example: value.filter(|value| !value.is_empty()).map(|value| Value::String(value.to_string())),
+        default: None,
+        constraints: Constraints::default(),
+        enum_values: Vec::new(),
+        extensions: BTreeMap::new(),
+    }
+}
+
+/// A request body documented only by the text sent as `media_type`: JSON text gets a schema inferred from it,
+/// anything else is kept as a string
+pub fn example_body(text: &str, media_type: &str) -> ApiParameter {
+    let json = serde_json::from_str::<Value>(text).ok().filter(|_| media_type.contains("json"));
+    let (schema, example) = match json {
+        Some(json) => (Schema::from_example(&json), json),
+        None => {
+            let example = Value::String(text.to_string());
+            let info = SchemaInfo { example: Some(example.clone()), ..SchemaInfo::default() };
+            (Schema::Primitive(PrimitiveSchema { kind: PrimitiveType::String, info }), example)
+        },
+    };
+    
+    ApiParameter {
+        name: "body".to_string(),
+        location: "body".to_string(),
+        required: true,
+        param_type: "object".to_string(),
+        format: None,
+        schema: Some(schema.clone()),
+        variants: Vec::new(),
+        content_type: Some(media_type.to_string()),
+        content: BTreeMap::from([(media_type.to_string(), Some(schema))]),
+        example: Some(example),
+        default: None,
+        constraints: Constraints::default(),
+        enum_values: Vec::new(),
+        extensions: BTreeMap::new(),
+    }
+}
+
+/// The example documented on a parameter or media type: `example`, the first of its named `examples`,
+/// or the `x-example` extension used by Swagger 2.0 documents
+fn documented_example(obj: &Value) -> Option<Value> {
+    obj.get("example")
+        .or_else(|| obj.get("examples")?.as_object()?.values().next()?.get("value"))
+        .or_else(|| obj.get("x-example"))
+        .cloned()
+}
+
+/// The `format` of a parameter, declared on the parameter itself (Swagger 2.0) or its schema (OpenAPI 3)
+fn parse_format(param: &Value) -> Option<String> {
+    param.get("schema").unwrap_or(param).get("format").and_then(Value::as_str).map(String::from)
+}
+
+/// The `default` of a parameter, declared on the parameter itself (Swagger 2.0) or its schema (OpenAPI 3)
+fn parse_default(param: &Value) -> Option<Value> {
+    param.get("schema").unwrap_or(param).get("default").cloned()
+}
+
+/// The `enum` of a parameter, found on the parameter itself (Swagger 2.0) or its schema (OpenAPI 3),
+/// or on the items of an array parameter
+fn parse_enum_values(param: &Value) -> Vec<Value> {
+    let owner = param.get("schema").unwrap_or(param);
+    owner
+        .get("enum")
+        .or_else(|| owner.get("items")?.get("enum"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// The media types listed in a Swagger 2.0 `consumes` or `produces`
+fn media_types_v2(media_types: Option<&Value>) -> Vec<String> {
+    media_types
+        .and_then(Value::as_array)
+        .map(|media_types| media_types.iter().filter_map(Value::as_str).map(String::from).collect())
+        .unwrap_or_default()
+}
+
+/// Pick the media type a request body is sent as by default: JSON when documented,
+/// then multipart and form encodings, then whichever comes first
+fn default_content_type<T>(content: &BTreeMap<String, T>) -> Option<String> {
+    let documented = |media_type: &str| content.contains_key(media_type).then(|| media_type.to_string());
+    
+    documented("application/json")
+        .or_else(|| content.keys().find(|media_type| media_type.ends_with("+json")).cloned())
+        .or_else(|| documented("multipart/form-data"))
+        .or_else(|| documented("application/x-www-form-urlencoded"))
+        .or_else(|| content.keys().next().cloned())
+}
+
+/// Split a polymorphic request body schema into its subtypes, naming each one after its
+/// discriminator mapping entry or the component schema it was resolved from
+fn body_variants(schema: &Value, spec: &Value, resolve: &dyn Fn(&Value) -> Result<Value>) -> Vec<SchemaVariant> {
+    if schema.get("oneOf").is_none() && schema.get("anyOf").is_none() {
+        return Vec::new();
+    }
+    
+    // Resolve a reference the same way parameter and response schemas are resolved
+    let resolve_ref = |reference: String| -> Option<Value> {
+        let wrapped = serde_json::json!({ "schema": { "$ref": reference } });
+        resolve(&wrapped).ok().map(|resolved| resolved["schema"].clone())
+    };
+    
+    let mapping: Vec<(String, Value)> = schema
+        .pointer("/discriminator/mapping")
+        .and_then(Value::as_object)
+        .map(|mapping| {
+            mapping
+                .iter()
+                .filter_map(|(value, reference)| Some((value.clone(), resolve_ref(reference.as_str()?.to_string())?)))
+                .collect()
+        })
+        .unwrap_or_default();
+    
+    let (components, prefix) = match spec.get("definitions") {
+        Some(definitions) => (Some(definitions), "#/definitions/"),
+        None => (spec.pointer("/components/schemas"), "#/components/schemas/"),
+    };
+    let named: Vec<(String, Value)> = components
+        .and_then(Value::as_object)
+        .map(|components| {
+            components
+                .keys()
+                .filter_map(|name| Some((name.clone(), resolve_ref(format!("{}{}", prefix, name))?)))
+                .collect()
+        })
+        .unwrap_or_default();
+    
+    schema_variants(schema, |member| {
+        mapping
+            .iter()
+            .chain(named.iter())
+            .find(|(_, candidate)| candidate == member)
+            .map(|(name, _)| name.clone())
+            .or_else(|| member.get("title").and_then(Value::as_str).map(String::from))
     })
 }
 
-/// Helper function to sanitize path for use in operation IDs
-fn sanitize_path(path: &str) -> String {
-    path.replace('/', "_")
-        .replace('{', "")
-        .replace('}', "")
-        .trim_start_matches('_')
-        .to_string()
+/// The operationId for an operation that doesn't declare one, from its method and path:
+/// `get /users/{id}/orders` becomes `get_users_by_id_orders`
+pub fn default_operation_id(method: &str, path: &str) -> String {
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(param) => format!("by_{}", param),
+            None => segment.to_string(),
+        });
+    std::iter::once(method.to_lowercase()).chain(segments).collect::<Vec<_>>().join("_")
 }
\ No newline at end of file
@@ -0,0 +1,249 @@
+// This file contains the structural checks run on a Swagger document by the `validate` command.
+
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// HTTP methods that can appear as operations in a path item
+const METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The document is broken, and tests generated from it will be wrong or fail to generate
+    Error,
+
+    /// The document works, but something in it is likely a mistake
+    Warning,
+}
+
+/// A problem found in a specification
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    /// How serious the problem is
+    pub severity: Severity,
+
+    /// JSON pointer to the offending part of the document, e.g. `/paths/~1users/get`
+    pub pointer: String,
+
+    /// Description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let pointer = if self.pointer.is_empty() { "/" } else { &self.pointer };
+        write!(f, "{}: {}: {}", severity, pointer, self.message)
+    }
+}
+
+/// Check a deserialized (unresolved) document for missing and duplicate operationIds, undefined
+/// local `$ref`s, path parameters that don't match their path template and unreferenced schemas
+pub fn validate_document(spec: &Value) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    if spec.get("swagger").is_none() && spec.get("openapi").is_none() {
+        findings.push(error("", "document declares neither `swagger` nor `openapi` version"));
+    }
+
+    check_references(spec, spec, "", &mut findings);
+    check_operations(spec, &mut findings);
+    check_unreferenced_schemas(spec, &mut findings);
+
+    findings
+}
+
+/// Report every local `$ref` under `value` that doesn't point at anything
+fn check_references(spec: &Value, value: &Value, pointer: &str, findings: &mut Vec<Finding>) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+                if let Some(target) = reference.strip_prefix('#') {
+                    if spec.pointer(target).is_none() {
+                        findings.push(error(&format!("{}/$ref", pointer), &format!("reference \"{}\" does not resolve", reference)));
+                    }
+                }
+            }
+            for (key, child) in obj {
+                check_references(spec, child, &child_pointer(pointer, key), findings);
+            }
+        },
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                check_references(spec, item, &child_pointer(pointer, &index.to_string()), findings);
+            }
+        },
+        _ => {},
+    }
+}
+
+/// Check the operationIds and path parameters of every operation
+fn check_operations(spec: &Value, findings: &mut Vec<Finding>) {
+    let paths = match spec.get("paths").and_then(Value::as_object) {
+        Some(paths) => paths,
+        None => return,
+    };
+
+    // Where each operationId was first declared
+    let mut operation_ids: HashMap<&str, String> = HashMap::new();
+
+    for (path, path_item) in paths {
+        let path_pointer = child_pointer("/paths", path);
+        let template_names = path_template_names(path);
+
+        // Path parameters declared for every operation of the path
+        let shared_params = declared_path_params(spec, path_item, &path_pointer);
+
+        for method in METHODS {
+            let operation = match path_item.get(method) {
+                Some(operation) => operation,
+                None => continue,
+            };
+            let pointer = child_pointer(&path_pointer, method);
+
+            match operation.get("operationId").and_then(Value::as_str) {
+                Some(operation_id) => match operation_ids.get(operation_id) {
+                    Some(first) => findings.push(error(
+                        &format!("{}/operationId", pointer),
+                        &format!("operationId \"{}\" is already used by {}", operation_id, first),
+                    )),
+                    None => {
+                        operation_ids.insert(operation_id, pointer.clone());
+                    },
+                },
+                None => findings.push(warning(&pointer, "operation has no operationId")),
+            }
+
+            let mut params = shared_params.clone();
+            for (name, param_pointer) in declared_path_params(spec, operation, &pointer) {
+                // An operation's parameter overrides the path's parameter of the same name
+                params.retain(|(shared, _)| *shared != name);
+                params.push((name, param_pointer));
+            }
+
+            for name in &template_names {
+                if params.iter().all(|(declared, _)| declared != name) {
+                    findings.push(error(&pointer, &format!("path parameter \"{}\" is not declared", name)));
+                }
+            }
+            for (name, param_pointer) in &params {
+                if !template_names.contains(name) {
+                    findings.push(error(param_pointer, &format!("path parameter \"{}\" does not appear in \"{}\"", name, path)));
+                }
+            }
+        }
+    }
+}
+
+/// Names of the `in: path` parameters declared by a path item or operation, with their pointers.
+/// Parameters given as local references are looked up; unresolvable ones are reported elsewhere.
+fn declared_path_params(spec: &Value, owner: &Value, owner_pointer: &str) -> Vec<(String, String)> {
+    let params = match owner.get("parameters").and_then(Value::as_array) {
+        Some(params) => params,
+        None => return Vec::new(),
+    };
+
+    params
+        .iter()
+        .enumerate()
+        .filter_map(|(index, param)| {
+            let param = match param.get("$ref").and_then(Value::as_str) {
+                Some(reference) => spec.pointer(reference.strip_prefix('#')?)?,
+                None => param,
+            };
+            if param.get("in").and_then(Value::as_str) != Some("path") {
+                return None;
+            }
+            let name = param.get("name").and_then(Value::as_str)?;
+            Some((name.to_string(), format!("{}/parameters/{}", owner_pointer, index)))
+        })
+        .collect()
+}
+
+/// The `{name}` placeholders of a path template
+fn path_template_names(path: &str) -> Vec<String> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name.to_string()))
+        .collect()
+}
+
+/// Report named schemas that nothing outside of themselves refers to, directly or through other schemas
+fn check_unreferenced_schemas(spec: &Value, findings: &mut Vec<Finding>) {
+    let (prefix, schemas) = match (spec.pointer("/components/schemas"), spec.get("definitions")) {
+        (Some(schemas), _) => ("/components/schemas", schemas),
+        (None, Some(schemas)) => ("/definitions", schemas),
+        _ => return,
+    };
+    let schemas = match schemas.as_object() {
+        Some(schemas) => schemas,
+        None => return,
+    };
+
+    // Start from the references made outside of the schemas section
+    let mut pending = Vec::new();
+    collect_references(spec, "", prefix, &mut pending);
+
+    let mut reached = BTreeSet::new();
+    while let Some(reference) = pending.pop() {
+        let name = match reference.strip_prefix(&format!("#{}/", prefix)) {
+            Some(rest) => unescape(rest.split('/').next().unwrap_or(rest)),
+            None => continue,
+        };
+        if let Some(schema) = schemas.get(&name) {
+            if reached.insert(name) {
+                collect_references(schema, "", "", &mut pending);
+            }
+        }
+    }
+
+    for name in schemas.keys().filter(|name| !reached.contains(*name)) {
+        findings.push(warning(&child_pointer(prefix, name), &format!("schema \"{}\" is never referenced", name)));
+    }
+}
+
+/// Collect every `$ref` under `value`, except those under the `skip` pointer
+fn collect_references(value: &Value, pointer: &str, skip: &str, references: &mut Vec<String>) {
+    if !skip.is_empty() && pointer == skip {
+        return;
+    }
+
+    match value {
+        Value::Object(obj) => {
+            if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+                references.push(reference.to_string());
+            }
+            for (key, child) in obj {
+                collect_references(child, &child_pointer(pointer, key), skip, references);
+            }
+        },
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                collect_references(item, &child_pointer(pointer, &index.to_string()), skip, references);
+            }
+        },
+        _ => {},
+    }
+}
+
+/// Append a key to a JSON pointer, escaping `~` and `/`
+pub fn child_pointer(pointer: &str, key: &str) -> String {
+    format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Decode a JSON pointer token
+fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+fn error(pointer: &str, message: &str) -> Finding {
+    Finding { severity: Severity::Error, pointer: pointer.to_string(), message: message.to_string() }
+}
+
+fn warning(pointer: &str, message: &str) -> Finding {
+    Finding { severity: Severity::Warning, pointer: pointer.to_string(), message: message.to_string() }
+}
@@ -1,4 +1,5 @@
 pub mod helpers;
+pub mod synthesis;
 
 // Only re-export sanitize_path_for_filename since it's used in api_endpoints.rs
 pub use helpers::sanitize_path_for_filename;
\ No newline at end of file
@@ -23,15 +23,33 @@ pub fn get_relative_path<P: AsRef<Path>, B: AsRef<Path>>(path: P, base: B) -> Pa
 
 /// Sanitizes a path segment for use in filenames
 pub fn sanitize_path_for_filename(path: &str) -> String {
-    path.replace('/', "_")
-        .replace('\\', "_")
-        .replace('{', "")
-        .replace('}', "")
-        .replace(':', "")
+    path.replace(['/', '\\'], "_")
+        .replace(['{', '}', ':'], "")
         .trim_matches('_')
         .to_string()
 }
 
+/// Whether the text matches a glob pattern, in which `*` stands for any characters other than `/`, `**` for any
+/// characters and `?` for one character other than `/`
+pub fn glob_matches(pattern: &str, text: &str) -> bool {
+    fn matches(pattern: &[char], text: &[char]) -> bool {
+        match pattern {
+            [] => text.is_empty(),
+            ['*', '*', rest @ ..] => (0..=text.len()).any(|skipped| matches(rest, &text[skipped..])),
+            ['*', rest @ ..] => {
+                let segment = text.iter().position(|&c| c == '/').unwrap_or(text.len());
+                (0..=segment).any(|skipped| matches(rest, &text[skipped..]))
+            },
+            ['?', rest @ ..] => text.first().is_some_and(|&c| c != '/') && matches(rest, &text[1..]),
+            [c, rest @ ..] => text.first() == Some(c) && matches(rest, &text[1..]),
+        }
+    }
+    
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    matches(&pattern, &text)
+}
+
 /// Writes content to a file, creating parent directories if needed
 pub fn write_to_file<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, content: C) -> io::Result<()> {
     if let Some(parent) = path.as_ref().parent() {
@@ -46,11 +64,9 @@ pub fn write_to_file<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, content: C) -> io:
 /// Converts CamelCase to snake_case
 pub fn camel_to_snake(camel: &str) -> String {
     let mut snake = String::new();
-    let mut chars = camel.chars().peekable();
-    
-    while let Some(c) = chars.next() {
+    for c in camel.chars() {
         if c.is_uppercase() {
-            if !snake.is_empty() && snake.chars().last().unwrap() != '_' {
+            if !snake.is_empty() && !snake.ends_with('_') {
                 snake.push('_');
             }
             snake.push(c.to_lowercase().next().unwrap());
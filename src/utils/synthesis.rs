@@ -0,0 +1,158 @@
+// This file contains the synthesis of test values for parameters and schemas that don't document one.
+
+use regex_syntax::hir::{Class, Hir, HirKind};
+use serde_json::Value;
+use crate::parser::Constraints;
+
+/// A valid value for a string or number `format`, or `None` for formats without a fixed shape
+pub fn format_value(format: &str) -> Option<Value> {
+    let value = match format {
+        "uuid" => Value::from("3fa85f64-5717-4562-b3fc-2c963f66afa6"),
+        "date-time" => Value::from("2024-01-01T12:00:00Z"),
+        "date" => Value::from("2024-01-01"),
+        "time" => Value::from("12:00:00Z"),
+        "duration" => Value::from("P1D"),
+        "email" | "idn-email" => Value::from("user@example.com"),
+        "uri" | "url" | "iri" => Value::from("https://example.com"),
+        "uri-reference" | "iri-reference" => Value::from("/example"),
+        "hostname" | "idn-hostname" => Value::from("example.com"),
+        "ipv4" => Value::from("192.0.2.1"),
+        "ipv6" => Value::from("2001:db8::1"),
+        "byte" => Value::from("dGVzdA=="),
+        "password" => Value::from("P@ssw0rd123"),
+        "int32" | "int64" => Value::from(1),
+        "float" | "double" => Value::from(1.5),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// A value of the given type and format that satisfies the constraints, for parameters the specification gives no value for
+pub fn synthesize_value(param_type: &str, format: Option<&str>, constraints: &Constraints) -> Value {
+    if let Some(value) = format.and_then(format_value) {
+        return constrain(value, constraints);
+    }
+
+    let value = match param_type {
+        "integer" => Value::from(1),
+        "number" => Value::from(1.5),
+        "boolean" => Value::Bool(true),
+        _ => Value::from("test_value"),
+    };
+    constrain(value, constraints)
+}
+
+/// Adjust a synthesized value to the constraints: numbers are moved into the accepted range,
+/// strings are generated from the pattern when there is one, otherwise padded or cut to the accepted length
+pub fn constrain(value: Value, constraints: &Constraints) -> Value {
+    match value {
+        Value::Number(number) if number.is_i64() || number.is_u64() => {
+            let (lowest, highest) = constraints.accepted_range(1.0);
+            let mut value = number.as_f64().unwrap_or_default();
+            if let Some(lowest) = lowest.map(f64::ceil) {
+                value = value.max(lowest);
+            }
+            if let Some(highest) = highest.map(f64::floor) {
+                value = value.min(highest);
+            }
+            Value::from(value as i64)
+        },
+        Value::Number(number) => {
+            // Exclusive bounds are stepped inside by a fraction of the range, or by 1 without the other bound
+            let span = match (constraints.minimum, constraints.maximum) {
+                (Some(min), Some(max)) if max > min => (max - min) / 10.0,
+                _ => 1.0,
+            };
+            let (lowest, highest) = constraints.accepted_range(span);
+            let mut value = number.as_f64().unwrap_or_default();
+            if let Some(lowest) = lowest {
+                value = value.max(lowest);
+            }
+            if let Some(highest) = highest {
+                value = value.min(highest);
+            }
+            Value::from(value)
+        },
+        Value::String(text) => {
+            if let Some(generated) = constraints.pattern.as_deref().and_then(string_matching) {
+                return Value::String(generated);
+            }
+            Value::String(fit_length(text, constraints.min_length, constraints.max_length))
+        },
+        other => other,
+    }
+}
+
+/// Pad a string with `x` up to the minimum length, or cut it down to the maximum length
+fn fit_length(mut text: String, min_length: Option<u64>, max_length: Option<u64>) -> String {
+    let length = text.chars().count() as u64;
+    if let Some(min) = min_length.filter(|min| length < *min) {
+        text.extend(std::iter::repeat_n('x', (min - length) as usize));
+    }
+    if let Some(max) = max_length.filter(|max| length > *max) {
+        text = text.chars().take(max as usize).collect();
+    }
+    text
+}
+
+/// A short string matching a regular expression, or `None` when the expression can't be parsed
+pub fn string_matching(pattern: &str) -> Option<String> {
+    let hir = regex_syntax::parse(pattern).ok()?;
+    let mut text = String::new();
+    write_match(&hir, &mut text);
+    Some(text)
+}
+
+/// Append a string matched by `hir`, choosing the first alternative and the fewest repetitions
+/// (but at least one, so that `\d*` still produces a digit)
+fn write_match(hir: &Hir, text: &mut String) {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => {},
+        HirKind::Literal(literal) => text.push_str(&String::from_utf8_lossy(&literal.0)),
+        HirKind::Class(class) => {
+            if let Some(c) = class_member(class) {
+                text.push(c);
+            }
+        },
+        HirKind::Repetition(repetition) => {
+            let count = match (repetition.min, repetition.max) {
+                (0, Some(0)) => 0,
+                (0, _) => 1,
+                (min, _) => min,
+            };
+            for _ in 0..count {
+                write_match(&repetition.sub, text);
+            }
+        },
+        HirKind::Capture(capture) => write_match(&capture.sub, text),
+        HirKind::Concat(parts) => parts.iter().for_each(|part| write_match(part, text)),
+        HirKind::Alternation(alternatives) => {
+            if let Some(first) = alternatives.first() {
+                write_match(first, text);
+            }
+        },
+    }
+}
+
+/// A readable member of a character class: a letter or digit when there is one, otherwise any printable character
+fn class_member(class: &Class) -> Option<char> {
+    let chars: Vec<char> = match class {
+        Class::Unicode(class) => class
+            .ranges()
+            .iter()
+            .flat_map(|range| (range.start()..=range.end()).take(128))
+            .collect(),
+        Class::Bytes(class) => class
+            .ranges()
+            .iter()
+            .flat_map(|range| (range.start()..=range.end()).map(char::from))
+            .collect(),
+    };
+
+    chars
+        .iter()
+        .find(|c| c.is_ascii_alphanumeric())
+        .or_else(|| chars.iter().find(|c| c.is_ascii_graphic()))
+        .or_else(|| chars.first())
+        .copied()
+}
@@ -3,13 +3,24 @@
 #[cfg(test)]
 mod tests {
     use swagger_test_generator::{
-        cli::TestFramework,
-        parser::parse_swagger_file,
-        generator::{create_generator, generate_axum_api},
+        generate_tests_from_spec, ignored_options, GenerateOptions,
+        cli::{args::{parse_assert_level, parse_auth, parse_duration, parse_max_response_time, parse_layout, parse_method, parse_status_coverage}, TestFramework},
+        utils::helpers::glob_matches,
+        parser::{bundle_file, ApiInfo, diff_specs, write_document, Change, ChangeKind, CompositeKind, Constraints, SecuritySchemeKind, parse_swagger_file, parse_swagger_file_with_options, parse_swagger_string, parse_swagger_url, FetchOptions, LinkValue, Pagination, ParserError, ParserOptions, ParseMode, RefResolver, ServerSelection, Schema, Severity, SpecFormat, SwaggerSpec, read_document, validate_document},
+        generator::{data::{example_from_schema, with_seed}, datasets::{DataSetError, DataSets}, fuzz::Strategy, layout::{operation_groups, OutputLayout}, lifecycle::{lifecycles, Action}, links::{self, incoming_link, Dependency}, negative::StatusCoverage, create_generator, create_generator_with_options, generate_axum_api, AssertLevel, AuthConfig, AuthSource, GeneratorError, GeneratorOptions},
     };
-    use std::path::{Path, PathBuf};
+    use std::path::PathBuf;
     use std::fs;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
 
+    /// Options sending the optional query parameters too, for tests checking the values query parameters get
+    fn with_optional_query_params() -> GeneratorOptions {
+        GeneratorOptions { optional_query_params: true, ..GeneratorOptions::default() }
+    }
+    
     fn get_test_data_path(file_name: &str) -> PathBuf {
         let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         path.push("tests");
@@ -37,85 +48,5321 @@ mod tests {
     }
 
     #[test]
-    fn test_generate_reqwest_tests() {
+    fn test_parse_yaml_swagger_file() {
+        let swagger_path = get_test_data_path("sample_openapi.yaml");
+        let spec = parse_swagger_file(&swagger_path).unwrap();
+        assert_eq!(spec.base_url, "http://api.sample.com/v1");
+        
+        let all_operations: usize = spec.paths
+            .iter()
+            .map(|p| p.operations.len())
+            .sum();
+        assert_eq!(all_operations, 3);
+        
+        // Unquoted YAML status codes are still read as response keys
+        let get_users = spec.paths.iter().find(|p| p.path == "/users").unwrap().operations
+            .iter().find(|o| o.operation_id == "getUsers").unwrap();
+        assert_eq!(get_users.responses[0].status_code, "200");
+        
+        // Content without a known extension is sniffed
+        let content = fs::read_to_string(&swagger_path).unwrap();
+        assert!(parse_swagger_string(&content).is_ok());
+    }
+
+    #[test]
+    fn test_parse_resolves_local_refs() {
+        let swagger_path = get_test_data_path("sample_swagger.json");
+        let spec = parse_swagger_file(&swagger_path).unwrap();
+        
+        let create_user = spec.paths.iter()
+            .flat_map(|p| p.operations.iter())
+            .find(|o| o.operation_id == "createUser")
+            .unwrap();
+        
+        // Body schema is inlined from #/definitions/UserCreate
+        let body_schema = create_user.body_param.as_ref().unwrap().schema.as_ref().unwrap();
+        assert!(!matches!(body_schema, Schema::Ref(_)));
+        assert!(body_schema.property("email").is_some());
+        
+        // Response schema is inlined from #/definitions/User
+        let created = create_user.responses.iter().find(|r| r.status_code == "201").unwrap();
+        assert_eq!(created.schema.as_ref().unwrap().property("id").unwrap().type_name(), Some("integer"));
+    }
+
+    #[test]
+    fn test_bundle() {
+        let bundled = bundle_file(get_test_data_path("multi_file").join("openapi.yaml")).unwrap();
+        
+        // References into other files are inlined, the recursive one is moved into the components
+        let yaml = write_document(&bundled, SpecFormat::Yaml).unwrap();
+        assert!(!yaml.contains(".yaml#"));
+        let category = &bundled["components"]["schemas"]["Category"];
+        assert_eq!(category["properties"]["owner"]["properties"]["email"]["format"], "email");
+        assert_eq!(category["properties"]["children"]["items"]["$ref"], "#/components/schemas/Category");
+        let body = &bundled["paths"]["/categories"]["post"]["requestBody"]["content"]["application/json"]["schema"];
+        assert_eq!(body["$ref"], "#/components/schemas/Category");
+        
+        // The bundle parses on its own, in either format
+        let spec = parse_swagger_string(&yaml).unwrap();
+        let schema = spec.paths[0].operations[0].body_param.as_ref().unwrap().schema.as_ref().unwrap();
+        assert_eq!(schema.property("name").unwrap().type_name(), Some("string"));
+        let json = write_document(&bundled, SpecFormat::Json).unwrap();
+        assert!(parse_swagger_string(&json).is_ok());
+        
+        // References within a single-file specification are kept as they are
         let swagger_path = get_test_data_path("sample_swagger.json");
+        assert_eq!(bundle_file(&swagger_path).unwrap(), read_document(&swagger_path).unwrap());
+    }
+
+    #[test]
+    fn test_parse_resolves_external_refs() {
+        let swagger_path = get_test_data_path("multi_file").join("openapi.yaml");
         let spec = parse_swagger_file(&swagger_path).unwrap();
         
+        let operation = &spec.paths[0].operations[0];
+        let body_schema = operation.body_param.as_ref().unwrap().schema.as_ref().unwrap();
+        
+        // Refs into sibling files are followed relative to the referencing file
+        let email = body_schema.property("owner").and_then(|owner| owner.property("email")).unwrap();
+        assert_eq!(email.info().unwrap().format.as_deref(), Some("email"));
+        
+        // The self-referencing `children` items are kept as a reference
+        match body_schema.property("children") {
+            Some(Schema::Array(children)) => assert_eq!(children.items.as_deref(), Some(&Schema::Ref("#/Category".to_string()))),
+            other => panic!("Expected an array of children, got {:?}", other),
+        }
+        
+        // With external resolution disabled the reference is passed through
+        let options = ParserOptions { resolve_external: false, ..ParserOptions::default() };
+        let spec = parse_swagger_file_with_options(&swagger_path, &options).unwrap();
+        let body_schema = spec.paths[0].operations[0].body_param.as_ref().unwrap().schema.clone().unwrap();
+        assert_eq!(body_schema, Schema::Ref("./schemas/category.yaml#/Category".to_string()));
+    }
+
+    #[test]
+    fn test_parse_swagger_url() {
+        let content = fs::read_to_string(get_test_data_path("sample_openapi.yaml")).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        
+        // Serve the YAML spec once and hand back the raw request for inspection
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 4096];
+            let len = stream.read(&mut request).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/yaml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                content.len(),
+                content
+            ).unwrap();
+            String::from_utf8_lossy(&request[..len]).to_lowercase()
+        });
+        
+        let options = FetchOptions {
+            headers: vec![("Authorization".to_string(), "Bearer secret".to_string())],
+            ..FetchOptions::default()
+        };
+        let spec = parse_swagger_url(&format!("http://127.0.0.1:{}/openapi", port), &options).unwrap();
+        assert_eq!(spec.base_url, "http://api.sample.com/v1");
+        
+        let request = server.join().unwrap();
+        assert!(request.contains("authorization: bearer secret"));
+    }
+
+    #[test]
+    fn test_parse_openapi_3_1() {
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi_3_1.yaml")).unwrap();
+        let operation = &spec.paths[0].operations[0];
+        
+        // `type: [integer, "null"]` is read as a nullable integer
+        let id = &operation.path_params[0];
+        assert_eq!(id.param_type, "integer");
+        assert!(id.schema.as_ref().unwrap().info().unwrap().nullable);
+        
+        // The type of a `const` schema is inferred from its value
+        let view = &operation.query_params[0];
+        assert_eq!(view.param_type, "string");
+        assert_eq!(view.schema.as_ref().unwrap().info().unwrap().enum_values, vec![serde_json::json!("summary")]);
+        
+        // Numeric exclusive bounds become 3.0 style bounds, and property names are left alone
+        let schema = operation.responses[0].schema.as_ref().unwrap();
+        let age = &schema.property("age").unwrap().info().unwrap().constraints;
+        assert_eq!(age.minimum, Some(0.0));
+        assert!(age.exclusive_minimum);
+        assert!(schema.property("const").unwrap().info().unwrap().enum_values.is_empty());
+        
+        // 3.1 documents may omit paths entirely
+        let webhooks_only = "openapi: 3.1.0\ninfo:\n  title: Hooks\n  version: 1.0.0\nwebhooks: {}\n";
+        assert!(parse_swagger_string(webhooks_only).unwrap().paths.is_empty());
+    }
+
+    #[test]
+    fn test_parse_flattens_all_of() {
+        let spec = parse_swagger_file(get_test_data_path("sample_allof.yaml")).unwrap();
+        let operation = &spec.paths[0].operations[0];
+        let schema = operation.responses[0].schema.as_ref().unwrap();
+        
+        let object = match schema {
+            Schema::Object(object) => object,
+            other => panic!("Expected a flattened object, got {:?}", other),
+        };
+        assert!(object.properties.contains_key("id"));
+        assert!(object.properties.contains_key("name"));
+        assert!(object.properties.contains_key("tag"));
+        assert_eq!(object.required, vec!["name", "id"]);
+    }
+
+    #[test]
+    fn test_polymorphic_body_variants() {
+        let spec = parse_swagger_file(get_test_data_path("sample_polymorphic.yaml")).unwrap();
+        let body = spec.paths[0].operations[0].body_param.as_ref().unwrap();
+        
+        // Names come from the discriminator mapping, then the component name
+        let names: Vec<&str> = body.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["cat", "Dog"]);
+        let pet_type = body.variants[0].schema.property("petType").unwrap();
+        assert_eq!(pet_type.info().unwrap().enum_values, vec![serde_json::json!("cat")]);
+        
         let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("target")
             .join("test-output")
-            .join("reqwest");
-        
-        // Clean previous test output
+            .join("polymorphic");
         if test_output_dir.exists() {
             fs::remove_dir_all(&test_output_dir).unwrap();
         }
-        fs::create_dir_all(&test_output_dir).unwrap();
         
-        // Generate tests
+        // Every framework gets one test per subtype, with the discriminator set
+        let generator = create_generator(TestFramework::Pytest).unwrap();
+        generator.generate_tests(&spec, &test_output_dir, "http://localhost:3000").unwrap();
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains("def test_createPet_cat():"));
+        assert!(pytest.contains("def test_createPet_dog():"));
+        assert!(pytest.contains(r#""petType": "cat""#));
+        assert!(pytest.contains(r#""huntingSkill": "lazy""#));
+        
         let generator = create_generator(TestFramework::Reqwest).unwrap();
-        let result = generator.generate_tests(&spec, &test_output_dir, "http://localhost:3000");
+        generator.generate_tests(&spec, &test_output_dir, "http://localhost:3000").unwrap();
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("async fn test_create_pet_dog()"));
         
-        assert!(result.is_ok());
+        let generator = create_generator(TestFramework::Jest).unwrap();
+        generator.generate_tests(&spec, &test_output_dir, "http://localhost:3000").unwrap();
+        let jest = fs::read_to_string(test_output_dir.join("pets.test.js")).unwrap();
+        assert!(jest.contains("test('createPet (Dog)'"));
         
-        // Check that test files were created
-        assert!(test_output_dir.join("api_tests.rs").exists());
-        assert!(test_output_dir.join("main.rs").exists());
-        assert!(test_output_dir.join("Cargo.toml").exists());
+        let generator = create_generator(TestFramework::Postman).unwrap();
+        generator.generate_tests(&spec, &test_output_dir, "http://localhost:3000").unwrap();
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let requests = collection["item"][0]["item"].as_array().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1]["request"]["body"]["raw"].as_str().unwrap().contains("packSize"));
     }
 
     #[test]
-    fn test_generate_pytest_tests() {
-        let swagger_path = get_test_data_path("sample_swagger.json");
-        let spec = parse_swagger_file(&swagger_path).unwrap();
+    fn test_parse_security_schemes() {
+        let spec = parse_swagger_file(get_test_data_path("sample_security.yaml")).unwrap();
+        
+        let kind = |name: &str| spec.security_schemes.iter().find(|s| s.name == name).unwrap().kind.clone();
+        assert_eq!(kind("bearerAuth"), SecuritySchemeKind::Bearer { format: Some("JWT".to_string()) });
+        assert_eq!(kind("basicAuth"), SecuritySchemeKind::Basic);
+        assert_eq!(kind("apiKeyAuth"), SecuritySchemeKind::ApiKey {
+            name: "X-API-Key".to_string(),
+            location: "header".to_string(),
+        });
+        match kind("oauth") {
+            SecuritySchemeKind::OAuth2 { flows } => {
+                assert_eq!(flows[0].flow, "clientCredentials");
+                assert_eq!(flows[0].token_url.as_deref(), Some("https://auth.sample.com/token"));
+                assert_eq!(flows[0].scopes, vec!["read", "write"]);
+            },
+            other => panic!("unexpected scheme {:?}", other),
+        }
+        
+        // Global requirements are alternatives, each combining one or more schemes
+        assert_eq!(spec.security.len(), 2);
+        assert_eq!(spec.security[1].schemes, vec![
+            ("apiKeyAuth".to_string(), vec![]),
+            ("oauth".to_string(), vec!["read".to_string()]),
+        ]);
+        
+        // Swagger 2.0 definitions use the older flow names
+        let v2 = r#"{"swagger": "2.0", "paths": {}, "securityDefinitions": {
+            "legacy": {"type": "oauth2", "flow": "application", "tokenUrl": "https://auth/token", "scopes": {}},
+            "basic": {"type": "basic"}
+        }}"#;
+        let spec = parse_swagger_string(v2).unwrap();
+        assert_eq!(spec.security_schemes.len(), 2);
+        assert!(matches!(&spec.security_schemes[1].kind, SecuritySchemeKind::OAuth2 { flows } if flows[0].flow == "clientCredentials"));
+    }
+
+    #[test]
+    fn test_parse_operation_security() {
+        let spec = parse_swagger_file(get_test_data_path("sample_security.yaml")).unwrap();
+        let operation = |id: &str| spec.paths.iter()
+            .flat_map(|p| p.operations.iter())
+            .find(|o| o.operation_id == id)
+            .unwrap();
+        
+        // Inherited from the global requirements
+        assert_eq!(operation("getUsers").security, spec.security);
+        assert!(operation("getUsers").requires_auth());
+        
+        // Overridden at the operation level
+        let create = operation("createUser");
+        assert_eq!(create.security.len(), 1);
+        assert_eq!(create.security[0].schemes[0], ("oauth".to_string(), vec!["write".to_string()]));
+        
+        // An explicit empty list opts out of authentication
+        assert!(operation("getHealth").security.is_empty());
+        assert!(!operation("getHealth").requires_auth());
+    }
+
+    #[test]
+    fn test_header_parameters() {
+        let spec = parse_swagger_file(get_test_data_path("sample_headers.yaml")).unwrap();
+        let operation = &spec.paths[0].operations[0];
+        let names: Vec<&str> = operation.header_params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["X-Tenant-Id", "X-Trace"]);
         
         let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("target")
             .join("test-output")
-            .join("pytest");
+            .join("headers");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
         
-        // Clean previous test output
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#".header("X-Tenant-Id", "1")"#));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains(r#""X-Trace": "on""#));
+        assert!(pytest.contains("requests.get(url, params=params, headers=headers)"));
+        
+        let jest = fs::read_to_string(test_output_dir.join("reports.test.js")).unwrap();
+        assert!(jest.contains("axios.get(url, { params, headers })"));
+        
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let header = &collection["item"][0]["item"][0]["request"]["header"];
+        assert_eq!(header[0]["key"], "X-Tenant-Id");
+        assert_eq!(header[1]["value"], "on");
+    }
+
+    #[test]
+    fn test_cookie_parameters() {
+        let spec = parse_swagger_file(get_test_data_path("sample_cookies.yaml")).unwrap();
+        let operation = &spec.paths[0].operations[0];
+        let names: Vec<&str> = operation.cookie_params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["session_id", "theme"]);
+        assert!(operation.header_params.is_empty());
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("cookies");
         if test_output_dir.exists() {
             fs::remove_dir_all(&test_output_dir).unwrap();
         }
-        fs::create_dir_all(&test_output_dir).unwrap();
         
-        // Generate tests
-        let generator = create_generator(TestFramework::Pytest).unwrap();
-        let result = generator.generate_tests(&spec, &test_output_dir, "http://localhost:3000");
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
         
-        assert!(result.is_ok());
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#"cookies.add_cookie_str("session_id=string", &url.parse().unwrap());"#));
+        assert!(reqwest.contains(".cookie_provider(cookies)"));
         
-        // Check that test files were created
-        assert!(test_output_dir.join("test_api.py").exists());
-        assert!(test_output_dir.join("requirements.txt").exists());
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains(r#""theme": "dark""#));
+        assert!(pytest.contains("requests.get(url, params=params, cookies=cookies)"));
+        
+        let jest = fs::read_to_string(test_output_dir.join("cart.test.js")).unwrap();
+        assert!(jest.contains(r#""Cookie": "session_id=string; theme=dark""#));
+        
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let header = &collection["item"][0]["item"][0]["request"]["header"];
+        assert_eq!(header[0]["key"], "Cookie");
+        assert_eq!(header[0]["value"], "session_id=string; theme=dark");
     }
 
     #[test]
-    fn test_generate_api_endpoints() {
-        let swagger_path = get_test_data_path("sample_swagger.json");
-        let spec = parse_swagger_file(&swagger_path).unwrap();
+    fn test_multipart_bodies() {
+        let spec = parse_swagger_file(get_test_data_path("sample_upload_v2.yaml")).unwrap();
+        let body = spec.paths[0].operations[0].body_param.as_ref().unwrap();
+        assert_eq!(body.content_type.as_deref(), Some("multipart/form-data"));
+        assert!(body.required);
+        let schema = body.schema.as_ref().unwrap();
+        assert_eq!(schema.property("avatar").unwrap().info().unwrap().format.as_deref(), Some("binary"));
+        assert_eq!(schema.property("caption").unwrap().type_name(), Some("string"));
         
-        let api_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        let body = spec.paths[0].operations[0].body_param.as_ref().unwrap();
+        assert_eq!(body.content_type.as_deref(), Some("multipart/form-data"));
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("target")
             .join("test-output")
-            .join("generated_api");
+            .join("upload");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
         
-        // Clean previous test output
-        if api_output_dir.exists() {
-            fs::remove_dir_all(&api_output_dir).unwrap();
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
         }
         
-        // Generate API
-        let result = generate_axum_api(&spec, &api_output_dir);
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#".text("title", "Example title")"#));
+        assert!(reqwest.contains(r#".part("file", reqwest::multipart::Part::bytes("#));
+        assert!(reqwest.contains("client.post(&url).multipart(form)"));
+        let cargo_toml = fs::read_to_string(test_output_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains(r#""multipart""#));
         
-        assert!(result.is_ok());
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains("def test_uploadDocument(tmp_path):"));
+        assert!(pytest.contains(r#""file": upload_file.open("rb")"#));
+        assert!(pytest.contains("requests.post(url, data=data, files=files, params=params)"));
         
-        // Check that API files were created
-        assert!(api_output_dir.join("Cargo.toml").exists());
-        assert!(api_output_dir.join("src").join("main.rs").exists());
-        assert!(api_output_dir.join("src").join("models").exists());
-        assert!(api_output_dir.join("src").join("handlers").exists());
-        assert!(api_output_dir.join("src").join("routes").exists());
+        let jest = fs::read_to_string(test_output_dir.join("documents.test.js")).unwrap();
+        assert!(jest.contains("const FormData = require('form-data');"));
+        assert!(jest.contains(r#"form.append("file", fs.createReadStream(uploadPath));"#));
+        assert!(jest.contains("axios.post(url, form, { params, headers: form.getHeaders() })"));
+        let package = fs::read_to_string(test_output_dir.join("package.json")).unwrap();
+        assert!(package.contains("form-data"));
+        
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let body = &collection["item"][0]["item"][0]["request"]["body"];
+        assert_eq!(body["mode"], "formdata");
+        assert_eq!(body["formdata"][0]["key"], "file");
+        assert_eq!(body["formdata"][0]["type"], "file");
+        assert_eq!(body["formdata"][1]["value"], "Example title");
+    }
+
+    #[test]
+    fn test_form_data_parameters() {
+        let spec = parse_swagger_file(get_test_data_path("sample_form_data.yaml")).unwrap();
+        let operation = &spec.paths[0].operations[0];
+        let names: Vec<&str> = operation.form_params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["username", "password", "remember"]);
+        assert_eq!(operation.form_params[0].location, "formData");
+        
+        // Without files the fields are sent URL-encoded
+        let body = operation.body_param.as_ref().unwrap();
+        assert_eq!(body.content_type.as_deref(), Some("application/x-www-form-urlencoded"));
+        assert!(body.required);
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("form-data");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#"("username", "ada"),"#));
+        assert!(reqwest.contains(r#"("password", "P@ssw0rd123"),"#));
+        assert!(reqwest.contains("client.post(&url).form(&form)"));
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains("requests.post(url, data=data, params=params)"));
+        let jest = fs::read_to_string(test_output_dir.join("login.test.js")).unwrap();
+        assert!(jest.contains("const form = new URLSearchParams({"));
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let body = &collection["item"][0]["item"][0]["request"]["body"];
+        assert_eq!(body["mode"], "urlencoded");
+        assert_eq!(body["urlencoded"][2]["key"], "username");
+        
+        // Operations that consume multipart get a multipart body, even without files
+        let multipart = parse_swagger_string(r#"
+swagger: '2.0'
+info: {title: Profile, version: '1.0'}
+consumes: [multipart/form-data]
+paths:
+  /profile:
+    put:
+      parameters:
+        - {name: bio, in: formData, type: string}
+      responses:
+        '204': {description: Updated}
+"#).unwrap();
+        let body = multipart.paths[0].operations[0].body_param.as_ref().unwrap();
+        assert_eq!(body.content_type.as_deref(), Some("multipart/form-data"));
+        assert!(!body.required);
+    }
+
+    #[test]
+    fn test_request_body_content_types() {
+        let mut spec = parse_swagger_file(get_test_data_path("sample_content_types.yaml")).unwrap();
+        let body = spec.paths[0].operations[0].body_param.clone().unwrap();
+        let media_types: Vec<&str> = body.content.keys().map(String::as_str).collect();
+        assert_eq!(media_types, vec!["application/json", "application/x-www-form-urlencoded", "application/xml", "text/plain"]);
+        assert_eq!(body.content_type.as_deref(), Some("application/json"));
+        assert_eq!(body.content["application/xml"].as_ref().unwrap().property("pinned").unwrap().type_name(), Some("boolean"));
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("content-types");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        let generate = |spec: &SwaggerSpec, name: &str| {
+            for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+                create_generator(framework).unwrap()
+                    .generate_tests(spec, &test_output_dir.join(name), "http://localhost:3000")
+                    .unwrap();
+            }
+        };
+        
+        // Form-encoded bodies
+        spec.select_content_type("application/x-www-form-urlencoded");
+        generate(&spec, "form");
+        let reqwest = fs::read_to_string(test_output_dir.join("form").join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#"("pinned", "true"),"#));
+        assert!(reqwest.contains("client.post(&url).form(&form)"));
+        let pytest = fs::read_to_string(test_output_dir.join("form").join("test_api.py")).unwrap();
+        assert!(pytest.contains("requests.post(url, data=data, params=params)"));
+        let jest = fs::read_to_string(test_output_dir.join("form").join("notes.test.js")).unwrap();
+        assert!(jest.contains("const form = new URLSearchParams({"));
+        let collection = fs::read_to_string(test_output_dir.join("form").join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        assert_eq!(collection["item"][0]["item"][0]["request"]["body"]["urlencoded"][1]["key"], "title");
+        
+        // Other media types are sent as text with an explicit Content-Type
+        spec.select_content_type("text/plain");
+        generate(&spec, "text");
+        let reqwest = fs::read_to_string(test_output_dir.join("text").join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#".header("Content-Type", "text/plain")"#));
+        assert!(reqwest.contains("client.post(&url).body(body)"));
+        let pytest = fs::read_to_string(test_output_dir.join("text").join("test_api.py")).unwrap();
+        assert!(pytest.contains(r#""Content-Type": "text/plain""#));
+        let jest = fs::read_to_string(test_output_dir.join("text").join("notes.test.js")).unwrap();
+        assert!(jest.contains("axios.post(url, rawBody, { params, headers })"));
+        let collection = fs::read_to_string(test_output_dir.join("text").join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let request = &collection["item"][0]["item"][0]["request"];
+        assert_eq!(request["body"]["raw"], "string");
+        assert_eq!(request["header"][0]["value"], "text/plain");
+        
+        // Media types the operation doesn't document leave the body alone
+        spec.select_content_type("application/yaml");
+        assert_eq!(spec.paths[0].operations[0].body_param.as_ref().unwrap().content_type.as_deref(), Some("text/plain"));
+    }
+
+    #[test]
+    fn test_consumes_and_produces() {
+        let spec = parse_swagger_file(get_test_data_path("sample_media_types.yaml")).unwrap();
+        let list = &spec.paths[0].operations[0];
+        let create = &spec.paths[0].operations[1];
+        
+        // Operations inherit the API's media types unless they declare their own
+        assert_eq!(list.produces, vec!["application/json"]);
+        assert!(list.consumes.is_empty());
+        assert_eq!(create.consumes, vec!["application/xml"]);
+        assert_eq!(create.produces, vec!["application/xml", "text/plain"]);
+        assert_eq!(create.body_param.as_ref().unwrap().content_type.as_deref(), Some("application/xml"));
+        
+        // OpenAPI 3 takes them from the request body and responses
+        let v3 = parse_swagger_file(get_test_data_path("sample_openapi_3_1.yaml")).unwrap();
+        assert_eq!(v3.paths[0].operations[0].produces, vec!["application/json"]);
+        let v3 = parse_swagger_file(get_test_data_path("sample_content_types.yaml")).unwrap();
+        assert_eq!(v3.paths[0].operations[0].consumes.len(), 4);
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("media-types");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        // Bodies are sent with the consumed media type, and responses asked for in the produced one
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#".header("Content-Type", "application/xml")"#));
+        assert!(reqwest.contains(r#".header("Accept", "application/xml")"#));
+        assert!(reqwest.contains(r#".header("Accept", "application/json")"#));
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains(r#""Accept": "application/xml""#));
+        let jest = fs::read_to_string(test_output_dir.join("notes.test.js")).unwrap();
+        assert!(jest.contains(r#""Accept": "application/xml""#));
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let headers = &collection["item"][0]["item"][1]["request"]["header"];
+        assert_eq!(headers[0]["value"], "application/xml");
+        assert_eq!(headers[1]["key"], "Accept");
+        assert_eq!(headers[1]["value"], "application/xml");
+    }
+
+    #[test]
+    fn test_enum_parameters() {
+        let spec = parse_swagger_file(get_test_data_path("sample_enums.yaml")).unwrap();
+        let operation = &spec.paths[0].operations[0];
+        assert_eq!(operation.path_params[0].enum_values, vec![serde_json::json!("eu"), serde_json::json!("us"), serde_json::json!("apac")]);
+        assert_eq!(operation.query_params[1].enum_values, vec![serde_json::json!("id"), serde_json::json!("total")]);
+        assert_eq!(operation.header_params[0].enum_values.len(), 2);
+        
+        // Swagger 2.0 declares the enum on the parameter itself
+        let v2 = parse_swagger_string(r#"
+swagger: '2.0'
+info: {title: Enums, version: '1.0'}
+paths:
+  /items:
+    get:
+      parameters:
+        - {name: sort, in: query, type: string, enum: [asc, desc]}
+      responses:
+        '200': {description: OK}
+"#).unwrap();
+        assert_eq!(v2.paths[0].operations[0].query_params[0].enum_values, vec![serde_json::json!("asc"), serde_json::json!("desc")]);
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("enums");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator_with_options(framework, &with_optional_query_params()).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        // Parameters get the first member of their enum, unless they document an example
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#"let region = "eu";"#));
+        assert!(reqwest.contains(r#"("status", "pending")"#));
+        assert!(reqwest.contains(r#"("fields", "id")"#));
+        assert!(reqwest.contains(r#"("priority", "high")"#));
+        assert!(reqwest.contains(r#".header("X-Currency", "EUR")"#));
+        assert!(!reqwest.contains("test_value"));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains(r#"region = "eu""#));
+        assert!(pytest.contains(r#""status": "pending""#));
+        
+        let jest = fs::read_to_string(test_output_dir.join("orders_region.test.js")).unwrap();
+        assert!(jest.contains(r#"const region = "eu";"#));
+        assert!(jest.contains(r#"status: "pending""#));
+        
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        assert_eq!(collection["item"][0]["item"][0]["request"]["query"][0]["value"], "pending");
+    }
+
+    #[test]
+    fn test_default_values() {
+        let spec = parse_swagger_file(get_test_data_path("sample_defaults.yaml")).unwrap();
+        let operation = &spec.paths[0].operations[0];
+        assert_eq!(operation.path_params[0].default, Some(serde_json::json!("pdf")));
+        assert_eq!(operation.query_params[0].default, Some(serde_json::json!(50)));
+        
+        // Swagger 2.0 declares the default on the parameter itself
+        let v2 = parse_swagger_string(r#"
+swagger: '2.0'
+info: {title: Defaults, version: '1.0'}
+paths:
+  /items:
+    get:
+      parameters:
+        - {name: limit, in: query, type: integer, default: 20}
+      responses:
+        '200': {description: OK}
+"#).unwrap();
+        assert_eq!(v2.paths[0].operations[0].query_params[0].default, Some(serde_json::json!(20)));
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("defaults");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest] {
+            create_generator_with_options(framework, &with_optional_query_params()).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        // Defaults take precedence over enum members and synthesized values
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#"let format = "pdf";"#));
+        assert!(reqwest.contains(r#"("pageSize", "50")"#));
+        assert!(reqwest.contains(r#".header("X-Locale", "en-US")"#));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains(r#""pageSize": "50""#));
+        
+        let jest = fs::read_to_string(test_output_dir.join("reports_format.test.js")).unwrap();
+        assert!(jest.contains(r#"const format = "pdf";"#));
+    }
+
+    #[test]
+    fn test_format_values() {
+        let spec = parse_swagger_file(get_test_data_path("sample_formats.yaml")).unwrap();
+        let operation = &spec.paths[0].operations[0];
+        assert_eq!(operation.path_params[0].format.as_deref(), Some("uuid"));
+        assert_eq!(operation.query_params[2].format.as_deref(), Some("int64"));
+        
+        // Swagger 2.0 declares the format on the parameter itself
+        let v2 = parse_swagger_string(r#"
+swagger: '2.0'
+info: {title: Formats, version: '1.0'}
+paths:
+  /items:
+    get:
+      parameters:
+        - {name: updatedAfter, in: query, type: string, format: date}
+      responses:
+        '200': {description: OK}
+"#).unwrap();
+        assert_eq!(v2.paths[0].operations[0].query_params[0].format.as_deref(), Some("date"));
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("formats");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest] {
+            create_generator_with_options(framework, &with_optional_query_params()).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        // Values are synthesized to match the parameter's format
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#"let accountId = "3fa85f64-5717-4562-b3fc-2c963f66afa6"; // TODO: Replace with actual test value for accountId"#));
+        assert!(reqwest.contains(r#"("since", "2024-01-01T12:00:00Z")"#));
+        assert!(reqwest.contains(r#"("contact", "user@example.com")"#));
+        assert!(reqwest.contains(r#"("limit", "1")"#));
+        assert!(reqwest.contains(r#".header("X-Request-Id", "3fa85f64-5717-4562-b3fc-2c963f66afa6")"#));
+        assert!(!reqwest.contains("test_value"));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains(r#"accountId = "3fa85f64-5717-4562-b3fc-2c963f66afa6"  # Replace with actual test value"#));
+        
+        let jest = fs::read_to_string(test_output_dir.join("accounts_accountId_events.test.js")).unwrap();
+        assert!(jest.contains(r#"since: "2024-01-01T12:00:00Z""#));
+    }
+
+    #[test]
+    fn test_read_write_only_properties() {
+        let mut spec = parse_swagger_file(get_test_data_path("sample_read_write_only.yaml")).unwrap();
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("read-write-only");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Read-only properties are left out of request bodies
+        spec.select_content_type("application/x-www-form-urlencoded");
+        create_generator(TestFramework::Reqwest).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#"("email", "user@example.com")"#));
+        assert!(!reqwest.contains(r#"("id","#));
+        assert!(!reqwest.contains(r#"("createdAt","#));
+        
+        // Write-only properties aren't expected back in the response
+        assert!(reqwest.contains(r#"assert_eq!(user.name, "Jane Doe");"#));
+        assert!(!reqwest.contains("assert_eq!(user.email"));
+        
+        // Models accept null for nullable properties, and don't round-trip read-only or write-only ones
+        generate_axum_api(&spec, &test_output_dir.join("api")).unwrap();
+        let model = fs::read_to_string(test_output_dir.join("api").join("src").join("models").join("user.rs")).unwrap();
+        assert!(model.contains("    #[serde(skip_deserializing)]\n    pub id: i64,"));
+        assert!(model.contains("    #[serde(skip_serializing)]\n    pub email: String,"));
+        assert!(model.contains("    pub nickname: Option<String>,"));
+        assert!(model.contains("    #[serde(skip_deserializing)]\n    pub createdAt: DateTime<Utc>,"));
+    }
+
+    #[test]
+    fn test_recursive_schemas() {
+        let spec = parse_swagger_file(get_test_data_path("sample_recursive.yaml")).unwrap();
+        let body = spec.paths[0].operations[0].body_param.as_ref().unwrap().schema.as_ref().unwrap();
+        
+        // The reference back to the schema being resolved is kept, other references are inlined
+        let category = Schema::Ref("#/components/schemas/Category".to_string());
+        assert_eq!(body.property("parent"), Some(&category));
+        assert_eq!(category.ref_name(), Some("Category"));
+        assert_eq!(body.property("owner").unwrap().type_name(), Some("object"));
+        
+        // Example values stop at the recursion
+        assert_eq!(
+            example_from_schema(body),
+            serde_json::json!({ "name": "Books", "children": [], "owner": { "name": "Ada" } })
+        );
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("recursive");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Models box the reference back to themselves and import the models they use
+        generate_axum_api(&spec, &test_output_dir.join("api")).unwrap();
+        let models = test_output_dir.join("api").join("src").join("models");
+        let model = fs::read_to_string(models.join("category.rs")).unwrap();
+        assert!(model.starts_with("use super::Owner;\n"));
+        assert!(model.contains("    pub parent: Option<Box<Category>>,"));
+        assert!(model.contains("    pub children: Vec<Category>,"));
+        assert!(model.contains("    pub owner: Owner,"));
+    }
+
+    #[test]
+    fn test_resolver_cache() {
+        let document = read_document(get_test_data_path("sample_recursive.yaml")).unwrap();
+        let category_ref = serde_json::json!({ "$ref": "#/components/schemas/Category" });
+        let owner_ref = serde_json::json!({ "$ref": "#/components/schemas/Owner" });
+        
+        // A schema referred to from several places resolves the same each time, once it's been cached too
+        let resolver = RefResolver::new(&document);
+        let owner = resolver.resolve(&owner_ref).unwrap();
+        assert_eq!(owner, document["components"]["schemas"]["Owner"]);
+        assert_eq!(resolver.resolve(&owner_ref).unwrap(), owner);
+        let category = resolver.resolve(&category_ref).unwrap();
+        assert_eq!(category["properties"]["owner"], owner);
+        let resolved = resolver.resolve(&document).unwrap();
+        let operation = &resolved["paths"]["/categories"]["post"];
+        assert_eq!(operation["requestBody"]["content"]["application/json"]["schema"], category);
+        assert_eq!(operation["responses"]["201"]["content"]["application/json"]["schema"], category);
+        
+        // The recursive reference is kept however the schema is reached, before or after it was resolved on its own
+        assert_eq!(category["properties"]["parent"], category_ref);
+        assert_eq!(category["properties"]["children"]["items"], category_ref);
+        let children_ref = serde_json::json!({ "$ref": "#/components/schemas/Category/properties/children" });
+        for resolver in [RefResolver::new(&document), resolver] {
+            let children = resolver.resolve(&children_ref).unwrap();
+            assert_eq!(children["items"]["properties"]["parent"], category_ref);
+            assert_eq!(children["items"]["properties"]["children"]["items"], category_ref);
+            assert_eq!(children["items"]["properties"]["owner"], owner);
+        }
+        
+        // Across files, the schema shared by the request and the response stops at its reference to itself
+        let multi_file = get_test_data_path("multi_file");
+        let root = read_document(multi_file.join("openapi.yaml")).unwrap();
+        let resolver = RefResolver::external(&root, &multi_file);
+        let resolved = resolver.resolve(&root).unwrap();
+        let operation = &resolved["paths"]["/categories"]["post"];
+        let body = &operation["requestBody"]["content"]["application/json"]["schema"];
+        assert_eq!(body, &operation["responses"]["201"]["content"]["application/json"]["schema"]);
+        assert_eq!(body["properties"]["children"]["items"], serde_json::json!({ "$ref": "#/Category" }));
+        assert_eq!(body["properties"]["owner"]["properties"]["email"]["format"], "email");
+        assert_eq!(resolver.resolve(&root).unwrap(), resolved);
+        
+        // The resolved documents are byte for byte those resolved before references were cached, at first and once cached
+        let pretty = |value: &serde_json::Value| serde_json::to_string_pretty(value).unwrap() + "\n";
+        let expected = fs::read_to_string(get_test_data_path("sample_recursive.resolved.json")).unwrap();
+        let resolver = RefResolver::new(&document);
+        assert_eq!(pretty(&resolver.resolve(&document).unwrap()), expected);
+        assert_eq!(pretty(&resolver.resolve(&document).unwrap()), expected);
+        let expected = fs::read_to_string(multi_file.join("openapi.resolved.json")).unwrap();
+        let resolver = RefResolver::external(&root, &multi_file);
+        assert_eq!(pretty(&resolver.resolve(&root).unwrap()), expected);
+        assert_eq!(pretty(&resolver.resolve(&root).unwrap()), expected);
+        
+        // Resolving without the cache, as the benchmark's baseline does, gives the same document
+        assert_eq!(pretty(&RefResolver::external(&root, &multi_file).uncached().resolve(&root).unwrap()), expected);
+    }
+
+    #[test]
+    fn test_synthesized_data() {
+        // String properties get values plausible for their names, within their constraints
+        let schema = Schema::from_value(&serde_json::json!({
+            "type": "object",
+            "properties": {
+                "first_name": { "type": "string" },
+                "email": { "type": "string" },
+                "phoneNumbers": { "type": "array", "items": { "type": "string" }, "minItems": 2 },
+                "city": { "type": "string", "maxLength": 6 },
+                "createdAt": { "type": "string" },
+                "code": { "type": "string" },
+                "attributes": { "type": "object", "additionalProperties": { "type": "integer", "minimum": 5 } }
+            }
+        }));
+        assert_eq!(
+            example_from_schema(&schema),
+            serde_json::json!({
+                "first_name": "Jane",
+                "email": "user@example.com",
+                "phoneNumbers": ["+1-555-0100", "+1-555-0100"],
+                "city": "Spring",
+                "createdAt": "2024-01-01T12:00:00Z",
+                "code": "string",
+                "attributes": { "key": 5 }
+            })
+        );
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("synthesized-data");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Bodies without a documented example are built from their schema in every framework
+        let spec = parse_swagger_file(get_test_data_path("sample_swagger.json")).unwrap();
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("    let body = json!({\n      \"email\": \"user@example.com\",\n      \"name\": \"Jane Doe\"\n    });"));
+        assert!(reqwest.contains("    assert_eq!(user.name, \"Jane Doe\");\n    assert_eq!(user.email, \"user@example.com\");"));
+        assert!(!reqwest.contains("Updated Name"));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains("    json_data = {\n        \"email\": \"user@example.com\",\n        \"name\": \"Jane Doe\"\n    }"));
+        
+        for file in ["users.test.js", "postman_collection.json"] {
+            let output = fs::read_to_string(test_output_dir.join(file)).unwrap();
+            assert!(output.contains("Jane Doe"));
+            assert!(!output.contains("Test User"));
+        }
+    }
+
+    #[test]
+    fn test_seeded_data() {
+        let schema = Schema::from_value(&serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "age": { "type": "integer", "minimum": 18, "maximum": 65 },
+                "score": { "type": "number" },
+                "active": { "type": "boolean" }
+            }
+        }));
+        
+        // The same seed picks the same values, within the constraints
+        let seeded = with_seed(Some(42), || example_from_schema(&schema));
+        assert_eq!(seeded, with_seed(Some(42), || example_from_schema(&schema)));
+        assert!((18..=65).contains(&seeded["age"].as_i64().unwrap()));
+        assert!((0..10).any(|seed| with_seed(Some(seed), || example_from_schema(&schema)) != seeded));
+        
+        // Without a seed, every property gets its first candidate
+        assert_eq!(example_from_schema(&schema), serde_json::json!({ "name": "Jane Doe", "age": 18, "score": 1.5, "active": true }));
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("seeded-data");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Generating twice with a seed gives the same tests, whichever threads generate them
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        let generate = |name: &str, jobs| {
+            let options = GeneratorOptions { seed: Some(7), jobs: Some(jobs), ..GeneratorOptions::default() };
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(TestFramework::Pytest, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            fs::read_to_string(output_dir.join("test_api.py")).unwrap()
+        };
+        let tests = generate("first", 1);
+        assert_eq!(tests, generate("second", 4));
+        assert!(tests.contains("        \"name\": \"John Smith\"\n"));
+    }
+
+    #[test]
+    fn test_fuzz_tests() {
+        let schema = Schema::from_value(&serde_json::json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "id": { "type": "integer", "readOnly": true },
+                "name": { "type": "string", "minLength": 1, "maxLength": 40 },
+                "age": { "type": "integer", "minimum": 0, "maximum": 120 },
+                "email": { "type": "string", "format": "email" },
+                "status": { "type": "string", "enum": ["available", "sold"] },
+                "tags": { "type": "array", "items": { "type": "string", "pattern": "^[a-z]+$" }, "maxItems": 3 }
+            }
+        }));
+        
+        // Strategies follow the constraints, and leave out read-only properties
+        let strategy = Strategy::from_schema(&schema);
+        assert_eq!(strategy, Strategy::Object(vec![
+            ("age".to_string(), Strategy::Integer { min: Some(0), max: Some(120) }),
+            ("email".to_string(), Strategy::Format("email".to_string())),
+            ("name".to_string(), Strategy::Text { min_length: 1, max_length: 40 }),
+            ("status".to_string(), Strategy::Choice(vec![serde_json::json!("available"), serde_json::json!("sold")])),
+            ("tags".to_string(), Strategy::Array {
+                items: Box::new(Strategy::Pattern("^[a-z]+$".to_string())),
+                min_items: 0,
+                max_items: 3,
+            }),
+        ]));
+        assert!(strategy.hypothesis("").contains("\"age\": st.integers(min_value=0, max_value=120),"));
+        assert!(strategy.fast_check("").contains("\"status\": fc.constantFrom(\"available\", \"sold\"),"));
+        assert!(strategy.proptest("").contains("prop::collection::vec(proptest::string::string_regex(\"[a-z]+\")"));
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("fuzz");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_constraints.yaml")).unwrap();
+        let options = GeneratorOptions { fuzz: true, optional_query_params: true, ..GeneratorOptions::default() };
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Query parameters are drawn within their bounds, and the API must not fail with a server error
+        let output_dir = generate(TestFramework::Reqwest, "reqwest");
+        let tests = fs::read_to_string(output_dir.join("api_tests.rs")).unwrap();
+        assert!(tests.starts_with("use proptest::prelude::*;\n"));
+        assert!(tests.contains("#![proptest_config(ProptestConfig::with_cases(50))]"));
+        assert!(tests.contains("        limit in (10i64..=100i64).prop_map(|value| json!(value)),\n"));
+        assert!(tests.contains("(\"maxPrice\", query_text(&max_price))"));
+        assert!(tests.contains("prop_assert!(status < 500, \"Server error {}\", status);"));
+        assert!(!tests.contains("async fn test_search_products()"));
+        assert!(fs::read_to_string(output_dir.join("Cargo.toml")).unwrap().contains("proptest = \"1\""));
+        
+        let output_dir = generate(TestFramework::Pytest, "pytest");
+        let tests = fs::read_to_string(output_dir.join("test_api.py")).unwrap();
+        assert!(tests.contains("from hypothesis import given, settings, strategies as st\n"));
+        assert!(tests.contains("@settings(max_examples=50, deadline=None)\n@given(\n"));
+        assert!(tests.contains("    sku=st.from_regex(\"^[A-Z]{3}-\\\\d{4}$\", fullmatch=True),\n"));
+        assert!(tests.contains("    query=st.text(min_size=12, max_size=32),\n"));
+        assert!(tests.contains("def test_searchProducts(sku, limit, max_price, query, lang):"));
+        assert!(tests.contains("    assert response.status_code < 500\n"));
+        assert!(fs::read_to_string(output_dir.join("requirements.txt")).unwrap().contains("hypothesis=="));
+        
+        let output_dir = generate(TestFramework::Jest, "jest");
+        let tests = fs::read_to_string(output_dir.join("products.test.js")).unwrap();
+        assert!(tests.contains("const fc = require('fast-check');\n"));
+        assert!(tests.contains("      fc.double({ min: 0, max: 0.9, noNaN: true, noDefaultInfinity: true }),\n"));
+        assert!(tests.contains("      fc.string({ minLength: 0, maxLength: 2 }),\n"));
+        assert!(tests.contains("    { numRuns: 50 },\n"));
+        assert!(fs::read_to_string(output_dir.join("package.json")).unwrap().contains("\"fast-check\""));
+    }
+
+    #[test]
+    fn test_lifecycle_tests() {
+        let spec = parse_swagger_file(get_test_data_path("sample_lifecycle.yaml")).unwrap();
+        let operation = |id: &str| spec.paths.iter().flat_map(|p| &p.operations).find(|op| op.operation_id == id).unwrap();
+        
+        // Operations on an item depend on the POST to its collection, or on the operation named by `x-depends-on`
+        let setup = incoming_link(&spec, operation("deletePet")).unwrap();
+        assert_eq!(setup.operation.operation_id, "createPet");
+        assert_eq!(setup.dependency, Dependency::Inferred);
+        assert_eq!(setup.link.parameters, vec![("petId".to_string(), LinkValue::ResponseBody("/id".to_string()))]);
+        let setup = incoming_link(&spec, operation("listVaccinations")).unwrap();
+        assert_eq!(setup.dependency, Dependency::DependsOn);
+        assert_eq!(setup.origin(), "x-depends-on");
+        assert!(incoming_link(&spec, operation("createPet")).is_none());
+        
+        // Resources without a DELETE have no lifecycle
+        let options = GeneratorOptions::default();
+        let found = lifecycles(&spec, &options);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "pets");
+        let actions: Vec<_> = found[0].steps.iter().map(|step| (step.action, step.operation.operation_id.as_str())).collect();
+        assert_eq!(actions, vec![
+            (Action::Read, "getPet"),
+            (Action::Update, "updatePet"),
+            (Action::Delete, "deletePet"),
+            (Action::ReadDeleted, "getPet"),
+        ]);
+        assert_eq!(found[0].steps[3].expected_status(), "404");
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("lifecycle");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator(framework).unwrap().generate_tests(&spec, &output_dir, "http://localhost:3000").unwrap();
+            output_dir
+        };
+        
+        let output_dir = generate(TestFramework::Reqwest, "reqwest");
+        let tests = fs::read_to_string(output_dir.join("api_tests.rs")).unwrap();
+        assert!(tests.contains("    // Call createPet first, and take parameters from its response (x-depends-on)\n"));
+        assert!(tests.contains("async fn test_pets_lifecycle() {"));
+        assert!(tests.contains("client.request(reqwest::Method::PATCH, format!(\"http://localhost:3000/pets/{petId}\"))"));
+        assert!(tests.contains("    // Check it is gone: getPet\n"));
+        assert!(tests.contains("    assert_eq!(response.status().as_u16(), 404);\n}"));
+        
+        let output_dir = generate(TestFramework::Pytest, "pytest");
+        let tests = fs::read_to_string(output_dir.join("test_api.py")).unwrap();
+        assert!(tests.contains("def test_pets_lifecycle():"));
+        assert!(tests.contains("    response = requests.request(\"DELETE\", f\"http://localhost:3000/pets/{petId}\")\n    assert response.status_code == 204\n"));
+        assert!(tests.contains("    assert response.status_code == 404\n"));
+        
+        // The lifecycle goes in the file of its create operation
+        let output_dir = generate(TestFramework::Jest, "jest");
+        let tests = fs::read_to_string(output_dir.join("pets.test.js")).unwrap();
+        assert!(tests.contains("test(\"pets lifecycle\", async () => {"));
+        assert!(tests.contains("    url: `http://localhost:3000/pets/${petId}`,\n"));
+        assert!(tests.contains("  expect(response.status).toBe(404);\n});"));
+        assert!(!fs::read_to_string(output_dir.join("pets_petId.test.js")).unwrap().contains("lifecycle"));
+        
+        let output_dir = generate(TestFramework::Postman, "postman");
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        let folder = collection["item"].as_array().unwrap().iter().find(|item| item["name"] == "pets lifecycle").unwrap();
+        let items = folder["item"].as_array().unwrap();
+        assert_eq!(items.len(), 5);
+        assert_eq!(items[0]["event"][0]["script"]["exec"][3], "pm.collectionVariables.set(\"getPet.petId\", pm.response.json()[\"id\"]);");
+        assert_eq!(items[4]["request"]["url"]["raw"], "{{baseUrl}}/pets/{{getPet.petId}}");
+        assert_eq!(items[4]["event"][0]["script"]["exec"][1], "    pm.response.to.have.status(404);");
+        
+        // Other frameworks warn about the lifecycles they leave out, at the create operation
+        let sample = get_test_data_path("sample_lifecycle.yaml");
+        let options = GenerateOptions::default();
+        let warnings = generate_tests_from_spec(&sample, test_output_dir.join("go"), TestFramework::Go, "http://localhost:3000", &options).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].pointer, "/paths/~1pets/post");
+        assert_eq!(
+            warnings[0].message,
+            "go tests have no lifecycle test of the resource this operation creates, which only reqwest, ureq, pytest, Jest and Postman tests generate"
+        );
+        let warnings = generate_tests_from_spec(&sample, test_output_dir.join("ureq"), TestFramework::Ureq, "http://localhost:3000", &options).unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_cleanup() {
+        let spec = parse_swagger_file(get_test_data_path("sample_lifecycle.yaml")).unwrap();
+        let operation = |id: &str| spec.paths.iter().flat_map(|p| &p.operations).find(|op| op.operation_id == id).unwrap();
+        
+        // Pets are deleted by the DELETE depending on the POST, owners have none
+        let cleanup = links::cleanup(&spec, operation("createPet")).unwrap();
+        assert_eq!(cleanup.operation.operation_id, "deletePet");
+        assert_eq!(cleanup.path, "/pets/{petId}");
+        assert!(links::cleanup(&spec, operation("createOwner")).is_none());
+        
+        // The DELETE's own test doesn't delete the pet its setup request created again
+        assert!(links::resources(&spec, operation("getPet")).setup_cleanup.is_some());
+        assert!(links::resources(&spec, operation("deletePet")).setup_cleanup.is_none());
+        assert!(!links::resources(&spec, operation("getOwner")).cleans_up());
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("cleanup");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator(framework).unwrap().generate_tests(&spec, &output_dir, "http://localhost:3000").unwrap();
+            output_dir
+        };
+        let registration = "Cleanup(format!(\"http://localhost:3000/pets/{petId}\", petId = linked_value(&";
+        
+        let output_dir = generate(TestFramework::Reqwest, "reqwest");
+        let tests = fs::read_to_string(output_dir.join("api_tests.rs")).unwrap();
+        assert!(tests.contains("let _ = std::thread::spawn(move || reqwest::blocking::Client::new().delete(url).send()).join();"));
+        assert!(tests.contains("    let created: serde_json::Value = response.json().await.unwrap_or_default();\n"));
+        assert!(tests.contains(&format!("    let _cleanup = {}created, \"/id\")));\n", registration)));
+        assert!(tests.contains(&format!("    let _cleanup = {}setup_body, \"/id\")));\n", registration)));
+        assert!(tests.contains("let _body: ResponseBody = serde_json::from_value(created.clone())"));
+        assert_eq!(tests.matches("let _cleanup = ").count(), 4);
+        
+        let output_dir = generate(TestFramework::Ureq, "ureq");
+        let tests = fs::read_to_string(output_dir.join("api_tests.rs")).unwrap();
+        assert!(tests.contains("        let _ = ureq::delete(&self.0).call();\n"));
+        assert!(tests.contains("    let created: serde_json::Value = response.into_json().unwrap_or_default();\n"));
+        
+        let output_dir = generate(TestFramework::Pytest, "pytest");
+        let tests = fs::read_to_string(output_dir.join("test_api.py")).unwrap();
+        assert!(tests.contains("@pytest.fixture\ndef cleanup():\n"));
+        assert!(tests.contains("def test_createPet(cleanup):"));
+        assert!(tests.contains("    cleanup.append(\"http://localhost:3000/pets/{petId}\".format(petId=linked_value(response.json(), \"/id\")))\n"));
+        assert!(tests.contains("def test_getOwner():"));
+        
+        let output_dir = generate(TestFramework::PytestAsync, "pytest_async");
+        let tests = fs::read_to_string(output_dir.join("test_api.py")).unwrap();
+        assert!(tests.contains("import pytest_asyncio\n"));
+        assert!(tests.contains("@pytest_asyncio.fixture\nasync def cleanup(client):\n"));
+        assert!(tests.contains("async def test_getPet(client, cleanup):"));
+        
+        let output_dir = generate(TestFramework::Jest, "jest");
+        let tests = fs::read_to_string(output_dir.join("pets_petId.test.js")).unwrap();
+        assert!(tests.contains("afterEach(async () => {\n"));
+        assert!(tests.contains("  createdUrls.push(`http://localhost:3000/pets/${linkedValue(setupResponse.data, \"/id\")}`);\n"));
+        assert!(!fs::read_to_string(output_dir.join("owners.test.js")).unwrap().contains("createdUrls"));
+    }
+
+    #[test]
+    fn test_constraints() {
+        let spec = parse_swagger_file(get_test_data_path("sample_constraints.yaml")).unwrap();
+        let params = &spec.paths[0].operations[0].query_params;
+        assert_eq!(params[0].constraints.pattern.as_deref(), Some(r"^[A-Z]{3}-\d{4}$"));
+        assert_eq!(params[1].constraints.minimum, Some(10.0));
+        assert_eq!(params[1].constraints.maximum, Some(100.0));
+        assert!(params[2].constraints.exclusive_maximum);
+        assert_eq!(params[3].constraints.min_length, Some(12));
+        
+        // Boundary values, with the OpenAPI 3.1 form of exclusive bounds
+        let constraints = Constraints::from_schema(&serde_json::json!({ "type": "integer", "exclusiveMinimum": 0, "maximum": 10 }));
+        assert_eq!(constraints.minimum, Some(0.0));
+        assert!(constraints.exclusive_minimum);
+        assert_eq!(constraints.numeric_boundaries(1.0), vec![(1.0, true), (0.0, false), (10.0, true), (11.0, false)]);
+        assert_eq!(params[3].constraints.length_boundaries(), vec![(12, true), (11, false)]);
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("constraints");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        create_generator_with_options(TestFramework::Reqwest, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        // Synthesized values match patterns and stay within bounds and length limits
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#"("sku", "AAA-0000")"#));
+        assert!(reqwest.contains(r#"("limit", "10")"#));
+        assert!(reqwest.contains(r#"("maxPrice", "0.9")"#));
+        assert!(reqwest.contains(r#"("query", "test_valuexx")"#));
+        assert!(reqwest.contains(r#"("lang", "te")"#));
+    }
+
+    #[test]
+    fn test_schema_ir() {
+        let schema = Schema::from_value(&serde_json::json!({
+            "properties": {
+                "id": { "type": "integer", "readOnly": true },
+                "nickname": { "type": ["string", "null"], "maxLength": 20 },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "parent": { "$ref": "#/components/schemas/Node" },
+                "metadata": {}
+            },
+            "required": ["id"]
+        }));
+        
+        // Objects are recognised by their properties even without a `type`
+        let object = match &schema {
+            Schema::Object(object) => object,
+            other => panic!("Expected an object, got {:?}", other),
+        };
+        assert_eq!(object.required, vec!["id"]);
+        assert!(schema.property("id").unwrap().info().unwrap().read_only);
+        
+        // OpenAPI 3.1 type arrays become a nullable primitive
+        let nickname = schema.property("nickname").unwrap();
+        assert_eq!(nickname.type_name(), Some("string"));
+        assert!(nickname.info().unwrap().nullable);
+        assert_eq!(nickname.info().unwrap().constraints.max_length, Some(20));
+        
+        match schema.property("tags") {
+            Some(Schema::Array(tags)) => assert_eq!(tags.items.as_ref().unwrap().type_name(), Some("string")),
+            other => panic!("Expected an array, got {:?}", other),
+        }
+        assert_eq!(schema.property("parent"), Some(&Schema::Ref("#/components/schemas/Node".to_string())));
+        assert!(matches!(schema.property("metadata"), Some(Schema::Any(_))));
+        
+        // Compositions keep their members and discriminator
+        let pet = Schema::from_value(&serde_json::json!({
+            "oneOf": [{ "type": "object", "properties": { "meows": { "type": "boolean" } } }, { "type": "string" }],
+            "discriminator": { "propertyName": "petType" }
+        }));
+        match pet {
+            Schema::Composite(composite) => {
+                assert_eq!(composite.kind, CompositeKind::OneOf);
+                assert_eq!(composite.members.len(), 2);
+                assert_eq!(composite.discriminator.as_deref(), Some("petType"));
+            },
+            other => panic!("Expected a composition, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_response_headers() {
+        let spec = parse_swagger_file(get_test_data_path("sample_response_headers.yaml")).unwrap();
+        let headers = &spec.paths[0].operations[0].responses[0].headers;
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers[0].name, "Location");
+        assert!(headers[0].required);
+        assert_eq!(headers[1].description.as_deref(), Some("Requests left in the current window"));
+        assert_eq!(headers[1].schema.as_ref().unwrap().type_name(), Some("integer"));
+        
+        // Swagger 2.0 describes the header type inline
+        let v2 = parse_swagger_string(r#"
+swagger: '2.0'
+info: {title: Headers, version: '1.0'}
+paths:
+  /items:
+    get:
+      responses:
+        '200':
+          description: OK
+          headers:
+            ETag: {type: string, description: Version of the list}
+"#).unwrap();
+        let etag = &v2.paths[0].operations[0].responses[0].headers[0];
+        assert_eq!(etag.name, "ETag");
+        assert_eq!(etag.schema.as_ref().unwrap().type_name(), Some("string"));
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("response-headers");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#"assert!(response.headers().contains_key("Location"), "Expected a Location header");"#));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains(r#"assert "X-Rate-Limit-Remaining" in response.headers"#));
+        
+        let jest = fs::read_to_string(test_output_dir.join("orders.test.js")).unwrap();
+        assert!(jest.contains(r#"expect(response.headers["location"]).toBeDefined();"#));
+        
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let exec = &collection["item"][0]["item"][0]["event"][0]["script"]["exec"];
+        assert_eq!(exec[4], r#"    pm.response.to.have.header("Location");"#);
+    }
+
+    #[test]
+    fn test_documented_examples() {
+        let spec = parse_swagger_file(get_test_data_path("sample_examples.yaml")).unwrap();
+        let update = &spec.paths.iter().find(|p| p.path == "/books/{isbn}").unwrap().operations[0];
+        assert_eq!(update.path_params[0].example, Some(serde_json::json!("978-0321751041")));
+        assert_eq!(update.query_params[0].example, Some(serde_json::json!(1)));
+        let body = update.body_param.as_ref().unwrap();
+        assert_eq!(body.example.as_ref().unwrap()["pages"], 672);
+        assert_eq!(body.schema.as_ref().unwrap().example().unwrap()["title"], "The Art of Computer Programming");
+        
+        // Swagger 2.0 uses `x-example` on parameters and `example` on body schemas
+        let v2 = parse_swagger_string(r#"
+swagger: '2.0'
+info: {title: Examples, version: '1.0'}
+paths:
+  /items:
+    post:
+      parameters:
+        - {name: limit, in: query, type: integer, x-example: 25}
+        - name: item
+          in: body
+          schema: {type: object, example: {name: widget}}
+      responses:
+        '201': {description: Created}
+"#).unwrap();
+        let operation = &v2.paths[0].operations[0];
+        assert_eq!(operation.query_params[0].example, Some(serde_json::json!(25)));
+        assert_eq!(operation.body_param.as_ref().unwrap().example, Some(serde_json::json!({"name": "widget"})));
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("examples");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator_with_options(framework, &with_optional_query_params()).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#"let isbn = "978-0321751041";"#));
+        assert!(reqwest.contains(r#"("edition", "1")"#));
+        assert!(reqwest.contains(r#""title": "The Art of Computer Programming""#));
+        assert!(!reqwest.contains("Updated Name"));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains(r#""author": "Knuth""#));
+        assert!(pytest.contains(r#""pages": 672"#));
+        
+        let jest = fs::read_to_string(test_output_dir.join("books_isbn.test.js")).unwrap();
+        assert!(jest.contains(r#"const isbn = "978-0321751041";"#));
+        
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let raw = collection["item"][1]["item"][0]["request"]["body"]["raw"].as_str().unwrap();
+        assert!(raw.contains("The Art of Computer Programming"));
+    }
+
+    #[test]
+    fn test_postman_collection_input() {
+        let spec = parse_swagger_file(get_test_data_path("sample_postman_collection.json")).unwrap();
+        assert_eq!(spec.base_url, "https://api.sample.com/v1");
+        let paths: Vec<&str> = spec.paths.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, vec!["/users", "/users/{id}", "/login"]);
+        
+        // Folders become tags, enabled query parameters and saved responses are kept
+        let list = &spec.paths[0].operations[0];
+        assert_eq!(list.operation_id, "listUsers");
+        assert_eq!(list.tags, vec!["Users"]);
+        assert_eq!(list.query_params.len(), 1);
+        assert_eq!(list.query_params[0].example, Some(serde_json::json!("10")));
+        assert_eq!(list.produces, vec!["application/json"]);
+        assert_eq!(list.responses[0].schema.as_ref().unwrap().type_name(), Some("array"));
+        assert_eq!(list.security[0].schemes[0].0, "bearer");
+        
+        // Raw JSON bodies get a schema inferred from them
+        let create = &spec.paths[0].operations[1];
+        assert_eq!(create.method, "POST");
+        assert_eq!(create.header_params[0].name, "X-Request-Id");
+        let body = create.body_param.as_ref().unwrap();
+        assert_eq!(body.content_type.as_deref(), Some("application/json"));
+        assert_eq!(body.schema.as_ref().unwrap().property("email").unwrap().type_name(), Some("string"));
+        assert_eq!(create.responses[0].status_code, "201");
+        
+        // `:name` segments are path parameters, and `noauth` folders allow anonymous access
+        let get = &spec.paths[1].operations[0];
+        assert_eq!(get.path_params[0].example, Some(serde_json::json!("42")));
+        let login = &spec.paths[2].operations[0];
+        assert!(login.security.is_empty());
+        assert_eq!(login.body_param.as_ref().unwrap().content_type.as_deref(), Some("application/x-www-form-urlencoded"));
+        assert!(matches!(spec.security_schemes[0].kind, SecuritySchemeKind::Bearer { .. }));
+        
+        // The generators work on the converted collection as on any specification
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("postman-input");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        create_generator(TestFramework::Pytest).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains("def test_createUser():"));
+        assert!(pytest.contains(r#""email": "ada@example.com""#));
+        assert!(pytest.contains(r#"url = f"http://localhost:3000/users/{id}""#));
+    }
+
+    #[test]
+    fn test_har_input() {
+        let spec = parse_swagger_file(get_test_data_path("sample_capture.har")).unwrap();
+        assert_eq!(spec.base_url, "https://app.sample.com");
+        
+        // Static assets and third-party calls are left out, and identifiers become path parameters
+        let paths: Vec<&str> = spec.paths.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, vec!["/api/users", "/api/users/{userId}/orders/{orderId}", "/api/login"]);
+        
+        let list = &spec.paths[0].operations[0];
+        assert_eq!(list.operation_id, "get_api_users");
+        assert_eq!(list.query_params[0].example, Some(serde_json::json!("10")));
+        assert_eq!(list.produces, vec!["application/json", "text/plain"]);
+        assert!(list.header_params.is_empty());
+        assert_eq!(list.security[0].schemes[0].0, "bearer");
+        assert_eq!(list.responses[0].schema.as_ref().unwrap().type_name(), Some("array"));
+        
+        let create = &spec.paths[0].operations[1];
+        assert_eq!(create.header_params[0].name, "X-Request-Id");
+        let body = create.body_param.as_ref().unwrap();
+        assert_eq!(body.schema.as_ref().unwrap().property("email").unwrap().type_name(), Some("string"));
+        assert_eq!(create.responses[0].status_code, "201");
+        
+        // Calls to the same template are merged, keeping the first call's values and every status
+        let order = &spec.paths[1].operations[0];
+        assert_eq!(spec.paths[1].operations.len(), 1);
+        assert_eq!(order.path_params[0].example, Some(serde_json::json!("42")));
+        let statuses: Vec<&str> = order.responses.iter().map(|r| r.status_code.as_str()).collect();
+        assert_eq!(statuses, vec!["200", "404"]);
+        
+        let login = &spec.paths[2].operations[0];
+        assert_eq!(login.body_param.as_ref().unwrap().content_type.as_deref(), Some("application/x-www-form-urlencoded"));
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("har-input");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        create_generator(TestFramework::Reqwest).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("async fn test_get_api_users_by_user_id_orders_by_order_id()"));
+        assert!(reqwest.contains(r#""email": "ada@example.com""#));
+    }
+
+    #[test]
+    fn test_spec_diff() {
+        let old = parse_swagger_file(get_test_data_path("sample_diff_old.yaml")).unwrap();
+        let new = parse_swagger_file(get_test_data_path("sample_diff_new.yaml")).unwrap();
+        let changes = diff_specs(&old, &new);
+        let lines: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+        
+        assert!(lines.contains(&"added: GET /users: optional query parameter `cursor` added".to_string()));
+        assert!(lines.contains(&"added (breaking): GET /users: required header parameter `X-Tenant` added".to_string()));
+        assert!(lines.contains(&"changed (breaking): GET /users: response 200 body items property `id` type changed from string to integer".to_string()));
+        assert!(lines.contains(&"removed (breaking): GET /users: response 200 body items property `email` removed".to_string()));
+        assert!(lines.contains(&"added: GET /users: response 200 body items property `nickname` added".to_string()));
+        assert!(lines.contains(&"changed: POST /users: request body property `role` now also allows \"guest\"".to_string()));
+        assert!(lines.contains(&"removed (breaking): DELETE /users/{id}: operation removed".to_string()));
+        assert!(lines.contains(&"added: GET /users/{id}/avatar: operation added".to_string()));
+        
+        let breaking: Vec<&Change> = changes.iter().filter(|c| c.breaking).collect();
+        assert_eq!(breaking.len(), 4);
+        assert!(breaking.iter().all(|c| c.kind != ChangeKind::Added || c.message.contains("required")));
+        
+        // Identical specifications have no changes
+        assert!(diff_specs(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_operation_id_generation() {
+        let spec = parse_swagger_file(get_test_data_path("sample_operation_ids.yaml")).unwrap();
+        let operation = |path: &str, method: &str| {
+            spec.paths
+                .iter()
+                .filter(|p| p.path == path)
+                .flat_map(|p| p.operations.iter())
+                .find(|op| op.method == method)
+                .unwrap_or_else(|| panic!("{} {} not found", method, path))
+        };
+        
+        // Declared operationIds keep their name, generated ones that collide get a suffix
+        assert_eq!(operation("/accounts", "GET").operation_id, "get_users");
+        assert_eq!(operation("/users", "GET").operation_id, "get_users_2");
+        assert_eq!(operation("/users", "GET").declared_operation_id, None);
+        assert_eq!(operation("/users/{id}/orders", "GET").operation_id, "get_users_by_id_orders");
+        
+        // Characters that can't appear in identifiers are replaced, and the declared value is kept
+        assert_eq!(operation("/users/{id}", "GET").operation_id, "user_get");
+        assert_eq!(operation("/users/{id}", "GET").declared_operation_id.as_deref(), Some("user.get"));
+        assert_eq!(operation("/users/{id}", "PUT").operation_id, "user_get_2");
+        assert_eq!(operation("/2fa", "POST").operation_id, "op_2fa_verify");
+    }
+
+    #[test]
+    fn test_string_id_path_params() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("string_ids");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A GET with a string `id` and no documented body only checks the status, instead of reading the body as a user
+        for (sample, test) in [("sample_diff_new.yaml", "fn test_get_avatar()"), ("sample_operation_ids.yaml", "fn test_user_get()")] {
+            let spec = parse_swagger_file(get_test_data_path(sample)).unwrap();
+            for (framework, name) in [(TestFramework::Reqwest, "reqwest"), (TestFramework::Ureq, "ureq")] {
+                let output_dir = test_output_dir.join(name).join(sample);
+                create_generator(framework).unwrap()
+                    .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                    .unwrap();
+                let tests = fs::read_to_string(output_dir.join("api_tests.rs")).unwrap();
+                let get = tests.split(test).nth(1).unwrap().split("#[").next().unwrap();
+                assert!(get.contains("    let id = \"test_value\";") && get.contains("    assert_eq!(response.status()"));
+                assert!(!get.contains("User") && !get.contains("user.id"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_api_info() {
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi_3_1.yaml")).unwrap();
+        assert_eq!(spec.info.title, "Sample 3.1 API");
+        assert_eq!(spec.info.version, "1.0.0");
+        assert_eq!(spec.info.contact.as_ref().unwrap().email.as_deref(), Some("support@sample.com"));
+        let license = spec.info.license.as_ref().unwrap();
+        assert_eq!(license.name, "Apache 2.0");
+        assert_eq!(license.identifier.as_deref(), Some("Apache-2.0"));
+        
+        // Versions that package managers don't accept fall back to a default
+        let info = ApiInfo { title: "2024 Billing API".to_string(), version: "2024-01-01".to_string(), ..ApiInfo::default() };
+        assert_eq!(info.package_name("-", "tests"), "api-2024-billing-api-tests");
+        assert_eq!(info.package_version("0.1.0"), "0.1.0");
+        
+        // The title and version name the generated projects
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("api-info");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        for framework in [TestFramework::Reqwest, TestFramework::Postman, TestFramework::Jest] {
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        let cargo = fs::read_to_string(test_output_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo.contains(r#"name = "sample_3_1_api_tests""#));
+        let package: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("package.json")).unwrap()).unwrap();
+        assert_eq!(package["name"], "sample-3-1-api-tests");
+        assert_eq!(package["version"], "1.0.0");
+        let readme = fs::read_to_string(test_output_dir.join("README.md")).unwrap();
+        assert!(readme.starts_with("# Sample 3.1 API Tests"));
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        assert_eq!(collection["info"]["name"], "Sample 3.1 API Tests");
+    }
+
+    #[test]
+    fn test_lenient_parsing() {
+        // A reference that doesn't resolve fails parsing by default
+        let result = parse_swagger_file(get_test_data_path("sample_lenient.yaml"));
+        assert!(matches!(result, Err(ParserError::UnresolvedReference(_))));
+        
+        let options = ParserOptions { mode: ParseMode::Lenient, ..ParserOptions::default() };
+        let spec = parse_swagger_file_with_options(get_test_data_path("sample_lenient.yaml"), &options).unwrap();
+        assert_eq!(spec.paths.len(), 2);
+        
+        // Malformed parameters are left out, and the broken reference is kept as it is
+        let list = &spec.paths[1].operations[0];
+        assert_eq!(list.operation_id, "listUsers");
+        let names: Vec<&str> = list.query_params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["page"]);
+        assert!(list.body_param.is_none());
+        let items = match list.responses[0].schema.as_ref() {
+            Some(Schema::Array(array)) => array.items.as_deref(),
+            _ => None,
+        };
+        assert_eq!(items, Some(&Schema::Ref("#/components/schemas/Missing".to_string())));
+        
+        // Every problem is reported with where it is in the document
+        let warnings: Vec<String> = spec.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(warnings, vec![
+            "/paths/~1users/get: parameter has no name, left out",
+            "/paths/~1users/get: parameter \"limit\" has unknown location \"body\", left out",
+            "/paths/~1users/get: Unresolved reference: #/components/schemas/Missing, left unresolved",
+        ]);
+    }
+
+    #[test]
+    fn test_server_variables() {
+        let mut spec = parse_swagger_file(get_test_data_path("sample_servers.yaml")).unwrap();
+        assert_eq!(spec.base_url, "https://us.api.sample.com/v2");
+        
+        let server = spec.server.clone().unwrap();
+        assert_eq!(server.description.as_deref(), Some("Production"));
+        assert_eq!(server.variables[0].name, "region");
+        assert_eq!(server.variables[0].enum_values, vec!["us", "eu", "ap"]);
+        
+        spec.set_server_variables(&[("region".to_string(), "eu".to_string()), ("version".to_string(), "v3".to_string())]).unwrap();
+        assert_eq!(spec.base_url, "https://eu.api.sample.com/v3");
+        
+        let unknown = spec.set_server_variables(&[("zone".to_string(), "a".to_string())]);
+        assert!(matches!(unknown, Err(ParserError::UnknownServerVariable(name)) if name == "zone"));
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("servers");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        create_generator(TestFramework::Reqwest).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("http://localhost:3000/v3/status"));
+    }
+
+    #[test]
+    fn test_server_selection() {
+        let mut spec = parse_swagger_file(get_test_data_path("sample_servers.yaml")).unwrap();
+        let descriptions: Vec<Option<&str>> = spec.servers.iter().map(|s| s.description.as_deref()).collect();
+        assert_eq!(descriptions, vec![Some("Production"), Some("Staging"), Some("Mock server")]);
+        
+        spec.select_server(&ServerSelection::Index(2)).unwrap();
+        assert_eq!(spec.base_url, "http://localhost:4010/mock");
+        spec.select_server(&ServerSelection::Name("staging".to_string())).unwrap();
+        assert_eq!(spec.base_url, "https://staging.api.sample.com/beta");
+        assert!(spec.server.as_ref().unwrap().variables.is_empty());
+        
+        let unknown = spec.select_server(&ServerSelection::Index(3));
+        assert!(matches!(unknown, Err(ParserError::UnknownServer(selection)) if selection == "#3"));
+        
+        // The selected server seeds the base path of the generated tests
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("server-selection");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        let options = GenerateOptions {
+            server: Some(ServerSelection::Name("Mock server".to_string())),
+            ..GenerateOptions::default()
+        };
+        generate_tests_from_spec(get_test_data_path("sample_servers.yaml"), &test_output_dir, TestFramework::Reqwest, "http://localhost:3000", &options).unwrap();
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("http://localhost:3000/mock/status"));
+    }
+
+    #[test]
+    fn test_path_level_parameters() {
+        let spec = parse_swagger_file(get_test_data_path("sample_path_params.yaml")).unwrap();
+        let operation = |id: &str| spec.paths[0].operations.iter().find(|op| op.operation_id == id).unwrap();
+        
+        // Both operations inherit the path's parameters
+        let get = operation("getProject");
+        assert_eq!(get.path_params[0].name, "projectId");
+        assert_eq!(get.query_params.len(), 1);
+        assert!(!get.query_params[0].required);
+        
+        // An operation level parameter with the same name and location replaces the inherited one
+        let delete = operation("deleteProject");
+        assert_eq!(delete.path_params[0].name, "projectId");
+        assert_eq!(delete.query_params.len(), 1);
+        assert!(delete.query_params[0].required);
+        
+        // Swagger 2.0 path items share parameters the same way
+        let v2 = parse_swagger_string(r#"
+swagger: '2.0'
+info: {title: Path parameters, version: '1.0'}
+paths:
+  /teams/{teamId}:
+    parameters:
+      - {name: teamId, in: path, required: true, type: string}
+    get:
+      responses:
+        '200': {description: OK}
+"#).unwrap();
+        assert_eq!(v2.paths[0].operations[0].path_params[0].name, "teamId");
+    }
+
+    #[test]
+    fn test_group_by_tag() {
+        let spec = parse_swagger_file(get_test_data_path("sample_tags.yaml")).unwrap();
+        let get_pet = spec.paths.iter().find(|p| p.path == "/pets/{petId}").unwrap();
+        assert_eq!(get_pet.operations[0].tags, vec!["Pet Store", "Inventory"]);
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("tags");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let options = GeneratorOptions { layout: Some(OutputLayout::PerTag), ..Default::default() };
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        // Operations go to the file of their first tag, untagged ones to `default`
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("mod pet_store_tests;"));
+        assert!(reqwest.contains("mod default_tests;"));
+        let pet_store = fs::read_to_string(test_output_dir.join("api_tests").join("pet_store_tests.rs")).unwrap();
+        assert!(pet_store.starts_with("use super::*;"));
+        assert!(pet_store.contains("async fn test_list_pets()"));
+        assert!(pet_store.contains("async fn test_get_pet()"));
+        assert!(!test_output_dir.join("api_tests").join("inventory_tests.rs").exists());
+        
+        let orders = fs::read_to_string(test_output_dir.join("test_orders.py")).unwrap();
+        assert!(orders.contains("def test_createOrder():"));
+        assert!(!test_output_dir.join("test_api.py").exists());
+        
+        let default = fs::read_to_string(test_output_dir.join("default.test.js")).unwrap();
+        assert!(default.contains("test('health'"));
+        
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let folders: Vec<&str> = collection["item"].as_array().unwrap().iter().map(|f| f["name"].as_str().unwrap()).collect();
+        assert_eq!(folders, vec!["default", "orders", "Pet Store"]);
+        assert_eq!(collection["item"][2]["item"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_output_layout() {
+        assert_eq!(parse_layout("single"), Ok(OutputLayout::Single));
+        assert_eq!(parse_layout("Per-Tag"), Ok(OutputLayout::PerTag));
+        assert_eq!(parse_layout("per-path"), Ok(OutputLayout::PerPath));
+        assert!(parse_layout("nested").is_err());
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_tags.yaml")).unwrap();
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("layout");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        let generate = |layout: OutputLayout, frameworks: &[TestFramework]| -> PathBuf {
+            let output_dir = test_output_dir.join(layout.split().replace(' ', "_"));
+            let options = GeneratorOptions { layout: Some(layout), ..Default::default() };
+            for &framework in frameworks {
+                create_generator_with_options(framework, &options).unwrap()
+                    .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                    .unwrap();
+            }
+            output_dir
+        };
+        
+        // A single file holds every operation, whatever the framework's usual layout
+        let single = generate(OutputLayout::Single, &[TestFramework::Jest, TestFramework::Postman, TestFramework::Go, TestFramework::Karate]);
+        let jest = fs::read_to_string(single.join("api.test.js")).unwrap();
+        for test in ["test('listPets'", "test('getPet'", "test('createOrder'", "test('health'"] {
+            assert!(jest.contains(test), "{}", test);
+        }
+        assert!(!single.join("pets.test.js").exists());
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(single.join("postman_collection.json")).unwrap()).unwrap();
+        assert_eq!(collection["item"].as_array().unwrap().len(), 1);
+        assert_eq!(collection["item"][0]["name"], "Tagged API");
+        assert_eq!(collection["item"][0]["item"].as_array().unwrap().len(), 4);
+        assert!(fs::read_to_string(single.join("api_test.go")).unwrap().contains("// TestApi tests all operations\n"));
+        assert!(fs::read_dir(single).unwrap().all(|entry| !entry.unwrap().file_name().to_string_lossy().starts_with("pet_store")));
+        
+        // One file per path, for the frameworks writing one file or one per tag by default
+        let per_path = generate(OutputLayout::PerPath, &[TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Hurl, TestFramework::Karate]);
+        let reqwest = fs::read_to_string(per_path.join("api_tests.rs")).unwrap();
+        for module in ["mod pets_tests;", "mod pets_petid_tests;", "mod orders_tests;", "mod health_tests;"] {
+            assert!(reqwest.contains(module), "{}", module);
+        }
+        let get_pet = fs::read_to_string(per_path.join("api_tests").join("pets_petid_tests.rs")).unwrap();
+        assert!(get_pet.contains("async fn test_get_pet()"));
+        assert!(!get_pet.contains("async fn test_list_pets()"));
+        let pytest = fs::read_to_string(per_path.join("test_pets_petid.py")).unwrap();
+        assert!(pytest.contains("def test_getPet():"));
+        assert!(!per_path.join("test_api.py").exists());
+        assert!(fs::read_to_string(per_path.join("orders.hurl")).unwrap().starts_with("# Operations on /orders\n"));
+        let feature = fs::read_to_string(per_path.join("src/test/java/apitests/pets_petId.feature")).unwrap();
+        assert!(feature.starts_with("Feature: Operations on /pets/{petId}\n"));
+        
+        // One file per tag, as --group-by-tag writes them
+        let per_tag = generate(OutputLayout::PerTag, &[TestFramework::Jest]);
+        assert!(per_tag.join("pet_store.test.js").exists());
+        assert!(per_tag.join("default.test.js").exists());
+    }
+
+    #[test]
+    fn test_assert_level() {
+        assert_eq!(parse_assert_level("status"), Ok(AssertLevel::Status));
+        assert_eq!(parse_assert_level("Exact"), Ok(AssertLevel::Exact));
+        assert!(parse_assert_level("strict").is_err());
+        
+        // Response examples are read from the JSON media type, or the `examples` of Swagger 2.0 responses
+        let spec = parse_swagger_file(get_test_data_path("sample_assert_level.yaml")).unwrap();
+        let get_widget = spec.paths.iter().flat_map(|p| &p.operations).find(|op| op.operation_id == "getWidget").unwrap();
+        assert_eq!(get_widget.responses[0].example, Some(serde_json::json!({ "id": 7, "name": "Sprocket" })));
+        let swagger = parse_swagger_string(r#"
+swagger: "2.0"
+info: { title: Widgets, version: "1.0" }
+paths:
+  /widgets:
+    get:
+      operationId: listWidgets
+      responses:
+        "200":
+          description: The widgets
+          examples:
+            application/json: [{ id: 7 }]
+"#).unwrap();
+        assert_eq!(swagger.paths[0].operations[0].responses[0].example, Some(serde_json::json!([{ "id": 7 }])));
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("assert-level");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        let generate = |assert_level: AssertLevel| -> PathBuf {
+            let output_dir = test_output_dir.join(format!("{:?}", assert_level).to_lowercase());
+            let options = GeneratorOptions { assert_level, ..Default::default() };
+            for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+                create_generator_with_options(framework, &options).unwrap()
+                    .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                    .unwrap();
+            }
+            output_dir
+        };
+        
+        // The status only: no headers, schemas or bodies are checked
+        let status = generate(AssertLevel::Status);
+        let reqwest = fs::read_to_string(status.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("assert_eq!(response.status().as_u16(), 200);\n}"));
+        assert!(!reqwest.contains("ETag"));
+        let pytest = fs::read_to_string(status.join("test_api.py")).unwrap();
+        assert!(!pytest.contains("validate_schema") && !pytest.contains("\"ETag\" in response.headers"));
+        assert!(!status.join("schemas").exists());
+        let jest = fs::read_to_string(status.join("widgets_widgetId.test.js")).unwrap();
+        assert!(!jest.contains("schemaErrors") && !jest.contains("toEqual"));
+        let postman = fs::read_to_string(status.join("postman_collection.json")).unwrap();
+        assert!(!postman.contains("header is present") && !postman.contains("matches its schema"));
+        
+        // The schema, by default, without comparing the body to the example
+        let schema = generate(AssertLevel::Schema);
+        let pytest = fs::read_to_string(schema.join("test_api.py")).unwrap();
+        assert!(pytest.contains("validate_schema(response.json(), \"getWidget\")"));
+        assert!(!pytest.contains("documented example"));
+        
+        // The documented example too, from the media type or the schema
+        let exact = generate(AssertLevel::Exact);
+        let reqwest = fs::read_to_string(exact.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("let actual: serde_json::Value = response.json().await.expect(\"Failed to parse response\");\n    assert_eq!(actual, json!({\n      \"id\": 7,\n      \"name\": \"Sprocket\"\n    }));"));
+        assert!(reqwest.contains("let _body: ResponseBody = serde_json::from_value(actual)"));
+        let pytest = fs::read_to_string(exact.join("test_api.py")).unwrap();
+        assert!(pytest.contains("validate_schema(response.json(), \"getStatus\")\n    \n    # Verify the response body is the documented example\n    assert response.json() == {\n        \"healthy\": True\n    }"));
+        let jest = fs::read_to_string(exact.join("widgets_widgetId.test.js")).unwrap();
+        assert!(jest.contains("expect(response.data).toEqual({\n    \"id\": 7,\n    \"name\": \"Sprocket\"\n  });"));
+        let postman = fs::read_to_string(exact.join("postman_collection.json")).unwrap();
+        assert!(postman.contains("pm.expect(pm.response.json()).to.eql({\\\"id\\\":7,\\\"name\\\":\\\"Sprocket\\\"});"));
+    }
+
+    #[test]
+    fn test_ignored_options() {
+        // Options left at their defaults are never reported
+        let options = GenerateOptions::default();
+        assert!(ignored_options(TestFramework::Go, &options).is_empty());
+        
+        let options = GenerateOptions {
+            generator: GeneratorOptions { assert_level: AssertLevel::Status, negative_tests: true, ..Default::default() },
+            ..Default::default()
+        };
+        for framework in [TestFramework::Reqwest, TestFramework::Ureq, TestFramework::Pytest, TestFramework::PytestAsync, TestFramework::Jest, TestFramework::Postman] {
+            assert!(ignored_options(framework, &options).is_empty());
+        }
+        assert_eq!(ignored_options(TestFramework::RestAssured, &options), vec![
+            "rest-assured tests ignore --negative-tests, which only reqwest, ureq, pytest, Jest and Postman tests support",
+            "rest-assured tests ignore --assert-level, which only reqwest, ureq, pytest, Jest and Postman tests support",
+        ]);
+        
+        // Credentials are only sent by the same generators
+        let auth = AuthConfig { api_key_env: Some("API_KEY".to_string()), ..Default::default() };
+        let options = GenerateOptions { generator: GeneratorOptions { auth, ..Default::default() }, ..Default::default() };
+        assert!(ignored_options(TestFramework::Postman, &options).is_empty());
+        assert_eq!(
+            ignored_options(TestFramework::K6, &options),
+            vec!["k6 tests ignore --auth apikey, which only reqwest, ureq, pytest, Jest and Postman tests support"]
+        );
+        
+        // Access tokens too, each kind of credentials getting its own warning
+        let auth = AuthConfig {
+            bearer_env: Some("API_TOKEN".to_string()),
+            client_credentials_env: Some(("CLIENT_ID".to_string(), "CLIENT_SECRET".to_string())),
+            ..Default::default()
+        };
+        let options = GenerateOptions { generator: GeneratorOptions { auth, ..Default::default() }, ..Default::default() };
+        assert!(ignored_options(TestFramework::Ureq, &options).is_empty());
+        assert_eq!(ignored_options(TestFramework::Playwright, &options), vec![
+            "playwright tests ignore --auth bearer, which only reqwest, ureq, pytest, Jest and Postman tests support",
+            "playwright tests ignore --auth oauth2, which only reqwest, ureq, pytest, Jest and Postman tests support",
+        ]);
+        
+        // Only the Rust tests take the base path from the chosen server
+        let options = GenerateOptions { server: Some(ServerSelection::Index(1)), ..Default::default() };
+        assert!(ignored_options(TestFramework::Ureq, &options).is_empty());
+        assert_eq!(
+            ignored_options(TestFramework::Pytest, &options),
+            vec!["pytest tests ignore --server-index and --server-name, which only reqwest and ureq tests support"]
+        );
+        let options = GenerateOptions { server_variables: vec![("version".to_string(), "v2".to_string())], ..Default::default() };
+        assert!(ignored_options(TestFramework::Reqwest, &options).is_empty());
+        assert_eq!(
+            ignored_options(TestFramework::Hurl, &options),
+            vec!["hurl tests ignore --server-var, which only reqwest and ureq tests support"]
+        );
+    }
+
+    #[test]
+    fn test_deprecated_operations() {
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        let operations: Vec<_> = spec.paths.iter().flat_map(|p| &p.operations).collect();
+        assert!(!operations.iter().find(|op| op.operation_id == "listOrders").unwrap().deprecated);
+        assert!(operations.iter().find(|op| op.operation_id == "listLegacyOrders").unwrap().deprecated);
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("deprecated");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // By default, deprecated operations get a test that is skipped
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("#[tokio::test]\n#[ignore = \"deprecated operation\"]\nasync fn test_list_legacy_orders()"));
+        assert!(reqwest.contains("#[tokio::test]\nasync fn test_list_orders()"));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains("@pytest.mark.skip(reason=\"deprecated operation\")\ndef test_listLegacyOrders():"));
+        assert!(!pytest.contains("@pytest.mark.skip(reason=\"deprecated operation\")\ndef test_listOrders():"));
+        
+        let jest = fs::read_to_string(test_output_dir.join("legacy_orders.test.js")).unwrap();
+        assert!(jest.contains("test.skip('listLegacyOrders'"));
+        
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        assert!(collection.contains("pm.test.skip(\\\"Status code is 200\\\""));
+        
+        // With `skip_deprecated`, they are left out altogether
+        fs::remove_dir_all(&test_output_dir).unwrap();
+        let options = GeneratorOptions { skip_deprecated: true, ..Default::default() };
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(!reqwest.contains("test_list_legacy_orders"));
+        assert!(reqwest.contains("test_list_orders"));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(!pytest.contains("listLegacyOrders"));
+        
+        assert!(test_output_dir.join("orders.test.js").exists());
+        assert!(!test_output_dir.join("legacy_orders.test.js").exists());
+        
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        assert_eq!(collection["item"].as_array().unwrap().len(), 1);
+        assert!(!collection.to_string().contains("pm.test.skip"));
+    }
+
+    #[test]
+    fn test_parallel_generation() {
+        let spec = parse_swagger_file(get_test_data_path("sample_tags.yaml")).unwrap();
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("parallel");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Every thread count writes the same files, in the same order
+        let generate = |jobs: usize, group_by_tag: bool| -> Vec<(PathBuf, String)> {
+            let output_dir = test_output_dir.join(format!("{}-{}", jobs, group_by_tag));
+            let layout = group_by_tag.then_some(OutputLayout::PerTag);
+            let options = GeneratorOptions { layout, jobs: Some(jobs), ..Default::default() };
+            for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+                create_generator_with_options(framework, &options).unwrap()
+                    .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                    .unwrap();
+            }
+            
+            let mut files = Vec::new();
+            let mut dirs = vec![output_dir.clone()];
+            while let Some(dir) = dirs.pop() {
+                for entry in fs::read_dir(dir).unwrap() {
+                    let path = entry.unwrap().path();
+                    if path.is_dir() {
+                        dirs.push(path);
+                    } else {
+                        // Postman collections get a new id every time
+                        let content = fs::read_to_string(&path).unwrap()
+                            .lines()
+                            .filter(|line| !line.contains("_postman_id"))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        files.push((path.strip_prefix(&output_dir).unwrap().to_path_buf(), content));
+                    }
+                }
+            }
+            files.sort();
+            files
+        };
+        
+        for group_by_tag in [false, true] {
+            let sequential = generate(1, group_by_tag);
+            assert!(sequential.len() > 5);
+            assert_eq!(generate(4, group_by_tag), sequential);
+        }
+        
+        let reqwest = fs::read_to_string(test_output_dir.join("4-false").join("api_tests.rs")).unwrap();
+        let positions: Vec<usize> = ["async fn test_health", "async fn test_create_order", "async fn test_list_pets", "async fn test_get_pet"]
+            .iter()
+            .map(|test| reqwest.find(test).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_vendor_extensions() {
+        let spec = parse_swagger_file(get_test_data_path("sample_extensions.yaml")).unwrap();
+        let operation = &spec.paths[0].operations[0];
+        
+        assert_eq!(operation.extensions.get("x-flaky"), Some(&serde_json::json!(true)));
+        assert_eq!(operation.extensions.get("x-auth-scope"), Some(&serde_json::json!("reports:write")));
+        assert!(!operation.extensions.contains_key("operationId"));
+        
+        assert_eq!(operation.query_params[0].extensions.get("x-internal"), Some(&serde_json::json!(true)));
+        
+        let body = operation.body_param.as_ref().unwrap();
+        assert_eq!(body.extensions.get("x-body-name"), Some(&serde_json::json!("report")));
+        
+        let created = operation.responses.iter().find(|r| r.status_code == "201").unwrap();
+        assert_eq!(created.extensions.get("x-rate-limited"), Some(&serde_json::json!({ "perMinute": 10 })));
+        let invalid = operation.responses.iter().find(|r| r.status_code == "400").unwrap();
+        assert!(invalid.extensions.is_empty());
+    }
+
+    #[test]
+    fn test_validate_document() {
+        let document = read_document(get_test_data_path("sample_invalid.yaml")).unwrap();
+        let findings = validate_document(&document);
+        let has = |severity: Severity, pointer: &str, message: &str| {
+            findings.iter().any(|f| f.severity == severity && f.pointer == pointer && f.message.contains(message))
+        };
+        
+        assert!(has(
+            Severity::Error,
+            "/paths/~1users~1{userId}~1posts~1{postId}/get/responses/200/content/application~1json/schema/$ref",
+            "\"#/components/schemas/Post\" does not resolve",
+        ));
+        assert!(has(Severity::Error, "/paths/~1users~1{userId}/delete/operationId", "\"getUser\" is already used by /paths/~1users~1{userId}/get"));
+        assert!(has(Severity::Warning, "/paths/~1users~1{userId}~1posts~1{postId}/get", "no operationId"));
+        assert!(has(Severity::Error, "/paths/~1users~1{userId}~1posts~1{postId}/get", "\"postId\" is not declared"));
+        assert!(has(Severity::Error, "/paths/~1users~1{userId}~1posts~1{postId}/get/parameters/1", "\"slug\" does not appear"));
+        assert!(has(Severity::Warning, "/components/schemas/Legacy~1User", "never referenced"));
+        
+        // Path parameters given by reference, and schemas only referenced from other schemas, are fine
+        assert!(!findings.iter().any(|f| f.message.contains("\"userId\"") || f.message.contains("\"Address\"")));
+        assert_eq!(findings.len(), 6);
+        
+        assert_eq!(
+            findings[0].to_string(),
+            "error: /paths/~1users~1{userId}~1posts~1{postId}/get/responses/200/content/application~1json/schema/$ref: reference \"#/components/schemas/Post\" does not resolve"
+        );
+        
+        for sample in ["sample_openapi.yaml", "sample_swagger.json"] {
+            let document = read_document(get_test_data_path(sample)).unwrap();
+            assert!(validate_document(&document).iter().all(|f| f.severity != Severity::Error), "{}", sample);
+        }
+    }
+
+    #[test]
+    fn test_webhooks() {
+        let spec = parse_swagger_file(get_test_data_path("sample_webhooks.yaml")).unwrap();
+        assert_eq!(spec.paths.len(), 1);
+        assert_eq!(spec.webhooks.len(), 2);
+        
+        let shipped = spec.webhooks.iter().find(|w| w.name == "orderShipped").unwrap();
+        let operation = &shipped.operations[0];
+        assert_eq!(operation.method, "POST");
+        assert_eq!(operation.body_param.as_ref().unwrap().example, Some(serde_json::json!({ "orderId": 42, "carrier": "DHL" })));
+        
+        // Webhooks can be declared by reference to a path item
+        let cancelled = spec.webhooks.iter().find(|w| w.name == "orderCancelled").unwrap();
+        assert!(cancelled.operations[0].deprecated);
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("webhooks");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        let main = fs::read_to_string(test_output_dir.join("main.rs")).unwrap();
+        assert!(main.contains("mod webhook_tests;"));
+        let reqwest = fs::read_to_string(test_output_dir.join("webhook_tests.rs")).unwrap();
+        assert!(reqwest.contains("std::env::var(\"WEBHOOK_URL\")"));
+        assert!(reqwest.contains("async fn test_webhook_order_shipped()"));
+        assert!(reqwest.contains("\"carrier\": \"DHL\""));
+        assert!(reqwest.contains("client.request(reqwest::Method::POST, webhook_url()).json(&payload)"));
+        assert!(reqwest.contains("assert_eq!(response.status().as_u16(), 202);"));
+        assert!(reqwest.contains("#[ignore = \"deprecated operation\"]\nasync fn test_webhook_post_order_cancelled()"));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_webhooks.py")).unwrap();
+        assert!(pytest.contains("WEBHOOK_URL = os.environ.get(\"WEBHOOK_URL\""));
+        assert!(pytest.contains("@pytest.fixture\ndef deliver_webhook():"));
+        assert!(pytest.contains("def test_webhook_orderShipped(deliver_webhook):"));
+        assert!(pytest.contains("response = deliver_webhook(\"POST\", payload)"));
+        
+        let jest = fs::read_to_string(test_output_dir.join("webhooks.test.js")).unwrap();
+        assert!(jest.contains("process.env.WEBHOOK_URL"));
+        assert!(jest.contains("test('webhook orderShipped'"));
+        
+        let collection = fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let folder = &collection["item"][1];
+        assert_eq!(folder["name"], "Webhooks");
+        assert_eq!(folder["item"][1]["request"]["url"]["raw"], "{{webhookUrl}}");
+        assert_eq!(collection["variable"][0]["key"], "webhookUrl");
+        
+        // Specs without webhooks don't get webhook files
+        let spec = parse_swagger_file(get_test_data_path("sample_tags.yaml")).unwrap();
+        fs::remove_dir_all(&test_output_dir).unwrap();
+        create_generator(TestFramework::Reqwest).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        assert!(!test_output_dir.join("webhook_tests.rs").exists());
+        assert!(!fs::read_to_string(test_output_dir.join("main.rs")).unwrap().contains("webhook_tests"));
+    }
+
+    #[test]
+    fn test_callbacks() {
+        let spec = parse_swagger_file(get_test_data_path("sample_callbacks.yaml")).unwrap();
+        let operation = &spec.paths[0].operations[0];
+        assert_eq!(operation.callbacks.len(), 2);
+        
+        let shipped = operation.callbacks.iter().find(|c| c.name == "onShipped").unwrap();
+        assert_eq!(shipped.expression, "{$request.body#/callbackUrl}");
+        assert_eq!(shipped.operations[0].method, "POST");
+        assert!(shipped.operations[0].body_param.is_some());
+        
+        // Callbacks can be declared by reference
+        let cancelled = operation.callbacks.iter().find(|c| c.name == "onCancelled").unwrap();
+        assert_eq!(cancelled.expression, "{$request.body#/callbackUrl}/cancelled");
+        assert_eq!(cancelled.operations[0].responses[0].status_code, "204");
+        
+        let api_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("callbacks_api");
+        if api_output_dir.exists() {
+            fs::remove_dir_all(&api_output_dir).unwrap();
+        }
+        
+        generate_axum_api(&spec, &api_output_dir).unwrap();
+        
+        let callbacks = fs::read_to_string(api_output_dir.join("src").join("callbacks.rs")).unwrap();
+        assert!(callbacks.contains("/// `onShipped` callback of `createOrder`, sent to `{$request.body#/callbackUrl}`"));
+        assert!(callbacks.contains("pub async fn create_order_on_shipped(Json(target): Json<CallbackTarget>)"));
+        assert!(callbacks.contains("\"status\": \"shipped\""));
+        assert!(callbacks.contains("client.request(reqwest::Method::POST, &target.url).json(&payload)"));
+        assert!(callbacks.contains(".route(\"/_callbacks/createOrder/onCancelled\", post(create_order_on_cancelled))"));
+        
+        let main = fs::read_to_string(api_output_dir.join("src").join("main.rs")).unwrap();
+        assert!(main.contains("mod callbacks;"));
+        assert!(main.contains("app_router().merge(callbacks::routes())"));
+        let cargo = fs::read_to_string(api_output_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo.contains("reqwest = "));
+    }
+
+    #[test]
+    fn test_links() {
+        let spec = parse_swagger_file(get_test_data_path("sample_links.yaml")).unwrap();
+        let create = spec.paths.iter().find(|p| p.path == "/users").unwrap();
+        let created = &create.operations[0].responses[0];
+        assert_eq!(created.links.len(), 3);
+        
+        let get_link = created.links.iter().find(|l| l.name == "GetUserById").unwrap();
+        assert_eq!(get_link.operation_id.as_deref(), Some("getUser"));
+        assert_eq!(get_link.parameters, vec![("id".to_string(), LinkValue::ResponseBody("/id".to_string()))]);
+        assert_eq!(spec.link_target(get_link).unwrap().1.operation_id, "getUser");
+        
+        // Links can name their operation by reference, wrap expressions in braces and be declared by reference
+        let delete_link = created.links.iter().find(|l| l.name == "DeleteUser").unwrap();
+        assert_eq!(delete_link.parameters[0].1, LinkValue::ResponseBody("/id".to_string()));
+        assert_eq!(spec.link_target(delete_link).unwrap().1.operation_id, "deleteUser");
+        let orders_link = created.links.iter().find(|l| l.name == "ListUserOrders").unwrap();
+        assert!(orders_link.parameters.contains(&("userId".to_string(), LinkValue::ResponseHeader("X-User-Id".to_string()))));
+        assert!(orders_link.parameters.contains(&("status".to_string(), LinkValue::Constant(serde_json::json!("open")))));
+        
+        let output_dir = |name: &str| {
+            let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("target")
+                .join("test-output")
+                .join(name);
+            if dir.exists() {
+                fs::remove_dir_all(&dir).unwrap();
+            }
+            dir
+        };
+        
+        // Linked operations create their resource first instead of using the sample user API helper
+        let reqwest_dir = output_dir("links_reqwest");
+        create_generator_with_options(TestFramework::Reqwest, &with_optional_query_params()).unwrap().generate_tests(&spec, &reqwest_dir, "http://localhost:3000").unwrap();
+        let rust = fs::read_to_string(reqwest_dir.join("api_tests.rs")).unwrap();
+        assert!(rust.contains("fn linked_value(body: &serde_json::Value, pointer: &str) -> String"));
+        assert!(rust.contains(".request(reqwest::Method::POST, \"http://localhost:3000/users\")"));
+        assert!(rust.contains("let id = linked_value(&setup_body, \"/id\");"));
+        assert!(rust.contains("let userId = setup_headers.get(\"X-User-Id\")"));
+        assert!(rust.contains("let status = \"open\".to_string();"));
+        assert!(rust.contains("(\"userId\", userId.as_str())"));
+        assert!(!rust.contains("create_test_user(\""));
+        
+        let pytest_dir = output_dir("links_pytest");
+        create_generator_with_options(TestFramework::Pytest, &with_optional_query_params()).unwrap().generate_tests(&spec, &pytest_dir, "http://localhost:3000").unwrap();
+        let python = fs::read_to_string(pytest_dir.join("test_api.py")).unwrap();
+        assert!(python.contains("def linked_value(body, pointer):"));
+        assert!(python.contains("setup_response = requests.request(\"POST\", f\"http://localhost:3000/users\", json={"));
+        assert!(python.contains("id = linked_value(setup_response.json(), \"/id\")"));
+        assert!(python.contains("userId = setup_response.headers[\"X-User-Id\"]"));
+        
+        let jest_dir = output_dir("links_jest");
+        create_generator_with_options(TestFramework::Jest, &with_optional_query_params()).unwrap().generate_tests(&spec, &jest_dir, "http://localhost:3000").unwrap();
+        let js = fs::read_to_string(jest_dir.join("users_id.test.js")).unwrap();
+        assert!(js.contains("function linkedValue(body, pointer)"));
+        assert!(js.contains("const id = linkedValue(setupResponse.data, \"/id\");"));
+        let js = fs::read_to_string(jest_dir.join("orders.test.js")).unwrap();
+        assert!(js.contains("const userId = setupResponse.headers[\"x-user-id\"];"));
+        
+        // Postman stores the linked values in collection variables
+        let postman_dir = output_dir("links_postman");
+        create_generator_with_options(TestFramework::Postman, &with_optional_query_params()).unwrap().generate_tests(&spec, &postman_dir, "http://localhost:3000").unwrap();
+        let collection = fs::read_to_string(postman_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        let collection = collection.to_string();
+        assert!(collection.contains(r#"pm.collectionVariables.set(\"GetUserById.id\", pm.response.json()[\"id\"]);"#));
+        assert!(collection.contains(r#"pm.collectionVariables.set(\"ListUserOrders.userId\", pm.response.headers.get(\"X-User-Id\"));"#));
+        assert!(collection.contains("{{baseUrl}}/users/{{GetUserById.id}}"));
+    }
+
+    #[test]
+    fn test_generate_reqwest_tests() {
+        let swagger_path = get_test_data_path("sample_swagger.json");
+        let spec = parse_swagger_file(&swagger_path).unwrap();
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("reqwest");
+        
+        // Clean previous test output
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        fs::create_dir_all(&test_output_dir).unwrap();
+        
+        // Generate tests
+        let generator = create_generator(TestFramework::Reqwest).unwrap();
+        let result = generator.generate_tests(&spec, &test_output_dir, "http://localhost:3000");
+        
+        assert!(result.is_ok());
+        
+        // Check that test files were created
+        assert!(test_output_dir.join("api_tests.rs").exists());
+        assert!(test_output_dir.join("main.rs").exists());
+        assert!(test_output_dir.join("Cargo.toml").exists());
+    }
+
+    #[test]
+    fn test_generate_pytest_tests() {
+        let swagger_path = get_test_data_path("sample_swagger.json");
+        let spec = parse_swagger_file(&swagger_path).unwrap();
+        
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("pytest");
+        
+        // Clean previous test output
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        fs::create_dir_all(&test_output_dir).unwrap();
+        
+        // Generate tests
+        let generator = create_generator(TestFramework::Pytest).unwrap();
+        let result = generator.generate_tests(&spec, &test_output_dir, "http://localhost:3000");
+        
+        assert!(result.is_ok());
+        
+        // Check that test files were created
+        assert!(test_output_dir.join("test_api.py").exists());
+        assert!(test_output_dir.join("requirements.txt").exists());
+    }
+
+    #[test]
+    fn test_generate_go_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("go");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // One test file per tag, with a table of requests
+        let spec = parse_swagger_file(get_test_data_path("sample_tags.yaml")).unwrap();
+        create_generator(TestFramework::Go).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let pet_store = fs::read_to_string(test_output_dir.join("pet_store_test.go")).unwrap();
+        assert!(pet_store.starts_with("package apitests\n\nimport \"testing\"\n"));
+        assert!(pet_store.contains("func TestPetStore(t *testing.T) {\n\trunAPITests(t, []apiTest{"));
+        assert!(pet_store.contains("\t\t\tname:       \"getPet\",\n\t\t\tmethod:     \"GET\",\n\t\t\tpath:       \"/pets/1\","));
+        assert!(test_output_dir.join("orders_test.go").exists());
+        assert!(test_output_dir.join("default_test.go").exists());
+        
+        let helpers = fs::read_to_string(test_output_dir.join("helpers_test.go")).unwrap();
+        assert!(helpers.contains("return \"http://localhost:3000\""));
+        assert!(helpers.contains("func runAPITests(t *testing.T, tests []apiTest)"));
+        
+        let go_mod = fs::read_to_string(test_output_dir.join("go.mod")).unwrap();
+        assert!(go_mod.starts_with("module tagged-api-tests\n\ngo 1.21"));
+        
+        // JSON bodies are sent with their example, headers as a map
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::Go).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let default = fs::read_to_string(test_output_dir.join("default_test.go")).unwrap();
+        assert!(default.contains(r#"body:       "{\"email\":\"user@example.com\",\"name\":\"Jane Doe\"}","#));
+        assert!(default.contains(r#"headers:    map[string]string{"Accept": "application/json", "Content-Type": "application/json"},"#));
+        assert!(default.contains("wantStatus: 201,"));
+    }
+
+    #[test]
+    fn test_generate_rest_assured_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("rest_assured");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // One test class per tag, extending the base class that sets the base URI
+        let spec = parse_swagger_file(get_test_data_path("sample_tags.yaml")).unwrap();
+        create_generator(TestFramework::RestAssured).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let java_dir = test_output_dir.join("src").join("test").join("java").join("apitests");
+        let pet_store = fs::read_to_string(java_dir.join("PetStoreTest.java")).unwrap();
+        assert!(pet_store.starts_with("package apitests;\n"));
+        assert!(pet_store.contains("class PetStoreTest extends ApiTest {"));
+        assert!(pet_store.contains("    void getPet() {\n        given()\n            .pathParam(\"petId\", \"1\")\n        .when()\n            .request(\"GET\", \"/pets/{petId}\")"));
+        assert!(java_dir.join("OrdersTest.java").exists());
+        assert!(java_dir.join("DefaultTest.java").exists());
+        
+        let base_class = fs::read_to_string(java_dir.join("ApiTest.java")).unwrap();
+        assert!(base_class.contains(": \"http://localhost:3000\";"));
+        
+        let pom = fs::read_to_string(test_output_dir.join("pom.xml")).unwrap();
+        assert!(pom.contains("<artifactId>tagged-api-tests</artifactId>"));
+        assert!(pom.contains("<artifactId>rest-assured</artifactId>"));
+        
+        // JSON bodies are sent with their example, and response bodies checked against their schema
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::RestAssured).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let default = fs::read_to_string(java_dir.join("DefaultTest.java")).unwrap();
+        assert!(default.contains(r#".contentType("application/json")"#));
+        assert!(default.contains(r#".body("{\"email\":\"user@example.com\",\"name\":\"Jane Doe\"}")"#));
+        assert!(default.contains(".statusCode(equalTo(201))"));
+        assert!(default.contains(r#".body(matchesJsonSchemaInClasspath("schemas/createUser.json"))"#));
+        
+        let schema: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(test_output_dir.join("src/test/resources/schemas/getUsers.json")).unwrap()
+        ).unwrap();
+        assert_eq!(schema["type"], "array");
+        assert_eq!(schema["items"]["required"], serde_json::json!(["id", "name", "email", "created_at"]));
+    }
+
+    #[test]
+    fn test_generate_xunit_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("xunit");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // One test class per tag, sharing the client of the fixture
+        let spec = parse_swagger_file(get_test_data_path("sample_tags.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Xunit, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let pet_store = fs::read_to_string(test_output_dir.join("PetStoreTests.cs")).unwrap();
+        assert!(pet_store.contains("public class PetStoreTests : IClassFixture<ApiFixture>"));
+        assert!(pet_store.contains("    public async Task GetPet()\n    {\n        using var request = new HttpRequestMessage(new HttpMethod(\"GET\"), \"pets/1\");"));
+        assert!(test_output_dir.join("OrdersTests.cs").exists());
+        assert!(test_output_dir.join("DefaultTests.cs").exists());
+        assert!(test_output_dir.join("TaggedApiTests.csproj").exists());
+        
+        let fixture = fs::read_to_string(test_output_dir.join("ApiFixture.cs")).unwrap();
+        assert!(fixture.contains("baseUrl = \"http://localhost:3000\";"));
+        
+        // Query parameters are percent-encoded into the request URI
+        let spec = parse_swagger_file(get_test_data_path("sample_constraints.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Xunit, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let default = fs::read_to_string(test_output_dir.join("DefaultTests.cs")).unwrap();
+        assert!(default.contains("\"products?sku=AAA-0000&limit=10&maxPrice=0.9&query=test_valuexx&lang=te\""));
+        
+        // Response bodies deserialize into the generated models, whose required properties have to be present
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Xunit, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let default = fs::read_to_string(test_output_dir.join("DefaultTests.cs")).unwrap();
+        assert!(default.contains("Assert.Equal(201, (int)response.StatusCode);"));
+        assert!(default.contains("var body = await response.Content.ReadFromJsonAsync<List<GetUsersResponseItem?>>();"));
+        assert!(default.contains("var body = await response.Content.ReadFromJsonAsync<CreateUserResponse>();"));
+        
+        let models = fs::read_to_string(test_output_dir.join("Models.cs")).unwrap();
+        assert!(models.contains("public class GetUserByIdResponse\n{"));
+        assert!(models.contains("    [JsonPropertyName(\"created_at\")]\n    [JsonRequired]\n    public string? CreatedAt { get; set; }"));
+    }
+
+    #[test]
+    fn test_generate_phpunit_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("phpunit");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // One test class per tag, extending the base class that creates the client
+        let spec = parse_swagger_file(get_test_data_path("sample_tags.yaml")).unwrap();
+        create_generator(TestFramework::Phpunit).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests_dir = test_output_dir.join("tests");
+        let pet_store = fs::read_to_string(tests_dir.join("PetStoreTest.php")).unwrap();
+        assert!(pet_store.starts_with("<?php\n\ndeclare(strict_types=1);\n\nnamespace ApiTests;\n"));
+        assert!(pet_store.contains("final class PetStoreTest extends ApiTestCase"));
+        assert!(pet_store.contains("    public function testGetPet(): void\n    {\n        $response = $this->client->request('GET', 'pets/1');"));
+        assert!(tests_dir.join("OrdersTest.php").exists());
+        assert!(tests_dir.join("DefaultTest.php").exists());
+        
+        let base_class = fs::read_to_string(tests_dir.join("ApiTestCase.php")).unwrap();
+        assert!(base_class.contains("getenv('API_BASE_URL') ?: 'http://localhost:3000';"));
+        
+        let composer: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("composer.json")).unwrap()).unwrap();
+        assert_eq!(composer["name"], "apitests/tagged-api-tests");
+        assert_eq!(composer["autoload-dev"]["psr-4"]["ApiTests\\"], "tests/");
+        assert!(test_output_dir.join("phpunit.xml").exists());
+        
+        // JSON bodies are sent with their example, and the required properties of responses checked
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::Phpunit).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let default = fs::read_to_string(tests_dir.join("DefaultTest.php")).unwrap();
+        assert!(default.contains("            'body' => '{\"email\":\"user@example.com\",\"name\":\"Jane Doe\"}',"));
+        assert!(default.contains("                'Content-Type' => 'application/json',"));
+        assert!(default.contains("$this->assertSame(201, $response->getStatusCode());"));
+        assert!(default.contains("$this->assertArrayHasKey('created_at', $body);"));
+        assert!(default.contains("$this->assertTrue(array_is_list($body), 'the response body is not a JSON array');"));
+    }
+
+    #[test]
+    fn test_generate_karate_features() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("karate");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // One feature per tag, with a scenario per operation
+        let spec = parse_swagger_file(get_test_data_path("sample_tags.yaml")).unwrap();
+        create_generator(TestFramework::Karate).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let java_dir = test_output_dir.join("src").join("test").join("java");
+        let pet_store = fs::read_to_string(java_dir.join("apitests").join("pet_store.feature")).unwrap();
+        assert!(pet_store.starts_with("Feature: Operations tagged Pet Store\n\n  Background:\n    * url baseUrl\n"));
+        assert!(pet_store.contains("  @getPet\n  Scenario: GET /pets/{petId}\n    Given path 'pets/1'\n"));
+        assert!(pet_store.contains("    When method get\n    Then status 200"));
+        assert!(java_dir.join("apitests").join("orders.feature").exists());
+        assert!(java_dir.join("apitests").join("ApiTestsRunner.java").exists());
+        
+        let config = fs::read_to_string(java_dir.join("karate-config.js")).unwrap();
+        assert!(config.contains("java.lang.System.getenv('API_BASE_URL') || 'http://localhost:3000';"));
+        
+        let pom = fs::read_to_string(test_output_dir.join("pom.xml")).unwrap();
+        assert!(pom.contains("<artifactId>karate-junit5</artifactId>"));
+        
+        // JSON bodies are sent as they are, and the required properties of responses matched
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::Karate).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let default = fs::read_to_string(java_dir.join("apitests").join("default.feature")).unwrap();
+        assert!(default.contains("    And request {\"email\":\"user@example.com\",\"name\":\"Jane Doe\"}\n    When method post\n    Then status 201"));
+        assert!(default.contains("    And match response contains { \"id\": '#present', \"name\": '#present', \"email\": '#present', \"created_at\": '#present' }"));
+        
+        // Deprecated operations are skipped
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator(TestFramework::Karate).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let default = fs::read_to_string(java_dir.join("apitests").join("default.feature")).unwrap();
+        assert!(default.contains("  @listLegacyOrders @ignore\n"));
+    }
+
+    #[test]
+    fn test_generate_k6_scripts() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("k6");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // One script per tag, with the load the options ask for
+        let spec = parse_swagger_file(get_test_data_path("sample_tags.yaml")).unwrap();
+        let options = GeneratorOptions { virtual_users: Some(5), duration: Some(Duration::from_secs(120)), ..Default::default() };
+        create_generator_with_options(TestFramework::K6, &options).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let pet_store = fs::read_to_string(test_output_dir.join("pet_store.js")).unwrap();
+        assert!(pet_store.contains("const BASE_URL = __ENV.API_BASE_URL || \"http://localhost:3000\";"));
+        assert!(pet_store.contains("  vus: 5,\n  duration: '120s',"));
+        assert!(pet_store.contains("export function getPet() {"));
+        assert!(pet_store.contains("http.request(\"GET\", `${BASE_URL}/pets/1`, body, {"));
+        assert!(pet_store.contains("    \"checks{operation:getPet}\": ['rate==1'],"));
+        assert!(pet_store.contains("export default function () {\n  listPets();\n  getPet();\n}"));
+        assert!(test_output_dir.join("orders.js").exists());
+        
+        // Without options, 10 virtual users run for 30 seconds
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::K6).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let default = fs::read_to_string(test_output_dir.join("default.js")).unwrap();
+        assert!(default.contains("  vus: 10,\n  duration: '30s',"));
+        assert!(default.contains("  const body = JSON.stringify({\n    \"email\": \"user@example.com\",\n    \"name\": \"Jane Doe\"\n  });"));
+        assert!(default.contains("    responseCallback: http.expectedStatuses(201),"));
+        assert!(default.contains("    \"createUser status is 201\": (r) => r.status === 201,"));
+        
+        // Deprecated operations aren't part of the default scenario
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator(TestFramework::K6).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let default = fs::read_to_string(test_output_dir.join("default.js")).unwrap();
+        assert!(default.contains("export function listLegacyOrders() {"));
+        assert!(default.contains("export default function () {\n  listOrders();\n}"));
+    }
+
+    #[test]
+    fn test_generate_locustfile() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("locust");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Task weights come from the operation's x-weight, else from its tag's, else 1
+        let spec = parse_swagger_file(get_test_data_path("sample_weights.yaml")).unwrap();
+        let options = GeneratorOptions { virtual_users: Some(50), duration: Some(Duration::from_secs(300)), optional_query_params: true, ..Default::default() };
+        create_generator_with_options(TestFramework::Locust, &options).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let locustfile = fs::read_to_string(test_output_dir.join("locustfile.py")).unwrap();
+        assert!(locustfile.contains("class ApiUser(HttpUser):"));
+        assert!(locustfile.contains("    host = os.environ.get(\"API_BASE_URL\", \"http://localhost:3000\")"));
+        assert!(locustfile.contains("    @task(8)\n    def get_product(self):"));
+        assert!(locustfile.contains("    @task(5)\n    def list_products(self):"));
+        assert!(locustfile.contains("    @task(1)\n    def create_order(self):"));
+        
+        // Payloads come from the schemas, and any documented successful status is accepted
+        assert!(locustfile.contains("            params={\"category\": \"books\"},"));
+        assert!(locustfile.contains("            json={\n                \"productId\": 42,\n                \"quantity\": 1\n            },"));
+        assert!(locustfile.contains("            if response.status_code not in (200, 304):"));
+        assert!(locustfile.contains("            name=\"GET /products/{productId}\","));
+        
+        let config = fs::read_to_string(test_output_dir.join("locust.conf")).unwrap();
+        assert!(config.contains("users = 50\nspawn-rate = 50\nrun-time = 300s"));
+        
+        // Deprecated operations aren't run as tasks
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Locust, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let locustfile = fs::read_to_string(test_output_dir.join("locustfile.py")).unwrap();
+        assert!(locustfile.contains("    # Deprecated: not run as a task\n    def list_legacy_orders(self):"));
+    }
+
+    #[test]
+    fn test_generate_jmeter_plan() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("jmeter");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A thread group with the load the options ask for, and the server taken from the base URL
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        let options = GeneratorOptions { virtual_users: Some(25), duration: Some(Duration::from_secs(600)), ..Default::default() };
+        create_generator_with_options(TestFramework::Jmeter, &options).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://api.example.com/v1")
+            .unwrap();
+        
+        let plan = fs::read_to_string(test_output_dir.join("test-plan.jmx")).unwrap();
+        assert!(plan.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<jmeterTestPlan"));
+        assert!(plan.contains("<stringProp name=\"HTTPSampler.protocol\">${__P(protocol,https)}</stringProp>"));
+        assert!(plan.contains("<stringProp name=\"HTTPSampler.domain\">${__P(host,api.example.com)}</stringProp>"));
+        assert!(plan.contains("<stringProp name=\"HTTPSampler.port\">${__P(port,443)}</stringProp>"));
+        assert!(plan.contains("<stringProp name=\"Argument.value\">${__P(basePath,/v1)}</stringProp>"));
+        assert!(plan.contains("<stringProp name=\"ThreadGroup.num_threads\">${__P(threads,25)}</stringProp>"));
+        assert!(plan.contains("<stringProp name=\"ThreadGroup.duration\">${__P(duration,600)}</stringProp>"));
+        
+        // An HTTP sampler per operation, with its body, headers and a response code assertion
+        assert!(plan.contains("testname=\"createUser\" enabled=\"true\">\n            <stringProp name=\"HTTPSampler.path\">${basePath}/users</stringProp>\n            <stringProp name=\"HTTPSampler.method\">POST</stringProp>"));
+        assert!(plan.contains("<stringProp name=\"Argument.value\">{&quot;email&quot;:&quot;user@example.com&quot;,&quot;name&quot;:&quot;Jane Doe&quot;}</stringProp>"));
+        assert!(plan.contains("<stringProp name=\"Header.name\">Content-Type</stringProp>"));
+        assert!(plan.contains("<collectionProp name=\"Asserion.test_strings\">\n                <stringProp name=\"201\">201</stringProp>\n"));
+        assert_eq!(plan.matches("<HTTPSamplerProxy ").count(), 3);
+        
+        // Uploads send a file written next to the plan, and deprecated operations are disabled
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator(TestFramework::Jmeter).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let plan = fs::read_to_string(test_output_dir.join("test-plan.jmx")).unwrap();
+        assert!(plan.contains("<boolProp name=\"HTTPSampler.DO_MULTIPART_POST\">true</boolProp>"));
+        assert!(plan.contains("<stringProp name=\"File.paramname\">file</stringProp>"));
+        assert_eq!(fs::read_to_string(test_output_dir.join("upload.txt")).unwrap(), "test file content");
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator(TestFramework::Jmeter).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let plan = fs::read_to_string(test_output_dir.join("test-plan.jmx")).unwrap();
+        assert!(plan.contains("testname=\"listLegacyOrders\" enabled=\"false\">"));
+    }
+
+    #[test]
+    fn test_generate_hurl_files() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("hurl");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // An entry per operation, with its body and asserts on the response
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Hurl, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000/")
+            .unwrap();
+        
+        assert_eq!(fs::read_to_string(test_output_dir.join("vars.env")).unwrap(), "base_url=http://localhost:3000\n");
+        let entries = fs::read_to_string(test_output_dir.join("default.hurl")).unwrap();
+        assert!(entries.contains("# createUser\n# Create a new user\nPOST {{base_url}}/users\nAccept: application/json\n{\n  \"email\": \"user@example.com\",\n  \"name\": \"Jane Doe\"\n}\n\nHTTP 201\n[Asserts]\njsonpath \"$\" isCollection\njsonpath \"$['id']\" exists\n"));
+        assert!(entries.contains("GET {{base_url}}/users/1\nAccept: application/json\n\nHTTP 200\n"));
+        
+        // Query parameters, form fields, cookies and uploads go in their sections
+        let spec = parse_swagger_file(get_test_data_path("sample_weights.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Hurl, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let entries = fs::read_to_string(test_output_dir.join("catalog.hurl")).unwrap();
+        assert!(entries.contains("[QueryStringParams]\ncategory: books\n"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_cookies.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Hurl, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let entries = fs::read_to_string(test_output_dir.join("default.hurl")).unwrap();
+        assert!(entries.contains("GET {{base_url}}/cart\n[Cookies]\nsession_id: string\ntheme: dark\n"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Hurl, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let entries = fs::read_to_string(test_output_dir.join("default.hurl")).unwrap();
+        assert!(entries.contains("[MultipartFormData]\nfile: file,upload.txt; text/plain\ntitle: Example title\n"));
+        assert_eq!(fs::read_to_string(test_output_dir.join("upload.txt")).unwrap(), "test file content");
+        
+        // Deprecated operations are commented out
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Hurl, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let entries = fs::read_to_string(test_output_dir.join("default.hurl")).unwrap();
+        assert!(entries.contains("# GET {{base_url}}/legacy/orders\n#\n# HTTP 200\n"));
+        assert!(entries.contains("\nGET {{base_url}}/orders\n"));
+    }
+
+    #[test]
+    fn test_generate_http_files() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("http_file");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A named request per operation, sent to the file's base URL
+        let spec = parse_swagger_file(get_test_data_path("sample_weights.yaml")).unwrap();
+        create_generator_with_options(TestFramework::HttpFile, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://shop.example.com/")
+            .unwrap();
+        
+        let requests = fs::read_to_string(test_output_dir.join("catalog.http")).unwrap();
+        assert!(requests.starts_with("# Operations tagged catalog\n\n@baseUrl = https://shop.example.com\n\n###"));
+        assert!(requests.contains("# @name listProducts\nGET {{baseUrl}}/products?category=books\n"));
+        
+        let requests = fs::read_to_string(test_output_dir.join("checkout.http")).unwrap();
+        assert!(requests.contains("# @name createOrder\nPOST {{baseUrl}}/orders\n"));
+        assert!(requests.contains("Content-Type: application/json\n\n{\n  \"productId\": 42,\n  \"quantity\": 1\n}"));
+        
+        // Form bodies are URL-encoded, and uploads send a file from next to the request file
+        let spec = parse_swagger_file(get_test_data_path("sample_form_data.yaml")).unwrap();
+        create_generator_with_options(TestFramework::HttpFile, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let requests = fs::read_to_string(test_output_dir.join("default.http")).unwrap();
+        assert!(requests.contains("Content-Type: application/x-www-form-urlencoded\n\npassword=P%40ssw0rd123\n&remember=true\n&username=ada"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator_with_options(TestFramework::HttpFile, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let requests = fs::read_to_string(test_output_dir.join("default.http")).unwrap();
+        assert!(requests.contains("Content-Type: multipart/form-data; boundary=----SwaggerTestGeneratorBoundary\n"));
+        assert!(requests.contains("Content-Disposition: form-data; name=\"file\"; filename=\"upload.txt\"\nContent-Type: text/plain\n\n< ./upload.txt\n"));
+        assert!(test_output_dir.join("upload.txt").exists());
+    }
+
+    #[test]
+    fn test_generate_curl_smoke_test() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("curl");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A check per operation, accepting its documented successful statuses
+        let spec = parse_swagger_file(get_test_data_path("sample_weights.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Curl, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://shop.example.com/")
+            .unwrap();
+        
+        let script = fs::read_to_string(test_output_dir.join("smoke.sh")).unwrap();
+        assert!(script.starts_with("#!/usr/bin/env bash\n"));
+        assert!(script.contains("BASE_URL=\"${API_BASE_URL:-https://shop.example.com}\"\n"));
+        assert!(script.contains("--fail-with-body"));
+        assert!(script.contains("check 'listProducts' '200' \\\n  --request GET \\\n  \"$BASE_URL\"'/products?category=books'"));
+        assert!(script.contains("check 'getProduct' '200 304' \\\n"));
+        assert!(script.contains("--header 'Content-Type: application/json' \\\n  --data-binary '{\"productId\":42,\"quantity\":1}'"));
+        assert!(script.ends_with("echo \"$passed passed, $failed failed\"\n[ \"$failed\" -eq 0 ]\n"));
+        
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(test_output_dir.join("smoke.sh")).unwrap().permissions().mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+        
+        // Uploads send a file from next to the script, and deprecated operations aren't checked
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Curl, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let script = fs::read_to_string(test_output_dir.join("smoke.sh")).unwrap();
+        assert!(script.contains("--form 'file=@upload.txt;type=text/plain' \\\n  --form-string 'title=Example title'"));
+        assert!(test_output_dir.join("upload.txt").exists());
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Curl, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let script = fs::read_to_string(test_output_dir.join("smoke.sh")).unwrap();
+        assert!(script.contains("# Deprecated: listLegacyOrders is not checked"));
+        assert!(!script.contains("check 'listLegacyOrders'"));
+    }
+
+    #[test]
+    fn test_generate_pester_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("pester");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A test file per tag, with a Describe block per operation
+        let spec = parse_swagger_file(get_test_data_path("sample_weights.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Pester, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://shop.example.com/")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("Catalog.Tests.ps1")).unwrap();
+        assert!(tests.contains("$BaseUrl = if ($env:API_BASE_URL) { $env:API_BASE_URL.TrimEnd('/') } else { 'https://shop.example.com' }"));
+        assert!(tests.contains("Describe 'Operations tagged catalog' {\n    Describe 'listProducts' {\n"));
+        assert!(tests.contains("                Uri = $BaseUrl + '/products?category=books'\n"));
+        assert!(tests.contains("$response = Invoke-RestMethod @params\n"));
+        
+        let tests = fs::read_to_string(test_output_dir.join("Checkout.Tests.ps1")).unwrap();
+        assert!(tests.contains("It 'POST /orders returns 201' {"));
+        assert!(tests.contains("                ContentType = 'application/json'\n                Body = '{\"productId\":42,\"quantity\":1}'\n"));
+        assert!(tests.contains("                SkipHttpErrorCheck = $true\n                StatusCodeVariable = 'status'\n"));
+        assert!(tests.contains("            $status | Should -Be 201\n"));
+        
+        // Response headers and required properties are asserted
+        let spec = parse_swagger_file(get_test_data_path("sample_response_headers.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Pester, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("Default.Tests.ps1")).unwrap();
+        assert!(tests.contains("$responseHeaders.Keys | Should -Contain 'Location' -Because 'the operation documents the Location header'"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Pester, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("Default.Tests.ps1")).unwrap();
+        assert!(tests.contains("$response.PSObject.Properties.Name | Should -Contain 'email'"));
+        
+        // Deprecated operations are skipped
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Pester, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("Default.Tests.ps1")).unwrap();
+        assert!(tests.contains("It 'GET /legacy/orders returns 200' -Skip {"));
+        assert!(tests.contains("It 'GET /orders returns 200' {"));
+    }
+
+    #[test]
+    fn test_generate_playwright_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("playwright");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A spec file per tag, with requests relative to the configured base URL
+        let spec = parse_swagger_file(get_test_data_path("sample_weights.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Playwright, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://shop.example.com/")
+            .unwrap();
+        
+        let config = fs::read_to_string(test_output_dir.join("playwright.config.ts")).unwrap();
+        assert!(config.contains("const baseURL = (process.env.API_BASE_URL ?? \"https://shop.example.com\").replace(/\\/?$/, '/');"));
+        assert!(fs::read_to_string(test_output_dir.join("package.json")).unwrap().contains("\"@playwright/test\""));
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("catalog.spec.ts")).unwrap();
+        assert!(tests.starts_with("import { test, expect } from '@playwright/test';\n\ntest.describe(\"Operations tagged catalog\", () => {\n"));
+        assert!(tests.contains("const response = await request.fetch(\"products\", {\n      method: \"GET\",\n      params: {\n        \"category\": \"books\",\n      },\n    });"));
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("checkout.spec.ts")).unwrap();
+        assert!(tests.contains("      data: {\n        \"productId\": 42,\n        \"quantity\": 1\n      },\n"));
+        assert!(tests.contains("    expect(response.status()).toBe(201);"));
+        
+        // Response headers and bodies are checked
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Playwright, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("default.spec.ts")).unwrap();
+        assert!(tests.contains("    const body = await response.json();\n    expect(Array.isArray(body)).toBe(true);"));
+        assert!(tests.contains("    expect(body).toHaveProperty([\"email\"]);"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_response_headers.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Playwright, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("default.spec.ts")).unwrap();
+        assert!(tests.contains("expect(response.headers()[\"location\"]).toBeDefined();"));
+        
+        // Uploads are sent from a buffer, and deprecated operations are skipped
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Playwright, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("default.spec.ts")).unwrap();
+        assert!(tests.contains("\"file\": { name: \"upload.txt\", mimeType: \"text/plain\", buffer: Buffer.from(\"test file content\") },"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Playwright, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("default.spec.ts")).unwrap();
+        assert!(tests.contains("test.skip(\"listLegacyOrders\", async ({ request }) => {"));
+        assert!(tests.contains("test(\"listOrders\", async ({ request }) => {"));
+    }
+
+    #[test]
+    fn test_generate_vitest_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("vitest");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Test files are laid out like the Jest ones, as ES modules sending their requests with fetch
+        let spec = parse_swagger_file(get_test_data_path("sample_weights.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Vitest, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://shop.example.com/")
+            .unwrap();
+        
+        let package = fs::read_to_string(test_output_dir.join("package.json")).unwrap();
+        assert!(package.contains("\"type\": \"module\""));
+        assert!(package.contains("\"test\": \"vitest run\""));
+        assert!(test_output_dir.join("vitest.config.js").exists());
+        
+        let tests = fs::read_to_string(test_output_dir.join("products.test.js")).unwrap();
+        assert!(tests.starts_with("import { expect, test } from 'vitest';\n"));
+        assert!(tests.contains("const BASE_URL = (process.env.API_BASE_URL ?? \"https://shop.example.com\").replace(/\\/$/, '');"));
+        assert!(tests.contains("const response = await fetch(`${BASE_URL}/products?category=books`, {\n    method: \"GET\",\n  });"));
+        
+        let tests = fs::read_to_string(test_output_dir.join("orders.test.js")).unwrap();
+        assert!(tests.contains("      \"Content-Type\": \"application/json\",\n    },\n    body: JSON.stringify({\n      \"productId\": 42,\n      \"quantity\": 1\n    }),\n"));
+        assert!(tests.contains("  expect(response.status).toBe(201);"));
+        
+        // Response headers and bodies are checked
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Vitest, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("users_id.test.js")).unwrap();
+        assert!(tests.contains("  const body = await response.json();\n  expect(body).toEqual(expect.any(Object));\n  expect(body).toHaveProperty([\"id\"]);"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_response_headers.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Vitest, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests: String = fs::read_dir(&test_output_dir).unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.to_string_lossy().ends_with(".test.js"))
+            .map(|path| fs::read_to_string(path).unwrap())
+            .collect();
+        assert!(tests.contains("expect(response.headers.get(\"Location\")).not.toBeNull();"));
+        
+        // Uploads are sent as form data
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Vitest, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("documents.test.js")).unwrap();
+        assert!(tests.contains("form.append(\"file\", new Blob([\"test file content\"], { type: \"text/plain\" }), \"upload.txt\");"));
+        assert!(tests.contains("    body: form,\n"));
+    }
+
+    #[test]
+    fn test_generate_tavern_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("tavern");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A file of test documents per tag, with a stage per operation sent to API_BASE_URL
+        let spec = parse_swagger_file(get_test_data_path("sample_weights.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Tavern, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://shop.example.com/")
+            .unwrap();
+        
+        let conftest = fs::read_to_string(test_output_dir.join("conftest.py")).unwrap();
+        assert!(conftest.contains("os.environ.setdefault(\"API_BASE_URL\", \"https://shop.example.com\")"));
+        assert!(fs::read_to_string(test_output_dir.join("requirements.txt")).unwrap().contains("tavern"));
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("test_catalog.tavern.yaml")).unwrap();
+        assert!(tests.contains("---\ntest_name: listProducts\nstages:\n  - name: GET /products\n    request:\n      url: \"{tavern.env_vars.API_BASE_URL}/products\"\n      method: GET\n      params:\n        category: books\n    response:\n      status_code: 200\n"));
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("test_checkout.tavern.yaml")).unwrap();
+        assert!(tests.contains("      json:\n        productId: 42\n        quantity: 1\n    response:\n      status_code: 201"));
+        
+        // Response headers and required properties are checked
+        let spec = parse_swagger_file(get_test_data_path("sample_response_headers.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Tavern, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("test_default.tavern.yaml")).unwrap();
+        assert!(tests.contains("      headers:\n        Location: !anything\n"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Tavern, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("test_default.tavern.yaml")).unwrap();
+        assert!(tests.contains("        - function: \"tavern.helpers:check_jmespath_match\"\n          extra_kwargs:\n            query: \"\\\"email\\\"\"\n"));
+        
+        // Uploads send a file from the directory pytest runs in, and deprecated operations are skipped
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Tavern, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("test_default.tavern.yaml")).unwrap();
+        assert!(tests.contains("      data:\n        title: Example title\n      files:\n        file: upload.txt\n"));
+        assert!(test_output_dir.join("upload.txt").exists());
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Tavern, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("tests").join("test_default.tavern.yaml")).unwrap();
+        assert!(tests.contains("test_name: listLegacyOrders\nmarks:\n  - skip\n"));
+        assert!(tests.contains("test_name: listOrders\nstages:\n"));
+    }
+
+    #[test]
+    fn test_generate_gherkin_features() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("gherkin");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A feature per tag, with a scenario per operation, and the behave steps they use
+        let spec = parse_swagger_file(get_test_data_path("sample_weights.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Gherkin, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://shop.example.com/")
+            .unwrap();
+        
+        let features_dir = test_output_dir.join("features");
+        let environment = fs::read_to_string(features_dir.join("environment.py")).unwrap();
+        assert!(environment.contains("context.base_url = os.environ.get(\"API_BASE_URL\", \"https://shop.example.com\").rstrip(\"/\")"));
+        let steps = fs::read_to_string(features_dir.join("steps").join("api_steps.py")).unwrap();
+        assert!(steps.contains("@when(\"I send a {method} request\")"));
+        assert!(steps.contains("@then(\"the response status is {status:d}\")"));
+        
+        let feature = fs::read_to_string(features_dir.join("catalog.feature")).unwrap();
+        assert!(feature.starts_with("Feature: Operations tagged catalog\n\n  @listProducts\n"));
+        assert!(feature.contains("    Given the request path is \"/products\"\n    And the query parameter \"category\" is \"books\"\n    When I send a GET request\n    Then the response status is 200\n"));
+        
+        let feature = fs::read_to_string(features_dir.join("checkout.feature")).unwrap();
+        assert!(feature.contains("    And the JSON request body is:\n      \"\"\"\n      {\n        \"productId\": 42,\n        \"quantity\": 1\n      }\n      \"\"\"\n    When I send a POST request\n    Then the response status is 201"));
+        
+        // Response bodies and uploads have steps of their own, and deprecated operations are tagged to be skipped
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Gherkin, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let feature = fs::read_to_string(features_dir.join("default.feature")).unwrap();
+        assert!(feature.contains("    And the response body is a JSON object\n    And the response body has the property \"id\"\n"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Gherkin, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let feature = fs::read_to_string(features_dir.join("default.feature")).unwrap();
+        assert!(feature.contains("    And a file is uploaded as \"file\"\n    And the form field \"title\" is \"Example title\"\n"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Gherkin, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let feature = fs::read_to_string(features_dir.join("default.feature")).unwrap();
+        assert!(feature.contains("  @listLegacyOrders @deprecated\n  Scenario: GET /legacy/orders\n"));
+        assert!(feature.contains("  @listOrders\n  Scenario: GET /orders\n"));
+    }
+
+    #[test]
+    fn test_generate_wiremock_stubs() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("wiremock");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A stub per operation, served under the base URL's path
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::Wiremock).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://api.example.com/v1/")
+            .unwrap();
+        
+        let read_mapping = |name: &str| -> serde_json::Value {
+            serde_json::from_str(&fs::read_to_string(test_output_dir.join("mappings").join(name)).unwrap()).unwrap()
+        };
+        let mapping = read_mapping("getUsers.json");
+        assert_eq!(mapping["request"], serde_json::json!({ "method": "GET", "urlPath": "/v1/users" }));
+        assert_eq!(mapping["response"]["status"], 200);
+        assert_eq!(mapping["response"]["jsonBody"][0]["email"], "user@example.com");
+        
+        // Paths with parameters are matched by pattern, below literal paths
+        let mapping = read_mapping("getUserById.json");
+        assert_eq!(mapping["request"]["urlPathPattern"], "/v1/users/[^/]+");
+        assert_eq!(mapping["priority"], 2);
+        
+        // Examples of responses have their read-only properties, and not their write-only ones
+        let mapping = read_mapping("createUser.json");
+        assert_eq!(mapping["response"]["status"], 201);
+        assert_eq!(mapping["response"]["headers"]["Content-Type"], "application/json");
+        assert_eq!(mapping["response"]["jsonBody"]["id"], 1);
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_read_write_only.yaml")).unwrap();
+        create_generator(TestFramework::Wiremock).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let body = read_mapping("createUser.json")["response"]["jsonBody"].clone();
+        assert!(body.get("id").is_some());
+        assert!(body.get("password").is_none());
+        
+        // Documented response headers are returned with example values
+        let spec = parse_swagger_file(get_test_data_path("sample_response_headers.yaml")).unwrap();
+        create_generator(TestFramework::Wiremock).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let mapping = read_mapping("createOrder.json");
+        assert_eq!(mapping["request"]["urlPath"], "/orders");
+        assert!(mapping["response"]["headers"]["Location"].is_string());
+    }
+
+    #[test]
+    fn test_generate_msw_handlers() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("msw");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A handler per operation, answering with an example of the documented response
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::Msw).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://api.example.com/v1/")
+            .unwrap();
+        
+        let handlers = fs::read_to_string(test_output_dir.join("handlers.ts")).unwrap();
+        assert!(handlers.contains("import { http, HttpResponse } from 'msw';"));
+        assert!(handlers.contains("export const BASE_URL = \"https://api.example.com/v1\";"));
+        assert!(handlers.contains("  http.post(`${BASE_URL}/users`, () =>\n    HttpResponse.json({\n      \"created_at\": \"2024-01-01T12:00:00Z\",\n"));
+        assert!(handlers.contains("    }, { status: 201 }),\n"));
+        
+        // Path parameters use MSW's syntax, with templates after the literal paths they could match
+        assert!(handlers.contains("  http.get(`${BASE_URL}/users/:id`, () =>\n"));
+        assert!(handlers.find("${BASE_URL}/users`").unwrap() < handlers.find("${BASE_URL}/users/:id`").unwrap());
+        
+        // Responses without a body return the documented headers
+        let spec = parse_swagger_file(get_test_data_path("sample_response_headers.yaml")).unwrap();
+        create_generator(TestFramework::Msw).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let handlers = fs::read_to_string(test_output_dir.join("handlers.ts")).unwrap();
+        assert!(handlers.contains("new HttpResponse(null, { status: 201, headers: { \"Location\": "));
+    }
+
+    #[test]
+    fn test_generate_insomnia_export() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("insomnia");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A workspace with the base URL in its environment, and a request group per tag
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::Insomnia).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://api.example.com/v1/")
+            .unwrap();
+        
+        let export = fs::read_to_string(test_output_dir.join("insomnia.json")).unwrap();
+        let export: serde_json::Value = serde_json::from_str(&export).unwrap();
+        assert_eq!(export["_type"], "export");
+        assert_eq!(export["__export_format"], 4);
+        let resources = export["resources"].as_array().unwrap();
+        assert_eq!(resources[0]["_type"], "workspace");
+        assert_eq!(resources[1]["_type"], "environment");
+        assert_eq!(resources[1]["parentId"], resources[0]["_id"]);
+        assert_eq!(resources[1]["data"]["base_url"], "https://api.example.com/v1");
+        assert_eq!(resources[2]["_type"], "request_group");
+        assert_eq!(resources[2]["name"], "default");
+        
+        // Requests are sent to the base URL variable with example path parameters and bodies
+        let request = |id: &str| resources.iter().find(|resource| resource["_id"] == id).unwrap().clone();
+        let get_user = request("req_getUserById");
+        assert_eq!(get_user["parentId"], resources[2]["_id"]);
+        assert_eq!(get_user["method"], "GET");
+        assert_eq!(get_user["url"], "{{ _.base_url }}/users/1");
+        let create_user = request("req_createUser");
+        assert_eq!(create_user["body"]["mimeType"], "application/json");
+        assert!(create_user["body"]["text"].as_str().unwrap().contains("\"email\": \"user@example.com\""));
+        assert!(create_user["headers"].as_array().unwrap().contains(&serde_json::json!({ "name": "Content-Type", "value": "application/json" })));
+        
+        // Files are uploaded from upload.txt
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator(TestFramework::Insomnia).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let export = fs::read_to_string(test_output_dir.join("insomnia.json")).unwrap();
+        let export: serde_json::Value = serde_json::from_str(&export).unwrap();
+        let upload = &export["resources"][3];
+        assert_eq!(upload["body"]["mimeType"], "multipart/form-data");
+        assert_eq!(upload["body"]["params"][0], serde_json::json!({ "name": "file", "type": "file", "fileName": "upload.txt" }));
+        assert!(test_output_dir.join("upload.txt").exists());
+    }
+
+    #[test]
+    fn test_generate_bruno_collection() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("bruno");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A collection with an environment holding the base URL, and a folder per tag
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::Bruno).unwrap()
+            .generate_tests(&spec, &test_output_dir, "https://api.example.com/v1/")
+            .unwrap();
+        
+        let collection = fs::read_to_string(test_output_dir.join("bruno.json")).unwrap();
+        assert!(collection.contains("\"type\": \"collection\""));
+        let environment = fs::read_to_string(test_output_dir.join("environments").join("Local.bru")).unwrap();
+        assert_eq!(environment, "vars {\n  baseUrl: https://api.example.com/v1\n}\n");
+        
+        // A request file per operation, with its body and assertions on the response
+        let request = fs::read_to_string(test_output_dir.join("default").join("createUser.bru")).unwrap();
+        assert!(request.contains("post {\n  url: {{baseUrl}}/users\n  body: json\n  auth: none\n}"));
+        assert!(request.contains("body:json {\n  {\n    \"email\": \"user@example.com\",\n"));
+        assert!(request.contains("assert {\n  res.status: eq 201\n  res.body: isJson\n  res.body[\"id\"]: isDefined\n"));
+        let request = fs::read_to_string(test_output_dir.join("default").join("getUserById.bru")).unwrap();
+        assert!(request.contains("  url: {{baseUrl}}/users/1\n"));
+        
+        // Documented response headers are asserted
+        let spec = parse_swagger_file(get_test_data_path("sample_response_headers.yaml")).unwrap();
+        create_generator(TestFramework::Bruno).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let request = fs::read_to_string(test_output_dir.join("default").join("createOrder.bru")).unwrap();
+        assert!(request.contains("  res.headers[\"location\"]: isDefined\n"));
+        
+        // Files are uploaded from upload.txt
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator(TestFramework::Bruno).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let request = fs::read_to_string(test_output_dir.join("default").join("uploadDocument.bru")).unwrap();
+        assert!(request.contains("body:multipart-form {\n  file: @file(upload.txt)\n"));
+        assert!(test_output_dir.join("upload.txt").exists());
+        
+        // Deprecated operations are skipped when the collection runs
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator(TestFramework::Bruno).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let request = fs::read_to_string(test_output_dir.join("default").join("listLegacyOrders.bru")).unwrap();
+        assert!(request.contains("script:pre-request {\n  // Deprecated operation\n  bru.runner.skipRequest();\n}"));
+        let request = fs::read_to_string(test_output_dir.join("default").join("listOrders.bru")).unwrap();
+        assert!(!request.contains("skipRequest"));
+    }
+
+    #[test]
+    fn test_generate_pytest_async_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("pytest_async");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Async tests awaiting the requests of the shared httpx client
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::PytestAsync).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(tests.starts_with("import json\nimport pathlib\n\nimport jsonschema\nimport pytest\n"));
+        assert!(!tests.contains("import requests"));
+        assert!(tests.contains("@pytest.mark.asyncio\nasync def test_createUser(client):"));
+        assert!(tests.contains("    response = await client.post(url, json=json_data, params=params, headers=headers)"));
+        let conftest = fs::read_to_string(test_output_dir.join("conftest.py")).unwrap();
+        assert!(conftest.contains("@pytest_asyncio.fixture\nasync def client():"));
+        assert!(conftest.contains("    async with httpx.AsyncClient(timeout=30) as client:\n        yield client"));
+        let requirements = fs::read_to_string(test_output_dir.join("requirements.txt")).unwrap();
+        assert!(requirements.contains("httpx=="));
+        assert!(requirements.contains("pytest-asyncio=="));
+        
+        // Cookies are sent as a header, as httpx no longer takes them per request
+        let spec = parse_swagger_file(get_test_data_path("sample_cookies.yaml")).unwrap();
+        create_generator(TestFramework::PytestAsync).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(tests.contains("        \"Cookie\": \"session_id=string; theme=dark\""));
+        assert!(!tests.contains("cookies=cookies"));
+        
+        // Uploads still take pytest's temporary directory
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator(TestFramework::PytestAsync).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(tests.contains("async def test_uploadDocument(client, tmp_path):"));
+        assert!(tests.contains("response = await client.post(url, data=data, files=files, params=params)"));
+    }
+
+    #[test]
+    fn test_generate_ureq_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("ureq");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Synchronous tests, with ureq in place of reqwest and tokio
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::Ureq).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(!tests.contains("tokio"));
+        assert!(!tests.contains(".await"));
+        assert!(tests.contains("fn send(result: Result<ureq::Response, ureq::Error>) -> ureq::Response {"));
+        assert!(tests.contains("#[test]\nfn test_create_user() {"));
+        assert!(tests.contains("    let response = send(client.post(&url)\n        .set(\"Accept\", \"application/json\")\n        .send_json(&body));"));
+        assert!(tests.contains("    assert_eq!(response.status(), 201);"));
+        assert!(tests.contains("    // Call createUser first, and take parameters from its response (it creates the resource)\n"));
+        assert!(tests.contains("    let id = linked_value(&setup_body, \"/id\");"));
+        let cargo_toml = fs::read_to_string(test_output_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("ureq = { version = \"2\", features = [\"json\"] }"));
+        assert!(!cargo_toml.contains("tokio"));
+        
+        // Multipart bodies are written out by hand, and cookies sent as a header
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator(TestFramework::Ureq).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(tests.contains("filename=\\\"upload.txt\\\"\\r\\nContent-Type: text/plain\\r\\n\\r\\ntest file content\\r\\n"));
+        assert!(tests.contains(".set(\"Content-Type\", \"multipart/form-data; boundary=----SwaggerTestGeneratorBoundary\")\n        .send_string(form));"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_cookies.yaml")).unwrap();
+        create_generator(TestFramework::Ureq).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(tests.contains("        .set(\"Cookie\", \"session_id=string; theme=dark\")"));
+    }
+
+    #[test]
+    fn test_generate_schemathesis_setup() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("schemathesis");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A command fuzzing a copy of the specification, with the checks to run
+        let spec = parse_swagger_file(get_test_data_path("sample_security.yaml")).unwrap();
+        create_generator(TestFramework::Schemathesis).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000/")
+            .unwrap();
+        
+        let schema = fs::read_to_string(test_output_dir.join("openapi.json")).unwrap();
+        let schema: serde_json::Value = serde_json::from_str(&schema).unwrap();
+        assert_eq!(schema["info"]["title"], "Secured API");
+        let script = fs::read_to_string(test_output_dir.join("run-schemathesis.sh")).unwrap();
+        assert!(script.contains("BASE_URL=\"${API_BASE_URL:-http://localhost:3000}\""));
+        assert!(script.contains("CHECKS=(\n  not_a_server_error\n"));
+        assert!(script.contains("  response_schema_conformance\n)"));
+        assert!(script.contains("SCHEMATHESIS_HOOKS=hooks exec schemathesis run openapi.json \\\n  --base-url \"$BASE_URL\" \\\n"));
+        assert!(script.contains("  --workers \"${WORKERS:-auto}\" \\\n  --exclude-deprecated \\\n"));
+        
+        // The hooks authenticate requests with the credentials of each scheme kind
+        let hooks = fs::read_to_string(test_output_dir.join("hooks.py")).unwrap();
+        assert!(hooks.contains("@schemathesis.auth()\nclass ApiAuth:"));
+        assert!(hooks.contains("        # bearerAuth, oauth\n        if data[\"API_TOKEN\"]:\n            case.headers[\"Authorization\"] = f\"Bearer {data['API_TOKEN']}\""));
+        assert!(hooks.contains("        # basicAuth\n        elif data[\"API_USERNAME\"] and data[\"API_PASSWORD\"]:"));
+        assert!(hooks.contains("        # apiKeyAuth\n        if data[\"API_KEY\"]:\n            case.headers[\"X-API-Key\"] = data[\"API_KEY\"]"));
+        
+        // The number of workers follows the generator's jobs, and APIs without security schemes get no auth
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        let options = GeneratorOptions { jobs: Some(4), ..GeneratorOptions::default() };
+        create_generator_with_options(TestFramework::Schemathesis, &options).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let script = fs::read_to_string(test_output_dir.join("run-schemathesis.sh")).unwrap();
+        assert!(script.contains("  --workers \"${WORKERS:-4}\" \\\n"));
+        let hooks = fs::read_to_string(test_output_dir.join("hooks.py")).unwrap();
+        assert!(!hooks.contains("ApiAuth"));
+    }
+
+    #[test]
+    fn test_generate_mocha_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("mocha");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A suite per tag, with a test per operation checked with Chai
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::Mocha).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000/")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("test").join("default.spec.js")).unwrap();
+        assert!(tests.starts_with("const axios = require('axios');\nconst { expect } = require('chai');\n"));
+        assert!(tests.contains("  baseURL: process.env.API_BASE_URL || \"http://localhost:3000\",\n  validateStatus: () => true,\n"));
+        assert!(tests.contains("describe(\"Operations tagged default\", function () {\n  it(\"getUsers\", async function () {"));
+        assert!(tests.contains("      method: \"post\",\n      url: \"/users\",\n"));
+        assert!(tests.contains("      data: {\n        \"email\": \"user@example.com\",\n"));
+        assert!(tests.contains("    expect(response.status).to.equal(201);"));
+        assert!(tests.contains("    expect(response.data).to.have.property(\"created_at\");"));
+        assert!(tests.contains("      url: \"/users/1\",\n"));
+        let mocharc = fs::read_to_string(test_output_dir.join(".mocharc.json")).unwrap();
+        assert!(mocharc.contains("\"spec\": \"test/**/*.spec.js\""));
+        let package = fs::read_to_string(test_output_dir.join("package.json")).unwrap();
+        assert!(package.contains("\"chai\": \"^4.4.1\""));
+        assert!(package.contains("\"test\": \"mocha\""));
+        assert!(!package.contains("form-data"));
+        
+        // Uploads are built with form-data, and documented headers checked by their lowercase names
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator(TestFramework::Mocha).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("test").join("default.spec.js")).unwrap();
+        assert!(tests.contains("const FormData = require('form-data');"));
+        assert!(tests.contains("    form.append(\"file\", Buffer.from('test file content'), { filename: 'upload.txt', contentType: 'text/plain' });"));
+        assert!(tests.contains("      headers: {\n        ...form.getHeaders(),\n      },\n      data: form,"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_response_headers.yaml")).unwrap();
+        create_generator(TestFramework::Mocha).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("test").join("default.spec.js")).unwrap();
+        assert!(tests.contains("    expect(response.headers).to.have.property(\"location\");"));
+        
+        // Deprecated operations are skipped
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator(TestFramework::Mocha).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("test").join("default.spec.js")).unwrap();
+        assert!(tests.contains("  it.skip(\"listLegacyOrders\", async function () {"));
+    }
+
+    #[test]
+    fn test_generate_deno_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("deno");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // A test file per path, with `Deno.test` cases sending their requests with `fetch`
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        create_generator(TestFramework::Deno).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000/")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("users_test.ts")).unwrap();
+        assert!(tests.starts_with("import { assert, assertEquals } from \"@std/assert\";\n"));
+        assert!(tests.contains("const BASE_URL = (Deno.env.get(\"API_BASE_URL\") ?? \"http://localhost:3000\").replace(/\\/$/, \"\");"));
+        assert!(tests.contains("Deno.test(\"createUser\", async () => {"));
+        assert!(tests.contains("  const response = await fetch(`${BASE_URL}/users`, {\n    method: \"POST\",\n"));
+        assert!(tests.contains("  assertEquals(response.status, 201);"));
+        assert!(tests.contains("  assert(Object.hasOwn(body, \"created_at\"), \"missing property created_at\");"));
+        assert!(tests.contains("  assert(Array.isArray(body), \"expected an array\");"));
+        let config = fs::read_to_string(test_output_dir.join("deno.json")).unwrap();
+        assert!(config.contains("\"@std/assert\": \"jsr:@std/assert@^1.0.0\""));
+        assert!(config.contains("\"test\": \"deno test --allow-net --allow-env=API_BASE_URL\""));
+        assert!(!test_output_dir.join("package.json").exists());
+        
+        // Unchecked response bodies are discarded, and documented headers checked
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator(TestFramework::Deno).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("documents_test.ts")).unwrap();
+        assert!(tests.contains("  form.append(\"file\", new Blob([\"test file content\"], { type: \"text/plain\" }), \"upload.txt\");"));
+        assert!(tests.contains("  assertEquals(response.status, 201);\n  await response.body?.cancel();\n"));
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_response_headers.yaml")).unwrap();
+        create_generator(TestFramework::Deno).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("orders_test.ts")).unwrap();
+        assert!(tests.contains("  assert(response.headers.has(\"Location\"), \"missing Location header\");"));
+        
+        // Deprecated operations are ignored
+        let spec = parse_swagger_file(get_test_data_path("sample_deprecated.yaml")).unwrap();
+        create_generator(TestFramework::Deno).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let tests = fs::read_to_string(test_output_dir.join("legacy_orders_test.ts")).unwrap();
+        assert!(tests.contains("Deno.test({ name: \"listLegacyOrders\", ignore: true }, async () => {"));
+    }
+    
+    #[test]
+    fn test_generate_postman_newman_setup() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("newman");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Requests go to the environment's base URL, with the credentials of the API's security requirements
+        let spec = parse_swagger_file(get_test_data_path("sample_security.yaml")).unwrap();
+        create_generator(TestFramework::Postman).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000/")
+            .unwrap();
+        
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        assert_eq!(collection["auth"]["type"], "bearer");
+        assert_eq!(collection["auth"]["bearer"][0]["value"], "{{authToken}}");
+        let requests: Vec<&serde_json::Value> = collection["item"].as_array().unwrap()
+            .iter()
+            .flat_map(|folder| folder["item"].as_array().unwrap())
+            .collect();
+        let health = requests.iter().find(|request| request["name"] == "GET getHealth").unwrap();
+        assert_eq!(health["request"]["url"]["raw"], "{{baseUrl}}/health");
+        assert_eq!(health["request"]["url"]["host"][0], "{{baseUrl}}");
+        assert_eq!(health["request"]["auth"]["type"], "noauth");
+        let users = requests.iter().find(|request| request["name"] == "GET getUsers").unwrap();
+        assert!(users["request"].get("auth").is_none());
+        
+        let environment: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("postman_environment.json")).unwrap()).unwrap();
+        assert_eq!(environment["_postman_variable_scope"], "environment");
+        let keys: Vec<&str> = environment["values"].as_array().unwrap().iter().map(|value| value["key"].as_str().unwrap()).collect();
+        assert_eq!(keys, ["baseUrl", "authToken", "username", "password", "apiKey"]);
+        assert_eq!(environment["values"][0]["value"], "http://localhost:3000");
+        
+        let script = fs::read_to_string(test_output_dir.join("run-newman.sh")).unwrap();
+        assert!(script.starts_with("#!/usr/bin/env bash\n"));
+        assert!(script.contains("args=(--environment postman_environment.json --reporters cli,junit --reporter-junit-export results/newman.xml)"));
+        assert!(script.contains("if [[ -n \"${API_TOKEN:-}\" ]]; then\n  args+=(--env-var \"authToken=$API_TOKEN\")\nfi\n"));
+        assert!(script.contains("exec npx --yes newman@6 run postman_collection.json \"${args[@]}\" \"$@\""));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(test_output_dir.join("run-newman.sh")).unwrap().permissions().mode() & 0o111, 0o111);
+        }
+        
+        // Without security schemes there are no credentials, and uploads get their file
+        let spec = parse_swagger_file(get_test_data_path("sample_upload.yaml")).unwrap();
+        create_generator(TestFramework::Postman).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        assert!(collection.get("auth").is_none());
+        let environment = fs::read_to_string(test_output_dir.join("postman_environment.json")).unwrap();
+        assert!(!environment.contains("authToken"));
+        let script = fs::read_to_string(test_output_dir.join("run-newman.sh")).unwrap();
+        assert!(script.contains("baseUrl=$API_BASE_URL"));
+        assert!(!script.contains("API_TOKEN"));
+        assert_eq!(fs::read_to_string(test_output_dir.join("upload.txt")).unwrap(), "test file content");
+    }
+    
+    #[test]
+    fn test_response_schema_validation() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("response-schemas");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_openapi.yaml")).unwrap();
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        // The schemas of the responses are written as draft 4 JSON Schemas
+        let schema: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("schemas").join("getUsers.json")).unwrap()).unwrap();
+        assert_eq!(schema["$schema"], "http://json-schema.org/draft-04/schema#");
+        assert_eq!(schema["type"], "array");
+        assert_eq!(schema["items"]["required"], serde_json::json!(["id", "name", "email", "created_at"]));
+        
+        // pytest validates with jsonschema
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.starts_with("import json\nimport pathlib\n\nimport jsonschema\nimport requests\nimport pytest\n"));
+        assert!(pytest.contains("        jsonschema.validate(body, json.load(schema_file))"));
+        assert!(pytest.contains("    # Verify the response body matches its schema\n    validate_schema(response.json(), \"getUsers\")"));
+        let requirements = fs::read_to_string(test_output_dir.join("requirements.txt")).unwrap();
+        assert!(requirements.starts_with("jsonschema==4.22.0\n"));
+        
+        // Jest validates with Ajv
+        let jest = fs::read_to_string(test_output_dir.join("users.test.js")).unwrap();
+        assert!(jest.contains("const Ajv = require('ajv-draft-04');"));
+        assert!(jest.contains("  const validate = ajv.compile(require(`./schemas/${operationId}.json`));"));
+        assert!(jest.contains("  expect(schemaErrors(response.data, \"createUser\")).toEqual([]);"));
+        let package = fs::read_to_string(test_output_dir.join("package.json")).unwrap();
+        assert!(package.contains("\"ajv-draft-04\": \"^1.0.0\""));
+        
+        // Postman validates with tv4
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        let exec = collection["item"][0]["item"][0]["event"][0]["script"]["exec"].as_array().unwrap();
+        assert!(exec.iter().any(|line| line == "pm.test(\"Response body matches its schema\", function () {"));
+        assert!(exec.iter().any(|line| line.as_str().unwrap().starts_with("    var schema = {\"$schema\":\"http://json-schema.org/draft-04/schema#\"")));
+        assert!(exec.iter().any(|line| line == "    pm.expect(tv4.validate(pm.response.json(), schema), JSON.stringify(tv4.error)).to.be.true;"));
+        
+        // reqwest deserializes the body into a struct following the schema
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("    struct ResponseItem {\n        created_at: String,\n        email: String,\n        id: i64,\n        name: String,\n    }\n"));
+        assert!(reqwest.contains("    let _body: Vec<ResponseItem> = response.json().await.expect(\"Response body doesn't match its schema\");"));
+        assert!(!reqwest.contains("let users: Vec<User>"));
+        
+        // Optional, nullable and renamed properties, and checks of enum values
+        let spec = parse_swagger_file(get_test_data_path("sample_response_schemas.yaml")).unwrap();
+        create_generator(TestFramework::Reqwest).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains(r#"    struct ResponseBody {
+        #[serde(rename = "birthDate")]
+        birth_date: Option<String>,
+        id: i64,
+        name: String,
+        owner: Option<serde_json::Value>,
+        status: String,
+        tags: Option<Vec<serde_json::Value>>,
+        r#type: String,
+    }"#));
+        assert!(!reqwest.contains("password"));
+        assert!(reqwest.contains(r#"    assert!(["available", "sold"].contains(&body.status.as_str()), "Unexpected status: {}", body.status);"#));
+        assert!(reqwest.contains("    for item in &body {\n        assert!([\"available\", \"sold\"].contains(&item.status.as_str())"));
+        assert!(reqwest.contains("    let _body: i64 = response.json().await.expect(\"Response body doesn't match its schema\");"));
+    }
+    
+    #[test]
+    fn test_generate_negative_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("negative-tests");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_negative.yaml")).unwrap();
+        
+        // Without the flag, only the valid requests are tested
+        create_generator_with_options(TestFramework::Pytest, &with_optional_query_params()).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(!pytest.contains("missing"));
+        fs::remove_dir_all(&test_output_dir).unwrap();
+        
+        let options = GeneratorOptions { negative_tests: true, optional_query_params: true, ..Default::default() };
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        // A test per required property, leaving it out; read-only properties aren't sent anyway
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("async fn test_create_pet_missing_name() {\n    // Sent without the required property name, the request has to be rejected\n    let body = json!({\n      \"age\": 3,\n      \"status\": \"available\"\n    });"));
+        assert!(reqwest.contains("client.request(reqwest::Method::POST, url)\n        .query(&[(\"dryRun\", \"true\")])\n        .header(\"X-Request-Id\", \"abc-123\")\n        .json(&body)"));
+        assert!(reqwest.contains("async fn test_create_pet_missing_status() {"));
+        assert!(!reqwest.contains("test_create_pet_missing_id"));
+        
+        // The documented 400 or 422 is expected, or else any 4xx
+        assert!(reqwest.contains("    let url = \"http://localhost:3000/pets\";\n    \n    let response = client.request(reqwest::Method::POST, url)"));
+        assert!(reqwest.contains("    assert_eq!(response.status().as_u16(), 400);"));
+        assert!(reqwest.contains("    let url = \"http://localhost:3000/pets/7\";"));
+        assert!(reqwest.contains("    assert_eq!(response.status().as_u16(), 422);"));
+        assert!(reqwest.contains("    assert!((400..500).contains(&response.status().as_u16()), \"Expected a 4xx status, got {}\", response.status().as_u16());"));
+        
+        // Names that come out the same are numbered, and deprecated operations get no negative tests
+        assert!(reqwest.contains("async fn test_create_owner_missing_first_name() {"));
+        assert!(reqwest.contains("async fn test_create_owner_missing_first_name_2() {"));
+        assert!(!reqwest.contains("test_create_legacy_pet_missing"));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains("def test_createPet_missing_name():\n    \"\"\"\n    Sent without the required property name, the request has to be rejected\n    \"\"\""));
+        assert!(pytest.contains("    response = requests.request(\"POST\", url, params={\"dryRun\": \"true\"}, headers={\"X-Request-Id\": \"abc-123\"}, json=json_data)"));
+        assert!(pytest.contains("    assert 400 <= response.status_code < 500"));
+        
+        let jest = fs::read_to_string(test_output_dir.join("pets.test.js")).unwrap();
+        assert!(jest.contains("test(\"createPet without the required property name\", async () => {\n  const response = await axios.request({\n    method: \"post\",\n    url: \"http://localhost:3000/pets\","));
+        assert!(jest.contains("    validateStatus: () => true,\n  });\n  \n  // Verify the request is rejected\n  expect(response.status).toBe(400);"));
+        
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        let requests = collection["item"][2]["item"].as_array().unwrap();
+        let missing_name = requests.iter().find(|request| request["name"] == "POST Register a pet without the required property name").unwrap();
+        assert_eq!(missing_name["request"]["url"]["raw"], "{{baseUrl}}/pets?dryRun=true");
+        assert_eq!(missing_name["request"]["body"]["raw"], "{\n  \"age\": 3,\n  \"status\": \"available\"\n}");
+        assert_eq!(missing_name["event"][0]["script"]["exec"][1], "    pm.response.to.have.status(400);");
+    }
+    
+    #[test]
+    fn test_generate_wrong_type_negative_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("wrong-type-tests");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_negative.yaml")).unwrap();
+        let options = GeneratorOptions { negative_tests: true, ..Default::default() };
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        // Numbers are sent as text and text as a number, for every property but the read-only ones
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("async fn test_create_pet_age_as_string() {\n    // Sent with the integer property age as a string, the request has to be rejected\n    let body = json!({\n      \"age\": \"not-a-number\",\n      \"name\": \"Rex\",\n      \"status\": \"available\"\n    });"));
+        assert!(reqwest.contains("async fn test_create_pet_name_as_integer() {"));
+        assert!(reqwest.contains("      \"name\": 12345,"));
+        assert!(reqwest.contains("async fn test_update_pet_status_as_integer() {"));
+        assert!(!reqwest.contains("test_create_pet_id_as_string"));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains("def test_createPet_age_as_string():"));
+        assert!(pytest.contains("    json_data = {\n        \"age\": \"not-a-number\","));
+        
+        let jest = fs::read_to_string(test_output_dir.join("pets.test.js")).unwrap();
+        assert!(jest.contains("test(\"createPet with the string property name as an integer\", async () => {"));
+        
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        let requests = collection["item"][2]["item"].as_array().unwrap();
+        let age_as_string = requests.iter().find(|request| request["name"] == "POST Register a pet with the integer property age as a string").unwrap();
+        assert_eq!(age_as_string["request"]["body"]["raw"], "{\n  \"age\": \"not-a-number\",\n  \"name\": \"Rex\",\n  \"status\": \"available\"\n}");
+    }
+    
+    #[test]
+    fn test_generate_invalid_enum_negative_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("invalid-enum-tests");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_negative.yaml")).unwrap();
+        let options = GeneratorOptions { negative_tests: true, optional_query_params: true, ..Default::default() };
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        // Parameters restricted by an enum are sent with a value outside of it, the others keeping their valid values
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("async fn test_list_pets_invalid_query_status() {\n    // Sent with the query parameter status set to invalid-value, outside its enum, the request has to be rejected"));
+        assert!(reqwest.contains("        .query(&[(\"status\", \"invalid-value\")])\n        .header(\"X-Api-Version\", \"1\")"));
+        assert!(reqwest.contains("async fn test_list_pets_invalid_header_x_api_version() {"));
+        assert!(reqwest.contains("        .query(&[(\"status\", \"available\")])\n        .header(\"X-Api-Version\", \"3\")"));
+        assert!(reqwest.contains("async fn test_list_pets_by_species_invalid_path_species() {"));
+        assert!(reqwest.contains("    let url = \"http://localhost:3000/species/invalid-value/pets\";"));
+        
+        // So are body properties
+        assert!(reqwest.contains("async fn test_create_pet_invalid_status() {\n    // Sent with the property status set to invalid-value, outside its enum, the request has to be rejected\n    let body = json!({\n      \"age\": 3,\n      \"name\": \"Rex\",\n      \"status\": \"invalid-value\"\n    });"));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains("    response = requests.request(\"GET\", url, params={\"status\": \"invalid-value\"}, headers={\"X-Api-Version\": \"1\"})"));
+        
+        let jest = fs::read_to_string(test_output_dir.join("species_species_pets.test.js")).unwrap();
+        assert!(jest.contains("    url: \"http://localhost:3000/species/invalid-value/pets\","));
+        
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        let requests = collection["item"][2]["item"].as_array().unwrap();
+        let invalid_status = requests.iter().find(|request| request["name"] == "GET List pets with the query parameter status set to invalid-value, outside its enum").unwrap();
+        assert_eq!(invalid_status["request"]["url"]["raw"], "{{baseUrl}}/pets?status=invalid-value");
+        assert_eq!(invalid_status["event"][0]["script"]["exec"][1], "    pm.response.to.have.status(400);");
+    }
+    
+    #[test]
+    fn test_generate_boundary_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("boundary-tests");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Boundary tests don't come with the negative ones
+        let options = GeneratorOptions { boundary_tests: true, optional_query_params: true, ..Default::default() };
+        let spec = parse_swagger_file(get_test_data_path("sample_constraints.yaml")).unwrap();
+        for framework in [TestFramework::Reqwest, TestFramework::Pytest, TestFramework::Jest, TestFramework::Postman] {
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+                .unwrap();
+        }
+        
+        // Integers are sent at their bounds, expecting the success status, and one past them, expecting a 4xx status
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("async fn test_search_products_query_limit_at_minimum() {\n    // Sent with the query parameter limit at its minimum, 10, the request has to be accepted"));
+        assert!(reqwest.contains("(\"limit\", \"10\")"));
+        assert!(reqwest.contains("    assert_eq!(response.status().as_u16(), 200);\n}"));
+        assert!(reqwest.contains("async fn test_search_products_query_limit_below_minimum() {\n    // Sent with the query parameter limit below its minimum, 9, the request has to be rejected"));
+        assert!(reqwest.contains("async fn test_search_products_query_limit_at_maximum() {"));
+        assert!(reqwest.contains("async fn test_search_products_query_limit_above_maximum() {\n    // Sent with the query parameter limit above its maximum, 101,"));
+        assert!(reqwest.contains("    assert!((400..500).contains(&response.status().as_u16()), \"Expected a 4xx status, got {}\", response.status().as_u16());"));
+        
+        // An exclusive bound is itself outside, and numbers have no value just inside it
+        assert!(reqwest.contains("async fn test_search_products_query_max_price_at_minimum() {"));
+        assert!(reqwest.contains("async fn test_search_products_query_max_price_above_maximum() {\n    // Sent with the query parameter maxPrice above its maximum, 1.0,"));
+        assert!(!reqwest.contains("test_search_products_query_max_price_at_maximum"));
+        
+        // Strings are sent at their length limits, unless they must match a pattern
+        assert!(reqwest.contains("async fn test_search_products_query_query_at_min_length() {\n    // Sent with the query parameter query at its minimum length, 12 characters,"));
+        assert!(reqwest.contains("(\"query\", \"aaaaaaaaaaa\")"));
+        assert!(reqwest.contains("async fn test_search_products_query_lang_above_max_length() {"));
+        assert!(!reqwest.contains("test_search_products_query_sku_"));
+        assert!(!reqwest.contains("invalid_"));
+        
+        let pytest = fs::read_to_string(test_output_dir.join("test_api.py")).unwrap();
+        assert!(pytest.contains("def test_searchProducts_query_limit_at_minimum():\n    \"\"\"\n    Sent with the query parameter limit at its minimum, 10, the request has to be accepted\n    \"\"\""));
+        assert!(pytest.contains("    # Verify the request is accepted\n    assert response.status_code == 200"));
+        
+        let jest = fs::read_to_string(test_output_dir.join("products.test.js")).unwrap();
+        assert!(jest.contains("test(\"searchProducts with the query parameter lang at its maximum length, 2 characters\", async () => {"));
+        
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(test_output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        let requests = collection["item"][0]["item"].as_array().unwrap();
+        let at_minimum = requests.iter().find(|request| request["name"] == "GET Search products with the query parameter limit at its minimum, 10").unwrap();
+        assert_eq!(at_minimum["event"][0]["script"]["exec"][1], "    pm.response.to.have.status(200);");
+        
+        // Body properties get them too
+        fs::remove_dir_all(&test_output_dir).unwrap();
+        let spec = parse_swagger_file(get_test_data_path("sample_negative.yaml")).unwrap();
+        create_generator_with_options(TestFramework::Reqwest, &options).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        let reqwest = fs::read_to_string(test_output_dir.join("api_tests.rs")).unwrap();
+        assert!(reqwest.contains("async fn test_create_pet_age_above_maximum() {\n    // Sent with the property age above its maximum, 31, the request has to be rejected\n    let body = json!({\n      \"age\": 31,"));
+        assert!(reqwest.contains("async fn test_create_pet_name_below_min_length() {"));
+        assert!(reqwest.contains("      \"name\": \"\","));
+        assert!(reqwest.contains("    assert_eq!(response.status().as_u16(), 201);\n}"));
+    }
+    
+    #[test]
+    fn test_generate_bearer_auth_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("bearer_auth");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_security.yaml")).unwrap();
+        let options = GeneratorOptions {
+            auth: [AuthSource::Bearer("MY_TOKEN".to_string())].into_iter().collect(),
+            ..GeneratorOptions::default()
+        };
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Operations requiring a bearer or OAuth2 scheme send the token, the public health check doesn't
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest").join("api_tests.rs")).unwrap();
+        assert!(tests.contains("fn credential(name: &str) -> String {\n    std::env::var(name)"));
+        assert_eq!(tests.matches(".bearer_auth(credential(\"MY_TOKEN\"))").count(), 2);
+        let health = tests.split("fn test_get_health()").nth(1).unwrap().split("#[tokio::test]").next().unwrap();
+        assert!(!health.contains("bearer_auth"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Ureq, "ureq").join("api_tests.rs")).unwrap();
+        assert!(tests.contains(".set(\"Authorization\", &format!(\"Bearer {}\", credential(\"MY_TOKEN\")))"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest").join("test_api.py")).unwrap();
+        assert!(tests.starts_with("import os\n\nimport requests\nimport pytest\n"));
+        assert!(tests.contains("    headers = {\n        \"Authorization\": f\"Bearer {os.environ['MY_TOKEN']}\"\n    }"));
+        assert!(tests.contains("response = requests.post(url, json=json_data, params=params, headers=headers)"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest").join("users.test.js")).unwrap();
+        assert!(tests.contains("  const headers = {\n    \"Authorization\": `Bearer ${process.env.MY_TOKEN}`\n  };"));
+        assert!(tests.contains("const response = await axios.get(url, { params, headers });"));
+        
+        // Newman takes the collection's token from the configured variable
+        let output_dir = generate(TestFramework::Postman, "postman");
+        let script = fs::read_to_string(output_dir.join("run-newman.sh")).unwrap();
+        assert!(script.contains("if [[ -n \"${MY_TOKEN:-}\" ]]; then\n  args+=(--env-var \"authToken=$MY_TOKEN\")\nfi"));
+        assert!(!script.contains("API_TOKEN"));
+        
+        // Without credentials configured, no test authenticates
+        let output_dir = test_output_dir.join("anonymous");
+        create_generator(TestFramework::Pytest).unwrap()
+            .generate_tests(&spec, &output_dir, "http://localhost:3000")
+            .unwrap();
+        let tests = fs::read_to_string(output_dir.join("test_api.py")).unwrap();
+        assert!(tests.starts_with("import requests\nimport pytest\n"));
+        assert!(!tests.contains("Authorization"));
+    }
+
+    #[test]
+    fn test_generate_api_key_auth_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("api_key_auth");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_api_key.yaml")).unwrap();
+        let options = GeneratorOptions {
+            auth: [AuthSource::ApiKey("ORDERS_KEY".to_string())].into_iter().collect(),
+            negative_tests: true,
+            optional_query_params: true,
+            ..GeneratorOptions::default()
+        };
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Keys go into the scheme's header or query parameter; the report takes the query key, as keys aren't sent in cookies
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest").join("api_tests.rs")).unwrap();
+        assert!(tests.contains("client.get(&url)\n        .header(\"X-API-Key\", credential(\"ORDERS_KEY\"))\n        .send()"));
+        assert!(tests.contains("client.get(&url).query(&query_params)\n        .query(&[(\"api_key\", credential(\"ORDERS_KEY\"))])"));
+        let negative = tests.split("fn test_create_order_missing_item()").nth(1).unwrap();
+        assert!(negative.contains(".header(\"X-API-Key\", credential(\"ORDERS_KEY\"))\n        .json(&body)"));
+        let status = tests.split("fn test_get_status()").nth(1).unwrap().split("#[tokio::test]").next().unwrap();
+        assert!(!status.contains("credential("));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Ureq, "ureq").join("api_tests.rs")).unwrap();
+        assert!(tests.contains(".set(\"X-API-Key\", &credential(\"ORDERS_KEY\"))"));
+        assert!(tests.contains(".query(\"api_key\", &credential(\"ORDERS_KEY\"))"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest").join("test_api.py")).unwrap();
+        assert!(tests.contains("    headers = {\n        \"X-API-Key\": os.environ['ORDERS_KEY']\n    }"));
+        assert!(tests.contains("    params = {\n        \"month\": \"2024-01\",\n        \"api_key\": os.environ['ORDERS_KEY']\n    }"));
+        assert!(tests.contains("requests.request(\"POST\", url, headers={\"X-API-Key\": os.environ['ORDERS_KEY']}, json=json_data)"));
+        
+        let output_dir = generate(TestFramework::Jest, "jest");
+        let tests = fs::read_to_string(output_dir.join("orders.test.js")).unwrap();
+        assert!(tests.contains("  const headers = {\n    \"X-API-Key\": process.env.ORDERS_KEY\n  };"));
+        assert!(tests.contains("    headers: {\n      \"X-API-Key\": process.env.ORDERS_KEY,\n    },"));
+        let tests = fs::read_to_string(output_dir.join("reports.test.js")).unwrap();
+        assert!(tests.contains("  const params = {\n    month: \"2024-01\",\n    \"api_key\": process.env.ORDERS_KEY\n  };"));
+        
+        // Newman takes the collection's key from the configured variable
+        let script = fs::read_to_string(generate(TestFramework::Postman, "postman").join("run-newman.sh")).unwrap();
+        assert!(script.contains("if [[ -n \"${ORDERS_KEY:-}\" ]]; then\n  args+=(--env-var \"apiKey=$ORDERS_KEY\")\nfi"));
+    }
+
+    #[test]
+    fn test_generate_basic_auth_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("basic_auth");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_basic_auth.yaml")).unwrap();
+        let options = GeneratorOptions {
+            auth: [AuthSource::Basic("ACC_USER".to_string(), "ACC_PASS".to_string())].into_iter().collect(),
+            negative_tests: true,
+            ..GeneratorOptions::default()
+        };
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Each client sends basic authentication its own way, with the credentials read from the environment
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest").join("api_tests.rs")).unwrap();
+        assert_eq!(tests.matches(".basic_auth(credential(\"ACC_USER\"), Some(credential(\"ACC_PASS\")))").count(), 4);
+        let ping = tests.split("fn test_ping()").nth(1).unwrap().split("#[tokio::test]").next().unwrap();
+        assert!(!ping.contains("basic_auth"));
+        
+        // ureq has no basic authentication, so the tests encode the header with base64
+        let output_dir = generate(TestFramework::Ureq, "ureq");
+        let tests = fs::read_to_string(output_dir.join("api_tests.rs")).unwrap();
+        assert!(tests.contains("fn basic_auth(username_var: &str, password_var: &str) -> String {"));
+        assert!(tests.contains(".set(\"Authorization\", &basic_auth(\"ACC_USER\", \"ACC_PASS\"))"));
+        let cargo = fs::read_to_string(output_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo.contains("base64 = \"0.22\"\nureq = "));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest").join("test_api.py")).unwrap();
+        assert!(tests.starts_with("import os\n"));
+        assert!(tests.contains("response = requests.get(url, params=params, auth=(os.environ['ACC_USER'], os.environ['ACC_PASS']))"));
+        assert!(tests.contains("requests.request(\"POST\", url, json=json_data, auth=(os.environ['ACC_USER'], os.environ['ACC_PASS']))"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest").join("accounts.test.js")).unwrap();
+        assert!(tests.contains("  const auth = { username: process.env.ACC_USER, password: process.env.ACC_PASS };"));
+        assert!(tests.contains("const response = await axios.get(url, { params, auth });"));
+        assert!(tests.contains("    auth: { username: process.env.ACC_USER, password: process.env.ACC_PASS },\n    validateStatus: () => true,"));
+        
+        // Postman's basic auth takes the user name and password from the configured variables
+        let output_dir = generate(TestFramework::Postman, "postman");
+        let collection = fs::read_to_string(output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        assert_eq!(collection["auth"]["type"], "basic");
+        let script = fs::read_to_string(output_dir.join("run-newman.sh")).unwrap();
+        assert!(script.contains("  args+=(--env-var \"username=$ACC_USER\")"));
+        assert!(script.contains("  args+=(--env-var \"password=$ACC_PASS\")"));
+    }
+
+    #[test]
+    fn test_generate_client_credentials_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("client_credentials");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_security.yaml")).unwrap();
+        let options = GeneratorOptions {
+            auth: [
+                AuthSource::ClientCredentials("CLIENT_ID".to_string(), "CLIENT_SECRET".to_string()),
+                AuthSource::ApiKey("API_KEY".to_string()),
+            ].into_iter().collect(),
+            ..GeneratorOptions::default()
+        };
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Operations send a token of the scopes they require, fetched from the flow's token endpoint and cached
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest").join("api_tests.rs")).unwrap();
+        assert!(tests.contains("async fn access_token(token_url: &str, scope: &str) -> String {"));
+        assert!(tests.contains("        (\"client_id\", credential(\"CLIENT_ID\")),\n        (\"client_secret\", credential(\"CLIENT_SECRET\")),"));
+        assert!(tests.contains(".header(\"X-API-Key\", credential(\"API_KEY\"))\n        .bearer_auth(access_token(\"https://auth.sample.com/token\", \"read\").await)"));
+        assert!(tests.contains(".bearer_auth(access_token(\"https://auth.sample.com/token\", \"write\").await)"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Ureq, "ureq").join("api_tests.rs")).unwrap();
+        assert!(tests.contains("\nfn access_token(token_url: &str, scope: &str) -> String {"));
+        assert!(tests.contains("let response = send(ureq::post(token_url).send_form(&form));"));
+        assert!(tests.contains(".set(\"Authorization\", &format!(\"Bearer {}\", access_token(\"https://auth.sample.com/token\", \"write\")))"));
+        
+        // pytest tests fetch their tokens with the access_token fixture of conftest.py
+        let output_dir = generate(TestFramework::Pytest, "pytest");
+        let tests = fs::read_to_string(output_dir.join("test_api.py")).unwrap();
+        assert!(tests.contains("\"Authorization\": f\"Bearer {access_token('https://auth.sample.com/token', 'write')}\""));
+        let conftest = fs::read_to_string(output_dir.join("conftest.py")).unwrap();
+        assert!(conftest.starts_with("import os\nimport time\n\nimport pytest\nimport requests\n"));
+        assert!(conftest.contains("        \"client_id\": os.environ[\"CLIENT_ID\"],"));
+        assert!(conftest.contains("    response = requests.post(token_url, data=data)"));
+        
+        // Async tests fetch their tokens with httpx
+        let conftest = fs::read_to_string(generate(TestFramework::PytestAsync, "pytest_async").join("conftest.py")).unwrap();
+        assert!(conftest.starts_with("import os\nimport time\n\nimport httpx\nimport pytest\n"));
+        assert!(conftest.contains("    response = httpx.post(token_url, data=data)"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest").join("users.test.js")).unwrap();
+        assert!(tests.contains("async function accessToken(tokenUrl, scope) {"));
+        assert!(tests.contains("    client_id: process.env.CLIENT_ID,\n    client_secret: process.env.CLIENT_SECRET,"));
+        assert!(tests.contains("\"Authorization\": `Bearer ${await accessToken(\"https://auth.sample.com/token\", \"write\")}`"));
+        
+        // Postman fetches the token into authToken before each request needing one, for every scope asked for
+        let output_dir = generate(TestFramework::Postman, "postman");
+        let collection = fs::read_to_string(output_dir.join("postman_collection.json")).unwrap();
+        let collection: serde_json::Value = serde_json::from_str(&collection).unwrap();
+        assert_eq!(collection["event"][0]["listen"], "prerequest");
+        let script = collection["event"][0]["script"]["exec"].as_array().unwrap();
+        assert!(script.contains(&serde_json::json!("        url: \"https://auth.sample.com/token\",")));
+        assert!(script.contains(&serde_json::json!("                { key: 'scope', value: \"read write\" },")));
+        assert!(script.contains(&serde_json::json!("        pm.environment.set('authToken', body.access_token);")));
+        let script = fs::read_to_string(output_dir.join("run-newman.sh")).unwrap();
+        assert!(script.contains("  args+=(--env-var \"clientId=$CLIENT_ID\")"));
+        assert!(script.contains("  args+=(--env-var \"clientSecret=$CLIENT_SECRET\")"));
+    }
+
+    #[test]
+    fn test_generate_access_token_fetched_once() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("access_token_once");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_security.yaml")).unwrap();
+        let options = GeneratorOptions {
+            auth: [
+                AuthSource::ClientCredentials("CLIENT_ID".to_string(), "CLIENT_SECRET".to_string()),
+                AuthSource::ApiKey("API_KEY".to_string()),
+            ].into_iter().collect(),
+            ..GeneratorOptions::default()
+        };
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Rust tests share the tokens in a static behind a mutex, held while fetching so that concurrent tests wait for
+        // the one token request instead of each sending their own
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest").join("api_tests.rs")).unwrap();
+        assert!(tests.contains("static ACCESS_TOKENS: tokio::sync::Mutex<Vec<(String, String, String, std::time::Instant)>> = tokio::sync::Mutex::const_new(Vec::new());"));
+        assert!(tests.contains("async fn access_token(token_url: &str, scope: &str) -> String {\n    let mut tokens = ACCESS_TOKENS.lock().await;"));
+        assert!(tests.contains("*refresh_at > now) {\n        return token.clone();\n    }"));
+        assert_eq!(tests.matches(".post(token_url)").count(), 1);
+        let tests = fs::read_to_string(generate(TestFramework::Ureq, "ureq").join("api_tests.rs")).unwrap();
+        assert!(tests.contains("static ACCESS_TOKENS: std::sync::Mutex<Vec<(String, String, String, std::time::Instant)>> = std::sync::Mutex::new(Vec::new());"));
+        assert!(tests.contains("\nfn access_token(token_url: &str, scope: &str) -> String {\n    let mut tokens = ACCESS_TOKENS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());"));
+        assert_eq!(tests.matches("ureq::post(token_url)").count(), 1);
+        
+        // pytest tests take their tokens from a session-scoped fixture in conftest.py, the only place they're requested
+        for (framework, name, client, signature) in [
+            (TestFramework::Pytest, "pytest", "requests", "def test_createUser(access_token):"),
+            (TestFramework::PytestAsync, "pytest_async", "httpx", "async def test_createUser(client, access_token):"),
+        ] {
+            let output_dir = generate(framework, name);
+            let tests = fs::read_to_string(output_dir.join("test_api.py")).unwrap();
+            assert!(!tests.contains("def access_token("));
+            assert!(!tests.contains("token_url, data=data"));
+            assert!(tests.contains(signature));
+            let conftest = fs::read_to_string(output_dir.join("conftest.py")).unwrap();
+            assert!(conftest.contains("@pytest.fixture(scope=\"session\")\ndef access_token():"));
+            assert!(conftest.contains("        if cached and cached[1] > time.monotonic():\n            return cached[0]"));
+            assert_eq!(conftest.matches(&format!("{}.post(token_url, data=data)", client)).count(), 1);
+        }
+        
+        // Jest requests every token the tests need once in a global setup, and test files reuse them until they expire
+        let output_dir = generate(TestFramework::Jest, "jest");
+        let setup = fs::read_to_string(output_dir.join("global-setup.js")).unwrap();
+        assert_eq!(setup.matches("    [\"https://auth.sample.com/token\", \"read\"],\n").count(), 1);
+        assert_eq!(setup.matches("    [\"https://auth.sample.com/token\", \"write\"],\n").count(), 1);
+        assert_eq!(setup.matches("axios.post(tokenUrl, form)").count(), 1);
+        assert!(setup.contains("  process.env.ACCESS_TOKENS = JSON.stringify(tokens);"));
+        let package = fs::read_to_string(output_dir.join("package.json")).unwrap();
+        let package: serde_json::Value = serde_json::from_str(&package).unwrap();
+        assert_eq!(package["jest"]["globalSetup"], "./global-setup.js");
+        let tests = fs::read_to_string(output_dir.join("users.test.js")).unwrap();
+        assert!(tests.contains("const accessTokens = new Map(Object.entries(JSON.parse(process.env.ACCESS_TOKENS || '{}')));"));
+        assert!(tests.contains("  if (cached && cached.refreshAt > Date.now()) {\n    return cached.token;\n  }"));
+        
+        // Without client credentials, tests get no shared setup
+        let output_dir = test_output_dir.join("jest_without_tokens");
+        create_generator_with_options(TestFramework::Jest, &GeneratorOptions::default()).unwrap()
+            .generate_tests(&spec, &output_dir, "http://localhost:3000")
+            .unwrap();
+        assert!(!output_dir.join("global-setup.js").exists());
+        let output_dir = test_output_dir.join("pytest_without_tokens");
+        create_generator_with_options(TestFramework::Pytest, &GeneratorOptions::default()).unwrap()
+            .generate_tests(&spec, &output_dir, "http://localhost:3000")
+            .unwrap();
+        assert!(!output_dir.join("conftest.py").exists());
+    }
+
+    #[test]
+    fn test_generate_unauthorized_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("unauthorized");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_security.yaml")).unwrap();
+        let options = GeneratorOptions {
+            unauthorized_tests: true,
+            auth: [AuthSource::Bearer("MY_TOKEN".to_string())].into_iter().collect(),
+            ..GeneratorOptions::default()
+        };
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Secured operations get a test without the token, the public health check doesn't
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest").join("api_tests.rs")).unwrap();
+        let unauthorized = tests.split("async fn test_create_user_unauthorized()").nth(1).unwrap().split("#[tokio::test]").next().unwrap();
+        assert!(unauthorized.contains("// Sent without credentials, the request has to be rejected"));
+        assert!(!unauthorized.contains("bearer_auth"));
+        assert!(unauthorized.contains("assert_eq!(response.status().as_u16(), 401);"));
+        assert!(tests.contains("fn test_get_users_unauthorized()"));
+        assert!(!tests.contains("fn test_get_health_unauthorized()"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest").join("test_api.py")).unwrap();
+        assert!(tests.contains("def test_createUser_unauthorized():"));
+        assert!(tests.contains("    url = \"http://localhost:3000/users\"\n    response = requests.request(\"POST\", url)\n"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest").join("users.test.js")).unwrap();
+        let unauthorized = tests.split("test(\"createUser without credentials\"").nth(1).unwrap();
+        assert!(!unauthorized.split("});").next().unwrap().contains("headers"));
+        assert!(unauthorized.contains("expect(response.status).toBe(401);"));
+        
+        // Postman sends the case without the collection's auth
+        let output_dir = generate(TestFramework::Postman, "postman");
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        let requests = collection["item"][1]["item"].as_array().unwrap();
+        let unauthorized = requests.iter().find(|request| request["name"] == "POST createUser without credentials").unwrap();
+        assert_eq!(unauthorized["request"]["auth"]["type"], "noauth");
+        assert_eq!(unauthorized["event"][0]["script"]["exec"][1], "    pm.response.to.have.status(401);");
+    }
+    
+    #[test]
+    fn test_operation_filters() {
+        let spec = parse_swagger_file(get_test_data_path("sample_tags.yaml")).unwrap();
+        let operation_ids = |options: GeneratorOptions| -> Vec<String> {
+            operation_groups(&spec, &options)
+                .iter()
+                .flat_map(|group| group.operations.iter().map(|(_, operation)| operation.operation_id.clone()))
+                .collect()
+        };
+        
+        // Tags match whatever their case, and untagged operations have none to match
+        let tags = vec!["pet store".to_string(), "orders".to_string()];
+        assert_eq!(operation_ids(GeneratorOptions { include_tags: tags, ..GeneratorOptions::default() }), ["createOrder", "listPets", "getPet"]);
+        
+        let paths = vec!["/pets/*".to_string(), "/health".to_string()];
+        assert_eq!(operation_ids(GeneratorOptions { exclude_paths: paths, ..GeneratorOptions::default() }), ["createOrder", "listPets"]);
+        
+        let methods = vec!["GET".to_string()];
+        assert_eq!(operation_ids(GeneratorOptions { methods, ..GeneratorOptions::default() }), ["health", "listPets", "getPet"]);
+        
+        // The filters combine, and files are only written for the operations left
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("operation_filters");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        let options = GeneratorOptions {
+            include_tags: vec!["Pet Store".to_string()],
+            methods: vec!["GET".to_string()],
+            exclude_paths: vec!["/pets/**".to_string()],
+            ..GeneratorOptions::default()
+        };
+        create_generator_with_options(TestFramework::Jest, &options).unwrap()
+            .generate_tests(&spec, &test_output_dir, "http://localhost:3000")
+            .unwrap();
+        assert!(test_output_dir.join("pets.test.js").exists());
+        assert!(!test_output_dir.join("pets_petId.test.js").exists());
+        assert!(!test_output_dir.join("orders.test.js").exists());
+        
+        assert!(glob_matches("/admin/**", "/admin/users/{id}"));
+        assert!(!glob_matches("/admin/*", "/admin/users/{id}"));
+        assert!(glob_matches("/users/?", "/users/1"));
+        assert!(!glob_matches("/users", "/users/1"));
+        
+        assert_eq!(parse_method("get"), Ok("GET".to_string()));
+        assert!(parse_method("FETCH").is_err());
+    }
+    
+    #[test]
+    fn test_query_parameters() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("query_parameters");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_query_params.yaml")).unwrap();
+        let generate = |name: &str, options: &GeneratorOptions| {
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(TestFramework::Pytest, options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            fs::read_to_string(output_dir.join("test_api.py")).unwrap()
+        };
+        
+        // Only the required parameters are sent by default, with values of their types and formats
+        let tests = generate("required", &GeneratorOptions::default());
+        assert!(tests.contains("    params = {\n        \"limit\": \"5\",\n        \"bornAfter\": \"2024-01-01\"\n    }"));
+        
+        // The optional ones are sent too when asked for, arrays with their items separated by commas
+        let tests = generate("optional", &with_optional_query_params());
+        assert!(tests.contains("        \"vaccinated\": \"true\",\n"));
+        assert!(tests.contains("        \"species\": \"dog\",\n"));
+        assert!(tests.contains("        \"ids\": \"1,1\"\n"));
+        assert!(!tests.contains("test_value"));
+        
+        // Swagger 2.0 array parameters describe their items on the parameter itself
+        let v2 = parse_swagger_string(r#"
+swagger: '2.0'
+info: {title: Pets, version: '1.0'}
+paths:
+  /pets:
+    get:
+      parameters:
+        - {name: ids, in: query, type: array, items: {type: integer, format: int64}, collectionFormat: csv}
+      responses:
+        '200': {description: OK}
+"#).unwrap();
+        let output_dir = test_output_dir.join("v2");
+        create_generator_with_options(TestFramework::Reqwest, &with_optional_query_params()).unwrap()
+            .generate_tests(&v2, &output_dir, "http://localhost:3000")
+            .unwrap();
+        assert!(fs::read_to_string(output_dir.join("api_tests.rs")).unwrap().contains(r#"("ids", "1")"#));
+    }
+    
+    #[test]
+    fn test_patch_head_options() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("methods");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_methods.yaml")).unwrap();
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Each method is sent as itself, PATCH with its body, rather than as a GET
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest").join("api_tests.rs")).unwrap();
+        assert!(tests.contains("let response = client.patch(&url).json(&body)"));
+        assert!(tests.contains("let response = client.head(&url)"));
+        assert!(tests.contains("let response = client.request(reqwest::Method::OPTIONS, &url)"));
+        let head = tests.split("async fn test_pet_exists()").nth(1).unwrap().split("#[tokio::test]").next().unwrap();
+        assert!(head.contains("assert!(response.bytes().await.expect(\"Failed to read response\").is_empty(), \"Expected no body in a HEAD response\");"));
+        assert!(!head.contains("ResponseBody"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Ureq, "ureq").join("api_tests.rs")).unwrap();
+        assert!(tests.contains("let response = send(client.patch(&url)\n        .send_json(&body));"));
+        assert!(tests.contains("let response = send(client.request(\"OPTIONS\", &url)"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest").join("test_api.py")).unwrap();
+        assert!(tests.contains("    response = requests.patch(url, json=json_data, params=params)"));
+        assert!(tests.contains("    response = requests.head(url, params=params, headers=headers)"));
+        assert!(tests.contains("    assert response.content == b\"\""));
+        assert!(tests.contains("    response = requests.options(url, params=params)"));
+        assert!(tests.contains("    assert \"Allow\" in response.headers or \"Access-Control-Allow-Methods\" in response.headers"));
+        
+        let output_dir = generate(TestFramework::Jest, "jest");
+        let tests = fs::read_to_string(output_dir.join("pets_petId.test.js")).unwrap();
+        assert!(tests.contains("const response = await axios.patch(url, jsonData, { params });"));
+        assert!(tests.contains("const response = await axios.head(url, { params, headers });"));
+        assert!(tests.contains("expect(response.data).toBe('');"));
+        assert!(!output_dir.join("schemas").join("petExists.json").exists());
+        
+        let output_dir = generate(TestFramework::Postman, "postman");
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        let options = &collection["item"][0]["item"][0];
+        assert_eq!(options["request"]["method"], "OPTIONS");
+        assert_eq!(options["event"][0]["script"]["exec"][3], "pm.test(\"Allowed methods are listed\", function () {");
+        let head = collection["item"][1]["item"].as_array().unwrap().iter().find(|request| request["request"]["method"] == "HEAD").unwrap();
+        let script = head["event"][0]["script"]["exec"].as_array().unwrap();
+        assert!(script.contains(&serde_json::json!("    pm.expect(pm.response.text()).to.be.empty;")));
+        assert!(!script.contains(&serde_json::json!("pm.test(\"Response body matches its schema\", function () {")));
+    }
+    
+    /// The tests `framework` generates for sample_methods.yaml, whose HEAD operation documents a response body
+    fn generate_head_tests(framework: TestFramework, name: &str) -> PathBuf {
+        let output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("head")
+            .join(name);
+        if output_dir.exists() {
+            fs::remove_dir_all(&output_dir).unwrap();
+        }
+        let spec = parse_swagger_file(get_test_data_path("sample_methods.yaml")).unwrap();
+        create_generator(framework).unwrap()
+            .generate_tests(&spec, &output_dir, "http://localhost:3000")
+            .unwrap();
+        output_dir
+    }
+    
+    /// The part of generated `tests` from `start` to the next `end`
+    fn section<'a>(tests: &'a str, start: &str, end: &str) -> &'a str {
+        tests.split(start).nth(1).unwrap().split(end).next().unwrap()
+    }
+    
+    // Responses to HEAD have no body, so their tests check the status only, where GET ones check the body too
+    
+    #[test]
+    fn test_head_rest_assured() {
+        let output_dir = generate_head_tests(TestFramework::RestAssured, "rest_assured");
+        let tests = fs::read_to_string(output_dir.join("src/test/java/apitests/DefaultTest.java")).unwrap();
+        assert!(section(&tests, "void getPet()", "@Test").contains(".body(matchesJsonSchemaInClasspath(\"schemas/getPet.json\"));"));
+        let head = section(&tests, "void petExists()", "@Test");
+        assert!(head.contains(".request(\"HEAD\", \"/pets/{petId}\")\n        .then()\n            .statusCode(equalTo(200));\n    }"));
+        assert!(!head.contains(".body("));
+        assert!(!output_dir.join("src/test/resources/schemas/petExists.json").exists());
+    }
+    
+    #[test]
+    fn test_head_xunit() {
+        let output_dir = generate_head_tests(TestFramework::Xunit, "xunit");
+        let tests = fs::read_to_string(output_dir.join("DefaultTests.cs")).unwrap();
+        assert!(section(&tests, "Task GetPet()", "[Fact").contains("ReadFromJsonAsync<GetPetResponse>()"));
+        let head = section(&tests, "Task PetExists()", "[Fact");
+        assert!(head.contains("Assert.Equal(200, (int)response.StatusCode);\n    }"));
+        assert!(!head.contains("ReadFromJsonAsync"));
+        assert!(!fs::read_to_string(output_dir.join("Models.cs")).unwrap().contains("PetExistsResponse"));
+    }
+    
+    #[test]
+    fn test_head_phpunit() {
+        let output_dir = generate_head_tests(TestFramework::Phpunit, "phpunit");
+        let tests = fs::read_to_string(output_dir.join("tests/DefaultTest.php")).unwrap();
+        assert!(section(&tests, "function testGetPet()", "public function").contains("$body = $this->decodeJson($response);"));
+        let head = section(&tests, "function testPetExists()", "public function");
+        assert!(head.contains("$this->assertSame(200, $response->getStatusCode());\n    }"));
+        assert!(!head.contains("decodeJson"));
+    }
+    
+    #[test]
+    fn test_head_karate() {
+        let output_dir = generate_head_tests(TestFramework::Karate, "karate");
+        let tests = fs::read_to_string(output_dir.join("src/test/java/apitests/default.feature")).unwrap();
+        assert!(section(&tests, "@getPet", "@").contains("And match response == '#object'"));
+        let head = section(&tests, "@petExists", "@");
+        assert!(head.contains("When method head\n    Then status 200\n"));
+        assert!(!head.contains("match response"));
+    }
+    
+    #[test]
+    fn test_head_hurl() {
+        let output_dir = generate_head_tests(TestFramework::Hurl, "hurl");
+        let tests = fs::read_to_string(output_dir.join("default.hurl")).unwrap();
+        assert!(section(&tests, "# getPet\n", "\n\n\n").contains("jsonpath \"$\" isCollection"));
+        let head = section(&tests, "# petExists\n", "\n\n\n");
+        assert!(head.ends_with("HEAD {{base_url}}/pets/7\nAccept: application/json\n\nHTTP 200"));
+        assert!(!head.contains("[Asserts]"));
+    }
+    
+    #[test]
+    fn test_head_playwright() {
+        let output_dir = generate_head_tests(TestFramework::Playwright, "playwright");
+        let tests = fs::read_to_string(output_dir.join("tests/default.spec.ts")).unwrap();
+        assert!(section(&tests, "test(\"getPet\"", "test(").contains("const body = await response.json();"));
+        let head = section(&tests, "test(\"petExists\"", "test(");
+        assert!(head.contains("expect(response.status()).toBe(200);\n  });"));
+        assert!(!head.contains("response.json()"));
+    }
+    
+    #[test]
+    fn test_head_mocha() {
+        let output_dir = generate_head_tests(TestFramework::Mocha, "mocha");
+        let tests = fs::read_to_string(output_dir.join("test/default.spec.js")).unwrap();
+        assert!(section(&tests, "it(\"getPet\"", "it(").contains("expect(response.data).to.be.an('object');"));
+        let head = section(&tests, "it(\"petExists\"", "it(");
+        assert!(head.contains("expect(response.status).to.equal(200);\n  });"));
+        assert!(!head.contains("response.data"));
+    }
+    
+    #[test]
+    fn test_head_deno() {
+        let output_dir = generate_head_tests(TestFramework::Deno, "deno");
+        let tests = fs::read_to_string(output_dir.join("pets_petId_test.ts")).unwrap();
+        assert!(section(&tests, "Deno.test(\"getPet\"", "Deno.test(").contains("const body = await response.json();"));
+        let head = section(&tests, "Deno.test(\"petExists\"", "Deno.test(");
+        assert!(head.contains("assertEquals(response.status, 200);\n  await response.body?.cancel();\n});"));
+        assert!(!head.contains("response.json()"));
+    }
+    
+    #[test]
+    fn test_status_coverage() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("status_coverage");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_status_coverage.yaml")).unwrap();
+        let generate = |framework, name: &str, status_coverage| {
+            let output_dir = test_output_dir.join(name);
+            let options = GeneratorOptions { status_coverage, ..GeneratorOptions::default() };
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // By default only the success status is tested
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "success_only", StatusCoverage::SuccessOnly).join("test_api.py")).unwrap();
+        assert!(tests.contains("def test_getOrder(cleanup):"));
+        assert!(!tests.contains("def test_getOrder_not_found():"));
+        assert!(!tests.contains("_unauthorized():"));
+        
+        // Every documented error status a request is known to provoke gets a test
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest", StatusCoverage::All).join("test_api.py")).unwrap();
+        let not_found = tests.split("def test_getOrder_not_found():").nth(1).unwrap();
+        assert!(not_found.contains("    url = \"http://localhost:3000/orders/00000000-0000-0000-0000-000000000000\"\n"));
+        assert!(not_found.contains("    assert response.status_code == 404\n"));
+        let invalid_body = tests.split("def test_createOrder_invalid_body():").nth(1).unwrap();
+        assert!(invalid_body.contains("    json_data = \"not-a-valid-body\"\n"));
+        assert!(invalid_body.contains("    assert response.status_code == 422\n"));
+        let invalid_query = tests.split("def test_listOrders_invalid_query_limit():").nth(1).unwrap();
+        assert!(invalid_query.contains("params={\"limit\": \"not-a-number\"}"));
+        assert!(invalid_query.contains("    assert response.status_code == 400\n"));
+        assert!(tests.split("def test_getOrder_unauthorized():").nth(1).unwrap().contains("    assert response.status_code == 403\n"));
+        assert!(tests.contains("def test_listOrders_unauthorized():"));
+        assert!(tests.contains("def test_cancelOrder_not_found():"));
+        
+        // Public operations get no test without credentials, and undocumented statuses no test at all
+        assert!(!tests.contains("def test_cancelOrder_unauthorized():"));
+        assert!(!tests.contains("def test_createOrder_not_found():"));
+        assert!(!tests.contains("def test_cancelOrder_invalid_body():"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest", StatusCoverage::All).join("api_tests.rs")).unwrap();
+        let not_found = tests.split("async fn test_get_order_not_found()").nth(1).unwrap();
+        assert!(not_found.contains("let url = \"http://localhost:3000/orders/00000000-0000-0000-0000-000000000000\";"));
+        assert!(not_found.contains("assert_eq!(response.status().as_u16(), 404);"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest", StatusCoverage::All).join("orders.test.js")).unwrap();
+        let invalid_body = tests.split("test(\"createOrder with a body of the wrong type\"").nth(1).unwrap();
+        assert!(invalid_body.contains("expect(response.status).toBe(422);"));
+        
+        let output_dir = generate(TestFramework::Postman, "postman", StatusCoverage::All);
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        let requests = collection["item"][1]["item"].as_array().unwrap();
+        let not_found = requests.iter().find(|request| request["name"] == "GET Get an order with the nonexistent orderId 00000000-0000-0000-0000-000000000000").unwrap();
+        assert_eq!(not_found["event"][0]["script"]["exec"][1], "    pm.response.to.have.status(404);");
+    }
+    
+    #[test]
+    fn test_retries() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("retries");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_status_coverage.yaml")).unwrap();
+        let generate = |framework, name: &str, retries: u32| {
+            let output_dir = test_output_dir.join(format!("{}-{}", name, retries));
+            let options = GeneratorOptions { retries, retry_backoff_ms: Some(200), negative_tests: true, ..GeneratorOptions::default() };
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Operation, negative and setup requests are all retried, waiting twice as long each time
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest", 3).join("api_tests.rs")).unwrap();
+        assert!(tests.contains("async fn with_retries(request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {\n    let mut delay = std::time::Duration::from_millis(200);\n    for _ in 0..3 {"));
+        assert!(tests.contains("    let response = with_retries(client.get(&url))\n        .await\n        .expect(\"Failed to send request\");"));
+        assert!(tests.contains("    let response = with_retries(client.request(reqwest::Method::POST, url)\n        .json(&body))\n        .await"));
+        assert!(tests.contains("    let setup_response = with_retries(reqwest::Client::new()\n        .request(reqwest::Method::POST, \"http://localhost:3000/orders\")"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Ureq, "ureq", 3).join("api_tests.rs")).unwrap();
+        assert!(tests.contains("fn with_retries(mut call: impl FnMut() -> Result<ureq::Response, ureq::Error>) -> Result<ureq::Response, ureq::Error> {"));
+        assert!(tests.contains("    let response = send(with_retries(|| client.delete(&url)\n        .call()));"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest", 3).join("test_api.py")).unwrap();
+        assert!(tests.starts_with("import time\n\nimport requests\nimport pytest\n\n\ndef with_retries(send):"));
+        assert!(tests.contains("    delay = 200 / 1000\n    for _ in range(3):"));
+        assert!(tests.contains("    response = with_retries(lambda: requests.get(url, params=params))"));
+        assert!(tests.contains("    response = with_retries(lambda: requests.request(\"POST\", url, json=json_data))"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::PytestAsync, "pytest-async", 3).join("test_api.py")).unwrap();
+        assert!(tests.contains("async def with_retries(send):"));
+        assert!(tests.contains("    response = await with_retries(lambda: client.get(url, params=params))"));
+        assert!(tests.contains("        except httpx.TransportError:\n            pass\n        await asyncio.sleep(delay)"));
+        
+        let output_dir = generate(TestFramework::Jest, "jest", 3);
+        let tests = fs::read_to_string(output_dir.join("orders.test.js")).unwrap();
+        assert!(tests.starts_with("const axios = require('axios');\nconst axiosRetry = require('axios-retry').default;\n"));
+        assert!(tests.contains("axiosRetry(axios, {\n  retries: 3,\n  retryDelay: (retryCount) => 200 * 2 ** (retryCount - 1),"));
+        let package: serde_json::Value = serde_json::from_str(&fs::read_to_string(output_dir.join("package.json")).unwrap()).unwrap();
+        assert_eq!(package["dependencies"]["axios-retry"], "^4.0.0");
+        
+        // Without retries, requests are sent once
+        for (framework, name, file) in [
+            (TestFramework::Reqwest, "reqwest", "api_tests.rs"),
+            (TestFramework::Pytest, "pytest", "test_api.py"),
+            (TestFramework::Jest, "jest", "orders.test.js"),
+        ] {
+            let tests = fs::read_to_string(generate(framework, name, 0).join(file)).unwrap();
+            assert!(!tests.contains("with_retries") && !tests.contains("axiosRetry"), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_timeouts() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("timeouts");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_status_coverage.yaml")).unwrap();
+        let generate = |framework, name: &str, timeout_secs: Option<u64>| {
+            let output_dir = test_output_dir.join(format!("{}-{}", name, timeout_secs.unwrap_or(0)));
+            let options = GeneratorOptions { timeout_secs, negative_tests: true, ..GeneratorOptions::default() };
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Operation and setup requests both give up after the timeout
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest", Some(10)).join("api_tests.rs")).unwrap();
+        assert!(tests.contains("    let response = client.get(&url)\n        .timeout(std::time::Duration::from_secs(10))\n        .send()"));
+        assert!(tests.contains("        .request(reqwest::Method::POST, \"http://localhost:3000/orders\")\n        .timeout(std::time::Duration::from_secs(10))"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Ureq, "ureq", Some(10)).join("api_tests.rs")).unwrap();
+        assert!(tests.contains("    let response = send(client.delete(&url)\n        .timeout(std::time::Duration::from_secs(10))\n        .call());"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest", Some(10)).join("test_api.py")).unwrap();
+        assert!(tests.contains("    response = requests.get(url, params=params, timeout=10)"));
+        assert!(tests.contains("    response = requests.request(\"POST\", url, json=json_data, timeout=10)"));
+        
+        let conftest = fs::read_to_string(generate(TestFramework::PytestAsync, "pytest-async", Some(10)).join("conftest.py")).unwrap();
+        assert!(conftest.contains("    async with httpx.AsyncClient(timeout=10) as client:"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest", Some(10)).join("orders.test.js")).unwrap();
+        assert!(tests.contains("axios.defaults.timeout = 10000;"));
+        
+        let output_dir = generate(TestFramework::Postman, "postman", Some(10));
+        let script = fs::read_to_string(output_dir.join("run-newman.sh")).unwrap();
+        assert!(script.contains("--reporter-junit-export results/newman.xml --timeout-request 10000)"));
+        
+        // Without a timeout, requests wait as long as the clients do by default
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest", None).join("api_tests.rs")).unwrap();
+        assert!(!tests.contains(".timeout("));
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest", None).join("test_api.py")).unwrap();
+        assert!(!tests.contains("timeout="));
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest", None).join("orders.test.js")).unwrap();
+        assert!(!tests.contains("axios.defaults.timeout"));
+        let script = fs::read_to_string(generate(TestFramework::Postman, "postman", None).join("run-newman.sh")).unwrap();
+        assert!(!script.contains("--timeout-request"));
+    }
+
+    #[test]
+    fn test_static_headers() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("static_headers");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let headers = vec![("X-Tenant-Id".to_string(), "42".to_string()), ("traceparent".to_string(), "00-abc".to_string())];
+        let options = GeneratorOptions { headers, ..GeneratorOptions::default() };
+        let generate = |framework, name: &str, sample: &str| {
+            let spec = parse_swagger_file(get_test_data_path(sample)).unwrap();
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Operation, setup and cleanup requests all send the headers
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest", "sample_status_coverage.yaml").join("api_tests.rs")).unwrap();
+        assert!(tests.contains("    let response = client.get(&url).query(&query_params)\n        .header(\"X-Tenant-Id\", \"42\")\n        .header(\"traceparent\", \"00-abc\")"));
+        assert!(tests.contains("        .request(reqwest::Method::POST, \"http://localhost:3000/orders\")\n        .header(\"X-Tenant-Id\", \"42\")"));
+        assert!(tests.contains("reqwest::blocking::Client::new().delete(url).header(\"X-Tenant-Id\", \"42\").header(\"traceparent\", \"00-abc\").send()"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Ureq, "ureq", "sample_status_coverage.yaml").join("api_tests.rs")).unwrap();
+        assert!(tests.contains("ureq::delete(&self.0).set(\"X-Tenant-Id\", \"42\").set(\"traceparent\", \"00-abc\").call()"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest", "sample_status_coverage.yaml").join("test_api.py")).unwrap();
+        assert!(tests.contains("        requests.delete(url, headers={\"X-Tenant-Id\": \"42\", \"traceparent\": \"00-abc\"})"));
+        assert!(tests.contains("    }, headers={\"X-Tenant-Id\": \"42\", \"traceparent\": \"00-abc\"})"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest", "sample_status_coverage.yaml").join("orders.test.js")).unwrap();
+        assert!(tests.contains("    await axios.delete(createdUrls.pop(), { headers: { \"X-Tenant-Id\": \"42\", \"traceparent\": \"00-abc\" }, validateStatus: () => true });"));
+        assert!(tests.contains("    headers: { \"X-Tenant-Id\": \"42\", \"traceparent\": \"00-abc\" }\n  });"));
+        
+        let script = fs::read_to_string(generate(TestFramework::Schemathesis, "schemathesis", "sample_status_coverage.yaml").join("run-schemathesis.sh")).unwrap();
+        assert!(script.contains("  --header 'X-Tenant-Id: 42' \\\n  --header 'traceparent: 00-abc' \\\n"));
+        
+        let collection = fs::read_to_string(generate(TestFramework::Postman, "postman", "sample_status_coverage.yaml").join("postman_collection.json")).unwrap();
+        assert!(collection.contains("\"key\": \"X-Tenant-Id\""));
+        
+        // The headers replace header parameters of the same name in the other frameworks too
+        let options = GeneratorOptions { headers: vec![("X-Locale".to_string(), "de-DE".to_string())], ..GeneratorOptions::default() };
+        let spec = parse_swagger_file(get_test_data_path("sample_defaults.yaml")).unwrap();
+        let output_dir = test_output_dir.join("k6");
+        create_generator_with_options(TestFramework::K6, &options).unwrap()
+            .generate_tests(&spec, &output_dir, "http://localhost:3000")
+            .unwrap();
+        let script = fs::read_to_string(output_dir.join("default.js")).unwrap();
+        assert!(script.contains("    headers: {\n      \"X-Locale\": \"de-DE\",\n    },"));
+        assert!(!script.contains("en-US"));
+    }
+
+    #[test]
+    fn test_environments() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("environments");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_status_coverage.yaml")).unwrap();
+        let environments = vec![
+            ("dev".to_string(), "http://localhost:3000".to_string()),
+            ("staging".to_string(), "https://staging.example.com".to_string()),
+        ];
+        let options = GeneratorOptions { environments, negative_tests: true, ..GeneratorOptions::default() };
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Each environment gets a .env file setting the base URL
+        let output_dir = generate(TestFramework::Reqwest, "reqwest");
+        let env_file = fs::read_to_string(output_dir.join(".env.staging")).unwrap();
+        assert!(env_file.contains("API_BASE_URL=https://staging.example.com\n"));
+        assert!(output_dir.join(".env.dev").exists());
+        
+        // Request URLs start with the base URL read at runtime instead of a fixed one
+        let tests = fs::read_to_string(output_dir.join("api_tests.rs")).unwrap();
+        assert!(tests.contains("        let base_url = std::env::var(\"API_BASE_URL\").unwrap_or_else(|_| \"http://localhost:3000\".to_string());"));
+        assert!(tests.contains("    let url = format!(\"{BASE_URL}/orders/{orderId}\");"));
+        assert!(tests.contains("    let url = &format!(\"{BASE_URL}/orders\");"));
+        assert!(tests.contains("        .request(reqwest::Method::POST, &format!(\"{BASE_URL}/orders\"))"));
+        assert!(!tests.contains("\"http://localhost:3000/orders"));
+        
+        let output_dir = generate(TestFramework::Pytest, "pytest");
+        let conftest = fs::read_to_string(output_dir.join("conftest.py")).unwrap();
+        assert!(conftest.contains("load_dotenv(pathlib.Path(__file__).parent / f\".env.{os.environ.get('TEST_ENV', 'dev')}\")"));
+        let tests = fs::read_to_string(output_dir.join("test_api.py")).unwrap();
+        assert!(tests.contains("BASE_URL = os.environ.get(\"API_BASE_URL\", \"http://localhost:3000\")"));
+        assert!(tests.contains("    url = f\"{BASE_URL}/orders/{orderId}\""));
+        assert!(tests.contains("    url = BASE_URL + \"/orders\""));
+        assert!(fs::read_to_string(output_dir.join("requirements.txt")).unwrap().contains("python-dotenv"));
+        assert!(fs::read_to_string(output_dir.join("README.md")).unwrap().contains("TEST_ENV=staging pytest -v"));
+        
+        let conftest = fs::read_to_string(generate(TestFramework::PytestAsync, "pytest-async").join("conftest.py")).unwrap();
+        assert!(conftest.contains("from dotenv import load_dotenv"));
+        assert!(conftest.contains("async def client():"));
+        
+        let output_dir = generate(TestFramework::Jest, "jest");
+        let tests = fs::read_to_string(output_dir.join("orders.test.js")).unwrap();
+        assert!(tests.contains("require('dotenv').config({ path: require('path').join(__dirname, `.env.${process.env.TEST_ENV || 'dev'}`) });"));
+        assert!(tests.contains("const BASE_URL = process.env.API_BASE_URL || \"http://localhost:3000\";"));
+        assert!(tests.contains("  const url = `${BASE_URL}/orders`;"));
+        assert!(tests.contains("    url: BASE_URL + \"/orders\","));
+        let package: serde_json::Value = serde_json::from_str(&fs::read_to_string(output_dir.join("package.json")).unwrap()).unwrap();
+        assert_eq!(package["dependencies"]["dotenv"], "^16.4.5");
+        
+        // Postman gets an environment for each, which the Newman script picks
+        let output_dir = generate(TestFramework::Postman, "postman");
+        let environment: serde_json::Value = serde_json::from_str(&fs::read_to_string(output_dir.join("staging.postman_environment.json")).unwrap()).unwrap();
+        assert_eq!(environment["values"][0]["key"], "baseUrl");
+        assert_eq!(environment["values"][0]["value"], "https://staging.example.com");
+        assert!(!output_dir.join("postman_environment.json").exists());
+        let script = fs::read_to_string(output_dir.join("run-newman.sh")).unwrap();
+        assert!(script.contains("args=(--environment \"${TEST_ENV:-dev}.postman_environment.json\""));
+        
+        // Credentials are left commented out, so that loading the file doesn't set them to an empty value the tests
+        // would send instead of failing with the variable to set
+        let options = GeneratorOptions { auth: [AuthSource::Bearer("API_TOKEN".to_string())].into_iter().collect(), ..options };
+        let output_dir = test_output_dir.join("reqwest-credentials");
+        create_generator_with_options(TestFramework::Reqwest, &options).unwrap()
+            .generate_tests(&spec, &output_dir, "http://localhost:3000")
+            .unwrap();
+        let env_file = fs::read_to_string(output_dir.join(".env.staging")).unwrap();
+        assert!(env_file.ends_with("API_BASE_URL=https://staging.example.com\n# API_TOKEN=\n"));
+        let loaded: Vec<&str> = env_file
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split_once('=').map(|(name, _)| name))
+            .collect();
+        assert_eq!(loaded, ["API_BASE_URL"]);
+        let tests = fs::read_to_string(output_dir.join("api_tests.rs")).unwrap();
+        assert!(tests.contains("panic!(\"Set {} to the credential the tests authenticate with\", name)"));
+        // Without environments, the base URL is written into the tests
+        let options = GeneratorOptions::default();
+        let output_dir = test_output_dir.join("pytest-fixed");
+        create_generator_with_options(TestFramework::Pytest, &options).unwrap()
+            .generate_tests(&spec, &output_dir, "http://localhost:3000")
+            .unwrap();
+        let tests = fs::read_to_string(output_dir.join("test_api.py")).unwrap();
+        assert!(tests.contains("    url = f\"http://localhost:3000/orders\"") && !tests.contains("BASE_URL"));
+        assert!(!output_dir.join("conftest.py").exists() && !output_dir.join(".env.dev").exists());
+    }
+
+    #[test]
+    fn test_data_driven_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("data_driven");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_negative.yaml")).unwrap();
+        let data_sets = DataSets::from_file(&get_test_data_path("sample_data.json")).unwrap();
+        assert_eq!(data_sets.operations.iter().map(|(id, rows)| (id.as_str(), rows.len())).collect::<Vec<_>>(), vec![("listPets", 3), ("updatePet", 2)]);
+        let options = GeneratorOptions { data_sets, ..GeneratorOptions::default() };
+        let generate = |framework, name: &str, options: &GeneratorOptions| {
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(framework, options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Each request is a case of one test, sending the optional query parameters it sets and the operation's values
+        // for the others, and expecting the operation's status unless it gives one
+        let output_dir = generate(TestFramework::Pytest, "pytest", &options);
+        let tests = fs::read_to_string(output_dir.join("test_api.py")).unwrap();
+        assert!(tests.contains("@pytest.mark.parametrize(\"case\", [\n    {\n        \"headers\": {\n            \"X-Api-Version\": \"1\"\n        },\n        \"json\": None,\n        \"params\": {\n            \"status\": \"available\"\n        },\n        \"path\": \"/pets\",\n        \"status\": 200\n    },"));
+        assert!(tests.contains("        \"path\": \"/pets/8\",\n        \"status\": 422\n    }\n])\ndef test_updatePet_data(case):"));
+        assert!(tests.contains("    response = requests.request(\"PUT\", url, params=case[\"params\"], headers=case[\"headers\"], json=case[\"json\"])\n    \n    assert response.status_code == case[\"status\"]"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::PytestAsync, "pytest_async", &options).join("test_api.py")).unwrap();
+        assert!(tests.contains("])\n@pytest.mark.asyncio\nasync def test_listPets_data(case, client):"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest", &options).join("pets_petId.test.js")).unwrap();
+        assert!(tests.contains("])(\"updatePet data file request %#\", async ({ path, params, headers, data, status }) => {"));
+        assert!(tests.contains("    url: \"http://localhost:3000\" + path,\n    params,\n    headers,\n    data,"));
+        
+        // The Rust tests are rstest cases
+        let output_dir = generate(TestFramework::Reqwest, "reqwest", &options);
+        let tests = fs::read_to_string(output_dir.join("api_tests.rs")).unwrap();
+        assert!(tests.contains("#[rstest::rstest]\n#[case::request_1(\"/pets\", &[(\"status\", \"available\")], &[(\"X-Api-Version\", \"1\")], None, 200)]"));
+        assert!(tests.contains("#[case::request_2(\"/pets/8\", &[], &[], Some(json!({\"name\":\"\"})), 422)]\n#[tokio::test]\nasync fn test_update_pet_data("));
+        assert!(fs::read_to_string(output_dir.join("Cargo.toml")).unwrap().contains("rstest = \"0.18\""));
+        let tests = fs::read_to_string(generate(TestFramework::Ureq, "ureq", &options).join("api_tests.rs")).unwrap();
+        assert!(tests.contains(", 422)]\nfn test_update_pet_data("));
+        assert!(tests.contains("        request = request.query(name, value);"));
+        
+        // The same requests in CSV make the same tests
+        let csv_options = GeneratorOptions {
+            data_sets: DataSets::from_file(&get_test_data_path("sample_data.csv")).unwrap(),
+            ..GeneratorOptions::default()
+        };
+        let csv_tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest_csv", &csv_options).join("test_api.py")).unwrap();
+        assert_eq!(csv_tests, fs::read_to_string(test_output_dir.join("pytest").join("test_api.py")).unwrap());
+        
+        // Requests for unknown operations or parameters are errors, as are malformed files
+        let data_sets = DataSets::from_json(r#"{"listPets": [{"params": {"color": "red"}}]}"#).unwrap();
+        let result = create_generator_with_options(TestFramework::Pytest, &GeneratorOptions { data_sets, ..GeneratorOptions::default() }).unwrap()
+            .generate_tests(&spec, &test_output_dir.join("unknown"), "http://localhost:3000");
+        assert!(matches!(result, Err(GeneratorError::DataSetError(DataSetError::UnknownParameter { parameter, .. })) if parameter == "color"));
+        let data_sets = DataSets::from_json(r#"{"deletePet": [{}]}"#).unwrap();
+        let result = create_generator_with_options(TestFramework::Pytest, &GeneratorOptions { data_sets, ..GeneratorOptions::default() }).unwrap()
+            .generate_tests(&spec, &test_output_dir.join("unknown"), "http://localhost:3000");
+        assert!(matches!(result, Err(GeneratorError::DataSetError(DataSetError::UnknownOperation(id))) if id == "deletePet"));
+        assert!(matches!(DataSets::from_json(r#"{"listPets": [{"status": 700}]}"#), Err(DataSetError::FormatError(_))));
+        assert!(matches!(DataSets::from_csv("operationId,color\nlistPets,red\n"), Err(DataSetError::CsvError { line: 1, .. })));
+        assert!(matches!(DataSets::from_csv("operationId,params.status\nlistPets\n"), Err(DataSetError::CsvError { line: 2, .. })));
+        assert!(!fs::read_to_string(generate(TestFramework::Pytest, "pytest_without_data", &GeneratorOptions::default()).join("test_api.py")).unwrap().contains("_data("));
+    }
+    
+    #[test]
+    fn test_content_negotiation_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("content_negotiation");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_negotiation.yaml")).unwrap();
+        let responses = &spec.paths.iter().flat_map(|path| &path.operations).find(|op| op.operation_id == "getReport").unwrap().responses;
+        assert_eq!(responses.iter().find(|resp| resp.status_code == "200").unwrap().media_types, vec!["application/json", "application/xml", "text/csv"]);
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator(framework).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        // Each media type of the success response is asked for, the error response's and single ones aren't
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest").join("api_tests.rs")).unwrap();
+        let xml = tests.split("async fn test_get_report_accept_application_xml()").nth(1).unwrap().split("#[tokio::test]").next().unwrap();
+        assert!(xml.contains(".header(\"Accept\", \"application/xml\")"));
+        assert!(xml.contains("assert!(content_type.starts_with(\"application/xml\"), \"Expected application/xml, got {}\", content_type);"));
+        assert!(tests.contains("fn test_get_report_accept_application_json()"));
+        assert!(tests.contains("fn test_get_report_accept_text_csv()"));
+        assert!(!tests.contains("accept_application_problem_json"));
+        assert!(!tests.contains("fn test_list_reports_accept_"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Ureq, "ureq").join("api_tests.rs")).unwrap();
+        let csv = tests.split("fn test_get_report_accept_text_csv()").nth(1).unwrap();
+        assert!(csv.contains(".set(\"Accept\", \"text/csv\")"));
+        assert!(csv.contains("assert_eq!(response.content_type(), \"text/csv\");"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest").join("test_api.py")).unwrap();
+        let csv = tests.split("def test_getReport_accept_text_csv():").nth(1).unwrap();
+        assert!(csv.contains("headers={\"Accept\": \"text/csv\"}"));
+        assert!(csv.contains("    assert response.headers.get(\"Content-Type\", \"\").startswith(\"text/csv\")\n"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest").join("reports_reportId.test.js")).unwrap();
+        let xml = tests.split("test(\"getReport with Accept: application/xml\"").nth(1).unwrap();
+        assert!(xml.contains("expect(response.headers['content-type']?.split(';')[0].trim()).toBe(\"application/xml\");"));
+        
+        let output_dir = generate(TestFramework::Postman, "postman");
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        let requests: Vec<&serde_json::Value> = collection["item"].as_array().unwrap().iter().flat_map(|folder| folder["item"].as_array().unwrap()).collect();
+        let xml = requests.iter().find(|request| request["name"] == "GET getReport with Accept: application/xml").unwrap();
+        assert!(xml["request"]["header"].as_array().unwrap().iter().any(|header| header["key"] == "Accept" && header["value"] == "application/xml"));
+        assert!(xml["event"][0]["script"]["exec"].as_array().unwrap().iter().any(|line| line == "pm.test(\"Content-Type is application/xml\", function () {"));
+    }
+    
+    #[test]
+    fn test_pagination_tests() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("pagination");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        // Pagination parameters are found by their usual names or as x-pagination names them, counts being integers
+        let spec = parse_swagger_file(get_test_data_path("sample_pagination.yaml")).unwrap();
+        let pagination = |operation_id: &str| {
+            spec.paths.iter().flat_map(|path| &path.operations).find(|op| op.operation_id == operation_id).unwrap().pagination.clone()
+        };
+        let pets = pagination("listPets").unwrap();
+        assert_eq!((pets.page_param.as_deref(), pets.limit_param.as_deref(), pets.first_page), (Some("page"), Some("per_page"), 1));
+        assert_eq!(pagination("listOrders").unwrap().offset_param.as_deref(), Some("offset"));
+        assert_eq!(pagination("listLogs"), Some(Pagination {
+            cursor_param: Some("after".to_string()),
+            limit_param: Some("count".to_string()),
+            items_pointer: Some("/entries".to_string()),
+            next_cursor_pointer: Some("/paging/next".to_string()),
+            first_page: 1,
+            ..Pagination::default()
+        }));
+        assert_eq!(pagination("listSizes"), None);
+        assert_eq!(pagination("createPet"), None);
+        
+        let generate = |framework, name: &str, pagination_tests| {
+            let output_dir = test_output_dir.join(name);
+            let options = GeneratorOptions { pagination_tests, ..GeneratorOptions::default() };
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "disabled", false).join("test_api.py")).unwrap();
+        assert!(!tests.contains("_pagination("));
+        
+        // Numbered pages go from the first to the next, offsets by the limit, and cursors come from the first page
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest", true).join("test_api.py")).unwrap();
+        let pets = tests.split("def test_listPets_pagination():").nth(1).unwrap().split("\n\n\n").next().unwrap();
+        assert!(pets.contains("params={\"page\": \"1\", \"per_page\": \"2\"}"));
+        assert!(pets.contains("params={\"page\": \"2\", \"per_page\": \"2\"}"));
+        assert!(pets.contains("    first_items = response.json()[\"data\"]\n    assert len(first_items) <= 2\n"));
+        assert!(pets.contains("    assert first_items != second_items, \"Expected the second page to differ from the first\""));
+        let orders = tests.split("def test_listOrders_pagination():").nth(1).unwrap();
+        assert!(orders.contains("params={\"status\": \"open\", \"offset\": \"2\", \"limit\": \"2\"}"));
+        assert!(orders.contains("    first_items = response.json()\n"));
+        let events = tests.split("def test_listEvents_pagination():").nth(1).unwrap();
+        assert!(events.contains("    cursor = response.json()[\"meta\"][\"next_cursor\"]\n"));
+        assert!(events.contains("params={\"limit\": \"2\", \"cursor\": cursor}"));
+        assert!(!tests.contains("def test_listSizes_pagination"));
+        assert!(!tests.contains("def test_createPet_pagination"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest", true).join("api_tests.rs")).unwrap();
+        let logs = tests.split("async fn test_list_logs_pagination()").nth(1).unwrap();
+        assert!(logs.contains("let cursor = match first_page.pointer(\"/paging/next\") {"));
+        assert!(logs.contains(".query(&[(\"count\", \"2\"), (\"after\", cursor.as_str())])"));
+        assert!(logs.contains("let second_items = second_page.pointer(\"/entries\").and_then(|items| items.as_array())"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Ureq, "ureq", true).join("api_tests.rs")).unwrap();
+        let logs = tests.split("fn test_list_logs_pagination()").nth(1).unwrap();
+        assert!(logs.contains(".query(\"after\", &cursor)"));
+        assert!(logs.contains("let first_page: serde_json::Value = response.into_json().expect(\"Failed to parse the first page\");"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest", true).join("pets.test.js")).unwrap();
+        let pets = tests.split("test(\"listPets pagination\"").nth(1).unwrap();
+        assert!(pets.contains("  const firstItems = response.data[\"data\"];\n  expect(firstItems.length).toBeLessThanOrEqual(2);\n"));
+        assert!(pets.contains("  expect(secondItems).not.toEqual(firstItems);\n"));
+        
+        // Postman passes the first page's items and cursor to the second page's request through collection variables
+        let output_dir = generate(TestFramework::Postman, "postman", true);
+        let collection: serde_json::Value = serde_json::from_str(&fs::read_to_string(output_dir.join("postman_collection.json")).unwrap()).unwrap();
+        let requests: Vec<&serde_json::Value> = collection["item"].as_array().unwrap().iter().flat_map(|folder| folder["item"].as_array().unwrap()).collect();
+        let exec = |name: &str| requests.iter().find(|request| request["name"] == name).unwrap()["event"][0]["script"]["exec"].clone();
+        assert!(exec("GET listEvents first page").as_array().unwrap().iter()
+            .any(|line| line == "pm.collectionVariables.set(\"listEvents.nextCursor\", pm.response.json()[\"meta\"][\"next_cursor\"]);"));
+        assert!(exec("GET listEvents second page").as_array().unwrap().iter()
+            .any(|line| line == "    pm.expect(JSON.stringify(items)).to.not.eql(pm.collectionVariables.get(\"listEvents.firstPageItems\"));"));
+        let second = requests.iter().find(|request| request["name"] == "GET listEvents second page").unwrap();
+        assert_eq!(second["request"]["url"]["raw"], "{{baseUrl}}/events?limit=2&cursor={{listEvents.nextCursor}}");
+    }
+    
+    #[test]
+    fn test_max_response_time() {
+        let test_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("max_response_time");
+        if test_output_dir.exists() {
+            fs::remove_dir_all(&test_output_dir).unwrap();
+        }
+        
+        let spec = parse_swagger_file(get_test_data_path("sample_swagger.json")).unwrap();
+        let options = GeneratorOptions { max_response_time_ms: Some(500), ..GeneratorOptions::default() };
+        let generate = |framework, name: &str| {
+            let output_dir = test_output_dir.join(name);
+            create_generator_with_options(framework, &options).unwrap()
+                .generate_tests(&spec, &output_dir, "http://localhost:3000")
+                .unwrap();
+            output_dir
+        };
+        
+        let tests = fs::read_to_string(generate(TestFramework::Reqwest, "reqwest").join("api_tests.rs")).unwrap();
+        assert!(tests.contains("    let started = std::time::Instant::now();\n    let response = client.get(&url)"));
+        assert!(tests.contains("    let elapsed = started.elapsed();\n    assert!(elapsed.as_millis() <= 500, \"Response took {} ms, more than 500 ms\", elapsed.as_millis());"));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Pytest, "pytest").join("test_api.py")).unwrap();
+        assert!(tests.contains("    assert response.elapsed.total_seconds() * 1000 <= 500, "));
+        
+        let tests = fs::read_to_string(generate(TestFramework::Jest, "jest").join("users.test.js")).unwrap();
+        assert!(tests.contains("  const started = Date.now();\n  const response = await axios.get(url, { params, headers });\n  expect(Date.now() - started).toBeLessThanOrEqual(500);"));
+        
+        let output_dir = generate(TestFramework::Postman, "postman");
+        let collection = fs::read_to_string(output_dir.join("postman_collection.json")).unwrap();
+        assert!(collection.contains("\"    pm.expect(pm.response.responseTime).to.be.at.most(500);\""));
+        
+        // Without a limit, responses aren't timed
+        create_generator(TestFramework::Pytest).unwrap()
+            .generate_tests(&spec, &test_output_dir.join("unlimited"), "http://localhost:3000")
+            .unwrap();
+        assert!(!fs::read_to_string(test_output_dir.join("unlimited").join("test_api.py")).unwrap().contains("elapsed"));
+        
+        assert_eq!(parse_max_response_time("250"), Ok(250));
+        assert!(parse_max_response_time("0").is_err());
+        assert!(parse_max_response_time("fast").is_err());
+    }
+    
+    #[test]
+    fn test_parse_auth() {
+        assert_eq!(parse_auth("bearer:API_TOKEN"), Ok(AuthSource::Bearer("API_TOKEN".to_string())));
+        assert_eq!(parse_auth("bearer:$MY_TOKEN"), Ok(AuthSource::Bearer("MY_TOKEN".to_string())));
+        assert_eq!(parse_auth("apikey:API_KEY"), Ok(AuthSource::ApiKey("API_KEY".to_string())));
+        assert_eq!(
+            parse_auth("basic:API_USERNAME:$API_PASSWORD"),
+            Ok(AuthSource::Basic("API_USERNAME".to_string(), "API_PASSWORD".to_string()))
+        );
+        assert_eq!(
+            parse_auth("oauth2:CLIENT_ID:CLIENT_SECRET"),
+            Ok(AuthSource::ClientCredentials("CLIENT_ID".to_string(), "CLIENT_SECRET".to_string()))
+        );
+        assert!(parse_auth("basic:API_USERNAME").is_err());
+        assert!(parse_auth("bearer:API_TOKEN:OTHER").is_err());
+        assert!(parse_auth("bearer:").is_err());
+        assert!(parse_auth("bearer:1TOKEN").is_err());
+        assert!(parse_auth("digest:API_TOKEN").is_err());
+        assert!(parse_auth("API_TOKEN").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("45"), Ok(Duration::from_secs(45)));
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("5x").is_err());
+        assert!(parse_duration("m").is_err());
+    }
+
+    #[test]
+    fn test_parse_status_coverage() {
+        assert_eq!(parse_status_coverage("all"), Ok(StatusCoverage::All));
+        assert_eq!(parse_status_coverage("success-only"), Ok(StatusCoverage::SuccessOnly));
+        assert_eq!(parse_status_coverage("Success-Only"), Ok(StatusCoverage::SuccessOnly));
+        assert!(parse_status_coverage("some").is_err());
+    }
+
+    #[test]
+    fn test_generate_api_endpoints() {
+        let swagger_path = get_test_data_path("sample_swagger.json");
+        let spec = parse_swagger_file(&swagger_path).unwrap();
+        
+        let api_output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("test-output")
+            .join("generated_api");
+        
+        // Clean previous test output
+        if api_output_dir.exists() {
+            fs::remove_dir_all(&api_output_dir).unwrap();
+        }
+        
+        // Generate API
+        let result = generate_axum_api(&spec, &api_output_dir);
+        
+        assert!(result.is_ok());
+        
+        // Check that API files were created
+        assert!(api_output_dir.join("Cargo.toml").exists());
+        assert!(api_output_dir.join("src").join("main.rs").exists());
+        assert!(api_output_dir.join("src").join("models").exists());
+        assert!(api_output_dir.join("src").join("handlers").exists());
+        assert!(api_output_dir.join("src").join("routes").exists());
+        
+        // No callbacks are documented, so there is nothing to simulate
+        assert!(!api_output_dir.join("src").join("callbacks.rs").exists());
+        assert!(!fs::read_to_string(api_output_dir.join("src").join("main.rs")).unwrap().contains("mod callbacks;"));
     }
 }
\ No newline at end of file